@@ -1,4 +1,4 @@
--- Rollback: 015_oui_rtsp_ssot_rollback.sql
+-- Rollback (down): 015_oui_rtsp_ssot.sql
 -- Description: Rollback OUI/RTSP SSoT tables
 -- Date: 2026-01-07
 -- Warning: This will delete all camera brand, OUI, and RTSP template data
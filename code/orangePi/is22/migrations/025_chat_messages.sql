@@ -2,20 +2,19 @@
 -- Issue: Mobile view cannot load chat history (localStorage is per-device)
 
 CREATE TABLE IF NOT EXISTS chat_messages (
-    id INTEGER PRIMARY KEY AUTOINCREMENT,
-    message_id TEXT NOT NULL UNIQUE,  -- Client-generated UUID
-    role TEXT NOT NULL CHECK(role IN ('user', 'assistant', 'system')),
+    id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+    message_id VARCHAR(64) NOT NULL COMMENT 'Client-generated UUID',
+    role ENUM('user', 'assistant', 'system') NOT NULL,
     content TEXT NOT NULL,
-    timestamp TEXT NOT NULL,  -- ISO8601 format
-    handled INTEGER DEFAULT 0,  -- For system messages with actions
-    action_type TEXT,  -- 'preset_change' etc.
-    action_camera_id TEXT,
-    action_preset_id TEXT,
-    dismiss_at INTEGER,  -- Unix timestamp for auto-dismiss
-    is_hiding INTEGER DEFAULT 0,
-    created_at TEXT NOT NULL DEFAULT (datetime('now'))
-);
-
--- Index for efficient retrieval
-CREATE INDEX IF NOT EXISTS idx_chat_messages_timestamp ON chat_messages(timestamp DESC);
-CREATE INDEX IF NOT EXISTS idx_chat_messages_role ON chat_messages(role);
+    timestamp VARCHAR(40) NOT NULL COMMENT 'ISO8601 format',
+    handled INT DEFAULT 0 COMMENT 'For system messages with actions',
+    action_type VARCHAR(32) COMMENT 'preset_change etc.',
+    action_camera_id VARCHAR(64),
+    action_preset_id VARCHAR(64),
+    dismiss_at BIGINT COMMENT 'Unix timestamp for auto-dismiss',
+    is_hiding INT DEFAULT 0,
+    created_at DATETIME(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+    UNIQUE KEY uk_chat_messages_message_id (message_id),
+    INDEX idx_chat_messages_timestamp (timestamp),
+    INDEX idx_chat_messages_role (role)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
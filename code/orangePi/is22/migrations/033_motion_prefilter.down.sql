@@ -0,0 +1,8 @@
+-- Rollback (down): 033_motion_prefilter.sql
+
+ALTER TABLE polling_cycles
+DROP COLUMN no_change_count;
+
+ALTER TABLE cameras
+DROP COLUMN motion_sensitivity,
+DROP COLUMN motion_noise_floor;
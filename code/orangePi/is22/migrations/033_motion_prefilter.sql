@@ -0,0 +1,12 @@
+-- Migration 033: Motion pre-filter (IS22ローカル変化検出)
+-- IS21送信前にPrevFrameCacheのフレームと縮小グレースケール差分を比較し、
+-- 変化がなければ推論をスキップする。カメラごとの感度・ノイズフロアを保持。
+-- NULL = settings.polling.prefilter のデフォルト値を使用
+
+ALTER TABLE cameras
+ADD COLUMN motion_sensitivity FLOAT DEFAULT NULL COMMENT 'Motion pre-filter sensitivity (0.0-1.0), NULL = policy default',
+ADD COLUMN motion_noise_floor INT DEFAULT NULL COMMENT 'Per-pixel luma diff ignored as noise (0-255), NULL = policy default';
+
+-- 巡回サイクル統計: 変化なしでIS21推論をスキップした件数
+ALTER TABLE polling_cycles
+ADD COLUMN no_change_count INT NOT NULL DEFAULT 0 COMMENT '変化なし（推論スキップ）件数' AFTER timeout_count;
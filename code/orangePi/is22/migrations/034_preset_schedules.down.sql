@@ -0,0 +1,5 @@
+-- Rollback (down): 034_preset_schedules.sql
+
+DROP TABLE IF EXISTS preset_switch_logs;
+DROP TABLE IF EXISTS preset_holidays;
+DROP TABLE IF EXISTS preset_schedules;
@@ -0,0 +1,37 @@
+-- Migration 034: Scheduled preset switching
+-- カメラ/施設(fid)/全体単位の週間スケジュール（JST）と祝日オーバーライド
+
+CREATE TABLE IF NOT EXISTS preset_schedules (
+    schedule_id VARCHAR(64) NOT NULL PRIMARY KEY,
+    name VARCHAR(128) NOT NULL,
+    -- 'camera' | 'fid' | 'global'
+    scope VARCHAR(16) NOT NULL DEFAULT 'camera',
+    -- camera_id (scope=camera) / fid (scope=fid) / NULL (scope=global)
+    scope_id VARCHAR(64) NULL,
+    -- [{ "days": [0-7], "start": "HH:MM", "end": "HH:MM", "preset_id": "..." }]
+    -- days: 0=Mon ... 6=Sun, 7=Holiday
+    rules JSON NOT NULL,
+    priority INT NOT NULL DEFAULT 0,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at DATETIME(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+    updated_at DATETIME(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3) ON UPDATE CURRENT_TIMESTAMP(3),
+    INDEX idx_scope (scope, scope_id)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS preset_holidays (
+    holiday_date DATE NOT NULL PRIMARY KEY,
+    name VARCHAR(128) NOT NULL DEFAULT '',
+    created_at DATETIME(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+CREATE TABLE IF NOT EXISTS preset_switch_logs (
+    id BIGINT UNSIGNED NOT NULL AUTO_INCREMENT PRIMARY KEY,
+    camera_id VARCHAR(64) NOT NULL,
+    from_preset_id VARCHAR(64) NOT NULL,
+    to_preset_id VARCHAR(64) NOT NULL,
+    -- NULL = schedule ended, fell back to cameras.preset_id
+    schedule_id VARCHAR(64) NULL,
+    switched_at DATETIME(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+    INDEX idx_camera_time (camera_id, switched_at),
+    INDEX idx_to_preset_time (to_preset_id, switched_at)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
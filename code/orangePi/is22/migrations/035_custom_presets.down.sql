@@ -0,0 +1,5 @@
+-- Rollback (down): 035_custom_presets.sql
+-- Warning: カメラに割り当て済みのカスタムプリセットIDは cameras.preset_id に残る
+
+DROP TABLE IF EXISTS custom_preset_versions;
+DROP TABLE IF EXISTS custom_presets;
@@ -0,0 +1,26 @@
+-- Migration 035: User-defined presets
+-- 組み込みプリセットを継承するカスタムプリセット（DB永続化・revision管理）
+
+CREATE TABLE IF NOT EXISTS custom_presets (
+    preset_id VARCHAR(64) NOT NULL PRIMARY KEY,
+    base_preset_id VARCHAR(64) NOT NULL,
+    name VARCHAR(128) NOT NULL,
+    description TEXT NOT NULL,
+    -- 基底プリセットへの上書き（未指定キーは継承）
+    overrides JSON NOT NULL,
+    -- 更新ごとに+1。preset_version = '{base_version}+c{revision}'
+    revision INT UNSIGNED NOT NULL DEFAULT 1,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at DATETIME(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+    updated_at DATETIME(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3) ON UPDATE CURRENT_TIMESTAMP(3)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+-- revision履歴（削除後も detection_logs.preset_version から定義を参照可能）
+CREATE TABLE IF NOT EXISTS custom_preset_versions (
+    preset_id VARCHAR(64) NOT NULL,
+    revision INT UNSIGNED NOT NULL,
+    preset_version VARCHAR(64) NOT NULL,
+    preset_json JSON NOT NULL,
+    created_at DATETIME(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+    PRIMARY KEY (preset_id, revision)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
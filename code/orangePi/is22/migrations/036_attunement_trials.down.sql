@@ -0,0 +1,3 @@
+-- Rollback (down): 036_attunement_trials.sql
+
+DROP TABLE IF EXISTS attunement_trials;
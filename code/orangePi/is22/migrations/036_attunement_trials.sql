@@ -0,0 +1,27 @@
+-- Migration 036: Closed-loop auto attunement trials
+-- 閾値候補の試行（A/B評価）と採用/ロールバック履歴
+
+CREATE TABLE IF NOT EXISTS attunement_trials (
+    trial_id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+    camera_id VARCHAR(64) NOT NULL,
+    -- 'conf_override' | 'nms_threshold' | 'par_threshold'
+    parameter VARCHAR(32) NOT NULL,
+    -- 'reduce_false_positives' | 'reduce_unknown'
+    goal VARCHAR(32) NOT NULL,
+    -- 試行前の cameras.<parameter>（NULL = プリセット既定値）
+    baseline_value FLOAT NULL,
+    -- 試行前の実効値（プリセット既定値を含む）
+    effective_baseline FLOAT NOT NULL,
+    candidate_value FLOAT NOT NULL,
+    -- 'running' | 'promoted' | 'rolled_back' | 'aborted'
+    status VARCHAR(16) NOT NULL DEFAULT 'running',
+    baseline_metrics JSON NOT NULL,
+    trial_metrics JSON NULL,
+    decision_reason TEXT NULL,
+    started_at DATETIME(3) NOT NULL,
+    ends_at DATETIME(3) NOT NULL,
+    finished_at DATETIME(3) NULL,
+    INDEX idx_trial_camera (camera_id, started_at),
+    INDEX idx_trial_status (status),
+    FOREIGN KEY (camera_id) REFERENCES cameras(camera_id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
@@ -0,0 +1,3 @@
+-- Rollback (down): 037_summary_templates.sql
+
+DROP TABLE IF EXISTS summary_templates;
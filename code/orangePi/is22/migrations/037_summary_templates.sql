@@ -0,0 +1,19 @@
+-- Migration 037: Summary text templates
+-- Summary/GrandSummary テキストのテンプレート（fid・locale別）
+
+CREATE TABLE IF NOT EXISTS summary_templates (
+    template_id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+    -- NULL = 全施設共通
+    fid VARCHAR(32) NULL,
+    -- 'ja' | 'en'
+    locale VARCHAR(8) NOT NULL,
+    -- 'summary' | 'grand_summary'
+    kind VARCHAR(16) NOT NULL,
+    name VARCHAR(128) NOT NULL,
+    body TEXT NOT NULL,
+    -- 同一 (fid, locale, kind) で有効なのは1件のみ（アプリ側で保証）
+    enabled BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at DATETIME(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+    updated_at DATETIME(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3) ON UPDATE CURRENT_TIMESTAMP(3),
+    INDEX idx_template_scope (fid, locale, kind, enabled)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
@@ -0,0 +1,4 @@
+-- Rollback (down): 038_report_attachments.sql
+
+ALTER TABLE scheduled_reports
+DROP COLUMN report_format;
@@ -0,0 +1,8 @@
+-- Migration 038: Summary report attachments
+-- スケジュール実行時に印刷用レポート（HTML/PDF）を生成・保存する
+
+-- NULL = レポート生成なし, 'html' | 'pdf'
+ALTER TABLE scheduled_reports
+    ADD COLUMN report_format VARCHAR(8) NULL
+    COMMENT 'スケジュール実行時に生成するレポート形式 (html/pdf)'
+    AFTER scheduled_times;
@@ -0,0 +1,4 @@
+-- Rollback (down): 039_email_notifications.sql
+
+DROP TABLE IF EXISTS email_send_queue;
+DROP TABLE IF EXISTS email_subscriptions;
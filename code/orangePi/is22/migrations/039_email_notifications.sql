@@ -0,0 +1,58 @@
+-- Migration 039: Email (SMTP) notifications
+-- 受信者ごとの購読設定と送信キュー（paraclate_send_queue と同じリトライモデル）
+-- SMTPサーバー設定は settings テーブルの 'smtp' キーに保存
+
+-- ============================================================
+-- 1. email_subscriptions テーブル作成
+-- ============================================================
+
+CREATE TABLE IF NOT EXISTS email_subscriptions (
+    subscription_id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+
+    email VARCHAR(254) NOT NULL COMMENT '受信者メールアドレス',
+    display_name VARCHAR(128) NULL COMMENT '受信者表示名',
+
+    -- 購読対象（空配列 = 全施設）
+    fids JSON NOT NULL COMMENT '対象FIDリスト',
+    -- 'summary' | 'grand_summary' | 'daily'
+    report_types JSON NOT NULL COMMENT '受信するレポート種別',
+    -- NULL = 検出アラートを受信しない
+    alert_min_severity INT NULL COMMENT 'アラート受信する最小severity',
+
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at DATETIME(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+    updated_at DATETIME(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3) ON UPDATE CURRENT_TIMESTAMP(3),
+
+    UNIQUE KEY uk_email (email)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci
+COMMENT='メール通知の購読設定';
+
+-- ============================================================
+-- 2. email_send_queue テーブル作成
+-- ============================================================
+-- 受信者ごとに1行（本文は送信時に組み立てる）
+
+CREATE TABLE IF NOT EXISTS email_send_queue (
+    queue_id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+
+    tid VARCHAR(32) NOT NULL COMMENT 'テナントID',
+    fid VARCHAR(32) NOT NULL COMMENT '施設ID',
+
+    kind ENUM('report', 'daily', 'alert', 'test') NOT NULL COMMENT 'メール種別',
+    recipient VARCHAR(254) NOT NULL COMMENT '送信先',
+    payload JSON NOT NULL COMMENT '本文組み立て用データ',
+    reference_id BIGINT UNSIGNED NULL COMMENT '参照ID（summary_id / log_id）',
+
+    status ENUM('pending', 'sending', 'sent', 'failed', 'skipped') NOT NULL DEFAULT 'pending',
+    retry_count INT NOT NULL DEFAULT 0,
+    max_retries INT NOT NULL DEFAULT 5,
+    next_retry_at DATETIME(3) NULL COMMENT '次回リトライ日時（指数バックオフ）',
+    last_error TEXT NULL,
+
+    created_at DATETIME(3) NOT NULL DEFAULT CURRENT_TIMESTAMP(3),
+    sent_at DATETIME(3) NULL,
+
+    INDEX idx_status_retry (status, next_retry_at),
+    INDEX idx_created (created_at)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci
+COMMENT='メール送信キュー';
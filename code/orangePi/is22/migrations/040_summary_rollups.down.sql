@@ -0,0 +1,18 @@
+-- Rollback (down): 040_summary_rollups.sql
+-- Warning: 週次・月次ロールアップのサマリー・スケジュール・送信キューは削除される
+-- （ENUM を縮小する前に該当行を削除する）
+
+DELETE FROM paraclate_send_queue WHERE payload_type IN ('weekly_rollup', 'monthly_rollup');
+DELETE FROM scheduled_reports WHERE report_type IN ('weekly_rollup', 'monthly_rollup');
+DELETE FROM ai_summary_cache WHERE summary_type IN ('weekly', 'monthly');
+
+ALTER TABLE paraclate_send_queue
+    MODIFY COLUMN payload_type ENUM('summary', 'grand_summary', 'event', 'emergency') NOT NULL
+    COMMENT 'ペイロード種別';
+
+ALTER TABLE scheduled_reports
+    MODIFY COLUMN report_type ENUM('summary', 'grand_summary') NOT NULL
+    COMMENT 'summary=間隔ベース, grand_summary=時刻指定ベース';
+
+ALTER TABLE ai_summary_cache
+    MODIFY COLUMN summary_type ENUM('hourly', 'daily', 'emergency') NOT NULL;
@@ -0,0 +1,15 @@
+-- Migration 040: Weekly / monthly rollup reports
+-- ISMSレビュー用の週次・月次ロールアップ（GrandSummaryの上位集計）
+
+ALTER TABLE ai_summary_cache
+    MODIFY COLUMN summary_type ENUM('hourly', 'daily', 'emergency', 'weekly', 'monthly') NOT NULL;
+
+-- weekly_rollup: scheduled_times[0] = 実行時刻（JST, 月曜）
+-- monthly_rollup: scheduled_times[0] = 実行時刻（JST, 毎月1日）
+ALTER TABLE scheduled_reports
+    MODIFY COLUMN report_type ENUM('summary', 'grand_summary', 'weekly_rollup', 'monthly_rollup') NOT NULL
+    COMMENT 'summary=間隔ベース, grand_summary=時刻指定ベース, weekly_rollup/monthly_rollup=期間ロールアップ';
+
+ALTER TABLE paraclate_send_queue
+    MODIFY COLUMN payload_type ENUM('summary', 'grand_summary', 'event', 'emergency', 'weekly_rollup', 'monthly_rollup') NOT NULL
+    COMMENT 'ペイロード種別';
@@ -0,0 +1,3 @@
+-- Rollback (down): 041_camera_availability.sql
+
+DROP TABLE IF EXISTS camera_availability_intervals;
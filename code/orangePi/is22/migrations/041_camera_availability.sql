@@ -0,0 +1,21 @@
+-- Migration 041: Camera availability timeline
+-- CameraStatusTracker の遷移を区間として永続化（稼働率・MTTR/MTBF算出、起動時の状態復元）
+-- 1カメラにつき ended_at IS NULL の区間は常に最大1件（現在の状態）
+
+CREATE TABLE IF NOT EXISTS camera_availability_intervals (
+    interval_id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+
+    camera_id VARCHAR(64) NOT NULL COMMENT 'カメラID',
+    state ENUM('online', 'offline') NOT NULL COMMENT '接続状態',
+    -- offline区間のみ: 'snapshot_timeout' | 'rtsp_busy' | 'auth_failure' | 'ip_relocated' | 'unreachable'
+    cause VARCHAR(32) NULL COMMENT 'オフライン原因',
+    detail TEXT NULL COMMENT '原因詳細（エラーメッセージ等）',
+
+    started_at DATETIME(3) NOT NULL COMMENT '区間開始',
+    ended_at DATETIME(3) NULL COMMENT '区間終了（NULL = 継続中）',
+
+    INDEX idx_camera_started (camera_id, started_at),
+    INDEX idx_open (ended_at, camera_id),
+    INDEX idx_range (started_at, ended_at)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci
+COMMENT='カメラ稼働状態の区間履歴';
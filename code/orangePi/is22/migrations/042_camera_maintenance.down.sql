@@ -0,0 +1,4 @@
+-- Rollback (down): 042_camera_maintenance.sql
+
+DROP TABLE IF EXISTS camera_clock_events;
+DROP TABLE IF EXISTS camera_firmware_history;
@@ -0,0 +1,36 @@
+-- Migration 042: Camera maintenance (firmware history / clock checks)
+-- ONVIF GetDeviceInformation で検出したファームウェア変更履歴と、
+-- GetSystemDateAndTime による時刻ずれの検出・補正履歴
+
+CREATE TABLE IF NOT EXISTS camera_firmware_history (
+    history_id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+
+    camera_id VARCHAR(64) NOT NULL COMMENT 'カメラID',
+    previous_version VARCHAR(128) NULL COMMENT '変更前ファームウェア（NULL = 初回取得）',
+    firmware_version VARCHAR(128) NOT NULL COMMENT '検出したファームウェア',
+    manufacturer VARCHAR(128) NULL COMMENT 'メーカー（検出時点）',
+    model VARCHAR(128) NULL COMMENT '機種（検出時点）',
+
+    detected_at DATETIME(3) NOT NULL COMMENT '検出日時',
+
+    INDEX idx_camera_detected (camera_id, detected_at),
+    INDEX idx_detected (detected_at)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci
+COMMENT='カメラファームウェア変更履歴';
+
+-- 閾値超過（skewed）または補正を行ったチェックのみ記録
+CREATE TABLE IF NOT EXISTS camera_clock_events (
+    event_id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+
+    camera_id VARCHAR(64) NOT NULL COMMENT 'カメラID',
+    skew_ms BIGINT NOT NULL COMMENT 'カメラ時刻 - サーバー時刻（ミリ秒）',
+    date_time_type VARCHAR(16) NULL COMMENT 'Manual | NTP',
+    -- NULL = 未補正, 'manual' = SetSystemDateAndTime, 'ntp' = SetNTP + NTPモード
+    correction VARCHAR(16) NULL COMMENT '補正方法',
+    correction_error TEXT NULL COMMENT '補正失敗時のエラー',
+
+    checked_at DATETIME(3) NOT NULL COMMENT 'チェック日時',
+
+    INDEX idx_camera_checked (camera_id, checked_at)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci
+COMMENT='カメラ時刻ずれ検出・補正履歴';
@@ -0,0 +1,3 @@
+-- Rollback (down): 043_modal_leases.sql
+
+DROP TABLE IF EXISTS modal_leases;
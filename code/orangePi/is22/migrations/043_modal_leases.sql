@@ -0,0 +1,22 @@
+-- Migration 043: Persistent modal leases
+-- AdmissionController の発行済みリース。再起動時に復元して視聴中ユーザーの切断を防ぐ
+
+CREATE TABLE IF NOT EXISTS modal_leases (
+    lease_id CHAR(36) NOT NULL PRIMARY KEY COMMENT 'リースID（UUID）',
+
+    user_id VARCHAR(128) NOT NULL COMMENT 'ユーザーID',
+    camera_id VARCHAR(64) NOT NULL COMMENT 'カメラID',
+    quality ENUM('main', 'sub') NOT NULL COMMENT '許可された画質',
+    requested_quality ENUM('main', 'sub') NOT NULL COMMENT '要求画質',
+    role VARCHAR(16) NOT NULL DEFAULT 'viewer' COMMENT 'viewer | operator',
+    cost INT UNSIGNED NOT NULL COMMENT 'ストリームユニット',
+
+    created_at DATETIME(3) NOT NULL COMMENT '発行日時',
+    expires_at DATETIME(3) NOT NULL COMMENT '有効期限',
+    last_heartbeat DATETIME(3) NOT NULL COMMENT '最終ハートビート',
+
+    UNIQUE KEY uk_user (user_id),
+    INDEX idx_camera (camera_id),
+    INDEX idx_expires (expires_at)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci
+COMMENT='モーダル視聴リース';
@@ -0,0 +1,3 @@
+-- Rollback (down): 044_live_layouts.sql
+
+DROP TABLE IF EXISTS live_layouts;
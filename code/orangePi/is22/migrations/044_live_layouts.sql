@@ -0,0 +1,22 @@
+-- Migration 044: Live wall layouts
+-- ユーザーごとの名前付きグリッドレイアウト。pages が複数かつ tour_interval_sec > 0 ならツアー表示
+
+CREATE TABLE IF NOT EXISTS live_layouts (
+    layout_id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+
+    user_id VARCHAR(128) NOT NULL COMMENT 'ユーザーID',
+    name VARCHAR(128) NOT NULL COMMENT 'レイアウト名',
+    grid_cols TINYINT UNSIGNED NOT NULL COMMENT '列数',
+    grid_rows TINYINT UNSIGNED NOT NULL COMMENT '行数',
+    quality ENUM('main', 'sub') NOT NULL DEFAULT 'sub' COMMENT 'タイルの希望画質',
+    tour_interval_sec INT UNSIGNED NOT NULL DEFAULT 0 COMMENT 'ページ切替間隔（0 = ツアーなし）',
+    -- [{"title": "1F", "cells": ["cam-xxx", null, ...]}, ...]
+    pages JSON NOT NULL COMMENT 'ページごとのセル配置',
+
+    created_at DATETIME(3) NOT NULL,
+    updated_at DATETIME(3) NOT NULL,
+
+    UNIQUE KEY uk_user_name (user_id, name),
+    INDEX idx_user (user_id)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci
+COMMENT='ライブウォールレイアウト';
@@ -0,0 +1,3 @@
+-- Rollback (down): 045_audit_log.sql
+
+DROP TABLE IF EXISTS audit_log;
@@ -0,0 +1,32 @@
+-- Migration 045: Audit trail
+-- 変更系 API（POST/PUT/PATCH/DELETE）の操作記録。entry_hash は prev_hash と記録内容の SHA-256 で、
+-- 行の改ざん・削除・挿入は GET /api/audit/verify で検出できる
+
+CREATE TABLE IF NOT EXISTS audit_log (
+    audit_id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+
+    occurred_at DATETIME(3) NOT NULL COMMENT '記録時刻（UTC）',
+    actor VARCHAR(128) NOT NULL COMMENT '操作者（X-Actor / X-User-Id、なければ anonymous）',
+    source_ip VARCHAR(64) NULL COMMENT '接続元IP',
+    method VARCHAR(8) NOT NULL COMMENT 'HTTP メソッド',
+    path VARCHAR(512) NOT NULL COMMENT 'リクエストパス',
+    action VARCHAR(255) NOT NULL COMMENT 'メソッド + ルートパターン',
+    target_type VARCHAR(64) NULL COMMENT '対象種別（camera, subnet, credential ...）',
+    target_id VARCHAR(255) NULL COMMENT '対象ID',
+    status_code SMALLINT UNSIGNED NOT NULL COMMENT 'レスポンスステータス',
+    -- 秘匿情報（password, token 等・URL 内の認証情報）はマスク済み。
+    -- ハッシュ対象の文字列をそのまま保持するため JSON 型ではなくテキストで保存する
+    request_json MEDIUMTEXT NULL COMMENT 'リクエストボディ',
+    before_json MEDIUMTEXT NULL COMMENT '変更前の対象',
+    after_json MEDIUMTEXT NULL COMMENT '変更後の対象',
+    diff_json MEDIUMTEXT NULL COMMENT '変更差分 {"field": {"before": .., "after": ..}}',
+
+    prev_hash CHAR(64) NOT NULL COMMENT '直前エントリの entry_hash（先頭は 0 × 64）',
+    entry_hash CHAR(64) NOT NULL COMMENT 'SHA-256(prev_hash + 正規化した記録内容)',
+
+    UNIQUE KEY uk_entry_hash (entry_hash),
+    INDEX idx_occurred (occurred_at),
+    INDEX idx_actor (actor, occurred_at),
+    INDEX idx_target (target_type, target_id, occurred_at)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci
+COMMENT='操作監査ログ（ハッシュチェーン）';
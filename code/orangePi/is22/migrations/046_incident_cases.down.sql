@@ -0,0 +1,6 @@
+-- Rollback (down): 046_incident_cases.sql
+
+DROP TABLE IF EXISTS incident_exports;
+DROP TABLE IF EXISTS incident_custody_log;
+DROP TABLE IF EXISTS incident_case_events;
+DROP TABLE IF EXISTS incident_cases;
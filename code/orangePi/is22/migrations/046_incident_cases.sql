@@ -0,0 +1,85 @@
+-- Migration 046: Incident cases / evidence export
+-- 事案（ケース）単位で detection_logs のイベントを保全し、署名付きパッケージとしてエクスポートする。
+-- 保全時に検出画像を evidence_dir にコピーして SHA-256 を記録するため、元画像の退避・削除の影響を受けない
+
+CREATE TABLE IF NOT EXISTS incident_cases (
+    case_id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+
+    title VARCHAR(255) NOT NULL COMMENT '件名',
+    description TEXT NULL COMMENT '概要',
+    status ENUM('open', 'closed') NOT NULL DEFAULT 'open' COMMENT 'closed 後はイベント追加・削除不可',
+    created_by VARCHAR(128) NOT NULL COMMENT '作成者',
+
+    created_at DATETIME(3) NOT NULL,
+    updated_at DATETIME(3) NOT NULL,
+    closed_at DATETIME(3) NULL,
+
+    INDEX idx_status (status, created_at)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci
+COMMENT='インシデントケース';
+
+CREATE TABLE IF NOT EXISTS incident_case_events (
+    case_id BIGINT UNSIGNED NOT NULL,
+    log_id BIGINT UNSIGNED NOT NULL COMMENT 'detection_logs.log_id',
+
+    camera_id VARCHAR(64) NOT NULL,
+    captured_at DATETIME(3) NOT NULL,
+    primary_event VARCHAR(64) NOT NULL,
+    severity INT NOT NULL,
+    -- 保全時点の detection_logs 行（is21_log を含む）とカメラ設定（秘匿情報マスク済み）
+    event_json MEDIUMTEXT NOT NULL COMMENT '保全時点のイベント',
+    camera_json MEDIUMTEXT NULL COMMENT '保全時点のカメラメタデータ',
+    image_file VARCHAR(255) NULL COMMENT '保全した画像のファイル名（evidence_dir/cases/<case_id>/events/<log_id>/）',
+    image_sha256 CHAR(64) NULL COMMENT '保全時の画像 SHA-256',
+    image_size BIGINT UNSIGNED NULL,
+
+    added_by VARCHAR(128) NOT NULL,
+    added_at DATETIME(3) NOT NULL,
+
+    PRIMARY KEY (case_id, log_id),
+    INDEX idx_log (log_id),
+    CONSTRAINT fk_incident_events_case FOREIGN KEY (case_id)
+        REFERENCES incident_cases(case_id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci
+COMMENT='ケースに保全したイベント';
+
+CREATE TABLE IF NOT EXISTS incident_custody_log (
+    custody_id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+    case_id BIGINT UNSIGNED NOT NULL,
+
+    occurred_at DATETIME(3) NOT NULL,
+    action VARCHAR(32) NOT NULL COMMENT 'created / event_added / event_removed / exported / verified / closed / reopened',
+    actor VARCHAR(128) NOT NULL,
+    source_ip VARCHAR(64) NULL,
+    details MEDIUMTEXT NULL COMMENT 'JSON',
+
+    -- ケースごとのハッシュチェーン（先頭の prev_hash は 0 × 64）
+    prev_hash CHAR(64) NOT NULL,
+    entry_hash CHAR(64) NOT NULL,
+
+    UNIQUE KEY uk_entry_hash (entry_hash),
+    INDEX idx_case (case_id, custody_id),
+    CONSTRAINT fk_incident_custody_case FOREIGN KEY (case_id)
+        REFERENCES incident_cases(case_id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci
+COMMENT='証拠の管理履歴（chain of custody）';
+
+CREATE TABLE IF NOT EXISTS incident_exports (
+    export_id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+    case_id BIGINT UNSIGNED NOT NULL,
+
+    package_id VARCHAR(128) NOT NULL COMMENT 'パッケージディレクトリ名',
+    package_path VARCHAR(512) NOT NULL,
+    manifest_sha256 CHAR(64) NOT NULL COMMENT 'manifest.json の SHA-256',
+    lacis_id VARCHAR(20) NOT NULL COMMENT '署名した is22 の LacisID',
+    key_fingerprint CHAR(64) NOT NULL COMMENT '署名鍵（公開鍵 DER）の SHA-256',
+    file_count INT UNSIGNED NOT NULL,
+    exported_by VARCHAR(128) NOT NULL,
+    exported_at DATETIME(3) NOT NULL,
+
+    UNIQUE KEY uk_package (package_id),
+    INDEX idx_case (case_id, exported_at),
+    CONSTRAINT fk_incident_exports_case FOREIGN KEY (case_id)
+        REFERENCES incident_cases(case_id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci
+COMMENT='証拠パッケージのエクスポート履歴';
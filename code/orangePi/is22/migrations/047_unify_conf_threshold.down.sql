@@ -0,0 +1,9 @@
+-- Rollback (down): 047_unify_conf_threshold.sql
+
+ALTER TABLE camera_thresholds
+    ADD COLUMN conf_threshold DECIMAL(5,4) NOT NULL DEFAULT 0.5 COMMENT '信頼度閾値（0.2-0.8）' AFTER camera_id;
+
+UPDATE camera_thresholds ct
+JOIN cameras c ON c.camera_id = ct.camera_id
+SET ct.conf_threshold = c.conf_override
+WHERE c.conf_override IS NOT NULL;
@@ -0,0 +1,12 @@
+-- Migration 047: 信頼度閾値の保存先を cameras.conf_override に一本化
+--
+-- camera_thresholds.conf_threshold は推論に反映されていなかった（ポーリングは
+-- cameras.conf_override を参照する）。手動/自動で設定された値を conf_override へ
+-- 移し、camera_thresholds には自動調整フラグと調整範囲のみを残す。
+
+UPDATE cameras c
+JOIN camera_thresholds ct ON ct.camera_id = c.camera_id
+SET c.conf_override = ct.conf_threshold
+WHERE c.conf_override IS NULL;
+
+ALTER TABLE camera_thresholds DROP COLUMN conf_threshold;
@@ -0,0 +1,454 @@
+-- IS22 SQLite schema (repository layer)
+-- Version: 001
+--
+-- MySQL migrations 001-044 のうち、SQLite 対応リポジトリが使うテーブルのみを
+-- 現行スキーマで作成する（config_store, detection_log, summary, paraclate,
+-- camera_sync, access_absorber, camera_brand）。
+--
+-- 型の対応:
+--   BOOLEAN -> INTEGER (0/1), JSON -> TEXT, ENUM -> TEXT
+--   DATETIME(3) -> TEXT (RFC3339, UTC: 2025-01-01T00:00:00.000+00:00)
+-- 日時の DEFAULT は sqlx の DateTime<Utc> エンコードと同じ形式にし、文字列比較で順序を保つ。
+
+-- ========================================
+-- Cameras (SSoT)
+-- ========================================
+CREATE TABLE IF NOT EXISTS cameras (
+    camera_id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    location TEXT NOT NULL,
+    floor TEXT,
+    rid TEXT,
+    rtsp_main TEXT,
+    rtsp_sub TEXT,
+    rtsp_username TEXT,
+    rtsp_password TEXT,
+    snapshot_url TEXT,
+    family TEXT NOT NULL DEFAULT 'unknown',
+    access_family TEXT,
+    access_limit_override TEXT,
+    last_disconnect_at TEXT,
+    manufacturer TEXT,
+    model TEXT,
+    ip_address TEXT,
+    mac_address TEXT,
+    lacis_id TEXT,
+    cic TEXT,
+    enabled INTEGER NOT NULL DEFAULT 1,
+    polling_enabled INTEGER NOT NULL DEFAULT 1,
+    polling_interval_sec INTEGER NOT NULL DEFAULT 60,
+    suggest_policy_weight INTEGER NOT NULL DEFAULT 5,
+    camera_context TEXT,
+    inference_config TEXT,
+    rotation INTEGER NOT NULL DEFAULT 0,
+    fit_mode TEXT NOT NULL DEFAULT 'trim',
+    fid TEXT,
+    tid TEXT,
+    sort_order INTEGER NOT NULL DEFAULT 0,
+    preset_id TEXT NOT NULL DEFAULT 'balanced',
+    preset_version TEXT,
+    ai_enabled INTEGER NOT NULL DEFAULT 0,
+    ai_interval_sec INTEGER NOT NULL DEFAULT 15,
+    serial_number TEXT,
+    hardware_id TEXT,
+    firmware_version TEXT,
+    onvif_endpoint TEXT,
+    rtsp_port INTEGER DEFAULT 554,
+    http_port INTEGER DEFAULT 80,
+    onvif_port INTEGER DEFAULT 80,
+    resolution_main TEXT,
+    codec_main TEXT,
+    fps_main INTEGER,
+    bitrate_main INTEGER,
+    resolution_sub TEXT,
+    codec_sub TEXT,
+    fps_sub INTEGER,
+    bitrate_sub INTEGER,
+    ptz_supported INTEGER NOT NULL DEFAULT 0,
+    ptz_continuous INTEGER NOT NULL DEFAULT 0,
+    ptz_absolute INTEGER NOT NULL DEFAULT 0,
+    ptz_relative INTEGER NOT NULL DEFAULT 0,
+    ptz_pan_range TEXT,
+    ptz_tilt_range TEXT,
+    ptz_zoom_range TEXT,
+    ptz_presets TEXT,
+    ptz_home_supported INTEGER NOT NULL DEFAULT 0,
+    ptz_disabled INTEGER NOT NULL DEFAULT 0,
+    audio_input_supported INTEGER NOT NULL DEFAULT 0,
+    audio_output_supported INTEGER NOT NULL DEFAULT 0,
+    audio_codec TEXT,
+    onvif_profiles TEXT,
+    onvif_scopes TEXT,
+    onvif_network_interfaces TEXT,
+    onvif_capabilities TEXT,
+    discovery_method TEXT,
+    last_verified_at TEXT,
+    last_rescan_at TEXT,
+    deleted_at TEXT,
+    sdm_device_id TEXT,
+    sdm_structure TEXT,
+    sdm_traits TEXT,
+    conf_override REAL,
+    nms_threshold REAL,
+    par_threshold REAL,
+    motion_sensitivity REAL,
+    motion_noise_floor INTEGER,
+    mobes_synced_at TEXT,
+    mobes_sync_version INTEGER DEFAULT 0,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now')),
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_cameras_location ON cameras (location);
+CREATE INDEX IF NOT EXISTS idx_cameras_enabled ON cameras (enabled, polling_enabled);
+CREATE INDEX IF NOT EXISTS idx_cameras_mac ON cameras (mac_address);
+
+-- ========================================
+-- Schema Versions / Settings
+-- ========================================
+CREATE TABLE IF NOT EXISTS schema_versions (
+    version_id TEXT PRIMARY KEY,
+    schema_json TEXT NOT NULL,
+    is_active INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now'))
+);
+
+CREATE TABLE IF NOT EXISTS settings (
+    setting_key TEXT PRIMARY KEY,
+    setting_json TEXT NOT NULL,
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now'))
+);
+
+INSERT INTO schema_versions (version_id, schema_json, is_active) VALUES
+('2025-12-29.1', '{"version":"2025-12-29.1","supported_events":["human","animal","vehicle","unknown","camera_issue"],"severity_levels":[0,1,2,3]}', 1);
+
+-- ========================================
+-- Scan Subnets (paraclate の tid/fid 検証用)
+-- ========================================
+CREATE TABLE IF NOT EXISTS scan_subnets (
+    subnet_id TEXT PRIMARY KEY,
+    cidr TEXT NOT NULL UNIQUE,
+    fid TEXT,
+    tid TEXT,
+    facility_name TEXT,
+    credentials TEXT,
+    description TEXT,
+    enabled INTEGER NOT NULL DEFAULT 1,
+    last_scanned_at TEXT,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now'))
+);
+
+-- ========================================
+-- Detection Logs
+-- ========================================
+CREATE TABLE IF NOT EXISTS detection_logs (
+    log_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    tid TEXT NOT NULL,
+    fid TEXT NOT NULL,
+    camera_id TEXT NOT NULL REFERENCES cameras(camera_id) ON DELETE CASCADE,
+    lacis_id TEXT,
+    camera_lacis_id TEXT,
+    captured_at TEXT NOT NULL,
+    analyzed_at TEXT NOT NULL,
+    primary_event TEXT NOT NULL,
+    severity INTEGER NOT NULL DEFAULT 1,
+    confidence REAL NOT NULL,
+    count_hint INTEGER NOT NULL DEFAULT 0,
+    unknown_flag INTEGER NOT NULL DEFAULT 0,
+    tags TEXT NOT NULL,
+    person_details TEXT,
+    vehicle_details TEXT,
+    bboxes TEXT,
+    suspicious TEXT,
+    frame_diff TEXT,
+    loitering_detected INTEGER NOT NULL DEFAULT 0,
+    preset_id TEXT NOT NULL DEFAULT 'balanced',
+    preset_version TEXT,
+    output_schema TEXT,
+    context_applied INTEGER NOT NULL DEFAULT 0,
+    camera_context TEXT,
+    is21_log TEXT NOT NULL,
+    camera_response TEXT,
+    image_path_local TEXT NOT NULL,
+    image_path_cloud TEXT,
+    processing_ms INTEGER,
+    polling_cycle_id TEXT,
+    schema_version TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now')),
+    synced_to_bq INTEGER NOT NULL DEFAULT 0,
+    synced_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_logs_tid_fid ON detection_logs (tid, fid);
+CREATE INDEX IF NOT EXISTS idx_logs_camera ON detection_logs (camera_id, captured_at);
+CREATE INDEX IF NOT EXISTS idx_logs_captured ON detection_logs (captured_at);
+CREATE INDEX IF NOT EXISTS idx_logs_severity ON detection_logs (severity, captured_at);
+CREATE INDEX IF NOT EXISTS idx_logs_camera_lacis_id ON detection_logs (camera_lacis_id);
+
+-- ========================================
+-- Summary Service
+-- ========================================
+CREATE TABLE IF NOT EXISTS ai_summary_cache (
+    summary_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    tid TEXT NOT NULL,
+    fid TEXT NOT NULL,
+    summary_type TEXT NOT NULL,
+    period_start TEXT NOT NULL,
+    period_end TEXT NOT NULL,
+    summary_text TEXT NOT NULL,
+    summary_json TEXT,
+    detection_count INTEGER NOT NULL DEFAULT 0,
+    severity_max INTEGER NOT NULL DEFAULT 0,
+    camera_ids TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now')),
+    expires_at TEXT NOT NULL,
+    UNIQUE (tid, fid, summary_type, period_start)
+);
+
+CREATE INDEX IF NOT EXISTS idx_summary_expires ON ai_summary_cache (expires_at);
+CREATE INDEX IF NOT EXISTS idx_summary_period ON ai_summary_cache (period_start, period_end);
+
+CREATE TABLE IF NOT EXISTS scheduled_reports (
+    schedule_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    tid TEXT NOT NULL,
+    fid TEXT NOT NULL,
+    report_type TEXT NOT NULL,
+    interval_minutes INTEGER,
+    scheduled_times TEXT,
+    report_format TEXT,
+    last_run_at TEXT,
+    next_run_at TEXT,
+    enabled INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now')),
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now')),
+    UNIQUE (tid, fid, report_type)
+);
+
+CREATE TABLE IF NOT EXISTS summary_templates (
+    template_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    fid TEXT,
+    locale TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    name TEXT NOT NULL,
+    body TEXT NOT NULL,
+    enabled INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now')),
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_template_scope ON summary_templates (fid, locale, kind, enabled);
+
+-- ========================================
+-- Paraclate Client
+-- ========================================
+CREATE TABLE IF NOT EXISTS paraclate_config (
+    config_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    tid TEXT NOT NULL,
+    fid TEXT NOT NULL,
+    endpoint TEXT NOT NULL,
+    report_interval_minutes INTEGER NOT NULL DEFAULT 60,
+    grand_summary_times TEXT NOT NULL DEFAULT '["09:00", "17:00", "21:00"]',
+    retention_days INTEGER NOT NULL DEFAULT 60,
+    attunement TEXT NOT NULL DEFAULT '{}',
+    sync_source_timestamp TEXT,
+    last_sync_at TEXT,
+    connection_status TEXT NOT NULL DEFAULT 'disconnected',
+    last_error TEXT,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now')),
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now')),
+    UNIQUE (tid, fid)
+);
+
+CREATE TABLE IF NOT EXISTS paraclate_send_queue (
+    queue_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    tid TEXT NOT NULL,
+    fid TEXT NOT NULL,
+    payload_type TEXT NOT NULL,
+    payload TEXT NOT NULL,
+    reference_id INTEGER,
+    status TEXT NOT NULL DEFAULT 'pending',
+    retry_count INTEGER NOT NULL DEFAULT 0,
+    max_retries INTEGER NOT NULL DEFAULT 5,
+    next_retry_at TEXT,
+    last_error TEXT,
+    http_status_code INTEGER,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now')),
+    sent_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_send_queue_status ON paraclate_send_queue (status);
+CREATE INDEX IF NOT EXISTS idx_send_queue_tid_fid ON paraclate_send_queue (tid, fid);
+CREATE INDEX IF NOT EXISTS idx_send_queue_next_retry ON paraclate_send_queue (next_retry_at, status);
+
+CREATE TABLE IF NOT EXISTS paraclate_connection_log (
+    log_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    tid TEXT NOT NULL,
+    fid TEXT NOT NULL,
+    event_type TEXT NOT NULL,
+    event_detail TEXT,
+    error_code TEXT,
+    http_status_code INTEGER,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_connection_log_tid_fid ON paraclate_connection_log (tid, fid);
+CREATE INDEX IF NOT EXISTS idx_connection_log_created ON paraclate_connection_log (created_at);
+
+-- ========================================
+-- Camera Sync (mobes2.0)
+-- ========================================
+CREATE TABLE IF NOT EXISTS camera_sync_state (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    camera_id TEXT NOT NULL UNIQUE,
+    lacis_id TEXT NOT NULL UNIQUE,
+    last_sync_to_mobes TEXT,
+    last_sync_from_mobes TEXT,
+    mobes_sync_status TEXT DEFAULT 'pending',
+    sync_error_message TEXT,
+    retry_count INTEGER DEFAULT 0,
+    created_at TEXT DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now')),
+    updated_at TEXT DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now'))
+);
+
+CREATE TABLE IF NOT EXISTS camera_paraclate_settings (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    camera_id TEXT NOT NULL UNIQUE,
+    lacis_id TEXT NOT NULL UNIQUE,
+    sensitivity REAL DEFAULT 0.60,
+    detection_zone TEXT DEFAULT 'full',
+    alert_threshold INTEGER DEFAULT 3,
+    custom_preset TEXT,
+    settings_json TEXT,
+    synced_at TEXT NOT NULL,
+    created_at TEXT DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now')),
+    updated_at TEXT DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now'))
+);
+
+CREATE TABLE IF NOT EXISTS camera_sync_logs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    camera_id TEXT NOT NULL,
+    lacis_id TEXT,
+    sync_direction TEXT NOT NULL,
+    sync_type TEXT NOT NULL,
+    status TEXT NOT NULL,
+    changed_fields TEXT,
+    error_message TEXT,
+    request_payload TEXT,
+    response_payload TEXT,
+    created_at TEXT DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_sync_logs_camera_id ON camera_sync_logs (camera_id);
+
+-- ========================================
+-- Access Absorber
+-- ========================================
+CREATE TABLE IF NOT EXISTS camera_access_limits (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    family TEXT NOT NULL UNIQUE,
+    display_name TEXT NOT NULL,
+    max_concurrent_streams INTEGER NOT NULL DEFAULT 2,
+    min_reconnect_interval_ms INTEGER NOT NULL DEFAULT 0,
+    require_exclusive_lock INTEGER NOT NULL DEFAULT 0,
+    connection_timeout_ms INTEGER NOT NULL DEFAULT 10000,
+    model_pattern TEXT,
+    notes TEXT,
+    is_builtin INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now')),
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now'))
+);
+
+INSERT INTO camera_access_limits
+(family, display_name, max_concurrent_streams, min_reconnect_interval_ms, require_exclusive_lock, model_pattern, notes, is_builtin)
+VALUES
+('tapo_ptz', 'TP-Link Tapo (PTZ)', 1, 0, 1, 'C2[0-9]{2}|C5[0-9]{2}', 'C200/C210/C220/C500等PTZ系。放熱問題あり。並列接続不可', 1),
+('tapo_fixed', 'TP-Link Tapo (Fixed)', 2, 0, 0, 'C1[0-9]{2}', 'C100/C110/C120等固定系。2並列安定', 1),
+('vigi', 'TP-Link VIGI', 3, 0, 0, NULL, 'VIGI C3xx/C4xx等。3並列まで安定', 1),
+('nvt', 'NVT / JOOAN', 2, 2000, 0, NULL, '2秒間隔必須。急速再接続5%成功率。Main+Sub同時可', 1),
+('hikvision', 'Hikvision', 4, 0, 0, NULL, '推定値。実機テスト推奨', 1),
+('dahua', 'Dahua', 4, 0, 0, NULL, '推定値。実機テスト推奨', 1),
+('axis', 'Axis Communications', 8, 0, 0, NULL, '推定値。エンタープライズ品質', 1),
+('unknown', '不明', 1, 1000, 1, NULL, '安全側デフォルト。1接続、1秒間隔', 1);
+
+CREATE TABLE IF NOT EXISTS camera_stream_sessions (
+    session_id TEXT PRIMARY KEY,
+    camera_id TEXT NOT NULL,
+    stream_type TEXT NOT NULL DEFAULT 'main',
+    purpose TEXT NOT NULL,
+    client_id TEXT NOT NULL,
+    started_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now')),
+    expires_at TEXT,
+    last_heartbeat_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now')),
+    status TEXT NOT NULL DEFAULT 'active'
+);
+
+CREATE INDEX IF NOT EXISTS idx_stream_sessions_camera_status ON camera_stream_sessions (camera_id, status);
+
+CREATE TABLE IF NOT EXISTS camera_connection_events (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    camera_id TEXT NOT NULL,
+    event_type TEXT NOT NULL,
+    purpose TEXT,
+    client_id TEXT,
+    blocked_reason TEXT,
+    wait_time_ms INTEGER,
+    preempted_by TEXT,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_connection_events_camera ON camera_connection_events (camera_id, created_at);
+
+-- ========================================
+-- Camera Brands / OUI / RTSP templates
+-- ========================================
+CREATE TABLE IF NOT EXISTS camera_brands (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE,
+    display_name TEXT NOT NULL,
+    category TEXT DEFAULT 'unknown',
+    product_code TEXT DEFAULT '0000',
+    is_builtin INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now')),
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now'))
+);
+
+CREATE TABLE IF NOT EXISTS oui_entries (
+    oui_prefix TEXT NOT NULL PRIMARY KEY,
+    brand_id INTEGER NOT NULL REFERENCES camera_brands(id) ON DELETE CASCADE,
+    description TEXT,
+    score_bonus INTEGER NOT NULL DEFAULT 20,
+    status TEXT NOT NULL DEFAULT 'confirmed',
+    verification_source TEXT,
+    is_builtin INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now')),
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_oui_brand ON oui_entries (brand_id);
+
+CREATE TABLE IF NOT EXISTS rtsp_templates (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    brand_id INTEGER NOT NULL REFERENCES camera_brands(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    main_path TEXT NOT NULL,
+    sub_path TEXT,
+    default_port INTEGER NOT NULL DEFAULT 554,
+    is_default INTEGER NOT NULL DEFAULT 0,
+    priority INTEGER NOT NULL DEFAULT 100,
+    notes TEXT,
+    is_builtin INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now')),
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now')),
+    UNIQUE (brand_id, name)
+);
+
+CREATE TABLE IF NOT EXISTS generic_rtsp_paths (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    main_path TEXT NOT NULL,
+    sub_path TEXT,
+    description TEXT,
+    priority INTEGER NOT NULL DEFAULT 100,
+    is_enabled INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now')),
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now'))
+);
@@ -1,10 +1,11 @@
 //! Access Absorber Repository - Database operations
 
 use super::types::*;
+use crate::db::{Db, IntervalUnit};
 use crate::error::{Error, Result};
+use crate::with_db;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::MySqlPool;
 use tracing::{debug, info, warn};
 use uuid::Uuid;
 
@@ -36,18 +37,36 @@ struct StreamSessionRow {
     pub status: String,
 }
 
+impl From<StreamSessionRow> for ActiveStream {
+    fn from(r: StreamSessionRow) -> Self {
+        ActiveStream {
+            session_id: r.session_id,
+            camera_id: r.camera_id,
+            stream_type: match r.stream_type.as_str() {
+                "sub" => StreamType::Sub,
+                _ => StreamType::Main,
+            },
+            purpose: match r.purpose.as_str() {
+                "click_modal" => StreamPurpose::ClickModal,
+                "suggest_play" => StreamPurpose::SuggestPlay,
+                "polling" => StreamPurpose::Polling,
+                "snapshot" => StreamPurpose::Snapshot,
+                _ => StreamPurpose::HealthCheck,
+            },
+            client_id: r.client_id,
+            started_at: r.started_at,
+            expires_at: r.expires_at,
+        }
+    }
+}
+
 pub struct AccessAbsorberRepository {
-    pub(crate) pool: MySqlPool,
+    pub(crate) db: Db,
 }
 
 impl AccessAbsorberRepository {
-    pub fn new(pool: MySqlPool) -> Self {
-        Self { pool }
-    }
-
-    /// Get pool reference (for internal use)
-    pub fn pool(&self) -> &MySqlPool {
-        &self.pool
+    pub fn new(db: impl Into<Db>) -> Self {
+        Self { db: db.into() }
     }
 
     // ========================================================================
@@ -56,18 +75,20 @@ impl AccessAbsorberRepository {
 
     /// Get connection limits for a family
     pub async fn get_limits(&self, family: &str) -> Result<Option<ConnectionLimits>> {
-        let row: Option<AccessLimitRow> = sqlx::query_as(
-            r#"
-            SELECT id, family, display_name, max_concurrent_streams,
-                   min_reconnect_interval_ms, require_exclusive_lock,
-                   connection_timeout_ms, model_pattern, notes
-            FROM camera_access_limits
-            WHERE family = ?
-            "#,
-        )
-        .bind(family)
-        .fetch_optional(&self.pool)
-        .await
+        let row: Option<AccessLimitRow> = with_db!(&self.db, pool => {
+            sqlx::query_as(
+                r#"
+                SELECT id, family, display_name, max_concurrent_streams,
+                       min_reconnect_interval_ms, require_exclusive_lock,
+                       connection_timeout_ms, model_pattern, notes
+                FROM camera_access_limits
+                WHERE family = ?
+                "#,
+            )
+            .bind(family)
+            .fetch_optional(pool)
+            .await
+        })
         .map_err(|e| Error::Database(e.to_string()))?;
 
         Ok(row.map(|r| ConnectionLimits {
@@ -83,17 +104,19 @@ impl AccessAbsorberRepository {
 
     /// Get all connection limits
     pub async fn get_all_limits(&self) -> Result<Vec<ConnectionLimits>> {
-        let rows: Vec<AccessLimitRow> = sqlx::query_as(
-            r#"
-            SELECT id, family, display_name, max_concurrent_streams,
-                   min_reconnect_interval_ms, require_exclusive_lock,
-                   connection_timeout_ms, model_pattern, notes
-            FROM camera_access_limits
-            ORDER BY family
-            "#,
-        )
-        .fetch_all(&self.pool)
-        .await
+        let rows: Vec<AccessLimitRow> = with_db!(&self.db, pool => {
+            sqlx::query_as(
+                r#"
+                SELECT id, family, display_name, max_concurrent_streams,
+                       min_reconnect_interval_ms, require_exclusive_lock,
+                       connection_timeout_ms, model_pattern, notes
+                FROM camera_access_limits
+                ORDER BY family
+                "#,
+            )
+            .fetch_all(pool)
+            .await
+        })
         .map_err(|e| Error::Database(e.to_string()))?;
 
         Ok(rows
@@ -117,16 +140,18 @@ impl AccessAbsorberRepository {
         default_family: &str,
     ) -> Result<ConnectionLimits> {
         // First check if camera has access_family set
-        let camera_family: Option<(Option<String>, Option<String>)> = sqlx::query_as(
-            r#"
-            SELECT access_family, access_limit_override
-            FROM cameras
-            WHERE camera_id = ?
-            "#,
-        )
-        .bind(camera_id)
-        .fetch_optional(&self.pool)
-        .await
+        let camera_family: Option<(Option<String>, Option<String>)> = with_db!(&self.db, pool => {
+            sqlx::query_as(
+                r#"
+                SELECT access_family, access_limit_override
+                FROM cameras
+                WHERE camera_id = ?
+                "#,
+            )
+            .bind(camera_id)
+            .fetch_optional(pool)
+            .await
+        })
         .map_err(|e| Error::Database(e.to_string()))?;
 
         let family = camera_family
@@ -173,40 +198,42 @@ impl AccessAbsorberRepository {
 
     /// Get active sessions for a camera
     pub async fn get_active_sessions(&self, camera_id: &str) -> Result<Vec<ActiveStream>> {
-        let rows: Vec<StreamSessionRow> = sqlx::query_as(
-            r#"
-            SELECT session_id, camera_id, stream_type, purpose, client_id,
-                   started_at, expires_at, last_heartbeat_at, status
-            FROM camera_stream_sessions
-            WHERE camera_id = ? AND status = 'active'
-            "#,
-        )
-        .bind(camera_id)
-        .fetch_all(&self.pool)
-        .await
+        let rows: Vec<StreamSessionRow> = with_db!(&self.db, pool => {
+            sqlx::query_as(
+                r#"
+                SELECT session_id, camera_id, stream_type, purpose, client_id,
+                       started_at, expires_at, last_heartbeat_at, status
+                FROM camera_stream_sessions
+                WHERE camera_id = ? AND status = 'active'
+                "#,
+            )
+            .bind(camera_id)
+            .fetch_all(pool)
+            .await
+        })
         .map_err(|e| Error::Database(e.to_string()))?;
 
-        Ok(rows
-            .into_iter()
-            .map(|r| ActiveStream {
-                session_id: r.session_id,
-                camera_id: r.camera_id,
-                stream_type: match r.stream_type.as_str() {
-                    "sub" => StreamType::Sub,
-                    _ => StreamType::Main,
-                },
-                purpose: match r.purpose.as_str() {
-                    "click_modal" => StreamPurpose::ClickModal,
-                    "suggest_play" => StreamPurpose::SuggestPlay,
-                    "polling" => StreamPurpose::Polling,
-                    "snapshot" => StreamPurpose::Snapshot,
-                    _ => StreamPurpose::HealthCheck,
-                },
-                client_id: r.client_id,
-                started_at: r.started_at,
-                expires_at: r.expires_at,
-            })
-            .collect())
+        Ok(rows.into_iter().map(ActiveStream::from).collect())
+    }
+
+    /// Get active sessions across all cameras
+    pub async fn get_all_active_sessions(&self) -> Result<Vec<ActiveStream>> {
+        let rows: Vec<StreamSessionRow> = with_db!(&self.db, pool => {
+            sqlx::query_as(
+                r#"
+                SELECT session_id, camera_id, stream_type, purpose, client_id,
+                       started_at, expires_at, last_heartbeat_at, status
+                FROM camera_stream_sessions
+                WHERE status = 'active'
+                ORDER BY camera_id, started_at
+                "#,
+            )
+            .fetch_all(pool)
+            .await
+        })
+        .map_err(|e| Error::Database(e.to_string()))?;
+
+        Ok(rows.into_iter().map(ActiveStream::from).collect())
     }
 
     /// Create a new session
@@ -233,21 +260,24 @@ impl AccessAbsorberRepository {
 
         let expires_at = expires_in_secs.map(|secs| Utc::now() + chrono::Duration::seconds(secs));
 
-        sqlx::query(
-            r#"
-            INSERT INTO camera_stream_sessions
-            (session_id, camera_id, stream_type, purpose, client_id, expires_at)
-            VALUES (?, ?, ?, ?, ?, ?)
-            "#,
-        )
-        .bind(&session_id)
-        .bind(camera_id)
-        .bind(stream_type_str)
-        .bind(purpose_str)
-        .bind(client_id)
-        .bind(expires_at)
-        .execute(&self.pool)
-        .await
+        with_db!(&self.db, pool => {
+            sqlx::query(
+                r#"
+                INSERT INTO camera_stream_sessions
+                (session_id, camera_id, stream_type, purpose, client_id, expires_at)
+                VALUES (?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(&session_id)
+            .bind(camera_id)
+            .bind(stream_type_str)
+            .bind(purpose_str)
+            .bind(client_id)
+            .bind(expires_at)
+            .execute(pool)
+            .await
+            .map(|r| r.rows_affected())
+        })
         .map_err(|e| Error::Database(e.to_string()))?;
 
         debug!(
@@ -262,84 +292,101 @@ impl AccessAbsorberRepository {
 
     /// Delete a session
     pub async fn delete_session(&self, session_id: &str) -> Result<bool> {
-        let result = sqlx::query(
-            r#"
-            DELETE FROM camera_stream_sessions
-            WHERE session_id = ?
-            "#,
-        )
-        .bind(session_id)
-        .execute(&self.pool)
-        .await
+        let affected = with_db!(&self.db, pool => {
+            sqlx::query(
+                r#"
+                DELETE FROM camera_stream_sessions
+                WHERE session_id = ?
+                "#,
+            )
+            .bind(session_id)
+            .execute(pool)
+            .await
+            .map(|r| r.rows_affected())
+        })
         .map_err(|e| Error::Database(e.to_string()))?;
 
-        Ok(result.rows_affected() > 0)
+        Ok(affected > 0)
     }
 
     /// Update session heartbeat
     pub async fn update_heartbeat(&self, session_id: &str) -> Result<bool> {
-        let result = sqlx::query(
+        let sql = format!(
             r#"
             UPDATE camera_stream_sessions
-            SET last_heartbeat_at = NOW(3)
+            SET last_heartbeat_at = {}
             WHERE session_id = ? AND status = 'active'
             "#,
-        )
-        .bind(session_id)
-        .execute(&self.pool)
-        .await
+            self.db.now()
+        );
+        let affected = with_db!(&self.db, pool => {
+            sqlx::query(&sql)
+                .bind(session_id)
+                .execute(pool)
+                .await
+                .map(|r| r.rows_affected())
+        })
         .map_err(|e| Error::Database(e.to_string()))?;
 
-        Ok(result.rows_affected() > 0)
+        Ok(affected > 0)
     }
 
     /// Mark session as releasing
     pub async fn mark_releasing(&self, session_id: &str) -> Result<bool> {
-        let result = sqlx::query(
-            r#"
-            UPDATE camera_stream_sessions
-            SET status = 'releasing'
-            WHERE session_id = ? AND status = 'active'
-            "#,
-        )
-        .bind(session_id)
-        .execute(&self.pool)
-        .await
+        let affected = with_db!(&self.db, pool => {
+            sqlx::query(
+                r#"
+                UPDATE camera_stream_sessions
+                SET status = 'releasing'
+                WHERE session_id = ? AND status = 'active'
+                "#,
+            )
+            .bind(session_id)
+            .execute(pool)
+            .await
+            .map(|r| r.rows_affected())
+        })
         .map_err(|e| Error::Database(e.to_string()))?;
 
-        Ok(result.rows_affected() > 0)
+        Ok(affected > 0)
     }
 
     /// Cleanup expired sessions
     pub async fn cleanup_expired_sessions(&self) -> Result<u64> {
+        let expired = format!(
+            "status = 'active' AND (expires_at < {} OR last_heartbeat_at < {})",
+            self.db.now(),
+            self.db.ago("2", IntervalUnit::Minute)
+        );
+
         // First log expired sessions
-        let expired_count = sqlx::query(
+        let insert_sql = format!(
             r#"
             INSERT INTO camera_connection_events
             (camera_id, event_type, purpose, client_id)
             SELECT camera_id, 'disconnect_timeout', purpose, client_id
             FROM camera_stream_sessions
-            WHERE status = 'active'
-            AND (expires_at < NOW() OR last_heartbeat_at < DATE_SUB(NOW(), INTERVAL 2 MINUTE))
+            WHERE {}
             "#,
-        )
-        .execute(&self.pool)
-        .await
-        .map_err(|e| Error::Database(e.to_string()))?
-        .rows_affected();
+            expired
+        );
+        let expired_count = with_db!(&self.db, pool => {
+            sqlx::query(&insert_sql)
+                .execute(pool)
+                .await
+                .map(|r| r.rows_affected())
+        })
+        .map_err(|e| Error::Database(e.to_string()))?;
 
         // Then delete them
-        let deleted = sqlx::query(
-            r#"
-            DELETE FROM camera_stream_sessions
-            WHERE status = 'active'
-            AND (expires_at < NOW() OR last_heartbeat_at < DATE_SUB(NOW(), INTERVAL 2 MINUTE))
-            "#,
-        )
-        .execute(&self.pool)
-        .await
-        .map_err(|e| Error::Database(e.to_string()))?
-        .rows_affected();
+        let delete_sql = format!("DELETE FROM camera_stream_sessions WHERE {}", expired);
+        let deleted = with_db!(&self.db, pool => {
+            sqlx::query(&delete_sql)
+                .execute(pool)
+                .await
+                .map(|r| r.rows_affected())
+        })
+        .map_err(|e| Error::Database(e.to_string()))?;
 
         if deleted > 0 {
             info!(deleted = deleted, "Cleaned up expired stream sessions");
@@ -354,16 +401,18 @@ impl AccessAbsorberRepository {
 
     /// Get last disconnect time for a camera
     pub async fn get_last_disconnect(&self, camera_id: &str) -> Result<Option<DateTime<Utc>>> {
-        let result: Option<(Option<DateTime<Utc>>,)> = sqlx::query_as(
-            r#"
-            SELECT last_disconnect_at
-            FROM cameras
-            WHERE camera_id = ?
-            "#,
-        )
-        .bind(camera_id)
-        .fetch_optional(&self.pool)
-        .await
+        let result: Option<(Option<DateTime<Utc>>,)> = with_db!(&self.db, pool => {
+            sqlx::query_as(
+                r#"
+                SELECT last_disconnect_at
+                FROM cameras
+                WHERE camera_id = ?
+                "#,
+            )
+            .bind(camera_id)
+            .fetch_optional(pool)
+            .await
+        })
         .map_err(|e| Error::Database(e.to_string()))?;
 
         Ok(result.and_then(|(dt,)| dt))
@@ -371,16 +420,17 @@ impl AccessAbsorberRepository {
 
     /// Update last disconnect time
     pub async fn update_last_disconnect(&self, camera_id: &str) -> Result<()> {
-        sqlx::query(
-            r#"
-            UPDATE cameras
-            SET last_disconnect_at = NOW(3)
-            WHERE camera_id = ?
-            "#,
-        )
-        .bind(camera_id)
-        .execute(&self.pool)
-        .await
+        let sql = format!(
+            "UPDATE cameras SET last_disconnect_at = {} WHERE camera_id = ?",
+            self.db.now()
+        );
+        with_db!(&self.db, pool => {
+            sqlx::query(&sql)
+                .bind(camera_id)
+                .execute(pool)
+                .await
+                .map(|r| r.rows_affected())
+        })
         .map_err(|e| Error::Database(e.to_string()))?;
 
         Ok(())
@@ -388,17 +438,20 @@ impl AccessAbsorberRepository {
 
     /// Update camera access family
     pub async fn update_access_family(&self, camera_id: &str, family: &str) -> Result<()> {
-        sqlx::query(
-            r#"
-            UPDATE cameras
-            SET access_family = ?
-            WHERE camera_id = ?
-            "#,
-        )
-        .bind(family)
-        .bind(camera_id)
-        .execute(&self.pool)
-        .await
+        with_db!(&self.db, pool => {
+            sqlx::query(
+                r#"
+                UPDATE cameras
+                SET access_family = ?
+                WHERE camera_id = ?
+                "#,
+            )
+            .bind(family)
+            .bind(camera_id)
+            .execute(pool)
+            .await
+            .map(|r| r.rows_affected())
+        })
         .map_err(|e| Error::Database(e.to_string()))?;
 
         Ok(())
@@ -419,17 +472,7 @@ impl AccessAbsorberRepository {
         wait_time_ms: Option<u32>,
         preempted_by: Option<&str>,
     ) -> Result<()> {
-        let event_type_str = match event_type {
-            ConnectionEventType::ConnectSuccess => "connect_success",
-            ConnectionEventType::ConnectBlockedConcurrent => "connect_blocked_concurrent",
-            ConnectionEventType::ConnectBlockedInterval => "connect_blocked_interval",
-            ConnectionEventType::ConnectTimeout => "connect_timeout",
-            ConnectionEventType::ConnectPreempted => "connect_preempted",
-            ConnectionEventType::DisconnectNormal => "disconnect_normal",
-            ConnectionEventType::DisconnectPreempted => "disconnect_preempted",
-            ConnectionEventType::DisconnectTimeout => "disconnect_timeout",
-            ConnectionEventType::DisconnectError => "disconnect_error",
-        };
+        let event_type_str = event_type.as_str();
 
         let purpose_str = purpose.map(|p| match p {
             StreamPurpose::ClickModal => "click_modal",
@@ -439,22 +482,25 @@ impl AccessAbsorberRepository {
             StreamPurpose::HealthCheck => "health_check",
         });
 
-        sqlx::query(
-            r#"
-            INSERT INTO camera_connection_events
-            (camera_id, event_type, purpose, client_id, blocked_reason, wait_time_ms, preempted_by)
-            VALUES (?, ?, ?, ?, ?, ?, ?)
-            "#,
-        )
-        .bind(camera_id)
-        .bind(event_type_str)
-        .bind(purpose_str)
-        .bind(client_id)
-        .bind(blocked_reason)
-        .bind(wait_time_ms)
-        .bind(preempted_by)
-        .execute(&self.pool)
-        .await
+        with_db!(&self.db, pool => {
+            sqlx::query(
+                r#"
+                INSERT INTO camera_connection_events
+                (camera_id, event_type, purpose, client_id, blocked_reason, wait_time_ms, preempted_by)
+                VALUES (?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(camera_id)
+            .bind(event_type_str)
+            .bind(purpose_str)
+            .bind(client_id)
+            .bind(blocked_reason)
+            .bind(wait_time_ms)
+            .bind(preempted_by)
+            .execute(pool)
+            .await
+            .map(|r| r.rows_affected())
+        })
         .map_err(|e| Error::Database(e.to_string()))?;
 
         Ok(())
@@ -466,21 +512,23 @@ impl AccessAbsorberRepository {
         camera_id: &str,
         since: DateTime<Utc>,
     ) -> Result<ConnectionStats> {
-        let row: Option<(i64, i64, i64, i64)> = sqlx::query_as(
-            r#"
-            SELECT
-                SUM(CASE WHEN event_type = 'connect_success' THEN 1 ELSE 0 END) as success_count,
-                SUM(CASE WHEN event_type = 'connect_blocked_concurrent' THEN 1 ELSE 0 END) as concurrent_blocked,
-                SUM(CASE WHEN event_type = 'connect_blocked_interval' THEN 1 ELSE 0 END) as interval_blocked,
-                SUM(CASE WHEN event_type LIKE 'disconnect_preempted%' THEN 1 ELSE 0 END) as preempted
-            FROM camera_connection_events
-            WHERE camera_id = ? AND created_at >= ?
-            "#,
-        )
-        .bind(camera_id)
-        .bind(since)
-        .fetch_optional(&self.pool)
-        .await
+        let row: Option<(i64, i64, i64, i64)> = with_db!(&self.db, pool => {
+            sqlx::query_as(
+                r#"
+                SELECT
+                    SUM(CASE WHEN event_type = 'connect_success' THEN 1 ELSE 0 END) as success_count,
+                    SUM(CASE WHEN event_type = 'connect_blocked_concurrent' THEN 1 ELSE 0 END) as concurrent_blocked,
+                    SUM(CASE WHEN event_type = 'connect_blocked_interval' THEN 1 ELSE 0 END) as interval_blocked,
+                    SUM(CASE WHEN event_type LIKE 'disconnect_preempted%' THEN 1 ELSE 0 END) as preempted
+                FROM camera_connection_events
+                WHERE camera_id = ? AND created_at >= ?
+                "#,
+            )
+            .bind(camera_id)
+            .bind(since)
+            .fetch_optional(pool)
+            .await
+        })
         .map_err(|e| Error::Database(e.to_string()))?;
 
         Ok(row
@@ -502,3 +550,46 @@ pub struct ConnectionStats {
     pub interval_limit_hits: u32,
     pub preemption_count: u32,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_sqlite_builtin_limits() {
+        let repo = AccessAbsorberRepository::new(Db::memory().await.unwrap());
+
+        let limits = repo.get_limits("tapo_ptz").await.unwrap().unwrap();
+        assert_eq!(limits.max_concurrent_streams, 1);
+        assert!(limits.require_exclusive_lock);
+        assert!(repo.get_limits("missing").await.unwrap().is_none());
+        assert_eq!(repo.get_all_limits().await.unwrap().len(), 8);
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_session_lifecycle() {
+        let repo = AccessAbsorberRepository::new(Db::memory().await.unwrap());
+
+        let first = repo
+            .create_session("cam-1", StreamType::Main, StreamPurpose::ClickModal, "client-a", Some(60))
+            .await
+            .unwrap();
+        repo.create_session("cam-2", StreamType::Sub, StreamPurpose::Polling, "client-b", None)
+            .await
+            .unwrap();
+
+        let sessions = repo.get_active_sessions("cam-1").await.unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].session_id, first);
+        assert!(matches!(sessions[0].purpose, StreamPurpose::ClickModal));
+        assert!(sessions[0].expires_at.is_some());
+        assert_eq!(repo.get_all_active_sessions().await.unwrap().len(), 2);
+
+        assert!(repo.update_heartbeat(&first).await.unwrap());
+        assert!(repo.mark_releasing(&first).await.unwrap());
+        assert!(repo.get_active_sessions("cam-1").await.unwrap().is_empty());
+        assert!(repo.delete_session(&first).await.unwrap());
+        assert!(!repo.delete_session(&first).await.unwrap());
+        assert_eq!(repo.get_all_active_sessions().await.unwrap().len(), 1);
+    }
+}
@@ -2,13 +2,14 @@
 
 use super::repository::{AccessAbsorberRepository, ConnectionStats};
 use super::types::*;
-use crate::error::{Error, Result};
+use crate::error::Result;
+use crate::metrics::Metrics;
 use chrono::{Duration, Utc};
 use sqlx::MySqlPool;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use tracing::{debug, info, warn};
+use tracing::{debug, info};
 
 /// In-memory cache for connection limits
 #[derive(Debug, Default)]
@@ -27,16 +28,19 @@ struct LimitsCache {
 pub struct AccessAbsorberService {
     repo: AccessAbsorberRepository,
     cache: Arc<RwLock<LimitsCache>>,
+    /// Rejection / preemption counters (GET /metrics)
+    metrics: Arc<Metrics>,
     /// Default session expiry in seconds (1 hour)
     default_session_expiry_secs: i64,
 }
 
 impl AccessAbsorberService {
     /// Create new service
-    pub fn new(pool: MySqlPool) -> Self {
+    pub fn new(pool: MySqlPool, metrics: Arc<Metrics>) -> Self {
         Self {
             repo: AccessAbsorberRepository::new(pool),
             cache: Arc::new(RwLock::new(LimitsCache::default())),
+            metrics,
             default_session_expiry_secs: 3600,
         }
     }
@@ -183,6 +187,10 @@ impl AccessAbsorberService {
                             .await;
                     } else {
                         // Log the event
+                        self.metrics.absorber_event(
+                            camera_id,
+                            ConnectionEventType::ConnectBlockedInterval.as_str(),
+                        );
                         let _ = self
                             .repo
                             .log_event(
@@ -447,26 +455,7 @@ impl AccessAbsorberService {
 
     /// Get all active sessions across all cameras
     async fn get_all_active_sessions(&self) -> Result<Vec<ActiveStream>> {
-        // This is a simplified version - in production, you might want pagination
-        let rows: Vec<(String,)> = sqlx::query_as(
-            r#"
-            SELECT DISTINCT camera_id
-            FROM camera_stream_sessions
-            WHERE status = 'active'
-            "#,
-        )
-        .fetch_all(self.repo.pool())
-        .await
-        .map_err(|e| Error::Database(e.to_string()))?;
-
-        let mut all_sessions = Vec::new();
-        for (camera_id,) in rows {
-            if let Ok(sessions) = self.repo.get_active_sessions(&camera_id).await {
-                all_sessions.extend(sessions);
-            }
-        }
-
-        Ok(all_sessions)
+        self.repo.get_all_active_sessions().await
     }
 
     /// Try to preempt a lower priority session
@@ -501,6 +490,8 @@ impl AccessAbsorberService {
             let _ = self.repo.update_last_disconnect(camera_id).await;
 
             // Log preemption event
+            self.metrics
+                .absorber_event(camera_id, ConnectionEventType::DisconnectPreempted.as_str());
             let _ = self
                 .repo
                 .log_event(
@@ -543,9 +534,8 @@ impl AccessAbsorberService {
         current_count: u8,
         active_sessions: &[ActiveStream],
     ) -> AbsorberError {
-        // Log the event (fire and forget)
-        let camera_id_clone = camera_id.to_string();
-        let repo = &self.repo;
+        self.metrics
+            .absorber_event(camera_id, ConnectionEventType::ConnectBlockedConcurrent.as_str());
         let purposes: Vec<String> = active_sessions
             .iter()
             .map(|s| s.purpose.to_japanese().to_string())
@@ -442,6 +442,22 @@ pub enum ConnectionEventType {
     DisconnectError,
 }
 
+impl ConnectionEventType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ConnectSuccess => "connect_success",
+            Self::ConnectBlockedConcurrent => "connect_blocked_concurrent",
+            Self::ConnectBlockedInterval => "connect_blocked_interval",
+            Self::ConnectTimeout => "connect_timeout",
+            Self::ConnectPreempted => "connect_preempted",
+            Self::DisconnectNormal => "disconnect_normal",
+            Self::DisconnectPreempted => "disconnect_preempted",
+            Self::DisconnectTimeout => "disconnect_timeout",
+            Self::DisconnectError => "disconnect_error",
+        }
+    }
+}
+
 /// Stream status API response
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StreamStatusResponse {
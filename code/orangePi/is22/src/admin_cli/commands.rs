@@ -0,0 +1,344 @@
+//! Admin subcommand execution
+//!
+//! HTTP ハンドラと同じサービスを必要な分だけ組み立てて実行する。
+//! 結果は標準出力、ログは標準エラーへ出す。
+
+use super::Command;
+use crate::aranea_register::{AraneaRegisterService, RegisterRequest, TenantPrimaryAuth};
+use crate::camera_malfunction_reporter::CameraMalfunctionReporter;
+use crate::camera_registry::CameraContextService;
+use crate::config_store::{ConfigStore, UpdateCameraRequest};
+use crate::detection_log_service::DetectionLogService;
+use crate::ipcam_scan::{DeviceFilter, IpcamScan, ScanJobRequest};
+use crate::metrics::Metrics;
+use crate::paraclate_client::{ParaclateClient, SendQueueRepository};
+use crate::prev_frame_cache::PrevFrameCache;
+use crate::realtime_hub::RealtimeHub;
+use crate::state::AppConfig;
+use crate::storage_manager::{StorageManager, StorageStore};
+use crate::summary_service::{
+    SummaryGenerator, SummaryRepository, SummaryTextBuilder, TemplateRepository,
+};
+use crate::{Error, Result};
+use ipnetwork::IpNetwork;
+use sqlx::MySqlPool;
+use std::io::Write;
+use std::sync::Arc;
+
+/// Shared resources for admin subcommands
+pub struct AdminContext {
+    pub pool: MySqlPool,
+    pub config: AppConfig,
+    pub config_store: Arc<ConfigStore>,
+}
+
+impl AdminContext {
+    pub async fn new(pool: MySqlPool, config: AppConfig) -> Result<Self> {
+        let config_store = Arc::new(ConfigStore::new(pool.clone()).await?);
+        Ok(Self {
+            pool,
+            config,
+            config_store,
+        })
+    }
+
+    fn detection_log(&self) -> Arc<DetectionLogService> {
+        Arc::new(DetectionLogService::with_pool(self.pool.clone()))
+    }
+
+    /// StorageManager wired like `serve` (same stores, settings and malfunction reports)
+    async fn storage_manager(&self) -> Result<StorageManager> {
+        let mut cache_stores = vec![
+            (StorageStore::Snapshots, self.config.snapshot_dir.clone()),
+            (StorageStore::PrevImages, self.config.temp_dir.clone()),
+        ];
+        if let Some(dir) = PrevFrameCache::with_defaults().persist_dir() {
+            cache_stores.push((StorageStore::FrameCache, dir.to_path_buf()));
+        }
+        let paraclate = Arc::new(ParaclateClient::new(
+            self.pool.clone(),
+            self.config_store.clone(),
+            Arc::new(Metrics::new()),
+        ));
+        let aranea_register = self.config.aranea_gate_url.as_ref().map(|url| {
+            Arc::new(AraneaRegisterService::new(
+                url.clone(),
+                self.pool.clone(),
+                self.config_store.clone(),
+            ))
+        });
+        let (tid, fid) = default_tid_fid();
+        let manager = StorageManager::new(
+            self.config_store.clone(),
+            self.detection_log(),
+            CameraMalfunctionReporter::new(paraclate),
+            Arc::new(RealtimeHub::new()),
+            aranea_register,
+            cache_stores,
+            tid,
+            fid,
+        );
+        manager.load_config().await?;
+        Ok(manager)
+    }
+}
+
+/// Default TID/FID (same env as `serve`)
+fn default_tid_fid() -> (String, String) {
+    (
+        std::env::var("DEFAULT_TID").unwrap_or_else(|_| "T0000000000000000000".to_string()),
+        std::env::var("DEFAULT_FID").unwrap_or_else(|_| "0000".to_string()),
+    )
+}
+
+/// Execute an admin subcommand (`Serve` / `Migrate` / `Doctor` are handled by main)
+pub async fn run(ctx: &AdminContext, command: Command) -> Result<()> {
+    match command {
+        Command::CamerasList => {
+            let cameras = ctx.config_store.service().list_cameras().await?;
+            println!(
+                "{:<24} {:<24} {:<16} {:<10} {:<8} STATE",
+                "CAMERA_ID", "NAME", "IP", "FAMILY", "FID"
+            );
+            for camera in &cameras {
+                let state = match (camera.enabled, camera.polling_enabled) {
+                    (false, _) => "disabled",
+                    (true, false) => "no-polling",
+                    (true, true) => "enabled",
+                };
+                println!(
+                    "{:<24} {:<24} {:<16} {:<10} {:<8} {}",
+                    camera.camera_id,
+                    camera.name,
+                    camera.ip_address.as_deref().unwrap_or("-"),
+                    camera.family,
+                    camera.fid.as_deref().unwrap_or("-"),
+                    state
+                );
+            }
+            println!("{} camera(s)", cameras.len());
+        }
+        Command::CamerasAdd(request) => {
+            let camera = ctx.config_store.service().create_camera(request).await?;
+            println!("created {} ({})", camera.camera_id, camera.name);
+        }
+        Command::CamerasDisable { camera_id } => {
+            let camera = ctx
+                .config_store
+                .service()
+                .update_camera(
+                    &camera_id,
+                    UpdateCameraRequest {
+                        enabled: Some(false),
+                        ..Default::default()
+                    },
+                )
+                .await?;
+            println!("disabled {} ({})", camera.camera_id, camera.name);
+        }
+        Command::ScanRun { cidr, brute_force } => {
+            let network: IpNetwork = cidr
+                .parse()
+                .map_err(|_| Error::Validation(format!("invalid CIDR: {}", cidr)))?;
+            let scanner = IpcamScan::new(ctx.pool.clone(), ctx.config_store.clone());
+            let job = scanner
+                .create_job(ScanJobRequest {
+                    targets: vec![cidr.clone()],
+                    mode: None,
+                    ports: None,
+                    timeout_ms: None,
+                    concurrency: None,
+                    brute_force,
+                })
+                .await?;
+            eprintln!("scanning {} (job {})...", cidr, job.job_id);
+            scanner.run_job(job.job_id).await?;
+
+            if let Some(summary) = scanner.get_job(&job.job_id).await.and_then(|j| j.summary) {
+                println!(
+                    "ips={} alive={} cameras={} verified={}",
+                    summary.total_ips,
+                    summary.hosts_alive,
+                    summary.cameras_found,
+                    summary.cameras_verified
+                );
+            }
+            for device in scanner.list_devices(DeviceFilter::default()).await {
+                let in_range = device
+                    .ip
+                    .parse()
+                    .map(|ip| network.contains(ip))
+                    .unwrap_or(false);
+                if in_range {
+                    println!(
+                        "{:<16} {:<18} {:<20} score={:<4} {:?}",
+                        device.ip,
+                        device.mac.as_deref().unwrap_or("-"),
+                        device.manufacturer.as_deref().unwrap_or("-"),
+                        device.score,
+                        device.status
+                    );
+                }
+            }
+        }
+        Command::SummaryGenerate { tid, fid, from, to } => {
+            let (default_tid, default_fid) = default_tid_fid();
+            let tid = tid.unwrap_or(default_tid);
+            let fid = fid.unwrap_or(default_fid);
+
+            let detection_log = ctx.detection_log();
+            let camera_context_service = CameraContextService::new(ctx.pool.clone());
+            let summary_repository = SummaryRepository::new(ctx.pool.clone());
+            let text_builder = Arc::new(SummaryTextBuilder::new(
+                detection_log.clone(),
+                camera_context_service.clone(),
+                TemplateRepository::new(ctx.pool.clone()),
+                summary_repository.clone(),
+                ctx.config_store.clone(),
+            ));
+            let generator = SummaryGenerator::new(
+                detection_log,
+                camera_context_service,
+                summary_repository,
+                ctx.config_store.clone(),
+                text_builder,
+            );
+            let summary = generator.generate(&tid, &fid, from, to).await?;
+            println!(
+                "summary {} ({} detections, severity_max={}, cameras={})",
+                summary.summary_id,
+                summary.detection_count,
+                summary.severity_max,
+                summary.camera_ids.len()
+            );
+            println!("{}", summary.summary_text);
+        }
+        Command::QueueList { fid, status, limit } => {
+            let repo = SendQueueRepository::new(ctx.pool.clone());
+            let items = repo.list_recent(fid.as_deref(), status, limit).await?;
+            println!(
+                "{:<8} {:<6} {:<14} {:<8} {:<7} {:<25} LAST_ERROR",
+                "ID", "FID", "TYPE", "STATUS", "RETRY", "CREATED"
+            );
+            for item in &items {
+                println!(
+                    "{:<8} {:<6} {:<14} {:<8} {:<7} {:<25} {}",
+                    item.queue_id,
+                    item.fid,
+                    format!("{:?}", item.payload_type),
+                    item.status.to_string(),
+                    format!("{}/{}", item.retry_count, item.max_retries),
+                    item.created_at.to_rfc3339(),
+                    item.last_error.as_deref().unwrap_or("")
+                );
+            }
+        }
+        Command::QueueRetry { fid, queue_id } => {
+            let repo = SendQueueRepository::new(ctx.pool.clone());
+            let count = repo.requeue_failed(fid.as_deref(), queue_id).await?;
+            println!("requeued {} failed item(s)", count);
+        }
+        Command::QueuePurge { fid, status } => {
+            let repo = SendQueueRepository::new(ctx.pool.clone());
+            let count = repo.purge(fid.as_deref(), status).await?;
+            println!("purged {} {} item(s)", count, status);
+        }
+        Command::RegisterDevice {
+            tid,
+            fid,
+            lacis_id,
+            user_id,
+            cic,
+        } => {
+            let gate_url = ctx.config.aranea_gate_url.clone().ok_or_else(|| {
+                Error::Config("aranea_gate_url (ARANEA_GATE_URL) is not set".to_string())
+            })?;
+            let service =
+                AraneaRegisterService::new(gate_url, ctx.pool.clone(), ctx.config_store.clone());
+            let result = service
+                .register_device(RegisterRequest {
+                    tenant_primary_auth: TenantPrimaryAuth {
+                        lacis_id,
+                        user_id,
+                        cic,
+                    },
+                    tid,
+                    fid,
+                })
+                .await?;
+            if !result.ok {
+                return Err(Error::Internal(
+                    result
+                        .error
+                        .unwrap_or_else(|| "registration failed".to_string()),
+                ));
+            }
+            println!(
+                "registered lacis_id={} cic={}",
+                result.lacis_id.unwrap_or_default(),
+                result.cic.unwrap_or_default()
+            );
+        }
+        Command::StorageCleanup => {
+            // 定期タスクと同じ入口（集計 → 逼迫時は low watermark まで退避）
+            let manager = ctx.storage_manager().await?;
+            let config = manager.config().await;
+            let report = manager.run_cycle().await?;
+            for disk in &report.disks {
+                let stores: Vec<&str> = disk.stores.iter().map(|s| s.as_str()).collect();
+                println!(
+                    "{:<24} used={:.1}% level={} stores={}",
+                    disk.mount_point.display(),
+                    disk.used_percent,
+                    disk.level.as_str(),
+                    stores.join(",")
+                );
+            }
+            if !config.enabled {
+                println!("storage manager is disabled (PUT /api/storage/config); nothing evicted");
+            } else if let Some(eviction) = &report.last_eviction {
+                println!(
+                    "evicted: freed {} MB (event_images={} cache_files={})",
+                    eviction.bytes_freed / (1024 * 1024),
+                    eviction.event_images_deleted,
+                    eviction.cache_files_deleted
+                );
+            }
+            println!("level={}", report.level.as_str());
+        }
+        Command::ExportLogs {
+            from,
+            to,
+            output,
+            limit,
+        } => {
+            let logs = ctx
+                .detection_log()
+                .get_by_time_range(from, to, limit)
+                .await?;
+            let mut out: Box<dyn Write> = match &output {
+                Some(path) => Box::new(std::io::BufWriter::new(std::fs::File::create(path)?)),
+                None => Box::new(std::io::stdout().lock()),
+            };
+            // 古い順の JSON Lines
+            for log in logs.iter().rev() {
+                serde_json::to_writer(&mut out, log)?;
+                writeln!(out)?;
+            }
+            out.flush()?;
+            if let Some(path) = output {
+                eprintln!("exported {} log(s) to {}", logs.len(), path.display());
+            }
+        }
+        Command::Serve
+        | Command::Help
+        | Command::Migrate(_)
+        | Command::Doctor
+        | Command::EvidenceVerify { .. } => {
+            return Err(Error::Internal(
+                "command is handled by the binary entry point".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,207 @@
+//! `camserver doctor` - 稼働前提条件のチェック
+//!
+//! DB / IS21 / go2rtc / ffmpeg / arp-scan / ディスク空き容量を順に確認する。
+//! DB に接続できなくても他のチェックは続行する。
+
+use crate::ai_client::AIClient;
+use crate::health::{self, ComponentStatus};
+use crate::migration::{MigrationState, Migrator};
+use crate::snapshot_service::SnapshotService;
+use crate::state::AppConfig;
+use crate::stream_gateway::StreamGateway;
+use sqlx::mysql::MySqlPoolOptions;
+use std::path::Path;
+use std::time::Duration;
+use tokio::process::Command;
+
+/// Outcome of one check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Ok,
+    Warn,
+    Fail,
+}
+
+impl CheckStatus {
+    fn label(&self) -> &'static str {
+        match self {
+            CheckStatus::Ok => " OK ",
+            CheckStatus::Warn => "WARN",
+            CheckStatus::Fail => "FAIL",
+        }
+    }
+}
+
+/// One doctor check result
+#[derive(Debug, Clone)]
+pub struct CheckResult {
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+impl CheckResult {
+    fn new(name: &str, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status,
+            detail: detail.into(),
+        }
+    }
+}
+
+impl From<ComponentStatus> for CheckStatus {
+    fn from(status: ComponentStatus) -> Self {
+        match status {
+            ComponentStatus::Ok => CheckStatus::Ok,
+            ComponentStatus::Degraded => CheckStatus::Warn,
+            ComponentStatus::Down => CheckStatus::Fail,
+        }
+    }
+}
+
+/// Run all checks and print a report (true = no failures)
+pub async fn run(config: &AppConfig) -> bool {
+    let mut results = vec![check_database(config).await];
+
+    let is21 = AIClient::new(config.is21_url.clone());
+    results.push(match is21.health_check().await {
+        Ok(true) => CheckResult::new("is21", CheckStatus::Ok, config.is21_url.as_str()),
+        _ => CheckResult::new(
+            "is21",
+            CheckStatus::Fail,
+            format!("{}/healthz unreachable", config.is21_url),
+        ),
+    });
+
+    let go2rtc = StreamGateway::new(config.go2rtc_url.clone());
+    results.push(match go2rtc.health_check().await {
+        Ok(true) => CheckResult::new("go2rtc", CheckStatus::Ok, config.go2rtc_url.as_str()),
+        _ => CheckResult::new(
+            "go2rtc",
+            CheckStatus::Fail,
+            format!("{} unreachable", config.go2rtc_url),
+        ),
+    });
+
+    results.push(match SnapshotService::check_ffmpeg().await {
+        Ok(version) => CheckResult::new("ffmpeg", CheckStatus::Ok, version),
+        Err(e) => CheckResult::new("ffmpeg", CheckStatus::Fail, e.to_string()),
+    });
+
+    results.push(check_arp_scan().await);
+
+    for (name, path) in [
+        ("disk:snapshots", config.snapshot_dir.as_path()),
+        ("disk:temp", config.temp_dir.as_path()),
+        ("disk:evidence", config.evidence_dir.as_path()),
+    ] {
+        results.push(check_disk(name, path));
+    }
+
+    for result in &results {
+        println!(
+            "[{}] {:<16} {}",
+            result.status.label(),
+            result.name,
+            result.detail
+        );
+    }
+    let failures = results
+        .iter()
+        .filter(|r| r.status == CheckStatus::Fail)
+        .count();
+    if failures > 0 {
+        println!("{} check(s) failed", failures);
+    }
+    failures == 0
+}
+
+async fn check_database(config: &AppConfig) -> CheckResult {
+    let pool = match MySqlPoolOptions::new()
+        .max_connections(1)
+        .acquire_timeout(Duration::from_secs(5))
+        .connect(&config.database_url)
+        .await
+    {
+        Ok(pool) => pool,
+        Err(e) => return CheckResult::new("database", CheckStatus::Fail, e.to_string()),
+    };
+
+    match Migrator::new(pool).status().await {
+        Ok(statuses) => {
+            let count =
+                |state: MigrationState| statuses.iter().filter(|s| s.state == state).count();
+            let applied = count(MigrationState::Applied);
+            let pending = count(MigrationState::Pending);
+            let broken = count(MigrationState::ChecksumMismatch) + count(MigrationState::Unknown);
+            let status = if broken > 0 {
+                CheckStatus::Fail
+            } else if pending > 0 {
+                CheckStatus::Warn
+            } else {
+                CheckStatus::Ok
+            };
+            CheckResult::new(
+                "database",
+                status,
+                format!(
+                    "connected (migrations: {} applied, {} pending, {} mismatched/unknown)",
+                    applied, pending, broken
+                ),
+            )
+        }
+        Err(e) => CheckResult::new(
+            "database",
+            CheckStatus::Warn,
+            format!("connected, migration status unavailable: {}", e),
+        ),
+    }
+}
+
+/// IpcamScan は `sudo arp-scan` を使う（パスワードなし sudo が必要）
+async fn check_arp_scan() -> CheckResult {
+    let output = Command::new("sudo")
+        .args(["-n", "arp-scan", "--version"])
+        .output()
+        .await;
+    match output {
+        Ok(o) if o.status.success() => {
+            // arp-scan prints its version to stderr
+            let text = String::from_utf8_lossy(if o.stdout.is_empty() {
+                &o.stderr
+            } else {
+                &o.stdout
+            })
+            .lines()
+            .next()
+            .unwrap_or("available")
+            .to_string();
+            CheckResult::new("arp-scan", CheckStatus::Ok, text)
+        }
+        Ok(_) => CheckResult::new(
+            "arp-scan",
+            CheckStatus::Warn,
+            "not runnable via passwordless sudo (scan / LostCamTracker degraded)",
+        ),
+        Err(e) => CheckResult::new("arp-scan", CheckStatus::Warn, e.to_string()),
+    }
+}
+
+/// `/readyz` と同じ判定（空き 10% 未満で WARN、2% 未満で FAIL）
+fn check_disk(name: &str, path: &Path) -> CheckResult {
+    let health = health::check_disk(name, path);
+    CheckResult::new(name, health.status.into(), health.detail)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_component_status_mapping() {
+        assert_eq!(CheckStatus::from(ComponentStatus::Ok), CheckStatus::Ok);
+        assert_eq!(CheckStatus::from(ComponentStatus::Degraded), CheckStatus::Warn);
+        assert_eq!(CheckStatus::from(ComponentStatus::Down), CheckStatus::Fail);
+    }
+}
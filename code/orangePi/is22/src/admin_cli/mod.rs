@@ -0,0 +1,401 @@
+//! Admin CLI - `camserver <subcommand>`
+//!
+//! ## Responsibilities
+//!
+//! - ブラウザなしで SSH 越しに運用操作を行うためのサブコマンド
+//! - HTTP API と同じライブラリサービスを再利用（ロジックを重複させない）
+//!
+//! ```text
+//! camserver [serve]                                     サーバー起動（従来動作）
+//! camserver migrate ...                                 スキーマ移行（migration::cli）
+//! camserver cameras list
+//! camserver cameras add --id <id> --name <name> --ip <ip> [--location ..] [--rtsp-main ..] [--family ..]
+//! camserver cameras disable <camera_id>
+//! camserver scan run --cidr <cidr> [--brute-force]
+//! camserver summary generate --from <t> --to <t> [--tid ..] [--fid ..]
+//! camserver paraclate queue list [--fid ..] [--status ..] [--limit N]
+//! camserver paraclate queue retry [--fid ..] [--id <queue_id>]
+//! camserver paraclate queue purge [--fid ..] [--status failed|sent|skipped|pending]
+//! camserver register device --tid .. --lacis-id .. --user-id .. --cic .. [--fid ..]
+//! camserver storage cleanup
+//! camserver export logs --from <t> --to <t> [--output <file>] [--limit N]
+//! camserver evidence verify <package_dir> --fingerprint <sha256> | --pubkey <pem>   証拠パッケージ検証（DB・設定不要）
+//! camserver doctor
+//! ```
+//!
+//! 時刻 `<t>` は RFC3339 または `YYYY-MM-DD`（JST 0:00）。
+
+mod commands;
+pub mod doctor;
+
+pub use commands::{run, AdminContext};
+
+use crate::config_store::{CameraFamily, CreateCameraRequest};
+use crate::paraclate_client::QueueStatus;
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use chrono_tz::Asia::Tokyo;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+pub const USAGE: &str = "\
+usage: camserver [serve]
+       camserver migrate [status | up | down <version> | baseline <version>]
+       camserver cameras list
+       camserver cameras add --id <id> --name <name> --ip <ip> [--location <loc>] [--floor <floor>]
+                             [--rtsp-main <url>] [--rtsp-sub <url>] [--snapshot-url <url>]
+                             [--family <family>] [--mac <mac>]
+       camserver cameras disable <camera_id>
+       camserver scan run --cidr <cidr> [--brute-force]
+       camserver summary generate --from <time> --to <time> [--tid <tid>] [--fid <fid>]
+       camserver paraclate queue list [--fid <fid>] [--status <status>] [--limit <n>]
+       camserver paraclate queue retry [--fid <fid>] [--id <queue_id>]
+       camserver paraclate queue purge [--fid <fid>] [--status <status>]
+       camserver register device --tid <tid> --lacis-id <id> --user-id <user> --cic <cic> [--fid <fid>]
+       camserver storage cleanup
+       camserver export logs --from <time> --to <time> [--output <file>] [--limit <n>]
+       camserver evidence verify <package_dir> (--fingerprint <sha256> | --pubkey <pem_file>)
+       camserver doctor
+  <time>: RFC3339 or YYYY-MM-DD (00:00 JST)";
+
+/// Parsed subcommand
+#[derive(Debug, Clone)]
+pub enum Command {
+    Serve,
+    Help,
+    /// Arguments after "migrate"
+    Migrate(Vec<String>),
+    Doctor,
+    /// Handled before config load (no DB needed)
+    EvidenceVerify {
+        package: PathBuf,
+        /// Trusted key fingerprint obtained outside the package
+        fingerprint: Option<String>,
+        /// Trusted public key PEM file obtained outside the package
+        pubkey: Option<PathBuf>,
+    },
+    CamerasList,
+    CamerasAdd(CreateCameraRequest),
+    CamerasDisable {
+        camera_id: String,
+    },
+    ScanRun {
+        cidr: String,
+        brute_force: bool,
+    },
+    SummaryGenerate {
+        tid: Option<String>,
+        fid: Option<String>,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    },
+    QueueList {
+        fid: Option<String>,
+        status: Option<QueueStatus>,
+        limit: i32,
+    },
+    QueueRetry {
+        fid: Option<String>,
+        queue_id: Option<u64>,
+    },
+    QueuePurge {
+        fid: Option<String>,
+        status: QueueStatus,
+    },
+    RegisterDevice {
+        tid: String,
+        fid: Option<String>,
+        lacis_id: String,
+        user_id: String,
+        cic: String,
+    },
+    StorageCleanup,
+    ExportLogs {
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        output: Option<PathBuf>,
+        limit: u32,
+    },
+}
+
+impl Command {
+    /// Parse process arguments (excluding the binary name)
+    pub fn parse(args: &[String]) -> crate::Result<Self> {
+        let words: Vec<&str> = args.iter().map(String::as_str).collect();
+        let command = match words.as_slice() {
+            [] | ["serve"] => Command::Serve,
+            ["help" | "--help" | "-h", ..] => Command::Help,
+            ["migrate", ..] => Command::Migrate(args[1..].to_vec()),
+            ["doctor"] => Command::Doctor,
+            ["evidence", "verify", package, rest @ ..] if !package.starts_with("--") => {
+                let mut flags = Flags::parse(rest, &["fingerprint", "pubkey"], &[])?;
+                let fingerprint = flags.take("fingerprint");
+                let pubkey = flags.take("pubkey").map(PathBuf::from);
+                if fingerprint.is_some() && pubkey.is_some() {
+                    return Err(usage_error("use either --fingerprint or --pubkey"));
+                }
+                Command::EvidenceVerify {
+                    package: PathBuf::from(package),
+                    fingerprint,
+                    pubkey,
+                }
+            }
+            ["cameras", "list"] => Command::CamerasList,
+            ["cameras", "add", rest @ ..] => {
+                let mut flags = Flags::parse(
+                    rest,
+                    &[
+                        "id",
+                        "name",
+                        "ip",
+                        "location",
+                        "floor",
+                        "rtsp-main",
+                        "rtsp-sub",
+                        "snapshot-url",
+                        "family",
+                        "mac",
+                    ],
+                    &[],
+                )?;
+                Command::CamerasAdd(CreateCameraRequest {
+                    camera_id: flags.required("id")?,
+                    name: flags.required("name")?,
+                    ip_address: Some(flags.required("ip")?),
+                    location: flags.take("location").unwrap_or_default(),
+                    floor: flags.take("floor"),
+                    rtsp_main: flags.take("rtsp-main"),
+                    rtsp_sub: flags.take("rtsp-sub"),
+                    snapshot_url: flags.take("snapshot-url"),
+                    family: flags.take("family").map(CameraFamily::from),
+                    manufacturer: None,
+                    model: None,
+                    mac_address: flags.take("mac"),
+                    camera_context: None,
+                })
+            }
+            ["cameras", "disable", camera_id] => Command::CamerasDisable {
+                camera_id: camera_id.to_string(),
+            },
+            ["scan", "run", rest @ ..] => {
+                let mut flags = Flags::parse(rest, &["cidr"], &["brute-force"])?;
+                Command::ScanRun {
+                    cidr: flags.required("cidr")?,
+                    brute_force: flags.switch("brute-force"),
+                }
+            }
+            ["summary", "generate", rest @ ..] => {
+                let mut flags = Flags::parse(rest, &["from", "to", "tid", "fid"], &[])?;
+                let (from, to) = flags.period()?;
+                Command::SummaryGenerate {
+                    tid: flags.take("tid"),
+                    fid: flags.take("fid"),
+                    from,
+                    to,
+                }
+            }
+            ["paraclate", "queue", "list", rest @ ..] => {
+                let mut flags = Flags::parse(rest, &["fid", "status", "limit"], &[])?;
+                Command::QueueList {
+                    fid: flags.take("fid"),
+                    status: flags.take("status").map(|s| parse_status(&s)).transpose()?,
+                    limit: flags.number("limit")?.unwrap_or(50),
+                }
+            }
+            ["paraclate", "queue", "retry", rest @ ..] => {
+                let mut flags = Flags::parse(rest, &["fid", "id"], &[])?;
+                Command::QueueRetry {
+                    fid: flags.take("fid"),
+                    queue_id: flags.number("id")?,
+                }
+            }
+            ["paraclate", "queue", "purge", rest @ ..] => {
+                let mut flags = Flags::parse(rest, &["fid", "status"], &[])?;
+                Command::QueuePurge {
+                    fid: flags.take("fid"),
+                    status: flags
+                        .take("status")
+                        .map(|s| parse_status(&s))
+                        .transpose()?
+                        .unwrap_or(QueueStatus::Failed),
+                }
+            }
+            ["register", "device", rest @ ..] => {
+                let mut flags =
+                    Flags::parse(rest, &["tid", "fid", "lacis-id", "user-id", "cic"], &[])?;
+                Command::RegisterDevice {
+                    tid: flags.required("tid")?,
+                    fid: flags.take("fid"),
+                    lacis_id: flags.required("lacis-id")?,
+                    user_id: flags.required("user-id")?,
+                    cic: flags.required("cic")?,
+                }
+            }
+            ["storage", "cleanup"] => Command::StorageCleanup,
+            ["export", "logs", rest @ ..] => {
+                let mut flags = Flags::parse(rest, &["from", "to", "output", "limit"], &[])?;
+                let (from, to) = flags.period()?;
+                Command::ExportLogs {
+                    from,
+                    to,
+                    output: flags.take("output").map(PathBuf::from),
+                    limit: flags.number("limit")?.unwrap_or(100_000),
+                }
+            }
+            _ => return Err(usage_error("unknown command")),
+        };
+        Ok(command)
+    }
+}
+
+fn usage_error(reason: &str) -> crate::Error {
+    crate::Error::Validation(format!("{}\n{}", reason, USAGE))
+}
+
+fn parse_status(value: &str) -> crate::Result<QueueStatus> {
+    match value {
+        "pending" | "sending" | "sent" | "failed" | "skipped" => Ok(QueueStatus::from(value)),
+        _ => Err(usage_error(&format!("unknown queue status: {}", value))),
+    }
+}
+
+/// Parse `<time>`: RFC3339 or `YYYY-MM-DD` (00:00 JST)
+pub fn parse_time(value: &str) -> crate::Result<DateTime<Utc>> {
+    if let Ok(t) = DateTime::parse_from_rfc3339(value) {
+        return Ok(t.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .and_then(|t| Tokyo.from_local_datetime(&t).single())
+        .map(|t| t.with_timezone(&Utc))
+        .ok_or_else(|| usage_error(&format!("invalid time: {}", value)))
+}
+
+/// `--key value` / `--switch` options of one subcommand
+struct Flags {
+    values: HashMap<String, String>,
+    switches: Vec<String>,
+}
+
+impl Flags {
+    fn parse(args: &[&str], options: &[&str], switches: &[&str]) -> crate::Result<Self> {
+        let mut flags = Flags {
+            values: HashMap::new(),
+            switches: Vec::new(),
+        };
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            let name = arg
+                .strip_prefix("--")
+                .ok_or_else(|| usage_error(&format!("unexpected argument: {}", arg)))?;
+            if switches.contains(&name) {
+                flags.switches.push(name.to_string());
+            } else if options.contains(&name) {
+                let value = iter
+                    .next()
+                    .ok_or_else(|| usage_error(&format!("--{} requires a value", name)))?;
+                flags.values.insert(name.to_string(), value.to_string());
+            } else {
+                return Err(usage_error(&format!("unknown option: --{}", name)));
+            }
+        }
+        Ok(flags)
+    }
+
+    fn take(&mut self, name: &str) -> Option<String> {
+        self.values.remove(name)
+    }
+
+    fn required(&mut self, name: &str) -> crate::Result<String> {
+        self.take(name)
+            .ok_or_else(|| usage_error(&format!("--{} is required", name)))
+    }
+
+    fn switch(&self, name: &str) -> bool {
+        self.switches.iter().any(|s| s == name)
+    }
+
+    fn number<T: std::str::FromStr>(&mut self, name: &str) -> crate::Result<Option<T>> {
+        self.take(name)
+            .map(|v| {
+                v.parse()
+                    .map_err(|_| usage_error(&format!("--{} must be a number", name)))
+            })
+            .transpose()
+    }
+
+    /// `--from` / `--to` (from < to)
+    fn period(&mut self) -> crate::Result<(DateTime<Utc>, DateTime<Utc>)> {
+        let from = parse_time(&self.required("from")?)?;
+        let to = parse_time(&self.required("to")?)?;
+        if from >= to {
+            return Err(usage_error("--from must be earlier than --to"));
+        }
+        Ok((from, to))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(line: &str) -> crate::Result<Command> {
+        let args: Vec<String> = line.split_whitespace().map(String::from).collect();
+        Command::parse(&args)
+    }
+
+    #[test]
+    fn test_parse_subcommands() {
+        assert!(matches!(parse("").unwrap(), Command::Serve));
+        assert!(
+            matches!(parse("migrate down 43").unwrap(), Command::Migrate(a) if a == ["down", "43"])
+        );
+        assert!(matches!(
+            parse("scan run --cidr 192.168.125.0/24 --brute-force").unwrap(),
+            Command::ScanRun { cidr, brute_force: true } if cidr == "192.168.125.0/24"
+        ));
+        assert!(matches!(
+            parse("paraclate queue purge --fid 0150").unwrap(),
+            Command::QueuePurge { fid: Some(f), status: QueueStatus::Failed } if f == "0150"
+        ));
+        match parse("cameras add --id cam-9 --name Gate --ip 192.168.125.19 --family tapo").unwrap()
+        {
+            Command::CamerasAdd(req) => {
+                assert_eq!(req.camera_id, "cam-9");
+                assert_eq!(req.ip_address.as_deref(), Some("192.168.125.19"));
+                assert!(matches!(req.family, Some(CameraFamily::Tapo)));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(matches!(
+            parse("evidence verify ./pkg --fingerprint ab12").unwrap(),
+            Command::EvidenceVerify { fingerprint: Some(f), pubkey: None, .. } if f == "ab12"
+        ));
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_arguments() {
+        assert!(parse("cameras add --name Gate --ip 10.0.0.1").is_err());
+        assert!(parse("scan run --cidr").is_err());
+        assert!(parse("paraclate queue list --status bogus").is_err());
+        assert!(parse("export logs --from 2026-01-02 --to 2026-01-01").is_err());
+        assert!(parse("cameras remove cam-1").is_err());
+        assert!(parse("evidence verify").is_err());
+        assert!(parse("evidence verify --fingerprint ab").is_err());
+        assert!(parse("evidence verify ./pkg --fingerprint ab --pubkey key.pem").is_err());
+    }
+
+    #[test]
+    fn test_parse_time_date_is_jst_midnight() {
+        assert_eq!(
+            parse_time("2026-01-02").unwrap().to_rfc3339(),
+            "2026-01-01T15:00:00+00:00"
+        );
+        assert_eq!(
+            parse_time("2026-01-02T09:30:00+09:00")
+                .unwrap()
+                .to_rfc3339(),
+            "2026-01-02T00:30:00+00:00"
+        );
+    }
+}
@@ -41,14 +41,28 @@ impl LeaseManager {
         leases.len()
     }
 
+    /// Get active lease count for a camera
+    pub async fn camera_count(&self, camera_id: &str) -> usize {
+        let leases = self.leases.read().await;
+        leases.values().filter(|l| l.camera_id == camera_id).count()
+    }
+
     /// Create a new lease
-    pub async fn create_lease(&self, request: LeaseRequest, cost: u32, ttl_sec: u64) -> ModalLease {
+    pub async fn create_lease(
+        &self,
+        request: LeaseRequest,
+        quality: StreamQuality,
+        cost: u32,
+        ttl_sec: u64,
+    ) -> ModalLease {
         let now = Utc::now();
         let lease = ModalLease {
             lease_id: Uuid::new_v4(),
             user_id: request.user_id.clone(),
             camera_id: request.camera_id,
-            quality: request.quality,
+            quality,
+            requested_quality: request.quality,
+            role: request.role,
             cost,
             created_at: now,
             expires_at: now + Duration::seconds(ttl_sec as i64),
@@ -69,14 +83,23 @@ impl LeaseManager {
             lease_id = %lease.lease_id,
             user_id = %lease.user_id,
             camera_id = %lease.camera_id,
+            quality = %lease.quality.as_str(),
             "Lease created"
         );
 
         lease
     }
 
+    /// Restore a persisted lease (server restart)
+    pub async fn restore_lease(&self, lease: ModalLease) {
+        let mut leases = self.leases.write().await;
+        let mut user_leases = self.user_leases.write().await;
+        user_leases.insert(lease.user_id.clone(), lease.lease_id);
+        leases.insert(lease.lease_id, lease);
+    }
+
     /// Update heartbeat
-    pub async fn heartbeat(&self, lease_id: &Uuid, ttl_sec: u64) -> Result<HeartbeatResponse> {
+    pub async fn heartbeat(&self, lease_id: &Uuid, ttl_sec: u64) -> Result<ModalLease> {
         let mut leases = self.leases.write().await;
 
         if let Some(lease) = leases.get_mut(lease_id) {
@@ -85,19 +108,14 @@ impl LeaseManager {
             // Extend expiration
             lease.expires_at = now + Duration::seconds(ttl_sec as i64);
 
-            let remaining = (lease.expires_at - now).num_seconds();
-
-            Ok(HeartbeatResponse {
-                ok: true,
-                remaining_sec: remaining,
-            })
+            Ok(lease.clone())
         } else {
             Err(Error::NotFound(format!("Lease {} not found", lease_id)))
         }
     }
 
-    /// Release a lease
-    pub async fn release_lease(&self, lease_id: &Uuid) -> Result<()> {
+    /// Release a lease (Ok(false) if it did not exist)
+    pub async fn release_lease(&self, lease_id: &Uuid) -> Result<bool> {
         let user_id = {
             let mut leases = self.leases.write().await;
             if let Some(lease) = leases.remove(lease_id) {
@@ -112,16 +130,17 @@ impl LeaseManager {
             }
         };
 
+        let released = user_id.is_some();
         if let Some(user_id) = user_id {
             let mut user_leases = self.user_leases.write().await;
             user_leases.remove(&user_id);
         }
 
-        Ok(())
+        Ok(released)
     }
 
-    /// Cleanup expired leases
-    pub async fn cleanup(&self, heartbeat_grace_sec: u64) {
+    /// Cleanup expired leases. Returns removed lease IDs
+    pub async fn cleanup(&self, heartbeat_grace_sec: u64) -> Vec<Uuid> {
         let now = Utc::now();
         let grace = Duration::seconds(heartbeat_grace_sec as i64);
 
@@ -148,6 +167,8 @@ impl LeaseManager {
                 tracing::info!(lease_id = %id, "Expired lease cleaned up");
             }
         }
+
+        expired_ids.into_iter().map(|(id, _)| id).collect()
     }
 
     /// Get lease by ID
@@ -13,24 +13,39 @@
 //! - Budget Model: Fixed capacity with reserved units
 //! - Lease/Heartbeat: Server-issued, auto-cleanup
 //! - Hysteresis: Prevents flapping
+//! - Waiting Queue: 空きがない場合はチケットを発行し、空き発生時に優先度順で自動発行
+//!   （RealtimeHub lease_queue で通知）
+//! - Downgrade: main が予算に収まらない、または待機者がいる場合は sub で発行
+//! - Persistence: 発行済みリースを modal_leases に保存し、再起動時に復元
 
 mod lease;
+mod queue;
+mod store;
 mod types;
 
 pub use lease::LeaseManager;
+pub use queue::AdmissionQueue;
+pub use store::LeaseStore;
 pub use types::*;
 
 use crate::config_store::AdmissionPolicy;
 use crate::error::{Error, Result};
+use crate::realtime_hub::{HubMessage, LeaseQueueMessage, RealtimeHub};
 use crate::state::SystemHealth;
+use chrono::Utc;
+use sqlx::MySqlPool;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{Mutex, RwLock};
 
 /// AdmissionController instance
 pub struct AdmissionController {
     policy: Arc<RwLock<AdmissionPolicy>>,
     lease_manager: LeaseManager,
     system_health: Arc<RwLock<SystemHealth>>,
+    /// Waiting queue. Also serializes admission decisions
+    queue: Mutex<AdmissionQueue>,
+    store: LeaseStore,
+    realtime_hub: Arc<RealtimeHub>,
 }
 
 impl AdmissionController {
@@ -38,14 +53,33 @@ impl AdmissionController {
     pub fn new(
         policy: AdmissionPolicy,
         system_health: Arc<RwLock<SystemHealth>>,
+        pool: MySqlPool,
+        realtime_hub: Arc<RealtimeHub>,
     ) -> Self {
         Self {
             policy: Arc::new(RwLock::new(policy)),
             lease_manager: LeaseManager::new(),
             system_health,
+            queue: Mutex::new(AdmissionQueue::new()),
+            store: LeaseStore::new(pool),
+            realtime_hub,
         }
     }
 
+    /// Restore persisted leases (call once at startup)
+    ///
+    /// 再起動中はハートビートを送れないため、last_heartbeat を現在時刻にして猶予を与える
+    pub async fn restore_leases(&self) -> Result<usize> {
+        let now = Utc::now();
+        let leases = self.store.load_active(now).await?;
+        let count = leases.len();
+        for mut lease in leases {
+            lease.last_heartbeat = now;
+            self.lease_manager.restore_lease(lease).await;
+        }
+        Ok(count)
+    }
+
     /// Check if a modal lease can be admitted
     pub async fn can_admit(&self, request: &LeaseRequest) -> Result<AdmissionResult> {
         // 1. System overload check
@@ -61,97 +95,226 @@ impl AdmissionController {
             return Ok(AdmissionResult::Rejected(RejectReason::UserAlreadyHasModal));
         }
 
-        // 3. Budget check
-        let policy = self.policy.read().await;
-        let cost = match request.quality {
-            StreamQuality::Main => policy.main_stream_cost,
-            StreamQuality::Sub => policy.sub_stream_cost,
-        };
+        // 3. Budget / per-camera check
+        let others_waiting = self.queue.lock().await.waiting_count() > 0;
+        let policy = self.policy.read().await.clone();
+        Ok(self.evaluate(&policy, request, others_waiting).await)
+    }
 
-        let current_usage = self.lease_manager.current_usage().await;
-        let modal_budget = policy.total_stream_units
-            - policy.reserved_baseline_units
-            - (policy.max_ui_users * policy.sub_stream_cost); // Reserve for suggest
+    /// Budget / per-camera check against current leases
+    async fn evaluate(
+        &self,
+        policy: &AdmissionPolicy,
+        request: &LeaseRequest,
+        others_waiting: bool,
+    ) -> AdmissionResult {
+        decide(
+            policy,
+            self.lease_manager.current_usage().await,
+            self.lease_manager.camera_count(&request.camera_id).await,
+            request.quality,
+            others_waiting,
+        )
+    }
 
-        if current_usage + cost > modal_budget {
-            return Ok(AdmissionResult::Rejected(RejectReason::OverCapacity));
+    /// Request a new lease
+    ///
+    /// `request.queue` が true の場合、空きがなければ待機チケットを返す
+    pub async fn request_lease(&self, request: LeaseRequest) -> Result<LeaseGrant> {
+        let mut queue = self.queue.lock().await;
+
+        if self.system_health.read().await.overloaded {
+            return Err(reject_error(RejectReason::SystemOverload));
+        }
+        if self.lease_manager.has_active_lease(&request.user_id).await {
+            return Err(reject_error(RejectReason::UserAlreadyHasModal));
         }
+        // 再リクエストは既存チケットを置き換える
+        queue.remove_user(&request.user_id);
 
-        Ok(AdmissionResult::Admitted(cost as u32))
+        let policy = self.policy.read().await.clone();
+        match self.evaluate(&policy, &request, queue.waiting_count() > 0).await {
+            AdmissionResult::Admitted(cost, quality) => Ok(LeaseGrant::Granted(
+                self.issue_lease(request, quality, cost, &policy).await,
+            )),
+            AdmissionResult::Rejected(reason) if request.queue && policy.max_queue_length > 0 => {
+                if queue.waiting_count() >= policy.max_queue_length as usize {
+                    return Err(reject_error(RejectReason::QueueFull));
+                }
+                let ticket = queue.enqueue(&request, reason, Utc::now());
+                tracing::info!(
+                    ticket_id = %ticket.ticket_id,
+                    user_id = %ticket.user_id,
+                    camera_id = %ticket.camera_id,
+                    position = ?ticket.position,
+                    "Lease request queued"
+                );
+                Ok(LeaseGrant::Queued(ticket))
+            }
+            AdmissionResult::Rejected(reason) => Err(reject_error(reason)),
+        }
     }
 
-    /// Request a new lease
-    pub async fn request_lease(&self, request: LeaseRequest) -> Result<ModalLease> {
-        let result = self.can_admit(&request).await?;
-
-        match result {
-            AdmissionResult::Admitted(cost) => {
-                let policy = self.policy.read().await;
-                let lease = self.lease_manager.create_lease(
-                    request,
-                    cost,
-                    policy.modal_ttl_sec as u64,
-                ).await;
-                Ok(lease)
-            }
-            AdmissionResult::Rejected(reason) => {
-                Err(match reason {
-                    RejectReason::SystemOverload => Error::SystemOverload(
-                        "サーバーが高負荷のためしばらく経ってからお試しください".to_string(),
-                    ),
-                    RejectReason::UserAlreadyHasModal => Error::Conflict(
-                        "既に別のカメラを表示中です".to_string(),
-                    ),
-                    RejectReason::OverCapacity => Error::OverCapacity(
-                        "現在多数のアクセスがありストリーム数が上限に達しています".to_string(),
-                    ),
-                })
+    async fn issue_lease(
+        &self,
+        request: LeaseRequest,
+        quality: StreamQuality,
+        cost: u32,
+        policy: &AdmissionPolicy,
+    ) -> ModalLease {
+        let lease = self
+            .lease_manager
+            .create_lease(request, quality, cost, policy.modal_ttl_sec as u64)
+            .await;
+        if let Err(e) = self.store.insert(&lease).await {
+            tracing::warn!(lease_id = %lease.lease_id, error = %e, "Failed to persist lease");
+        }
+        lease
+    }
+
+    /// Admit waiting tickets in priority order while capacity allows
+    ///
+    /// CAMERA_BUSY のチケットは読み飛ばし、OVER_CAPACITY で止まる（後続に追い越させない）
+    async fn drain_queue(&self, queue: &mut AdmissionQueue) {
+        if self.system_health.read().await.overloaded {
+            return;
+        }
+        let policy = self.policy.read().await.clone();
+        let waiting = queue.waiting();
+        let total = waiting.len();
+
+        for (index, ticket) in waiting.into_iter().enumerate() {
+            let request = LeaseRequest {
+                user_id: ticket.user_id.clone(),
+                camera_id: ticket.camera_id.clone(),
+                quality: ticket.quality,
+                role: ticket.role,
+                queue: true,
+            };
+            match self.evaluate(&policy, &request, index + 1 < total).await {
+                AdmissionResult::Admitted(cost, quality) => {
+                    let lease = self.issue_lease(request, quality, cost, &policy).await;
+                    queue.admit(&ticket.ticket_id, lease.clone());
+                    tracing::info!(
+                        ticket_id = %ticket.ticket_id,
+                        lease_id = %lease.lease_id,
+                        user_id = %lease.user_id,
+                        "Queued lease request admitted"
+                    );
+                    self.realtime_hub
+                        .broadcast(HubMessage::LeaseQueue(LeaseQueueMessage {
+                            ticket_id: ticket.ticket_id.to_string(),
+                            user_id: lease.user_id.clone(),
+                            camera_id: lease.camera_id.clone(),
+                            status: "admitted".to_string(),
+                            lease_id: Some(lease.lease_id.to_string()),
+                            allowed_quality: Some(lease.quality.as_str().to_string()),
+                        }))
+                        .await;
+                }
+                AdmissionResult::Rejected(RejectReason::CameraBusy) => {
+                    queue.set_waiting_for(&ticket.ticket_id, RejectReason::CameraBusy);
+                }
+                AdmissionResult::Rejected(reason) => {
+                    queue.set_waiting_for(&ticket.ticket_id, reason);
+                    break;
+                }
             }
         }
     }
 
     /// Heartbeat for a lease
     pub async fn heartbeat(&self, lease_id: &uuid::Uuid) -> Result<HeartbeatResponse> {
-        let policy = self.policy.read().await;
-        self.lease_manager.heartbeat(lease_id, policy.modal_ttl_sec as u64).await
+        let ttl_sec = self.policy.read().await.modal_ttl_sec as u64;
+        let lease = self.lease_manager.heartbeat(lease_id, ttl_sec).await?;
+        if let Err(e) = self.store.heartbeat(&lease).await {
+            tracing::warn!(lease_id = %lease_id, error = %e, "Failed to persist lease heartbeat");
+        }
+        Ok(HeartbeatResponse {
+            ok: true,
+            remaining_sec: (lease.expires_at - Utc::now()).num_seconds(),
+        })
     }
 
     /// Release a lease
     pub async fn release_lease(&self, lease_id: &uuid::Uuid) -> Result<()> {
-        self.lease_manager.release_lease(lease_id).await
+        let mut queue = self.queue.lock().await;
+        if let Some(lease) = self.lease_manager.get_lease(lease_id).await {
+            queue.remove_user(&lease.user_id);
+        }
+        if self.lease_manager.release_lease(lease_id).await? {
+            if let Err(e) = self.store.delete(&[*lease_id]).await {
+                tracing::warn!(lease_id = %lease_id, error = %e, "Failed to delete persisted lease");
+            }
+            self.drain_queue(&mut queue).await;
+        }
+        Ok(())
+    }
+
+    /// Get a queue ticket (polling also keeps the ticket alive)
+    pub async fn get_ticket(&self, ticket_id: &uuid::Uuid) -> Option<QueueTicket> {
+        self.queue.lock().await.touch(ticket_id, Utc::now())
+    }
+
+    /// Cancel a queue ticket
+    pub async fn cancel_ticket(&self, ticket_id: &uuid::Uuid) -> bool {
+        self.queue.lock().await.remove(ticket_id).is_some()
+    }
+
+    /// Waiting tickets in service order
+    pub async fn waiting_tickets(&self) -> Vec<QueueTicket> {
+        self.queue.lock().await.waiting()
     }
 
     /// Get current system status
     pub async fn get_status(&self) -> AdmissionStatus {
+        // queue → policy の順でロックする（update_policy と同順）
+        let queue_waiting = self.queue.lock().await.waiting_count();
         let policy = self.policy.read().await;
         let current_usage = self.lease_manager.current_usage().await;
         let active_modals = self.lease_manager.active_count().await;
         let health = self.system_health.read().await;
 
-        let modal_budget = policy.total_stream_units
-            - policy.reserved_baseline_units
-            - (policy.max_ui_users * policy.sub_stream_cost);
-
         AdmissionStatus {
             healthy: !health.overloaded,
             cpu_percent: health.cpu_percent,
             memory_percent: health.memory_percent,
             active_modals: active_modals as i32,
-            modal_budget_remaining: modal_budget - current_usage,
+            modal_budget_remaining: modal_budget(&policy) - current_usage,
+            queue_waiting,
             suggest_active: false, // TODO: Get from SuggestEngine
         }
     }
 
+    /// Current policy
+    pub async fn policy(&self) -> AdmissionPolicy {
+        self.policy.read().await.clone()
+    }
+
     /// Update policy
     pub async fn update_policy(&self, policy: AdmissionPolicy) {
-        let mut p = self.policy.write().await;
-        *p = policy;
+        let mut queue = self.queue.lock().await;
+        *self.policy.write().await = policy;
+        self.drain_queue(&mut queue).await;
     }
 
-    /// Cleanup expired leases (should be called periodically)
+    /// Cleanup expired leases and queue tickets, then admit waiting tickets
+    /// (should be called periodically; also picks up overload recovery)
     pub async fn cleanup(&self) {
-        let policy = self.policy.read().await;
-        self.lease_manager.cleanup(policy.heartbeat_grace_sec as u64).await;
+        let mut queue = self.queue.lock().await;
+        let policy = self.policy.read().await.clone();
+
+        let expired = self.lease_manager.cleanup(policy.heartbeat_grace_sec as u64).await;
+        if !expired.is_empty() {
+            if let Err(e) = self.store.delete(&expired).await {
+                tracing::warn!(error = %e, "Failed to delete expired leases");
+            }
+        }
+        for ticket in queue.expire(policy.queue_ticket_ttl_sec.max(1) as u64, Utc::now()) {
+            tracing::info!(ticket_id = %ticket.ticket_id, "Expired queue ticket cleaned up");
+        }
+
+        self.drain_queue(&mut queue).await;
     }
 
     /// Get lease by ID (for PTZ authentication)
@@ -159,3 +322,102 @@ impl AdmissionController {
         self.lease_manager.get_lease(lease_id).await
     }
 }
+
+/// Budget available for modal leases (reserving sub streams for suggest)
+fn modal_budget(policy: &AdmissionPolicy) -> i32 {
+    policy.total_stream_units
+        - policy.reserved_baseline_units
+        - (policy.max_ui_users * policy.sub_stream_cost)
+}
+
+/// Admission decision
+///
+/// - カメラ単位の上限到達 → CAMERA_BUSY
+/// - main は予算に収まり、かつ待機者がいない場合のみ許可。それ以外は sub へダウングレード
+/// - sub も収まらない → OVER_CAPACITY
+pub fn decide(
+    policy: &AdmissionPolicy,
+    current_usage: i32,
+    camera_leases: usize,
+    requested: StreamQuality,
+    others_waiting: bool,
+) -> AdmissionResult {
+    if policy.max_leases_per_camera > 0 && camera_leases >= policy.max_leases_per_camera as usize {
+        return AdmissionResult::Rejected(RejectReason::CameraBusy);
+    }
+
+    let remaining = modal_budget(policy) - current_usage;
+    if requested == StreamQuality::Main && !others_waiting && remaining >= policy.main_stream_cost {
+        AdmissionResult::Admitted(policy.main_stream_cost as u32, StreamQuality::Main)
+    } else if remaining >= policy.sub_stream_cost {
+        AdmissionResult::Admitted(policy.sub_stream_cost as u32, StreamQuality::Sub)
+    } else {
+        AdmissionResult::Rejected(RejectReason::OverCapacity)
+    }
+}
+
+fn reject_error(reason: RejectReason) -> Error {
+    match reason {
+        RejectReason::SystemOverload => Error::SystemOverload(
+            "サーバーが高負荷のためしばらく経ってからお試しください".to_string(),
+        ),
+        RejectReason::UserAlreadyHasModal => Error::Conflict(
+            "既に別のカメラを表示中です".to_string(),
+        ),
+        RejectReason::OverCapacity => Error::OverCapacity(
+            "現在多数のアクセスがありストリーム数が上限に達しています".to_string(),
+        ),
+        RejectReason::CameraBusy => Error::OverCapacity(
+            "このカメラの同時視聴数が上限に達しています".to_string(),
+        ),
+        RejectReason::QueueFull => Error::OverCapacity(
+            "待機列が満員です。しばらく経ってからお試しください".to_string(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decide_downgrades_when_tight() {
+        // modal budget = 50 - 5 - 10 = 35
+        let policy = AdmissionPolicy::default();
+
+        assert!(matches!(
+            decide(&policy, 0, 0, StreamQuality::Main, false),
+            AdmissionResult::Admitted(2, StreamQuality::Main)
+        ));
+        // main が収まらない
+        assert!(matches!(
+            decide(&policy, 34, 0, StreamQuality::Main, false),
+            AdmissionResult::Admitted(1, StreamQuality::Sub)
+        ));
+        // 待機者がいる
+        assert!(matches!(
+            decide(&policy, 0, 0, StreamQuality::Main, true),
+            AdmissionResult::Admitted(1, StreamQuality::Sub)
+        ));
+        assert!(matches!(
+            decide(&policy, 35, 0, StreamQuality::Sub, false),
+            AdmissionResult::Rejected(RejectReason::OverCapacity)
+        ));
+    }
+
+    #[test]
+    fn test_decide_per_camera_limit() {
+        let policy = AdmissionPolicy {
+            max_leases_per_camera: 2,
+            ..Default::default()
+        };
+        assert!(matches!(
+            decide(&policy, 0, 2, StreamQuality::Sub, false),
+            AdmissionResult::Rejected(RejectReason::CameraBusy)
+        ));
+        assert!(matches!(
+            decide(&policy, 0, 1, StreamQuality::Sub, false),
+            AdmissionResult::Admitted(1, StreamQuality::Sub)
+        ));
+    }
+}
@@ -0,0 +1,204 @@
+//! Waiting queue
+//!
+//! 空きがないリクエストの待機キュー。
+//! - 順序: ロール優先度（operator > viewer）→ 到着順
+//! - CAMERA_BUSY（カメラ単位の上限）で待つチケットは他カメラのチケットを塞がない
+//! - OVER_CAPACITY（全体予算）で待つ先頭チケットより後ろは追い越さない（head-of-line）
+//! - チケットはクライアントのポーリングで延命され、途絶えると期限切れ
+
+use super::types::*;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Waiting queue (caller provides synchronization)
+#[derive(Default)]
+pub struct AdmissionQueue {
+    tickets: HashMap<Uuid, QueueTicket>,
+}
+
+impl AdmissionQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a waiting ticket (replaces the user's previous ticket)
+    pub fn enqueue(&mut self, request: &LeaseRequest, waiting_for: RejectReason, now: DateTime<Utc>) -> QueueTicket {
+        self.remove_user(&request.user_id);
+        let ticket = QueueTicket {
+            ticket_id: Uuid::new_v4(),
+            user_id: request.user_id.clone(),
+            camera_id: request.camera_id.clone(),
+            quality: request.quality,
+            role: request.role,
+            status: QueueStatus::Waiting,
+            waiting_for,
+            position: None,
+            enqueued_at: now,
+            last_seen: now,
+            lease: None,
+        };
+        let ticket_id = ticket.ticket_id;
+        self.tickets.insert(ticket_id, ticket);
+        self.update_positions();
+        self.tickets[&ticket_id].clone()
+    }
+
+    fn find_user(&self, user_id: &str) -> Option<Uuid> {
+        self.tickets
+            .values()
+            .find(|t| t.user_id == user_id)
+            .map(|t| t.ticket_id)
+    }
+
+    pub fn remove_user(&mut self, user_id: &str) -> Option<QueueTicket> {
+        let id = self.find_user(user_id)?;
+        self.remove(&id)
+    }
+
+    pub fn remove(&mut self, ticket_id: &Uuid) -> Option<QueueTicket> {
+        let removed = self.tickets.remove(ticket_id);
+        if removed.is_some() {
+            self.update_positions();
+        }
+        removed
+    }
+
+    /// Refresh a ticket's last_seen (client polling)
+    pub fn touch(&mut self, ticket_id: &Uuid, now: DateTime<Utc>) -> Option<QueueTicket> {
+        let ticket = self.tickets.get_mut(ticket_id)?;
+        ticket.last_seen = now;
+        Some(ticket.clone())
+    }
+
+    pub fn get(&self, ticket_id: &Uuid) -> Option<&QueueTicket> {
+        self.tickets.get(ticket_id)
+    }
+
+    /// Waiting tickets in service order
+    pub fn waiting(&self) -> Vec<QueueTicket> {
+        let mut waiting: Vec<_> = self
+            .tickets
+            .values()
+            .filter(|t| t.status == QueueStatus::Waiting)
+            .cloned()
+            .collect();
+        waiting.sort_by(|a, b| {
+            b.role
+                .priority()
+                .cmp(&a.role.priority())
+                .then(a.enqueued_at.cmp(&b.enqueued_at))
+                .then(a.ticket_id.cmp(&b.ticket_id))
+        });
+        waiting
+    }
+
+    pub fn waiting_count(&self) -> usize {
+        self.tickets
+            .values()
+            .filter(|t| t.status == QueueStatus::Waiting)
+            .count()
+    }
+
+    /// Mark a ticket as admitted with its lease
+    pub fn admit(&mut self, ticket_id: &Uuid, lease: ModalLease) -> Option<QueueTicket> {
+        let ticket = self.tickets.get_mut(ticket_id)?;
+        ticket.status = QueueStatus::Admitted;
+        ticket.lease = Some(lease);
+        ticket.position = None;
+        let ticket = ticket.clone();
+        self.update_positions();
+        Some(ticket)
+    }
+
+    pub fn set_waiting_for(&mut self, ticket_id: &Uuid, reason: RejectReason) {
+        if let Some(ticket) = self.tickets.get_mut(ticket_id) {
+            ticket.waiting_for = reason;
+        }
+    }
+
+    /// Remove tickets not polled within ttl. Returns removed tickets
+    pub fn expire(&mut self, ttl_sec: u64, now: DateTime<Utc>) -> Vec<QueueTicket> {
+        let ttl = Duration::seconds(ttl_sec as i64);
+        let expired: Vec<Uuid> = self
+            .tickets
+            .values()
+            .filter(|t| now - t.last_seen > ttl)
+            .map(|t| t.ticket_id)
+            .collect();
+        let removed: Vec<_> = expired.iter().filter_map(|id| self.tickets.remove(id)).collect();
+        if !removed.is_empty() {
+            self.update_positions();
+        }
+        removed
+    }
+
+    fn update_positions(&mut self) {
+        let order: Vec<Uuid> = self.waiting().into_iter().map(|t| t.ticket_id).collect();
+        for (index, id) in order.iter().enumerate() {
+            if let Some(ticket) = self.tickets.get_mut(id) {
+                ticket.position = Some(index + 1);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(user: &str, camera: &str, role: UserRole) -> LeaseRequest {
+        LeaseRequest {
+            user_id: user.to_string(),
+            camera_id: camera.to_string(),
+            quality: StreamQuality::Main,
+            role,
+            queue: true,
+        }
+    }
+
+    #[test]
+    fn test_priority_then_fifo_order() {
+        let mut queue = AdmissionQueue::new();
+        let t0 = Utc::now();
+        queue.enqueue(&request("v1", "cam", UserRole::Viewer), RejectReason::OverCapacity, t0);
+        queue.enqueue(
+            &request("v2", "cam", UserRole::Viewer),
+            RejectReason::OverCapacity,
+            t0 + Duration::seconds(1),
+        );
+        let op = queue.enqueue(
+            &request("op", "cam", UserRole::Operator),
+            RejectReason::OverCapacity,
+            t0 + Duration::seconds(2),
+        );
+
+        let order: Vec<_> = queue.waiting().into_iter().map(|t| t.user_id).collect();
+        assert_eq!(order, vec!["op", "v1", "v2"]);
+        assert_eq!(queue.get(&op.ticket_id).unwrap().position, Some(1));
+
+        // 再リクエストはチケットを置き換え、最後尾へ
+        queue.enqueue(
+            &request("v1", "cam", UserRole::Viewer),
+            RejectReason::OverCapacity,
+            t0 + Duration::seconds(3),
+        );
+        let order: Vec<_> = queue.waiting().into_iter().map(|t| t.user_id).collect();
+        assert_eq!(order, vec!["op", "v2", "v1"]);
+        assert_eq!(queue.waiting_count(), 3);
+    }
+
+    #[test]
+    fn test_expire_unpolled_tickets() {
+        let mut queue = AdmissionQueue::new();
+        let t0 = Utc::now();
+        let a = queue.enqueue(&request("a", "cam", UserRole::Viewer), RejectReason::OverCapacity, t0);
+        let b = queue.enqueue(&request("b", "cam", UserRole::Viewer), RejectReason::OverCapacity, t0);
+
+        queue.touch(&b.ticket_id, t0 + Duration::seconds(50));
+        let expired = queue.expire(30, t0 + Duration::seconds(60));
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].ticket_id, a.ticket_id);
+        assert_eq!(queue.get(&b.ticket_id).unwrap().position, Some(1));
+    }
+}
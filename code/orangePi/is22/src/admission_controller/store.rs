@@ -0,0 +1,99 @@
+//! Lease persistence
+//!
+//! 再起動で視聴中のユーザーが切断されないよう、発行済みリースを modal_leases に保存する。
+//! 待機チケットはクライアントのポーリングで延命される短命な状態のため保存しない。
+
+use super::types::*;
+use chrono::{DateTime, Utc};
+use sqlx::{MySqlPool, Row};
+use uuid::Uuid;
+
+/// modal_leases repository
+pub struct LeaseStore {
+    pool: MySqlPool,
+}
+
+impl LeaseStore {
+    pub fn new(pool: MySqlPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn insert(&self, lease: &ModalLease) -> crate::Result<()> {
+        sqlx::query(
+            "REPLACE INTO modal_leases \
+             (lease_id, user_id, camera_id, quality, requested_quality, role, cost, \
+              created_at, expires_at, last_heartbeat) \
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(lease.lease_id.to_string())
+        .bind(&lease.user_id)
+        .bind(&lease.camera_id)
+        .bind(lease.quality.as_str())
+        .bind(lease.requested_quality.as_str())
+        .bind(lease.role.as_str())
+        .bind(lease.cost)
+        .bind(lease.created_at)
+        .bind(lease.expires_at)
+        .bind(lease.last_heartbeat)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn heartbeat(&self, lease: &ModalLease) -> crate::Result<()> {
+        sqlx::query("UPDATE modal_leases SET expires_at = ?, last_heartbeat = ? WHERE lease_id = ?")
+            .bind(lease.expires_at)
+            .bind(lease.last_heartbeat)
+            .bind(lease.lease_id.to_string())
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn delete(&self, lease_ids: &[Uuid]) -> crate::Result<()> {
+        for id in lease_ids {
+            sqlx::query("DELETE FROM modal_leases WHERE lease_id = ?")
+                .bind(id.to_string())
+                .execute(&self.pool)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Load unexpired leases and purge expired rows
+    pub async fn load_active(&self, now: DateTime<Utc>) -> crate::Result<Vec<ModalLease>> {
+        sqlx::query("DELETE FROM modal_leases WHERE expires_at <= ?")
+            .bind(now)
+            .execute(&self.pool)
+            .await?;
+
+        let rows = sqlx::query(
+            "SELECT lease_id, user_id, camera_id, quality, requested_quality, role, cost, \
+             created_at, expires_at, last_heartbeat FROM modal_leases ORDER BY created_at",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| {
+                let lease_id: String = row.get("lease_id");
+                let quality: String = row.get("quality");
+                let requested_quality: String = row.get("requested_quality");
+                let role: String = row.get("role");
+                Some(ModalLease {
+                    lease_id: Uuid::parse_str(&lease_id).ok()?,
+                    user_id: row.get("user_id"),
+                    camera_id: row.get("camera_id"),
+                    quality: StreamQuality::parse(&quality)?,
+                    requested_quality: StreamQuality::parse(&requested_quality)?,
+                    role: UserRole::parse(&role).unwrap_or_default(),
+                    cost: row.get("cost"),
+                    created_at: row.get("created_at"),
+                    expires_at: row.get("expires_at"),
+                    last_heartbeat: row.get("last_heartbeat"),
+                })
+            })
+            .collect())
+    }
+}
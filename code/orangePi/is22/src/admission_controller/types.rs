@@ -18,6 +18,59 @@ impl Default for StreamQuality {
     }
 }
 
+impl StreamQuality {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Sub => "sub",
+            Self::Main => "main",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "sub" => Some(Self::Sub),
+            "main" => Some(Self::Main),
+            _ => None,
+        }
+    }
+}
+
+/// User role (queue priority)
+///
+/// 認証基盤がないためクライアント申告値をそのまま使用する
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum UserRole {
+    #[default]
+    Viewer,
+    Operator,
+}
+
+impl UserRole {
+    /// Queue priority (higher = served first)
+    pub fn priority(&self) -> u8 {
+        match self {
+            Self::Viewer => 0,
+            Self::Operator => 1,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Viewer => "viewer",
+            Self::Operator => "operator",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "viewer" => Some(Self::Viewer),
+            "operator" => Some(Self::Operator),
+            _ => None,
+        }
+    }
+}
+
 /// Lease request
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LeaseRequest {
@@ -25,6 +78,11 @@ pub struct LeaseRequest {
     pub camera_id: String,
     #[serde(default)]
     pub quality: StreamQuality,
+    #[serde(default)]
+    pub role: UserRole,
+    /// 空きがない場合に待機キューへ入る（false なら従来通り即時拒否）
+    #[serde(default)]
+    pub queue: bool,
 }
 
 /// Modal lease
@@ -33,7 +91,10 @@ pub struct ModalLease {
     pub lease_id: Uuid,
     pub user_id: String,
     pub camera_id: String,
+    /// Allowed quality (may be downgraded from requested_quality)
     pub quality: StreamQuality,
+    pub requested_quality: StreamQuality,
+    pub role: UserRole,
     pub cost: u32,
     pub created_at: DateTime<Utc>,
     pub expires_at: DateTime<Utc>,
@@ -43,17 +104,54 @@ pub struct ModalLease {
 /// Admission result
 #[derive(Debug, Clone)]
 pub enum AdmissionResult {
-    Admitted(u32), // cost
+    Admitted(u32, StreamQuality), // cost, allowed quality
     Rejected(RejectReason),
 }
 
 /// Rejection reason
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum RejectReason {
     SystemOverload,
     UserAlreadyHasModal,
     OverCapacity,
+    /// Per-camera lease limit reached
+    CameraBusy,
+    QueueFull,
+}
+
+/// Result of a lease request
+#[derive(Debug, Clone)]
+pub enum LeaseGrant {
+    Granted(ModalLease),
+    Queued(QueueTicket),
+}
+
+/// Queue ticket status
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QueueStatus {
+    Waiting,
+    /// Lease issued; client fetches it via the ticket
+    Admitted,
+}
+
+/// Waiting queue ticket
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueueTicket {
+    pub ticket_id: Uuid,
+    pub user_id: String,
+    pub camera_id: String,
+    pub quality: StreamQuality,
+    pub role: UserRole,
+    pub status: QueueStatus,
+    /// 待機理由（OVER_CAPACITY = 全体待ち / CAMERA_BUSY = カメラ待ち）
+    pub waiting_for: RejectReason,
+    /// 1-based position among waiting tickets (None once admitted)
+    pub position: Option<usize>,
+    pub enqueued_at: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+    pub lease: Option<ModalLease>,
 }
 
 /// Heartbeat response
@@ -68,6 +166,8 @@ pub struct HeartbeatResponse {
 pub struct LeaseResponse {
     pub lease_id: Uuid,
     pub allowed_quality: StreamQuality,
+    /// Main was requested but only sub was allowed
+    pub downgraded: bool,
     pub expires_at: DateTime<Utc>,
     pub stream_url: String,
 }
@@ -80,5 +180,6 @@ pub struct AdmissionStatus {
     pub memory_percent: f32,
     pub active_modals: i32,
     pub modal_budget_remaining: i32,
+    pub queue_waiting: usize,
     pub suggest_active: bool,
 }
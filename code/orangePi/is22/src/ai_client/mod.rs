@@ -60,7 +60,7 @@ impl Default for AnalyzeRequest {
 }
 
 /// Camera context for hints
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct CameraContext {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub location_type: Option<String>,
@@ -0,0 +1,340 @@
+//! AuditLog - 変更系 API の監査証跡
+//!
+//! ## 概要
+//! - POST/PUT/PATCH/DELETE の各リクエストを操作者・時刻・接続元IP・対象・変更前後の差分とともに記録
+//! - 秘匿情報（password, token 等のキーと URL 内の認証情報）は保存前にマスク
+//! - 各エントリは直前エントリの entry_hash を prev_hash に持つハッシュチェーンで、
+//!   `verify_chain` で改ざん・削除・挿入を検出できる
+//!
+//! 記録は web_api の監査ミドルウェアから行う。記録失敗はリクエストを失敗させない。
+
+pub mod types;
+
+pub use types::*;
+
+use crate::error::Result;
+use chrono::{SubsecRound, Utc};
+use serde_json::Value;
+use sqlx::mysql::MySqlRow;
+use sqlx::{Column, MySqlPool, Row, TypeInfo};
+use tokio::sync::Mutex;
+
+/// 変更前後のスナップショットを取得できる対象 (target_type, table, key column)
+const SNAPSHOT_SOURCES: &[(&str, &str, &str)] = &[
+    ("camera", "cameras", "camera_id"),
+    ("subnet", "scan_subnets", "subnet_id"),
+    ("credential", "facility_credentials", "fid"),
+    ("device", "ipcamscan_devices", "ip"),
+    ("camera_brand", "camera_brands", "id"),
+    ("oui", "oui_entries", "oui_prefix"),
+    ("rtsp_template", "rtsp_templates", "id"),
+    ("generic_rtsp_path", "generic_rtsp_paths", "id"),
+];
+
+/// 検証時に一度に読み込む行数
+const VERIFY_PAGE_SIZE: u32 = 1000;
+
+/// 監査ログサービス
+pub struct AuditLogService {
+    pool: MySqlPool,
+    /// チェーン末尾の entry_hash（None = 未読込）。記録の直列化も兼ねる
+    head: Mutex<Option<String>>,
+}
+
+impl AuditLogService {
+    pub fn new(pool: MySqlPool) -> Self {
+        Self {
+            pool,
+            head: Mutex::new(None),
+        }
+    }
+
+    const COLUMNS: &'static str = "audit_id, occurred_at, actor, source_ip, method, path, action, \
+        target_type, target_id, status_code, request_json, before_json, after_json, diff_json, \
+        prev_hash, entry_hash";
+
+    fn row_to_entry(row: &MySqlRow) -> Result<AuditEntry> {
+        Ok(AuditEntry {
+            audit_id: row.try_get("audit_id")?,
+            occurred_at: row.try_get("occurred_at")?,
+            actor: row.try_get("actor")?,
+            source_ip: row.try_get("source_ip")?,
+            method: row.try_get("method")?,
+            path: row.try_get("path")?,
+            action: row.try_get("action")?,
+            target_type: row.try_get("target_type")?,
+            target_id: row.try_get("target_id")?,
+            status_code: row.try_get("status_code")?,
+            request_json: row.try_get("request_json")?,
+            before_json: row.try_get("before_json")?,
+            after_json: row.try_get("after_json")?,
+            diff_json: row.try_get("diff_json")?,
+            prev_hash: row.try_get("prev_hash")?,
+            entry_hash: row.try_get("entry_hash")?,
+        })
+    }
+
+    /// スナップショット対象なら (table, key column)
+    pub fn snapshot_source(target_type: &str) -> Option<(&'static str, &'static str)> {
+        SNAPSHOT_SOURCES
+            .iter()
+            .find(|(t, _, _)| *t == target_type)
+            .map(|(_, table, key)| (*table, *key))
+    }
+
+    /// 対象行を JSON で取得（秘匿情報はマスク済み）
+    pub async fn snapshot(&self, target_type: &str, target_id: &str) -> Result<Option<Value>> {
+        let Some((table, key)) = Self::snapshot_source(target_type) else {
+            return Ok(None);
+        };
+        let row = sqlx::query(&format!("SELECT * FROM {} WHERE {} = ?", table, key))
+            .bind(target_id)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(row.map(|row| {
+            let mut value = row_to_json(&row);
+            mask_secrets(&mut value);
+            value
+        }))
+    }
+
+    /// エントリを記録しチェーンに連結
+    pub async fn record(&self, new: NewAuditEntry) -> Result<AuditEntry> {
+        let masked = |value: Option<Value>| {
+            value.map(|mut v| {
+                mask_secrets(&mut v);
+                v
+            })
+        };
+        let request = masked(new.request);
+        let before = masked(new.before);
+        let after = masked(new.after);
+        let diff = match (&before, &after) {
+            (None, None) => None,
+            (b, a) => Some(Value::Object(json_diff(
+                b.as_ref().unwrap_or(&Value::Null),
+                a.as_ref().unwrap_or(&Value::Null),
+            ))),
+        };
+        let to_text = |value: &Option<Value>| value.as_ref().map(|v| v.to_string());
+
+        let mut head = self.head.lock().await;
+        let prev_hash = match head.as_ref() {
+            Some(hash) => hash.clone(),
+            None => self.load_head().await?,
+        };
+
+        let mut entry = AuditEntry {
+            audit_id: 0,
+            occurred_at: Utc::now().trunc_subsecs(3),
+            actor: new.actor,
+            source_ip: new.source_ip,
+            method: new.method,
+            path: new.path,
+            action: new.action,
+            target_type: new.target_type,
+            target_id: new.target_id,
+            status_code: new.status_code,
+            request_json: to_text(&request),
+            before_json: to_text(&before),
+            after_json: to_text(&after),
+            diff_json: to_text(&diff),
+            prev_hash: prev_hash.clone(),
+            entry_hash: String::new(),
+        };
+        entry.entry_hash = entry.compute_hash(&prev_hash);
+
+        let result = sqlx::query(
+            "INSERT INTO audit_log \
+             (occurred_at, actor, source_ip, method, path, action, target_type, target_id, status_code, \
+              request_json, before_json, after_json, diff_json, prev_hash, entry_hash) \
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(entry.occurred_at)
+        .bind(&entry.actor)
+        .bind(&entry.source_ip)
+        .bind(&entry.method)
+        .bind(&entry.path)
+        .bind(&entry.action)
+        .bind(&entry.target_type)
+        .bind(&entry.target_id)
+        .bind(entry.status_code)
+        .bind(&entry.request_json)
+        .bind(&entry.before_json)
+        .bind(&entry.after_json)
+        .bind(&entry.diff_json)
+        .bind(&entry.prev_hash)
+        .bind(&entry.entry_hash)
+        .execute(&self.pool)
+        .await;
+
+        match result {
+            Ok(result) => {
+                entry.audit_id = result.last_insert_id();
+                *head = Some(entry.entry_hash.clone());
+                Ok(entry)
+            }
+            Err(e) => {
+                // 末尾が不明になったので次回 DB から読み直す
+                *head = None;
+                Err(e.into())
+            }
+        }
+    }
+
+    async fn load_head(&self) -> Result<String> {
+        let hash: Option<String> =
+            sqlx::query_scalar("SELECT entry_hash FROM audit_log ORDER BY audit_id DESC LIMIT 1")
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(hash.unwrap_or_else(|| GENESIS_HASH.to_string()))
+    }
+
+    /// 新しい順に検索
+    pub async fn query(&self, query: &AuditQuery) -> Result<Vec<AuditEntry>> {
+        let mut conditions: Vec<&str> = Vec::new();
+        let mut binds: Vec<String> = Vec::new();
+        let filters = [
+            ("actor = ?", query.actor.clone()),
+            ("target_type = ?", query.target_type.clone()),
+            ("target_id = ?", query.target_id.clone()),
+            ("action LIKE ?", query.action.as_ref().map(|a| format!("{}%", a.replace('%', "\\%")))),
+            ("occurred_at >= ?", query.since.map(|t| t.format("%Y-%m-%d %H:%M:%S%.3f").to_string())),
+            ("occurred_at < ?", query.until.map(|t| t.format("%Y-%m-%d %H:%M:%S%.3f").to_string())),
+        ];
+        for (condition, value) in filters {
+            if let Some(value) = value.filter(|v| !v.is_empty()) {
+                conditions.push(condition);
+                binds.push(value);
+            }
+        }
+        let where_clause = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        };
+        let sql = format!(
+            "SELECT {} FROM audit_log {} ORDER BY audit_id DESC LIMIT {} OFFSET {}",
+            Self::COLUMNS,
+            where_clause,
+            query.limit(),
+            query.offset.unwrap_or(0)
+        );
+        let mut q = sqlx::query(&sql);
+        for value in &binds {
+            q = q.bind(value);
+        }
+        let rows = q.fetch_all(&self.pool).await?;
+        rows.iter().map(Self::row_to_entry).collect()
+    }
+
+    /// チェーン全体を先頭から検証
+    pub async fn verify_chain(&self) -> Result<ChainVerification> {
+        let mut prev = GENESIS_HASH.to_string();
+        let mut last_id = 0u64;
+        let mut checked = 0u64;
+        loop {
+            let rows = sqlx::query(&format!(
+                "SELECT {} FROM audit_log WHERE audit_id > ? ORDER BY audit_id LIMIT {}",
+                Self::COLUMNS,
+                VERIFY_PAGE_SIZE
+            ))
+            .bind(last_id)
+            .fetch_all(&self.pool)
+            .await?;
+            if rows.is_empty() {
+                break;
+            }
+            let entries = rows.iter().map(Self::row_to_entry).collect::<Result<Vec<_>>>()?;
+            match verify_entries(&prev, &entries) {
+                Ok(head) => prev = head,
+                Err((audit_id, reason)) => {
+                    let position = entries.iter().position(|e| e.audit_id == audit_id).unwrap_or(0);
+                    return Ok(ChainVerification {
+                        checked_at: Utc::now(),
+                        entries_checked: checked + position as u64 + 1,
+                        valid: false,
+                        first_broken_id: Some(audit_id),
+                        reason: Some(reason),
+                        head_hash: prev,
+                    });
+                }
+            }
+            checked += entries.len() as u64;
+            last_id = entries.last().map(|e| e.audit_id).unwrap_or(last_id);
+        }
+        Ok(ChainVerification {
+            checked_at: Utc::now(),
+            entries_checked: checked,
+            valid: true,
+            first_broken_id: None,
+            reason: None,
+            head_hash: prev,
+        })
+    }
+}
+
+/// 任意の行を列名 → 値の JSON に変換（型はカラム型名で判定、変換できない列は null）
+fn row_to_json(row: &MySqlRow) -> Value {
+    let mut map = serde_json::Map::new();
+    for column in row.columns() {
+        let i = column.ordinal();
+        let type_name = column.type_info().name().to_ascii_uppercase();
+        let value = if type_name.contains("UNSIGNED") {
+            row.try_get::<Option<u64>, _>(i).ok().flatten().map(Value::from)
+        } else if type_name == "BOOLEAN" {
+            row.try_get::<Option<bool>, _>(i).ok().flatten().map(Value::from)
+        } else if type_name.contains("INT") {
+            row.try_get::<Option<i64>, _>(i).ok().flatten().map(Value::from)
+        } else if type_name == "FLOAT" || type_name == "DOUBLE" {
+            row.try_get::<Option<f64>, _>(i).ok().flatten().map(Value::from)
+        } else if type_name == "DATETIME" || type_name == "TIMESTAMP" {
+            row.try_get::<Option<chrono::DateTime<Utc>>, _>(i)
+                .ok()
+                .flatten()
+                .map(|t| Value::from(t.to_rfc3339()))
+        } else if type_name == "DATE" {
+            row.try_get::<Option<chrono::NaiveDate>, _>(i)
+                .ok()
+                .flatten()
+                .map(|d| Value::from(d.to_string()))
+        } else if type_name == "JSON" {
+            row.try_get::<Option<Value>, _>(i).ok().flatten()
+        } else if type_name.contains("BLOB") || type_name.contains("BINARY") {
+            row.try_get::<Option<Vec<u8>>, _>(i)
+                .ok()
+                .flatten()
+                .map(|b| Value::from(format!("<{} bytes>", b.len())))
+        } else {
+            row.try_get::<Option<String>, _>(i).ok().flatten().map(Value::from)
+        };
+        map.insert(column.name().to_string(), value.unwrap_or(Value::Null));
+    }
+    Value::Object(map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_sources_cover_classified_targets() {
+        for (route, path) in [
+            ("/api/cameras/:id", "/api/cameras/cam-1"),
+            ("/api/subnets/:id", "/api/subnets/1"),
+            ("/api/credentials/:id", "/api/credentials/0150"),
+            ("/api/ipcamscan/devices/:ip/approve", "/api/ipcamscan/devices/10.0.0.5/approve"),
+            ("/api/settings/oui/:prefix", "/api/settings/oui/AA:BB:CC"),
+            ("/api/settings/rtsp-templates/:id", "/api/settings/rtsp-templates/4"),
+        ] {
+            let (target_type, target_id) = classify_target(route, path);
+            assert!(target_id.is_some(), "{}", route);
+            assert!(
+                AuditLogService::snapshot_source(&target_type.unwrap()).is_some(),
+                "{} has no snapshot source",
+                route
+            );
+        }
+        assert!(AuditLogService::snapshot_source("register").is_none());
+    }
+}
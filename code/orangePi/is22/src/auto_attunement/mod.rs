@@ -7,10 +7,25 @@
 //! - 誤検知率に基づく閾値調整
 //! - カメラ別の最適閾値計算
 //! - 閾値変更履歴の記録
+//! - クローズドループ調整: 候補値の試行 → A/B評価 → 採用/ロールバック
+//!   (conf_override / nms_threshold / par_threshold)
+//!
+//! 信頼度閾値の保存先は cameras.conf_override のみ（ポーリングが推論に渡す値）。
+//! camera_thresholds は自動調整フラグと調整範囲（min/max）だけを持つ。
+
+mod trial;
+
+pub use trial::{
+    evaluate_trial, propose_candidate, TrialCandidate, TrialDecision, TrialGoal,
+    TuningParameter, WindowMetrics,
+};
 
-use sqlx::{MySql, Pool};
+use chrono::{DateTime, Duration, Utc};
+use sqlx::{MySql, Pool, Row};
 use std::sync::Arc;
+use crate::config_store::AttunementPolicy;
 use crate::inference_stats_service::InferenceStatsService;
+use crate::preset_loader::PresetLoader;
 use crate::Result;
 
 /// 閾値調整計算結果
@@ -37,6 +52,36 @@ pub struct CameraAttunementStats {
     pub current_threshold: f32,
 }
 
+/// カメラ別閾値設定（GET/PUT /api/cameras/:id/threshold）
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ThresholdSettings {
+    pub camera_id: String,
+    /// 有効な信頼度閾値（cameras.conf_override → プリセット → 既定値）
+    pub conf_threshold: f32,
+    /// cameras.conf_override（None = プリセット値を使用）
+    pub conf_override: Option<f32>,
+    pub min_threshold: f32,
+    pub max_threshold: f32,
+    pub auto_adjust_enabled: bool,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// 閾値変更の理由 (threshold_change_history.change_reason)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThresholdChangeReason {
+    Manual,
+    AutoAdjust,
+}
+
+impl ThresholdChangeReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Manual => "manual",
+            Self::AutoAdjust => "auto_adjust",
+        }
+    }
+}
+
 /// 誤検知報告データ
 #[derive(Debug, Clone)]
 pub struct FeedbackStats {
@@ -46,17 +91,86 @@ pub struct FeedbackStats {
     pub false_negative_count: i64,
 }
 
+/// Trial status
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrialStatus {
+    Running,
+    Promoted,
+    RolledBack,
+    /// Parameter changed manually during the trial (no rollback)
+    Aborted,
+}
+
+impl TrialStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Running => "running",
+            Self::Promoted => "promoted",
+            Self::RolledBack => "rolled_back",
+            Self::Aborted => "aborted",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "running" => Some(Self::Running),
+            "promoted" => Some(Self::Promoted),
+            "rolled_back" => Some(Self::RolledBack),
+            "aborted" => Some(Self::Aborted),
+            _ => None,
+        }
+    }
+}
+
+/// 閾値試行レコード (attunement_trials)
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct AttunementTrial {
+    pub trial_id: u64,
+    pub camera_id: String,
+    pub parameter: TuningParameter,
+    pub goal: TrialGoal,
+    pub baseline_value: Option<f32>,
+    pub effective_baseline: f32,
+    pub candidate_value: f32,
+    pub status: TrialStatus,
+    pub baseline_metrics: WindowMetrics,
+    pub trial_metrics: Option<WindowMetrics>,
+    pub decision_reason: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+/// 1サイクルで発生したイベント
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct AttunementCycleEvent {
+    pub camera_id: String,
+    pub trial_id: u64,
+    pub parameter: TuningParameter,
+    pub status: TrialStatus,
+    /// Value in effect after this event
+    pub value: Option<f32>,
+    pub reason: String,
+}
+
 /// Auto Attunement Service
 pub struct AutoAttunementService {
     pool: Pool<MySql>,
     inference_stats: Arc<InferenceStatsService>,
+    preset_loader: Arc<PresetLoader>,
 }
 
 impl AutoAttunementService {
-    pub fn new(pool: Pool<MySql>, inference_stats: Arc<InferenceStatsService>) -> Self {
+    pub fn new(
+        pool: Pool<MySql>,
+        inference_stats: Arc<InferenceStatsService>,
+        preset_loader: Arc<PresetLoader>,
+    ) -> Self {
         Self {
             pool,
             inference_stats,
+            preset_loader,
         }
     }
 
@@ -81,15 +195,10 @@ impl AutoAttunementService {
 
         let (total, detection, unknown, avg_conf) = stats.unwrap_or((0, 0, 0, 0.0));
 
-        // camera_thresholdsから現在の閾値を取得
-        let threshold: Option<(f32,)> = sqlx::query_as(
-            "SELECT conf_threshold FROM camera_thresholds WHERE camera_id = ?",
-        )
-        .bind(camera_id)
-        .fetch_optional(&self.pool)
-        .await?;
-
-        let current_threshold = threshold.map(|t| t.0).unwrap_or(0.5);
+        // 現在推論に使われている閾値（cameras.conf_override → プリセット）
+        let (_, current_threshold) = self
+            .effective_parameter(camera_id, TuningParameter::ConfOverride)
+            .await?;
 
         Ok(CameraAttunementStats {
             camera_id: camera_id.to_string(),
@@ -181,53 +290,147 @@ impl AutoAttunementService {
 
     /// 閾値を適用
     pub async fn apply_threshold(&self, camera_id: &str, new_threshold: f32) -> Result<()> {
-        // 範囲チェック
-        let new_threshold = new_threshold.clamp(0.2, 0.8);
+        let (min, max) = self.threshold_bounds(camera_id).await?;
+        self.write_conf_threshold(
+            camera_id,
+            new_threshold.clamp(min, max),
+            ThresholdChangeReason::AutoAdjust,
+        )
+        .await
+    }
 
-        // 現在の閾値を取得
-        let old_threshold: Option<(f32,)> = sqlx::query_as(
-            "SELECT conf_threshold FROM camera_thresholds WHERE camera_id = ?",
+    /// カメラ別閾値設定を取得
+    pub async fn get_threshold_settings(&self, camera_id: &str) -> Result<ThresholdSettings> {
+        let (conf_override, conf_threshold) = self
+            .effective_parameter(camera_id, TuningParameter::ConfOverride)
+            .await?;
+        let row: Option<(f32, f32, bool, DateTime<Utc>)> = sqlx::query_as(
+            r#"
+            SELECT CAST(min_threshold AS FLOAT), CAST(max_threshold AS FLOAT),
+                   auto_adjust_enabled, updated_at
+            FROM camera_thresholds
+            WHERE camera_id = ?
+            "#,
         )
         .bind(camera_id)
         .fetch_optional(&self.pool)
         .await?;
 
-        // camera_thresholdsを更新（UPSERT）
+        let (min_threshold, max_threshold, auto_adjust_enabled, updated_at) = match row {
+            Some((min, max, enabled, updated_at)) => (min, max, enabled, Some(updated_at)),
+            None => (0.2, 0.8, false, None),
+        };
+        Ok(ThresholdSettings {
+            camera_id: camera_id.to_string(),
+            conf_threshold,
+            conf_override,
+            min_threshold,
+            max_threshold,
+            auto_adjust_enabled,
+            updated_at,
+        })
+    }
+
+    /// カメラ別閾値設定を更新（手動）
+    ///
+    /// 閾値は cameras.conf_override へ書き込み、自動調整フラグは camera_thresholds に保存する
+    pub async fn update_threshold_settings(
+        &self,
+        camera_id: &str,
+        conf_threshold: f32,
+        auto_adjust_enabled: Option<bool>,
+    ) -> Result<()> {
+        let (min, max) = self.threshold_bounds(camera_id).await?;
+        if !(min..=max).contains(&conf_threshold) {
+            return Err(crate::Error::Validation(format!(
+                "conf_threshold must be between {} and {}",
+                min, max
+            )));
+        }
+
+        self.write_conf_threshold(camera_id, conf_threshold, ThresholdChangeReason::Manual)
+            .await?;
+
+        if let Some(enabled) = auto_adjust_enabled {
+            sqlx::query(
+                r#"
+                INSERT INTO camera_thresholds (camera_id, auto_adjust_enabled)
+                VALUES (?, ?)
+                ON DUPLICATE KEY UPDATE
+                    auto_adjust_enabled = VALUES(auto_adjust_enabled),
+                    updated_at = NOW(3)
+                "#,
+            )
+            .bind(camera_id)
+            .bind(enabled)
+            .execute(&self.pool)
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// cameras.conf_override を更新し、変更履歴を記録
+    async fn write_conf_threshold(
+        &self,
+        camera_id: &str,
+        new_threshold: f32,
+        reason: ThresholdChangeReason,
+    ) -> Result<()> {
+        let (_, old_threshold) = self
+            .effective_parameter(camera_id, TuningParameter::ConfOverride)
+            .await?;
+
+        self.set_camera_parameter(camera_id, TuningParameter::ConfOverride, Some(new_threshold))
+            .await?;
+        self.record_threshold_change(camera_id, Some(old_threshold), new_threshold, reason)
+            .await?;
+
+        tracing::info!(
+            camera_id = %camera_id,
+            old = %old_threshold,
+            new = %new_threshold,
+            reason = reason.as_str(),
+            "Confidence threshold updated"
+        );
+        Ok(())
+    }
+
+    /// threshold_change_history に1件記録
+    async fn record_threshold_change(
+        &self,
+        camera_id: &str,
+        old_threshold: Option<f32>,
+        new_threshold: f32,
+        reason: ThresholdChangeReason,
+    ) -> Result<()> {
         sqlx::query(
             r#"
-            INSERT INTO camera_thresholds (camera_id, conf_threshold, updated_at)
-            VALUES (?, ?, NOW(3))
-            ON DUPLICATE KEY UPDATE
-                conf_threshold = VALUES(conf_threshold),
-                updated_at = NOW(3)
+            INSERT INTO threshold_change_history (camera_id, old_threshold, new_threshold, change_reason, created_at)
+            VALUES (?, ?, ?, ?, NOW(3))
             "#,
         )
         .bind(camera_id)
+        .bind(old_threshold)
         .bind(new_threshold)
+        .bind(reason.as_str())
         .execute(&self.pool)
         .await?;
+        Ok(())
+    }
 
-        // 変更履歴を記録
-        sqlx::query(
+    /// 調整範囲 (min_threshold, max_threshold)。未設定なら 0.2-0.8
+    async fn threshold_bounds(&self, camera_id: &str) -> Result<(f32, f32)> {
+        let row: Option<(f32, f32)> = sqlx::query_as(
             r#"
-            INSERT INTO threshold_change_history (camera_id, old_threshold, new_threshold, change_reason, created_at)
-            VALUES (?, ?, ?, 'auto_adjust', NOW(3))
+            SELECT CAST(min_threshold AS FLOAT), CAST(max_threshold AS FLOAT)
+            FROM camera_thresholds
+            WHERE camera_id = ?
             "#,
         )
         .bind(camera_id)
-        .bind(old_threshold.map(|t| t.0))
-        .bind(new_threshold)
-        .execute(&self.pool)
+        .fetch_optional(&self.pool)
         .await?;
-
-        tracing::info!(
-            camera_id = %camera_id,
-            old = ?old_threshold.map(|t| t.0),
-            new = %new_threshold,
-            "Threshold updated via auto-attunement"
-        );
-
-        Ok(())
+        Ok(row.unwrap_or((0.2, 0.8)))
     }
 
     /// 全カメラの調整状態を取得
@@ -256,41 +459,403 @@ impl AutoAttunementService {
         Ok(results)
     }
 
-    /// 自動調整が有効なカメラに対して閾値を自動適用
-    pub async fn run_auto_adjustment(&self) -> Result<Vec<AttunementResult>> {
+    /// 自動調整が有効なカメラに対してクローズドループ調整を1サイクル実行
+    ///
+    /// 候補値を即時適用せず試行（trial）として開始し、試行窓の経過後に
+    /// ベースラインと比較して採用またはロールバックする
+    pub async fn run_auto_adjustment(
+        &self,
+        policy: &AttunementPolicy,
+    ) -> Result<Vec<AttunementCycleEvent>> {
         // auto_adjust_enabled = true のカメラを取得
         let enabled_cameras: Vec<(String,)> = sqlx::query_as(
-            "SELECT camera_id FROM camera_thresholds WHERE auto_adjust_enabled = TRUE",
+            r#"
+            SELECT ct.camera_id
+            FROM camera_thresholds ct
+            JOIN cameras c ON c.camera_id = ct.camera_id
+            WHERE ct.auto_adjust_enabled = TRUE AND c.deleted_at IS NULL
+            "#,
         )
         .fetch_all(&self.pool)
         .await?;
 
-        let mut results = Vec::new();
+        let now = Utc::now();
+        let mut events = Vec::new();
         for (camera_id,) in enabled_cameras {
-            match self.calculate_optimal_threshold(&camera_id).await {
-                Ok(result) => {
-                    // 調整が必要な場合のみ適用
-                    if result.adjustment.abs() > 0.001 {
-                        if let Err(e) = self.apply_threshold(&camera_id, result.recommended_threshold).await {
-                            tracing::error!(camera_id = %camera_id, error = %e, "Failed to apply threshold");
-                        } else {
-                            tracing::info!(
-                                camera_id = %camera_id,
-                                old = %result.current_threshold,
-                                new = %result.recommended_threshold,
-                                "Auto-adjusted threshold"
-                            );
-                        }
-                    }
-                    results.push(result);
+            match self.step_camera(&camera_id, policy, now).await {
+                Ok(Some(event)) => {
+                    tracing::info!(
+                        camera_id = %event.camera_id,
+                        trial_id = event.trial_id,
+                        parameter = event.parameter.column(),
+                        status = event.status.as_str(),
+                        value = ?event.value,
+                        reason = %event.reason,
+                        "Attunement trial event"
+                    );
+                    events.push(event);
                 }
+                Ok(None) => {}
                 Err(e) => {
-                    tracing::warn!(camera_id = %camera_id, error = %e, "Failed to calculate attunement");
+                    tracing::warn!(camera_id = %camera_id, error = %e, "Attunement step failed");
                 }
             }
         }
 
-        Ok(results)
+        Ok(events)
+    }
+
+    /// Advance one camera: evaluate a finished trial or start a new one
+    async fn step_camera(
+        &self,
+        camera_id: &str,
+        policy: &AttunementPolicy,
+        now: DateTime<Utc>,
+    ) -> Result<Option<AttunementCycleEvent>> {
+        let last_trial = self.list_trials(Some(camera_id), 1).await?.into_iter().next();
+
+        if let Some(trial) = last_trial.as_ref().filter(|t| t.status == TrialStatus::Running) {
+            if now < trial.ends_at {
+                return Ok(None);
+            }
+            return self.finish_trial(trial, policy, now).await.map(Some);
+        }
+
+        // クールダウン中は新規試行しない
+        if let Some(finished_at) = last_trial.as_ref().and_then(|t| t.finished_at) {
+            if now - finished_at < Duration::hours(policy.cooldown_hours as i64) {
+                return Ok(None);
+            }
+        }
+
+        let baseline = self
+            .window_metrics(
+                camera_id,
+                now - Duration::hours(policy.baseline_window_hours as i64),
+                now,
+            )
+            .await?;
+
+        // 前回と異なるパラメータから順に候補を探す（ラウンドロビン）
+        let start = last_trial
+            .as_ref()
+            .and_then(|t| TuningParameter::ALL.iter().position(|p| *p == t.parameter))
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        let (preset_id, values) = self.camera_parameters(camera_id).await?;
+
+        for offset in 0..TuningParameter::ALL.len() {
+            let parameter = TuningParameter::ALL[(start + offset) % TuningParameter::ALL.len()];
+            if !parameter.enabled(policy) {
+                continue;
+            }
+            let column_value = values[parameter as usize];
+            let effective = self.resolve_parameter(&preset_id, parameter, column_value);
+
+            if let Some(candidate) = propose_candidate(parameter, effective, &baseline, policy) {
+                let trial_id = self
+                    .start_trial(camera_id, &candidate, column_value, effective, &baseline, policy, now)
+                    .await?;
+                return Ok(Some(AttunementCycleEvent {
+                    camera_id: camera_id.to_string(),
+                    trial_id,
+                    parameter,
+                    status: TrialStatus::Running,
+                    value: Some(candidate.candidate_value),
+                    reason: format!(
+                        "{:?}: {:.3} → {:.3}",
+                        candidate.goal, effective, candidate.candidate_value
+                    ),
+                }));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Evaluate a trial whose window elapsed and promote / roll back
+    async fn finish_trial(
+        &self,
+        trial: &AttunementTrial,
+        policy: &AttunementPolicy,
+        now: DateTime<Utc>,
+    ) -> Result<AttunementCycleEvent> {
+        let (_, values) = self.camera_parameters(&trial.camera_id).await?;
+        let current = values[trial.parameter as usize];
+
+        // 試行中に手動変更された場合は上書きしない
+        let manually_changed = current
+            .map(|v| (v - trial.candidate_value).abs() > 0.001)
+            .unwrap_or(true);
+
+        let trial_metrics = self
+            .window_metrics(&trial.camera_id, trial.started_at, now)
+            .await?;
+
+        let (status, value, reason) = if manually_changed {
+            (
+                TrialStatus::Aborted,
+                current,
+                "試行中に手動で変更されたため中止".to_string(),
+            )
+        } else {
+            match evaluate_trial(trial.goal, &trial.baseline_metrics, &trial_metrics, policy) {
+                TrialDecision::Promote(reason) => {
+                    (TrialStatus::Promoted, Some(trial.candidate_value), reason)
+                }
+                TrialDecision::Rollback(reason) => {
+                    self.set_camera_parameter(&trial.camera_id, trial.parameter, trial.baseline_value)
+                        .await?;
+                    if trial.parameter == TuningParameter::ConfOverride {
+                        self.record_threshold_change(
+                            &trial.camera_id,
+                            Some(trial.candidate_value),
+                            trial.effective_baseline,
+                            ThresholdChangeReason::AutoAdjust,
+                        )
+                        .await?;
+                    }
+                    (TrialStatus::RolledBack, trial.baseline_value, reason)
+                }
+            }
+        };
+
+        sqlx::query(
+            r#"
+            UPDATE attunement_trials
+            SET status = ?, trial_metrics = CAST(? AS JSON), decision_reason = ?, finished_at = ?
+            WHERE trial_id = ?
+            "#,
+        )
+        .bind(status.as_str())
+        .bind(serde_json::to_string(&trial_metrics)?)
+        .bind(&reason)
+        .bind(now)
+        .bind(trial.trial_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(AttunementCycleEvent {
+            camera_id: trial.camera_id.clone(),
+            trial_id: trial.trial_id,
+            parameter: trial.parameter,
+            status,
+            value,
+            reason,
+        })
+    }
+
+    /// Apply candidate value and record a running trial
+    #[allow(clippy::too_many_arguments)]
+    async fn start_trial(
+        &self,
+        camera_id: &str,
+        candidate: &TrialCandidate,
+        baseline_value: Option<f32>,
+        effective_baseline: f32,
+        baseline: &WindowMetrics,
+        policy: &AttunementPolicy,
+        now: DateTime<Utc>,
+    ) -> Result<u64> {
+        self.set_camera_parameter(camera_id, candidate.parameter, Some(candidate.candidate_value))
+            .await?;
+        if candidate.parameter == TuningParameter::ConfOverride {
+            self.record_threshold_change(
+                camera_id,
+                Some(effective_baseline),
+                candidate.candidate_value,
+                ThresholdChangeReason::AutoAdjust,
+            )
+            .await?;
+        }
+
+        let goal = serde_json::to_value(candidate.goal)?;
+        let result = sqlx::query(
+            r#"
+            INSERT INTO attunement_trials
+                (camera_id, parameter, goal, baseline_value, effective_baseline, candidate_value,
+                 status, baseline_metrics, started_at, ends_at)
+            VALUES (?, ?, ?, ?, ?, ?, 'running', CAST(? AS JSON), ?, ?)
+            "#,
+        )
+        .bind(camera_id)
+        .bind(candidate.parameter.column())
+        .bind(goal.as_str().unwrap_or_default())
+        .bind(baseline_value)
+        .bind(effective_baseline)
+        .bind(candidate.candidate_value)
+        .bind(serde_json::to_string(baseline)?)
+        .bind(now)
+        .bind(now + Duration::hours(policy.trial_window_hours as i64))
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.last_insert_id())
+    }
+
+    /// detection_logs / misdetection_feedbacks から窓内の品質指標を集計
+    pub async fn window_metrics(
+        &self,
+        camera_id: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<WindowMetrics> {
+        let (detections, unknown): (i64, i64) = sqlx::query_as(
+            r#"
+            SELECT
+                COUNT(*) as detections,
+                CAST(COALESCE(SUM(CASE WHEN primary_event = 'unknown' THEN 1 ELSE 0 END), 0) AS SIGNED) as unknown_count
+            FROM detection_logs
+            WHERE camera_id = ? AND captured_at >= ? AND captured_at < ?
+            "#,
+        )
+        .bind(camera_id)
+        .bind(from)
+        .bind(to)
+        .fetch_one(&self.pool)
+        .await?;
+
+        // 誤検知は検知時刻で窓に帰属させる（フィードバックは後から届く）
+        let (false_positives,): (i64,) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*)
+            FROM misdetection_feedbacks mf
+            JOIN detection_logs dl ON mf.log_id = dl.log_id
+            WHERE dl.camera_id = ? AND dl.captured_at >= ? AND dl.captured_at < ?
+              AND mf.correct_label = 'none' AND mf.reported_label != 'none'
+            "#,
+        )
+        .bind(camera_id)
+        .bind(from)
+        .bind(to)
+        .fetch_one(&self.pool)
+        .await?;
+
+        let hours = (to - from).num_seconds() as f64 / 3600.0;
+        Ok(WindowMetrics::new(hours, detections, unknown, false_positives))
+    }
+
+    /// (cameras column, effective value) for one parameter
+    pub async fn effective_parameter(
+        &self,
+        camera_id: &str,
+        parameter: TuningParameter,
+    ) -> Result<(Option<f32>, f32)> {
+        let (preset_id, values) = self.camera_parameters(camera_id).await?;
+        let column_value = values[parameter as usize];
+        Ok((column_value, self.resolve_parameter(&preset_id, parameter, column_value)))
+    }
+
+    /// Column value → preset value → built-in default
+    fn resolve_parameter(
+        &self,
+        preset_id: &str,
+        parameter: TuningParameter,
+        column_value: Option<f32>,
+    ) -> f32 {
+        let preset = self.preset_loader.get_or_default(preset_id);
+        let preset_value = match parameter {
+            TuningParameter::ConfOverride => preset.conf_override,
+            TuningParameter::NmsThreshold => preset.nms_threshold,
+            TuningParameter::ParThreshold => preset.par_threshold,
+        };
+        column_value
+            .or(preset_value)
+            .unwrap_or_else(|| parameter.default_value())
+    }
+
+    /// preset_id and [conf_override, nms_threshold, par_threshold] columns
+    async fn camera_parameters(&self, camera_id: &str) -> Result<(String, [Option<f32>; 3])> {
+        type CameraParamRow = (Option<String>, Option<f32>, Option<f32>, Option<f32>);
+        let row: Option<CameraParamRow> = sqlx::query_as(
+            "SELECT preset_id, conf_override, nms_threshold, par_threshold FROM cameras WHERE camera_id = ?",
+        )
+        .bind(camera_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let (preset_id, conf, nms, par) = row.ok_or_else(|| {
+            crate::Error::NotFound(format!("Camera {} not found", camera_id))
+        })?;
+        Ok((
+            preset_id.unwrap_or_else(|| "balanced".to_string()),
+            [conf, nms, par],
+        ))
+    }
+
+    /// Write cameras.<parameter> (None = back to preset default)
+    async fn set_camera_parameter(
+        &self,
+        camera_id: &str,
+        parameter: TuningParameter,
+        value: Option<f32>,
+    ) -> Result<()> {
+        // column() は固定値のみを返すため埋め込みは安全
+        sqlx::query(&format!(
+            "UPDATE cameras SET {} = ?, updated_at = NOW(3) WHERE camera_id = ?",
+            parameter.column()
+        ))
+        .bind(value)
+        .bind(camera_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// 試行履歴を取得（新しい順）
+    pub async fn list_trials(
+        &self,
+        camera_id: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<AttunementTrial>> {
+        let mut sql = String::from(
+            r#"
+            SELECT trial_id, camera_id, parameter, goal, baseline_value, effective_baseline,
+                   candidate_value, status, baseline_metrics, trial_metrics, decision_reason,
+                   started_at, ends_at, finished_at
+            FROM attunement_trials
+            "#,
+        );
+        if camera_id.is_some() {
+            sql.push_str(" WHERE camera_id = ?");
+        }
+        sql.push_str(" ORDER BY started_at DESC, trial_id DESC LIMIT ?");
+
+        let mut query = sqlx::query(&sql);
+        if let Some(camera_id) = camera_id {
+            query = query.bind(camera_id);
+        }
+        let rows = query.bind(limit).fetch_all(&self.pool).await?;
+
+        rows.iter()
+            .map(|row| {
+                let parameter: String = row.try_get("parameter")?;
+                let goal: String = row.try_get("goal")?;
+                let status: String = row.try_get("status")?;
+                let baseline_metrics: serde_json::Value = row.try_get("baseline_metrics")?;
+                let trial_metrics: Option<serde_json::Value> = row.try_get("trial_metrics")?;
+                Ok(AttunementTrial {
+                    trial_id: row.try_get("trial_id")?,
+                    camera_id: row.try_get("camera_id")?,
+                    parameter: TuningParameter::parse(&parameter).ok_or_else(|| {
+                        crate::Error::Internal(format!("Unknown tuning parameter: {}", parameter))
+                    })?,
+                    goal: serde_json::from_value(serde_json::Value::String(goal))?,
+                    baseline_value: row.try_get("baseline_value")?,
+                    effective_baseline: row.try_get("effective_baseline")?,
+                    candidate_value: row.try_get("candidate_value")?,
+                    status: TrialStatus::parse(&status).ok_or_else(|| {
+                        crate::Error::Internal(format!("Unknown trial status: {}", status))
+                    })?,
+                    baseline_metrics: serde_json::from_value(baseline_metrics)?,
+                    trial_metrics: trial_metrics.map(serde_json::from_value).transpose()?,
+                    decision_reason: row.try_get("decision_reason")?,
+                    started_at: row.try_get("started_at")?,
+                    ends_at: row.try_get("ends_at")?,
+                    finished_at: row.try_get("finished_at")?,
+                })
+            })
+            .collect()
     }
 }
 
@@ -0,0 +1,346 @@
+//! Attunement Trial - candidate proposal and A/B evaluation
+//!
+//! ## フロー
+//!
+//! 1. ベースライン窓（baseline_window_hours）の指標から候補値を提案
+//! 2. 候補値を cameras.<param> に適用し trial_window_hours の間試行
+//! 3. 試行窓の指標をベースラインと比較し promote / rollback を決定
+//!
+//! detection_logs は「何もない」推論を保存しないため、検出量は
+//! 検出件数/時間（detections_per_hour）で比較する。
+
+use crate::config_store::AttunementPolicy;
+use serde::{Deserialize, Serialize};
+
+/// Tunable threshold parameter (cameras column)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TuningParameter {
+    ConfOverride,
+    NmsThreshold,
+    ParThreshold,
+}
+
+impl TuningParameter {
+    pub const ALL: [TuningParameter; 3] = [
+        TuningParameter::ConfOverride,
+        TuningParameter::NmsThreshold,
+        TuningParameter::ParThreshold,
+    ];
+
+    /// cameras column name
+    pub fn column(&self) -> &'static str {
+        match self {
+            Self::ConfOverride => "conf_override",
+            Self::NmsThreshold => "nms_threshold",
+            Self::ParThreshold => "par_threshold",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "conf_override" => Some(Self::ConfOverride),
+            "nms_threshold" => Some(Self::NmsThreshold),
+            "par_threshold" => Some(Self::ParThreshold),
+            _ => None,
+        }
+    }
+
+    /// Allowed range (same as CameraContext docs)
+    pub fn range(&self) -> (f32, f32) {
+        match self {
+            Self::ConfOverride => (0.2, 0.8),
+            Self::NmsThreshold => (0.3, 0.6),
+            Self::ParThreshold => (0.3, 0.8),
+        }
+    }
+
+    /// Value used when neither camera nor preset sets the parameter
+    pub fn default_value(&self) -> f32 {
+        match self {
+            Self::ConfOverride => 0.5,
+            Self::NmsThreshold => 0.45,
+            Self::ParThreshold => 0.5,
+        }
+    }
+
+    /// Sign of the step that reduces false positives
+    ///
+    /// conf/par: 上げると厳しくなる、nms: 下げると重複ボックスが減る
+    pub fn strict_direction(&self) -> f32 {
+        match self {
+            Self::ConfOverride | Self::ParThreshold => 1.0,
+            Self::NmsThreshold => -1.0,
+        }
+    }
+
+    pub fn step(&self, policy: &AttunementPolicy) -> f32 {
+        match self {
+            Self::ConfOverride => policy.conf_step,
+            Self::NmsThreshold => policy.nms_step,
+            Self::ParThreshold => policy.par_step,
+        }
+    }
+
+    pub fn enabled(&self, policy: &AttunementPolicy) -> bool {
+        match self {
+            Self::ConfOverride => policy.tune_conf,
+            Self::NmsThreshold => policy.tune_nms,
+            Self::ParThreshold => policy.tune_par,
+        }
+    }
+}
+
+/// Aggregated detection quality for a time window
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct WindowMetrics {
+    pub hours: f64,
+    pub detections: i64,
+    pub unknown_count: i64,
+    pub false_positive_count: i64,
+    pub detections_per_hour: f64,
+    pub unknown_rate: f64,
+    pub false_positive_rate: f64,
+}
+
+impl WindowMetrics {
+    pub fn new(hours: f64, detections: i64, unknown_count: i64, false_positive_count: i64) -> Self {
+        let ratio = |n: i64| {
+            if detections > 0 {
+                n as f64 / detections as f64
+            } else {
+                0.0
+            }
+        };
+        Self {
+            hours,
+            detections,
+            unknown_count,
+            false_positive_count,
+            detections_per_hour: if hours > 0.0 { detections as f64 / hours } else { 0.0 },
+            unknown_rate: ratio(unknown_count),
+            false_positive_rate: ratio(false_positive_count),
+        }
+    }
+}
+
+/// Trial goal (decides which metric must improve)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrialGoal {
+    /// Reduce false positives (stricter thresholds)
+    ReduceFalsePositives,
+    /// Reduce unknown results (looser thresholds)
+    ReduceUnknown,
+}
+
+/// Proposed trial
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrialCandidate {
+    pub parameter: TuningParameter,
+    pub goal: TrialGoal,
+    pub candidate_value: f32,
+}
+
+/// Propose a candidate for one parameter from baseline metrics
+pub fn propose_candidate(
+    parameter: TuningParameter,
+    current_value: f32,
+    baseline: &WindowMetrics,
+    policy: &AttunementPolicy,
+) -> Option<TrialCandidate> {
+    if baseline.detections < policy.min_detections {
+        return None;
+    }
+
+    let goal = if baseline.false_positive_rate > policy.target_false_positive_rate as f64 {
+        TrialGoal::ReduceFalsePositives
+    } else if baseline.false_positive_rate < (policy.target_false_positive_rate as f64) / 10.0
+        && baseline.unknown_rate > policy.target_unknown_rate as f64
+    {
+        TrialGoal::ReduceUnknown
+    } else {
+        return None;
+    };
+
+    let direction = match goal {
+        TrialGoal::ReduceFalsePositives => parameter.strict_direction(),
+        TrialGoal::ReduceUnknown => -parameter.strict_direction(),
+    };
+    let (min, max) = parameter.range();
+    let candidate = (current_value + direction * parameter.step(policy)).clamp(min, max);
+    // 丸め（DB FLOAT での比較誤差対策）
+    let candidate = (candidate * 1000.0).round() / 1000.0;
+
+    if (candidate - current_value).abs() < 0.001 {
+        // Already at the range limit
+        return None;
+    }
+
+    Some(TrialCandidate {
+        parameter,
+        goal,
+        candidate_value: candidate,
+    })
+}
+
+/// Trial evaluation outcome
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "decision", content = "reason", rename_all = "snake_case")]
+pub enum TrialDecision {
+    Promote(String),
+    Rollback(String),
+}
+
+/// Compare trial window against baseline
+pub fn evaluate_trial(
+    goal: TrialGoal,
+    baseline: &WindowMetrics,
+    trial: &WindowMetrics,
+    policy: &AttunementPolicy,
+) -> TrialDecision {
+    let tolerance = policy.rate_tolerance as f64;
+
+    if trial.detections < policy.min_detections {
+        return TrialDecision::Rollback(format!(
+            "検出数不足で判定不能 ({} < {})",
+            trial.detections, policy.min_detections
+        ));
+    }
+
+    if baseline.detections_per_hour > 0.0 {
+        let drop = 1.0 - trial.detections_per_hour / baseline.detections_per_hour;
+        if drop > policy.max_volume_drop as f64 {
+            return TrialDecision::Rollback(format!(
+                "検出量が{:.0}%減少（許容{:.0}%）- 見逃しの恐れ",
+                drop * 100.0,
+                policy.max_volume_drop * 100.0
+            ));
+        }
+    }
+
+    if trial.false_positive_rate > baseline.false_positive_rate + tolerance {
+        return TrialDecision::Rollback(format!(
+            "誤検知率が悪化 ({:.1}% → {:.1}%)",
+            baseline.false_positive_rate * 100.0,
+            trial.false_positive_rate * 100.0
+        ));
+    }
+
+    if trial.unknown_rate > baseline.unknown_rate + tolerance {
+        return TrialDecision::Rollback(format!(
+            "unknown率が悪化 ({:.1}% → {:.1}%)",
+            baseline.unknown_rate * 100.0,
+            trial.unknown_rate * 100.0
+        ));
+    }
+
+    match goal {
+        TrialGoal::ReduceFalsePositives if trial.false_positive_rate < baseline.false_positive_rate => {
+            TrialDecision::Promote(format!(
+                "誤検知率が改善 ({:.1}% → {:.1}%)",
+                baseline.false_positive_rate * 100.0,
+                trial.false_positive_rate * 100.0
+            ))
+        }
+        TrialGoal::ReduceUnknown if trial.unknown_rate < baseline.unknown_rate => {
+            TrialDecision::Promote(format!(
+                "unknown率が改善 ({:.1}% → {:.1}%)",
+                baseline.unknown_rate * 100.0,
+                trial.unknown_rate * 100.0
+            ))
+        }
+        _ => TrialDecision::Rollback("改善が見られないため元に戻す".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> AttunementPolicy {
+        AttunementPolicy {
+            min_detections: 10,
+            ..AttunementPolicy::default()
+        }
+    }
+
+    #[test]
+    fn test_window_metrics_rates() {
+        let m = WindowMetrics::new(10.0, 100, 20, 5);
+        assert!((m.detections_per_hour - 10.0).abs() < 1e-9);
+        assert!((m.unknown_rate - 0.2).abs() < 1e-9);
+        assert!((m.false_positive_rate - 0.05).abs() < 1e-9);
+        assert_eq!(WindowMetrics::new(0.0, 0, 0, 0).false_positive_rate, 0.0);
+    }
+
+    #[test]
+    fn test_propose_candidate_directions() {
+        let p = policy();
+        let high_fp = WindowMetrics::new(24.0, 100, 0, 20);
+
+        let conf = propose_candidate(TuningParameter::ConfOverride, 0.5, &high_fp, &p).unwrap();
+        assert_eq!(conf.goal, TrialGoal::ReduceFalsePositives);
+        assert!((conf.candidate_value - 0.55).abs() < 1e-6);
+
+        // NMS tightens downward
+        let nms = propose_candidate(TuningParameter::NmsThreshold, 0.45, &high_fp, &p).unwrap();
+        assert!((nms.candidate_value - 0.40).abs() < 1e-6);
+
+        // Low FP + high unknown → loosen conf
+        let high_unknown = WindowMetrics::new(24.0, 100, 30, 0);
+        let conf = propose_candidate(TuningParameter::ConfOverride, 0.5, &high_unknown, &p).unwrap();
+        assert_eq!(conf.goal, TrialGoal::ReduceUnknown);
+        assert!((conf.candidate_value - 0.45).abs() < 1e-6);
+
+        // At range limit → no trial
+        assert!(propose_candidate(TuningParameter::ConfOverride, 0.8, &high_fp, &p).is_none());
+        // Healthy camera → no trial
+        let healthy = WindowMetrics::new(24.0, 100, 2, 5);
+        assert!(propose_candidate(TuningParameter::ConfOverride, 0.5, &healthy, &p).is_none());
+        // Not enough data
+        let sparse = WindowMetrics::new(24.0, 5, 0, 3);
+        assert!(propose_candidate(TuningParameter::ConfOverride, 0.5, &sparse, &p).is_none());
+    }
+
+    #[test]
+    fn test_evaluate_trial() {
+        let p = policy();
+        let baseline = WindowMetrics::new(24.0, 240, 10, 48); // FP 20%
+
+        // FP improved, volume stable → promote
+        let improved = WindowMetrics::new(24.0, 200, 8, 20);
+        assert!(matches!(
+            evaluate_trial(TrialGoal::ReduceFalsePositives, &baseline, &improved, &p),
+            TrialDecision::Promote(_)
+        ));
+
+        // Volume collapsed → rollback even though FP improved
+        let collapsed = WindowMetrics::new(24.0, 60, 2, 1);
+        assert!(matches!(
+            evaluate_trial(TrialGoal::ReduceFalsePositives, &baseline, &collapsed, &p),
+            TrialDecision::Rollback(_)
+        ));
+
+        // No improvement → rollback
+        let same = WindowMetrics::new(24.0, 240, 10, 48);
+        assert!(matches!(
+            evaluate_trial(TrialGoal::ReduceFalsePositives, &baseline, &same, &p),
+            TrialDecision::Rollback(_)
+        ));
+
+        // Unknown worsened → rollback
+        let unknown_up = WindowMetrics::new(24.0, 240, 40, 20);
+        assert!(matches!(
+            evaluate_trial(TrialGoal::ReduceFalsePositives, &baseline, &unknown_up, &p),
+            TrialDecision::Rollback(_)
+        ));
+
+        // Inconclusive → rollback
+        let sparse = WindowMetrics::new(24.0, 3, 0, 0);
+        assert!(matches!(
+            evaluate_trial(TrialGoal::ReduceFalsePositives, &baseline, &sparse, &p),
+            TrialDecision::Rollback(_)
+        ));
+    }
+}
@@ -0,0 +1,429 @@
+//! CameraAvailability - カメラ稼働履歴とSLA指標
+//!
+//! ## 概要
+//! CameraStatusTracker はメモリ上の現在状態しか持たないため、
+//! online/offline の遷移を区間（camera_availability_intervals）として永続化する。
+//!
+//! - 遷移時: 継続中の区間を閉じ、新しい区間を開く（オフライン原因付き）
+//! - IP追随時: LostCamTracker が継続中のオフライン区間の原因を `ip_relocated` に更新
+//! - 起動時: 継続中の区間から CameraStatusTracker の状態を復元
+//! - 集計: 任意範囲の稼働率・MTTR・MTBF・原因別内訳
+
+pub mod types;
+
+pub use types::*;
+
+use crate::camera_status_tracker::{CameraConnectionStatus, CameraStatusTracker};
+use chrono::{DateTime, Utc};
+use sqlx::{MySqlPool, Row};
+use std::collections::BTreeMap;
+
+/// カメラ稼働履歴サービス
+pub struct CameraAvailabilityService {
+    pool: MySqlPool,
+}
+
+impl CameraAvailabilityService {
+    pub fn new(pool: MySqlPool) -> Self {
+        Self { pool }
+    }
+
+    /// 状態遷移を記録
+    ///
+    /// 継続中の区間が同じ状態なら何もしない（false）。
+    /// offline 以外では cause / detail は無視する。
+    pub async fn record_transition(
+        &self,
+        camera_id: &str,
+        state: AvailabilityState,
+        cause: Option<OutageCause>,
+        detail: Option<&str>,
+        at: DateTime<Utc>,
+    ) -> crate::Result<bool> {
+        let mut tx = self.pool.begin().await?;
+
+        let open: Option<String> = sqlx::query_scalar(
+            r#"
+            SELECT state FROM camera_availability_intervals
+            WHERE camera_id = ? AND ended_at IS NULL
+            ORDER BY started_at DESC
+            LIMIT 1
+            FOR UPDATE
+            "#,
+        )
+        .bind(camera_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        if open.as_deref().map(AvailabilityState::from) == Some(state) {
+            tx.rollback().await?;
+            return Ok(false);
+        }
+
+        sqlx::query(
+            r#"
+            UPDATE camera_availability_intervals
+            SET ended_at = GREATEST(started_at, ?)
+            WHERE camera_id = ? AND ended_at IS NULL
+            "#,
+        )
+        .bind(at)
+        .bind(camera_id)
+        .execute(&mut *tx)
+        .await?;
+
+        let (cause, detail) = match state {
+            AvailabilityState::Offline => (
+                Some(cause.unwrap_or(OutageCause::Unreachable).as_str()),
+                detail,
+            ),
+            AvailabilityState::Online => (None, None),
+        };
+
+        sqlx::query(
+            r#"
+            INSERT INTO camera_availability_intervals
+                (camera_id, state, cause, detail, started_at)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(camera_id)
+        .bind(state.as_str())
+        .bind(cause)
+        .bind(detail)
+        .bind(at)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    /// 継続中のオフライン区間の原因を更新（IP追随など後から判明した原因）
+    pub async fn annotate_open_outage(
+        &self,
+        camera_id: &str,
+        cause: OutageCause,
+        detail: Option<&str>,
+    ) -> crate::Result<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE camera_availability_intervals
+            SET cause = ?, detail = COALESCE(?, detail)
+            WHERE camera_id = ? AND state = 'offline' AND ended_at IS NULL
+            "#,
+        )
+        .bind(cause.as_str())
+        .bind(detail)
+        .bind(camera_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// 各カメラの現在状態（継続中の区間）
+    pub async fn current_states(&self) -> crate::Result<Vec<(String, AvailabilityState)>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT camera_id, state FROM camera_availability_intervals
+            WHERE ended_at IS NULL
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| {
+                let camera_id: String = row.get("camera_id");
+                let state: String = row.get("state");
+                (camera_id, AvailabilityState::from(state.as_str()))
+            })
+            .collect())
+    }
+
+    /// DBの継続中区間から CameraStatusTracker を復元
+    ///
+    /// 復元しない場合、再起動直後は全カメラ Unknown から始まり
+    /// 停止前からオフラインのカメラの復旧を検知できない
+    pub async fn restore_tracker(&self, tracker: &CameraStatusTracker) -> crate::Result<usize> {
+        let states = self.current_states().await?;
+        let count = states.len();
+        for (camera_id, state) in states {
+            let status = match state {
+                AvailabilityState::Online => CameraConnectionStatus::Online,
+                AvailabilityState::Offline => CameraConnectionStatus::Offline,
+            };
+            tracker.restore_status(&camera_id, status).await;
+        }
+        Ok(count)
+    }
+
+    /// 範囲に重なる区間を取得
+    pub async fn intervals(
+        &self,
+        camera_id: Option<&str>,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> crate::Result<Vec<AvailabilityInterval>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT interval_id, camera_id, state, cause, detail, started_at, ended_at
+            FROM camera_availability_intervals
+            WHERE started_at < ?
+              AND (ended_at IS NULL OR ended_at > ?)
+              AND (? IS NULL OR camera_id = ?)
+            ORDER BY camera_id, started_at
+            "#,
+        )
+        .bind(to)
+        .bind(from)
+        .bind(camera_id)
+        .bind(camera_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| {
+                let state: String = row.get("state");
+                let cause: Option<String> = row.get("cause");
+                AvailabilityInterval {
+                    interval_id: row.get("interval_id"),
+                    camera_id: row.get("camera_id"),
+                    state: AvailabilityState::from(state.as_str()),
+                    cause: cause.as_deref().map(OutageCause::from),
+                    detail: row.get("detail"),
+                    started_at: row.get("started_at"),
+                    ended_at: row.get("ended_at"),
+                }
+            })
+            .collect())
+    }
+
+    /// 稼働レポート作成
+    pub async fn report(
+        &self,
+        camera_id: Option<&str>,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        include_timeline: bool,
+    ) -> crate::Result<AvailabilityReport> {
+        if from >= to {
+            return Err(crate::Error::Validation(
+                "'from' must be earlier than 'to'".to_string(),
+            ));
+        }
+
+        let intervals = self.intervals(camera_id, from, to).await?;
+        let mut report = build_report(&intervals, from, to, Utc::now());
+        if include_timeline {
+            report.timeline = Some(intervals);
+        }
+        Ok(report)
+    }
+}
+
+/// 区間リストからレポートを組み立て（カメラ別 + 全体）
+pub fn build_report(
+    intervals: &[AvailabilityInterval],
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> AvailabilityReport {
+    let mut by_camera: BTreeMap<&str, Vec<&AvailabilityInterval>> = BTreeMap::new();
+    for interval in intervals {
+        by_camera.entry(&interval.camera_id).or_default().push(interval);
+    }
+
+    let cameras: Vec<CameraAvailabilityMetrics> = by_camera
+        .into_iter()
+        .map(|(camera_id, intervals)| compute_metrics(camera_id, &intervals, from, to, now))
+        .collect();
+
+    let online: i64 = cameras.iter().map(|c| c.online_secs).sum();
+    let offline: i64 = cameras.iter().map(|c| c.offline_secs).sum();
+    let total_failures = cameras.iter().map(|c| c.failures).sum();
+
+    // 全体MTTRは復旧件数で重み付け
+    let (repair_secs, repairs) = cameras
+        .iter()
+        .filter_map(|c| c.mttr_secs.map(|mttr| (mttr, c.recoveries)))
+        .fold((0.0, 0u32), |(secs, n), (mttr, count)| {
+            (secs + mttr * count as f64, n + count)
+        });
+
+    AvailabilityReport {
+        from,
+        to,
+        overall_uptime_pct: uptime_pct(online, offline),
+        total_failures,
+        overall_mttr_secs: (repairs > 0).then(|| repair_secs / repairs as f64),
+        cameras,
+        timeline: None,
+    }
+}
+
+/// 1カメラの区間から稼働指標を算出
+///
+/// 区間は範囲 [from, to) にクリップして集計する。
+/// MTTR は範囲内に復旧したオフライン区間の全長で算出する（範囲外の開始分も含む）。
+pub fn compute_metrics(
+    camera_id: &str,
+    intervals: &[&AvailabilityInterval],
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> CameraAvailabilityMetrics {
+    let range_end = to.min(now).max(from);
+    let mut online_secs = 0i64;
+    let mut offline_secs = 0i64;
+    let mut failures = 0u32;
+    let mut repair_total = 0i64;
+    let mut repairs = 0u32;
+    let mut causes: BTreeMap<OutageCause, OutageCauseStats> = BTreeMap::new();
+    let mut current_state = None;
+
+    for interval in intervals {
+        let end = interval.ended_at.unwrap_or(now);
+        let start = interval.started_at.max(from);
+        let clipped = (end.min(range_end) - start).num_seconds().max(0);
+
+        if interval.ended_at.is_none() {
+            current_state = Some(interval.state);
+        }
+
+        match interval.state {
+            AvailabilityState::Online => online_secs += clipped,
+            AvailabilityState::Offline => {
+                offline_secs += clipped;
+                let stats = causes
+                    .entry(interval.cause.unwrap_or(OutageCause::Unreachable))
+                    .or_default();
+                stats.offline_secs += clipped;
+
+                if interval.started_at >= from && interval.started_at < to {
+                    failures += 1;
+                    stats.count += 1;
+                }
+                if let Some(ended_at) = interval.ended_at {
+                    if ended_at > from && ended_at <= to {
+                        repair_total += (ended_at - interval.started_at).num_seconds().max(0);
+                        repairs += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    let observed = (range_end - from).num_seconds().max(0);
+
+    CameraAvailabilityMetrics {
+        camera_id: camera_id.to_string(),
+        online_secs,
+        offline_secs,
+        unknown_secs: (observed - online_secs - offline_secs).max(0),
+        uptime_pct: uptime_pct(online_secs, offline_secs),
+        failures,
+        recoveries: repairs,
+        mttr_secs: (repairs > 0).then(|| repair_total as f64 / repairs as f64),
+        mtbf_secs: (failures > 0).then(|| online_secs as f64 / failures as f64),
+        current_state,
+        causes,
+    }
+}
+
+fn uptime_pct(online_secs: i64, offline_secs: i64) -> Option<f64> {
+    let monitored = online_secs + offline_secs;
+    (monitored > 0).then(|| online_secs as f64 * 100.0 / monitored as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    fn interval(
+        state: AvailabilityState,
+        cause: Option<OutageCause>,
+        start_h: i64,
+        end_h: Option<i64>,
+    ) -> AvailabilityInterval {
+        let base = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        AvailabilityInterval {
+            interval_id: 0,
+            camera_id: "cam1".to_string(),
+            state,
+            cause,
+            detail: None,
+            started_at: base + Duration::hours(start_h),
+            ended_at: end_h.map(|h| base + Duration::hours(h)),
+        }
+    }
+
+    #[test]
+    fn test_classify_cause() {
+        assert_eq!(
+            OutageCause::classify("Internal error: ffmpeg timeout (10s)"),
+            OutageCause::SnapshotTimeout
+        );
+        assert_eq!(
+            OutageCause::classify("RTSP busy for camera cam1: lock held"),
+            OutageCause::RtspBusy
+        );
+        assert_eq!(
+            OutageCause::classify("Snapshot HTTP error: 401 Unauthorized"),
+            OutageCause::AuthFailure
+        );
+        assert_eq!(
+            OutageCause::classify("Connection refused"),
+            OutageCause::Unreachable
+        );
+    }
+
+    #[test]
+    fn test_compute_metrics_clipped() {
+        use AvailabilityState::*;
+        let base = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        // 範囲 2h〜12h: online(0-4) offline(4-6, timeout) online(6-10) offline(10-, auth)
+        let intervals = [
+            interval(Online, None, 0, Some(4)),
+            interval(Offline, Some(OutageCause::SnapshotTimeout), 4, Some(6)),
+            interval(Online, None, 6, Some(10)),
+            interval(Offline, Some(OutageCause::AuthFailure), 10, None),
+        ];
+        let refs: Vec<&AvailabilityInterval> = intervals.iter().collect();
+        let metrics = compute_metrics(
+            "cam1",
+            &refs,
+            base + Duration::hours(2),
+            base + Duration::hours(12),
+            base + Duration::hours(12),
+        );
+
+        assert_eq!(metrics.online_secs, 6 * 3600);
+        assert_eq!(metrics.offline_secs, 4 * 3600);
+        assert_eq!(metrics.unknown_secs, 0);
+        assert_eq!(metrics.uptime_pct, Some(60.0));
+        assert_eq!(metrics.failures, 2);
+        assert_eq!(metrics.mttr_secs, Some(2.0 * 3600.0));
+        assert_eq!(metrics.mtbf_secs, Some(3.0 * 3600.0));
+        assert_eq!(metrics.current_state, Some(Offline));
+        assert_eq!(metrics.causes[&OutageCause::AuthFailure].offline_secs, 2 * 3600);
+    }
+
+    #[test]
+    fn test_build_report_unknown_gap() {
+        use AvailabilityState::*;
+        let base = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        // 0-2h は記録なし（サーバー停止中）
+        let intervals = [interval(Online, None, 2, None)];
+        let report = build_report(&intervals, base, base + Duration::hours(4), base + Duration::hours(4));
+
+        assert_eq!(report.cameras.len(), 1);
+        assert_eq!(report.cameras[0].unknown_secs, 2 * 3600);
+        assert_eq!(report.overall_uptime_pct, Some(100.0));
+        assert_eq!(report.overall_mttr_secs, None);
+    }
+}
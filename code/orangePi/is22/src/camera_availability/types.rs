@@ -0,0 +1,174 @@
+//! Camera availability types
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// 区間の接続状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AvailabilityState {
+    Online,
+    Offline,
+}
+
+impl AvailabilityState {
+    pub fn from_online(is_online: bool) -> Self {
+        if is_online {
+            Self::Online
+        } else {
+            Self::Offline
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Online => "online",
+            Self::Offline => "offline",
+        }
+    }
+}
+
+impl From<&str> for AvailabilityState {
+    fn from(s: &str) -> Self {
+        match s {
+            "online" => Self::Online,
+            _ => Self::Offline,
+        }
+    }
+}
+
+/// オフライン原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutageCause {
+    /// スナップショット取得タイムアウト（ffmpeg / HTTP）
+    SnapshotTimeout,
+    /// RTSP使用中（RtspManagerロック / AccessAbsorber拒否）
+    RtspBusy,
+    /// 認証失敗（401/403）
+    AuthFailure,
+    /// LostCamTrackerによるIP追随（DHCP再割当）
+    IpRelocated,
+    /// 上記以外の到達不能
+    Unreachable,
+}
+
+impl OutageCause {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::SnapshotTimeout => "snapshot_timeout",
+            Self::RtspBusy => "rtsp_busy",
+            Self::AuthFailure => "auth_failure",
+            Self::IpRelocated => "ip_relocated",
+            Self::Unreachable => "unreachable",
+        }
+    }
+
+    /// ポーリングエラーから原因を分類
+    pub fn from_error(error: &crate::Error) -> Self {
+        match error {
+            crate::Error::AccessAbsorber { .. } => Self::RtspBusy,
+            other => Self::classify(&other.to_string()),
+        }
+    }
+
+    /// エラーメッセージから原因を分類
+    ///
+    /// SnapshotService / PollingOrchestrator のエラー文言に依存する
+    pub fn classify(message: &str) -> Self {
+        let lower = message.to_lowercase();
+        if lower.contains("rtsp busy") || lower.contains("access denied") {
+            Self::RtspBusy
+        } else if lower.contains("401")
+            || lower.contains("403")
+            || lower.contains("unauthorized")
+            || lower.contains("forbidden")
+            || lower.contains("authentication")
+        {
+            Self::AuthFailure
+        } else if lower.contains("timeout") || lower.contains("timed out") {
+            Self::SnapshotTimeout
+        } else {
+            Self::Unreachable
+        }
+    }
+}
+
+impl From<&str> for OutageCause {
+    fn from(s: &str) -> Self {
+        match s {
+            "snapshot_timeout" => Self::SnapshotTimeout,
+            "rtsp_busy" => Self::RtspBusy,
+            "auth_failure" => Self::AuthFailure,
+            "ip_relocated" => Self::IpRelocated,
+            _ => Self::Unreachable,
+        }
+    }
+}
+
+impl std::fmt::Display for OutageCause {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// 稼働状態区間（camera_availability_intervals）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AvailabilityInterval {
+    pub interval_id: u64,
+    pub camera_id: String,
+    pub state: AvailabilityState,
+    pub cause: Option<OutageCause>,
+    pub detail: Option<String>,
+    pub started_at: DateTime<Utc>,
+    /// None = 継続中
+    pub ended_at: Option<DateTime<Utc>>,
+}
+
+/// 原因別のオフライン集計
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OutageCauseStats {
+    pub count: u32,
+    pub offline_secs: i64,
+}
+
+/// カメラ単位の稼働指標
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CameraAvailabilityMetrics {
+    pub camera_id: String,
+    /// 範囲内のonline秒数
+    pub online_secs: i64,
+    /// 範囲内のoffline秒数
+    pub offline_secs: i64,
+    /// 記録のない秒数（サーバー停止中・登録前）
+    pub unknown_secs: i64,
+    /// online / (online + offline)。監視時間がなければ None
+    pub uptime_pct: Option<f64>,
+    /// 範囲内に開始したオフライン回数
+    pub failures: u32,
+    /// 範囲内に復旧したオフライン回数
+    pub recoveries: u32,
+    /// 範囲内に復旧したオフライン区間の平均長（秒）
+    pub mttr_secs: Option<f64>,
+    /// 平均故障間隔（online秒 / failures）
+    pub mtbf_secs: Option<f64>,
+    pub current_state: Option<AvailabilityState>,
+    pub causes: BTreeMap<OutageCause, OutageCauseStats>,
+}
+
+/// /api/stats/availability レスポンス
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AvailabilityReport {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    /// 全カメラ合算の稼働率
+    pub overall_uptime_pct: Option<f64>,
+    pub total_failures: u32,
+    /// 全カメラ合算のMTTR（秒）
+    pub overall_mttr_secs: Option<f64>,
+    pub cameras: Vec<CameraAvailabilityMetrics>,
+    /// include_timeline=true の場合のみ
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeline: Option<Vec<AvailabilityInterval>>,
+}
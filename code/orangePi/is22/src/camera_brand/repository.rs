@@ -3,19 +3,20 @@
 //! Database access layer for camera brands, OUI entries, and RTSP templates.
 
 use super::types::*;
+use crate::db::{Db, InsertId};
 use crate::error::{Error, Result};
-use sqlx::MySqlPool;
+use crate::with_db;
 
 /// Camera brand repository for database operations
 #[derive(Clone)]
 pub struct CameraBrandRepository {
-    pool: MySqlPool,
+    db: Db,
 }
 
 impl CameraBrandRepository {
     /// Create new repository
-    pub fn new(pool: MySqlPool) -> Self {
-        Self { pool }
+    pub fn new(db: impl Into<Db>) -> Self {
+        Self { db: db.into() }
     }
 
     // ========================================================================
@@ -24,41 +25,49 @@ impl CameraBrandRepository {
 
     /// Get all brands
     pub async fn get_all_brands(&self) -> Result<Vec<CameraBrand>> {
-        let brands = sqlx::query_as::<_, CameraBrand>(
-            r#"SELECT id, name, display_name, category, is_builtin, created_at, updated_at
-               FROM camera_brands
-               ORDER BY is_builtin DESC, display_name"#,
-        )
-        .fetch_all(&self.pool)
-        .await?;
+        let brands = with_db!(&self.db, pool => {
+            sqlx::query_as::<_, CameraBrand>(
+                r#"SELECT id, name, display_name, category, is_builtin, created_at, updated_at
+                   FROM camera_brands
+                   ORDER BY is_builtin DESC, display_name"#,
+            )
+            .fetch_all(pool)
+            .await
+        })?;
         Ok(brands)
     }
 
     /// Get all brands with counts
     pub async fn get_all_brands_with_counts(&self) -> Result<Vec<CameraBrandWithCounts>> {
-        let rows = sqlx::query_as::<_, CameraBrand>(
-            r#"SELECT id, name, display_name, category, is_builtin, created_at, updated_at
-               FROM camera_brands
-               ORDER BY is_builtin DESC, display_name"#,
-        )
-        .fetch_all(&self.pool)
-        .await?;
+        let rows = with_db!(&self.db, pool => {
+            sqlx::query_as::<_, CameraBrand>(
+                r#"SELECT id, name, display_name, category, is_builtin, created_at, updated_at
+                   FROM camera_brands
+                   ORDER BY is_builtin DESC, display_name"#,
+            )
+            .fetch_all(pool)
+            .await
+        })?;
 
         let mut result = Vec::new();
         for brand in rows {
-            let oui_count: (i64,) = sqlx::query_as(
-                "SELECT COUNT(*) FROM oui_entries WHERE brand_id = ?",
-            )
-            .bind(brand.id)
-            .fetch_one(&self.pool)
-            .await?;
-
-            let template_count: (i64,) = sqlx::query_as(
-                "SELECT COUNT(*) FROM rtsp_templates WHERE brand_id = ?",
-            )
-            .bind(brand.id)
-            .fetch_one(&self.pool)
-            .await?;
+            let oui_count: (i64,) = with_db!(&self.db, pool => {
+                sqlx::query_as(
+                    "SELECT COUNT(*) FROM oui_entries WHERE brand_id = ?",
+                )
+                .bind(brand.id)
+                .fetch_one(pool)
+                .await
+            })?;
+
+            let template_count: (i64,) = with_db!(&self.db, pool => {
+                sqlx::query_as(
+                    "SELECT COUNT(*) FROM rtsp_templates WHERE brand_id = ?",
+                )
+                .bind(brand.id)
+                .fetch_one(pool)
+                .await
+            })?;
 
             result.push(CameraBrandWithCounts {
                 brand,
@@ -71,41 +80,46 @@ impl CameraBrandRepository {
 
     /// Get brand by ID
     pub async fn get_brand(&self, id: i32) -> Result<Option<CameraBrand>> {
-        let brand = sqlx::query_as::<_, CameraBrand>(
-            r#"SELECT id, name, display_name, category, is_builtin, created_at, updated_at
-               FROM camera_brands WHERE id = ?"#,
-        )
-        .bind(id)
-        .fetch_optional(&self.pool)
-        .await?;
+        let brand = with_db!(&self.db, pool => {
+            sqlx::query_as::<_, CameraBrand>(
+                r#"SELECT id, name, display_name, category, is_builtin, created_at, updated_at
+                   FROM camera_brands WHERE id = ?"#,
+            )
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+        })?;
         Ok(brand)
     }
 
     /// Get brand by name
     pub async fn get_brand_by_name(&self, name: &str) -> Result<Option<CameraBrand>> {
-        let brand = sqlx::query_as::<_, CameraBrand>(
-            r#"SELECT id, name, display_name, category, is_builtin, created_at, updated_at
-               FROM camera_brands WHERE name = ?"#,
-        )
-        .bind(name)
-        .fetch_optional(&self.pool)
-        .await?;
+        let brand = with_db!(&self.db, pool => {
+            sqlx::query_as::<_, CameraBrand>(
+                r#"SELECT id, name, display_name, category, is_builtin, created_at, updated_at
+                   FROM camera_brands WHERE name = ?"#,
+            )
+            .bind(name)
+            .fetch_optional(pool)
+            .await
+        })?;
         Ok(brand)
     }
 
     /// Create brand
     pub async fn create_brand(&self, req: &CreateBrandRequest) -> Result<CameraBrand> {
-        let result = sqlx::query(
-            r#"INSERT INTO camera_brands (name, display_name, category, is_builtin)
-               VALUES (?, ?, ?, FALSE)"#,
-        )
-        .bind(&req.name)
-        .bind(&req.display_name)
-        .bind(&req.category)
-        .execute(&self.pool)
-        .await?;
-
-        let id = result.last_insert_id() as i32;
+        let id = with_db!(&self.db, pool => {
+            sqlx::query(
+                r#"INSERT INTO camera_brands (name, display_name, category, is_builtin)
+                   VALUES (?, ?, ?, FALSE)"#,
+            )
+            .bind(&req.name)
+            .bind(&req.display_name)
+            .bind(&req.category)
+            .execute(pool)
+            .await
+            .map(|r| r.insert_id())
+        })? as i32;
         self.get_brand(id)
             .await?
             .ok_or_else(|| Error::Internal("Failed to get created brand".to_string()))
@@ -121,17 +135,20 @@ impl CameraBrandRepository {
         let display_name = req.display_name.as_ref().unwrap_or(&current.display_name);
         let category = req.category.as_ref().unwrap_or(&current.category);
 
-        sqlx::query(
-            r#"UPDATE camera_brands
-               SET name = ?, display_name = ?, category = ?
-               WHERE id = ?"#,
-        )
-        .bind(name)
-        .bind(display_name)
-        .bind(category)
-        .bind(id)
-        .execute(&self.pool)
-        .await?;
+        with_db!(&self.db, pool => {
+            sqlx::query(
+                r#"UPDATE camera_brands
+                   SET name = ?, display_name = ?, category = ?
+                   WHERE id = ?"#,
+            )
+            .bind(name)
+            .bind(display_name)
+            .bind(category)
+            .bind(id)
+            .execute(pool)
+            .await
+            .map(|r| r.rows_affected())
+        })?;
 
         self.get_brand(id)
             .await?
@@ -140,10 +157,13 @@ impl CameraBrandRepository {
 
     /// Delete brand
     pub async fn delete_brand(&self, id: i32) -> Result<()> {
-        sqlx::query("DELETE FROM camera_brands WHERE id = ?")
-            .bind(id)
-            .execute(&self.pool)
-            .await?;
+        with_db!(&self.db, pool => {
+            sqlx::query("DELETE FROM camera_brands WHERE id = ?")
+                .bind(id)
+                .execute(pool)
+                .await
+                .map(|r| r.rows_affected())
+        })?;
         Ok(())
     }
 
@@ -153,63 +173,72 @@ impl CameraBrandRepository {
 
     /// Get all OUI entries
     pub async fn get_all_oui_entries(&self) -> Result<Vec<OuiEntryWithBrand>> {
-        let entries = sqlx::query_as::<_, OuiEntryWithBrand>(
-            r#"SELECT o.oui_prefix, o.brand_id, b.name as brand_name, b.display_name as brand_display_name,
-                      o.description, o.score_bonus, o.status, o.verification_source,
-                      o.is_builtin, o.created_at, o.updated_at
-               FROM oui_entries o
-               JOIN camera_brands b ON o.brand_id = b.id
-               ORDER BY b.display_name, o.oui_prefix"#,
-        )
-        .fetch_all(&self.pool)
-        .await?;
+        let entries = with_db!(&self.db, pool => {
+            sqlx::query_as::<_, OuiEntryWithBrand>(
+                r#"SELECT o.oui_prefix, o.brand_id, b.name as brand_name, b.display_name as brand_display_name,
+                          o.description, o.score_bonus, o.status, o.verification_source,
+                          o.is_builtin, o.created_at, o.updated_at
+                   FROM oui_entries o
+                   JOIN camera_brands b ON o.brand_id = b.id
+                   ORDER BY b.display_name, o.oui_prefix"#,
+            )
+            .fetch_all(pool)
+            .await
+        })?;
         Ok(entries)
     }
 
     /// Get OUI entries for a brand
     pub async fn get_oui_entries_for_brand(&self, brand_id: i32) -> Result<Vec<OuiEntry>> {
-        let entries = sqlx::query_as::<_, OuiEntry>(
-            r#"SELECT oui_prefix, brand_id, description, score_bonus, status,
-                      verification_source, is_builtin, created_at, updated_at
-               FROM oui_entries
-               WHERE brand_id = ?
-               ORDER BY oui_prefix"#,
-        )
-        .bind(brand_id)
-        .fetch_all(&self.pool)
-        .await?;
+        let entries = with_db!(&self.db, pool => {
+            sqlx::query_as::<_, OuiEntry>(
+                r#"SELECT oui_prefix, brand_id, description, score_bonus, status,
+                          verification_source, is_builtin, created_at, updated_at
+                   FROM oui_entries
+                   WHERE brand_id = ?
+                   ORDER BY oui_prefix"#,
+            )
+            .bind(brand_id)
+            .fetch_all(pool)
+            .await
+        })?;
         Ok(entries)
     }
 
     /// Get OUI entry by prefix
     pub async fn get_oui_entry(&self, oui_prefix: &str) -> Result<Option<OuiEntry>> {
-        let entry = sqlx::query_as::<_, OuiEntry>(
-            r#"SELECT oui_prefix, brand_id, description, score_bonus, status,
-                      verification_source, is_builtin, created_at, updated_at
-               FROM oui_entries
-               WHERE oui_prefix = ?"#,
-        )
-        .bind(oui_prefix.to_uppercase())
-        .fetch_optional(&self.pool)
-        .await?;
+        let entry = with_db!(&self.db, pool => {
+            sqlx::query_as::<_, OuiEntry>(
+                r#"SELECT oui_prefix, brand_id, description, score_bonus, status,
+                          verification_source, is_builtin, created_at, updated_at
+                   FROM oui_entries
+                   WHERE oui_prefix = ?"#,
+            )
+            .bind(oui_prefix.to_uppercase())
+            .fetch_optional(pool)
+            .await
+        })?;
         Ok(entry)
     }
 
     /// Add OUI entry
     pub async fn add_oui_entry(&self, brand_id: i32, req: &AddOuiRequest) -> Result<OuiEntry> {
         let oui_upper = req.oui_prefix.to_uppercase();
-        sqlx::query(
-            r#"INSERT INTO oui_entries (oui_prefix, brand_id, description, score_bonus, status, verification_source, is_builtin)
-               VALUES (?, ?, ?, ?, ?, ?, FALSE)"#,
-        )
-        .bind(&oui_upper)
-        .bind(brand_id)
-        .bind(&req.description)
-        .bind(req.score_bonus)
-        .bind(&req.status)
-        .bind(&req.verification_source)
-        .execute(&self.pool)
-        .await?;
+        with_db!(&self.db, pool => {
+            sqlx::query(
+                r#"INSERT INTO oui_entries (oui_prefix, brand_id, description, score_bonus, status, verification_source, is_builtin)
+                   VALUES (?, ?, ?, ?, ?, ?, FALSE)"#,
+            )
+            .bind(&oui_upper)
+            .bind(brand_id)
+            .bind(&req.description)
+            .bind(req.score_bonus)
+            .bind(&req.status)
+            .bind(&req.verification_source)
+            .execute(pool)
+            .await
+            .map(|r| r.rows_affected())
+        })?;
 
         self.get_oui_entry(&oui_upper)
             .await?
@@ -228,18 +257,21 @@ impl CameraBrandRepository {
         let status = req.status.as_ref().unwrap_or(&current.status);
         let verification_source = req.verification_source.as_ref().or(current.verification_source.as_ref());
 
-        sqlx::query(
-            r#"UPDATE oui_entries
-               SET description = ?, score_bonus = ?, status = ?, verification_source = ?
-               WHERE oui_prefix = ?"#,
-        )
-        .bind(description)
-        .bind(score_bonus)
-        .bind(status)
-        .bind(verification_source)
-        .bind(&oui_upper)
-        .execute(&self.pool)
-        .await?;
+        with_db!(&self.db, pool => {
+            sqlx::query(
+                r#"UPDATE oui_entries
+                   SET description = ?, score_bonus = ?, status = ?, verification_source = ?
+                   WHERE oui_prefix = ?"#,
+            )
+            .bind(description)
+            .bind(score_bonus)
+            .bind(status)
+            .bind(verification_source)
+            .bind(&oui_upper)
+            .execute(pool)
+            .await
+            .map(|r| r.rows_affected())
+        })?;
 
         self.get_oui_entry(&oui_upper)
             .await?
@@ -248,10 +280,13 @@ impl CameraBrandRepository {
 
     /// Delete OUI entry
     pub async fn delete_oui_entry(&self, oui_prefix: &str) -> Result<()> {
-        sqlx::query("DELETE FROM oui_entries WHERE oui_prefix = ?")
-            .bind(oui_prefix.to_uppercase())
-            .execute(&self.pool)
-            .await?;
+        with_db!(&self.db, pool => {
+            sqlx::query("DELETE FROM oui_entries WHERE oui_prefix = ?")
+                .bind(oui_prefix.to_uppercase())
+                .execute(pool)
+                .await
+                .map(|r| r.rows_affected())
+        })?;
         Ok(())
     }
 
@@ -261,60 +296,68 @@ impl CameraBrandRepository {
 
     /// Get all RTSP templates
     pub async fn get_all_templates(&self) -> Result<Vec<RtspTemplateWithBrand>> {
-        let templates = sqlx::query_as::<_, RtspTemplateWithBrand>(
-            r#"SELECT t.id, t.brand_id, b.name as brand_name, b.display_name as brand_display_name,
-                      t.name, t.main_path, t.sub_path, t.default_port, t.is_default, t.priority,
-                      t.notes, t.is_builtin, t.created_at, t.updated_at
-               FROM rtsp_templates t
-               JOIN camera_brands b ON t.brand_id = b.id
-               ORDER BY b.display_name, t.priority, t.name"#,
-        )
-        .fetch_all(&self.pool)
-        .await?;
+        let templates = with_db!(&self.db, pool => {
+            sqlx::query_as::<_, RtspTemplateWithBrand>(
+                r#"SELECT t.id, t.brand_id, b.name as brand_name, b.display_name as brand_display_name,
+                          t.name, t.main_path, t.sub_path, t.default_port, t.is_default, t.priority,
+                          t.notes, t.is_builtin, t.created_at, t.updated_at
+                   FROM rtsp_templates t
+                   JOIN camera_brands b ON t.brand_id = b.id
+                   ORDER BY b.display_name, t.priority, t.name"#,
+            )
+            .fetch_all(pool)
+            .await
+        })?;
         Ok(templates)
     }
 
     /// Get RTSP templates for a brand
     pub async fn get_templates_for_brand(&self, brand_id: i32) -> Result<Vec<RtspTemplate>> {
-        let templates = sqlx::query_as::<_, RtspTemplate>(
-            r#"SELECT id, brand_id, name, main_path, sub_path, default_port,
-                      is_default, priority, notes, is_builtin, created_at, updated_at
-               FROM rtsp_templates
-               WHERE brand_id = ?
-               ORDER BY priority, name"#,
-        )
-        .bind(brand_id)
-        .fetch_all(&self.pool)
-        .await?;
+        let templates = with_db!(&self.db, pool => {
+            sqlx::query_as::<_, RtspTemplate>(
+                r#"SELECT id, brand_id, name, main_path, sub_path, default_port,
+                          is_default, priority, notes, is_builtin, created_at, updated_at
+                   FROM rtsp_templates
+                   WHERE brand_id = ?
+                   ORDER BY priority, name"#,
+            )
+            .bind(brand_id)
+            .fetch_all(pool)
+            .await
+        })?;
         Ok(templates)
     }
 
     /// Get RTSP template by ID
     pub async fn get_template(&self, id: i32) -> Result<Option<RtspTemplate>> {
-        let template = sqlx::query_as::<_, RtspTemplate>(
-            r#"SELECT id, brand_id, name, main_path, sub_path, default_port,
-                      is_default, priority, notes, is_builtin, created_at, updated_at
-               FROM rtsp_templates
-               WHERE id = ?"#,
-        )
-        .bind(id)
-        .fetch_optional(&self.pool)
-        .await?;
+        let template = with_db!(&self.db, pool => {
+            sqlx::query_as::<_, RtspTemplate>(
+                r#"SELECT id, brand_id, name, main_path, sub_path, default_port,
+                          is_default, priority, notes, is_builtin, created_at, updated_at
+                   FROM rtsp_templates
+                   WHERE id = ?"#,
+            )
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+        })?;
         Ok(template)
     }
 
     /// Get default RTSP template for a brand
     pub async fn get_default_template(&self, brand_id: i32) -> Result<Option<RtspTemplate>> {
-        let template = sqlx::query_as::<_, RtspTemplate>(
-            r#"SELECT id, brand_id, name, main_path, sub_path, default_port,
-                      is_default, priority, notes, is_builtin, created_at, updated_at
-               FROM rtsp_templates
-               WHERE brand_id = ? AND is_default = TRUE
-               LIMIT 1"#,
-        )
-        .bind(brand_id)
-        .fetch_optional(&self.pool)
-        .await?;
+        let template = with_db!(&self.db, pool => {
+            sqlx::query_as::<_, RtspTemplate>(
+                r#"SELECT id, brand_id, name, main_path, sub_path, default_port,
+                          is_default, priority, notes, is_builtin, created_at, updated_at
+                   FROM rtsp_templates
+                   WHERE brand_id = ? AND is_default = TRUE
+                   LIMIT 1"#,
+            )
+            .bind(brand_id)
+            .fetch_optional(pool)
+            .await
+        })?;
         Ok(template)
     }
 
@@ -322,28 +365,32 @@ impl CameraBrandRepository {
     pub async fn add_template(&self, brand_id: i32, req: &AddTemplateRequest) -> Result<RtspTemplate> {
         // If this is marked as default, unset other defaults for this brand
         if req.is_default {
-            sqlx::query("UPDATE rtsp_templates SET is_default = FALSE WHERE brand_id = ?")
-                .bind(brand_id)
-                .execute(&self.pool)
-                .await?;
+            with_db!(&self.db, pool => {
+                sqlx::query("UPDATE rtsp_templates SET is_default = FALSE WHERE brand_id = ?")
+                    .bind(brand_id)
+                    .execute(pool)
+                    .await
+                    .map(|r| r.rows_affected())
+            })?;
         }
 
-        let result = sqlx::query(
-            r#"INSERT INTO rtsp_templates (brand_id, name, main_path, sub_path, default_port, is_default, priority, notes, is_builtin)
-               VALUES (?, ?, ?, ?, ?, ?, ?, ?, FALSE)"#,
-        )
-        .bind(brand_id)
-        .bind(&req.name)
-        .bind(&req.main_path)
-        .bind(&req.sub_path)
-        .bind(req.default_port)
-        .bind(req.is_default)
-        .bind(req.priority)
-        .bind(&req.notes)
-        .execute(&self.pool)
-        .await?;
-
-        let id = result.last_insert_id() as i32;
+        let id = with_db!(&self.db, pool => {
+            sqlx::query(
+                r#"INSERT INTO rtsp_templates (brand_id, name, main_path, sub_path, default_port, is_default, priority, notes, is_builtin)
+                   VALUES (?, ?, ?, ?, ?, ?, ?, ?, FALSE)"#,
+            )
+            .bind(brand_id)
+            .bind(&req.name)
+            .bind(&req.main_path)
+            .bind(&req.sub_path)
+            .bind(req.default_port)
+            .bind(req.is_default)
+            .bind(req.priority)
+            .bind(&req.notes)
+            .execute(pool)
+            .await
+            .map(|r| r.insert_id())
+        })? as i32;
         self.get_template(id)
             .await?
             .ok_or_else(|| Error::Internal("Failed to get created template".to_string()))
@@ -357,11 +404,14 @@ impl CameraBrandRepository {
 
         // If setting as default, unset other defaults
         if req.is_default == Some(true) {
-            sqlx::query("UPDATE rtsp_templates SET is_default = FALSE WHERE brand_id = ? AND id != ?")
-                .bind(current.brand_id)
-                .bind(id)
-                .execute(&self.pool)
-                .await?;
+            with_db!(&self.db, pool => {
+                sqlx::query("UPDATE rtsp_templates SET is_default = FALSE WHERE brand_id = ? AND id != ?")
+                    .bind(current.brand_id)
+                    .bind(id)
+                    .execute(pool)
+                    .await
+                    .map(|r| r.rows_affected())
+            })?;
         }
 
         let name = req.name.as_ref().unwrap_or(&current.name);
@@ -378,22 +428,25 @@ impl CameraBrandRepository {
             None => current.notes.clone(),
         };
 
-        sqlx::query(
-            r#"UPDATE rtsp_templates
-               SET name = ?, main_path = ?, sub_path = ?, default_port = ?,
-                   is_default = ?, priority = ?, notes = ?
-               WHERE id = ?"#,
-        )
-        .bind(name)
-        .bind(main_path)
-        .bind(&sub_path)
-        .bind(default_port)
-        .bind(is_default)
-        .bind(priority)
-        .bind(&notes)
-        .bind(id)
-        .execute(&self.pool)
-        .await?;
+        with_db!(&self.db, pool => {
+            sqlx::query(
+                r#"UPDATE rtsp_templates
+                   SET name = ?, main_path = ?, sub_path = ?, default_port = ?,
+                       is_default = ?, priority = ?, notes = ?
+                   WHERE id = ?"#,
+            )
+            .bind(name)
+            .bind(main_path)
+            .bind(&sub_path)
+            .bind(default_port)
+            .bind(is_default)
+            .bind(priority)
+            .bind(&notes)
+            .bind(id)
+            .execute(pool)
+            .await
+            .map(|r| r.rows_affected())
+        })?;
 
         self.get_template(id)
             .await?
@@ -402,10 +455,13 @@ impl CameraBrandRepository {
 
     /// Delete RTSP template
     pub async fn delete_template(&self, id: i32) -> Result<()> {
-        sqlx::query("DELETE FROM rtsp_templates WHERE id = ?")
-            .bind(id)
-            .execute(&self.pool)
-            .await?;
+        with_db!(&self.db, pool => {
+            sqlx::query("DELETE FROM rtsp_templates WHERE id = ?")
+                .bind(id)
+                .execute(pool)
+                .await
+                .map(|r| r.rows_affected())
+        })?;
         Ok(())
     }
 
@@ -415,57 +471,64 @@ impl CameraBrandRepository {
 
     /// Get all generic paths
     pub async fn get_all_generic_paths(&self) -> Result<Vec<GenericRtspPath>> {
-        let paths = sqlx::query_as::<_, GenericRtspPath>(
-            r#"SELECT id, main_path, sub_path, description, priority, is_enabled, created_at, updated_at
-               FROM generic_rtsp_paths
-               ORDER BY priority"#,
-        )
-        .fetch_all(&self.pool)
-        .await?;
+        let paths = with_db!(&self.db, pool => {
+            sqlx::query_as::<_, GenericRtspPath>(
+                r#"SELECT id, main_path, sub_path, description, priority, is_enabled, created_at, updated_at
+                   FROM generic_rtsp_paths
+                   ORDER BY priority"#,
+            )
+            .fetch_all(pool)
+            .await
+        })?;
         Ok(paths)
     }
 
     /// Get enabled generic paths (for scanning)
     pub async fn get_enabled_generic_paths(&self) -> Result<Vec<GenericRtspPath>> {
-        let paths = sqlx::query_as::<_, GenericRtspPath>(
-            r#"SELECT id, main_path, sub_path, description, priority, is_enabled, created_at, updated_at
-               FROM generic_rtsp_paths
-               WHERE is_enabled = TRUE
-               ORDER BY priority"#,
-        )
-        .fetch_all(&self.pool)
-        .await?;
+        let paths = with_db!(&self.db, pool => {
+            sqlx::query_as::<_, GenericRtspPath>(
+                r#"SELECT id, main_path, sub_path, description, priority, is_enabled, created_at, updated_at
+                   FROM generic_rtsp_paths
+                   WHERE is_enabled = TRUE
+                   ORDER BY priority"#,
+            )
+            .fetch_all(pool)
+            .await
+        })?;
         Ok(paths)
     }
 
     /// Get generic path by ID
     pub async fn get_generic_path(&self, id: i32) -> Result<Option<GenericRtspPath>> {
-        let path = sqlx::query_as::<_, GenericRtspPath>(
-            r#"SELECT id, main_path, sub_path, description, priority, is_enabled, created_at, updated_at
-               FROM generic_rtsp_paths
-               WHERE id = ?"#,
-        )
-        .bind(id)
-        .fetch_optional(&self.pool)
-        .await?;
+        let path = with_db!(&self.db, pool => {
+            sqlx::query_as::<_, GenericRtspPath>(
+                r#"SELECT id, main_path, sub_path, description, priority, is_enabled, created_at, updated_at
+                   FROM generic_rtsp_paths
+                   WHERE id = ?"#,
+            )
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+        })?;
         Ok(path)
     }
 
     /// Add generic path
     pub async fn add_generic_path(&self, req: &AddGenericPathRequest) -> Result<GenericRtspPath> {
-        let result = sqlx::query(
-            r#"INSERT INTO generic_rtsp_paths (main_path, sub_path, description, priority, is_enabled)
-               VALUES (?, ?, ?, ?, ?)"#,
-        )
-        .bind(&req.main_path)
-        .bind(&req.sub_path)
-        .bind(&req.description)
-        .bind(req.priority)
-        .bind(req.is_enabled)
-        .execute(&self.pool)
-        .await?;
-
-        let id = result.last_insert_id() as i32;
+        let id = with_db!(&self.db, pool => {
+            sqlx::query(
+                r#"INSERT INTO generic_rtsp_paths (main_path, sub_path, description, priority, is_enabled)
+                   VALUES (?, ?, ?, ?, ?)"#,
+            )
+            .bind(&req.main_path)
+            .bind(&req.sub_path)
+            .bind(&req.description)
+            .bind(req.priority)
+            .bind(req.is_enabled)
+            .execute(pool)
+            .await
+            .map(|r| r.insert_id())
+        })? as i32;
         self.get_generic_path(id)
             .await?
             .ok_or_else(|| Error::Internal("Failed to get created generic path".to_string()))
@@ -489,19 +552,22 @@ impl CameraBrandRepository {
         let priority = req.priority.unwrap_or(current.priority);
         let is_enabled = req.is_enabled.unwrap_or(current.is_enabled);
 
-        sqlx::query(
-            r#"UPDATE generic_rtsp_paths
-               SET main_path = ?, sub_path = ?, description = ?, priority = ?, is_enabled = ?
-               WHERE id = ?"#,
-        )
-        .bind(main_path)
-        .bind(&sub_path)
-        .bind(&description)
-        .bind(priority)
-        .bind(is_enabled)
-        .bind(id)
-        .execute(&self.pool)
-        .await?;
+        with_db!(&self.db, pool => {
+            sqlx::query(
+                r#"UPDATE generic_rtsp_paths
+                   SET main_path = ?, sub_path = ?, description = ?, priority = ?, is_enabled = ?
+                   WHERE id = ?"#,
+            )
+            .bind(main_path)
+            .bind(&sub_path)
+            .bind(&description)
+            .bind(priority)
+            .bind(is_enabled)
+            .bind(id)
+            .execute(pool)
+            .await
+            .map(|r| r.rows_affected())
+        })?;
 
         self.get_generic_path(id)
             .await?
@@ -510,10 +576,13 @@ impl CameraBrandRepository {
 
     /// Delete generic path
     pub async fn delete_generic_path(&self, id: i32) -> Result<()> {
-        sqlx::query("DELETE FROM generic_rtsp_paths WHERE id = ?")
-            .bind(id)
-            .execute(&self.pool)
-            .await?;
+        with_db!(&self.db, pool => {
+            sqlx::query("DELETE FROM generic_rtsp_paths WHERE id = ?")
+                .bind(id)
+                .execute(pool)
+                .await
+                .map(|r| r.rows_affected())
+        })?;
         Ok(())
     }
 
@@ -523,14 +592,16 @@ impl CameraBrandRepository {
 
     /// Load all OUI entries with brand info (for cache)
     pub async fn load_oui_brand_map(&self) -> Result<Vec<OuiBrandInfo>> {
-        let entries = sqlx::query_as::<_, (String, i32, String, String, String, i32, String)>(
-            r#"SELECT o.oui_prefix, o.brand_id, b.name, b.display_name, b.category, o.score_bonus, o.status
-               FROM oui_entries o
-               JOIN camera_brands b ON o.brand_id = b.id
-               WHERE o.status IN ('confirmed', 'candidate')"#,
-        )
-        .fetch_all(&self.pool)
-        .await?;
+        let entries = with_db!(&self.db, pool => {
+            sqlx::query_as::<_, (String, i32, String, String, String, i32, String)>(
+                r#"SELECT o.oui_prefix, o.brand_id, b.name, b.display_name, b.category, o.score_bonus, o.status
+                   FROM oui_entries o
+                   JOIN camera_brands b ON o.brand_id = b.id
+                   WHERE o.status IN ('confirmed', 'candidate')"#,
+            )
+            .fetch_all(pool)
+            .await
+        })?;
 
         Ok(entries
             .into_iter()
@@ -550,14 +621,16 @@ impl CameraBrandRepository {
 
     /// Load all RTSP templates with brand info (for cache)
     pub async fn load_rtsp_templates(&self) -> Result<Vec<RtspTemplateInfo>> {
-        let templates = sqlx::query_as::<_, (i32, i32, String, String, String, Option<String>, i32, bool, i32)>(
-            r#"SELECT t.id, t.brand_id, b.name, t.name, t.main_path, t.sub_path, t.default_port, t.is_default, t.priority
-               FROM rtsp_templates t
-               JOIN camera_brands b ON t.brand_id = b.id
-               ORDER BY t.brand_id, t.priority"#,
-        )
-        .fetch_all(&self.pool)
-        .await?;
+        let templates = with_db!(&self.db, pool => {
+            sqlx::query_as::<_, (i32, i32, String, String, String, Option<String>, i32, bool, i32)>(
+                r#"SELECT t.id, t.brand_id, b.name, t.name, t.main_path, t.sub_path, t.default_port, t.is_default, t.priority
+                   FROM rtsp_templates t
+                   JOIN camera_brands b ON t.brand_id = b.id
+                   ORDER BY t.brand_id, t.priority"#,
+            )
+            .fetch_all(pool)
+            .await
+        })?;
 
         Ok(templates
             .into_iter()
@@ -577,3 +650,74 @@ impl CameraBrandRepository {
             .collect())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_sqlite_brand_oui_template_crud() {
+        let repo = CameraBrandRepository::new(Db::memory().await.unwrap());
+
+        let brand = repo
+            .create_brand(&CreateBrandRequest {
+                name: "tplink".to_string(),
+                display_name: "TP-Link".to_string(),
+                category: "consumer".to_string(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(repo.get_brand_by_name("tplink").await.unwrap().unwrap().id, brand.id);
+
+        let oui: AddOuiRequest =
+            serde_json::from_value(json!({"oui_prefix": "70:5A:0F"})).unwrap();
+        repo.add_oui_entry(brand.id, &oui).await.unwrap();
+        let template: AddTemplateRequest = serde_json::from_value(json!({
+            "name": "Tapo", "main_path": "/stream1", "sub_path": "/stream2", "is_default": true
+        }))
+        .unwrap();
+        let template = repo.add_template(brand.id, &template).await.unwrap();
+        assert_eq!(template.default_port, 554);
+
+        let brands = repo.get_all_brands_with_counts().await.unwrap();
+        assert_eq!(brands.len(), 1);
+        assert_eq!((brands[0].oui_count, brands[0].template_count), (1, 1));
+
+        let ouis = repo.load_oui_brand_map().await.unwrap();
+        assert_eq!(ouis.len(), 1);
+        assert_eq!(ouis[0].brand_name, "tplink");
+        let templates = repo.load_rtsp_templates().await.unwrap();
+        assert_eq!(templates[0].main_path, "/stream1");
+        assert!(templates[0].is_default);
+        assert_eq!(
+            repo.get_default_template(brand.id).await.unwrap().unwrap().id,
+            template.id
+        );
+
+        // ブランド削除で OUI / テンプレートも消える（外部キー CASCADE）
+        repo.delete_brand(brand.id).await.unwrap();
+        assert!(repo.get_oui_entry("70:5A:0F").await.unwrap().is_none());
+        assert!(repo.get_template(template.id).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_generic_paths() {
+        let repo = CameraBrandRepository::new(Db::memory().await.unwrap());
+
+        let enabled: AddGenericPathRequest =
+            serde_json::from_value(json!({"main_path": "/live/ch0", "priority": 10})).unwrap();
+        let disabled: AddGenericPathRequest =
+            serde_json::from_value(json!({"main_path": "/h264", "is_enabled": false})).unwrap();
+        let path = repo.add_generic_path(&enabled).await.unwrap();
+        repo.add_generic_path(&disabled).await.unwrap();
+
+        assert_eq!(repo.get_all_generic_paths().await.unwrap().len(), 2);
+        let enabled_paths = repo.get_enabled_generic_paths().await.unwrap();
+        assert_eq!(enabled_paths.len(), 1);
+        assert_eq!(enabled_paths[0].id, path.id);
+
+        repo.delete_generic_path(path.id).await.unwrap();
+        assert!(repo.get_generic_path(path.id).await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,204 @@
+//! スナップショット画像の品質解析
+//!
+//! - 黒画面: 平均輝度が低く、かつ低コントラスト
+//! - 静止: デコード後の輝度プレーンが完全一致（センサーノイズがあれば一致しない）
+//! - ボケ: ラプラシアン分散が小さい
+//! - 画角急変: 基準グリッドとの変化セル割合が大きい（移動・遮蔽）
+
+use super::types::{DiagnosticIssue, DiagnosticIssueKind, DiagnosticsConfig, FrameMetrics};
+use crate::motion_prefilter::{compare, decode_luma, ChangeParams, LumaGrid};
+use serde_json::json;
+use std::hash::{Hash, Hasher};
+
+/// 解析用デコードサイズ（DCTスケーリングでこれ以上の最小サイズ）
+const ANALYSIS_WIDTH: u16 = 320;
+const ANALYSIS_HEIGHT: u16 = 240;
+
+/// デコード済みフレーム
+pub struct AnalyzedFrame {
+    pub mean_luma: f64,
+    pub stddev_luma: f64,
+    pub laplacian_variance: f64,
+    /// 輝度プレーンのハッシュ（静止判定用）
+    pub fingerprint: u64,
+    pub grid: LumaGrid,
+}
+
+impl AnalyzedFrame {
+    pub fn from_jpeg(data: &[u8]) -> Result<Self, String> {
+        let (luma, width, height) = decode_luma(data, ANALYSIS_WIDTH, ANALYSIS_HEIGHT)?;
+        Self::from_luma(&luma, width, height)
+    }
+
+    pub fn from_luma(luma: &[u8], width: usize, height: usize) -> Result<Self, String> {
+        let grid = LumaGrid::from_luma(luma, width, height)?;
+        let plane = &luma[..width * height];
+
+        let n = plane.len() as f64;
+        let mean = plane.iter().map(|&v| v as f64).sum::<f64>() / n;
+        let variance = plane.iter().map(|&v| (v as f64 - mean).powi(2)).sum::<f64>() / n;
+
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        plane.hash(&mut hasher);
+
+        Ok(Self {
+            mean_luma: mean,
+            stddev_luma: variance.sqrt(),
+            laplacian_variance: laplacian_variance(plane, width, height),
+            fingerprint: hasher.finish(),
+            grid,
+        })
+    }
+}
+
+/// 4近傍ラプラシアンの分散
+fn laplacian_variance(plane: &[u8], width: usize, height: usize) -> f64 {
+    if width < 3 || height < 3 {
+        return 0.0;
+    }
+    let mut values = Vec::with_capacity((width - 2) * (height - 2));
+    for y in 1..height - 1 {
+        for x in 1..width - 1 {
+            let at = |x: usize, y: usize| plane[y * width + x] as f64;
+            values.push(at(x - 1, y) + at(x + 1, y) + at(x, y - 1) + at(x, y + 1) - 4.0 * at(x, y));
+        }
+    }
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n
+}
+
+/// 基準グリッドとの変化セル割合
+pub fn scene_change_ratio(baseline: &LumaGrid, current: &LumaGrid, noise_floor: i32) -> f32 {
+    compare(
+        baseline,
+        current,
+        ChangeParams {
+            sensitivity: 0.0,
+            noise_floor,
+        },
+    )
+    .changed_ratio
+}
+
+/// 画像指標から問題を判定
+pub fn evaluate_frame(metrics: &FrameMetrics, config: &DiagnosticsConfig) -> Vec<DiagnosticIssue> {
+    let mut issues = Vec::new();
+    let issue = |kind: DiagnosticIssueKind, message: String, details: serde_json::Value| {
+        DiagnosticIssue {
+            kind,
+            source: "snapshot".to_string(),
+            message,
+            details,
+        }
+    };
+
+    let black = metrics.mean_luma <= config.black_max_luma
+        && metrics.stddev_luma <= config.black_max_stddev;
+    if black {
+        issues.push(issue(
+            DiagnosticIssueKind::BlackFrame,
+            format!("mean luma {:.1}", metrics.mean_luma),
+            json!({ "meanLuma": metrics.mean_luma, "stddevLuma": metrics.stddev_luma }),
+        ));
+    } else if metrics.laplacian_variance < config.blur_min_laplacian {
+        // 黒画面はエッジがないためボケ判定しない
+        issues.push(issue(
+            DiagnosticIssueKind::Blurred,
+            format!("laplacian variance {:.1}", metrics.laplacian_variance),
+            json!({
+                "laplacianVariance": metrics.laplacian_variance,
+                "threshold": config.blur_min_laplacian
+            }),
+        ));
+    }
+
+    if metrics.identical_repeats >= config.frozen_min_repeats {
+        issues.push(issue(
+            DiagnosticIssueKind::FrozenFrame,
+            format!("identical frame {} times", metrics.identical_repeats),
+            json!({ "identicalRepeats": metrics.identical_repeats }),
+        ));
+    }
+
+    if let Some(ratio) = metrics.scene_change_ratio {
+        if ratio >= config.scene_shift_ratio {
+            issues.push(issue(
+                DiagnosticIssueKind::SceneShift,
+                format!("{:.0}% of scene changed", ratio * 100.0),
+                json!({ "changedRatio": ratio, "threshold": config.scene_shift_ratio }),
+            ));
+        }
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plane(width: usize, height: usize, f: impl Fn(usize, usize) -> u8) -> Vec<u8> {
+        (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| f(x, y))
+            .collect()
+    }
+
+    #[test]
+    fn test_black_and_sharp_frames() {
+        let config = DiagnosticsConfig::default();
+
+        let dark = AnalyzedFrame::from_luma(&plane(320, 240, |_, _| 4), 320, 240).unwrap();
+        let metrics = FrameMetrics {
+            mean_luma: dark.mean_luma,
+            stddev_luma: dark.stddev_luma,
+            laplacian_variance: dark.laplacian_variance,
+            ..Default::default()
+        };
+        let kinds: Vec<_> = evaluate_frame(&metrics, &config).iter().map(|i| i.kind).collect();
+        assert_eq!(kinds, vec![DiagnosticIssueKind::BlackFrame]);
+
+        // 市松模様はエッジが多い
+        let sharp = AnalyzedFrame::from_luma(
+            &plane(320, 240, |x, y| if (x / 4 + y / 4) % 2 == 0 { 30 } else { 220 }),
+            320,
+            240,
+        )
+        .unwrap();
+        let metrics = FrameMetrics {
+            mean_luma: sharp.mean_luma,
+            stddev_luma: sharp.stddev_luma,
+            laplacian_variance: sharp.laplacian_variance,
+            ..Default::default()
+        };
+        assert!(evaluate_frame(&metrics, &config).is_empty());
+    }
+
+    #[test]
+    fn test_flat_gray_is_blurred() {
+        let flat = AnalyzedFrame::from_luma(&plane(320, 240, |_, _| 128), 320, 240).unwrap();
+        assert!(flat.laplacian_variance < 1.0);
+        let metrics = FrameMetrics {
+            mean_luma: flat.mean_luma,
+            stddev_luma: flat.stddev_luma,
+            laplacian_variance: flat.laplacian_variance,
+            ..Default::default()
+        };
+        let kinds: Vec<_> = evaluate_frame(&metrics, &DiagnosticsConfig::default())
+            .iter()
+            .map(|i| i.kind)
+            .collect();
+        assert_eq!(kinds, vec![DiagnosticIssueKind::Blurred]);
+    }
+
+    #[test]
+    fn test_scene_shift_ratio() {
+        let a = AnalyzedFrame::from_luma(&plane(320, 240, |x, _| (x % 256) as u8), 320, 240).unwrap();
+        // 輝度反転（カメラ移動・遮蔽相当）
+        let b = AnalyzedFrame::from_luma(&plane(320, 240, |x, _| 255 - (x % 256) as u8), 320, 240)
+            .unwrap();
+        assert_eq!(scene_change_ratio(&a.grid, &a.grid, 40), 0.0);
+        assert!(scene_change_ratio(&a.grid, &b.grid, 40) > 0.6);
+    }
+}
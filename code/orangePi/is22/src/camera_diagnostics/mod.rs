@@ -0,0 +1,444 @@
+//! CameraDiagnostics - カメラ健全性の定期診断
+//!
+//! ## 概要
+//! CameraMalfunctionReporter の low_fps / stream_error 等を実測に基づいて発報する。
+//!
+//! 1. ストリーム計測: RtspManager のリースを取得して ffprobe で main/sub を計測し、
+//!    Camera の能力情報（codec/resolution/fps/bitrate）と比較
+//! 2. 画像解析: ポーリングが保存した latest.jpg を解析（カメラへの追加アクセスなし）
+//!    - 黒画面 / 静止 / ボケ / 画角急変（移動・遮蔽）
+//! 3. 発報: 前回から新たに検出された問題のみ Paraclate（CameraMalfunctionReporter）へ報告し、
+//!    問題の増減を RealtimeHub（camera_health）へ通知
+//!
+//! ffprobe 計測中はリースを保持するため、その間のポーリングは待機する。
+//! probe_seconds は短く保つこと。
+
+pub mod analysis;
+pub mod probe;
+pub mod types;
+
+pub use types::*;
+
+use crate::camera_malfunction_reporter::CameraMalfunctionReporter;
+use crate::config_store::{Camera, ConfigStore};
+use crate::motion_prefilter::LumaGrid;
+use crate::realtime_hub::{CameraHealthMessage, HubMessage, RealtimeHub};
+use crate::rtsp_manager::RtspManager;
+use crate::snapshot_service::SnapshotService;
+use analysis::{evaluate_frame, scene_change_ratio, AnalyzedFrame};
+use chrono::Utc;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+use std::time::SystemTime;
+use tokio::sync::RwLock;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
+
+/// 起動後、初回診断までの待機（秒）
+const INITIAL_DELAY_SECS: u64 = 120;
+
+/// カメラごとの診断状態
+#[derive(Default)]
+struct CameraDiagState {
+    last_report: Option<CameraHealthReport>,
+    active: BTreeSet<DiagnosticIssueKind>,
+    /// 画角急変判定の基準（前回診断フレーム）
+    baseline: Option<LumaGrid>,
+    last_fingerprint: Option<u64>,
+    identical_repeats: u32,
+    last_snapshot_mtime: Option<SystemTime>,
+}
+
+/// カメラ診断サービス
+pub struct CameraDiagnosticsService {
+    config_store: Arc<ConfigStore>,
+    rtsp_manager: Arc<RtspManager>,
+    snapshot_service: Arc<SnapshotService>,
+    reporter: CameraMalfunctionReporter,
+    realtime_hub: Arc<RealtimeHub>,
+    /// Camera に tid/fid がない場合の報告先
+    default_tid: String,
+    default_fid: String,
+    config: RwLock<DiagnosticsConfig>,
+    states: RwLock<HashMap<String, CameraDiagState>>,
+}
+
+impl CameraDiagnosticsService {
+    pub fn new(
+        config_store: Arc<ConfigStore>,
+        rtsp_manager: Arc<RtspManager>,
+        snapshot_service: Arc<SnapshotService>,
+        reporter: CameraMalfunctionReporter,
+        realtime_hub: Arc<RealtimeHub>,
+        default_tid: String,
+        default_fid: String,
+    ) -> Self {
+        Self {
+            config_store,
+            rtsp_manager,
+            snapshot_service,
+            reporter,
+            realtime_hub,
+            default_tid,
+            default_fid,
+            config: RwLock::new(DiagnosticsConfig::default()),
+            states: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// 設定をDBから読み込み（未設定ならデフォルト）
+    pub async fn load_config(&self) -> crate::Result<()> {
+        let stored = self
+            .config_store
+            .service()
+            .get_setting(DIAGNOSTICS_CONFIG_KEY)
+            .await?
+            .and_then(|v| serde_json::from_value(v).ok());
+        if let Some(config) = stored {
+            *self.config.write().await = config;
+        }
+        Ok(())
+    }
+
+    pub async fn config(&self) -> DiagnosticsConfig {
+        self.config.read().await.clone()
+    }
+
+    /// 設定を保存
+    pub async fn set_config(&self, config: DiagnosticsConfig) -> crate::Result<DiagnosticsConfig> {
+        config.validate()?;
+        self.config_store
+            .service()
+            .set_setting(DIAGNOSTICS_CONFIG_KEY, serde_json::to_value(&config)?)
+            .await?;
+        *self.config.write().await = config.clone();
+        Ok(config)
+    }
+
+    /// 全カメラの最新診断結果
+    pub async fn reports(&self) -> Vec<CameraHealthReport> {
+        let mut reports: Vec<_> = self
+            .states
+            .read()
+            .await
+            .values()
+            .filter_map(|s| s.last_report.clone())
+            .collect();
+        reports.sort_by(|a, b| a.camera_id.cmp(&b.camera_id));
+        reports
+    }
+
+    pub async fn report(&self, camera_id: &str) -> Option<CameraHealthReport> {
+        self.states
+            .read()
+            .await
+            .get(camera_id)
+            .and_then(|s| s.last_report.clone())
+    }
+
+    /// 画角急変の基準フレームを破棄（カメラを意図的に向け直した場合など）
+    pub async fn reset_baseline(&self, camera_id: &str) {
+        if let Some(state) = self.states.write().await.get_mut(camera_id) {
+            state.baseline = None;
+        }
+    }
+
+    /// 定期診断ループ（`shutdown` がキャンセルされるまで。TaskSupervisor から起動）
+    pub async fn run(self: Arc<Self>, shutdown: CancellationToken) {
+        if let Err(e) = self.load_config().await {
+            warn!(error = %e, "Camera diagnostics: failed to load config, using defaults");
+        }
+
+        let mut delay = std::time::Duration::from_secs(INITIAL_DELAY_SECS);
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = tokio::time::sleep(delay) => {}
+            }
+            let config = self.config().await;
+            if config.enabled {
+                let checked = self.run_all().await;
+                info!(cameras = checked, "Camera diagnostics cycle completed");
+            }
+            delay = std::time::Duration::from_secs(config.interval_minutes.max(1) as u64 * 60);
+        }
+    }
+
+    /// 有効な全カメラを順に診断
+    pub async fn run_all(&self) -> usize {
+        let cameras: Vec<Camera> = self
+            .config_store
+            .get_cached_cameras()
+            .await
+            .into_iter()
+            .filter(|c| c.enabled)
+            .collect();
+
+        for camera in &cameras {
+            self.run_camera(camera).await;
+        }
+        cameras.len()
+    }
+
+    /// 1カメラを診断し、新規問題を発報
+    pub async fn run_camera(&self, camera: &Camera) -> CameraHealthReport {
+        let config = self.config().await;
+        let mut issues = Vec::new();
+        let mut skipped = Vec::new();
+
+        // 1. ストリーム計測
+        let mut streams = Vec::new();
+        let candidates = [
+            (
+                "main",
+                camera.rtsp_main.as_deref(),
+                StreamExpectation {
+                    codec: camera.codec_main.clone(),
+                    resolution: camera.resolution_main.clone(),
+                    fps: camera.fps_main,
+                    bitrate_kbps: camera.bitrate_main,
+                },
+            ),
+            (
+                "sub",
+                camera.rtsp_sub.as_deref(),
+                StreamExpectation {
+                    codec: camera.codec_sub.clone(),
+                    resolution: camera.resolution_sub.clone(),
+                    fps: camera.fps_sub,
+                    bitrate_kbps: camera.bitrate_sub,
+                },
+            ),
+        ];
+        for (name, url, expected) in candidates {
+            let Some(url) = url else { continue };
+
+            let lease = match self.rtsp_manager.acquire(&camera.camera_id).await {
+                Ok(lease) => lease,
+                Err(e) => {
+                    skipped.push(format!("{}: {}", name, e));
+                    continue;
+                }
+            };
+            let result = probe::probe_stream(url, config.probe_seconds, config.probe_timeout_secs).await;
+            drop(lease);
+
+            match result {
+                Ok(measured) => {
+                    issues.extend(probe::evaluate_stream(name, &expected, &measured, &config));
+                    streams.push(StreamDiagnostics {
+                        stream: name.to_string(),
+                        expected,
+                        probe: Some(measured),
+                        error: None,
+                    });
+                }
+                Err(e) => {
+                    issues.push(DiagnosticIssue {
+                        kind: DiagnosticIssueKind::StreamUnavailable,
+                        source: name.to_string(),
+                        message: e.to_string(),
+                        details: serde_json::json!({ "errorMessage": e.to_string() }),
+                    });
+                    streams.push(StreamDiagnostics {
+                        stream: name.to_string(),
+                        expected,
+                        probe: None,
+                        error: Some(e.to_string()),
+                    });
+                }
+            }
+        }
+
+        // 2. 画像解析（ポーリングで更新された latest.jpg のみ）
+        let frame = self
+            .analyze_snapshot(&camera.camera_id, &config, &mut skipped)
+            .await;
+        if let Some(metrics) = &frame {
+            issues.extend(evaluate_frame(metrics, &config));
+        }
+
+        let mut states = self.states.write().await;
+        let state = states.entry(camera.camera_id.clone()).or_default();
+
+        // 計測できなかったソースは前回の問題を引き継ぐ（誤って解消扱いにしない）
+        if let Some(previous) = &state.last_report {
+            let measured: BTreeSet<&str> = streams
+                .iter()
+                .map(|s| s.stream.as_str())
+                .chain(frame.as_ref().map(|_| "snapshot"))
+                .collect();
+            issues.extend(
+                previous
+                    .issues
+                    .iter()
+                    .filter(|i| !measured.contains(i.source.as_str()))
+                    .cloned(),
+            );
+        }
+
+        let report = CameraHealthReport {
+            camera_id: camera.camera_id.clone(),
+            camera_name: camera.name.clone(),
+            lacis_id: camera.lacis_id.clone(),
+            checked_at: Utc::now(),
+            streams,
+            frame,
+            issues,
+            skipped,
+        };
+
+        let active: BTreeSet<DiagnosticIssueKind> = report.issues.iter().map(|i| i.kind).collect();
+        let raised: Vec<DiagnosticIssueKind> = active.difference(&state.active).copied().collect();
+        let cleared: Vec<DiagnosticIssueKind> = state.active.difference(&active).copied().collect();
+        state.active = active.clone();
+        state.last_report = Some(report.clone());
+        drop(states);
+
+        if !raised.is_empty() || !cleared.is_empty() {
+            let tid = camera.tid.as_deref().unwrap_or(&self.default_tid);
+            let fid = camera.fid.as_deref().unwrap_or(&self.default_fid);
+            self.notify(camera, tid, fid, &report, &raised, &cleared, &active)
+                .await;
+        }
+
+        debug!(
+            camera_id = %camera.camera_id,
+            issues = report.issues.len(),
+            skipped = report.skipped.len(),
+            "Camera diagnostics completed"
+        );
+        report
+    }
+
+    /// latest.jpg を解析して画像指標を算出
+    ///
+    /// 前回から更新されていない場合（ポーリング停止・オフライン）は None
+    async fn analyze_snapshot(
+        &self,
+        camera_id: &str,
+        config: &DiagnosticsConfig,
+        skipped: &mut Vec<String>,
+    ) -> Option<FrameMetrics> {
+        let path = self.snapshot_service.get_cache_path(camera_id);
+        let mtime = match tokio::fs::metadata(&path).await.and_then(|m| m.modified()) {
+            Ok(mtime) => mtime,
+            Err(_) => {
+                skipped.push("snapshot: no cached snapshot".to_string());
+                return None;
+            }
+        };
+
+        let mut states = self.states.write().await;
+        let state = states.entry(camera_id.to_string()).or_default();
+        if state.last_snapshot_mtime == Some(mtime) {
+            skipped.push("snapshot: not updated since last check".to_string());
+            return None;
+        }
+
+        let data = match tokio::fs::read(&path).await {
+            Ok(data) => data,
+            Err(e) => {
+                skipped.push(format!("snapshot: {}", e));
+                return None;
+            }
+        };
+        let analyzed = match AnalyzedFrame::from_jpeg(&data) {
+            Ok(frame) => frame,
+            Err(e) => {
+                skipped.push(format!("snapshot: decode failed: {}", e));
+                return None;
+            }
+        };
+        state.last_snapshot_mtime = Some(mtime);
+
+        state.identical_repeats = if state.last_fingerprint == Some(analyzed.fingerprint) {
+            state.identical_repeats + 1
+        } else {
+            0
+        };
+        state.last_fingerprint = Some(analyzed.fingerprint);
+
+        let scene_change_ratio = state
+            .baseline
+            .as_ref()
+            .map(|baseline| scene_change_ratio(baseline, &analyzed.grid, config.scene_shift_noise_floor));
+
+        let metrics = FrameMetrics {
+            mean_luma: analyzed.mean_luma,
+            stddev_luma: analyzed.stddev_luma,
+            laplacian_variance: analyzed.laplacian_variance,
+            // 初回を1回目として数える
+            identical_repeats: state.identical_repeats + 1,
+            scene_change_ratio,
+        };
+
+        // 基準は常に直近フレームに更新（緩やかな変化は追従し、診断間の急変のみ検出）
+        // 黒画面（遮蔽中）は基準にしない
+        if metrics.mean_luma > config.black_max_luma {
+            state.baseline = Some(analyzed.grid);
+        }
+
+        Some(metrics)
+    }
+
+    /// 新規問題の Paraclate 報告と RealtimeHub 通知
+    #[allow(clippy::too_many_arguments)]
+    async fn notify(
+        &self,
+        camera: &Camera,
+        tid: &str,
+        fid: &str,
+        report: &CameraHealthReport,
+        raised: &[DiagnosticIssueKind],
+        cleared: &[DiagnosticIssueKind],
+        active: &BTreeSet<DiagnosticIssueKind>,
+    ) {
+        info!(
+            camera_id = %camera.camera_id,
+            raised = ?raised,
+            cleared = ?cleared,
+            "Camera health changed"
+        );
+
+        if let Some(lacis_id) = camera.lacis_id.as_deref() {
+            for kind in raised {
+                let Some(issue) = report.issues.iter().find(|i| i.kind == *kind) else {
+                    continue;
+                };
+                let mut details = issue.details.clone();
+                if let Some(obj) = details.as_object_mut() {
+                    obj.insert("diagnostic".to_string(), serde_json::json!(kind.as_str()));
+                    obj.insert("source".to_string(), serde_json::json!(issue.source));
+                    obj.insert("message".to_string(), serde_json::json!(issue.message));
+                }
+                if let Err(e) = self
+                    .reporter
+                    .report_malfunction(tid, fid, lacis_id, kind.malfunction_type(), Some(details))
+                    .await
+                {
+                    warn!(
+                        camera_id = %camera.camera_id,
+                        issue = %kind,
+                        error = %e,
+                        "Failed to report camera diagnostic issue"
+                    );
+                }
+            }
+        }
+
+        let names = |kinds: &mut dyn Iterator<Item = &DiagnosticIssueKind>| -> Vec<String> {
+            kinds.map(|k| k.as_str().to_string()).collect()
+        };
+        self.realtime_hub
+            .broadcast(HubMessage::CameraHealth(CameraHealthMessage {
+                camera_id: camera.camera_id.clone(),
+                lacis_id: camera.lacis_id.clone(),
+                raised: names(&mut raised.iter()),
+                cleared: names(&mut cleared.iter()),
+                active: names(&mut active.iter()),
+                checked_at: report.checked_at.to_rfc3339(),
+            }))
+            .await;
+    }
+}
@@ -0,0 +1,260 @@
+//! ffprobe によるストリーム実測
+//!
+//! 計測窓（probe_seconds）分のビデオパケットを読み、
+//! パケット間隔から実FPS、サイズ合計から実ビットレートを算出する。
+
+use super::types::{
+    DiagnosticIssue, DiagnosticIssueKind, DiagnosticsConfig, StreamExpectation, StreamProbe,
+};
+use crate::Error;
+use serde::Deserialize;
+use serde_json::json;
+use std::time::Duration;
+use tokio::process::Command;
+
+#[derive(Debug, Deserialize)]
+struct FfprobeOutput {
+    #[serde(default)]
+    streams: Vec<FfprobeStream>,
+    #[serde(default)]
+    packets: Vec<FfprobePacket>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FfprobeStream {
+    codec_name: Option<String>,
+    width: Option<u32>,
+    height: Option<u32>,
+    avg_frame_rate: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FfprobePacket {
+    pts_time: Option<String>,
+    size: Option<String>,
+}
+
+/// RTSPストリームを ffprobe で計測
+///
+/// 呼び出し側で RtspManager のリースを保持していること
+pub async fn probe_stream(
+    rtsp_url: &str,
+    probe_seconds: u32,
+    timeout_secs: u64,
+) -> crate::Result<StreamProbe> {
+    use std::process::Stdio;
+
+    let interval = format!("%+{}", probe_seconds);
+    let child = Command::new("ffprobe")
+        .args([
+            "-v", "error",
+            "-rtsp_transport", "tcp",
+            "-select_streams", "v:0",
+            "-read_intervals", &interval,
+            "-show_entries", "stream=codec_name,width,height,avg_frame_rate:packet=pts_time,size",
+            "-of", "json",
+            rtsp_url,
+        ])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| Error::Internal(format!("ffprobe spawn failed: {}", e)))?;
+
+    let output = tokio::time::timeout(Duration::from_secs(timeout_secs), child.wait_with_output())
+        .await
+        .map_err(|_| Error::Internal(format!("ffprobe timeout ({}s)", timeout_secs)))?
+        .map_err(|e| Error::Internal(format!("ffprobe execution failed: {}", e)))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(Error::Internal(format!("ffprobe failed: {}", stderr.trim())));
+    }
+
+    parse_ffprobe_output(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// ffprobe の JSON 出力を解析
+pub fn parse_ffprobe_output(output: &str) -> crate::Result<StreamProbe> {
+    let parsed: FfprobeOutput = serde_json::from_str(output)
+        .map_err(|e| Error::Parse(format!("Invalid ffprobe output: {}", e)))?;
+
+    let stream = parsed
+        .streams
+        .first()
+        .ok_or_else(|| Error::Internal("ffprobe found no video stream".to_string()))?;
+
+    let mut pts: Vec<f64> = parsed
+        .packets
+        .iter()
+        .filter_map(|p| p.pts_time.as_deref()?.parse().ok())
+        .collect();
+    pts.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let total_bytes: u64 = parsed
+        .packets
+        .iter()
+        .filter_map(|p| p.size.as_deref()?.parse::<u64>().ok())
+        .sum();
+
+    let duration = match (pts.first(), pts.last()) {
+        (Some(first), Some(last)) if last > first => Some(last - first),
+        _ => None,
+    };
+
+    Ok(StreamProbe {
+        codec: stream.codec_name.clone(),
+        width: stream.width,
+        height: stream.height,
+        // n個のパケットは n-1 個の間隔
+        measured_fps: duration.map(|d| (pts.len() - 1) as f64 / d),
+        // 末尾パケット分の時間を含めるため (n / (n-1)) で補正
+        measured_bitrate_kbps: duration.map(|d| {
+            let span = d * pts.len() as f64 / (pts.len() - 1) as f64;
+            total_bytes as f64 * 8.0 / span / 1000.0
+        }),
+        declared_fps: stream.avg_frame_rate.as_deref().and_then(parse_rate),
+        packet_count: parsed.packets.len(),
+    })
+}
+
+/// "25/1" 形式のレートを解析
+fn parse_rate(rate: &str) -> Option<f64> {
+    let (num, den) = rate.split_once('/')?;
+    let num: f64 = num.trim().parse().ok()?;
+    let den: f64 = den.trim().parse().ok()?;
+    (den > 0.0 && num > 0.0).then(|| num / den)
+}
+
+/// "1920x1080" 形式の解像度を解析
+pub fn parse_resolution(resolution: &str) -> Option<(u32, u32)> {
+    let (w, h) = resolution.trim().split_once(['x', 'X', '*'])?;
+    Some((w.trim().parse().ok()?, h.trim().parse().ok()?))
+}
+
+/// コーデック名を正規化（ONVIF "H264" / ffprobe "h264" / "H.265" 等）
+pub fn normalize_codec(codec: &str) -> String {
+    let lower = codec.trim().to_lowercase().replace(['.', '-', ' '], "");
+    match lower.as_str() {
+        "h264" | "avc" | "avc1" => "h264".to_string(),
+        "h265" | "hevc" | "hvc1" => "hevc".to_string(),
+        "mjpeg" | "jpeg" | "mjpg" => "mjpeg".to_string(),
+        _ => lower,
+    }
+}
+
+/// 実測値を期待値と比較
+pub fn evaluate_stream(
+    stream: &str,
+    expected: &StreamExpectation,
+    probe: &StreamProbe,
+    config: &DiagnosticsConfig,
+) -> Vec<DiagnosticIssue> {
+    let mut issues = Vec::new();
+    let issue = |kind: DiagnosticIssueKind, message: String, details: serde_json::Value| {
+        DiagnosticIssue {
+            kind,
+            source: stream.to_string(),
+            message,
+            details,
+        }
+    };
+
+    if let (Some(expected_codec), Some(actual_codec)) = (&expected.codec, &probe.codec) {
+        if normalize_codec(expected_codec) != normalize_codec(actual_codec) {
+            issues.push(issue(
+                DiagnosticIssueKind::CodecMismatch,
+                format!("codec {} (expected {})", actual_codec, expected_codec),
+                json!({ "expected": expected_codec, "actual": actual_codec }),
+            ));
+        }
+    }
+
+    if let (Some((ew, eh)), Some(aw), Some(ah)) = (
+        expected.resolution.as_deref().and_then(parse_resolution),
+        probe.width,
+        probe.height,
+    ) {
+        if (ew, eh) != (aw, ah) {
+            issues.push(issue(
+                DiagnosticIssueKind::ResolutionMismatch,
+                format!("resolution {}x{} (expected {}x{})", aw, ah, ew, eh),
+                json!({ "expected": format!("{}x{}", ew, eh), "actual": format!("{}x{}", aw, ah) }),
+            ));
+        }
+    }
+
+    if let (Some(expected_fps), Some(actual_fps)) = (expected.fps, probe.measured_fps) {
+        if expected_fps > 0 && actual_fps < expected_fps as f64 * config.min_fps_ratio {
+            issues.push(issue(
+                DiagnosticIssueKind::LowFps,
+                format!("{:.1} fps (expected {})", actual_fps, expected_fps),
+                json!({ "expectedFps": expected_fps, "currentFps": actual_fps }),
+            ));
+        }
+    }
+
+    if let (Some(expected_kbps), Some(actual_kbps)) =
+        (expected.bitrate_kbps, probe.measured_bitrate_kbps)
+    {
+        if expected_kbps > 0 && actual_kbps < expected_kbps as f64 * config.min_bitrate_ratio {
+            issues.push(issue(
+                DiagnosticIssueKind::LowBitrate,
+                format!("{:.0} kbps (expected {})", actual_kbps, expected_kbps),
+                json!({ "expectedKbps": expected_kbps, "currentKbps": actual_kbps }),
+            ));
+        }
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ffprobe_output() {
+        // 25fps / 5000 byte packets x 11 = 0.4s
+        let packets: Vec<String> = (0..11)
+            .map(|i| format!(r#"{{"pts_time":"{:.2}","size":"5000"}}"#, i as f64 * 0.04))
+            .collect();
+        let output = format!(
+            r#"{{"packets":[{}],"streams":[{{"codec_name":"h264","width":1920,"height":1080,"avg_frame_rate":"25/1"}}]}}"#,
+            packets.join(",")
+        );
+
+        let probe = parse_ffprobe_output(&output).unwrap();
+        assert_eq!(probe.codec.as_deref(), Some("h264"));
+        assert_eq!((probe.width, probe.height), (Some(1920), Some(1080)));
+        assert!((probe.measured_fps.unwrap() - 25.0).abs() < 0.01);
+        assert!((probe.measured_bitrate_kbps.unwrap() - 1000.0).abs() < 0.5);
+        assert_eq!(probe.declared_fps, Some(25.0));
+    }
+
+    #[test]
+    fn test_evaluate_stream() {
+        let expected = StreamExpectation {
+            codec: Some("H.265".to_string()),
+            resolution: Some("1920x1080".to_string()),
+            fps: Some(20),
+            bitrate_kbps: Some(2048),
+        };
+        let probe = StreamProbe {
+            codec: Some("hevc".to_string()),
+            width: Some(1280),
+            height: Some(720),
+            measured_fps: Some(8.0),
+            measured_bitrate_kbps: Some(1500.0),
+            ..Default::default()
+        };
+
+        let kinds: Vec<_> = evaluate_stream("main", &expected, &probe, &DiagnosticsConfig::default())
+            .into_iter()
+            .map(|i| i.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![DiagnosticIssueKind::ResolutionMismatch, DiagnosticIssueKind::LowFps]
+        );
+    }
+}
@@ -0,0 +1,220 @@
+//! Camera diagnostics types
+
+use crate::paraclate_client::types::CameraMalfunctionType;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// settings テーブルのキー
+pub const DIAGNOSTICS_CONFIG_KEY: &str = "camera_diagnostics";
+
+/// 診断設定（settings.camera_diagnostics）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DiagnosticsConfig {
+    pub enabled: bool,
+    /// 全カメラ診断の間隔（分）
+    pub interval_minutes: u32,
+    /// ffprobe でパケットを計測する秒数
+    pub probe_seconds: u32,
+    /// ffprobe プロセスのタイムアウト（秒）
+    pub probe_timeout_secs: u64,
+    /// 実測FPS / 設定FPS がこれ未満で low_fps
+    pub min_fps_ratio: f64,
+    /// 実測ビットレート / 設定ビットレート がこれ未満で low_bitrate
+    pub min_bitrate_ratio: f64,
+    /// 平均輝度がこれ以下かつ低コントラストで black_frame
+    pub black_max_luma: f64,
+    /// 輝度標準偏差がこれ以下を低コントラストとみなす
+    pub black_max_stddev: f64,
+    /// ラプラシアン分散がこれ未満で blurred
+    pub blur_min_laplacian: f64,
+    /// 同一フレームがこの回数連続したら frozen_frame
+    pub frozen_min_repeats: u32,
+    /// 基準フレームとの変化セル割合がこれ以上で scene_shift
+    pub scene_shift_ratio: f32,
+    /// scene_shift 判定のセルノイズフロア（0-255）
+    pub scene_shift_noise_floor: i32,
+}
+
+impl Default for DiagnosticsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_minutes: 30,
+            probe_seconds: 5,
+            probe_timeout_secs: 20,
+            min_fps_ratio: 0.7,
+            min_bitrate_ratio: 0.3,
+            black_max_luma: 16.0,
+            black_max_stddev: 6.0,
+            blur_min_laplacian: 15.0,
+            frozen_min_repeats: 3,
+            scene_shift_ratio: 0.6,
+            scene_shift_noise_floor: 40,
+        }
+    }
+}
+
+impl DiagnosticsConfig {
+    pub fn validate(&self) -> crate::Result<()> {
+        if self.interval_minutes < 5 {
+            return Err(crate::Error::Validation(
+                "interval_minutes must be >= 5".to_string(),
+            ));
+        }
+        if !(1..=30).contains(&self.probe_seconds) {
+            return Err(crate::Error::Validation(
+                "probe_seconds must be between 1 and 30".to_string(),
+            ));
+        }
+        if self.probe_timeout_secs <= self.probe_seconds as u64 {
+            return Err(crate::Error::Validation(
+                "probe_timeout_secs must be greater than probe_seconds".to_string(),
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.min_fps_ratio)
+            || !(0.0..=1.0).contains(&self.min_bitrate_ratio)
+            || !(0.0..=1.0).contains(&self.scene_shift_ratio)
+        {
+            return Err(crate::Error::Validation(
+                "ratios must be between 0.0 and 1.0".to_string(),
+            ));
+        }
+        if self.frozen_min_repeats < 2 {
+            return Err(crate::Error::Validation(
+                "frozen_min_repeats must be >= 2".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// 診断で検出する問題の種別
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagnosticIssueKind {
+    /// ffprobe でストリームを開けない
+    StreamUnavailable,
+    CodecMismatch,
+    ResolutionMismatch,
+    LowFps,
+    LowBitrate,
+    BlackFrame,
+    FrozenFrame,
+    Blurred,
+    SceneShift,
+}
+
+impl DiagnosticIssueKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::StreamUnavailable => "stream_unavailable",
+            Self::CodecMismatch => "codec_mismatch",
+            Self::ResolutionMismatch => "resolution_mismatch",
+            Self::LowFps => "low_fps",
+            Self::LowBitrate => "low_bitrate",
+            Self::BlackFrame => "black_frame",
+            Self::FrozenFrame => "frozen_frame",
+            Self::Blurred => "blurred",
+            Self::SceneShift => "scene_shift",
+        }
+    }
+
+    /// Paraclate へ報告する不調種別
+    pub fn malfunction_type(&self) -> CameraMalfunctionType {
+        match self {
+            Self::StreamUnavailable => CameraMalfunctionType::StreamError,
+            Self::CodecMismatch | Self::ResolutionMismatch => CameraMalfunctionType::StreamMismatch,
+            Self::LowFps => CameraMalfunctionType::LowFps,
+            Self::LowBitrate => CameraMalfunctionType::LowBitrate,
+            Self::BlackFrame => CameraMalfunctionType::BlackFrame,
+            Self::FrozenFrame => CameraMalfunctionType::FrozenFrame,
+            Self::Blurred => CameraMalfunctionType::Blurred,
+            Self::SceneShift => CameraMalfunctionType::SceneShift,
+        }
+    }
+}
+
+impl std::fmt::Display for DiagnosticIssueKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// 検出された問題
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiagnosticIssue {
+    pub kind: DiagnosticIssueKind,
+    /// "main" | "sub" | "snapshot"
+    pub source: String,
+    pub message: String,
+    /// 実測値・期待値など
+    pub details: serde_json::Value,
+}
+
+/// ffprobe による実測値
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StreamProbe {
+    pub codec: Option<String>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    /// 計測窓内のパケット間隔から算出
+    pub measured_fps: Option<f64>,
+    /// 計測窓内のパケットサイズ合計から算出（kbps）
+    pub measured_bitrate_kbps: Option<f64>,
+    /// ストリームヘッダ上のFPS（avg_frame_rate）
+    pub declared_fps: Option<f64>,
+    pub packet_count: usize,
+}
+
+/// Camera の能力情報（期待値）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StreamExpectation {
+    pub codec: Option<String>,
+    pub resolution: Option<String>,
+    pub fps: Option<i32>,
+    pub bitrate_kbps: Option<i32>,
+}
+
+/// ストリーム単位の診断結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamDiagnostics {
+    /// "main" | "sub"
+    pub stream: String,
+    pub expected: StreamExpectation,
+    pub probe: Option<StreamProbe>,
+    pub error: Option<String>,
+}
+
+/// スナップショット画像の品質指標
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FrameMetrics {
+    pub mean_luma: f64,
+    pub stddev_luma: f64,
+    /// ラプラシアン分散（小さいほどボケ）
+    pub laplacian_variance: f64,
+    /// 同一フレームの連続数（1 = 前回診断と異なるフレーム）
+    pub identical_repeats: u32,
+    /// 基準フレームとの変化セル割合
+    pub scene_change_ratio: Option<f32>,
+}
+
+/// カメラ単位の診断レポート
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CameraHealthReport {
+    pub camera_id: String,
+    pub camera_name: String,
+    pub lacis_id: Option<String>,
+    pub checked_at: DateTime<Utc>,
+    pub streams: Vec<StreamDiagnostics>,
+    pub frame: Option<FrameMetrics>,
+    pub issues: Vec<DiagnosticIssue>,
+    /// RTSP使用中などで一部の計測を省略した理由
+    pub skipped: Vec<String>,
+}
+
+impl CameraHealthReport {
+    pub fn healthy(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
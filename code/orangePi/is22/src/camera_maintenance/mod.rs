@@ -0,0 +1,498 @@
+//! CameraMaintenance - ONVIF によるファームウェア・時刻の定期メンテナンス
+//!
+//! ## 概要
+//! - GetDeviceInformation: スキャン時に一度だけ取得していた firmware_version を定期更新し、
+//!   変更を camera_firmware_history へ記録
+//! - GetSystemDateAndTime: カメラ時刻のずれを計測し、閾値超過を skewed としてフラグ
+//!   （captured_at の整合性確保）。auto_correct_clock 有効時は SetSystemDateAndTime / SetNTP で補正
+//! - ファームウェアインベントリ: メーカー/機種ごとのバージョン分布
+//!
+//! ONVIF 認証は rtsp_username / rtsp_password を使用する（TapoPtzClient と同じ）。
+
+pub mod onvif;
+pub mod types;
+
+pub use types::*;
+
+use crate::config_store::{Camera, ConfigStore};
+use chrono::{DateTime, Utc};
+use onvif::OnvifDeviceClient;
+use sqlx::{MySqlPool, Row};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+/// 起動後、初回チェックまでの待機（秒）
+const INITIAL_DELAY_SECS: u64 = 180;
+
+/// カメラメンテナンスサービス
+pub struct CameraMaintenanceService {
+    pool: MySqlPool,
+    config_store: Arc<ConfigStore>,
+    config: RwLock<MaintenanceConfig>,
+    /// カメラごとの最新時刻チェック結果
+    clocks: RwLock<HashMap<String, ClockStatus>>,
+}
+
+impl CameraMaintenanceService {
+    pub fn new(pool: MySqlPool, config_store: Arc<ConfigStore>) -> Self {
+        Self {
+            pool,
+            config_store,
+            config: RwLock::new(MaintenanceConfig::default()),
+            clocks: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// 設定をDBから読み込み（未設定ならデフォルト）
+    pub async fn load_config(&self) -> crate::Result<()> {
+        let stored = self
+            .config_store
+            .service()
+            .get_setting(MAINTENANCE_CONFIG_KEY)
+            .await?
+            .and_then(|v| serde_json::from_value(v).ok());
+        if let Some(config) = stored {
+            *self.config.write().await = config;
+        }
+        Ok(())
+    }
+
+    pub async fn config(&self) -> MaintenanceConfig {
+        self.config.read().await.clone()
+    }
+
+    /// 設定を保存
+    pub async fn set_config(&self, mut config: MaintenanceConfig) -> crate::Result<MaintenanceConfig> {
+        config.validate()?;
+        config.ntp_server = config.ntp_server.map(|s| s.trim().to_string());
+        self.config_store
+            .service()
+            .set_setting(MAINTENANCE_CONFIG_KEY, serde_json::to_value(&config)?)
+            .await?;
+        *self.config.write().await = config.clone();
+        Ok(config)
+    }
+
+    /// 定期チェックループ（`shutdown` がキャンセルされるまで。TaskSupervisor から起動）
+    pub async fn run(self: Arc<Self>, shutdown: CancellationToken) {
+        if let Err(e) = self.load_config().await {
+            warn!(error = %e, "Camera maintenance: failed to load config, using defaults");
+        }
+
+        let mut delay = std::time::Duration::from_secs(INITIAL_DELAY_SECS);
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = tokio::time::sleep(delay) => {}
+            }
+            let config = self.config().await;
+            if config.enabled {
+                let results = self.run_all().await;
+                info!(
+                    cameras = results.len(),
+                    firmware_changes = results.iter().filter(|r| r.firmware_change.is_some()).count(),
+                    skewed = results.iter().filter(|r| r.clock.skewed).count(),
+                    "Camera maintenance cycle completed"
+                );
+            }
+            delay = std::time::Duration::from_secs(config.interval_minutes.max(1) as u64 * 60);
+        }
+    }
+
+    /// 有効な全カメラを順にチェック
+    pub async fn run_all(&self) -> Vec<CameraMaintenanceResult> {
+        let cameras: Vec<Camera> = self
+            .config_store
+            .get_cached_cameras()
+            .await
+            .into_iter()
+            .filter(|c| c.enabled)
+            .collect();
+
+        let mut results = Vec::with_capacity(cameras.len());
+        for camera in &cameras {
+            results.push(self.check_camera(camera).await);
+        }
+
+        if results.iter().any(|r| r.firmware_change.is_some()) {
+            if let Err(e) = self.config_store.refresh_cache().await {
+                warn!(error = %e, "Camera maintenance: failed to refresh camera cache");
+            }
+        }
+        results
+    }
+
+    /// 1カメラをチェック（単体実行用。キャッシュも更新する）
+    pub async fn run_camera(&self, camera: &Camera) -> CameraMaintenanceResult {
+        let result = self.check_camera(camera).await;
+        if result.firmware_change.is_some() {
+            if let Err(e) = self.config_store.refresh_cache().await {
+                warn!(error = %e, "Camera maintenance: failed to refresh camera cache");
+            }
+        }
+        result
+    }
+
+    async fn check_camera(&self, camera: &Camera) -> CameraMaintenanceResult {
+        let config = self.config().await;
+        let checked_at = Utc::now();
+        let mut clock = ClockStatus {
+            camera_id: camera.camera_id.clone(),
+            camera_name: camera.name.clone(),
+            checked_at,
+            camera_utc: None,
+            skew_ms: None,
+            round_trip_ms: None,
+            date_time_type: None,
+            skewed: false,
+            correction: None,
+            correction_error: None,
+            error: None,
+        };
+
+        let Some(client) = OnvifDeviceClient::for_camera(camera, config.request_timeout_secs) else {
+            clock.error = Some("No ONVIF endpoint (onvif_endpoint / ip_address not set)".to_string());
+            self.clocks.write().await.insert(camera.camera_id.clone(), clock.clone());
+            return CameraMaintenanceResult {
+                camera_id: camera.camera_id.clone(),
+                device: None,
+                device_error: clock.error.clone(),
+                firmware_change: None,
+                clock,
+            };
+        };
+
+        // 1. 時刻（認証不要のため先に計測し、以降の WS-Security に反映）
+        let mut skew_ms = 0;
+        match client.get_system_date_and_time().await {
+            Ok(sample) => {
+                skew_ms = sample.skew_ms();
+                clock.camera_utc = Some(sample.clock.utc);
+                clock.skew_ms = Some(skew_ms);
+                clock.round_trip_ms = Some(sample.round_trip_ms());
+                clock.date_time_type = sample.clock.date_time_type.clone();
+                clock.skewed = skew_ms.abs() > config.max_clock_skew_secs as i64 * 1000;
+            }
+            Err(e) => clock.error = Some(e.to_string()),
+        }
+
+        if clock.skewed {
+            warn!(
+                camera_id = %camera.camera_id,
+                skew_ms = skew_ms,
+                date_time_type = ?clock.date_time_type,
+                "Camera clock skew exceeds threshold"
+            );
+            if config.auto_correct_clock {
+                let correction = match &config.ntp_server {
+                    Some(_) => ClockCorrection::Ntp,
+                    None => ClockCorrection::Manual,
+                };
+                clock.correction = Some(correction);
+                if let Err(e) = self.correct_clock(&client, &config, skew_ms).await {
+                    warn!(camera_id = %camera.camera_id, error = %e, "Camera clock correction failed");
+                    clock.correction_error = Some(e.to_string());
+                }
+            }
+            if let Err(e) = self.record_clock_event(&clock).await {
+                warn!(camera_id = %camera.camera_id, error = %e, "Failed to record clock event");
+            }
+        }
+
+        // 2. デバイス情報
+        let (device, device_error, firmware_change) = match client.get_device_information().await {
+            Ok(device) => {
+                let change = match self.record_firmware(camera, &device, checked_at).await {
+                    Ok(change) => change,
+                    Err(e) => {
+                        warn!(camera_id = %camera.camera_id, error = %e, "Failed to record firmware");
+                        None
+                    }
+                };
+                (Some(device), None, change)
+            }
+            Err(e) => (None, Some(e.to_string()), None),
+        };
+
+        self.clocks.write().await.insert(camera.camera_id.clone(), clock.clone());
+        CameraMaintenanceResult {
+            camera_id: camera.camera_id.clone(),
+            device,
+            device_error,
+            firmware_change,
+            clock,
+        }
+    }
+
+    /// 手動時刻設定、NTPサーバー設定があれば続けて NTP モードへ切替
+    async fn correct_clock(
+        &self,
+        client: &OnvifDeviceClient,
+        config: &MaintenanceConfig,
+        skew_ms: i64,
+    ) -> crate::Result<()> {
+        client.set_manual_time(Utc::now(), skew_ms).await?;
+        if let Some(server) = &config.ntp_server {
+            // 手動設定後はカメラ時刻がサーバーと一致している
+            client.set_ntp(server, 0).await?;
+        }
+        info!(endpoint = %client.endpoint(), skew_ms = skew_ms, "Camera clock corrected");
+        Ok(())
+    }
+
+    async fn record_clock_event(&self, clock: &ClockStatus) -> crate::Result<()> {
+        sqlx::query(
+            "INSERT INTO camera_clock_events \
+             (camera_id, skew_ms, date_time_type, correction, correction_error, checked_at) \
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&clock.camera_id)
+        .bind(clock.skew_ms.unwrap_or_default())
+        .bind(&clock.date_time_type)
+        .bind(clock.correction.map(|c| c.as_str()))
+        .bind(&clock.correction_error)
+        .bind(clock.checked_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// firmware_version の変化を履歴に記録し cameras を更新
+    async fn record_firmware(
+        &self,
+        camera: &Camera,
+        device: &DeviceInformation,
+        detected_at: DateTime<Utc>,
+    ) -> crate::Result<Option<FirmwareChange>> {
+        let Some(version) = device.firmware_version.clone() else {
+            return Ok(None);
+        };
+        let previous = camera.firmware_version.clone().filter(|v| !v.is_empty());
+        if previous.as_deref() == Some(version.as_str()) {
+            return Ok(None);
+        }
+
+        let manufacturer = device.manufacturer.clone().or_else(|| camera.manufacturer.clone());
+        let model = device.model.clone().or_else(|| camera.model.clone());
+
+        let mut tx = self.pool.begin().await?;
+        let history_id = sqlx::query(
+            "INSERT INTO camera_firmware_history \
+             (camera_id, previous_version, firmware_version, manufacturer, model, detected_at) \
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&camera.camera_id)
+        .bind(&previous)
+        .bind(&version)
+        .bind(&manufacturer)
+        .bind(&model)
+        .bind(detected_at)
+        .execute(&mut *tx)
+        .await?
+        .last_insert_id();
+
+        // メーカー/機種等は未設定の場合のみ補完（手動編集を上書きしない）
+        sqlx::query(
+            "UPDATE cameras SET firmware_version = ?, \
+             manufacturer = COALESCE(manufacturer, ?), model = COALESCE(model, ?), \
+             serial_number = COALESCE(serial_number, ?), hardware_id = COALESCE(hardware_id, ?) \
+             WHERE camera_id = ?",
+        )
+        .bind(&version)
+        .bind(&device.manufacturer)
+        .bind(&device.model)
+        .bind(&device.serial_number)
+        .bind(&device.hardware_id)
+        .bind(&camera.camera_id)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        if previous.is_some() {
+            info!(
+                camera_id = %camera.camera_id,
+                previous = ?previous,
+                firmware = %version,
+                "Camera firmware change detected"
+            );
+        }
+
+        Ok(Some(FirmwareChange {
+            history_id,
+            camera_id: camera.camera_id.clone(),
+            previous_version: previous,
+            firmware_version: version,
+            manufacturer,
+            model,
+            detected_at,
+        }))
+    }
+
+    /// 全カメラの最新時刻チェック結果
+    pub async fn clock_statuses(&self) -> Vec<ClockStatus> {
+        let mut statuses: Vec<_> = self.clocks.read().await.values().cloned().collect();
+        statuses.sort_by(|a, b| a.camera_id.cmp(&b.camera_id));
+        statuses
+    }
+
+    /// ファームウェア変更履歴（新しい順）
+    pub async fn firmware_history(
+        &self,
+        camera_id: Option<&str>,
+        limit: u32,
+    ) -> crate::Result<Vec<FirmwareChange>> {
+        let mut sql = String::from(
+            "SELECT history_id, camera_id, previous_version, firmware_version, manufacturer, model, detected_at \
+             FROM camera_firmware_history",
+        );
+        if camera_id.is_some() {
+            sql.push_str(" WHERE camera_id = ?");
+        }
+        sql.push_str(" ORDER BY detected_at DESC, history_id DESC LIMIT ?");
+
+        let mut query = sqlx::query(&sql);
+        if let Some(camera_id) = camera_id {
+            query = query.bind(camera_id);
+        }
+        let rows = query.bind(limit).fetch_all(&self.pool).await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| FirmwareChange {
+                history_id: row.get("history_id"),
+                camera_id: row.get("camera_id"),
+                previous_version: row.get("previous_version"),
+                firmware_version: row.get("firmware_version"),
+                manufacturer: row.get("manufacturer"),
+                model: row.get("model"),
+                detected_at: row.get("detected_at"),
+            })
+            .collect())
+    }
+
+    /// フリートのファームウェアインベントリ（論理削除済みを除く登録カメラ）
+    pub async fn firmware_inventory(&self) -> FirmwareInventory {
+        let cameras = self
+            .config_store
+            .get_cached_cameras()
+            .await
+            .into_iter()
+            .map(|c| InventoryCamera {
+                camera_id: c.camera_id,
+                name: c.name,
+                manufacturer: c.manufacturer,
+                model: c.model,
+                firmware_version: c.firmware_version,
+            })
+            .collect();
+        build_inventory(cameras, Utc::now())
+    }
+}
+
+/// メーカー/機種ごとにファームウェアバージョンを集計
+pub fn build_inventory(cameras: Vec<InventoryCamera>, generated_at: DateTime<Utc>) -> FirmwareInventory {
+    let label = |value: &Option<String>| {
+        value
+            .as_deref()
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .unwrap_or("unknown")
+            .to_string()
+    };
+
+    let total_cameras = cameras.len();
+    let mut grouped: BTreeMap<(String, String), BTreeMap<Option<String>, Vec<String>>> = BTreeMap::new();
+    for camera in cameras {
+        let version = camera.firmware_version.filter(|v| !v.is_empty());
+        grouped
+            .entry((label(&camera.manufacturer), label(&camera.model)))
+            .or_default()
+            .entry(version)
+            .or_default()
+            .push(camera.camera_id);
+    }
+
+    let models = grouped
+        .into_iter()
+        .map(|((manufacturer, model), versions)| {
+            let known_versions = versions.keys().filter(|v| v.is_some()).count();
+            let versions: Vec<_> = versions
+                .into_iter()
+                .map(|(firmware_version, mut camera_ids)| {
+                    camera_ids.sort();
+                    FirmwareVersionGroup {
+                        firmware_version,
+                        camera_ids,
+                    }
+                })
+                .collect();
+            ModelInventory {
+                manufacturer,
+                model,
+                camera_count: versions.iter().map(|v| v.camera_ids.len()).sum(),
+                versions,
+                mixed_versions: known_versions > 1,
+            }
+        })
+        .collect();
+
+    FirmwareInventory {
+        generated_at,
+        total_cameras,
+        models,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn camera(id: &str, manufacturer: Option<&str>, model: Option<&str>, fw: Option<&str>) -> InventoryCamera {
+        InventoryCamera {
+            camera_id: id.to_string(),
+            name: id.to_string(),
+            manufacturer: manufacturer.map(str::to_string),
+            model: model.map(str::to_string),
+            firmware_version: fw.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_build_inventory_groups_by_model() {
+        let inventory = build_inventory(
+            vec![
+                camera("c3", Some("TP-Link"), Some("Tapo C200"), Some("1.3.6")),
+                camera("c1", Some("TP-Link"), Some("Tapo C200"), Some("1.3.6")),
+                camera("c2", Some("TP-Link"), Some("Tapo C200"), Some("1.3.4")),
+                camera("c4", Some("TP-Link"), Some("Tapo C210"), None),
+                camera("c5", None, None, Some("")),
+            ],
+            Utc::now(),
+        );
+
+        assert_eq!(inventory.total_cameras, 5);
+        let labels: Vec<_> = inventory
+            .models
+            .iter()
+            .map(|m| (m.manufacturer.as_str(), m.model.as_str(), m.camera_count))
+            .collect();
+        assert_eq!(
+            labels,
+            vec![
+                ("TP-Link", "Tapo C200", 3),
+                ("TP-Link", "Tapo C210", 1),
+                ("unknown", "unknown", 1),
+            ]
+        );
+
+        let c200 = &inventory.models[0];
+        assert!(c200.mixed_versions);
+        assert_eq!(c200.versions[1].firmware_version.as_deref(), Some("1.3.6"));
+        assert_eq!(c200.versions[1].camera_ids, vec!["c1", "c3"]);
+        assert!(!inventory.models[1].mixed_versions);
+        assert_eq!(inventory.models[2].versions[0].firmware_version, None);
+    }
+}
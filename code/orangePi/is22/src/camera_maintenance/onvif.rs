@@ -0,0 +1,302 @@
+//! ONVIF Device Management クライアント
+//!
+//! GetDeviceInformation / GetSystemDateAndTime / SetSystemDateAndTime / SetNTP のみを扱う。
+//! WS-Security の Created はカメラ時刻基準で検証されるため、
+//! 時刻ずれが判明している場合はその分ずらして生成する（大きくずれたカメラの補正に必要）。
+
+use super::types::{CameraClock, DeviceInformation};
+use crate::config_store::Camera;
+use crate::error::{Error, Result};
+use crate::ipcam_scan::{extract_xml_value, generate_ws_security_header, xml_escape};
+use chrono::{DateTime, Datelike, Duration, TimeZone, Timelike, Utc};
+use reqwest::Client;
+
+const DEVICE_NS: &str = "http://www.onvif.org/ver10/device/wsdl";
+const SCHEMA_NS: &str = "http://www.onvif.org/ver10/schema";
+
+/// GetSystemDateAndTime の応答と送受信時刻
+pub struct ClockSample {
+    pub clock: CameraClock,
+    pub sent_at: DateTime<Utc>,
+    pub received_at: DateTime<Utc>,
+}
+
+impl ClockSample {
+    /// カメラ時刻 - 往復の中間時刻（ミリ秒）
+    pub fn skew_ms(&self) -> i64 {
+        clock_skew_ms(self.clock.utc, self.sent_at, self.received_at)
+    }
+
+    pub fn round_trip_ms(&self) -> i64 {
+        (self.received_at - self.sent_at).num_milliseconds()
+    }
+}
+
+/// ONVIF デバイスサービスクライアント
+pub struct OnvifDeviceClient {
+    endpoint: String,
+    username: String,
+    password: String,
+    client: Client,
+}
+
+impl OnvifDeviceClient {
+    pub fn new(endpoint: &str, username: &str, password: &str, timeout_secs: u64) -> Self {
+        Self {
+            endpoint: endpoint.to_string(),
+            username: username.to_string(),
+            password: password.to_string(),
+            client: Client::builder()
+                .timeout(std::time::Duration::from_secs(timeout_secs))
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Camera の登録情報から生成（エンドポイントが決まらない場合 None）
+    pub fn for_camera(camera: &Camera, timeout_secs: u64) -> Option<Self> {
+        let endpoint = device_service_url(camera)?;
+        Some(Self::new(
+            &endpoint,
+            camera.rtsp_username.as_deref().unwrap_or_default(),
+            camera.rtsp_password.as_deref().unwrap_or_default(),
+            timeout_secs,
+        ))
+    }
+
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// GetDeviceInformation
+    pub async fn get_device_information(&self) -> Result<DeviceInformation> {
+        let body = format!(r#"<tds:GetDeviceInformation xmlns:tds="{}"/>"#, DEVICE_NS);
+        let xml = self.call("GetDeviceInformation", &body, Some(0)).await?;
+        Ok(parse_device_information(&xml))
+    }
+
+    /// GetSystemDateAndTime（認証不要のため時刻ずれに関係なく取得できる）
+    pub async fn get_system_date_and_time(&self) -> Result<ClockSample> {
+        let body = format!(r#"<tds:GetSystemDateAndTime xmlns:tds="{}"/>"#, DEVICE_NS);
+        let sent_at = Utc::now();
+        let xml = self.call("GetSystemDateAndTime", &body, None).await?;
+        let received_at = Utc::now();
+        let clock = parse_system_date_and_time(&xml)?;
+        Ok(ClockSample {
+            clock,
+            sent_at,
+            received_at,
+        })
+    }
+
+    /// SetSystemDateAndTime（Manual, UTC）。タイムゾーンは変更しない
+    pub async fn set_manual_time(&self, now: DateTime<Utc>, skew_ms: i64) -> Result<()> {
+        let body = format!(
+            r#"<tds:SetSystemDateAndTime xmlns:tds="{}" xmlns:tt="{}"><tds:DateTimeType>Manual</tds:DateTimeType><tds:DaylightSavings>false</tds:DaylightSavings>{}</tds:SetSystemDateAndTime>"#,
+            DEVICE_NS,
+            SCHEMA_NS,
+            utc_date_time_xml(now)
+        );
+        self.call("SetSystemDateAndTime", &body, Some(skew_ms)).await?;
+        Ok(())
+    }
+
+    /// SetNTP + DateTimeType=NTP
+    pub async fn set_ntp(&self, server: &str, skew_ms: i64) -> Result<()> {
+        let body = format!(
+            r#"<tds:SetNTP xmlns:tds="{}" xmlns:tt="{}"><tds:FromDHCP>false</tds:FromDHCP><tds:NTPManual>{}</tds:NTPManual></tds:SetNTP>"#,
+            DEVICE_NS,
+            SCHEMA_NS,
+            ntp_host_xml(server)
+        );
+        self.call("SetNTP", &body, Some(skew_ms)).await?;
+
+        let body = format!(
+            r#"<tds:SetSystemDateAndTime xmlns:tds="{}"><tds:DateTimeType>NTP</tds:DateTimeType><tds:DaylightSavings>false</tds:DaylightSavings></tds:SetSystemDateAndTime>"#,
+            DEVICE_NS
+        );
+        self.call("SetSystemDateAndTime", &body, Some(skew_ms)).await?;
+        Ok(())
+    }
+
+    /// SOAPリクエスト送信
+    ///
+    /// `auth_skew_ms` が Some なら WS-Security ヘッダーを付与（Created をずれ分補正）
+    async fn call(&self, action: &str, body: &str, auth_skew_ms: Option<i64>) -> Result<String> {
+        let header = auth_skew_ms
+            .map(|skew| {
+                let created_at = Utc::now() + Duration::milliseconds(skew);
+                format!(
+                    "<s:Header>{}</s:Header>",
+                    generate_ws_security_header(&self.username, &self.password, created_at)
+                )
+            })
+            .unwrap_or_default();
+        let envelope = format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?><s:Envelope xmlns:s="http://www.w3.org/2003/05/soap-envelope">{}<s:Body>{}</s:Body></s:Envelope>"#,
+            header, body
+        );
+
+        let response = self
+            .client
+            .post(&self.endpoint)
+            .header("Content-Type", "application/soap+xml; charset=utf-8")
+            .body(envelope)
+            .send()
+            .await
+            .map_err(|e| Error::Network(format!("ONVIF {} request failed: {}", action, e)))?;
+
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        if let Some(reason) = soap_fault_reason(&text) {
+            return Err(Error::Network(format!("ONVIF {} fault: {}", action, reason)));
+        }
+        if !status.is_success() {
+            return Err(Error::Network(format!(
+                "ONVIF {} failed with status {}",
+                action, status
+            )));
+        }
+        Ok(text)
+    }
+}
+
+/// デバイスサービスURL（onvif_endpoint 優先、なければ ip_address + onvif_port）
+pub fn device_service_url(camera: &Camera) -> Option<String> {
+    if let Some(endpoint) = camera.onvif_endpoint.as_deref().filter(|e| !e.trim().is_empty()) {
+        return Some(endpoint.trim().to_string());
+    }
+    let ip = camera.ip_address.as_deref().filter(|ip| !ip.is_empty())?;
+    let port = camera.onvif_port.filter(|p| *p > 0).unwrap_or(80);
+    Some(format!("http://{}:{}/onvif/device_service", ip, port))
+}
+
+/// カメラ時刻と往復の中間時刻との差（ミリ秒）
+///
+/// ONVIF の時刻は秒精度のため ±1秒の誤差を含む
+pub fn clock_skew_ms(camera_utc: DateTime<Utc>, sent_at: DateTime<Utc>, received_at: DateTime<Utc>) -> i64 {
+    let midpoint = sent_at + (received_at - sent_at) / 2;
+    (camera_utc - midpoint).num_milliseconds()
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.filter(|v| !v.is_empty())
+}
+
+/// GetDeviceInformationResponse を解析
+pub fn parse_device_information(xml: &str) -> DeviceInformation {
+    DeviceInformation {
+        manufacturer: non_empty(extract_xml_value(xml, "Manufacturer")),
+        model: non_empty(extract_xml_value(xml, "Model")),
+        firmware_version: non_empty(extract_xml_value(xml, "FirmwareVersion")),
+        serial_number: non_empty(extract_xml_value(xml, "SerialNumber")),
+        hardware_id: non_empty(extract_xml_value(xml, "HardwareId")),
+    }
+}
+
+/// GetSystemDateAndTimeResponse を解析（UTCDateTime のみ使用）
+pub fn parse_system_date_and_time(xml: &str) -> Result<CameraClock> {
+    // LocalDateTime と同名の子要素を持つため UTCDateTime 以降に限定する
+    let utc_start = xml
+        .find("UTCDateTime>")
+        .ok_or_else(|| Error::Parse("UTCDateTime not found in response".to_string()))?;
+    let utc_xml = &xml[utc_start..];
+    let utc_xml = &utc_xml[..utc_xml.find("LocalDateTime").unwrap_or(utc_xml.len())];
+
+    let field = |tag: &str| -> Result<u32> {
+        extract_xml_value(utc_xml, tag)
+            .and_then(|v| v.parse().ok())
+            .ok_or_else(|| Error::Parse(format!("UTCDateTime.{} missing or invalid", tag)))
+    };
+    let utc = Utc
+        .with_ymd_and_hms(
+            field("Year")? as i32,
+            field("Month")?,
+            field("Day")?,
+            field("Hour")?,
+            field("Minute")?,
+            field("Second")?,
+        )
+        .single()
+        .ok_or_else(|| Error::Parse("UTCDateTime out of range".to_string()))?;
+
+    Ok(CameraClock {
+        utc,
+        date_time_type: non_empty(extract_xml_value(xml, "DateTimeType")),
+        time_zone: non_empty(extract_xml_value(xml, "TZ")),
+    })
+}
+
+/// SOAP Fault の理由（Fault でなければ None）
+fn soap_fault_reason(xml: &str) -> Option<String> {
+    if !xml.contains("Fault>") && !xml.contains("Fault ") {
+        return None;
+    }
+    Some(
+        non_empty(extract_xml_value(xml, "Text"))
+            .or_else(|| non_empty(extract_xml_value(xml, "faultstring")))
+            .or_else(|| non_empty(extract_xml_value(xml, "Value")))
+            .unwrap_or_else(|| "unknown".to_string()),
+    )
+}
+
+fn utc_date_time_xml(now: DateTime<Utc>) -> String {
+    format!(
+        "<tds:UTCDateTime><tt:Date><tt:Year>{}</tt:Year><tt:Month>{}</tt:Month><tt:Day>{}</tt:Day></tt:Date><tt:Time><tt:Hour>{}</tt:Hour><tt:Minute>{}</tt:Minute><tt:Second>{}</tt:Second></tt:Time></tds:UTCDateTime>",
+        now.year(),
+        now.month(),
+        now.day(),
+        now.hour(),
+        now.minute(),
+        now.second()
+    )
+}
+
+fn ntp_host_xml(server: &str) -> String {
+    let server = server.trim();
+    if server.parse::<std::net::Ipv4Addr>().is_ok() {
+        format!("<tt:Type>IPv4</tt:Type><tt:IPv4Address>{}</tt:IPv4Address>", server)
+    } else {
+        format!("<tt:Type>DNS</tt:Type><tt:DNSname>{}</tt:DNSname>", xml_escape(server))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATE_TIME_RESPONSE: &str = r#"<SOAP-ENV:Envelope><SOAP-ENV:Body><tds:GetSystemDateAndTimeResponse><tds:SystemDateAndTime><tt:DateTimeType>NTP</tt:DateTimeType><tt:DaylightSavings>false</tt:DaylightSavings><tt:TimeZone><tt:TZ>JST-9</tt:TZ></tt:TimeZone><tt:LocalDateTime><tt:Time><tt:Hour>9</tt:Hour><tt:Minute>5</tt:Minute><tt:Second>7</tt:Second></tt:Time><tt:Date><tt:Year>2026</tt:Year><tt:Month>3</tt:Month><tt:Day>2</tt:Day></tt:Date></tt:LocalDateTime><tt:UTCDateTime><tt:Time><tt:Hour>0</tt:Hour><tt:Minute>5</tt:Minute><tt:Second>7</tt:Second></tt:Time><tt:Date><tt:Year>2026</tt:Year><tt:Month>3</tt:Month><tt:Day>2</tt:Day></tt:Date></tt:UTCDateTime></tds:SystemDateAndTime></tds:GetSystemDateAndTimeResponse></SOAP-ENV:Body></SOAP-ENV:Envelope>"#;
+
+    #[test]
+    fn test_parse_system_date_and_time_uses_utc() {
+        let clock = parse_system_date_and_time(DATE_TIME_RESPONSE).unwrap();
+        assert_eq!(clock.utc, Utc.with_ymd_and_hms(2026, 3, 2, 0, 5, 7).unwrap());
+        assert_eq!(clock.date_time_type.as_deref(), Some("NTP"));
+        assert_eq!(clock.time_zone.as_deref(), Some("JST-9"));
+
+        assert!(parse_system_date_and_time("<tds:GetSystemDateAndTimeResponse/>").is_err());
+    }
+
+    #[test]
+    fn test_parse_device_information_and_fault() {
+        let xml = r#"<s:Body><tds:GetDeviceInformationResponse><tds:Manufacturer>TP-Link</tds:Manufacturer><tds:Model>Tapo C200</tds:Model><tds:FirmwareVersion>1.3.6 Build 230803 &amp; Rel.58047n</tds:FirmwareVersion><tds:SerialNumber></tds:SerialNumber><tds:HardwareId>2.0</tds:HardwareId></tds:GetDeviceInformationResponse></s:Body>"#;
+        let info = parse_device_information(xml);
+        assert_eq!(info.manufacturer.as_deref(), Some("TP-Link"));
+        assert_eq!(info.model.as_deref(), Some("Tapo C200"));
+        assert_eq!(info.firmware_version.as_deref(), Some("1.3.6 Build 230803 & Rel.58047n"));
+        assert_eq!(info.serial_number, None);
+
+        let fault = r#"<env:Body><env:Fault><env:Code><env:Value>env:Sender</env:Value></env:Code><env:Reason><env:Text xml:lang="en">Sender not Authorized</env:Text></env:Reason></env:Fault></env:Body>"#;
+        assert_eq!(soap_fault_reason(fault).as_deref(), Some("Sender not Authorized"));
+        assert_eq!(soap_fault_reason(xml), None);
+    }
+
+    #[test]
+    fn test_clock_skew_uses_round_trip_midpoint() {
+        let sent = Utc.with_ymd_and_hms(2026, 3, 2, 0, 0, 0).unwrap();
+        let received = sent + Duration::milliseconds(400);
+        let camera = sent + Duration::seconds(30);
+        assert_eq!(clock_skew_ms(camera, sent, received), 29_800);
+        assert_eq!(clock_skew_ms(sent, sent, received), -200);
+    }
+}
@@ -0,0 +1,185 @@
+//! Camera maintenance types
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// settings テーブルのキー
+pub const MAINTENANCE_CONFIG_KEY: &str = "camera_maintenance";
+
+/// メンテナンス設定（settings.camera_maintenance）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MaintenanceConfig {
+    pub enabled: bool,
+    /// 全カメラチェックの間隔（分）
+    pub interval_minutes: u32,
+    /// ONVIFリクエストのタイムアウト（秒）
+    pub request_timeout_secs: u64,
+    /// 時刻ずれがこれを超えたら skewed とみなす（秒）
+    pub max_clock_skew_secs: u32,
+    /// skewed のカメラへ SetSystemDateAndTime を送る
+    pub auto_correct_clock: bool,
+    /// 補正時に設定するNTPサーバー（ホスト名またはIPv4）。None なら手動時刻のみ
+    pub ntp_server: Option<String>,
+}
+
+impl Default for MaintenanceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_minutes: 360,
+            request_timeout_secs: 10,
+            max_clock_skew_secs: 5,
+            auto_correct_clock: false,
+            ntp_server: None,
+        }
+    }
+}
+
+impl MaintenanceConfig {
+    pub fn validate(&self) -> crate::Result<()> {
+        if self.interval_minutes < 10 {
+            return Err(crate::Error::Validation(
+                "interval_minutes must be >= 10".to_string(),
+            ));
+        }
+        if !(1..=60).contains(&self.request_timeout_secs) {
+            return Err(crate::Error::Validation(
+                "request_timeout_secs must be between 1 and 60".to_string(),
+            ));
+        }
+        // カメラ時刻は秒精度のため 1秒以下は常に誤検出する
+        if self.max_clock_skew_secs < 2 {
+            return Err(crate::Error::Validation(
+                "max_clock_skew_secs must be >= 2".to_string(),
+            ));
+        }
+        if let Some(server) = &self.ntp_server {
+            let server = server.trim();
+            if server.is_empty() || server.contains(char::is_whitespace) || server.contains('<') {
+                return Err(crate::Error::Validation(format!(
+                    "Invalid ntp_server: {:?}",
+                    server
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// GetDeviceInformation の結果
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DeviceInformation {
+    pub manufacturer: Option<String>,
+    pub model: Option<String>,
+    pub firmware_version: Option<String>,
+    pub serial_number: Option<String>,
+    pub hardware_id: Option<String>,
+}
+
+/// GetSystemDateAndTime の結果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CameraClock {
+    pub utc: DateTime<Utc>,
+    /// "Manual" | "NTP"
+    pub date_time_type: Option<String>,
+    /// POSIX TZ 文字列（例: "JST-9"）
+    pub time_zone: Option<String>,
+}
+
+/// 時刻補正の方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClockCorrection {
+    /// SetSystemDateAndTime（Manual, UTC）
+    Manual,
+    /// 手動補正 + SetNTP + NTPモード
+    Ntp,
+}
+
+impl ClockCorrection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Manual => "manual",
+            Self::Ntp => "ntp",
+        }
+    }
+}
+
+/// カメラ単位の時刻チェック結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClockStatus {
+    pub camera_id: String,
+    pub camera_name: String,
+    pub checked_at: DateTime<Utc>,
+    pub camera_utc: Option<DateTime<Utc>>,
+    /// カメラ時刻 - サーバー時刻（正 = カメラが進んでいる）
+    pub skew_ms: Option<i64>,
+    pub round_trip_ms: Option<i64>,
+    pub date_time_type: Option<String>,
+    /// max_clock_skew_secs 超過
+    pub skewed: bool,
+    pub correction: Option<ClockCorrection>,
+    pub correction_error: Option<String>,
+    pub error: Option<String>,
+}
+
+/// ファームウェア変更履歴
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FirmwareChange {
+    pub history_id: u64,
+    pub camera_id: String,
+    /// None = 初回取得
+    pub previous_version: Option<String>,
+    pub firmware_version: String,
+    pub manufacturer: Option<String>,
+    pub model: Option<String>,
+    pub detected_at: DateTime<Utc>,
+}
+
+/// カメラ単位のメンテナンス結果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CameraMaintenanceResult {
+    pub camera_id: String,
+    pub device: Option<DeviceInformation>,
+    pub device_error: Option<String>,
+    pub firmware_change: Option<FirmwareChange>,
+    pub clock: ClockStatus,
+}
+
+/// インベントリ集計対象のカメラ
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InventoryCamera {
+    pub camera_id: String,
+    pub name: String,
+    pub manufacturer: Option<String>,
+    pub model: Option<String>,
+    pub firmware_version: Option<String>,
+}
+
+/// 同一ファームウェアのカメラ群
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FirmwareVersionGroup {
+    /// None = 未取得
+    pub firmware_version: Option<String>,
+    pub camera_ids: Vec<String>,
+}
+
+/// メーカー/機種単位の集計
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelInventory {
+    pub manufacturer: String,
+    pub model: String,
+    pub camera_count: usize,
+    pub versions: Vec<FirmwareVersionGroup>,
+    /// 同一機種で複数バージョンが混在
+    pub mixed_versions: bool,
+}
+
+/// フリートのファームウェアインベントリ
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FirmwareInventory {
+    pub generated_at: DateTime<Utc>,
+    pub total_cameras: usize,
+    pub models: Vec<ModelInventory>,
+}
@@ -8,6 +8,8 @@
 //! ## mobes2.0仕様
 //! - IngestEvent APIにmalfunction_type含めて送信
 //! - 不調種別: offline, stream_error, high_latency, low_fps, no_frames
+//! - 診断由来: stream_mismatch, low_bitrate, black_frame, frozen_frame, blurred, scene_shift
+//! - 本体由来: storage_pressure（StorageManager）
 
 use crate::paraclate_client::{
     types::{CameraMalfunctionType, EventPayload},
@@ -64,9 +66,16 @@ impl CameraMalfunctionReporter {
             severity: match malfunction_type {
                 CameraMalfunctionType::Offline => 3, // 最高重要度
                 CameraMalfunctionType::NoFrames => 3,
+                CameraMalfunctionType::BlackFrame => 3,
+                CameraMalfunctionType::SceneShift => 3,
+                CameraMalfunctionType::StoragePressure => 2,
                 CameraMalfunctionType::StreamError => 2,
+                CameraMalfunctionType::FrozenFrame => 2,
                 CameraMalfunctionType::HighLatency => 1,
                 CameraMalfunctionType::LowFps => 1,
+                CameraMalfunctionType::LowBitrate => 1,
+                CameraMalfunctionType::StreamMismatch => 1,
+                CameraMalfunctionType::Blurred => 1,
             },
             confidence: 1.0, // 不調は確実に検出されている
             tags: vec![
@@ -241,5 +250,8 @@ mod tests {
         assert_eq!(CameraMalfunctionType::HighLatency.to_string(), "high_latency");
         assert_eq!(CameraMalfunctionType::LowFps.to_string(), "low_fps");
         assert_eq!(CameraMalfunctionType::NoFrames.to_string(), "no_frames");
+        assert_eq!(CameraMalfunctionType::SceneShift.to_string(), "scene_shift");
+        assert_eq!(CameraMalfunctionType::FrozenFrame.to_string(), "frozen_frame");
+        assert_eq!(CameraMalfunctionType::StoragePressure.to_string(), "storage_pressure");
     }
 }
@@ -86,6 +86,7 @@ pub struct ContextMapEntry {
 }
 
 /// CameraContext Service
+#[derive(Clone)]
 pub struct CameraContextService {
     pool: MySqlPool,
 }
@@ -155,6 +155,16 @@ impl CameraStatusTracker {
         }
     }
 
+    /// Restore a camera's status without generating a transition event
+    ///
+    /// Used at startup to rebuild state from the persisted availability timeline.
+    pub async fn restore_status(&self, camera_id: &str, status: CameraConnectionStatus) {
+        self.statuses
+            .write()
+            .await
+            .insert(camera_id.to_string(), status);
+    }
+
     /// Get current status for a camera
     pub async fn get_status(&self, camera_id: &str) -> CameraConnectionStatus {
         self.statuses
@@ -341,6 +351,16 @@ mod tests {
         assert!(event.is_none());
     }
 
+    #[tokio::test]
+    async fn test_restored_offline_triggers_recovered() {
+        let tracker = CameraStatusTracker::new();
+        tracker
+            .restore_status("cam1", CameraConnectionStatus::Offline)
+            .await;
+        let event = tracker.update_status("cam1", true).await;
+        assert_eq!(event, Some(CameraStatusEvent::Recovered));
+    }
+
     #[tokio::test]
     async fn test_online_to_online_no_event() {
         let tracker = CameraStatusTracker::new();
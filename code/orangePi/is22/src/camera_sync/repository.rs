@@ -4,24 +4,20 @@
 //! T8-2: repository.rs
 
 use super::types::*;
+use crate::db::Db;
+use crate::with_db;
 use chrono::{DateTime, Utc};
-use sqlx::MySqlPool;
 use tracing::{debug, error, info};
 
 /// Camera Sync Repository
 pub struct CameraSyncRepository {
-    pool: MySqlPool,
+    db: Db,
 }
 
 impl CameraSyncRepository {
     /// 新しいCameraSyncRepositoryを作成
-    pub fn new(pool: MySqlPool) -> Self {
-        Self { pool }
-    }
-
-    /// プールへの参照を取得
-    pub fn pool(&self) -> &MySqlPool {
-        &self.pool
+    pub fn new(db: impl Into<Db>) -> Self {
+        Self { db: db.into() }
     }
 
     // ========================================
@@ -37,31 +33,40 @@ impl CameraSyncRepository {
             MobesSyncStatus::Deleted => "deleted",
         };
 
-        sqlx::query(
+        let sql = format!(
             r#"
             INSERT INTO camera_sync_state
                 (camera_id, lacis_id, last_sync_to_mobes, last_sync_from_mobes,
                  mobes_sync_status, sync_error_message, retry_count)
             VALUES (?, ?, ?, ?, ?, ?, ?)
-            ON DUPLICATE KEY UPDATE
-                lacis_id = VALUES(lacis_id),
-                last_sync_to_mobes = VALUES(last_sync_to_mobes),
-                last_sync_from_mobes = VALUES(last_sync_from_mobes),
-                mobes_sync_status = VALUES(mobes_sync_status),
-                sync_error_message = VALUES(sync_error_message),
-                retry_count = VALUES(retry_count),
-                updated_at = NOW(3)
+            {}
             "#,
-        )
-        .bind(&state.camera_id)
-        .bind(&state.lacis_id)
-        .bind(state.last_sync_to_mobes)
-        .bind(state.last_sync_from_mobes)
-        .bind(status_str)
-        .bind(&state.sync_error_message)
-        .bind(state.retry_count)
-        .execute(&self.pool)
-        .await
+            self.db.upsert(
+                "camera_id",
+                &[
+                    "lacis_id",
+                    "last_sync_to_mobes",
+                    "last_sync_from_mobes",
+                    "mobes_sync_status",
+                    "sync_error_message",
+                    "retry_count",
+                ],
+                &[&format!("updated_at = {}", self.db.now())],
+            )
+        );
+        with_db!(&self.db, pool => {
+            sqlx::query(&sql)
+                .bind(&state.camera_id)
+                .bind(&state.lacis_id)
+                .bind(state.last_sync_to_mobes)
+                .bind(state.last_sync_from_mobes)
+                .bind(status_str)
+                .bind(&state.sync_error_message)
+                .bind(state.retry_count)
+                .execute(pool)
+                .await
+                .map(|r| r.rows_affected())
+        })
         .map_err(|e| crate::Error::Database(e.to_string()))?;
 
         debug!(camera_id = %state.camera_id, "Upserted camera sync state");
@@ -70,17 +75,19 @@ impl CameraSyncRepository {
 
     /// 同期状態を取得
     pub async fn get_sync_state(&self, camera_id: &str) -> crate::Result<Option<CameraSyncState>> {
-        let row = sqlx::query_as::<_, SyncStateRow>(
-            r#"
-            SELECT id, camera_id, lacis_id, last_sync_to_mobes, last_sync_from_mobes,
-                   mobes_sync_status, sync_error_message, retry_count, created_at, updated_at
-            FROM camera_sync_state
-            WHERE camera_id = ?
-            "#,
-        )
-        .bind(camera_id)
-        .fetch_optional(&self.pool)
-        .await
+        let row = with_db!(&self.db, pool => {
+            sqlx::query_as::<_, SyncStateRow>(
+                r#"
+                SELECT id, camera_id, lacis_id, last_sync_to_mobes, last_sync_from_mobes,
+                       mobes_sync_status, sync_error_message, retry_count, created_at, updated_at
+                FROM camera_sync_state
+                WHERE camera_id = ?
+                "#,
+            )
+            .bind(camera_id)
+            .fetch_optional(pool)
+            .await
+        })
         .map_err(|e| crate::Error::Database(e.to_string()))?;
 
         Ok(row.map(|r| r.into()))
@@ -91,17 +98,19 @@ impl CameraSyncRepository {
         &self,
         lacis_id: &str,
     ) -> crate::Result<Option<CameraSyncState>> {
-        let row = sqlx::query_as::<_, SyncStateRow>(
-            r#"
-            SELECT id, camera_id, lacis_id, last_sync_to_mobes, last_sync_from_mobes,
-                   mobes_sync_status, sync_error_message, retry_count, created_at, updated_at
-            FROM camera_sync_state
-            WHERE lacis_id = ?
-            "#,
-        )
-        .bind(lacis_id)
-        .fetch_optional(&self.pool)
-        .await
+        let row = with_db!(&self.db, pool => {
+            sqlx::query_as::<_, SyncStateRow>(
+                r#"
+                SELECT id, camera_id, lacis_id, last_sync_to_mobes, last_sync_from_mobes,
+                       mobes_sync_status, sync_error_message, retry_count, created_at, updated_at
+                FROM camera_sync_state
+                WHERE lacis_id = ?
+                "#,
+            )
+            .bind(lacis_id)
+            .fetch_optional(pool)
+            .await
+        })
         .map_err(|e| crate::Error::Database(e.to_string()))?;
 
         Ok(row.map(|r| r.into()))
@@ -109,17 +118,19 @@ impl CameraSyncRepository {
 
     /// 同期保留中のカメラを取得
     pub async fn get_pending_cameras(&self) -> crate::Result<Vec<CameraSyncState>> {
-        let rows = sqlx::query_as::<_, SyncStateRow>(
-            r#"
-            SELECT id, camera_id, lacis_id, last_sync_to_mobes, last_sync_from_mobes,
-                   mobes_sync_status, sync_error_message, retry_count, created_at, updated_at
-            FROM camera_sync_state
-            WHERE mobes_sync_status = 'pending'
-            ORDER BY created_at ASC
-            "#,
-        )
-        .fetch_all(&self.pool)
-        .await
+        let rows = with_db!(&self.db, pool => {
+            sqlx::query_as::<_, SyncStateRow>(
+                r#"
+                SELECT id, camera_id, lacis_id, last_sync_to_mobes, last_sync_from_mobes,
+                       mobes_sync_status, sync_error_message, retry_count, created_at, updated_at
+                FROM camera_sync_state
+                WHERE mobes_sync_status = 'pending'
+                ORDER BY created_at ASC
+                "#,
+            )
+            .fetch_all(pool)
+            .await
+        })
         .map_err(|e| crate::Error::Database(e.to_string()))?;
 
         Ok(rows.into_iter().map(|r| r.into()).collect())
@@ -127,18 +138,20 @@ impl CameraSyncRepository {
 
     /// 同期失敗でリトライ可能なカメラを取得
     pub async fn get_retryable_cameras(&self, max_retry: i32) -> crate::Result<Vec<CameraSyncState>> {
-        let rows = sqlx::query_as::<_, SyncStateRow>(
-            r#"
-            SELECT id, camera_id, lacis_id, last_sync_to_mobes, last_sync_from_mobes,
-                   mobes_sync_status, sync_error_message, retry_count, created_at, updated_at
-            FROM camera_sync_state
-            WHERE mobes_sync_status = 'failed' AND retry_count < ?
-            ORDER BY updated_at ASC
-            "#,
-        )
-        .bind(max_retry)
-        .fetch_all(&self.pool)
-        .await
+        let rows = with_db!(&self.db, pool => {
+            sqlx::query_as::<_, SyncStateRow>(
+                r#"
+                SELECT id, camera_id, lacis_id, last_sync_to_mobes, last_sync_from_mobes,
+                       mobes_sync_status, sync_error_message, retry_count, created_at, updated_at
+                FROM camera_sync_state
+                WHERE mobes_sync_status = 'failed' AND retry_count < ?
+                ORDER BY updated_at ASC
+                "#,
+            )
+            .bind(max_retry)
+            .fetch_all(pool)
+            .await
+        })
         .map_err(|e| crate::Error::Database(e.to_string()))?;
 
         Ok(rows.into_iter().map(|r| r.into()).collect())
@@ -146,20 +159,25 @@ impl CameraSyncRepository {
 
     /// 同期状態を「同期済み」にマーク
     pub async fn mark_synced_to_mobes(&self, camera_id: &str) -> crate::Result<()> {
-        sqlx::query(
+        let sql = format!(
             r#"
             UPDATE camera_sync_state
             SET mobes_sync_status = 'synced',
-                last_sync_to_mobes = NOW(3),
+                last_sync_to_mobes = {now},
                 sync_error_message = NULL,
                 retry_count = 0,
-                updated_at = NOW(3)
+                updated_at = {now}
             WHERE camera_id = ?
             "#,
-        )
-        .bind(camera_id)
-        .execute(&self.pool)
-        .await
+            now = self.db.now()
+        );
+        with_db!(&self.db, pool => {
+            sqlx::query(&sql)
+                .bind(camera_id)
+                .execute(pool)
+                .await
+                .map(|r| r.rows_affected())
+        })
         .map_err(|e| crate::Error::Database(e.to_string()))?;
 
         Ok(())
@@ -171,20 +189,25 @@ impl CameraSyncRepository {
         camera_id: &str,
         error_message: &str,
     ) -> crate::Result<()> {
-        sqlx::query(
+        let sql = format!(
             r#"
             UPDATE camera_sync_state
             SET mobes_sync_status = 'failed',
                 sync_error_message = ?,
                 retry_count = retry_count + 1,
-                updated_at = NOW(3)
+                updated_at = {}
             WHERE camera_id = ?
             "#,
-        )
-        .bind(error_message)
-        .bind(camera_id)
-        .execute(&self.pool)
-        .await
+            self.db.now()
+        );
+        with_db!(&self.db, pool => {
+            sqlx::query(&sql)
+                .bind(error_message)
+                .bind(camera_id)
+                .execute(pool)
+                .await
+                .map(|r| r.rows_affected())
+        })
         .map_err(|e| crate::Error::Database(e.to_string()))?;
 
         Ok(())
@@ -192,17 +215,19 @@ impl CameraSyncRepository {
 
     /// 同期状態カウントを取得
     pub async fn get_sync_counts(&self) -> crate::Result<SyncCounts> {
-        let row = sqlx::query_as::<_, SyncCountsRow>(
-            r#"
-            SELECT
-                SUM(CASE WHEN mobes_sync_status = 'synced' THEN 1 ELSE 0 END) as synced_count,
-                SUM(CASE WHEN mobes_sync_status = 'pending' THEN 1 ELSE 0 END) as pending_count,
-                SUM(CASE WHEN mobes_sync_status = 'failed' THEN 1 ELSE 0 END) as failed_count
-            FROM camera_sync_state
-            "#,
-        )
-        .fetch_one(&self.pool)
-        .await
+        let row = with_db!(&self.db, pool => {
+            sqlx::query_as::<_, SyncCountsRow>(
+                r#"
+                SELECT
+                    SUM(CASE WHEN mobes_sync_status = 'synced' THEN 1 ELSE 0 END) as synced_count,
+                    SUM(CASE WHEN mobes_sync_status = 'pending' THEN 1 ELSE 0 END) as pending_count,
+                    SUM(CASE WHEN mobes_sync_status = 'failed' THEN 1 ELSE 0 END) as failed_count
+                FROM camera_sync_state
+                "#,
+            )
+            .fetch_one(pool)
+            .await
+        })
         .map_err(|e| crate::Error::Database(e.to_string()))?;
 
         Ok(SyncCounts {
@@ -230,32 +255,45 @@ impl CameraSyncRepository {
             "custom_preset": settings.custom_preset
         });
 
-        sqlx::query(
+        let now = self.db.now();
+        let sql = format!(
             r#"
             INSERT INTO camera_paraclate_settings
                 (camera_id, lacis_id, sensitivity, detection_zone, alert_threshold,
                  custom_preset, settings_json, synced_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, NOW(3))
-            ON DUPLICATE KEY UPDATE
-                lacis_id = VALUES(lacis_id),
-                sensitivity = VALUES(sensitivity),
-                detection_zone = VALUES(detection_zone),
-                alert_threshold = VALUES(alert_threshold),
-                custom_preset = VALUES(custom_preset),
-                settings_json = VALUES(settings_json),
-                synced_at = NOW(3),
-                updated_at = NOW(3)
+            VALUES (?, ?, ?, ?, ?, ?, ?, {})
+            {}
             "#,
-        )
-        .bind(camera_id)
-        .bind(lacis_id)
-        .bind(settings.sensitivity)
-        .bind(&settings.detection_zone)
-        .bind(settings.alert_threshold)
-        .bind(&settings.custom_preset)
-        .bind(settings_json.to_string())
-        .execute(&self.pool)
-        .await
+            now,
+            self.db.upsert(
+                "camera_id",
+                &[
+                    "lacis_id",
+                    "sensitivity",
+                    "detection_zone",
+                    "alert_threshold",
+                    "custom_preset",
+                    "settings_json",
+                ],
+                &[
+                    &format!("synced_at = {}", now),
+                    &format!("updated_at = {}", now),
+                ],
+            )
+        );
+        with_db!(&self.db, pool => {
+            sqlx::query(&sql)
+                .bind(camera_id)
+                .bind(lacis_id)
+                .bind(settings.sensitivity)
+                .bind(&settings.detection_zone)
+                .bind(settings.alert_threshold)
+                .bind(&settings.custom_preset)
+                .bind(settings_json.to_string())
+                .execute(pool)
+                .await
+                .map(|r| r.rows_affected())
+        })
         .map_err(|e| crate::Error::Database(e.to_string()))?;
 
         info!(camera_id = %camera_id, "Updated paraclate settings from mobes2.0");
@@ -267,16 +305,18 @@ impl CameraSyncRepository {
         &self,
         camera_id: &str,
     ) -> crate::Result<Option<CameraParaclateSettings>> {
-        let row = sqlx::query_as::<_, ParaclateSettingsRow>(
-            r#"
-            SELECT sensitivity, detection_zone, alert_threshold, custom_preset
-            FROM camera_paraclate_settings
-            WHERE camera_id = ?
-            "#,
-        )
-        .bind(camera_id)
-        .fetch_optional(&self.pool)
-        .await
+        let row = with_db!(&self.db, pool => {
+            sqlx::query_as::<_, ParaclateSettingsRow>(
+                r#"
+                SELECT sensitivity, detection_zone, alert_threshold, custom_preset
+                FROM camera_paraclate_settings
+                WHERE camera_id = ?
+                "#,
+            )
+            .bind(camera_id)
+            .fetch_optional(pool)
+            .await
+        })
         .map_err(|e| crate::Error::Database(e.to_string()))?;
 
         Ok(row.map(|r| CameraParaclateSettings {
@@ -309,25 +349,28 @@ impl CameraSyncRepository {
             SyncDirection::FromMobes => "from_mobes",
         };
 
-        sqlx::query(
-            r#"
-            INSERT INTO camera_sync_logs
-                (camera_id, lacis_id, sync_direction, sync_type, status,
-                 changed_fields, error_message, request_payload, response_payload)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
-            "#,
-        )
-        .bind(camera_id)
-        .bind(lacis_id)
-        .bind(direction_str)
-        .bind(sync_type)
-        .bind(status)
-        .bind(changed_fields.map(|v| v.to_string()))
-        .bind(error_message)
-        .bind(request_payload.map(|v| v.to_string()))
-        .bind(response_payload.map(|v| v.to_string()))
-        .execute(&self.pool)
-        .await
+        with_db!(&self.db, pool => {
+            sqlx::query(
+                r#"
+                INSERT INTO camera_sync_logs
+                    (camera_id, lacis_id, sync_direction, sync_type, status,
+                     changed_fields, error_message, request_payload, response_payload)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(camera_id)
+            .bind(lacis_id)
+            .bind(direction_str)
+            .bind(sync_type)
+            .bind(status)
+            .bind(changed_fields.map(|v| v.to_string()))
+            .bind(error_message)
+            .bind(request_payload.map(|v| v.to_string()))
+            .bind(response_payload.map(|v| v.to_string()))
+            .execute(pool)
+            .await
+            .map(|r| r.rows_affected())
+        })
         .map_err(|e| crate::Error::Database(e.to_string()))?;
 
         Ok(())
@@ -335,18 +378,20 @@ impl CameraSyncRepository {
 
     /// 最近の同期ログを取得
     pub async fn get_recent_logs(&self, limit: i32) -> crate::Result<Vec<CameraSyncLog>> {
-        let rows = sqlx::query_as::<_, SyncLogRow>(
-            r#"
-            SELECT id, camera_id, lacis_id, sync_direction, sync_type, status,
-                   changed_fields, error_message, request_payload, response_payload, created_at
-            FROM camera_sync_logs
-            ORDER BY created_at DESC
-            LIMIT ?
-            "#,
-        )
-        .bind(limit)
-        .fetch_all(&self.pool)
-        .await
+        let rows = with_db!(&self.db, pool => {
+            sqlx::query_as::<_, SyncLogRow>(
+                r#"
+                SELECT id, camera_id, lacis_id, sync_direction, sync_type, status,
+                       changed_fields, error_message, request_payload, response_payload, created_at
+                FROM camera_sync_logs
+                ORDER BY created_at DESC
+                LIMIT ?
+                "#,
+            )
+            .bind(limit)
+            .fetch_all(pool)
+            .await
+        })
         .map_err(|e| crate::Error::Database(e.to_string()))?;
 
         Ok(rows.into_iter().map(|r| r.into()).collect())
@@ -358,23 +403,118 @@ impl CameraSyncRepository {
 
     /// camerasテーブルの同期日時を更新
     pub async fn update_camera_mobes_sync(&self, camera_id: &str) -> crate::Result<()> {
-        sqlx::query(
+        let sql = format!(
             r#"
             UPDATE cameras
-            SET mobes_synced_at = NOW(3),
+            SET mobes_synced_at = {},
                 mobes_sync_version = mobes_sync_version + 1
             WHERE camera_id = ?
             "#,
-        )
-        .bind(camera_id)
-        .execute(&self.pool)
-        .await
+            self.db.now()
+        );
+        with_db!(&self.db, pool => {
+            sqlx::query(&sql)
+                .bind(camera_id)
+                .execute(pool)
+                .await
+                .map(|r| r.rows_affected())
+        })
         .map_err(|e| crate::Error::Database(e.to_string()))?;
 
         Ok(())
     }
+
+    /// 登録済みカメラ（LacisIDあり・未削除）を取得
+    pub async fn get_registered_cameras(&self, tid: &str) -> crate::Result<Vec<SyncCameraInfo>> {
+        let sql = format!(
+            "{} WHERE c.tid = ? AND c.deleted_at IS NULL AND c.lacis_id IS NOT NULL",
+            SYNC_CAMERA_SELECT
+        );
+        let rows = with_db!(&self.db, pool => {
+            sqlx::query_as::<_, SyncCameraRow>(&sql)
+                .bind(tid)
+                .fetch_all(pool)
+                .await
+        })
+        .map_err(|e| crate::Error::Database(e.to_string()))?;
+
+        Ok(rows.into_iter().map(|r| r.into()).collect())
+    }
+
+    /// 単一カメラを取得
+    pub async fn get_camera(&self, camera_id: &str) -> crate::Result<Option<SyncCameraInfo>> {
+        let sql = format!("{} WHERE c.camera_id = ?", SYNC_CAMERA_SELECT);
+        let row = with_db!(&self.db, pool => {
+            sqlx::query_as::<_, SyncCameraRow>(&sql)
+                .bind(camera_id)
+                .fetch_optional(pool)
+                .await
+        })
+        .map_err(|e| crate::Error::Database(e.to_string()))?;
+
+        Ok(row.map(|r| r.into()))
+    }
+
+    /// LacisIDからカメラIDを取得
+    pub async fn get_camera_id_by_lacis_id(&self, lacis_id: &str) -> crate::Result<Option<String>> {
+        with_db!(&self.db, pool => {
+            sqlx::query_scalar::<_, String>("SELECT camera_id FROM cameras WHERE lacis_id = ?")
+                .bind(lacis_id)
+                .fetch_optional(pool)
+                .await
+        })
+        .map_err(|e| crate::Error::Database(e.to_string()))
+    }
+
+    /// カメラを論理削除
+    pub async fn mark_camera_deleted(&self, camera_id: &str) -> crate::Result<()> {
+        let sql = format!(
+            "UPDATE cameras SET deleted_at = {} WHERE camera_id = ?",
+            self.db.now()
+        );
+        with_db!(&self.db, pool => {
+            sqlx::query(&sql)
+                .bind(camera_id)
+                .execute(pool)
+                .await
+                .map(|r| r.rows_affected())
+        })
+        .map_err(|e| crate::Error::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// 全同期状態を取得（更新の新しい順）
+    pub async fn get_all_sync_states(&self) -> crate::Result<Vec<CameraSyncStatusEntry>> {
+        let rows = with_db!(&self.db, pool => {
+            sqlx::query_as::<_, SyncStateEntryRow>(
+                r#"
+                SELECT camera_id, lacis_id, mobes_sync_status,
+                       last_sync_to_mobes, last_sync_from_mobes
+                FROM camera_sync_state
+                ORDER BY updated_at DESC
+                "#,
+            )
+            .fetch_all(pool)
+            .await
+        })
+        .map_err(|e| crate::Error::Database(e.to_string()))?;
+
+        Ok(rows.into_iter().map(|r| r.into()).collect())
+    }
 }
 
+/// 同期対象カメラの SELECT（OUI 先頭 8 文字 "AA:BB:CC" でブランドを引く）
+const SYNC_CAMERA_SELECT: &str = r#"
+    SELECT
+        c.camera_id, c.lacis_id, c.name, c.location, c.mac_address,
+        c.camera_context, c.enabled as is_online, c.updated_at as last_seen,
+        cb.name as brand_name, c.model
+    FROM cameras c
+    LEFT JOIN oui_entries oe ON UPPER(SUBSTR(c.mac_address, 1, 8)) = oe.oui_prefix
+    LEFT JOIN camera_brands cb ON oe.brand_id = cb.id
+"#;
+
 // ========================================
 // Row Types (sqlx)
 // ========================================
@@ -477,3 +617,143 @@ impl From<SyncLogRow> for CameraSyncLog {
         }
     }
 }
+
+#[derive(sqlx::FromRow)]
+struct SyncCameraRow {
+    camera_id: String,
+    lacis_id: Option<String>,
+    name: String,
+    location: Option<String>,
+    mac_address: Option<String>,
+    camera_context: Option<String>,
+    is_online: Option<bool>,
+    last_seen: Option<chrono::NaiveDateTime>,
+    brand_name: Option<String>,
+    model: Option<String>,
+}
+
+impl From<SyncCameraRow> for SyncCameraInfo {
+    fn from(row: SyncCameraRow) -> Self {
+        Self {
+            camera_id: row.camera_id,
+            lacis_id: row.lacis_id,
+            name: row.name,
+            location: row.location,
+            mac_address: row.mac_address,
+            camera_context: row.camera_context,
+            is_online: row.is_online,
+            last_seen: row.last_seen.map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+            brand_name: row.brand_name,
+            model: row.model,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct SyncStateEntryRow {
+    camera_id: String,
+    lacis_id: String,
+    mobes_sync_status: String,
+    last_sync_to_mobes: Option<chrono::NaiveDateTime>,
+    last_sync_from_mobes: Option<chrono::NaiveDateTime>,
+}
+
+impl From<SyncStateEntryRow> for CameraSyncStatusEntry {
+    fn from(row: SyncStateEntryRow) -> Self {
+        let status = match row.mobes_sync_status.as_str() {
+            "synced" => MobesSyncStatus::Synced,
+            "pending" => MobesSyncStatus::Pending,
+            "failed" => MobesSyncStatus::Failed,
+            "deleted" => MobesSyncStatus::Deleted,
+            _ => MobesSyncStatus::Pending,
+        };
+
+        Self {
+            camera_id: row.camera_id,
+            lacis_id: row.lacis_id,
+            mobes_sync_status: status,
+            last_sync_to_mobes: row.last_sync_to_mobes.map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+            last_sync_from_mobes: row.last_sync_from_mobes.map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// cameras / camera_brands / oui_entries は他モジュールの管理なので SQL で直接用意する
+    async fn seed(db: &Db) {
+        let pool = match db {
+            Db::Sqlite(pool) => pool,
+            Db::MySql(_) => unreachable!(),
+        };
+        for sql in [
+            "INSERT INTO camera_brands (id, name, display_name) VALUES (1, 'tplink', 'TP-Link')",
+            "INSERT INTO oui_entries (oui_prefix, brand_id) VALUES ('70:5A:0F', 1)",
+            r#"INSERT INTO cameras (camera_id, name, location, mac_address, lacis_id, tid, model)
+               VALUES ('cam-1', 'Entrance', '1F', '70:5a:0f:12:34:56', '3022705A0F123456', 'T1', 'C200')"#,
+            r#"INSERT INTO cameras (camera_id, name, location, tid)
+               VALUES ('cam-2', 'Unregistered', '2F', 'T1')"#,
+        ] {
+            sqlx::query(sql).execute(pool).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_registered_cameras_and_delete() {
+        let db = Db::memory().await.unwrap();
+        seed(&db).await;
+        let repo = CameraSyncRepository::new(db);
+
+        // LacisID のないカメラは対象外、ブランドは OUI から引く
+        let cameras = repo.get_registered_cameras("T1").await.unwrap();
+        assert_eq!(cameras.len(), 1);
+        assert_eq!(cameras[0].camera_id, "cam-1");
+        assert_eq!(cameras[0].brand_name.as_deref(), Some("tplink"));
+        assert_eq!(cameras[0].model.as_deref(), Some("C200"));
+        assert_eq!(cameras[0].is_online, Some(true));
+        assert!(cameras[0].last_seen.is_some());
+
+        assert_eq!(
+            repo.get_camera_id_by_lacis_id("3022705A0F123456").await.unwrap().as_deref(),
+            Some("cam-1")
+        );
+        assert!(repo.get_camera_id_by_lacis_id("missing").await.unwrap().is_none());
+
+        repo.mark_camera_deleted("cam-1").await.unwrap();
+        assert!(repo.get_registered_cameras("T1").await.unwrap().is_empty());
+        // 単体取得は削除済みも返す（削除通知の送信用）
+        assert!(repo.get_camera("cam-1").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_sync_state_roundtrip() {
+        let repo = CameraSyncRepository::new(Db::memory().await.unwrap());
+
+        let mut state = CameraSyncState {
+            id: None,
+            camera_id: "cam-1".to_string(),
+            lacis_id: "3022705A0F123456".to_string(),
+            last_sync_to_mobes: None,
+            last_sync_from_mobes: None,
+            mobes_sync_status: MobesSyncStatus::Pending,
+            sync_error_message: None,
+            retry_count: 0,
+            created_at: None,
+            updated_at: None,
+        };
+        repo.upsert_sync_state(&state).await.unwrap();
+        assert_eq!(repo.get_pending_cameras().await.unwrap().len(), 1);
+
+        state.mobes_sync_status = MobesSyncStatus::Synced;
+        state.last_sync_to_mobes = Some(Utc::now());
+        repo.upsert_sync_state(&state).await.unwrap();
+
+        let entries = repo.get_all_sync_states().await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].mobes_sync_status, MobesSyncStatus::Synced);
+        assert!(entries[0].last_sync_to_mobes.is_some());
+        assert!(repo.get_pending_cameras().await.unwrap().is_empty());
+    }
+}
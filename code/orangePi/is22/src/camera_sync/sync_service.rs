@@ -539,97 +539,38 @@ impl CameraSyncService {
     // ========================================
 
     /// 登録済みカメラ一覧を取得
-    async fn get_registered_cameras(&self, tid: &str) -> crate::Result<Vec<CameraInfo>> {
-        let rows = sqlx::query_as::<_, CameraInfoRow>(
-            r#"
-            SELECT
-                c.camera_id, c.lacis_id, c.name, c.location, c.mac_address,
-                c.camera_context, c.enabled as is_online, c.updated_at as last_seen,
-                cb.name as brand_name, c.onvif_device_info
-            FROM cameras c
-            LEFT JOIN oui_entries oe ON UPPER(SUBSTRING(c.mac_address, 1, 8)) = oe.oui_prefix
-            LEFT JOIN camera_brands cb ON oe.brand_id = cb.id
-            WHERE c.tid = ? AND c.deleted_at IS NULL
-            AND c.lacis_id IS NOT NULL
-            "#,
-        )
-        .bind(tid)
-        .fetch_all(self.repository.pool())
-        .await
-        .map_err(|e| crate::Error::Database(e.to_string()))?;
-
-        Ok(rows.into_iter().map(|r| r.into()).collect())
+    async fn get_registered_cameras(&self, tid: &str) -> crate::Result<Vec<SyncCameraInfo>> {
+        self.repository.get_registered_cameras(tid).await
     }
 
     /// 単一カメラ情報を取得
-    async fn get_camera_info(&self, camera_id: &str) -> crate::Result<CameraInfo> {
-        let row = sqlx::query_as::<_, CameraInfoRow>(
-            r#"
-            SELECT
-                c.camera_id, c.lacis_id, c.name, c.location, c.mac_address,
-                c.camera_context, c.enabled as is_online, c.updated_at as last_seen,
-                cb.name as brand_name, c.onvif_device_info
-            FROM cameras c
-            LEFT JOIN oui_entries oe ON UPPER(SUBSTRING(c.mac_address, 1, 8)) = oe.oui_prefix
-            LEFT JOIN camera_brands cb ON oe.brand_id = cb.id
-            WHERE c.camera_id = ?
-            "#,
-        )
-        .bind(camera_id)
-        .fetch_optional(self.repository.pool())
-        .await
-        .map_err(|e| crate::Error::Database(e.to_string()))?
-        .ok_or_else(|| crate::Error::NotFound(format!("Camera {} not found", camera_id)))?;
-
-        Ok(row.into())
+    async fn get_camera_info(&self, camera_id: &str) -> crate::Result<SyncCameraInfo> {
+        self.repository
+            .get_camera(camera_id)
+            .await?
+            .ok_or_else(|| crate::Error::NotFound(format!("Camera {} not found", camera_id)))
     }
 
     /// LacisIDからカメラIDを取得
     async fn get_camera_id_by_lacis_id(&self, lacis_id: &str) -> crate::Result<String> {
-        let row: Option<(String,)> = sqlx::query_as(
-            "SELECT camera_id FROM cameras WHERE lacis_id = ?",
-        )
-        .bind(lacis_id)
-        .fetch_optional(self.repository.pool())
-        .await
-        .map_err(|e| crate::Error::Database(e.to_string()))?;
-
-        row.map(|r| r.0)
+        self.repository
+            .get_camera_id_by_lacis_id(lacis_id)
+            .await?
             .ok_or_else(|| crate::Error::NotFound(format!("Camera with LacisID {} not found", lacis_id)))
     }
 
     /// カメラを論理削除
     async fn mark_camera_deleted(&self, camera_id: &str) -> crate::Result<()> {
-        sqlx::query(
-            "UPDATE cameras SET deleted_at = NOW(3) WHERE camera_id = ?",
-        )
-        .bind(camera_id)
-        .execute(self.repository.pool())
-        .await
-        .map_err(|e| crate::Error::Database(e.to_string()))?;
-
-        Ok(())
+        self.repository.mark_camera_deleted(camera_id).await
     }
 
     /// 全同期状態を取得
     async fn get_all_sync_states(&self) -> crate::Result<Vec<CameraSyncStatusEntry>> {
-        let rows = sqlx::query_as::<_, SyncStateEntryRow>(
-            r#"
-            SELECT camera_id, lacis_id, mobes_sync_status,
-                   last_sync_to_mobes, last_sync_from_mobes
-            FROM camera_sync_state
-            ORDER BY updated_at DESC
-            "#,
-        )
-        .fetch_all(self.repository.pool())
-        .await
-        .map_err(|e| crate::Error::Database(e.to_string()))?;
-
-        Ok(rows.into_iter().map(|r| r.into()).collect())
+        self.repository.get_all_sync_states().await
     }
 
     /// メタデータエントリを構築
-    fn build_metadata_entry(&self, camera: &CameraInfo) -> CameraMetadataEntry {
+    fn build_metadata_entry(&self, camera: &SyncCameraInfo) -> CameraMetadataEntry {
         let context = camera
             .camera_context
             .as_ref()
@@ -744,87 +685,3 @@ impl CameraSyncService {
         }
     }
 }
-
-// ========================================
-// Row Types
-// ========================================
-
-#[derive(sqlx::FromRow)]
-struct CameraInfoRow {
-    camera_id: String,
-    lacis_id: Option<String>,
-    name: String,
-    location: Option<String>,
-    mac_address: Option<String>,
-    camera_context: Option<String>,
-    is_online: Option<bool>,
-    last_seen: Option<chrono::NaiveDateTime>,
-    brand_name: Option<String>,
-    onvif_device_info: Option<String>,
-}
-
-struct CameraInfo {
-    camera_id: String,
-    lacis_id: Option<String>,
-    name: String,
-    location: Option<String>,
-    mac_address: Option<String>,
-    camera_context: Option<String>,
-    is_online: Option<bool>,
-    last_seen: Option<chrono::DateTime<chrono::Utc>>,
-    brand_name: Option<String>,
-    model: Option<String>,
-}
-
-impl From<CameraInfoRow> for CameraInfo {
-    fn from(row: CameraInfoRow) -> Self {
-        // onvif_device_infoからモデル名を抽出
-        let model = row.onvif_device_info.as_ref().and_then(|info| {
-            serde_json::from_str::<serde_json::Value>(info)
-                .ok()
-                .and_then(|v| v.get("model").and_then(|m| m.as_str()).map(String::from))
-        });
-
-        Self {
-            camera_id: row.camera_id,
-            lacis_id: row.lacis_id,
-            name: row.name,
-            location: row.location,
-            mac_address: row.mac_address,
-            camera_context: row.camera_context,
-            is_online: row.is_online,
-            last_seen: row.last_seen.map(|dt| chrono::DateTime::from_naive_utc_and_offset(dt, chrono::Utc)),
-            brand_name: row.brand_name,
-            model,
-        }
-    }
-}
-
-#[derive(sqlx::FromRow)]
-struct SyncStateEntryRow {
-    camera_id: String,
-    lacis_id: String,
-    mobes_sync_status: String,
-    last_sync_to_mobes: Option<chrono::NaiveDateTime>,
-    last_sync_from_mobes: Option<chrono::NaiveDateTime>,
-}
-
-impl From<SyncStateEntryRow> for CameraSyncStatusEntry {
-    fn from(row: SyncStateEntryRow) -> Self {
-        let status = match row.mobes_sync_status.as_str() {
-            "synced" => MobesSyncStatus::Synced,
-            "pending" => MobesSyncStatus::Pending,
-            "failed" => MobesSyncStatus::Failed,
-            "deleted" => MobesSyncStatus::Deleted,
-            _ => MobesSyncStatus::Pending,
-        };
-
-        Self {
-            camera_id: row.camera_id,
-            lacis_id: row.lacis_id,
-            mobes_sync_status: status,
-            last_sync_to_mobes: row.last_sync_to_mobes.map(|dt| chrono::DateTime::from_naive_utc_and_offset(dt, chrono::Utc)),
-            last_sync_from_mobes: row.last_sync_from_mobes.map(|dt| chrono::DateTime::from_naive_utc_and_offset(dt, chrono::Utc)),
-        }
-    }
-}
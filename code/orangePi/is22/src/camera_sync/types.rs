@@ -306,6 +306,21 @@ pub struct CameraSyncStatusEntry {
     pub last_sync_from_mobes: Option<DateTime<Utc>>,
 }
 
+/// 同期対象カメラ（cameras + OUI ブランド）
+#[derive(Debug, Clone)]
+pub struct SyncCameraInfo {
+    pub camera_id: String,
+    pub lacis_id: Option<String>,
+    pub name: String,
+    pub location: Option<String>,
+    pub mac_address: Option<String>,
+    pub camera_context: Option<String>,
+    pub is_online: Option<bool>,
+    pub last_seen: Option<DateTime<Utc>>,
+    pub brand_name: Option<String>,
+    pub model: Option<String>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -3,19 +3,20 @@
 //! Database access layer for ConfigStore
 
 use super::types::*;
+use crate::db::Db;
 use crate::error::{Error, Result};
-use sqlx::MySqlPool;
+use crate::with_db;
 
 /// ConfigStore repository for database operations
 #[derive(Clone)]
 pub struct ConfigRepository {
-    pool: MySqlPool,
+    db: Db,
 }
 
 impl ConfigRepository {
     /// Create new repository
-    pub fn new(pool: MySqlPool) -> Self {
-        Self { pool }
+    pub fn new(db: impl Into<Db>) -> Self {
+        Self { db: db.into() }
     }
 
     // ========================================
@@ -41,6 +42,7 @@ impl ConfigRepository {
         discovery_method, last_verified_at, last_rescan_at, deleted_at,
         sdm_device_id, sdm_structure, sdm_traits,
         conf_override, nms_threshold, par_threshold,
+        motion_sensitivity, motion_noise_floor,
         created_at, updated_at
     "#;
 
@@ -50,9 +52,11 @@ impl ConfigRepository {
             "SELECT {} FROM cameras WHERE deleted_at IS NULL ORDER BY sort_order, location, name",
             Self::CAMERA_COLUMNS
         );
-        let cameras = sqlx::query_as::<_, Camera>(&query)
-            .fetch_all(&self.pool)
-            .await?;
+        let cameras = with_db!(&self.db, pool => {
+            sqlx::query_as::<_, Camera>(&query)
+                .fetch_all(pool)
+                .await
+        })?;
 
         Ok(cameras)
     }
@@ -63,9 +67,11 @@ impl ConfigRepository {
             "SELECT {} FROM cameras WHERE enabled = TRUE AND polling_enabled = TRUE AND deleted_at IS NULL ORDER BY suggest_policy_weight DESC, sort_order, location, name",
             Self::CAMERA_COLUMNS
         );
-        let cameras = sqlx::query_as::<_, Camera>(&query)
-            .fetch_all(&self.pool)
-            .await?;
+        let cameras = with_db!(&self.db, pool => {
+            sqlx::query_as::<_, Camera>(&query)
+                .fetch_all(pool)
+                .await
+        })?;
 
         Ok(cameras)
     }
@@ -76,10 +82,12 @@ impl ConfigRepository {
             "SELECT {} FROM cameras WHERE camera_id = ?",
             Self::CAMERA_COLUMNS
         );
-        let camera = sqlx::query_as::<_, Camera>(&query)
-            .bind(camera_id)
-            .fetch_optional(&self.pool)
-            .await?;
+        let camera = with_db!(&self.db, pool => {
+            sqlx::query_as::<_, Camera>(&query)
+                .bind(camera_id)
+                .fetch_optional(pool)
+                .await
+        })?;
 
         Ok(camera)
     }
@@ -90,10 +98,12 @@ impl ConfigRepository {
             "SELECT {} FROM cameras WHERE mac_address = ?",
             Self::CAMERA_COLUMNS
         );
-        let camera = sqlx::query_as::<_, Camera>(&query)
-            .bind(mac_address)
-            .fetch_optional(&self.pool)
-            .await?;
+        let camera = with_db!(&self.db, pool => {
+            sqlx::query_as::<_, Camera>(&query)
+                .bind(mac_address)
+                .fetch_optional(pool)
+                .await
+        })?;
 
         Ok(camera)
     }
@@ -103,37 +113,40 @@ impl ConfigRepository {
         let now = chrono::Utc::now();
         let family = req.family.unwrap_or_default();
 
-        sqlx::query(
-            r#"
-            INSERT INTO cameras (
-                camera_id, name, location, floor,
-                rtsp_main, rtsp_sub, snapshot_url,
-                family, manufacturer, model,
-                ip_address, mac_address,
-                camera_context,
-                enabled, polling_enabled, polling_interval_sec,
-                suggest_policy_weight,
-                created_at, updated_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, TRUE, TRUE, 60, 5, ?, ?)
-            "#,
-        )
-        .bind(&req.camera_id)
-        .bind(&req.name)
-        .bind(&req.location)
-        .bind(&req.floor)
-        .bind(&req.rtsp_main)
-        .bind(&req.rtsp_sub)
-        .bind(&req.snapshot_url)
-        .bind(format!("{:?}", family).to_lowercase())
-        .bind(&req.manufacturer)
-        .bind(&req.model)
-        .bind(&req.ip_address)
-        .bind(&req.mac_address)
-        .bind(&req.camera_context)
-        .bind(&now)
-        .bind(&now)
-        .execute(&self.pool)
-        .await?;
+        with_db!(&self.db, pool => {
+            sqlx::query(
+                r#"
+                INSERT INTO cameras (
+                    camera_id, name, location, floor,
+                    rtsp_main, rtsp_sub, snapshot_url,
+                    family, manufacturer, model,
+                    ip_address, mac_address,
+                    camera_context,
+                    enabled, polling_enabled, polling_interval_sec,
+                    suggest_policy_weight,
+                    created_at, updated_at
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, TRUE, TRUE, 60, 5, ?, ?)
+                "#,
+            )
+            .bind(&req.camera_id)
+            .bind(&req.name)
+            .bind(&req.location)
+            .bind(&req.floor)
+            .bind(&req.rtsp_main)
+            .bind(&req.rtsp_sub)
+            .bind(&req.snapshot_url)
+            .bind(format!("{:?}", family).to_lowercase())
+            .bind(&req.manufacturer)
+            .bind(&req.model)
+            .bind(&req.ip_address)
+            .bind(&req.mac_address)
+            .bind(&req.camera_context)
+            .bind(&now)
+            .bind(&now)
+            .execute(pool)
+            .await
+            .map(|r| r.rows_affected())
+        })?;
 
         self.get_camera(&req.camera_id)
             .await?
@@ -215,6 +228,8 @@ impl ConfigRepository {
         if req.conf_override.is_some() { set_clauses.push("conf_override = ?".to_string()); }
         if req.nms_threshold.is_some() { set_clauses.push("nms_threshold = ?".to_string()); }
         if req.par_threshold.is_some() { set_clauses.push("par_threshold = ?".to_string()); }
+        if req.motion_sensitivity.is_some() { set_clauses.push("motion_sensitivity = ?".to_string()); }
+        if req.motion_noise_floor.is_some() { set_clauses.push("motion_noise_floor = ?".to_string()); }
 
         // JSON fields
         if req.camera_context.is_some() { set_clauses.push("camera_context = ?".to_string()); }
@@ -242,93 +257,97 @@ impl ConfigRepository {
         );
 
         // Build the query with bindings
-        let mut q = sqlx::query(&query).bind(now);
-
-        // Bind in same order as set_clauses
-        // String fields
-        if let Some(ref v) = req.name { q = q.bind(v); }
-        if let Some(ref v) = req.location { q = q.bind(v); }
-        if let Some(ref v) = req.floor { q = q.bind(v); }
-        if let Some(ref v) = req.rid { q = q.bind(v); }
-        if let Some(ref v) = req.rtsp_main { q = q.bind(v); }
-        if let Some(ref v) = req.rtsp_sub { q = q.bind(v); }
-        if let Some(ref v) = req.rtsp_username { q = q.bind(v); }
-        if let Some(ref v) = req.rtsp_password { q = q.bind(v); }
-        if let Some(ref v) = req.snapshot_url { q = q.bind(v); }
-        if let Some(ref v) = req.family { q = q.bind(String::from(*v)); }
-        if let Some(ref v) = req.manufacturer { q = q.bind(v); }
-        if let Some(ref v) = req.model { q = q.bind(v); }
-        if let Some(ref v) = req.ip_address { q = q.bind(v); }
-        if let Some(ref v) = req.mac_address { q = q.bind(v); }
-        if let Some(ref v) = req.lacis_id { q = q.bind(v); }
-        if let Some(ref v) = req.cic { q = q.bind(v); }
-        if let Some(ref v) = req.fid { q = q.bind(v); }
-        if let Some(ref v) = req.tid { q = q.bind(v); }
-        if let Some(ref v) = req.preset_id { q = q.bind(v); }
-        if let Some(ref v) = req.preset_version { q = q.bind(v); }
-        if let Some(ref v) = req.fit_mode { q = q.bind(v); }
-        if let Some(ref v) = req.serial_number { q = q.bind(v); }
-        if let Some(ref v) = req.hardware_id { q = q.bind(v); }
-        if let Some(ref v) = req.firmware_version { q = q.bind(v); }
-        if let Some(ref v) = req.onvif_endpoint { q = q.bind(v); }
-        if let Some(ref v) = req.resolution_main { q = q.bind(v); }
-        if let Some(ref v) = req.codec_main { q = q.bind(v); }
-        if let Some(ref v) = req.resolution_sub { q = q.bind(v); }
-        if let Some(ref v) = req.codec_sub { q = q.bind(v); }
-        if let Some(ref v) = req.audio_codec { q = q.bind(v); }
-        if let Some(ref v) = req.discovery_method { q = q.bind(v); }
-        // SDM fields (migration 018)
-        if let Some(ref v) = req.sdm_device_id { q = q.bind(v); }
-        if let Some(ref v) = req.sdm_structure { q = q.bind(v); }
-        if let Some(ref v) = req.sdm_traits { q = q.bind(v); }
-
-        // Boolean fields
-        if let Some(v) = req.enabled { q = q.bind(v); }
-        if let Some(v) = req.polling_enabled { q = q.bind(v); }
-        if let Some(v) = req.ai_enabled { q = q.bind(v); }
-        if let Some(v) = req.ptz_supported { q = q.bind(v); }
-        if let Some(v) = req.ptz_continuous { q = q.bind(v); }
-        if let Some(v) = req.ptz_absolute { q = q.bind(v); }
-        if let Some(v) = req.ptz_relative { q = q.bind(v); }
-        if let Some(v) = req.ptz_home_supported { q = q.bind(v); }
-        if let Some(v) = req.ptz_disabled { q = q.bind(v); }
-        if let Some(v) = req.audio_input_supported { q = q.bind(v); }
-        if let Some(v) = req.audio_output_supported { q = q.bind(v); }
-
-        // Integer fields
-        if let Some(v) = req.polling_interval_sec { q = q.bind(v); }
-        if let Some(v) = req.suggest_policy_weight { q = q.bind(v); }
-        if let Some(v) = req.ai_interval_sec { q = q.bind(v); }
-        if let Some(v) = req.rotation { q = q.bind(v); }
-        if let Some(v) = req.sort_order { q = q.bind(v); }
-        if let Some(v) = req.rtsp_port { q = q.bind(v); }
-        if let Some(v) = req.http_port { q = q.bind(v); }
-        if let Some(v) = req.onvif_port { q = q.bind(v); }
-        if let Some(v) = req.fps_main { q = q.bind(v); }
-        if let Some(v) = req.bitrate_main { q = q.bind(v); }
-        if let Some(v) = req.fps_sub { q = q.bind(v); }
-        if let Some(v) = req.bitrate_sub { q = q.bind(v); }
-
-        // Threshold override fields (f32) - double option: Some(inner) means update, inner can be Some(v) or None
-        if let Some(ref inner) = req.conf_override { q = q.bind(inner.as_ref()); }
-        if let Some(ref inner) = req.nms_threshold { q = q.bind(inner.as_ref()); }
-        if let Some(ref inner) = req.par_threshold { q = q.bind(inner.as_ref()); }
-
-        // JSON fields
-        if let Some(ref v) = req.camera_context { q = q.bind(v); }
-        if let Some(ref v) = req.inference_config { q = q.bind(v); }
-        if let Some(ref v) = req.ptz_pan_range { q = q.bind(v); }
-        if let Some(ref v) = req.ptz_tilt_range { q = q.bind(v); }
-        if let Some(ref v) = req.ptz_zoom_range { q = q.bind(v); }
-        if let Some(ref v) = req.ptz_presets { q = q.bind(v); }
-        if let Some(ref v) = req.onvif_profiles { q = q.bind(v); }
-        if let Some(ref v) = req.onvif_scopes { q = q.bind(v); }
-        if let Some(ref v) = req.onvif_network_interfaces { q = q.bind(v); }
-        if let Some(ref v) = req.onvif_capabilities { q = q.bind(v); }
-
-        // Bind camera_id last
-        q = q.bind(camera_id);
-        q.execute(&self.pool).await?;
+        with_db!(&self.db, pool => {
+            let mut q = sqlx::query(&query).bind(now);
+
+            // Bind in same order as set_clauses
+            // String fields
+            if let Some(ref v) = req.name { q = q.bind(v); }
+            if let Some(ref v) = req.location { q = q.bind(v); }
+            if let Some(ref v) = req.floor { q = q.bind(v); }
+            if let Some(ref v) = req.rid { q = q.bind(v); }
+            if let Some(ref v) = req.rtsp_main { q = q.bind(v); }
+            if let Some(ref v) = req.rtsp_sub { q = q.bind(v); }
+            if let Some(ref v) = req.rtsp_username { q = q.bind(v); }
+            if let Some(ref v) = req.rtsp_password { q = q.bind(v); }
+            if let Some(ref v) = req.snapshot_url { q = q.bind(v); }
+            if let Some(ref v) = req.family { q = q.bind(String::from(*v)); }
+            if let Some(ref v) = req.manufacturer { q = q.bind(v); }
+            if let Some(ref v) = req.model { q = q.bind(v); }
+            if let Some(ref v) = req.ip_address { q = q.bind(v); }
+            if let Some(ref v) = req.mac_address { q = q.bind(v); }
+            if let Some(ref v) = req.lacis_id { q = q.bind(v); }
+            if let Some(ref v) = req.cic { q = q.bind(v); }
+            if let Some(ref v) = req.fid { q = q.bind(v); }
+            if let Some(ref v) = req.tid { q = q.bind(v); }
+            if let Some(ref v) = req.preset_id { q = q.bind(v); }
+            if let Some(ref v) = req.preset_version { q = q.bind(v); }
+            if let Some(ref v) = req.fit_mode { q = q.bind(v); }
+            if let Some(ref v) = req.serial_number { q = q.bind(v); }
+            if let Some(ref v) = req.hardware_id { q = q.bind(v); }
+            if let Some(ref v) = req.firmware_version { q = q.bind(v); }
+            if let Some(ref v) = req.onvif_endpoint { q = q.bind(v); }
+            if let Some(ref v) = req.resolution_main { q = q.bind(v); }
+            if let Some(ref v) = req.codec_main { q = q.bind(v); }
+            if let Some(ref v) = req.resolution_sub { q = q.bind(v); }
+            if let Some(ref v) = req.codec_sub { q = q.bind(v); }
+            if let Some(ref v) = req.audio_codec { q = q.bind(v); }
+            if let Some(ref v) = req.discovery_method { q = q.bind(v); }
+            // SDM fields (migration 018)
+            if let Some(ref v) = req.sdm_device_id { q = q.bind(v); }
+            if let Some(ref v) = req.sdm_structure { q = q.bind(v); }
+            if let Some(ref v) = req.sdm_traits { q = q.bind(v); }
+
+            // Boolean fields
+            if let Some(v) = req.enabled { q = q.bind(v); }
+            if let Some(v) = req.polling_enabled { q = q.bind(v); }
+            if let Some(v) = req.ai_enabled { q = q.bind(v); }
+            if let Some(v) = req.ptz_supported { q = q.bind(v); }
+            if let Some(v) = req.ptz_continuous { q = q.bind(v); }
+            if let Some(v) = req.ptz_absolute { q = q.bind(v); }
+            if let Some(v) = req.ptz_relative { q = q.bind(v); }
+            if let Some(v) = req.ptz_home_supported { q = q.bind(v); }
+            if let Some(v) = req.ptz_disabled { q = q.bind(v); }
+            if let Some(v) = req.audio_input_supported { q = q.bind(v); }
+            if let Some(v) = req.audio_output_supported { q = q.bind(v); }
+
+            // Integer fields
+            if let Some(v) = req.polling_interval_sec { q = q.bind(v); }
+            if let Some(v) = req.suggest_policy_weight { q = q.bind(v); }
+            if let Some(v) = req.ai_interval_sec { q = q.bind(v); }
+            if let Some(v) = req.rotation { q = q.bind(v); }
+            if let Some(v) = req.sort_order { q = q.bind(v); }
+            if let Some(v) = req.rtsp_port { q = q.bind(v); }
+            if let Some(v) = req.http_port { q = q.bind(v); }
+            if let Some(v) = req.onvif_port { q = q.bind(v); }
+            if let Some(v) = req.fps_main { q = q.bind(v); }
+            if let Some(v) = req.bitrate_main { q = q.bind(v); }
+            if let Some(v) = req.fps_sub { q = q.bind(v); }
+            if let Some(v) = req.bitrate_sub { q = q.bind(v); }
+
+            // Threshold override fields (f32) - double option: Some(inner) means update, inner can be Some(v) or None
+            if let Some(ref inner) = req.conf_override { q = q.bind(inner.as_ref()); }
+            if let Some(ref inner) = req.nms_threshold { q = q.bind(inner.as_ref()); }
+            if let Some(ref inner) = req.par_threshold { q = q.bind(inner.as_ref()); }
+            if let Some(ref inner) = req.motion_sensitivity { q = q.bind(inner.as_ref()); }
+            if let Some(ref inner) = req.motion_noise_floor { q = q.bind(inner.as_ref()); }
+
+            // JSON fields
+            if let Some(ref v) = req.camera_context { q = q.bind(v); }
+            if let Some(ref v) = req.inference_config { q = q.bind(v); }
+            if let Some(ref v) = req.ptz_pan_range { q = q.bind(v); }
+            if let Some(ref v) = req.ptz_tilt_range { q = q.bind(v); }
+            if let Some(ref v) = req.ptz_zoom_range { q = q.bind(v); }
+            if let Some(ref v) = req.ptz_presets { q = q.bind(v); }
+            if let Some(ref v) = req.onvif_profiles { q = q.bind(v); }
+            if let Some(ref v) = req.onvif_scopes { q = q.bind(v); }
+            if let Some(ref v) = req.onvif_network_interfaces { q = q.bind(v); }
+            if let Some(ref v) = req.onvif_capabilities { q = q.bind(v); }
+
+            // Bind camera_id last
+            q = q.bind(camera_id);
+            q.execute(pool).await.map(|r| r.rows_affected())
+        })?;
 
         self.get_camera(camera_id)
             .await?
@@ -338,12 +357,15 @@ impl ConfigRepository {
     /// Soft delete camera (set deleted_at)
     pub async fn soft_delete_camera(&self, camera_id: &str) -> Result<Camera> {
         let now = chrono::Utc::now();
-        sqlx::query("UPDATE cameras SET deleted_at = ?, updated_at = ? WHERE camera_id = ?")
-            .bind(&now)
-            .bind(&now)
-            .bind(camera_id)
-            .execute(&self.pool)
-            .await?;
+        with_db!(&self.db, pool => {
+            sqlx::query("UPDATE cameras SET deleted_at = ?, updated_at = ? WHERE camera_id = ?")
+                .bind(&now)
+                .bind(&now)
+                .bind(camera_id)
+                .execute(pool)
+                .await
+                .map(|r| r.rows_affected())
+        })?;
 
         self.get_camera(camera_id)
             .await?
@@ -353,11 +375,14 @@ impl ConfigRepository {
     /// Restore soft-deleted camera by MAC address
     pub async fn restore_camera_by_mac(&self, mac_address: &str) -> Result<Camera> {
         let now = chrono::Utc::now();
-        sqlx::query("UPDATE cameras SET deleted_at = NULL, updated_at = ? WHERE mac_address = ?")
-            .bind(&now)
-            .bind(mac_address)
-            .execute(&self.pool)
-            .await?;
+        with_db!(&self.db, pool => {
+            sqlx::query("UPDATE cameras SET deleted_at = NULL, updated_at = ? WHERE mac_address = ?")
+                .bind(&now)
+                .bind(mac_address)
+                .execute(pool)
+                .await
+                .map(|r| r.rows_affected())
+        })?;
 
         self.get_camera_by_mac(mac_address)
             .await?
@@ -367,13 +392,16 @@ impl ConfigRepository {
     /// Update camera IP address (for rescan)
     pub async fn update_camera_ip(&self, camera_id: &str, new_ip: &str) -> Result<Camera> {
         let now = chrono::Utc::now();
-        sqlx::query("UPDATE cameras SET ip_address = ?, last_rescan_at = ?, updated_at = ? WHERE camera_id = ?")
-            .bind(new_ip)
-            .bind(&now)
-            .bind(&now)
-            .bind(camera_id)
-            .execute(&self.pool)
-            .await?;
+        with_db!(&self.db, pool => {
+            sqlx::query("UPDATE cameras SET ip_address = ?, last_rescan_at = ?, updated_at = ? WHERE camera_id = ?")
+                .bind(new_ip)
+                .bind(&now)
+                .bind(&now)
+                .bind(camera_id)
+                .execute(pool)
+                .await
+                .map(|r| r.rows_affected())
+        })?;
 
         self.get_camera(camera_id)
             .await?
@@ -383,21 +411,27 @@ impl ConfigRepository {
     /// Update last_verified_at
     pub async fn update_camera_verified(&self, camera_id: &str) -> Result<()> {
         let now = chrono::Utc::now();
-        sqlx::query("UPDATE cameras SET last_verified_at = ?, updated_at = ? WHERE camera_id = ?")
-            .bind(&now)
-            .bind(&now)
-            .bind(camera_id)
-            .execute(&self.pool)
-            .await?;
+        with_db!(&self.db, pool => {
+            sqlx::query("UPDATE cameras SET last_verified_at = ?, updated_at = ? WHERE camera_id = ?")
+                .bind(&now)
+                .bind(&now)
+                .bind(camera_id)
+                .execute(pool)
+                .await
+                .map(|r| r.rows_affected())
+        })?;
         Ok(())
     }
 
     /// Delete camera
     pub async fn delete_camera(&self, camera_id: &str) -> Result<()> {
-        sqlx::query("DELETE FROM cameras WHERE camera_id = ?")
-            .bind(camera_id)
-            .execute(&self.pool)
-            .await?;
+        with_db!(&self.db, pool => {
+            sqlx::query("DELETE FROM cameras WHERE camera_id = ?")
+                .bind(camera_id)
+                .execute(pool)
+                .await
+                .map(|r| r.rows_affected())
+        })?;
 
         Ok(())
     }
@@ -408,17 +442,19 @@ impl ConfigRepository {
 
     /// Get active schema version
     pub async fn get_active_schema_version(&self) -> Result<Option<SchemaVersion>> {
-        let version = sqlx::query_as::<_, SchemaVersion>(
-            r#"
-            SELECT version_id, schema_json, is_active, created_at
-            FROM schema_versions
-            WHERE is_active = TRUE
-            ORDER BY created_at DESC
-            LIMIT 1
-            "#,
-        )
-        .fetch_optional(&self.pool)
-        .await?;
+        let version = with_db!(&self.db, pool => {
+            sqlx::query_as::<_, SchemaVersion>(
+                r#"
+                SELECT version_id, schema_json, is_active, created_at
+                FROM schema_versions
+                WHERE is_active = TRUE
+                ORDER BY created_at DESC
+                LIMIT 1
+                "#,
+            )
+            .fetch_optional(pool)
+            .await
+        })?;
 
         Ok(version)
     }
@@ -428,24 +464,32 @@ impl ConfigRepository {
         let now = chrono::Utc::now();
 
         // Deactivate all
-        sqlx::query("UPDATE schema_versions SET is_active = FALSE")
-            .execute(&self.pool)
-            .await?;
+        with_db!(&self.db, pool => {
+            sqlx::query("UPDATE schema_versions SET is_active = FALSE")
+                .execute(pool)
+                .await
+                .map(|r| r.rows_affected())
+        })?;
 
         // Insert or update
-        sqlx::query(
+        let sql = format!(
             r#"
             INSERT INTO schema_versions (version_id, schema_json, is_active, created_at)
             VALUES (?, ?, TRUE, ?)
-            ON DUPLICATE KEY UPDATE schema_json = ?, is_active = TRUE
+            {}
             "#,
-        )
-        .bind(version_id)
-        .bind(&schema_json)
-        .bind(&now)
-        .bind(&schema_json)
-        .execute(&self.pool)
-        .await?;
+            self.db
+                .upsert("version_id", &["schema_json"], &["is_active = TRUE"])
+        );
+        with_db!(&self.db, pool => {
+            sqlx::query(&sql)
+                .bind(version_id)
+                .bind(&schema_json)
+                .bind(&now)
+                .execute(pool)
+            .await
+            .map(|r| r.rows_affected())
+        })?;
 
         Ok(())
     }
@@ -456,23 +500,27 @@ impl ConfigRepository {
 
     /// Get setting by key
     pub async fn get_setting(&self, key: &str) -> Result<Option<serde_json::Value>> {
-        let setting = sqlx::query_as::<_, Setting>(
-            "SELECT setting_key, setting_json, updated_at FROM settings WHERE setting_key = ?",
-        )
-        .bind(key)
-        .fetch_optional(&self.pool)
-        .await?;
+        let setting = with_db!(&self.db, pool => {
+            sqlx::query_as::<_, Setting>(
+                "SELECT setting_key, setting_json, updated_at FROM settings WHERE setting_key = ?",
+            )
+            .bind(key)
+            .fetch_optional(pool)
+            .await
+        })?;
 
         Ok(setting.map(|s| s.setting_json))
     }
 
     /// Get all settings
     pub async fn get_all_settings(&self) -> Result<std::collections::HashMap<String, serde_json::Value>> {
-        let settings = sqlx::query_as::<_, Setting>(
-            "SELECT setting_key, setting_json, updated_at FROM settings",
-        )
-        .fetch_all(&self.pool)
-        .await?;
+        let settings = with_db!(&self.db, pool => {
+            sqlx::query_as::<_, Setting>(
+                "SELECT setting_key, setting_json, updated_at FROM settings",
+            )
+            .fetch_all(pool)
+            .await
+        })?;
 
         let map = settings
             .into_iter()
@@ -486,21 +534,100 @@ impl ConfigRepository {
     pub async fn set_setting(&self, key: &str, value: serde_json::Value) -> Result<()> {
         let now = chrono::Utc::now();
 
-        sqlx::query(
+        let sql = format!(
             r#"
             INSERT INTO settings (setting_key, setting_json, updated_at)
             VALUES (?, ?, ?)
-            ON DUPLICATE KEY UPDATE setting_json = ?, updated_at = ?
+            {}
             "#,
-        )
-        .bind(key)
-        .bind(&value)
-        .bind(&now)
-        .bind(&value)
-        .bind(&now)
-        .execute(&self.pool)
-        .await?;
+            self.db
+                .upsert("setting_key", &["setting_json", "updated_at"], &[])
+        );
+        with_db!(&self.db, pool => {
+            sqlx::query(&sql)
+                .bind(key)
+                .bind(&value)
+                .bind(&now)
+                .execute(pool)
+                .await
+                .map(|r| r.rows_affected())
+        })?;
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_request(camera_id: &str) -> CreateCameraRequest {
+        CreateCameraRequest {
+            camera_id: camera_id.to_string(),
+            name: "Entrance".to_string(),
+            location: "1F".to_string(),
+            floor: None,
+            rtsp_main: Some("rtsp://192.168.125.10/stream1".to_string()),
+            rtsp_sub: None,
+            snapshot_url: None,
+            family: None,
+            manufacturer: Some("TP-Link".to_string()),
+            model: Some("C200".to_string()),
+            ip_address: Some("192.168.125.10".to_string()),
+            mac_address: Some("AA:BB:CC:DD:EE:FF".to_string()),
+            camera_context: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_camera_crud() {
+        let repo = ConfigRepository::new(Db::memory().await.unwrap());
+
+        let camera = repo.create_camera(&create_request("cam-1")).await.unwrap();
+        assert_eq!(camera.name, "Entrance");
+        assert!(camera.enabled);
+
+        let update = UpdateCameraRequest {
+            name: Some("Back door".to_string()),
+            polling_enabled: Some(false),
+            ..Default::default()
+        };
+        let camera = repo.update_camera("cam-1", &update).await.unwrap();
+        assert_eq!(camera.name, "Back door");
+        assert!(!camera.polling_enabled);
+        assert!(repo.get_enabled_cameras().await.unwrap().is_empty());
+
+        let camera = repo.soft_delete_camera("cam-1").await.unwrap();
+        assert!(camera.deleted_at.is_some());
+        let camera = repo.restore_camera_by_mac("AA:BB:CC:DD:EE:FF").await.unwrap();
+        assert!(camera.deleted_at.is_none());
+
+        repo.delete_camera("cam-1").await.unwrap();
+        assert!(repo.get_camera("cam-1").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_settings_and_schema_version() {
+        let repo = ConfigRepository::new(Db::memory().await.unwrap());
+
+        repo.set_setting("polling", serde_json::json!({"interval": 60}))
+            .await
+            .unwrap();
+        repo.set_setting("polling", serde_json::json!({"interval": 30}))
+            .await
+            .unwrap();
+        assert_eq!(
+            repo.get_setting("polling").await.unwrap(),
+            Some(serde_json::json!({"interval": 30}))
+        );
+        assert_eq!(repo.get_all_settings().await.unwrap().len(), 1);
+
+        let active = repo.get_active_schema_version().await.unwrap().unwrap();
+        assert_eq!(active.version_id, "2025-12-29.1");
+        repo.set_schema_version("2026-01-01.1", serde_json::json!({"version": "2026-01-01.1"}))
+            .await
+            .unwrap();
+        let active = repo.get_active_schema_version().await.unwrap().unwrap();
+        assert_eq!(active.version_id, "2026-01-01.1");
+    }
+}
@@ -58,6 +58,22 @@ impl ConfigService {
 
     /// Update camera
     pub async fn update_camera(&self, camera_id: &str, req: UpdateCameraRequest) -> Result<Camera> {
+        // Validate motion pre-filter overrides
+        if let Some(Some(v)) = req.motion_sensitivity {
+            if !(0.0..=1.0).contains(&v) {
+                return Err(crate::Error::Validation(
+                    "motion_sensitivity must be between 0.0 and 1.0".to_string(),
+                ));
+            }
+        }
+        if let Some(Some(v)) = req.motion_noise_floor {
+            if !(0..=255).contains(&v) {
+                return Err(crate::Error::Validation(
+                    "motion_noise_floor must be between 0 and 255".to_string(),
+                ));
+            }
+        }
+
         // Check existence
         if self.repo.get_camera(camera_id).await?.is_none() {
             return Err(crate::Error::NotFound(format!(
@@ -244,4 +260,19 @@ impl ConfigService {
         let json = serde_json::to_value(&policy)?;
         self.repo.set_setting("notification", json).await
     }
+
+    /// Get auto attunement policy
+    pub async fn get_attunement_policy(&self) -> Result<AttunementPolicy> {
+        let setting = self.repo.get_setting("attunement").await?;
+        match setting {
+            Some(json) => Ok(serde_json::from_value(json)?),
+            None => Ok(AttunementPolicy::default()),
+        }
+    }
+
+    /// Set auto attunement policy
+    pub async fn set_attunement_policy(&self, policy: AttunementPolicy) -> Result<()> {
+        let json = serde_json::to_value(&policy)?;
+        self.repo.set_setting("attunement", json).await
+    }
 }
@@ -121,6 +121,11 @@ pub struct Camera {
     pub nms_threshold: Option<f32>,
     /// PAR attribute threshold override (0.30-0.80), None = use preset default
     pub par_threshold: Option<f32>,
+    // === モーションプレフィルタ (migration 033) ===
+    /// Local change detection sensitivity (0.0-1.0), None = policy default
+    pub motion_sensitivity: Option<f32>,
+    /// Per-pixel luma diff treated as noise (0-255), None = policy default
+    pub motion_noise_floor: Option<i32>,
     // === タイムスタンプ ===
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
@@ -301,6 +306,13 @@ pub struct UpdateCameraRequest {
     /// PAR attribute threshold override (0.30-0.80)
     #[serde(default, deserialize_with = "double_option")]
     pub par_threshold: Option<Option<f32>>,
+    // === モーションプレフィルタ (migration 033) ===
+    /// Motion pre-filter sensitivity (0.0-1.0), null = policy default
+    #[serde(default, deserialize_with = "double_option")]
+    pub motion_sensitivity: Option<Option<f32>>,
+    /// Motion pre-filter noise floor (0-255), null = policy default
+    #[serde(default, deserialize_with = "double_option")]
+    pub motion_noise_floor: Option<Option<i32>>,
 }
 
 /// Schema version entity
@@ -327,6 +339,12 @@ pub struct PollingPolicy {
     pub priority_interval_sec: i32,
     pub max_concurrent: i32,
     pub timeout_ms: i32,
+    /// Activity-driven per-camera scheduling (absent in older settings rows)
+    #[serde(default)]
+    pub adaptive: AdaptivePollingPolicy,
+    /// Local motion pre-filter before IS21 inference
+    #[serde(default)]
+    pub prefilter: MotionPrefilterPolicy,
 }
 
 impl Default for PollingPolicy {
@@ -336,6 +354,65 @@ impl Default for PollingPolicy {
             priority_interval_sec: 15,
             max_concurrent: 5,
             timeout_ms: 10000,
+            adaptive: AdaptivePollingPolicy::default(),
+            prefilter: MotionPrefilterPolicy::default(),
+        }
+    }
+}
+
+/// Adaptive polling policy (settings.polling.adaptive)
+///
+/// enabled=false の場合は従来通り全カメラを毎サイクル巡回する
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AdaptivePollingPolicy {
+    /// Enable per-camera adaptive intervals
+    pub enabled: bool,
+    /// Seconds after a detection during which the camera stays at priority interval
+    pub activity_window_sec: i32,
+    /// Consecutive "no change" results before backoff starts
+    pub no_change_backoff_after: i32,
+    /// Backoff ceiling in seconds
+    pub max_interval_sec: i32,
+    /// Snapshot budget per subnet cycle in KB (0 = unlimited)
+    pub subnet_budget_kb_per_cycle: i32,
+}
+
+impl Default for AdaptivePollingPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            activity_window_sec: 300,
+            no_change_backoff_after: 3,
+            max_interval_sec: 600,
+            subnet_budget_kb_per_cycle: 0,
+        }
+    }
+}
+
+/// Motion pre-filter policy (settings.polling.prefilter)
+///
+/// カメラ個別値（cameras.motion_sensitivity / motion_noise_floor）が未設定の場合のデフォルト
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MotionPrefilterPolicy {
+    /// Skip IS21 inference when the scene has not changed
+    pub enabled: bool,
+    /// Default sensitivity (0.0 = only large changes, 1.0 = tiny changes)
+    pub default_sensitivity: f32,
+    /// Default per-pixel luma noise floor (0-255)
+    pub default_noise_floor: i32,
+    /// Force a full IS21 analysis after this many consecutive skips
+    pub force_analysis_every: u32,
+}
+
+impl Default for MotionPrefilterPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            default_sensitivity: 0.5,
+            default_noise_floor: 12,
+            force_analysis_every: 10,
         }
     }
 }
@@ -364,6 +441,7 @@ impl Default for SuggestPolicy {
 
 /// Admission policy
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AdmissionPolicy {
     pub total_stream_units: i32,
     pub max_ui_users: i32,
@@ -373,6 +451,12 @@ pub struct AdmissionPolicy {
     pub modal_ttl_sec: i32,
     pub heartbeat_interval_sec: i32,
     pub heartbeat_grace_sec: i32,
+    /// Max concurrent modal leases per camera (0 = unlimited)
+    pub max_leases_per_camera: i32,
+    /// Max waiting tickets (0 = queueing disabled)
+    pub max_queue_length: i32,
+    /// Waiting tickets expire if not polled within this period
+    pub queue_ticket_ttl_sec: i32,
 }
 
 impl Default for AdmissionPolicy {
@@ -386,6 +470,9 @@ impl Default for AdmissionPolicy {
             modal_ttl_sec: 300,
             heartbeat_interval_sec: 30,
             heartbeat_grace_sec: 45,
+            max_leases_per_camera: 4,
+            max_queue_length: 50,
+            queue_ticket_ttl_sec: 60,
         }
     }
 }
@@ -409,3 +496,63 @@ impl Default for NotificationPolicy {
         }
     }
 }
+
+/// Closed-loop auto attunement policy (settings.attunement)
+///
+/// 候補閾値を trial_window_hours の間試行し、ベースラインと比較して
+/// 採用（promote）またはロールバックする
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AttunementPolicy {
+    /// Run the attunement loop on a schedule
+    pub enabled: bool,
+    /// Loop interval (minutes)
+    pub evaluation_interval_min: u32,
+    /// Baseline window before a trial (hours)
+    pub baseline_window_hours: u32,
+    /// Trial duration (hours)
+    pub trial_window_hours: u32,
+    /// Wait after a finished trial before starting the next (hours)
+    pub cooldown_hours: u32,
+    /// Minimum detections in both windows for a conclusive evaluation
+    pub min_detections: i64,
+    /// False-positive rate above which thresholds are tightened
+    pub target_false_positive_rate: f32,
+    /// Unknown rate above which thresholds are loosened (when FP is low)
+    pub target_unknown_rate: f32,
+    /// Allowed worsening of FP / unknown rate before rollback
+    pub rate_tolerance: f32,
+    /// Maximum allowed drop of detections per hour (0.0-1.0)
+    pub max_volume_drop: f32,
+    /// Step per trial for each parameter
+    pub conf_step: f32,
+    pub nms_step: f32,
+    pub par_step: f32,
+    /// Parameters the loop may tune
+    pub tune_conf: bool,
+    pub tune_nms: bool,
+    pub tune_par: bool,
+}
+
+impl Default for AttunementPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            evaluation_interval_min: 30,
+            baseline_window_hours: 72,
+            trial_window_hours: 24,
+            cooldown_hours: 12,
+            min_detections: 30,
+            target_false_positive_rate: 0.10,
+            target_unknown_rate: 0.10,
+            rate_tolerance: 0.02,
+            max_volume_drop: 0.5,
+            conf_step: 0.05,
+            nms_step: 0.05,
+            par_step: 0.05,
+            tune_conf: true,
+            tune_nms: true,
+            tune_par: true,
+        }
+    }
+}
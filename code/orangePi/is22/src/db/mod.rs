@@ -0,0 +1,452 @@
+//! Db - storage backend selection (MySQL / SQLite)
+//!
+//! ## Responsibilities
+//!
+//! - DATABASE_URL のスキームからバックエンドを判定し、プールを作成
+//! - リポジトリ層が両バックエンドで同じクエリコードを使えるようにする（[`with_db!`]）
+//! - 方言差（現在時刻・upsert・日時演算・INSERT ID）の吸収
+//!
+//! SQLite 対応はリポジトリ層（config_store, detection_log, summary, paraclate,
+//! camera_sync, access_absorber, camera_brand）に限られ、用途はインメモリ DB での
+//! テスト（[`Db::memory`]）とライブラリとしての組み込み。それ以外のサービスは
+//! `MySqlPool` を直接使うため、camserver 本体（serve / 管理コマンド）は MySQL 専用で、
+//! AppConfig も `mysql://` 以外の `database_url` を拒否する。
+//!
+//! SQLite の日時は RFC3339（`2025-01-01T00:00:00.000+00:00`）の TEXT として保存し、
+//! sqlx の `DateTime<Utc>` エンコードと文字列比較の順序を一致させる。
+
+use crate::error::{Error, Result};
+use chrono::{DateTime, SecondsFormat, Utc};
+use sqlx::mysql::{MySqlPool, MySqlPoolOptions, MySqlQueryResult, MySqlRow};
+use sqlx::sqlite::{
+    SqliteConnectOptions, SqliteJournalMode, SqlitePool, SqlitePoolOptions, SqliteQueryResult,
+    SqliteRow,
+};
+use sqlx::{Decode, MySql, Row, Sqlite, Type};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// SQLite expression for the current time (same text format as bound `DateTime<Utc>`)
+const SQLITE_NOW: &str = "strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now')";
+
+/// Repositories that run on both backends (the rest of the service is MySQL-only)
+pub const SQLITE_READY_MODULES: &[&str] = &[
+    "config_store",
+    "detection_log",
+    "summary",
+    "paraclate",
+    "camera_sync",
+    "access_absorber",
+    "camera_brand",
+];
+
+/// Run `$body` with `$pool` bound to the concrete pool of either backend
+///
+/// 本体は各バックエンド向けに 2 回展開されるため、行の読み出しも本体内で行い
+/// バックエンド非依存の値を返すこと。
+#[macro_export]
+macro_rules! with_db {
+    ($db:expr, $pool:ident => $body:expr) => {
+        match $db {
+            $crate::db::Db::MySql($pool) => $body,
+            $crate::db::Db::Sqlite($pool) => $body,
+        }
+    };
+}
+
+/// Storage backend kind
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DbBackend {
+    MySql,
+    Sqlite,
+}
+
+impl DbBackend {
+    /// Backend for a DATABASE_URL (`mysql://...` / `sqlite:...`)
+    pub fn from_url(url: &str) -> Result<Self> {
+        if url.starts_with("mysql://") {
+            Ok(DbBackend::MySql)
+        } else if url.starts_with("sqlite:") {
+            Ok(DbBackend::Sqlite)
+        } else {
+            Err(Error::Config(
+                "database_url must be a mysql:// or sqlite: URL".to_string(),
+            ))
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DbBackend::MySql => "mysql",
+            DbBackend::Sqlite => "sqlite",
+        }
+    }
+}
+
+/// Connection pool of the configured backend
+#[derive(Debug, Clone)]
+pub enum Db {
+    MySql(MySqlPool),
+    Sqlite(SqlitePool),
+}
+
+impl From<MySqlPool> for Db {
+    fn from(pool: MySqlPool) -> Self {
+        Db::MySql(pool)
+    }
+}
+
+impl From<SqlitePool> for Db {
+    fn from(pool: SqlitePool) -> Self {
+        Db::Sqlite(pool)
+    }
+}
+
+impl Db {
+    /// Connect to `url`; SQLite databases are created and migrated on connect
+    pub async fn connect(url: &str, max_connections: u32, acquire_timeout: Duration) -> Result<Self> {
+        match DbBackend::from_url(url)? {
+            DbBackend::MySql => {
+                let pool = MySqlPoolOptions::new()
+                    .max_connections(max_connections)
+                    .acquire_timeout(acquire_timeout)
+                    .connect(url)
+                    .await?;
+                Ok(Db::MySql(pool))
+            }
+            DbBackend::Sqlite => {
+                let options = SqliteConnectOptions::from_str(url)?
+                    .create_if_missing(true)
+                    .journal_mode(SqliteJournalMode::Wal)
+                    .foreign_keys(true)
+                    .busy_timeout(acquire_timeout);
+                let pool = SqlitePoolOptions::new()
+                    .max_connections(max_connections)
+                    .acquire_timeout(acquire_timeout)
+                    .connect_with(options)
+                    .await?;
+                crate::migration::SqliteMigrator::new(pool.clone()).up().await?;
+                Ok(Db::Sqlite(pool))
+            }
+        }
+    }
+
+    /// Fresh in-memory SQLite database with all SQLite migrations applied
+    ///
+    /// インメモリ DB は接続ごとに別物のため、接続 1 本を使い回す（テスト用）
+    pub async fn memory() -> Result<Self> {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect_with(SqliteConnectOptions::from_str("sqlite::memory:")?.foreign_keys(true))
+            .await?;
+        crate::migration::SqliteMigrator::new(pool.clone()).up().await?;
+        Ok(Db::Sqlite(pool))
+    }
+
+    pub fn backend(&self) -> DbBackend {
+        match self {
+            Db::MySql(_) => DbBackend::MySql,
+            Db::Sqlite(_) => DbBackend::Sqlite,
+        }
+    }
+
+    /// Current timestamp expression (millisecond precision)
+    pub fn now(&self) -> &'static str {
+        match self {
+            Db::MySql(_) => "NOW(3)",
+            Db::Sqlite(_) => SQLITE_NOW,
+        }
+    }
+
+    /// Start of today (UTC on SQLite, session time zone on MySQL)
+    pub fn today(&self) -> &'static str {
+        match self {
+            Db::MySql(_) => "DATE(NOW())",
+            Db::Sqlite(_) => "strftime('%Y-%m-%dT00:00:00', 'now')",
+        }
+    }
+
+    /// Timestamp `amount` units before now; `amount` is a literal or `?`
+    pub fn ago(&self, amount: &str, unit: IntervalUnit) -> String {
+        match self {
+            Db::MySql(_) => format!("DATE_SUB(NOW(3), INTERVAL {} {})", amount, unit.mysql()),
+            Db::Sqlite(_) => format!(
+                "strftime('%Y-%m-%dT%H:%M:%f+00:00', 'now', '-' || {} || ' {}')",
+                amount,
+                unit.sqlite()
+            ),
+        }
+    }
+
+    /// Timestamp as a string bind parameter (for dynamically built UPDATEs)
+    ///
+    /// SQLite では [`Db::now`] と同じ RFC3339 形式にして文字列比較の順序を保つ。
+    pub fn datetime_param(&self, dt: DateTime<Utc>) -> String {
+        match self {
+            Db::MySql(_) => dt.format("%Y-%m-%d %H:%M:%S%.3f").to_string(),
+            Db::Sqlite(_) => dt.to_rfc3339_opts(SecondsFormat::Millis, false),
+        }
+    }
+
+    /// NULL-safe equality operator (`<=>` / `IS`)
+    pub fn null_safe_eq(&self) -> &'static str {
+        match self {
+            Db::MySql(_) => "<=>",
+            Db::Sqlite(_) => "IS",
+        }
+    }
+
+    /// Upsert tail for `INSERT ... VALUES (...)`
+    ///
+    /// `columns` は挿入値で上書きする列、`extra` は `col = expr` 形式の追加代入。
+    /// `key` は SQLite の ON CONFLICT 対象（主キー / UNIQUE 列）。
+    pub fn upsert(&self, key: &str, columns: &[&str], extra: &[&str]) -> String {
+        let assignments: Vec<String> = match self {
+            Db::MySql(_) => columns
+                .iter()
+                .map(|c| format!("{c} = VALUES({c})"))
+                .chain(extra.iter().map(|e| e.to_string()))
+                .collect(),
+            Db::Sqlite(_) => columns
+                .iter()
+                .map(|c| format!("{c} = excluded.{c}"))
+                .chain(extra.iter().map(|e| e.to_string()))
+                .collect(),
+        };
+        match self {
+            Db::MySql(_) => format!("ON DUPLICATE KEY UPDATE {}", assignments.join(", ")),
+            Db::Sqlite(_) => format!(
+                "ON CONFLICT({}) DO UPDATE SET {}",
+                key,
+                assignments.join(", ")
+            ),
+        }
+    }
+}
+
+/// Unit for [`Db::ago`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntervalUnit {
+    Minute,
+    Hour,
+    Day,
+}
+
+impl IntervalUnit {
+    fn mysql(self) -> &'static str {
+        match self {
+            IntervalUnit::Minute => "MINUTE",
+            IntervalUnit::Hour => "HOUR",
+            IntervalUnit::Day => "DAY",
+        }
+    }
+
+    fn sqlite(self) -> &'static str {
+        match self {
+            IntervalUnit::Minute => "minutes",
+            IntervalUnit::Hour => "hours",
+            IntervalUnit::Day => "days",
+        }
+    }
+}
+
+/// Column type decodable on both backends
+pub trait DbValue:
+    Sized + for<'r> Decode<'r, MySql> + Type<MySql> + for<'r> Decode<'r, Sqlite> + Type<Sqlite>
+{
+}
+
+impl<T> DbValue for T where
+    T: Sized + for<'r> Decode<'r, MySql> + Type<MySql> + for<'r> Decode<'r, Sqlite> + Type<Sqlite>
+{
+}
+
+/// Backend-neutral column access for row mappers
+///
+/// MySQL の UNSIGNED 列は符号なし型でしか読めず、SQLite は u64 を持たないため
+/// `u64` / `u32` の ID は専用メソッドで読む。
+pub trait DbRow: Row {
+    fn col<T: DbValue>(&self, name: &str) -> T {
+        self.try_col(name)
+            .unwrap_or_else(|e| panic!("column {}: {}", name, e))
+    }
+
+    fn try_col<T: DbValue>(&self, name: &str) -> std::result::Result<T, sqlx::Error>;
+
+    /// BIGINT UNSIGNED column
+    fn col_u64(&self, name: &str) -> std::result::Result<u64, sqlx::Error>;
+
+    /// Nullable BIGINT UNSIGNED column
+    fn col_opt_u64(&self, name: &str) -> std::result::Result<Option<u64>, sqlx::Error>;
+
+    /// INT UNSIGNED column
+    fn col_u32(&self, name: &str) -> std::result::Result<u32, sqlx::Error>;
+}
+
+impl DbRow for MySqlRow {
+    fn try_col<T: DbValue>(&self, name: &str) -> std::result::Result<T, sqlx::Error> {
+        self.try_get(name)
+    }
+
+    fn col_u64(&self, name: &str) -> std::result::Result<u64, sqlx::Error> {
+        self.try_get(name)
+    }
+
+    fn col_opt_u64(&self, name: &str) -> std::result::Result<Option<u64>, sqlx::Error> {
+        self.try_get(name)
+    }
+
+    fn col_u32(&self, name: &str) -> std::result::Result<u32, sqlx::Error> {
+        self.try_get(name)
+    }
+}
+
+impl DbRow for SqliteRow {
+    fn try_col<T: DbValue>(&self, name: &str) -> std::result::Result<T, sqlx::Error> {
+        self.try_get(name)
+    }
+
+    fn col_u64(&self, name: &str) -> std::result::Result<u64, sqlx::Error> {
+        Ok(self.try_get::<i64, _>(name)? as u64)
+    }
+
+    fn col_opt_u64(&self, name: &str) -> std::result::Result<Option<u64>, sqlx::Error> {
+        Ok(self.try_get::<Option<i64>, _>(name)?.map(|v| v as u64))
+    }
+
+    fn col_u32(&self, name: &str) -> std::result::Result<u32, sqlx::Error> {
+        self.try_get(name)
+    }
+}
+
+/// BIGINT UNSIGNED id for `FromRow` structs
+///
+/// `#[sqlx(try_from = "crate::db::DbId")]` を付けた `u64` フィールドは
+/// MySQL では u64、SQLite では i64 として読む。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DbId(pub u64);
+
+impl From<DbId> for u64 {
+    fn from(id: DbId) -> Self {
+        id.0
+    }
+}
+
+impl Type<MySql> for DbId {
+    fn type_info() -> sqlx::mysql::MySqlTypeInfo {
+        <u64 as Type<MySql>>::type_info()
+    }
+
+    fn compatible(ty: &sqlx::mysql::MySqlTypeInfo) -> bool {
+        <u64 as Type<MySql>>::compatible(ty)
+    }
+}
+
+impl<'r> Decode<'r, MySql> for DbId {
+    fn decode(value: sqlx::mysql::MySqlValueRef<'r>) -> std::result::Result<Self, sqlx::error::BoxDynError> {
+        <u64 as Decode<MySql>>::decode(value).map(DbId)
+    }
+}
+
+impl Type<Sqlite> for DbId {
+    fn type_info() -> sqlx::sqlite::SqliteTypeInfo {
+        <i64 as Type<Sqlite>>::type_info()
+    }
+
+    fn compatible(ty: &sqlx::sqlite::SqliteTypeInfo) -> bool {
+        <i64 as Type<Sqlite>>::compatible(ty)
+    }
+}
+
+impl<'r> Decode<'r, Sqlite> for DbId {
+    fn decode(value: sqlx::sqlite::SqliteValueRef<'r>) -> std::result::Result<Self, sqlx::error::BoxDynError> {
+        <i64 as Decode<Sqlite>>::decode(value).map(|v| DbId(v as u64))
+    }
+}
+
+/// AUTO_INCREMENT / ROWID of the inserted row
+pub trait InsertId {
+    fn insert_id(&self) -> u64;
+}
+
+impl InsertId for MySqlQueryResult {
+    fn insert_id(&self) -> u64 {
+        self.last_insert_id()
+    }
+}
+
+impl InsertId for SqliteQueryResult {
+    fn insert_id(&self) -> u64 {
+        self.last_insert_rowid() as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backend_from_url() {
+        assert_eq!(
+            DbBackend::from_url("mysql://root@localhost/camserver").unwrap(),
+            DbBackend::MySql
+        );
+        assert_eq!(
+            DbBackend::from_url("sqlite:///var/lib/is22/camserver.db").unwrap(),
+            DbBackend::Sqlite
+        );
+        assert_eq!(
+            DbBackend::from_url("sqlite::memory:").unwrap(),
+            DbBackend::Sqlite
+        );
+        assert!(DbBackend::from_url("postgres://localhost/camserver").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_dialect_helpers_run_on_sqlite() {
+        let db = Db::memory().await.unwrap();
+        let pool = match &db {
+            Db::Sqlite(pool) => pool,
+            Db::MySql(_) => unreachable!(),
+        };
+
+        // 現在時刻は bind した DateTime<Utc> と同じ順序で比較できる
+        let past = chrono::Utc::now() - chrono::Duration::minutes(5);
+        let sql = format!(
+            "SELECT ? < {} AND ? > {} AS ok",
+            db.now(),
+            db.ago("10", IntervalUnit::Minute)
+        );
+        let row = sqlx::query(&sql)
+            .bind(past)
+            .bind(past)
+            .fetch_one(pool)
+            .await
+            .unwrap();
+        assert!(row.col::<bool>("ok"));
+
+        sqlx::query("CREATE TABLE kv (k TEXT PRIMARY KEY, v INTEGER NOT NULL)")
+            .execute(pool)
+            .await
+            .unwrap();
+        let upsert = format!(
+            "INSERT INTO kv (k, v) VALUES (?, ?) {}",
+            db.upsert("k", &["v"], &[])
+        );
+        for v in [1, 2] {
+            sqlx::query(&upsert)
+                .bind("a")
+                .bind(v)
+                .execute(pool)
+                .await
+                .unwrap();
+        }
+        let v: i64 = sqlx::query_scalar("SELECT v FROM kv WHERE k = 'a'")
+            .fetch_one(pool)
+            .await
+            .unwrap();
+        assert_eq!(v, 2);
+    }
+}
@@ -13,12 +13,13 @@
 //! - migration 008_detection_logs.sql
 
 use crate::ai_client::{AnalyzeResponse, CameraContext};
+use crate::db::{Db, DbRow, InsertId};
 use crate::error::{Error, Result};
 use crate::models::ProcessingTimings;
+use crate::storage_manager::ImageRef;
+use crate::with_db;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::mysql::MySqlPool;
-use sqlx::Row;
 use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::fs;
@@ -209,7 +210,7 @@ pub fn should_save_image(primary_event: &str, severity: i32, unknown_flag: bool)
 
 /// DetectionLogService instance
 pub struct DetectionLogService {
-    pool: MySqlPool,
+    db: Db,
     config: Arc<RwLock<DetectionLogConfig>>,
     stats: Arc<RwLock<ServiceStats>>,
 }
@@ -224,22 +225,17 @@ pub struct ServiceStats {
 
 impl DetectionLogService {
     /// Create new DetectionLogService
-    pub fn new(pool: MySqlPool, config: DetectionLogConfig) -> Self {
+    pub fn new(db: impl Into<Db>, config: DetectionLogConfig) -> Self {
         Self {
-            pool,
+            db: db.into(),
             config: Arc::new(RwLock::new(config)),
             stats: Arc::new(RwLock::new(ServiceStats::default())),
         }
     }
 
     /// Create with default config
-    pub fn with_pool(pool: MySqlPool) -> Self {
-        Self::new(pool, DetectionLogConfig::default())
-    }
-
-    /// Get pool reference for direct SQL queries (T3-2: diagnostics API)
-    pub fn pool(&self) -> &MySqlPool {
-        &self.pool
+    pub fn with_pool(db: impl Into<Db>) -> Self {
+        Self::new(db, DetectionLogConfig::default())
     }
 
     /// Get current configuration (for API access)
@@ -492,69 +488,72 @@ impl DetectionLogService {
         let context_json = log.camera_context.as_ref().map(|v| v.to_string());
         let is21_log_json = log.is21_log.to_string();
 
-        let result = sqlx::query(
-            r#"
-            INSERT INTO detection_logs (
-                tid, fid, camera_id, lacis_id, camera_lacis_id,
-                captured_at, analyzed_at,
-                primary_event, severity, confidence, count_hint, unknown_flag,
-                tags, person_details, vehicle_details, bboxes, suspicious,
-                frame_diff, loitering_detected,
-                preset_id, preset_version, output_schema,
-                context_applied, camera_context,
-                is21_log,
-                image_path_local, image_path_cloud,
-                processing_ms, polling_cycle_id, schema_version,
-                synced_to_bq
-            ) VALUES (
-                ?, ?, ?, ?, ?,
-                ?, ?,
-                ?, ?, ?, ?, ?,
-                ?, ?, ?, ?, ?,
-                ?, ?,
-                ?, ?, ?,
-                ?, ?,
-                ?,
-                ?, ?,
-                ?, ?, ?,
-                FALSE
+        let log_id = with_db!(&self.db, pool => {
+            sqlx::query(
+                r#"
+                INSERT INTO detection_logs (
+                    tid, fid, camera_id, lacis_id, camera_lacis_id,
+                    captured_at, analyzed_at,
+                    primary_event, severity, confidence, count_hint, unknown_flag,
+                    tags, person_details, vehicle_details, bboxes, suspicious,
+                    frame_diff, loitering_detected,
+                    preset_id, preset_version, output_schema,
+                    context_applied, camera_context,
+                    is21_log,
+                    image_path_local, image_path_cloud,
+                    processing_ms, polling_cycle_id, schema_version,
+                    synced_to_bq
+                ) VALUES (
+                    ?, ?, ?, ?, ?,
+                    ?, ?,
+                    ?, ?, ?, ?, ?,
+                    ?, ?, ?, ?, ?,
+                    ?, ?,
+                    ?, ?, ?,
+                    ?, ?,
+                    ?,
+                    ?, ?,
+                    ?, ?, ?,
+                    FALSE
+                )
+                "#,
             )
-            "#,
-        )
-        .bind(&log.tid)
-        .bind(&log.fid)
-        .bind(&log.camera_id)
-        .bind(&log.lacis_id)
-        .bind(&log.camera_lacis_id)
-        .bind(log.captured_at)
-        .bind(log.analyzed_at)
-        .bind(&log.primary_event)
-        .bind(log.severity)
-        .bind(log.confidence)
-        .bind(log.count_hint)
-        .bind(log.unknown_flag)
-        .bind(&tags_json)
-        .bind(&person_details_json)
-        .bind(&vehicle_details_json)
-        .bind(&bboxes_json)
-        .bind(&suspicious_json)
-        .bind(&frame_diff_json)
-        .bind(log.loitering_detected)
-        .bind(&log.preset_id)
-        .bind(&log.preset_version)
-        .bind(&log.output_schema)
-        .bind(log.context_applied)
-        .bind(&context_json)
-        .bind(&is21_log_json)
-        .bind(&log.image_path_local)
-        .bind(&log.image_path_cloud)
-        .bind(log.processing_ms)
-        .bind(&log.polling_cycle_id)
-        .bind(&log.schema_version)
-        .execute(&self.pool)
-        .await?;
-
-        Ok(result.last_insert_id())
+            .bind(&log.tid)
+            .bind(&log.fid)
+            .bind(&log.camera_id)
+            .bind(&log.lacis_id)
+            .bind(&log.camera_lacis_id)
+            .bind(log.captured_at)
+            .bind(log.analyzed_at)
+            .bind(&log.primary_event)
+            .bind(log.severity)
+            .bind(log.confidence)
+            .bind(log.count_hint)
+            .bind(log.unknown_flag)
+            .bind(&tags_json)
+            .bind(&person_details_json)
+            .bind(&vehicle_details_json)
+            .bind(&bboxes_json)
+            .bind(&suspicious_json)
+            .bind(&frame_diff_json)
+            .bind(log.loitering_detected)
+            .bind(&log.preset_id)
+            .bind(&log.preset_version)
+            .bind(&log.output_schema)
+            .bind(log.context_applied)
+            .bind(&context_json)
+            .bind(&is21_log_json)
+            .bind(&log.image_path_local)
+            .bind(&log.image_path_cloud)
+            .bind(log.processing_ms)
+            .bind(&log.polling_cycle_id)
+            .bind(&log.schema_version)
+            .execute(pool)
+            .await
+            .map(|r| r.insert_id())
+        })?;
+
+        Ok(log_id)
     }
 
     // ========================================
@@ -563,96 +562,65 @@ impl DetectionLogService {
 
     /// Get latest detection logs
     pub async fn get_latest(&self, limit: u32) -> Result<Vec<DetectionLog>> {
-        let rows = sqlx::query(
-            r#"
-            SELECT
-                log_id, tid, fid, camera_id, lacis_id, camera_lacis_id,
-                captured_at, analyzed_at,
-                primary_event, severity, CAST(confidence AS DOUBLE) AS confidence, count_hint, unknown_flag,
-                tags, person_details, vehicle_details, bboxes, suspicious,
-                frame_diff, loitering_detected,
-                preset_id, preset_version, output_schema,
-                context_applied, camera_context,
-                is21_log,
-                image_path_local, image_path_cloud,
-                processing_ms, polling_cycle_id, schema_version,
-                created_at, synced_to_bq, synced_at
-            FROM detection_logs
-            ORDER BY captured_at DESC
-            LIMIT ?
-            "#,
-        )
-        .bind(limit)
-        .fetch_all(&self.pool)
-        .await?;
+        with_db!(&self.db, pool => {
+            let rows = sqlx::query(
+                r#"
+                SELECT
+                    log_id, tid, fid, camera_id, lacis_id, camera_lacis_id,
+                    captured_at, analyzed_at,
+                    primary_event, severity, CAST(confidence AS DOUBLE) AS confidence, count_hint, unknown_flag,
+                    tags, person_details, vehicle_details, bboxes, suspicious,
+                    frame_diff, loitering_detected,
+                    preset_id, preset_version, output_schema,
+                    context_applied, camera_context,
+                    is21_log,
+                    image_path_local, image_path_cloud,
+                    processing_ms, polling_cycle_id, schema_version,
+                    created_at, synced_to_bq, synced_at
+                FROM detection_logs
+                ORDER BY captured_at DESC
+                LIMIT ?
+                "#,
+            )
+            .bind(limit)
+            .fetch_all(pool)
+            .await?;
+            rows.into_iter().map(|row| self.row_to_log(row)).collect()
+        })
+    }
 
-        rows.into_iter().map(|row| self.row_to_log(row)).collect()
+    /// Get a single detection log by ID
+    pub async fn get_by_id(&self, log_id: u64) -> Result<Option<DetectionLog>> {
+        with_db!(&self.db, pool => {
+            let row = sqlx::query(
+                r#"
+                SELECT
+                    log_id, tid, fid, camera_id, lacis_id, camera_lacis_id,
+                    captured_at, analyzed_at,
+                    primary_event, severity, CAST(confidence AS DOUBLE) AS confidence, count_hint, unknown_flag,
+                    tags, person_details, vehicle_details, bboxes, suspicious,
+                    frame_diff, loitering_detected,
+                    preset_id, preset_version, output_schema,
+                    context_applied, camera_context,
+                    is21_log,
+                    image_path_local, image_path_cloud,
+                    processing_ms, polling_cycle_id, schema_version,
+                    created_at, synced_to_bq, synced_at
+                FROM detection_logs
+                WHERE log_id = ?
+                "#,
+            )
+            .bind(log_id as i64)
+            .fetch_optional(pool)
+            .await?;
+            row.map(|row| self.row_to_log(row)).transpose()
+        })
     }
 
     /// Get detection logs by camera
     pub async fn get_by_camera(&self, camera_id: &str, limit: u32) -> Result<Vec<DetectionLog>> {
-        let rows = sqlx::query(
-            r#"
-            SELECT
-                log_id, tid, fid, camera_id, lacis_id, camera_lacis_id,
-                captured_at, analyzed_at,
-                primary_event, severity, CAST(confidence AS DOUBLE) AS confidence, count_hint, unknown_flag,
-                tags, person_details, vehicle_details, bboxes, suspicious,
-                frame_diff, loitering_detected,
-                preset_id, preset_version, output_schema,
-                context_applied, camera_context,
-                is21_log,
-                image_path_local, image_path_cloud,
-                processing_ms, polling_cycle_id, schema_version,
-                created_at, synced_to_bq, synced_at
-            FROM detection_logs
-            WHERE camera_id = ?
-            ORDER BY captured_at DESC
-            LIMIT ?
-            "#,
-        )
-        .bind(camera_id)
-        .bind(limit)
-        .fetch_all(&self.pool)
-        .await?;
-
-        rows.into_iter().map(|row| self.row_to_log(row)).collect()
-    }
-
-    /// Get detection logs by camera LacisID (Phase 2: CameraRegistry)
-    pub async fn get_by_camera_lacis_id(&self, camera_lacis_id: &str, limit: u32) -> Result<Vec<DetectionLog>> {
-        let rows = sqlx::query(
-            r#"
-            SELECT
-                log_id, tid, fid, camera_id, lacis_id, camera_lacis_id,
-                captured_at, analyzed_at,
-                primary_event, severity, CAST(confidence AS DOUBLE) AS confidence, count_hint, unknown_flag,
-                tags, person_details, vehicle_details, bboxes, suspicious,
-                frame_diff, loitering_detected,
-                preset_id, preset_version, output_schema,
-                context_applied, camera_context,
-                is21_log,
-                image_path_local, image_path_cloud,
-                processing_ms, polling_cycle_id, schema_version,
-                created_at, synced_to_bq, synced_at
-            FROM detection_logs
-            WHERE camera_lacis_id = ?
-            ORDER BY captured_at DESC
-            LIMIT ?
-            "#,
-        )
-        .bind(camera_lacis_id)
-        .bind(limit)
-        .fetch_all(&self.pool)
-        .await?;
-
-        rows.into_iter().map(|row| self.row_to_log(row)).collect()
-    }
-
-    /// Get detection logs by tid/fid boundary (Phase 2: CameraRegistry)
-    pub async fn get_by_tid_fid(&self, tid: &str, fid: Option<&str>, limit: u32) -> Result<Vec<DetectionLog>> {
-        let rows = if let Some(fid) = fid {
-            sqlx::query(
+        with_db!(&self.db, pool => {
+            let rows = sqlx::query(
                 r#"
                 SELECT
                     log_id, tid, fid, camera_id, lacis_id, camera_lacis_id,
@@ -667,18 +635,23 @@ impl DetectionLogService {
                     processing_ms, polling_cycle_id, schema_version,
                     created_at, synced_to_bq, synced_at
                 FROM detection_logs
-                WHERE tid = ? AND fid = ?
+                WHERE camera_id = ?
                 ORDER BY captured_at DESC
                 LIMIT ?
                 "#,
             )
-            .bind(tid)
-            .bind(fid)
+            .bind(camera_id)
             .bind(limit)
-            .fetch_all(&self.pool)
-            .await?
-        } else {
-            sqlx::query(
+            .fetch_all(pool)
+            .await?;
+            rows.into_iter().map(|row| self.row_to_log(row)).collect()
+        })
+    }
+
+    /// Get detection logs by camera LacisID (Phase 2: CameraRegistry)
+    pub async fn get_by_camera_lacis_id(&self, camera_lacis_id: &str, limit: u32) -> Result<Vec<DetectionLog>> {
+        with_db!(&self.db, pool => {
+            let rows = sqlx::query(
                 r#"
                 SELECT
                     log_id, tid, fid, camera_id, lacis_id, camera_lacis_id,
@@ -693,18 +666,79 @@ impl DetectionLogService {
                     processing_ms, polling_cycle_id, schema_version,
                     created_at, synced_to_bq, synced_at
                 FROM detection_logs
-                WHERE tid = ?
+                WHERE camera_lacis_id = ?
                 ORDER BY captured_at DESC
                 LIMIT ?
                 "#,
             )
-            .bind(tid)
+            .bind(camera_lacis_id)
             .bind(limit)
-            .fetch_all(&self.pool)
-            .await?
-        };
+            .fetch_all(pool)
+            .await?;
+            rows.into_iter().map(|row| self.row_to_log(row)).collect()
+        })
+    }
 
-        rows.into_iter().map(|row| self.row_to_log(row)).collect()
+    /// Get detection logs by tid/fid boundary (Phase 2: CameraRegistry)
+    pub async fn get_by_tid_fid(&self, tid: &str, fid: Option<&str>, limit: u32) -> Result<Vec<DetectionLog>> {
+        if let Some(fid) = fid {
+            with_db!(&self.db, pool => {
+                let rows = sqlx::query(
+                    r#"
+                    SELECT
+                        log_id, tid, fid, camera_id, lacis_id, camera_lacis_id,
+                        captured_at, analyzed_at,
+                        primary_event, severity, CAST(confidence AS DOUBLE) AS confidence, count_hint, unknown_flag,
+                        tags, person_details, vehicle_details, bboxes, suspicious,
+                        frame_diff, loitering_detected,
+                        preset_id, preset_version, output_schema,
+                        context_applied, camera_context,
+                        is21_log,
+                        image_path_local, image_path_cloud,
+                        processing_ms, polling_cycle_id, schema_version,
+                        created_at, synced_to_bq, synced_at
+                    FROM detection_logs
+                    WHERE tid = ? AND fid = ?
+                    ORDER BY captured_at DESC
+                    LIMIT ?
+                    "#,
+                )
+                .bind(tid)
+                .bind(fid)
+                .bind(limit)
+                .fetch_all(pool)
+                .await?;
+                rows.into_iter().map(|row| self.row_to_log(row)).collect()
+            })
+        } else {
+            with_db!(&self.db, pool => {
+                let rows = sqlx::query(
+                    r#"
+                    SELECT
+                        log_id, tid, fid, camera_id, lacis_id, camera_lacis_id,
+                        captured_at, analyzed_at,
+                        primary_event, severity, CAST(confidence AS DOUBLE) AS confidence, count_hint, unknown_flag,
+                        tags, person_details, vehicle_details, bboxes, suspicious,
+                        frame_diff, loitering_detected,
+                        preset_id, preset_version, output_schema,
+                        context_applied, camera_context,
+                        is21_log,
+                        image_path_local, image_path_cloud,
+                        processing_ms, polling_cycle_id, schema_version,
+                        created_at, synced_to_bq, synced_at
+                    FROM detection_logs
+                    WHERE tid = ?
+                    ORDER BY captured_at DESC
+                    LIMIT ?
+                    "#,
+                )
+                .bind(tid)
+                .bind(limit)
+                .fetch_all(pool)
+                .await?;
+                rows.into_iter().map(|row| self.row_to_log(row)).collect()
+            })
+        }
     }
 
     /// Get detection logs by time range
@@ -714,150 +748,152 @@ impl DetectionLogService {
         end: DateTime<Utc>,
         limit: u32,
     ) -> Result<Vec<DetectionLog>> {
-        let rows = sqlx::query(
-            r#"
-            SELECT
-                log_id, tid, fid, camera_id, lacis_id, camera_lacis_id,
-                captured_at, analyzed_at,
-                primary_event, severity, CAST(confidence AS DOUBLE) AS confidence, count_hint, unknown_flag,
-                tags, person_details, vehicle_details, bboxes, suspicious,
-                frame_diff, loitering_detected,
-                preset_id, preset_version, output_schema,
-                context_applied, camera_context,
-                is21_log,
-                image_path_local, image_path_cloud,
-                processing_ms, polling_cycle_id, schema_version,
-                created_at, synced_to_bq, synced_at
-            FROM detection_logs
-            WHERE captured_at BETWEEN ? AND ?
-            ORDER BY captured_at DESC
-            LIMIT ?
-            "#,
-        )
-        .bind(start)
-        .bind(end)
-        .bind(limit)
-        .fetch_all(&self.pool)
-        .await?;
-
-        rows.into_iter().map(|row| self.row_to_log(row)).collect()
+        with_db!(&self.db, pool => {
+            let rows = sqlx::query(
+                r#"
+                SELECT
+                    log_id, tid, fid, camera_id, lacis_id, camera_lacis_id,
+                    captured_at, analyzed_at,
+                    primary_event, severity, CAST(confidence AS DOUBLE) AS confidence, count_hint, unknown_flag,
+                    tags, person_details, vehicle_details, bboxes, suspicious,
+                    frame_diff, loitering_detected,
+                    preset_id, preset_version, output_schema,
+                    context_applied, camera_context,
+                    is21_log,
+                    image_path_local, image_path_cloud,
+                    processing_ms, polling_cycle_id, schema_version,
+                    created_at, synced_to_bq, synced_at
+                FROM detection_logs
+                WHERE captured_at BETWEEN ? AND ?
+                ORDER BY captured_at DESC
+                LIMIT ?
+                "#,
+            )
+            .bind(start)
+            .bind(end)
+            .bind(limit)
+            .fetch_all(pool)
+            .await?;
+            rows.into_iter().map(|row| self.row_to_log(row)).collect()
+        })
     }
 
     /// Get high severity logs (severity >= threshold)
     pub async fn get_high_severity(&self, threshold: i32, limit: u32) -> Result<Vec<DetectionLog>> {
-        let rows = sqlx::query(
-            r#"
-            SELECT
-                log_id, tid, fid, camera_id, lacis_id, camera_lacis_id,
-                captured_at, analyzed_at,
-                primary_event, severity, CAST(confidence AS DOUBLE) AS confidence, count_hint, unknown_flag,
-                tags, person_details, vehicle_details, bboxes, suspicious,
-                frame_diff, loitering_detected,
-                preset_id, preset_version, output_schema,
-                context_applied, camera_context,
-                is21_log,
-                image_path_local, image_path_cloud,
-                processing_ms, polling_cycle_id, schema_version,
-                created_at, synced_to_bq, synced_at
-            FROM detection_logs
-            WHERE severity >= ?
-            ORDER BY captured_at DESC
-            LIMIT ?
-            "#,
-        )
-        .bind(threshold)
-        .bind(limit)
-        .fetch_all(&self.pool)
-        .await?;
-
-        rows.into_iter().map(|row| self.row_to_log(row)).collect()
+        with_db!(&self.db, pool => {
+            let rows = sqlx::query(
+                r#"
+                SELECT
+                    log_id, tid, fid, camera_id, lacis_id, camera_lacis_id,
+                    captured_at, analyzed_at,
+                    primary_event, severity, CAST(confidence AS DOUBLE) AS confidence, count_hint, unknown_flag,
+                    tags, person_details, vehicle_details, bboxes, suspicious,
+                    frame_diff, loitering_detected,
+                    preset_id, preset_version, output_schema,
+                    context_applied, camera_context,
+                    is21_log,
+                    image_path_local, image_path_cloud,
+                    processing_ms, polling_cycle_id, schema_version,
+                    created_at, synced_to_bq, synced_at
+                FROM detection_logs
+                WHERE severity >= ?
+                ORDER BY captured_at DESC
+                LIMIT ?
+                "#,
+            )
+            .bind(threshold)
+            .bind(limit)
+            .fetch_all(pool)
+            .await?;
+            rows.into_iter().map(|row| self.row_to_log(row)).collect()
+        })
     }
 
     /// Convert database row to DetectionLog
-    fn row_to_log(&self, row: sqlx::mysql::MySqlRow) -> Result<DetectionLog> {
+    fn row_to_log<R: DbRow>(&self, row: R) -> Result<DetectionLog> {
         // Parse JSON fields
-        let tags_str: String = row.try_get("tags")?;
+        let tags_str: String = row.try_col("tags")?;
         let tags: Vec<String> = serde_json::from_str(&tags_str)?;
 
         let person_details: Option<serde_json::Value> = row
-            .try_get::<Option<String>, _>("person_details")?
+            .try_col::<Option<String>>("person_details")?
             .map(|s| serde_json::from_str(&s))
             .transpose()?;
 
         // DD19対応: 車両詳細
         let vehicle_details: Option<serde_json::Value> = row
-            .try_get::<Option<String>, _>("vehicle_details")?
+            .try_col::<Option<String>>("vehicle_details")?
             .map(|s| serde_json::from_str(&s))
             .transpose()?;
 
         let bboxes: Option<serde_json::Value> = row
-            .try_get::<Option<String>, _>("bboxes")?
+            .try_col::<Option<String>>("bboxes")?
             .map(|s| serde_json::from_str(&s))
             .transpose()?;
 
         let suspicious: Option<serde_json::Value> = row
-            .try_get::<Option<String>, _>("suspicious")?
+            .try_col::<Option<String>>("suspicious")?
             .map(|s| serde_json::from_str(&s))
             .transpose()?;
 
         let frame_diff: Option<serde_json::Value> = row
-            .try_get::<Option<String>, _>("frame_diff")?
+            .try_col::<Option<String>>("frame_diff")?
             .map(|s| serde_json::from_str(&s))
             .transpose()?;
 
         let camera_context: Option<serde_json::Value> = row
-            .try_get::<Option<String>, _>("camera_context")?
+            .try_col::<Option<String>>("camera_context")?
             .map(|s| serde_json::from_str(&s))
             .transpose()?;
 
-        let is21_log_str: String = row.try_get("is21_log")?;
+        let is21_log_str: String = row.try_col("is21_log")?;
         let is21_log: serde_json::Value = serde_json::from_str(&is21_log_str)?;
 
         // Get timestamps
-        let captured_at: chrono::NaiveDateTime = row.try_get("captured_at")?;
-        let analyzed_at: chrono::NaiveDateTime = row.try_get("analyzed_at")?;
-        let created_at: chrono::NaiveDateTime = row.try_get("created_at")?;
-        let synced_at: Option<chrono::NaiveDateTime> = row.try_get("synced_at")?;
+        let captured_at: chrono::NaiveDateTime = row.try_col("captured_at")?;
+        let analyzed_at: chrono::NaiveDateTime = row.try_col("analyzed_at")?;
+        let created_at: chrono::NaiveDateTime = row.try_col("created_at")?;
+        let synced_at: Option<chrono::NaiveDateTime> = row.try_col("synced_at")?;
 
         // Get confidence as f64 (CAST from DECIMAL in SQL query)
-        let confidence: f64 = row.try_get("confidence")?;
+        let confidence: f64 = row.try_col("confidence")?;
 
         Ok(DetectionLog {
-            log_id: Some(row.try_get("log_id")?),
-            tid: row.try_get("tid")?,
-            fid: row.try_get("fid")?,
-            camera_id: row.try_get("camera_id")?,
-            lacis_id: row.try_get("lacis_id")?,
-            camera_lacis_id: row.try_get("camera_lacis_id")?,
+            log_id: Some(row.col_u64("log_id")?),
+            tid: row.try_col("tid")?,
+            fid: row.try_col("fid")?,
+            camera_id: row.try_col("camera_id")?,
+            lacis_id: row.try_col("lacis_id")?,
+            camera_lacis_id: row.try_col("camera_lacis_id")?,
             captured_at: DateTime::from_naive_utc_and_offset(captured_at, Utc),
             analyzed_at: DateTime::from_naive_utc_and_offset(analyzed_at, Utc),
-            primary_event: row.try_get("primary_event")?,
-            severity: row.try_get("severity")?,
+            primary_event: row.try_col("primary_event")?,
+            severity: row.try_col("severity")?,
             confidence: confidence as f32,
-            count_hint: row.try_get("count_hint")?,
-            unknown_flag: row.try_get("unknown_flag")?,
+            count_hint: row.try_col("count_hint")?,
+            unknown_flag: row.try_col("unknown_flag")?,
             tags,
             person_details,
             vehicle_details,
             bboxes,
             suspicious,
             frame_diff,
-            loitering_detected: row.try_get("loitering_detected")?,
-            preset_id: row.try_get("preset_id")?,
-            preset_version: row.try_get("preset_version")?,
-            output_schema: row.try_get("output_schema")?,
-            context_applied: row.try_get("context_applied")?,
+            loitering_detected: row.try_col("loitering_detected")?,
+            preset_id: row.try_col("preset_id")?,
+            preset_version: row.try_col("preset_version")?,
+            output_schema: row.try_col("output_schema")?,
+            context_applied: row.try_col("context_applied")?,
             camera_context,
             is21_log,
-            image_path_local: row.try_get("image_path_local")?,
-            image_path_cloud: row.try_get("image_path_cloud")?,
-            processing_ms: row.try_get("processing_ms")?,
-            polling_cycle_id: row.try_get("polling_cycle_id")?,
-            schema_version: row.try_get("schema_version")?,
+            image_path_local: row.try_col("image_path_local")?,
+            image_path_cloud: row.try_col("image_path_cloud")?,
+            processing_ms: row.try_col("processing_ms")?,
+            polling_cycle_id: row.try_col("polling_cycle_id")?,
+            schema_version: row.try_col("schema_version")?,
             // Timings are stored in is21_log, extract if needed
             timings: None, // TODO: Parse from is21_log if needed
             created_at: DateTime::from_naive_utc_and_offset(created_at, Utc),
-            synced_to_bq: row.try_get("synced_to_bq")?,
+            synced_to_bq: row.try_col("synced_to_bq")?,
             synced_at: synced_at.map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
         })
     }
@@ -869,16 +905,17 @@ impl DetectionLogService {
 
     /// Mark log as synced (for Ingest API tracking)
     pub async fn mark_synced(&self, log_id: u64) -> Result<()> {
-        sqlx::query(
-            r#"
-            UPDATE detection_logs
-            SET synced_to_bq = TRUE, synced_at = NOW(3)
-            WHERE log_id = ?
-            "#,
-        )
-        .bind(log_id)
-        .execute(&self.pool)
-        .await?;
+        let sql = format!(
+            "UPDATE detection_logs SET synced_to_bq = TRUE, synced_at = {} WHERE log_id = ?",
+            self.db.now()
+        );
+        with_db!(&self.db, pool => {
+            sqlx::query(&sql)
+                .bind(log_id as i64)
+                .execute(pool)
+                .await
+                .map(|r| r.rows_affected())
+        })?;
 
         Ok(())
     }
@@ -892,17 +929,20 @@ impl DetectionLogService {
         log_id: u64,
         cloud_path: &str,
     ) -> Result<()> {
-        sqlx::query(
-            r#"
-            UPDATE detection_logs
-            SET image_path_cloud = ?
-            WHERE log_id = ?
-            "#,
-        )
-        .bind(cloud_path)
-        .bind(log_id)
-        .execute(&self.pool)
-        .await?;
+        with_db!(&self.db, pool => {
+            sqlx::query(
+                r#"
+                UPDATE detection_logs
+                SET image_path_cloud = ?
+                WHERE log_id = ?
+                "#,
+            )
+            .bind(cloud_path)
+            .bind(log_id as i64)
+            .execute(pool)
+            .await
+            .map(|r| r.rows_affected())
+        })?;
 
         tracing::debug!(log_id = log_id, cloud_path = %cloud_path, "Updated cloud path");
 
@@ -1130,6 +1170,57 @@ impl DetectionLogService {
         Ok(stats)
     }
 
+    /// Detection rows that reference a local image (StorageManager: eviction / integrity)
+    pub async fn list_image_refs(&self, camera_id: Option<&str>) -> Result<Vec<ImageRef>> {
+        let sql = format!(
+            r#"
+            SELECT log_id, camera_id, image_path_local, severity FROM detection_logs
+            WHERE image_path_local IS NOT NULL AND image_path_local != ''
+            {}
+            "#,
+            if camera_id.is_some() { "AND camera_id = ?" } else { "" }
+        );
+        let refs = with_db!(&self.db, pool => {
+            let mut q = sqlx::query(&sql);
+            if let Some(camera_id) = camera_id {
+                q = q.bind(camera_id);
+            }
+            let rows = q.fetch_all(pool).await?;
+            rows.iter()
+                .map(|row| {
+                    Ok(ImageRef {
+                        log_id: row.col_u64("log_id")?,
+                        camera_id: row.col("camera_id"),
+                        path: row.col("image_path_local"),
+                        severity: row.col("severity"),
+                    })
+                })
+                .collect::<std::result::Result<Vec<_>, sqlx::Error>>()
+        })?;
+        Ok(refs)
+    }
+
+    /// Clear image_path_local of rows whose image was evicted
+    ///
+    /// 行（検出結果）は残し、画像のみ失われたことを明示する（カメライベントと同じ空文字）
+    pub async fn clear_image_paths(&self, log_ids: &[u64]) -> Result<u64> {
+        let mut cleared = 0;
+        for chunk in log_ids.chunks(500) {
+            let sql = format!(
+                "UPDATE detection_logs SET image_path_local = '' WHERE log_id IN ({})",
+                vec!["?"; chunk.len()].join(", ")
+            );
+            cleared += with_db!(&self.db, pool => {
+                let mut q = sqlx::query(&sql);
+                for log_id in chunk {
+                    q = q.bind(*log_id as i64);
+                }
+                q.execute(pool).await.map(|r| r.rows_affected())
+            })?;
+        }
+        Ok(cleared)
+    }
+
     /// Manual unknown image cleanup (administrator operation only)
     ///
     /// WARNING: Rule 5準拠 - 自動実行禁止
@@ -1150,19 +1241,24 @@ impl DetectionLogService {
         confirmed: bool,
     ) -> Result<CleanupStats> {
         // Query unknown images from database for this camera (newest first)
-        let rows = sqlx::query(
-            r#"
-            SELECT log_id, image_path_local, captured_at
-            FROM detection_logs
-            WHERE camera_id = ?
-              AND (primary_event = 'unknown' OR unknown_flag = TRUE)
-              AND image_path_local != ''
-            ORDER BY captured_at DESC
-            "#,
-        )
-        .bind(camera_id)
-        .fetch_all(&self.pool)
-        .await?;
+        let rows: Vec<(u64, String)> = with_db!(&self.db, pool => {
+            sqlx::query(
+                r#"
+                SELECT log_id, image_path_local, captured_at
+                FROM detection_logs
+                WHERE camera_id = ?
+                  AND (primary_event = 'unknown' OR unknown_flag = TRUE)
+                  AND image_path_local != ''
+                ORDER BY captured_at DESC
+                "#,
+            )
+            .bind(camera_id)
+            .fetch_all(pool)
+            .await?
+            .iter()
+            .map(|row| Ok((row.col_u64("log_id")?, row.try_col("image_path_local")?)))
+            .collect::<std::result::Result<_, sqlx::Error>>()?
+        });
 
         let total_count = rows.len();
         if total_count == 0 {
@@ -1196,9 +1292,7 @@ impl DetectionLogService {
         let mut bytes_freed = 0u64;
 
         // Skip the newest keep_count images, delete the rest
-        for row in rows.iter().skip(keep_count) {
-            let image_path: String = row.try_get("image_path_local")?;
-            let log_id: u64 = row.try_get("log_id")?;
+        for (log_id, image_path) in rows.into_iter().skip(keep_count) {
 
             // Get file size before deleting
             let path = std::path::Path::new(&image_path);
@@ -1225,16 +1319,19 @@ impl DetectionLogService {
             }
 
             // Clear image_path_local in database (keep the log record)
-            sqlx::query(
-                r#"
-                UPDATE detection_logs
-                SET image_path_local = ''
-                WHERE log_id = ?
-                "#,
-            )
-            .bind(log_id)
-            .execute(&self.pool)
-            .await?;
+            with_db!(&self.db, pool => {
+                sqlx::query(
+                    r#"
+                    UPDATE detection_logs
+                    SET image_path_local = ''
+                    WHERE log_id = ?
+                    "#,
+                )
+                .bind(log_id as i64)
+                .execute(pool)
+                .await
+                .map(|r| r.rows_affected())
+            })?;
 
             deleted_count += 1;
             bytes_freed += file_size;
@@ -1271,20 +1368,21 @@ impl DetectionLogService {
     /// WARNING: Rule 5準拠 - 管理者の明示的操作のみ
     pub async fn manual_cleanup_all_unknown_images(&self, confirmed: bool) -> Result<CleanupStats> {
         // Get all distinct camera IDs with unknown images
-        let rows = sqlx::query(
-            r#"
-            SELECT DISTINCT camera_id
-            FROM detection_logs
-            WHERE primary_event = 'unknown' OR unknown_flag = TRUE
-            "#,
-        )
-        .fetch_all(&self.pool)
-        .await?;
+        let camera_ids: Vec<String> = with_db!(&self.db, pool => {
+            sqlx::query_scalar(
+                r#"
+                SELECT DISTINCT camera_id
+                FROM detection_logs
+                WHERE primary_event = 'unknown' OR unknown_flag = TRUE
+                "#,
+            )
+            .fetch_all(pool)
+            .await?
+        });
 
         let mut total_stats = CleanupStats::default();
 
-        for row in rows {
-            let camera_id: String = row.try_get("camera_id")?;
+        for camera_id in camera_ids {
             let stats = self.manual_cleanup_unknown_images(&camera_id, confirmed).await?;
 
             total_stats.total += stats.total;
@@ -1383,36 +1481,37 @@ impl DetectionLogService {
             "bboxes": [],
         });
 
-        let result = sqlx::query(
-            r#"
-            INSERT INTO detection_logs (
-                tid, fid, camera_id, lacis_id,
-                captured_at, analyzed_at,
-                primary_event, severity, confidence, count_hint, unknown_flag,
-                tags, preset_id, schema_version,
-                is21_log, image_path_local, synced_to_bq
-            ) VALUES (
-                ?, ?, ?, ?,
-                ?, ?,
-                ?, ?, 1.0, 0, FALSE,
-                '[]', 'system', '2025-12-29.1',
-                ?, '', FALSE
+        let log_id = with_db!(&self.db, pool => {
+            sqlx::query(
+                r#"
+                INSERT INTO detection_logs (
+                    tid, fid, camera_id, lacis_id,
+                    captured_at, analyzed_at,
+                    primary_event, severity, confidence, count_hint, unknown_flag,
+                    tags, preset_id, schema_version,
+                    is21_log, image_path_local, synced_to_bq
+                ) VALUES (
+                    ?, ?, ?, ?,
+                    ?, ?,
+                    ?, ?, 1.0, 0, FALSE,
+                    '[]', 'system', '2025-12-29.1',
+                    ?, '', FALSE
+                )
+                "#,
             )
-            "#,
-        )
-        .bind(tid)
-        .bind(fid)
-        .bind(camera_id)
-        .bind(lacis_id)
-        .bind(now)
-        .bind(now)
-        .bind(event_type)
-        .bind(severity)
-        .bind(is21_log.to_string())
-        .execute(&self.pool)
-        .await?;
-
-        let log_id = result.last_insert_id();
+            .bind(tid)
+            .bind(fid)
+            .bind(camera_id)
+            .bind(lacis_id)
+            .bind(now)
+            .bind(now)
+            .bind(event_type)
+            .bind(severity)
+            .bind(is21_log.to_string())
+            .execute(pool)
+            .await
+            .map(|r| r.insert_id())
+        })?;
 
         tracing::info!(
             log_id = log_id,
@@ -1588,4 +1687,67 @@ mod tests {
         assert_eq!(stats.kept, 10);
         assert_eq!(stats.bytes_freed, 90 * 1024 * 1024);
     }
+
+    #[tokio::test]
+    async fn test_sqlite_camera_event_roundtrip() {
+        use crate::config_store::{ConfigRepository, CreateCameraRequest};
+
+        let db = Db::memory().await.unwrap();
+        ConfigRepository::new(db.clone())
+            .create_camera(&CreateCameraRequest {
+                camera_id: "cam-1".to_string(),
+                name: "Entrance".to_string(),
+                location: "1F".to_string(),
+                floor: None,
+                rtsp_main: None,
+                rtsp_sub: None,
+                snapshot_url: None,
+                family: None,
+                manufacturer: None,
+                model: None,
+                ip_address: None,
+                mac_address: None,
+                camera_context: None,
+            })
+            .await
+            .unwrap();
+
+        let service = DetectionLogService::with_pool(db);
+        let log_id = service
+            .save_camera_event("T1", "0150", "cam-1", None, "camera_lost", 3, Some("timeout"))
+            .await
+            .unwrap();
+        assert!(log_id > 0);
+
+        let log = service.get_by_id(log_id).await.unwrap().unwrap();
+        assert_eq!(log.log_id, Some(log_id));
+        assert_eq!(log.primary_event, "camera_lost");
+        assert_eq!(log.is21_log["message"], "timeout");
+        assert!(!log.synced_to_bq);
+
+        service.mark_synced(log_id).await.unwrap();
+        let logs = service.get_by_camera("cam-1", 10).await.unwrap();
+        assert_eq!(logs.len(), 1);
+        assert!(logs[0].synced_to_bq);
+        assert_eq!(service.get_high_severity(3, 10).await.unwrap().len(), 1);
+        assert_eq!(service.get_by_tid_fid("T1", Some("0150"), 10).await.unwrap().len(), 1);
+
+        // カメライベントは画像なし（image_path_local = ''）
+        assert!(service.list_image_refs(None).await.unwrap().is_empty());
+        with_db!(&service.db, pool => {
+            sqlx::query("UPDATE detection_logs SET image_path_local = '/tmp/cam-1/a.jpg'")
+                .execute(pool)
+                .await
+                .map(|r| r.rows_affected())
+        })
+        .unwrap();
+        let refs = service.list_image_refs(Some("cam-1")).await.unwrap();
+        assert_eq!(refs.len(), 1);
+        assert_eq!(refs[0].log_id, log_id);
+        assert_eq!(refs[0].severity, 3);
+        assert!(service.list_image_refs(Some("cam-2")).await.unwrap().is_empty());
+
+        assert_eq!(service.clear_image_paths(&[log_id]).await.unwrap(), 1);
+        assert!(service.list_image_refs(None).await.unwrap().is_empty());
+    }
 }
@@ -0,0 +1,345 @@
+//! MIME message builder
+//!
+//! ## 構造
+//! ```text
+//! multipart/mixed
+//! ├── multipart/related
+//! │   ├── multipart/alternative
+//! │   │   ├── text/plain
+//! │   │   └── text/html
+//! │   └── image/* (Content-ID, inline)
+//! └── 添付ファイル (PDFレポート等)
+//! ```
+//! 本文・ヘッダーはUTF-8（base64 / RFC 2047）で符号化する。
+
+use base64::Engine;
+use chrono::{DateTime, Utc};
+
+/// メールアドレス + 表示名
+#[derive(Debug, Clone)]
+pub struct Mailbox {
+    pub address: String,
+    pub name: Option<String>,
+}
+
+impl Mailbox {
+    pub fn new(address: impl Into<String>, name: Option<String>) -> Self {
+        Self {
+            address: address.into(),
+            name,
+        }
+    }
+
+    fn header_value(&self) -> String {
+        match &self.name {
+            Some(name) if !name.is_empty() => {
+                format!("{} <{}>", encode_header(name), self.address)
+            }
+            _ => format!("<{}>", self.address),
+        }
+    }
+}
+
+/// 添付・インライン画像
+#[derive(Debug, Clone)]
+pub struct MimePart {
+    pub filename: String,
+    pub content_type: String,
+    pub data: Vec<u8>,
+    /// Some = multipart/related 内のインライン画像（`cid:` で参照）
+    pub content_id: Option<String>,
+}
+
+/// 送信するメール
+#[derive(Debug, Clone)]
+pub struct MimeMessage {
+    pub from: Mailbox,
+    pub to: Vec<Mailbox>,
+    pub subject: String,
+    pub text: String,
+    pub html: Option<String>,
+    pub inline: Vec<MimePart>,
+    pub attachments: Vec<MimePart>,
+    pub date: DateTime<Utc>,
+}
+
+impl MimeMessage {
+    pub fn new(from: Mailbox, to: Vec<Mailbox>, subject: impl Into<String>, text: impl Into<String>) -> Self {
+        Self {
+            from,
+            to,
+            subject: subject.into(),
+            text: text.into(),
+            html: None,
+            inline: Vec::new(),
+            attachments: Vec::new(),
+            date: Utc::now(),
+        }
+    }
+
+    /// RFC 5322 メッセージ（CRLF改行）を生成
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let domain = self
+            .from
+            .address
+            .rsplit_once('@')
+            .map(|(_, d)| d)
+            .unwrap_or("localhost");
+        let mixed = new_boundary("mixed");
+        let related = new_boundary("related");
+        let alternative = new_boundary("alt");
+
+        let mut out = String::new();
+        push_line(&mut out, &format!("From: {}", self.from.header_value()));
+        push_line(
+            &mut out,
+            &format!(
+                "To: {}",
+                self.to.iter().map(|m| m.header_value()).collect::<Vec<_>>().join(", ")
+            ),
+        );
+        push_line(&mut out, &format!("Subject: {}", encode_header(&self.subject)));
+        push_line(&mut out, &format!("Date: {}", self.date.to_rfc2822()));
+        push_line(
+            &mut out,
+            &format!("Message-ID: <{}@{}>", uuid::Uuid::new_v4(), domain),
+        );
+        push_line(&mut out, "MIME-Version: 1.0");
+        push_line(
+            &mut out,
+            &format!("Content-Type: multipart/mixed; boundary=\"{}\"", mixed),
+        );
+        push_line(&mut out, "");
+
+        // multipart/related
+        push_line(&mut out, &format!("--{}", mixed));
+        push_line(
+            &mut out,
+            &format!("Content-Type: multipart/related; boundary=\"{}\"", related),
+        );
+        push_line(&mut out, "");
+
+        // multipart/alternative (text + html)
+        push_line(&mut out, &format!("--{}", related));
+        push_line(
+            &mut out,
+            &format!("Content-Type: multipart/alternative; boundary=\"{}\"", alternative),
+        );
+        push_line(&mut out, "");
+        push_line(&mut out, &format!("--{}", alternative));
+        push_text_part(&mut out, "text/plain", &self.text);
+        if let Some(html) = &self.html {
+            push_line(&mut out, &format!("--{}", alternative));
+            push_text_part(&mut out, "text/html", html);
+        }
+        push_line(&mut out, &format!("--{}--", alternative));
+
+        for part in &self.inline {
+            push_line(&mut out, &format!("--{}", related));
+            push_binary_part(&mut out, part, true);
+        }
+        push_line(&mut out, &format!("--{}--", related));
+
+        for part in &self.attachments {
+            push_line(&mut out, &format!("--{}", mixed));
+            push_binary_part(&mut out, part, false);
+        }
+        push_line(&mut out, &format!("--{}--", mixed));
+
+        out.into_bytes()
+    }
+}
+
+/// HTML内の `data:` URI画像をインライン添付（`cid:`参照）に置き換える
+///
+/// 多くのメールクライアントはdata URIの画像を表示しないため
+pub fn inline_data_uris(html: &str) -> (String, Vec<MimePart>) {
+    const PREFIX: &str = "src=\"data:";
+    let mut out = String::with_capacity(html.len());
+    let mut parts = Vec::new();
+    let mut rest = html;
+
+    while let Some(pos) = rest.find(PREFIX) {
+        out.push_str(&rest[..pos]);
+        let after = &rest[pos + PREFIX.len()..];
+        let Some(end) = after.find('"') else {
+            out.push_str(&rest[pos..]);
+            rest = "";
+            break;
+        };
+        let uri = &after[..end];
+        rest = &after[end + 1..];
+
+        let decoded = uri.split_once(";base64,").and_then(|(mime, data)| {
+            base64::engine::general_purpose::STANDARD
+                .decode(data)
+                .ok()
+                .map(|bytes| (mime.to_string(), bytes))
+        });
+        match decoded {
+            Some((content_type, data)) => {
+                let index = parts.len() + 1;
+                let extension = content_type.rsplit('/').next().unwrap_or("bin");
+                let content_id = format!("image{}@is22", index);
+                out.push_str(&format!("src=\"cid:{}\"", content_id));
+                parts.push(MimePart {
+                    filename: format!("image{}.{}", index, extension),
+                    content_type,
+                    data,
+                    content_id: Some(content_id),
+                });
+            }
+            None => {
+                out.push_str(PREFIX);
+                out.push_str(uri);
+                out.push('"');
+            }
+        }
+    }
+    out.push_str(rest);
+    (out, parts)
+}
+
+/// RFC 2047 encoded-word（ASCIIのみならそのまま）
+pub fn encode_header(value: &str) -> String {
+    // CR/LFはヘッダーインジェクション防止のため除去
+    let value: String = value.chars().filter(|c| *c != '\r' && *c != '\n').collect();
+    if value.is_ascii() {
+        return value;
+    }
+
+    // encoded-wordは75文字以内 → UTF-8文字境界で分割
+    let mut words = Vec::new();
+    let mut chunk = String::new();
+    for c in value.chars() {
+        if chunk.len() + c.len_utf8() > 45 {
+            words.push(std::mem::take(&mut chunk));
+        }
+        chunk.push(c);
+    }
+    if !chunk.is_empty() {
+        words.push(chunk);
+    }
+    words
+        .iter()
+        .map(|w| {
+            format!(
+                "=?UTF-8?B?{}?=",
+                base64::engine::general_purpose::STANDARD.encode(w.as_bytes())
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\r\n ")
+}
+
+fn new_boundary(kind: &str) -> String {
+    format!("=_is22_{}_{}", kind, uuid::Uuid::new_v4().simple())
+}
+
+fn push_line(out: &mut String, line: &str) {
+    out.push_str(line);
+    out.push_str("\r\n");
+}
+
+fn push_base64(out: &mut String, data: &[u8]) {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(data);
+    for line in encoded.as_bytes().chunks(76) {
+        // base64出力はASCIIのみ
+        push_line(out, std::str::from_utf8(line).unwrap_or_default());
+    }
+}
+
+fn push_text_part(out: &mut String, content_type: &str, body: &str) {
+    push_line(out, &format!("Content-Type: {}; charset=utf-8", content_type));
+    push_line(out, "Content-Transfer-Encoding: base64");
+    push_line(out, "");
+    push_base64(out, body.as_bytes());
+}
+
+fn push_binary_part(out: &mut String, part: &MimePart, inline: bool) {
+    let filename: String = part
+        .filename
+        .chars()
+        .filter(|c| !matches!(c, '"' | '\r' | '\n' | '\\'))
+        .collect();
+    push_line(
+        out,
+        &format!("Content-Type: {}; name=\"{}\"", part.content_type, filename),
+    );
+    push_line(out, "Content-Transfer-Encoding: base64");
+    if let (true, Some(cid)) = (inline, &part.content_id) {
+        push_line(out, &format!("Content-ID: <{}>", cid));
+        push_line(out, &format!("Content-Disposition: inline; filename=\"{}\"", filename));
+    } else {
+        push_line(
+            out,
+            &format!("Content-Disposition: attachment; filename=\"{}\"", filename),
+        );
+    }
+    push_line(out, "");
+    push_base64(out, &part.data);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_header() {
+        assert_eq!(encode_header("Daily report"), "Daily report");
+        assert_eq!(encode_header("a\r\nBcc: x"), "aBcc: x");
+
+        let encoded = encode_header("検出サマリーレポート 施設0150 2026-01-10");
+        assert!(encoded.starts_with("=?UTF-8?B?"));
+        for word in encoded.split("\r\n ") {
+            assert!(word.len() <= 75);
+        }
+    }
+
+    #[test]
+    fn test_inline_data_uris() {
+        let png = base64::engine::general_purpose::STANDARD.encode([0x89, b'P', b'N', b'G']);
+        let html = format!(
+            r#"<img src="data:image/png;base64,{}" alt=""><img src="https://x/y.png">"#,
+            png
+        );
+        let (html, parts) = inline_data_uris(&html);
+        assert_eq!(parts.len(), 1);
+        assert_eq!(parts[0].content_type, "image/png");
+        assert_eq!(parts[0].data, vec![0x89, b'P', b'N', b'G']);
+        assert!(html.contains(r#"src="cid:image1@is22""#));
+        assert!(html.contains(r#"src="https://x/y.png""#));
+    }
+
+    #[test]
+    fn test_message_structure() {
+        let mut message = MimeMessage::new(
+            Mailbox::new("is22@example.com", Some("IS22".to_string())),
+            vec![Mailbox::new("ops@example.com", Some("運用担当".to_string()))],
+            "テスト",
+            "本文",
+        );
+        message.html = Some("<p>本文</p>".to_string());
+        message.inline.push(MimePart {
+            filename: "snapshot.jpg".to_string(),
+            content_type: "image/jpeg".to_string(),
+            data: vec![0xff, 0xd8, 0xff],
+            content_id: Some("snapshot@is22".to_string()),
+        });
+        message.attachments.push(MimePart {
+            filename: "report.pdf".to_string(),
+            content_type: "application/pdf".to_string(),
+            data: b"%PDF-1.4".to_vec(),
+            content_id: None,
+        });
+
+        let raw = String::from_utf8(message.to_bytes()).unwrap();
+        assert!(raw.contains("Subject: =?UTF-8?B?"));
+        assert!(raw.contains("To: =?UTF-8?B?"));
+        assert!(raw.contains("multipart/related"));
+        assert!(raw.contains("Content-ID: <snapshot@is22>"));
+        assert!(raw.contains("Content-Disposition: attachment; filename=\"report.pdf\""));
+        assert!(raw.lines().all(|l| l.len() <= 998));
+        assert!(!raw.replace("\r\n", "").contains('\n'));
+    }
+}
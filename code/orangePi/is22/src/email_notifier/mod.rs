@@ -0,0 +1,578 @@
+//! Email Notifier (SMTP)
+//!
+//! Paraclate以外の配信チャネルとして、レポート・検出アラートをメールで配信する。
+//!
+//! ## 配信対象
+//! | 種別 | トリガー | 本文 |
+//! |------|---------|------|
+//! | report | SummaryScheduler実行時（summary / grand_summary） | HTMLレポート（画像インライン）+ 任意でPDF添付 |
+//! | daily | NotificationPolicy.daily_summary_time（JST） | 直近GrandSummaryのHTMLレポート |
+//! | alert | 検出severity >= 購読者の alert_min_severity | スナップショット画像インライン |
+//!
+//! ## 送信フロー
+//! 1. 購読設定（email_subscriptions）と照合し、受信者ごとに email_send_queue へ登録
+//! 2. バックグラウンドループがキューを処理（本文は送信時に組み立て）
+//! 3. 4xx・接続エラーは指数バックオフでリトライ、5xxは skipped として打ち切り
+//!
+//! SMTPサーバー設定は settings テーブルの `smtp` キー（`SmtpConfig`）。
+//! `security: "none"` でローカルのSMTPシンク（MailHog等）に対して動作確認できる。
+
+pub mod mime;
+pub mod repository;
+pub mod smtp;
+pub mod types;
+
+pub use mime::{Mailbox, MimeMessage, MimePart};
+pub use repository::{EmailQueueRepository, EmailSubscriptionRepository};
+pub use smtp::{Envelope, SmtpError};
+pub use types::*;
+
+use crate::config_store::ConfigStore;
+use crate::detection_log_service::DetectionLogService;
+use crate::summary_service::report::{html::render_html, read_image, ReportFormat, ReportService};
+use crate::summary_service::template::{event_label, SummaryLocale, TemplateKind};
+use crate::summary_service::{SummaryRepository, SummaryResult, SummaryType};
+use chrono::{DateTime, Duration, NaiveTime, Utc};
+use chrono_tz::Asia::Tokyo;
+use serde_json::json;
+use sqlx::MySqlPool;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info, warn};
+
+/// キュー処理間隔
+const PROCESS_INTERVAL_SECS: u64 = 30;
+/// 1回のキュー処理件数
+const PROCESS_BATCH: i32 = 20;
+/// 送信済み・skippedの保持日数
+const QUEUE_RETENTION_DAYS: i32 = 30;
+
+/// 検出アラートの入力
+#[derive(Debug, Clone)]
+pub struct DetectionAlert {
+    pub tid: String,
+    pub fid: String,
+    pub camera_id: String,
+    pub camera_name: String,
+    pub log_id: u64,
+    pub primary_event: String,
+    pub severity: i32,
+    pub captured_at: DateTime<Utc>,
+}
+
+/// メール通知サービス
+pub struct EmailNotifier {
+    config_store: Arc<ConfigStore>,
+    subscriptions: EmailSubscriptionRepository,
+    queue: EmailQueueRepository,
+    detection_log: Arc<DetectionLogService>,
+    report_service: Arc<ReportService>,
+    summary_repository: SummaryRepository,
+    /// camera_id → 最終アラート時刻（NotificationPolicy.cooldown_sec）
+    alert_cooldown: Mutex<HashMap<String, DateTime<Utc>>>,
+}
+
+impl EmailNotifier {
+    pub fn new(
+        pool: MySqlPool,
+        config_store: Arc<ConfigStore>,
+        detection_log: Arc<DetectionLogService>,
+        report_service: Arc<ReportService>,
+        summary_repository: SummaryRepository,
+    ) -> Self {
+        Self {
+            config_store,
+            subscriptions: EmailSubscriptionRepository::new(pool.clone()),
+            queue: EmailQueueRepository::new(pool),
+            detection_log,
+            report_service,
+            summary_repository,
+            alert_cooldown: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn subscriptions(&self) -> &EmailSubscriptionRepository {
+        &self.subscriptions
+    }
+
+    pub fn queue(&self) -> &EmailQueueRepository {
+        &self.queue
+    }
+
+    /// SMTP設定を取得（未設定時はデフォルト = 無効）
+    pub async fn smtp_config(&self) -> SmtpConfig {
+        self.config_store
+            .service()
+            .get_setting(SMTP_CONFIG_KEY)
+            .await
+            .ok()
+            .flatten()
+            .and_then(|v| serde_json::from_value(v).ok())
+            .unwrap_or_default()
+    }
+
+    /// SMTP設定を保存（パスワードがマスク値なら既存値を保持）
+    pub async fn set_smtp_config(&self, mut config: SmtpConfig) -> crate::Result<SmtpConfig> {
+        if config.password.as_deref() == Some(PASSWORD_MASK) {
+            config.password = self.smtp_config().await.password;
+        }
+        config.validate()?;
+        self.config_store
+            .service()
+            .set_setting(SMTP_CONFIG_KEY, serde_json::to_value(&config)?)
+            .await?;
+        Ok(config)
+    }
+
+    /// スケジュール実行されたレポートを購読者のキューに登録
+    ///
+    /// `report_type`: "summary" / "grand_summary"
+    pub async fn enqueue_report(
+        &self,
+        tid: &str,
+        fid: &str,
+        summary_id: u64,
+        report_type: &str,
+        attach_pdf: bool,
+    ) -> crate::Result<usize> {
+        self.enqueue_for(
+            tid,
+            fid,
+            EmailKind::Report,
+            |s| s.wants_report(fid, report_type),
+            json!({ "summaryId": summary_id, "reportType": report_type, "attachPdf": attach_pdf }),
+            Some(summary_id),
+        )
+        .await
+    }
+
+    /// 検出アラートを購読者のキューに登録（カメラ単位のクールダウンあり）
+    pub async fn notify_detection(&self, alert: &DetectionAlert) -> crate::Result<usize> {
+        if !self.smtp_config().await.enabled {
+            return Ok(0);
+        }
+        let subscribers: Vec<EmailSubscription> = self
+            .subscriptions
+            .list_enabled()
+            .await?
+            .into_iter()
+            .filter(|s| s.wants_alert(&alert.fid, alert.severity))
+            .collect();
+        if subscribers.is_empty() {
+            return Ok(0);
+        }
+
+        let cooldown_sec = self
+            .config_store
+            .service()
+            .get_notification_policy()
+            .await
+            .map(|p| p.cooldown_sec)
+            .unwrap_or(300);
+        {
+            let mut last = self.alert_cooldown.lock().await;
+            if let Some(at) = last.get(&alert.camera_id) {
+                if alert.captured_at - *at < Duration::seconds(cooldown_sec as i64) {
+                    debug!(camera_id = %alert.camera_id, "Email alert suppressed (cooldown)");
+                    return Ok(0);
+                }
+            }
+            last.insert(alert.camera_id.clone(), alert.captured_at);
+        }
+
+        let payload = json!({
+            "logId": alert.log_id,
+            "cameraId": alert.camera_id,
+            "cameraName": alert.camera_name,
+            "primaryEvent": alert.primary_event,
+            "severity": alert.severity,
+            "capturedAt": alert.captured_at,
+        });
+        for subscriber in &subscribers {
+            self.queue
+                .insert(EmailQueueInsert {
+                    tid: alert.tid.clone(),
+                    fid: alert.fid.clone(),
+                    kind: EmailKind::Alert,
+                    recipient: subscriber.email.clone(),
+                    payload: payload.clone(),
+                    reference_id: Some(alert.log_id),
+                    max_retries: None,
+                })
+                .await?;
+        }
+        info!(
+            camera_id = %alert.camera_id,
+            log_id = alert.log_id,
+            severity = alert.severity,
+            recipients = subscribers.len(),
+            "Email alert enqueued"
+        );
+        Ok(subscribers.len())
+    }
+
+    /// テストメールをキューに登録
+    pub async fn enqueue_test(&self, tid: &str, fid: &str, recipient: &str) -> crate::Result<u64> {
+        if !is_valid_email(recipient) {
+            return Err(crate::Error::Validation(format!("Invalid email: {}", recipient)));
+        }
+        self.queue
+            .insert(EmailQueueInsert {
+                tid: tid.to_string(),
+                fid: fid.to_string(),
+                kind: EmailKind::Test,
+                recipient: recipient.to_string(),
+                payload: json!({}),
+                reference_id: None,
+                max_retries: Some(0),
+            })
+            .await
+    }
+
+    async fn enqueue_for(
+        &self,
+        tid: &str,
+        fid: &str,
+        kind: EmailKind,
+        wants: impl Fn(&EmailSubscription) -> bool,
+        payload: serde_json::Value,
+        reference_id: Option<u64>,
+    ) -> crate::Result<usize> {
+        if !self.smtp_config().await.enabled {
+            return Ok(0);
+        }
+        let mut count = 0;
+        for subscriber in self.subscriptions.list_enabled().await? {
+            if !wants(&subscriber) {
+                continue;
+            }
+            self.queue
+                .insert(EmailQueueInsert {
+                    tid: tid.to_string(),
+                    fid: fid.to_string(),
+                    kind,
+                    recipient: subscriber.email,
+                    payload: payload.clone(),
+                    reference_id,
+                    max_retries: None,
+                })
+                .await?;
+            count += 1;
+        }
+        if count > 0 {
+            info!(fid = %fid, kind = kind.as_str(), recipients = count, "Email enqueued");
+        }
+        Ok(count)
+    }
+
+    /// NotificationPolicy.daily_summary_time（JST）を過ぎていれば当日分を登録
+    pub async fn check_daily_summary(&self, tid: &str, fid: &str, now: DateTime<Utc>) -> crate::Result<()> {
+        let policy = self.config_store.service().get_notification_policy().await?;
+        if !policy.daily_summary_enabled {
+            return Ok(());
+        }
+        let Ok(send_at) = NaiveTime::parse_from_str(&policy.daily_summary_time, "%H:%M") else {
+            warn!(time = %policy.daily_summary_time, "Invalid daily_summary_time");
+            return Ok(());
+        };
+        let now_jst = now.with_timezone(&Tokyo);
+        if now_jst.time() < send_at {
+            return Ok(());
+        }
+
+        let today = now_jst.date_naive().to_string();
+        let service = self.config_store.service();
+        let last_sent = service.get_setting(DAILY_LAST_SENT_KEY).await?;
+        if last_sent.as_ref().and_then(|v| v.as_str()) == Some(today.as_str()) {
+            return Ok(());
+        }
+        // 送信対象の有無にかかわらず当日分は処理済みとする
+        service.set_setting(DAILY_LAST_SENT_KEY, json!(today)).await?;
+
+        let latest = self
+            .summary_repository
+            .get_by_period(tid, fid, SummaryType::Daily, now - Duration::hours(24), now)
+            .await?
+            .into_iter()
+            .max_by_key(|s| s.period_end);
+        let Some(summary) = latest else {
+            info!(fid = %fid, "No grand summary in the last 24h, daily email skipped");
+            return Ok(());
+        };
+
+        self.enqueue_for(
+            tid,
+            fid,
+            EmailKind::Daily,
+            |s| s.wants_report(fid, "daily"),
+            json!({ "summaryId": summary.summary_id, "reportType": "daily", "attachPdf": false }),
+            Some(summary.summary_id),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// 送信キューを処理
+    pub async fn process_queue(&self, limit: i32) -> crate::Result<EmailProcessStats> {
+        let mut stats = EmailProcessStats::default();
+        let config = self.smtp_config().await;
+        if !config.enabled {
+            return Ok(stats);
+        }
+
+        for item in self.queue.get_pending(limit).await? {
+            if !self.queue.mark_sending(item.queue_id).await? {
+                continue;
+            }
+
+            let message = match self.build_message(&item, &config).await {
+                Ok(message) => message,
+                Err(e) => {
+                    // 参照先のSummary/検出ログが削除済み等
+                    warn!(queue_id = item.queue_id, error = %e, "Failed to build email, skipping");
+                    self.queue.mark_skipped(item.queue_id, &e.to_string()).await?;
+                    stats.skipped += 1;
+                    continue;
+                }
+            };
+            let envelope = Envelope {
+                from: config.from_address.clone(),
+                recipients: vec![item.recipient.clone()],
+            };
+
+            match smtp::send_mail(&config, &envelope, &message.to_bytes()).await {
+                Ok(()) => {
+                    self.queue.mark_sent(item.queue_id).await?;
+                    stats.sent += 1;
+                    info!(
+                        queue_id = item.queue_id,
+                        kind = item.kind.as_str(),
+                        recipient = %item.recipient,
+                        "Email sent"
+                    );
+                }
+                Err(e) if e.is_permanent() => {
+                    warn!(queue_id = item.queue_id, error = %e, "Email rejected permanently");
+                    self.queue.mark_skipped(item.queue_id, &e.to_string()).await?;
+                    stats.skipped += 1;
+                }
+                Err(e) => {
+                    warn!(
+                        queue_id = item.queue_id,
+                        retry_count = item.retry_count + 1,
+                        error = %e,
+                        "Email send failed, will retry"
+                    );
+                    self.queue
+                        .mark_failed(item.queue_id, &e.to_string(), item.retry_count + 1)
+                        .await?;
+                    stats.failed += 1;
+                }
+            }
+        }
+        Ok(stats)
+    }
+
+    /// キュー項目からメール本文を組み立てる
+    async fn build_message(&self, item: &EmailQueueItem, config: &SmtpConfig) -> crate::Result<MimeMessage> {
+        let from = Mailbox::new(config.from_address.clone(), config.from_name.clone());
+        let to = vec![Mailbox::new(item.recipient.clone(), None)];
+        let locale = self.report_service.locale_for(&item.fid).await;
+
+        match item.kind {
+            EmailKind::Report | EmailKind::Daily => {
+                let summary_id = item.payload["summaryId"]
+                    .as_u64()
+                    .ok_or_else(|| crate::Error::Validation("payload.summaryId missing".to_string()))?;
+                let data = self.report_service.build(summary_id).await?;
+                let (html, inline) = mime::inline_data_uris(&render_html(&data));
+
+                let mut text = data.summary.summary_text.clone();
+                if let Some(base) = &config.public_base_url {
+                    text.push_str(&format!(
+                        "\n\n{}/api/paraclate/summary/{}/report",
+                        base.trim_end_matches('/'),
+                        summary_id
+                    ));
+                }
+
+                let mut message = MimeMessage::new(
+                    from,
+                    to,
+                    report_subject(locale, data.kind, &data.summary),
+                    text,
+                );
+                message.html = Some(html);
+                message.inline = inline;
+
+                if item.payload["attachPdf"].as_bool().unwrap_or(false) {
+                    match self.report_service.get_or_render(summary_id, ReportFormat::Pdf).await {
+                        Ok(report) => message.attachments.push(MimePart {
+                            filename: report.filename,
+                            content_type: ReportFormat::Pdf.content_type().to_string(),
+                            data: report.bytes,
+                            content_id: None,
+                        }),
+                        Err(e) => warn!(summary_id = summary_id, error = %e, "PDF attachment unavailable"),
+                    }
+                }
+                Ok(message)
+            }
+            EmailKind::Alert => {
+                let payload = &item.payload;
+                let camera_name = payload["cameraName"].as_str().unwrap_or_default();
+                let event = event_label(payload["primaryEvent"].as_str().unwrap_or_default(), locale);
+                let severity = payload["severity"].as_i64().unwrap_or_default();
+                let captured_at = payload["capturedAt"]
+                    .as_str()
+                    .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+                    .map(|t| t.with_timezone(&Tokyo).format("%Y-%m-%d %H:%M:%S").to_string())
+                    .unwrap_or_default();
+
+                let (subject, heading) = match locale {
+                    SummaryLocale::Ja => (
+                        format!("[IS22] 検出アラート: {} {} (severity {})", camera_name, event, severity),
+                        "検出アラート",
+                    ),
+                    SummaryLocale::En => (
+                        format!("[IS22] Detection alert: {} {} (severity {})", camera_name, event, severity),
+                        "Detection alert",
+                    ),
+                };
+                let text = format!(
+                    "{}\n{} / {} / severity {}\n{} (JST)\nfid: {}",
+                    heading, camera_name, event, severity, captured_at, item.fid
+                );
+
+                let mut message = MimeMessage::new(from, to, subject, text);
+                let image = match item.reference_id {
+                    Some(log_id) => match self.detection_log.get_by_id(log_id).await? {
+                        Some(log) => read_image(&log.image_path_local).await,
+                        None => None,
+                    },
+                    None => None,
+                };
+                let image_html = match image {
+                    Some((content_type, data)) => {
+                        message.inline.push(MimePart {
+                            filename: format!("snapshot.{}", content_type.rsplit('/').next().unwrap_or("jpg")),
+                            content_type: content_type.to_string(),
+                            data,
+                            content_id: Some("snapshot@is22".to_string()),
+                        });
+                        r#"<p><img src="cid:snapshot@is22" alt="snapshot" style="max-width:640px"></p>"#
+                    }
+                    None => "",
+                };
+                let escape = crate::summary_service::report::html::escape;
+                message.html = Some(format!(
+                    r#"<!DOCTYPE html><html><body style="font-family:sans-serif"><h2>{}</h2><table><tr><td>Camera</td><td>{}</td></tr><tr><td>Event</td><td>{}</td></tr><tr><td>Severity</td><td><b>{}</b></td></tr><tr><td>Time</td><td>{} (JST)</td></tr><tr><td>fid</td><td>{}</td></tr></table>{}</body></html>"#,
+                    heading,
+                    escape(camera_name),
+                    escape(&event),
+                    severity,
+                    captured_at,
+                    escape(&item.fid),
+                    image_html
+                ));
+                Ok(message)
+            }
+            EmailKind::Test => {
+                let (subject, text) = match locale {
+                    SummaryLocale::Ja => (
+                        "[IS22] テストメール",
+                        "IS22 のメール通知設定は正常です。",
+                    ),
+                    SummaryLocale::En => (
+                        "[IS22] Test email",
+                        "IS22 email notifications are configured correctly.",
+                    ),
+                };
+                Ok(MimeMessage::new(from, to, subject, text))
+            }
+        }
+    }
+
+    /// バックグラウンド処理（キュー送信・daily summary・古い項目の削除）
+    ///
+    /// `shutdown` がキャンセルされるまでループする（TaskSupervisor から起動）
+    pub async fn run(self: Arc<Self>, tid: String, fid: String, shutdown: CancellationToken) {
+        // 前回プロセス（またはパニック前）の送信途中の項目を戻す
+        match self.queue.reset_stale_sending().await {
+            Ok(n) if n > 0 => info!(count = n, "Email queue: reset stale 'sending' items"),
+            Ok(_) => {}
+            Err(e) => warn!(error = %e, "Email queue: failed to reset stale items"),
+        }
+
+        let mut tick: u64 = 0;
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = tokio::time::sleep(std::time::Duration::from_secs(PROCESS_INTERVAL_SECS)) => {}
+            }
+            tick += 1;
+
+            if let Err(e) = self.check_daily_summary(&tid, &fid, Utc::now()).await {
+                error!(error = %e, "Daily summary email check failed");
+            }
+            match self.process_queue(PROCESS_BATCH).await {
+                Ok(stats) if stats.sent + stats.failed + stats.skipped > 0 => {
+                    debug!(?stats, "Email queue processed")
+                }
+                Ok(_) => {}
+                Err(e) => error!(error = %e, "Email queue processing failed"),
+            }
+            // 1時間ごと
+            if tick % (3600 / PROCESS_INTERVAL_SECS) == 0 {
+                if let Err(e) = self.queue.cleanup_old(QUEUE_RETENTION_DAYS).await {
+                    warn!(error = %e, "Email queue cleanup failed");
+                }
+            }
+        }
+        info!("Email queue worker stopped");
+    }
+
+    /// 送信待ちの項目を1バッチ送信（シャットダウン時の最終フラッシュ）
+    pub async fn flush(&self) -> crate::Result<EmailProcessStats> {
+        self.process_queue(PROCESS_BATCH).await
+    }
+}
+
+/// レポートメールの件名
+fn report_subject(locale: SummaryLocale, kind: TemplateKind, summary: &SummaryResult) -> String {
+    let start_jst = summary.period_start.with_timezone(&Tokyo);
+    let end_jst = summary.period_end.with_timezone(&Tokyo);
+
+    // ロールアップは日付範囲（終了は排他的境界なので前日まで）
+    if summary.summary_type.is_rollup() {
+        let title = match (locale, summary.summary_type) {
+            (SummaryLocale::Ja, SummaryType::Monthly) => "月次レポート",
+            (SummaryLocale::Ja, _) => "週次レポート",
+            (SummaryLocale::En, SummaryType::Monthly) => "Monthly report",
+            (SummaryLocale::En, _) => "Weekly report",
+        };
+        return format!(
+            "[IS22] {} {} {}-{}",
+            title,
+            summary.fid,
+            start_jst.format("%Y/%m/%d"),
+            (end_jst - Duration::days(1)).format("%m/%d")
+        );
+    }
+
+    let title = match (locale, kind) {
+        (SummaryLocale::Ja, TemplateKind::Summary) => "検出サマリー",
+        (SummaryLocale::Ja, TemplateKind::GrandSummary) => "シフトレポート",
+        (SummaryLocale::En, TemplateKind::Summary) => "Detection summary",
+        (SummaryLocale::En, TemplateKind::GrandSummary) => "Shift report",
+    };
+    format!(
+        "[IS22] {} {} {}-{}",
+        title,
+        summary.fid,
+        start_jst.format("%m/%d %H:%M"),
+        end_jst.format("%H:%M")
+    )
+}
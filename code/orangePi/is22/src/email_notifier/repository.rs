@@ -0,0 +1,358 @@
+//! Email notifier repositories
+//!
+//! - `EmailSubscriptionRepository`: email_subscriptions
+//! - `EmailQueueRepository`: email_send_queue（paraclate_send_queue と同じリトライモデル）
+
+use super::types::{
+    EmailKind, EmailQueueInsert, EmailQueueItem, EmailStatus, EmailSubscription,
+    UpsertEmailSubscriptionRequest, DEFAULT_MAX_RETRIES,
+};
+use crate::paraclate_client::types::calculate_retry_delay;
+use chrono::{Duration, Utc};
+use sqlx::{MySqlPool, Row};
+
+// ============================================================
+// Subscription Repository
+// ============================================================
+
+/// 購読設定リポジトリ
+#[derive(Clone)]
+pub struct EmailSubscriptionRepository {
+    pool: MySqlPool,
+}
+
+impl EmailSubscriptionRepository {
+    pub fn new(pool: MySqlPool) -> Self {
+        Self { pool }
+    }
+
+    /// 一覧取得
+    pub async fn list(&self) -> crate::Result<Vec<EmailSubscription>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT subscription_id, email, display_name, fids, report_types,
+                   alert_min_severity, enabled, created_at, updated_at
+            FROM email_subscriptions
+            ORDER BY email
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(row_to_subscription).collect())
+    }
+
+    /// 有効な購読のみ取得
+    pub async fn list_enabled(&self) -> crate::Result<Vec<EmailSubscription>> {
+        Ok(self.list().await?.into_iter().filter(|s| s.enabled).collect())
+    }
+
+    pub async fn get(&self, subscription_id: u64) -> crate::Result<Option<EmailSubscription>> {
+        let row = sqlx::query(
+            r#"
+            SELECT subscription_id, email, display_name, fids, report_types,
+                   alert_min_severity, enabled, created_at, updated_at
+            FROM email_subscriptions
+            WHERE subscription_id = ?
+            "#,
+        )
+        .bind(subscription_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.as_ref().map(row_to_subscription))
+    }
+
+    /// 作成（subscription_id = None）または更新
+    pub async fn save(
+        &self,
+        subscription_id: Option<u64>,
+        req: &UpsertEmailSubscriptionRequest,
+    ) -> crate::Result<EmailSubscription> {
+        let fids = serde_json::to_string(&req.fids)?;
+        let report_types = serde_json::to_string(&req.report_types)?;
+
+        let result = match subscription_id {
+            None => sqlx::query(
+                r#"
+                INSERT INTO email_subscriptions
+                    (email, display_name, fids, report_types, alert_min_severity, enabled)
+                VALUES (?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(&req.email)
+            .bind(&req.display_name)
+            .bind(&fids)
+            .bind(&report_types)
+            .bind(req.alert_min_severity)
+            .bind(req.enabled)
+            .execute(&self.pool)
+            .await,
+            Some(id) => sqlx::query(
+                r#"
+                UPDATE email_subscriptions
+                SET email = ?, display_name = ?, fids = ?, report_types = ?,
+                    alert_min_severity = ?, enabled = ?
+                WHERE subscription_id = ?
+                "#,
+            )
+            .bind(&req.email)
+            .bind(&req.display_name)
+            .bind(&fids)
+            .bind(&report_types)
+            .bind(req.alert_min_severity)
+            .bind(req.enabled)
+            .bind(id)
+            .execute(&self.pool)
+            .await,
+        };
+
+        let result = match result {
+            Ok(r) => r,
+            Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+                return Err(crate::Error::Conflict(format!(
+                    "Subscription for {} already exists",
+                    req.email
+                )))
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        let id = subscription_id.unwrap_or_else(|| result.last_insert_id());
+        self.get(id)
+            .await?
+            .ok_or_else(|| crate::Error::NotFound(format!("Subscription {} not found", id)))
+    }
+
+    pub async fn delete(&self, subscription_id: u64) -> crate::Result<bool> {
+        let result = sqlx::query("DELETE FROM email_subscriptions WHERE subscription_id = ?")
+            .bind(subscription_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+fn row_to_subscription(row: &sqlx::mysql::MySqlRow) -> EmailSubscription {
+    let json_list = |column: &str| -> Vec<String> {
+        row.try_get::<serde_json::Value, _>(column)
+            .ok()
+            .and_then(|v| serde_json::from_value(v).ok())
+            .unwrap_or_default()
+    };
+
+    EmailSubscription {
+        subscription_id: row.get("subscription_id"),
+        email: row.get("email"),
+        display_name: row.get("display_name"),
+        fids: json_list("fids"),
+        report_types: json_list("report_types"),
+        alert_min_severity: row.get("alert_min_severity"),
+        enabled: row.get("enabled"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    }
+}
+
+// ============================================================
+// Send Queue Repository
+// ============================================================
+
+/// メール送信キューリポジトリ
+#[derive(Clone)]
+pub struct EmailQueueRepository {
+    pool: MySqlPool,
+}
+
+impl EmailQueueRepository {
+    pub fn new(pool: MySqlPool) -> Self {
+        Self { pool }
+    }
+
+    /// キュー項目を挿入
+    pub async fn insert(&self, insert: EmailQueueInsert) -> crate::Result<u64> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO email_send_queue
+                (tid, fid, kind, recipient, payload, reference_id, max_retries)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&insert.tid)
+        .bind(&insert.fid)
+        .bind(insert.kind.as_str())
+        .bind(&insert.recipient)
+        .bind(insert.payload.to_string())
+        .bind(insert.reference_id)
+        .bind(insert.max_retries.unwrap_or(DEFAULT_MAX_RETRIES))
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.last_insert_id())
+    }
+
+    /// 送信待ちキューを取得（リトライ対象含む）
+    pub async fn get_pending(&self, limit: i32) -> crate::Result<Vec<EmailQueueItem>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                queue_id, tid, fid, kind, recipient, payload, reference_id,
+                status, retry_count, max_retries, next_retry_at,
+                last_error, created_at, sent_at
+            FROM email_send_queue
+            WHERE status = 'pending'
+               OR (status = 'failed' AND retry_count < max_retries
+                   AND (next_retry_at IS NULL OR next_retry_at <= NOW(3)))
+            ORDER BY created_at ASC
+            LIMIT ?
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(row_to_item).collect())
+    }
+
+    /// 送信中に更新（他の処理と重複しないよう条件付き）
+    pub async fn mark_sending(&self, queue_id: u64) -> crate::Result<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE email_send_queue SET status = 'sending'
+            WHERE queue_id = ? AND status IN ('pending', 'failed')
+            "#,
+        )
+        .bind(queue_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// 送信済みに更新
+    pub async fn mark_sent(&self, queue_id: u64) -> crate::Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE email_send_queue
+            SET status = 'sent', sent_at = ?, last_error = NULL
+            WHERE queue_id = ?
+            "#,
+        )
+        .bind(Utc::now())
+        .bind(queue_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// 失敗に更新（指数バックオフでリトライをスケジュール）
+    pub async fn mark_failed(&self, queue_id: u64, error: &str, retry_count: i32) -> crate::Result<()> {
+        let next_retry = Utc::now()
+            + Duration::from_std(calculate_retry_delay(retry_count)).unwrap_or(Duration::minutes(5));
+        sqlx::query(
+            r#"
+            UPDATE email_send_queue
+            SET status = 'failed', retry_count = ?, next_retry_at = ?, last_error = ?
+            WHERE queue_id = ?
+            "#,
+        )
+        .bind(retry_count)
+        .bind(next_retry)
+        .bind(error)
+        .bind(queue_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// 恒久エラー等で送信を打ち切る
+    pub async fn mark_skipped(&self, queue_id: u64, reason: &str) -> crate::Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE email_send_queue SET status = 'skipped', last_error = ?
+            WHERE queue_id = ?
+            "#,
+        )
+        .bind(reason)
+        .bind(queue_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// 起動時: 前回プロセスで 'sending' のまま残った項目を再送対象に戻す
+    pub async fn reset_stale_sending(&self) -> crate::Result<u64> {
+        let result = sqlx::query("UPDATE email_send_queue SET status = 'pending' WHERE status = 'sending'")
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// 一覧取得
+    pub async fn list(
+        &self,
+        status: Option<EmailStatus>,
+        limit: i32,
+        offset: i32,
+    ) -> crate::Result<Vec<EmailQueueItem>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                queue_id, tid, fid, kind, recipient, payload, reference_id,
+                status, retry_count, max_retries, next_retry_at,
+                last_error, created_at, sent_at
+            FROM email_send_queue
+            WHERE (? IS NULL OR status = ?)
+            ORDER BY created_at DESC
+            LIMIT ? OFFSET ?
+            "#,
+        )
+        .bind(status.map(|s| s.as_str()))
+        .bind(status.map(|s| s.as_str()))
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(row_to_item).collect())
+    }
+
+    /// 古いキュー項目を削除
+    pub async fn cleanup_old(&self, days: i32) -> crate::Result<u64> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM email_send_queue
+            WHERE status IN ('sent', 'skipped')
+              AND created_at < DATE_SUB(NOW(), INTERVAL ? DAY)
+            "#,
+        )
+        .bind(days)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+fn row_to_item(row: &sqlx::mysql::MySqlRow) -> EmailQueueItem {
+    let payload: serde_json::Value = row
+        .try_get::<serde_json::Value, _>("payload")
+        .unwrap_or(serde_json::json!({}));
+    let kind: String = row.get("kind");
+    let status: String = row.get("status");
+
+    EmailQueueItem {
+        queue_id: row.get("queue_id"),
+        tid: row.get("tid"),
+        fid: row.get("fid"),
+        kind: EmailKind::parse(&kind).unwrap_or(EmailKind::Test),
+        recipient: row.get("recipient"),
+        payload,
+        reference_id: row.get("reference_id"),
+        status: EmailStatus::parse(&status).unwrap_or(EmailStatus::Failed),
+        retry_count: row.get("retry_count"),
+        max_retries: row.get("max_retries"),
+        next_retry_at: row.get("next_retry_at"),
+        last_error: row.get("last_error"),
+        created_at: row.get("created_at"),
+        sent_at: row.get("sent_at"),
+    }
+}
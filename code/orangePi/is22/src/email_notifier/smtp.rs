@@ -0,0 +1,397 @@
+//! Minimal SMTP client
+//!
+//! EHLO → (STARTTLS → EHLO) → AUTH PLAIN/LOGIN → MAIL FROM → RCPT TO → DATA → QUIT
+//!
+//! 4xx・接続エラーは一時エラー（キューでリトライ）、5xxは恒久エラーとして区別する。
+
+use super::types::{SmtpConfig, SmtpSecurity};
+use base64::Engine;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader, ReadBuf};
+use tokio::net::TcpStream;
+use tokio_native_tls::{native_tls, TlsStream};
+
+/// SMTP送信エラー
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum SmtpError {
+    /// リトライで回復し得る（接続失敗・4xx・タイムアウト）
+    #[error("SMTP transient error: {0}")]
+    Transient(String),
+    /// リトライしても回復しない（5xx・設定不備）
+    #[error("SMTP permanent error: {0}")]
+    Permanent(String),
+}
+
+impl SmtpError {
+    pub fn is_permanent(&self) -> bool {
+        matches!(self, Self::Permanent(_))
+    }
+}
+
+impl From<std::io::Error> for SmtpError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Transient(e.to_string())
+    }
+}
+
+/// 送信エンベロープ
+#[derive(Debug, Clone)]
+pub struct Envelope {
+    pub from: String,
+    pub recipients: Vec<String>,
+}
+
+/// サーバー応答
+#[derive(Debug)]
+struct Reply {
+    code: u16,
+    lines: Vec<String>,
+}
+
+impl Reply {
+    fn text(&self) -> String {
+        self.lines.join(" / ")
+    }
+}
+
+/// 平文/TLSストリーム
+enum SmtpStream {
+    Plain(TcpStream),
+    Tls(Box<TlsStream<TcpStream>>),
+}
+
+impl AsyncRead for SmtpStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        match self.get_mut() {
+            Self::Plain(s) => Pin::new(s).poll_read(cx, buf),
+            Self::Tls(s) => Pin::new(s.as_mut()).poll_read(cx, buf),
+        }
+    }
+}
+
+impl AsyncWrite for SmtpStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        match self.get_mut() {
+            Self::Plain(s) => Pin::new(s).poll_write(cx, buf),
+            Self::Tls(s) => Pin::new(s.as_mut()).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        match self.get_mut() {
+            Self::Plain(s) => Pin::new(s).poll_flush(cx),
+            Self::Tls(s) => Pin::new(s.as_mut()).poll_flush(cx),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        match self.get_mut() {
+            Self::Plain(s) => Pin::new(s).poll_shutdown(cx),
+            Self::Tls(s) => Pin::new(s.as_mut()).poll_shutdown(cx),
+        }
+    }
+}
+
+struct Session {
+    stream: BufReader<SmtpStream>,
+}
+
+impl Session {
+    async fn read_reply(&mut self) -> Result<Reply, SmtpError> {
+        let mut lines = Vec::new();
+        loop {
+            let mut line = String::new();
+            if self.stream.read_line(&mut line).await? == 0 {
+                return Err(SmtpError::Transient("Connection closed by server".to_string()));
+            }
+            let line = line.trim_end_matches(['\r', '\n']);
+            if line.len() < 3 {
+                return Err(SmtpError::Transient(format!("Malformed reply: {}", line)));
+            }
+            let code: u16 = line[..3]
+                .parse()
+                .map_err(|_| SmtpError::Transient(format!("Malformed reply: {}", line)))?;
+            lines.push(line.get(4..).unwrap_or_default().to_string());
+            // "250-..." は継続行、"250 ..." で終了
+            if line.as_bytes().get(3) != Some(&b'-') {
+                return Ok(Reply { code, lines });
+            }
+        }
+    }
+
+    async fn command(&mut self, command: &str, expect: u16) -> Result<Reply, SmtpError> {
+        let label = command.split_whitespace().next().unwrap_or(command).to_string();
+        self.exchange(command, expect, &label).await
+    }
+
+    /// 認証情報を含む行（エラーには label のみ残す）
+    async fn exchange(&mut self, line: &str, expect: u16, label: &str) -> Result<Reply, SmtpError> {
+        let stream = self.stream.get_mut();
+        stream.write_all(line.as_bytes()).await?;
+        stream.write_all(b"\r\n").await?;
+        stream.flush().await?;
+        let reply = self.read_reply().await?;
+        check(&reply, expect, label)?;
+        Ok(reply)
+    }
+}
+
+/// 応答コードの検証
+fn check(reply: &Reply, expect: u16, label: &str) -> Result<(), SmtpError> {
+    if reply.code / 100 == expect / 100 {
+        return Ok(());
+    }
+    let message = format!("{} rejected: {} {}", label, reply.code, reply.text());
+    if reply.code >= 500 {
+        Err(SmtpError::Permanent(message))
+    } else {
+        Err(SmtpError::Transient(message))
+    }
+}
+
+/// DATA本文の正規化（CRLF統一・dot-stuffing・終端）
+pub fn dot_stuff(data: &[u8]) -> Vec<u8> {
+    let text = String::from_utf8_lossy(data);
+    let mut out = Vec::with_capacity(data.len() + 64);
+    for line in text.split('\n') {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        if line.starts_with('.') {
+            out.push(b'.');
+        }
+        out.extend_from_slice(line.as_bytes());
+        out.extend_from_slice(b"\r\n");
+    }
+    // split('\n') は末尾改行の後に空要素を生成する
+    if text.ends_with('\n') {
+        out.truncate(out.len() - 2);
+    }
+    out.extend_from_slice(b".\r\n");
+    out
+}
+
+async fn tls_connect(
+    config: &SmtpConfig,
+    stream: TcpStream,
+) -> Result<TlsStream<TcpStream>, SmtpError> {
+    let connector = native_tls::TlsConnector::builder()
+        .danger_accept_invalid_certs(config.accept_invalid_certs)
+        .build()
+        .map_err(|e| SmtpError::Permanent(format!("TLS setup failed: {}", e)))?;
+    tokio_native_tls::TlsConnector::from(connector)
+        .connect(&config.host, stream)
+        .await
+        .map_err(|e| SmtpError::Transient(format!("TLS handshake failed: {}", e)))
+}
+
+/// メール1通を送信
+pub async fn send_mail(
+    config: &SmtpConfig,
+    envelope: &Envelope,
+    data: &[u8],
+) -> Result<(), SmtpError> {
+    let timeout = Duration::from_secs(config.timeout_sec.max(1));
+    tokio::time::timeout(timeout, send_mail_inner(config, envelope, data))
+        .await
+        .map_err(|_| SmtpError::Transient(format!("Timed out after {}s", timeout.as_secs())))?
+}
+
+async fn send_mail_inner(
+    config: &SmtpConfig,
+    envelope: &Envelope,
+    data: &[u8],
+) -> Result<(), SmtpError> {
+    if envelope.recipients.is_empty() {
+        return Err(SmtpError::Permanent("No recipients".to_string()));
+    }
+
+    let tcp = TcpStream::connect((config.host.as_str(), config.port)).await?;
+    let stream = match config.security {
+        SmtpSecurity::Tls => SmtpStream::Tls(Box::new(tls_connect(config, tcp).await?)),
+        _ => SmtpStream::Plain(tcp),
+    };
+    let mut session = Session {
+        stream: BufReader::new(stream),
+    };
+
+    let greeting = session.read_reply().await?;
+    check(&greeting, 220, "CONNECT")?;
+
+    let ehlo = format!("EHLO {}", config.helo_name);
+    let mut capabilities = session.command(&ehlo, 250).await?.lines;
+
+    if config.security == SmtpSecurity::StartTls {
+        if !capabilities.iter().any(|c| c.eq_ignore_ascii_case("STARTTLS")) {
+            return Err(SmtpError::Permanent(
+                "Server does not support STARTTLS (set security to 'none' for plain relays)"
+                    .to_string(),
+            ));
+        }
+        session.command("STARTTLS", 220).await?;
+        let SmtpStream::Plain(tcp) = session.stream.into_inner() else {
+            return Err(SmtpError::Permanent("Unexpected TLS state".to_string()));
+        };
+        session = Session {
+            stream: BufReader::new(SmtpStream::Tls(Box::new(tls_connect(config, tcp).await?))),
+        };
+        capabilities = session.command(&ehlo, 250).await?.lines;
+    }
+
+    if let (Some(username), Some(password)) = (&config.username, &config.password) {
+        let auth = capabilities
+            .iter()
+            .find(|c| c.to_ascii_uppercase().starts_with("AUTH"))
+            .map(|c| c.to_ascii_uppercase())
+            .unwrap_or_default();
+        let b64 = base64::engine::general_purpose::STANDARD;
+        if auth.contains("PLAIN") {
+            let token = b64.encode(format!("\0{}\0{}", username, password));
+            session
+                .exchange(&format!("AUTH PLAIN {}", token), 235, "AUTH PLAIN")
+                .await?;
+        } else if auth.contains("LOGIN") {
+            session.command("AUTH LOGIN", 334).await?;
+            session.exchange(&b64.encode(username), 334, "AUTH LOGIN").await?;
+            session.exchange(&b64.encode(password), 235, "AUTH LOGIN").await?;
+        } else {
+            return Err(SmtpError::Permanent(
+                "Server does not offer AUTH PLAIN/LOGIN".to_string(),
+            ));
+        }
+    }
+
+    session
+        .command(&format!("MAIL FROM:<{}>", envelope.from), 250)
+        .await?;
+    for recipient in &envelope.recipients {
+        session
+            .command(&format!("RCPT TO:<{}>", recipient), 250)
+            .await?;
+    }
+    session.command("DATA", 354).await?;
+
+    let stream = session.stream.get_mut();
+    stream.write_all(&dot_stuff(data)).await?;
+    stream.flush().await?;
+    let reply = session.read_reply().await?;
+    check(&reply, 250, "DATA")?;
+
+    // QUIT失敗は送信結果に影響しない
+    let _ = session.command("QUIT", 221).await;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::AsyncReadExt;
+    use tokio::net::TcpListener;
+
+    /// ローカルSMTPシンク: 受信したDATAを返す
+    async fn smtp_sink(reject_rcpt: bool) -> (u16, tokio::task::JoinHandle<(Vec<String>, String)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let handle = tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut stream = BufReader::new(socket);
+            let mut commands = Vec::new();
+            let mut data = String::new();
+            stream.get_mut().write_all(b"220 sink ESMTP\r\n").await.unwrap();
+            loop {
+                let mut line = String::new();
+                if stream.read_line(&mut line).await.unwrap() == 0 {
+                    break;
+                }
+                let line = line.trim_end().to_string();
+                commands.push(line.clone());
+                let reply: &[u8] = if line.starts_with("EHLO") {
+                    b"250-sink\r\n250-AUTH PLAIN LOGIN\r\n250 SIZE 10485760\r\n"
+                } else if line.starts_with("RCPT") && reject_rcpt {
+                    b"550 5.1.1 No such user\r\n"
+                } else if line == "DATA" {
+                    stream.get_mut().write_all(b"354 go ahead\r\n").await.unwrap();
+                    loop {
+                        let mut l = String::new();
+                        stream.read_line(&mut l).await.unwrap();
+                        if l == ".\r\n" {
+                            break;
+                        }
+                        data.push_str(&l);
+                    }
+                    b"250 queued\r\n"
+                } else if line == "QUIT" {
+                    stream.get_mut().write_all(b"221 bye\r\n").await.unwrap();
+                    let _ = stream.read_to_end(&mut Vec::new()).await;
+                    break;
+                } else if line.starts_with("AUTH PLAIN") {
+                    b"235 ok\r\n"
+                } else {
+                    b"250 ok\r\n"
+                };
+                stream.get_mut().write_all(reply).await.unwrap();
+            }
+            (commands, data)
+        });
+        (port, handle)
+    }
+
+    fn sink_config(port: u16) -> SmtpConfig {
+        SmtpConfig {
+            enabled: true,
+            host: "127.0.0.1".to_string(),
+            port,
+            security: SmtpSecurity::None,
+            username: Some("user".to_string()),
+            password: Some("secret".to_string()),
+            timeout_sec: 5,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_dot_stuff() {
+        assert_eq!(dot_stuff(b"a\n.b\r\nc"), b"a\r\n..b\r\nc\r\n.\r\n".to_vec());
+        assert_eq!(dot_stuff(b"a\r\n"), b"a\r\n.\r\n".to_vec());
+    }
+
+    #[tokio::test]
+    async fn test_send_mail_to_local_sink() {
+        let (port, sink) = smtp_sink(false).await;
+        let envelope = Envelope {
+            from: "is22@example.com".to_string(),
+            recipients: vec!["a@example.com".to_string(), "b@example.com".to_string()],
+        };
+        send_mail(&sink_config(port), &envelope, b"Subject: t\r\n\r\n.hidden\r\n")
+            .await
+            .unwrap();
+
+        let (commands, data) = sink.await.unwrap();
+        assert_eq!(commands[0], "EHLO is22");
+        assert!(commands[1].starts_with("AUTH PLAIN "));
+        assert_eq!(commands[2], "MAIL FROM:<is22@example.com>");
+        assert_eq!(commands[3], "RCPT TO:<a@example.com>");
+        assert_eq!(commands[4], "RCPT TO:<b@example.com>");
+        assert_eq!(data, "Subject: t\r\n\r\n..hidden\r\n");
+    }
+
+    #[tokio::test]
+    async fn test_rejected_recipient_is_permanent() {
+        let (port, _sink) = smtp_sink(true).await;
+        let envelope = Envelope {
+            from: "is22@example.com".to_string(),
+            recipients: vec!["nobody@example.com".to_string()],
+        };
+        let err = send_mail(&sink_config(port), &envelope, b"x").await.unwrap_err();
+        assert!(err.is_permanent(), "{}", err);
+        assert!(!err.to_string().contains("secret"));
+    }
+}
@@ -0,0 +1,359 @@
+//! Email notifier types
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// settingsテーブルのキー（SMTPサーバー設定）
+pub const SMTP_CONFIG_KEY: &str = "smtp";
+
+/// settingsテーブルのキー（最後にdaily summaryを送信したJST日付）
+pub const DAILY_LAST_SENT_KEY: &str = "email_daily_last_sent";
+
+/// API応答でパスワードの代わりに返す値（PUTでこの値なら既存値を保持）
+pub const PASSWORD_MASK: &str = "********";
+
+/// 送信キューのデフォルト最大リトライ回数
+pub const DEFAULT_MAX_RETRIES: i32 = 5;
+
+/// SMTP connection security
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SmtpSecurity {
+    /// 平文（ローカルリレー・テスト用シンク）
+    None,
+    /// 平文接続後にSTARTTLSで昇格（587）
+    #[default]
+    StartTls,
+    /// 接続時からTLS（465）
+    Tls,
+}
+
+/// SMTP server configuration (settings.smtp)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SmtpConfig {
+    pub enabled: bool,
+    pub host: String,
+    pub port: u16,
+    pub security: SmtpSecurity,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub from_address: String,
+    pub from_name: Option<String>,
+    /// EHLOで名乗るホスト名
+    pub helo_name: String,
+    pub timeout_sec: u64,
+    /// メール内リンク用の外部URL（例: http://is22.local:8080）
+    pub public_base_url: Option<String>,
+    /// 自己署名証明書を許可（社内リレー用）
+    pub accept_invalid_certs: bool,
+}
+
+impl Default for SmtpConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            host: "localhost".to_string(),
+            port: 587,
+            security: SmtpSecurity::StartTls,
+            username: None,
+            password: None,
+            from_address: "is22@localhost".to_string(),
+            from_name: Some("IS22 Camserver".to_string()),
+            helo_name: "is22".to_string(),
+            timeout_sec: 30,
+            public_base_url: None,
+            accept_invalid_certs: false,
+        }
+    }
+}
+
+impl SmtpConfig {
+    /// パスワードをマスクしたコピー（API応答用）
+    pub fn masked(&self) -> Self {
+        let mut config = self.clone();
+        if config.password.is_some() {
+            config.password = Some(PASSWORD_MASK.to_string());
+        }
+        config
+    }
+
+    pub fn validate(&self) -> crate::Result<()> {
+        if self.host.is_empty() {
+            return Err(crate::Error::Validation("host is required".to_string()));
+        }
+        if self.port == 0 {
+            return Err(crate::Error::Validation("port must be 1-65535".to_string()));
+        }
+        if !is_valid_email(&self.from_address) {
+            return Err(crate::Error::Validation(format!(
+                "Invalid fromAddress: {}",
+                self.from_address
+            )));
+        }
+        if self.username.is_some() != self.password.is_some() {
+            return Err(crate::Error::Validation(
+                "username and password must be set together".to_string(),
+            ));
+        }
+        if !(1..=300).contains(&self.timeout_sec) {
+            return Err(crate::Error::Validation("timeout_sec must be 1-300".to_string()));
+        }
+        Ok(())
+    }
+}
+
+/// メール種別
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmailKind {
+    /// スケジュール実行されたSummary/GrandSummaryレポート
+    Report,
+    /// NotificationPolicy.daily_summary_time の日次レポート
+    Daily,
+    /// 高severity検出アラート
+    Alert,
+    /// 設定確認用テストメール
+    Test,
+}
+
+impl EmailKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Report => "report",
+            Self::Daily => "daily",
+            Self::Alert => "alert",
+            Self::Test => "test",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "report" => Some(Self::Report),
+            "daily" => Some(Self::Daily),
+            "alert" => Some(Self::Alert),
+            "test" => Some(Self::Test),
+            _ => None,
+        }
+    }
+}
+
+/// 送信状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmailStatus {
+    Pending,
+    Sending,
+    Sent,
+    Failed,
+    /// 恒久エラー（5xx応答）・購読解除などで送信しない
+    Skipped,
+}
+
+impl EmailStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Sending => "sending",
+            Self::Sent => "sent",
+            Self::Failed => "failed",
+            Self::Skipped => "skipped",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "pending" => Some(Self::Pending),
+            "sending" => Some(Self::Sending),
+            "sent" => Some(Self::Sent),
+            "failed" => Some(Self::Failed),
+            "skipped" => Some(Self::Skipped),
+            _ => None,
+        }
+    }
+}
+
+/// 購読できるレポート種別
+pub const REPORT_TYPES: &[&str] = &[
+    "summary",
+    "grand_summary",
+    "daily",
+    "weekly_rollup",
+    "monthly_rollup",
+];
+
+/// 受信者ごとの購読設定
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmailSubscription {
+    pub subscription_id: u64,
+    pub email: String,
+    pub display_name: Option<String>,
+    /// 対象FID（空 = 全施設）
+    pub fids: Vec<String>,
+    /// "summary" / "grand_summary" / "daily" / "weekly_rollup" / "monthly_rollup"
+    pub report_types: Vec<String>,
+    /// アラートを受信する最小severity（None = 受信しない）
+    pub alert_min_severity: Option<i32>,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl EmailSubscription {
+    fn covers_fid(&self, fid: &str) -> bool {
+        self.fids.is_empty() || self.fids.iter().any(|f| f == fid)
+    }
+
+    /// レポート種別の購読判定
+    pub fn wants_report(&self, fid: &str, report_type: &str) -> bool {
+        self.enabled && self.covers_fid(fid) && self.report_types.iter().any(|t| t == report_type)
+    }
+
+    /// 検出アラートの購読判定
+    pub fn wants_alert(&self, fid: &str, severity: i32) -> bool {
+        self.enabled
+            && self.covers_fid(fid)
+            && self.alert_min_severity.is_some_and(|min| severity >= min)
+    }
+}
+
+/// 購読作成・更新リクエスト
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpsertEmailSubscriptionRequest {
+    pub email: String,
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub fids: Vec<String>,
+    #[serde(default)]
+    pub report_types: Vec<String>,
+    pub alert_min_severity: Option<i32>,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl UpsertEmailSubscriptionRequest {
+    pub fn validate(&self) -> crate::Result<()> {
+        if !is_valid_email(&self.email) {
+            return Err(crate::Error::Validation(format!("Invalid email: {}", self.email)));
+        }
+        if let Some(t) = self.report_types.iter().find(|t| !REPORT_TYPES.contains(&t.as_str())) {
+            return Err(crate::Error::Validation(format!(
+                "Invalid report type '{}'. Use one of {:?}",
+                t, REPORT_TYPES
+            )));
+        }
+        if self.alert_min_severity.is_some_and(|s| !(1..=3).contains(&s)) {
+            return Err(crate::Error::Validation(
+                "alertMinSeverity must be 1-3".to_string(),
+            ));
+        }
+        if self.fids.iter().any(|f| f.is_empty() || f.len() > 32) {
+            return Err(crate::Error::Validation("fid must be 1-32 characters".to_string()));
+        }
+        Ok(())
+    }
+}
+
+/// 送信キュー項目
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmailQueueItem {
+    pub queue_id: u64,
+    pub tid: String,
+    pub fid: String,
+    pub kind: EmailKind,
+    pub recipient: String,
+    pub payload: serde_json::Value,
+    pub reference_id: Option<u64>,
+    pub status: EmailStatus,
+    pub retry_count: i32,
+    pub max_retries: i32,
+    pub next_retry_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub sent_at: Option<DateTime<Utc>>,
+}
+
+/// 送信キュー挿入
+#[derive(Debug, Clone)]
+pub struct EmailQueueInsert {
+    pub tid: String,
+    pub fid: String,
+    pub kind: EmailKind,
+    pub recipient: String,
+    pub payload: serde_json::Value,
+    pub reference_id: Option<u64>,
+    pub max_retries: Option<i32>,
+}
+
+/// キュー処理結果
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmailProcessStats {
+    pub sent: usize,
+    pub failed: usize,
+    pub skipped: usize,
+}
+
+/// 簡易メールアドレス検証（ヘッダーインジェクション防止を兼ねる）
+pub fn is_valid_email(email: &str) -> bool {
+    let Some((local, domain)) = email.rsplit_once('@') else {
+        return false;
+    };
+    !local.is_empty()
+        && !domain.is_empty()
+        && !domain.starts_with('.')
+        && !domain.ends_with('.')
+        && email.len() <= 254
+        && email
+            .chars()
+            .all(|c| c.is_ascii_graphic() && !matches!(c, '<' | '>' | ',' | ';' | '"' | '(' | ')'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn subscription(fids: &[&str], report_types: &[&str], min_severity: Option<i32>) -> EmailSubscription {
+        EmailSubscription {
+            subscription_id: 1,
+            email: "ops@example.com".to_string(),
+            display_name: None,
+            fids: fids.iter().map(|s| s.to_string()).collect(),
+            report_types: report_types.iter().map(|s| s.to_string()).collect(),
+            alert_min_severity: min_severity,
+            enabled: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_subscription_matching() {
+        let all = subscription(&[], &["grand_summary"], Some(3));
+        assert!(all.wants_report("0150", "grand_summary"));
+        assert!(!all.wants_report("0150", "summary"));
+        assert!(all.wants_alert("0150", 3));
+        assert!(!all.wants_alert("0150", 2));
+
+        let scoped = subscription(&["0150"], &["summary"], None);
+        assert!(scoped.wants_report("0150", "summary"));
+        assert!(!scoped.wants_report("0151", "summary"));
+        assert!(!scoped.wants_alert("0150", 3));
+    }
+
+    #[test]
+    fn test_is_valid_email() {
+        assert!(is_valid_email("ops@example.com"));
+        assert!(is_valid_email("ops@localhost"));
+        assert!(!is_valid_email("ops@"));
+        assert!(!is_valid_email("ops@example.com\r\nBcc: x@y.z"));
+        assert!(!is_valid_email("<ops@example.com>"));
+    }
+}
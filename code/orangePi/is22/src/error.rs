@@ -81,6 +81,10 @@ pub enum Error {
     #[error("Paraclate error: {0}")]
     Paraclate(String),
 
+    /// Schema migration error
+    #[error("Migration error: {0}")]
+    Migration(String),
+
     /// Access Absorber error (stream limit/connection control)
     #[error("Access denied for camera {camera_id}: {message}")]
     AccessAbsorber { camera_id: String, message: String },
@@ -162,6 +166,11 @@ impl IntoResponse for Error {
                 "PARACLATE_ERROR",
                 msg.clone(),
             ),
+            Error::Migration(msg) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "MIGRATION_ERROR",
+                msg.clone(),
+            ),
             Error::AccessAbsorber { camera_id, message } => (
                 StatusCode::SERVICE_UNAVAILABLE,
                 "ACCESS_DENIED",
@@ -0,0 +1,206 @@
+//! Background loop heartbeats
+//!
+//! 各バックグラウンドループはティック毎に結果を記録する。`stall_after` の間
+//! 一度もティックしないループは停止（stalled）と判定し、liveness を degraded にする。
+//! 失敗ティックはループ自体は生きているので stalled にはしない（last_error で報告）。
+
+use super::ComponentStatus;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Minimum stall threshold (short-interval loops)
+const MIN_STALL_AFTER: Duration = Duration::from_secs(60);
+/// Missed ticks before a loop is considered stalled
+const STALL_FACTOR: u32 = 3;
+
+/// Stall threshold for a loop ticking every `interval`
+pub fn stall_threshold(interval: Duration) -> Duration {
+    (interval * STALL_FACTOR).max(MIN_STALL_AFTER)
+}
+
+#[derive(Debug, Clone)]
+struct LoopState {
+    stall_after: Duration,
+    registered_at: Instant,
+    last_tick: Option<Instant>,
+    last_success_at: Option<DateTime<Utc>>,
+    last_error: Option<String>,
+    ticks: u64,
+    failures: u64,
+}
+
+/// Loop status for /readyz and /livez
+#[derive(Debug, Clone, Serialize)]
+pub struct LoopHealth {
+    pub name: String,
+    pub status: ComponentStatus,
+    pub stalled: bool,
+    pub stall_after_secs: u64,
+    pub last_tick_secs_ago: Option<u64>,
+    pub last_success_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub ticks: u64,
+    pub failures: u64,
+}
+
+/// Process uptime and background loop heartbeats
+pub struct HealthRegistry {
+    started_at: Instant,
+    loops: Mutex<BTreeMap<String, LoopState>>,
+}
+
+impl HealthRegistry {
+    pub fn new() -> Self {
+        Self {
+            started_at: Instant::now(),
+            loops: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    pub fn uptime(&self) -> Duration {
+        self.started_at.elapsed()
+    }
+
+    /// Start tracking a loop (registration counts as the first expected tick window)
+    pub fn register(&self, name: &str, stall_after: Duration) {
+        let mut loops = self.loops.lock().unwrap_or_else(|e| e.into_inner());
+        loops.insert(
+            name.to_string(),
+            LoopState {
+                stall_after,
+                registered_at: Instant::now(),
+                last_tick: None,
+                last_success_at: None,
+                last_error: None,
+                ticks: 0,
+                failures: 0,
+            },
+        );
+    }
+
+    /// Stop tracking a loop that exited on purpose (e.g. subnet removed)
+    pub fn unregister(&self, name: &str) {
+        let mut loops = self.loops.lock().unwrap_or_else(|e| e.into_inner());
+        loops.remove(name);
+    }
+
+    /// Record a successful tick
+    pub fn tick_ok(&self, name: &str) {
+        self.record(name, None);
+    }
+
+    /// Record a failed tick (loop alive, work failed)
+    pub fn tick_failed(&self, name: &str, error: impl std::fmt::Display) {
+        self.record(name, Some(error.to_string()));
+    }
+
+    fn record(&self, name: &str, error: Option<String>) {
+        let mut loops = self.loops.lock().unwrap_or_else(|e| e.into_inner());
+        // 未登録のループは無視
+        if let Some(state) = loops.get_mut(name) {
+            state.last_tick = Some(Instant::now());
+            state.ticks += 1;
+            match error {
+                Some(e) => {
+                    state.failures += 1;
+                    state.last_error = Some(e);
+                }
+                None => state.last_success_at = Some(Utc::now()),
+            }
+        }
+    }
+
+    /// Current status of all loops
+    pub fn loops(&self) -> Vec<LoopHealth> {
+        self.loops_at(Instant::now())
+    }
+
+    /// Names of stalled loops
+    pub fn stalled(&self) -> Vec<String> {
+        self.loops()
+            .into_iter()
+            .filter(|l| l.stalled)
+            .map(|l| l.name)
+            .collect()
+    }
+
+    fn loops_at(&self, now: Instant) -> Vec<LoopHealth> {
+        let loops = self.loops.lock().unwrap_or_else(|e| e.into_inner());
+        loops
+            .iter()
+            .map(|(name, state)| {
+                let reference = state.last_tick.unwrap_or(state.registered_at);
+                let stalled = now.saturating_duration_since(reference) > state.stall_after;
+                LoopHealth {
+                    name: name.clone(),
+                    status: if stalled {
+                        ComponentStatus::Degraded
+                    } else {
+                        ComponentStatus::Ok
+                    },
+                    stalled,
+                    stall_after_secs: state.stall_after.as_secs(),
+                    last_tick_secs_ago: state
+                        .last_tick
+                        .map(|t| now.saturating_duration_since(t).as_secs()),
+                    last_success_at: state.last_success_at,
+                    last_error: state.last_error.clone(),
+                    ticks: state.ticks,
+                    failures: state.failures,
+                }
+            })
+            .collect()
+    }
+}
+
+impl Default for HealthRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_loop_stalls_without_ticks() {
+        let registry = HealthRegistry::new();
+        registry.register("paraclate_queue", Duration::from_secs(90));
+        registry.register("lost_cam_tracker", Duration::from_secs(1800));
+        registry.tick_failed("paraclate_queue", "timeout");
+
+        let later = Instant::now() + Duration::from_secs(120);
+        let loops = registry.loops_at(later);
+        let queue = loops.iter().find(|l| l.name == "paraclate_queue").unwrap();
+        let tracker = loops.iter().find(|l| l.name == "lost_cam_tracker").unwrap();
+
+        assert!(queue.stalled);
+        assert_eq!(queue.failures, 1);
+        assert_eq!(queue.last_error.as_deref(), Some("timeout"));
+        assert!(queue.last_success_at.is_none());
+        assert!(!tracker.stalled);
+        assert_eq!(tracker.last_tick_secs_ago, None);
+    }
+
+    #[test]
+    fn test_unregistered_loops_are_ignored() {
+        let registry = HealthRegistry::new();
+        registry.register(
+            "polling:192.168.125",
+            stall_threshold(Duration::from_secs(10)),
+        );
+        registry.tick_ok("polling:192.168.125");
+        registry.tick_ok("unknown");
+        registry.unregister("polling:192.168.125");
+
+        assert!(registry.loops().is_empty());
+        assert_eq!(
+            stall_threshold(Duration::from_secs(600)),
+            Duration::from_secs(1800)
+        );
+    }
+}
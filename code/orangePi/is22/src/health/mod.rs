@@ -0,0 +1,347 @@
+//! Health - liveness / readiness reporting
+//!
+//! ## Responsibilities
+//!
+//! - プロセス稼働時間とバックグラウンドループのハートビート（[`HealthRegistry`]）
+//! - 依存コンポーネント（DB / IS21 / go2rtc / ffmpeg / ディスク / Paraclate）の疎通確認
+//! - TaskSupervisor 配下タスクの状態（再起動中・終了・シャットダウン中）
+//!
+//! `/livez` はループ停止のみを見る（watchdog 用）。`/readyz` は全コンポーネントを返す。
+
+mod heartbeat;
+
+pub use heartbeat::{stall_threshold, HealthRegistry, LoopHealth};
+
+use crate::paraclate_client::ConnectionStatus;
+use crate::snapshot_service::SnapshotService;
+use crate::state::AppState;
+use crate::task_supervisor::{TaskState, TaskStatus};
+use serde::Serialize;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Per-check timeout so a hung dependency cannot hang /readyz
+const CHECK_TIMEOUT: Duration = Duration::from_secs(3);
+/// Free space below this ratio is degraded
+const DISK_WARN_RATIO: f64 = 0.10;
+/// Free space below this ratio is down
+const DISK_FAIL_RATIO: f64 = 0.02;
+/// Components whose outage makes the whole service down (/readyz 503)
+const CRITICAL_COMPONENTS: &[&str] = &["database", "tasks"];
+
+/// Component / overall status
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ComponentStatus {
+    Ok,
+    Degraded,
+    Down,
+}
+
+/// One component check result
+#[derive(Debug, Clone, Serialize)]
+pub struct ComponentHealth {
+    pub name: String,
+    pub status: ComponentStatus,
+    pub detail: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+}
+
+impl ComponentHealth {
+    fn new(name: &str, status: ComponentStatus, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status,
+            detail: detail.into(),
+            latency_ms: None,
+        }
+    }
+
+    fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency_ms = Some(latency.as_millis() as u64);
+        self
+    }
+}
+
+/// Full readiness report (/readyz)
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthReport {
+    pub status: ComponentStatus,
+    pub version: String,
+    pub uptime_sec: u64,
+    pub components: Vec<ComponentHealth>,
+    pub loops: Vec<LoopHealth>,
+    pub tasks: Vec<TaskStatus>,
+}
+
+/// Filesystem usage for a path
+#[derive(Debug, Clone)]
+pub struct DiskUsage {
+    pub mount_point: PathBuf,
+    pub available: u64,
+    pub total: u64,
+}
+
+/// Usage of the filesystem holding `path` (longest matching mount point)
+pub fn disk_usage(path: &Path) -> Option<DiskUsage> {
+    let disks = sysinfo::Disks::new_with_refreshed_list();
+    disks
+        .iter()
+        .filter(|d| path.starts_with(d.mount_point()))
+        .max_by_key(|d| d.mount_point().as_os_str().len())
+        .map(|d| DiskUsage {
+            mount_point: d.mount_point().to_path_buf(),
+            available: d.available_space(),
+            total: d.total_space(),
+        })
+}
+
+/// Classify free space of a filesystem
+pub fn disk_status(available: u64, total: u64) -> ComponentStatus {
+    if total == 0 {
+        return ComponentStatus::Degraded;
+    }
+    let ratio = available as f64 / total as f64;
+    if ratio < DISK_FAIL_RATIO {
+        ComponentStatus::Down
+    } else if ratio < DISK_WARN_RATIO {
+        ComponentStatus::Degraded
+    } else {
+        ComponentStatus::Ok
+    }
+}
+
+/// Overall status: DB down / shutting down = down, anything else unhealthy = degraded
+pub fn overall_status(components: &[ComponentHealth], loops: &[LoopHealth]) -> ComponentStatus {
+    let critical_down = components.iter().any(|c| {
+        CRITICAL_COMPONENTS.contains(&c.name.as_str()) && c.status == ComponentStatus::Down
+    });
+    if critical_down {
+        ComponentStatus::Down
+    } else if components.iter().any(|c| c.status != ComponentStatus::Ok)
+        || loops.iter().any(|l| l.stalled)
+    {
+        ComponentStatus::Degraded
+    } else {
+        ComponentStatus::Ok
+    }
+}
+
+/// Run a check with timeout and latency measurement
+async fn timed<T, F>(future: F) -> (Option<T>, Duration)
+where
+    F: Future<Output = T>,
+{
+    let start = Instant::now();
+    let result = tokio::time::timeout(CHECK_TIMEOUT, future).await.ok();
+    (result, start.elapsed())
+}
+
+/// DB pool: `SELECT 1` plus pool usage
+pub async fn check_database(state: &AppState) -> ComponentHealth {
+    let pool = &state.pool;
+    let (result, latency) = timed(sqlx::query("SELECT 1").execute(pool)).await;
+    let usage = format!(
+        "pool {}/{} connections ({} idle)",
+        pool.size(),
+        state.config.database.max_connections,
+        pool.num_idle()
+    );
+    let health = match result {
+        Some(Ok(_)) => ComponentHealth::new("database", ComponentStatus::Ok, usage),
+        Some(Err(e)) => ComponentHealth::new(
+            "database",
+            ComponentStatus::Down,
+            format!("{}; {}", e, usage),
+        ),
+        None => ComponentHealth::new(
+            "database",
+            ComponentStatus::Down,
+            format!("SELECT 1 timed out; {}", usage),
+        ),
+    };
+    health.with_latency(latency)
+}
+
+async fn check_endpoint<F>(name: &str, url: &str, future: F) -> ComponentHealth
+where
+    F: Future<Output = crate::Result<bool>>,
+{
+    let (result, latency) = timed(future).await;
+    let health = match result {
+        Some(Ok(true)) => ComponentHealth::new(name, ComponentStatus::Ok, url),
+        Some(Ok(false)) => {
+            ComponentHealth::new(name, ComponentStatus::Down, format!("{} unhealthy", url))
+        }
+        Some(Err(e)) => ComponentHealth::new(name, ComponentStatus::Down, e.to_string()),
+        None => ComponentHealth::new(name, ComponentStatus::Down, format!("{} timed out", url)),
+    };
+    health.with_latency(latency)
+}
+
+async fn check_ffmpeg() -> ComponentHealth {
+    let (result, latency) = timed(SnapshotService::check_ffmpeg()).await;
+    let health = match result {
+        Some(Ok(version)) => ComponentHealth::new("ffmpeg", ComponentStatus::Ok, version),
+        Some(Err(e)) => ComponentHealth::new("ffmpeg", ComponentStatus::Down, e.to_string()),
+        None => ComponentHealth::new("ffmpeg", ComponentStatus::Down, "ffmpeg -version timed out"),
+    };
+    health.with_latency(latency)
+}
+
+/// Free space of the filesystem holding `path`
+pub fn check_disk(name: &str, path: &Path) -> ComponentHealth {
+    if !path.exists() {
+        return ComponentHealth::new(
+            name,
+            ComponentStatus::Down,
+            format!("{} does not exist", path.display()),
+        );
+    }
+    match disk_usage(path) {
+        Some(usage) => ComponentHealth::new(
+            name,
+            disk_status(usage.available, usage.total),
+            format!(
+                "{} on {}: {} MB free of {} MB",
+                path.display(),
+                usage.mount_point.display(),
+                usage.available / (1024 * 1024),
+                usage.total / (1024 * 1024)
+            ),
+        ),
+        None => ComponentHealth::new(
+            name,
+            ComponentStatus::Degraded,
+            format!("no mount found for {}", path.display()),
+        ),
+    }
+}
+
+/// Paraclate connection state of every configured TID/FID
+async fn check_paraclate(state: &AppState) -> ComponentHealth {
+    let (result, latency) = timed(state.paraclate_client.config_repo().get_all()).await;
+    let configs = match result {
+        Some(Ok(configs)) => configs,
+        Some(Err(e)) => {
+            return ComponentHealth::new("paraclate", ComponentStatus::Degraded, e.to_string())
+                .with_latency(latency)
+        }
+        None => {
+            return ComponentHealth::new("paraclate", ComponentStatus::Degraded, "query timed out")
+                .with_latency(latency)
+        }
+    };
+    if configs.is_empty() {
+        return ComponentHealth::new("paraclate", ComponentStatus::Ok, "not configured")
+            .with_latency(latency);
+    }
+
+    // 明示的な切断は正常扱い、エラー状態のみ degraded
+    let status = if configs
+        .iter()
+        .any(|c| c.connection_status == ConnectionStatus::Error)
+    {
+        ComponentStatus::Degraded
+    } else {
+        ComponentStatus::Ok
+    };
+    let detail = configs
+        .iter()
+        .map(|c| {
+            let mut entry = format!("{}: {}", c.fid, c.connection_status);
+            if let Some(at) = c.last_sync_at {
+                entry.push_str(&format!(" (last sync {})", at.to_rfc3339()));
+            }
+            if c.connection_status == ConnectionStatus::Error {
+                if let Some(err) = &c.last_error {
+                    entry.push_str(&format!(" - {}", err));
+                }
+            }
+            entry
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    ComponentHealth::new("paraclate", status, detail).with_latency(latency)
+}
+
+/// Supervised task summary (restarting / finished tasks are degraded)
+pub fn check_tasks(tasks: &[TaskStatus], shutting_down: bool) -> ComponentHealth {
+    if shutting_down {
+        return ComponentHealth::new("tasks", ComponentStatus::Down, "shutting down");
+    }
+    let unhealthy: Vec<String> = tasks
+        .iter()
+        .filter(|t| matches!(t.state, TaskState::Restarting | TaskState::Finished))
+        .map(|t| format!("{} {:?}", t.name, t.state).to_lowercase())
+        .collect();
+    let restarts: u32 = tasks.iter().map(|t| t.restarts).sum();
+    if unhealthy.is_empty() {
+        ComponentHealth::new(
+            "tasks",
+            ComponentStatus::Ok,
+            format!("{} running, {} restart(s)", tasks.len(), restarts),
+        )
+    } else {
+        ComponentHealth::new("tasks", ComponentStatus::Degraded, unhealthy.join(", "))
+    }
+}
+
+/// Check every component concurrently and build the readiness report
+pub async fn readiness(state: &AppState) -> HealthReport {
+    let is21_url = state.config.is21_url.clone();
+    let go2rtc_url = state.config.go2rtc_url.clone();
+    let (database, is21, go2rtc, ffmpeg, paraclate) = tokio::join!(
+        check_database(state),
+        check_endpoint("is21", &is21_url, state.ai_client.health_check()),
+        check_endpoint("go2rtc", &go2rtc_url, state.stream.health_check()),
+        check_ffmpeg(),
+        check_paraclate(state),
+    );
+    let disk = check_disk("disk:snapshots", &state.config.snapshot_dir);
+    let tasks = state.supervisor.tasks();
+    let task_health = check_tasks(&tasks, state.supervisor.is_shutting_down());
+
+    let components = vec![database, is21, go2rtc, ffmpeg, disk, paraclate, task_health];
+    let loops = state.health.loops();
+    HealthReport {
+        status: overall_status(&components, &loops),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        uptime_sec: state.health.uptime().as_secs(),
+        components,
+        loops,
+        tasks,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disk_status_thresholds() {
+        assert_eq!(disk_status(50, 100), ComponentStatus::Ok);
+        assert_eq!(disk_status(5, 100), ComponentStatus::Degraded);
+        assert_eq!(disk_status(1, 100), ComponentStatus::Down);
+        assert_eq!(disk_status(0, 0), ComponentStatus::Degraded);
+    }
+
+    #[test]
+    fn test_overall_status() {
+        let ok = |name: &str| ComponentHealth::new(name, ComponentStatus::Ok, "");
+        let mut components = vec![ok("database"), ok("is21")];
+        assert_eq!(overall_status(&components, &[]), ComponentStatus::Ok);
+
+        components[1].status = ComponentStatus::Down;
+        assert_eq!(overall_status(&components, &[]), ComponentStatus::Degraded);
+
+        components[0].status = ComponentStatus::Down;
+        assert_eq!(overall_status(&components, &[]), ComponentStatus::Down);
+
+        components[0].status = ComponentStatus::Ok;
+        components.push(check_tasks(&[], true));
+        assert_eq!(overall_status(&components, &[]), ComponentStatus::Down);
+    }
+}
@@ -0,0 +1,748 @@
+//! IncidentCase - インシデントケースと改ざん検知可能な証拠エクスポート
+//!
+//! ## 概要
+//! - detection_logs のイベントをケースに保全する。保全時に検出画像を evidence_dir にコピーして
+//!   SHA-256 を記録し、detection_logs 行（is21_log 含む）とカメラメタデータのスナップショットを保存
+//!   （StorageManager の退避や保存期間による削除の影響を受けない）
+//! - エクスポートは SHA-256 マニフェストを is22 の LacisID に紐づく署名鍵で署名したパッケージ（package.rs）
+//! - 作成・保全・削除・エクスポート・検証の各操作は操作者・接続元とともに
+//!   ケースごとのハッシュチェーン（chain of custody）に記録し、パッケージにも同梱する
+//!
+//! 署名には AraneaRegister で登録済みの LacisID が必要。
+
+pub mod package;
+pub mod types;
+
+pub use package::{verify_package, DeviceKey, Manifest, PackageVerification, TrustAnchor};
+pub use types::*;
+
+use crate::aranea_register::{config_keys, validate_lacis_id};
+use crate::audit_log::mask_secrets;
+use crate::config_store::ConfigStore;
+use crate::detection_log_service::DetectionLogService;
+use crate::error::{Error, Result};
+use chrono::{SubsecRound, Utc};
+use serde::Serialize;
+use serde_json::{json, Value};
+use sqlx::mysql::MySqlRow;
+use sqlx::{MySqlPool, Row};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::fs;
+use tokio::sync::Mutex;
+
+/// エクスポートの検証結果（DB の記録との照合を含む）
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportVerification {
+    pub export: ExportRecord,
+    pub package: PackageVerification,
+    /// manifest.json がエクスポート時に記録したものと同一
+    pub manifest_matches_record: bool,
+    /// 署名鍵がこの is22 の現在の鍵
+    pub signed_by_this_device: bool,
+    pub valid: bool,
+}
+
+/// 保全済みイベント（エクスポート用の全データ）
+struct PreservedEvent {
+    event: CaseEvent,
+    event_json: String,
+    camera_json: Option<String>,
+}
+
+/// インシデントケースサービス
+pub struct IncidentCaseService {
+    pool: MySqlPool,
+    config_store: Arc<ConfigStore>,
+    detection_log: Arc<DetectionLogService>,
+    evidence_dir: PathBuf,
+    /// カストディ記録の直列化（ケースごとのチェーン末尾を読んでから追記する）
+    custody_lock: Mutex<()>,
+    device_key: Mutex<Option<Arc<DeviceKey>>>,
+}
+
+impl IncidentCaseService {
+    pub fn new(
+        pool: MySqlPool,
+        config_store: Arc<ConfigStore>,
+        detection_log: Arc<DetectionLogService>,
+        evidence_dir: PathBuf,
+    ) -> Self {
+        Self {
+            pool,
+            config_store,
+            detection_log,
+            evidence_dir,
+            custody_lock: Mutex::new(()),
+            device_key: Mutex::new(None),
+        }
+    }
+
+    fn case_dir(&self, case_id: u64) -> PathBuf {
+        self.evidence_dir.join("cases").join(case_id.to_string())
+    }
+
+    fn event_dir(&self, case_id: u64, log_id: u64) -> PathBuf {
+        self.case_dir(case_id).join("events").join(log_id.to_string())
+    }
+
+    fn exports_dir(&self) -> PathBuf {
+        self.evidence_dir.join("exports")
+    }
+
+    // ========================================
+    // Cases
+    // ========================================
+
+    const CASE_COLUMNS: &'static str = "c.case_id, c.title, c.description, c.status, c.created_by, \
+        c.created_at, c.updated_at, c.closed_at, \
+        (SELECT COUNT(*) FROM incident_case_events e WHERE e.case_id = c.case_id) AS event_count";
+
+    fn row_to_case(row: &MySqlRow) -> Result<IncidentCase> {
+        let status: String = row.try_get("status")?;
+        let event_count: i64 = row.try_get("event_count")?;
+        Ok(IncidentCase {
+            case_id: row.try_get("case_id")?,
+            title: row.try_get("title")?,
+            description: row.try_get("description")?,
+            status: CaseStatus::parse(&status).unwrap_or_default(),
+            created_by: row.try_get("created_by")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+            closed_at: row.try_get("closed_at")?,
+            event_count: event_count as u64,
+        })
+    }
+
+    pub async fn list_cases(&self, status: Option<CaseStatus>) -> Result<Vec<IncidentCase>> {
+        let rows = match status {
+            Some(status) => {
+                sqlx::query(&format!(
+                    "SELECT {} FROM incident_cases c WHERE c.status = ? ORDER BY c.case_id DESC",
+                    Self::CASE_COLUMNS
+                ))
+                .bind(status.as_str())
+                .fetch_all(&self.pool)
+                .await?
+            }
+            None => {
+                sqlx::query(&format!(
+                    "SELECT {} FROM incident_cases c ORDER BY c.case_id DESC",
+                    Self::CASE_COLUMNS
+                ))
+                .fetch_all(&self.pool)
+                .await?
+            }
+        };
+        rows.iter().map(Self::row_to_case).collect()
+    }
+
+    pub async fn get_case(&self, case_id: u64) -> Result<IncidentCase> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM incident_cases c WHERE c.case_id = ?",
+            Self::CASE_COLUMNS
+        ))
+        .bind(case_id)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| Error::NotFound(format!("Case {} not found", case_id)))?;
+        Self::row_to_case(&row)
+    }
+
+    async fn get_open_case(&self, case_id: u64) -> Result<IncidentCase> {
+        let case = self.get_case(case_id).await?;
+        if case.status == CaseStatus::Closed {
+            return Err(Error::Conflict(format!(
+                "Case {} is closed; reopen it to change its events",
+                case_id
+            )));
+        }
+        Ok(case)
+    }
+
+    pub async fn create_case(&self, request: CreateCaseRequest, actor: &CustodyActor) -> Result<IncidentCase> {
+        validate_title(&request.title)?;
+        let now = Utc::now();
+        let result = sqlx::query(
+            "INSERT INTO incident_cases (title, description, status, created_by, created_at, updated_at) \
+             VALUES (?, ?, 'open', ?, ?, ?)",
+        )
+        .bind(request.title.trim())
+        .bind(request.description.as_deref().map(str::trim).filter(|d| !d.is_empty()))
+        .bind(&actor.name)
+        .bind(now)
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+        let case_id = result.last_insert_id();
+        self.append_custody(
+            case_id,
+            CustodyAction::Created,
+            actor,
+            Some(json!({ "title": request.title.trim() })),
+        )
+        .await?;
+        self.get_case(case_id).await
+    }
+
+    pub async fn update_case(
+        &self,
+        case_id: u64,
+        request: UpdateCaseRequest,
+        actor: &CustodyActor,
+    ) -> Result<IncidentCase> {
+        let current = self.get_case(case_id).await?;
+        if let Some(title) = &request.title {
+            validate_title(title)?;
+        }
+        let title = request.title.as_deref().map(str::trim).unwrap_or(&current.title);
+        let description = match &request.description {
+            Some(d) => Some(d.trim()).filter(|d| !d.is_empty()),
+            None => current.description.as_deref(),
+        };
+        let status = request.status.unwrap_or(current.status);
+        let now = Utc::now();
+        let closed_at = match (current.status, status) {
+            (CaseStatus::Open, CaseStatus::Closed) => Some(now),
+            (_, CaseStatus::Closed) => current.closed_at,
+            (_, CaseStatus::Open) => None,
+        };
+
+        sqlx::query(
+            "UPDATE incident_cases SET title = ?, description = ?, status = ?, closed_at = ?, updated_at = ? \
+             WHERE case_id = ?",
+        )
+        .bind(title)
+        .bind(description)
+        .bind(status.as_str())
+        .bind(closed_at)
+        .bind(now)
+        .bind(case_id)
+        .execute(&self.pool)
+        .await?;
+
+        if title != current.title || description != current.description.as_deref() {
+            self.append_custody(
+                case_id,
+                CustodyAction::Updated,
+                actor,
+                Some(json!({ "title": title, "description": description })),
+            )
+            .await?;
+        }
+        let status_action = match (current.status, status) {
+            (CaseStatus::Open, CaseStatus::Closed) => Some(CustodyAction::Closed),
+            (CaseStatus::Closed, CaseStatus::Open) => Some(CustodyAction::Reopened),
+            _ => None,
+        };
+        if let Some(action) = status_action {
+            self.append_custody(case_id, action, actor, None).await?;
+        }
+        self.get_case(case_id).await
+    }
+
+    // ========================================
+    // Events
+    // ========================================
+
+    const EVENT_COLUMNS: &'static str = "case_id, log_id, camera_id, captured_at, primary_event, severity, \
+        image_file, image_sha256, image_size, added_by, added_at";
+
+    fn row_to_event(row: &MySqlRow) -> Result<CaseEvent> {
+        Ok(CaseEvent {
+            case_id: row.try_get("case_id")?,
+            log_id: row.try_get("log_id")?,
+            camera_id: row.try_get("camera_id")?,
+            captured_at: row.try_get("captured_at")?,
+            primary_event: row.try_get("primary_event")?,
+            severity: row.try_get("severity")?,
+            image_file: row.try_get("image_file")?,
+            image_sha256: row.try_get("image_sha256")?,
+            image_size: row.try_get("image_size")?,
+            added_by: row.try_get("added_by")?,
+            added_at: row.try_get("added_at")?,
+        })
+    }
+
+    pub async fn list_events(&self, case_id: u64) -> Result<Vec<CaseEvent>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM incident_case_events WHERE case_id = ? ORDER BY captured_at, log_id",
+            Self::EVENT_COLUMNS
+        ))
+        .bind(case_id)
+        .fetch_all(&self.pool)
+        .await?;
+        rows.iter().map(Self::row_to_event).collect()
+    }
+
+    async fn preserved_events(&self, case_id: u64) -> Result<Vec<PreservedEvent>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {}, event_json, camera_json FROM incident_case_events \
+             WHERE case_id = ? ORDER BY captured_at, log_id",
+            Self::EVENT_COLUMNS
+        ))
+        .bind(case_id)
+        .fetch_all(&self.pool)
+        .await?;
+        rows.iter()
+            .map(|row| {
+                Ok(PreservedEvent {
+                    event: Self::row_to_event(row)?,
+                    event_json: row.try_get("event_json")?,
+                    camera_json: row.try_get("camera_json")?,
+                })
+            })
+            .collect()
+    }
+
+    /// detection_logs のイベントを保全（既にケースにあるものはスキップ）
+    pub async fn add_events(&self, case_id: u64, log_ids: &[u64], actor: &CustodyActor) -> Result<Vec<CaseEvent>> {
+        self.get_open_case(case_id).await?;
+        if log_ids.is_empty() {
+            return Err(Error::Validation("log_ids must not be empty".to_string()));
+        }
+        let existing: Vec<u64> = self.list_events(case_id).await?.iter().map(|e| e.log_id).collect();
+        if existing.len() + log_ids.len() > MAX_CASE_EVENTS {
+            return Err(Error::Validation(format!(
+                "a case can hold at most {} events",
+                MAX_CASE_EVENTS
+            )));
+        }
+
+        // 全件の存在を先に確認（途中で NotFound になり一部だけ保全されるのを避ける）
+        let mut logs = Vec::new();
+        for &log_id in log_ids {
+            if existing.contains(&log_id) || logs.iter().any(|(id, _)| *id == log_id) {
+                continue;
+            }
+            let log = self
+                .detection_log
+                .get_by_id(log_id)
+                .await?
+                .ok_or_else(|| Error::NotFound(format!("Detection log {} not found", log_id)))?;
+            logs.push((log_id, log));
+        }
+
+        for (log_id, log) in logs {
+            let (image_file, image_sha256, image_size) =
+                self.preserve_image(case_id, log_id, &log.image_path_local).await?;
+            let camera_json = match self.config_store.service().get_camera(&log.camera_id).await? {
+                Some(camera) => {
+                    let mut value = serde_json::to_value(&camera)?;
+                    mask_secrets(&mut value);
+                    Some(value.to_string())
+                }
+                None => None,
+            };
+            sqlx::query(
+                "INSERT INTO incident_case_events \
+                 (case_id, log_id, camera_id, captured_at, primary_event, severity, event_json, camera_json, \
+                  image_file, image_sha256, image_size, added_by, added_at) \
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(case_id)
+            .bind(log_id)
+            .bind(&log.camera_id)
+            .bind(log.captured_at)
+            .bind(&log.primary_event)
+            .bind(log.severity)
+            .bind(serde_json::to_string(&log)?)
+            .bind(&camera_json)
+            .bind(&image_file)
+            .bind(&image_sha256)
+            .bind(image_size)
+            .bind(&actor.name)
+            .bind(Utc::now())
+            .execute(&self.pool)
+            .await?;
+            self.append_custody(
+                case_id,
+                CustodyAction::EventAdded,
+                actor,
+                Some(json!({
+                    "log_id": log_id,
+                    "camera_id": log.camera_id,
+                    "source_image": Some(&log.image_path_local).filter(|p| !p.is_empty()),
+                    "image_sha256": image_sha256,
+                })),
+            )
+            .await?;
+        }
+        self.list_events(case_id).await
+    }
+
+    /// 検出画像をケースディレクトリへコピーし (file, sha256, size) を返す（元画像がなければ None）
+    async fn preserve_image(
+        &self,
+        case_id: u64,
+        log_id: u64,
+        source: &str,
+    ) -> Result<(Option<String>, Option<String>, Option<u64>)> {
+        let source = Path::new(source);
+        let Some(file_name) = source.file_name().filter(|_| !source.as_os_str().is_empty()) else {
+            return Ok((None, None, None));
+        };
+        let bytes = match fs::read(source).await {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                tracing::warn!(log_id, path = %source.display(), "Detection image missing; preserving event without image");
+                return Ok((None, None, None));
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let dir = self.event_dir(case_id, log_id);
+        fs::create_dir_all(&dir).await?;
+        fs::write(dir.join(file_name), &bytes).await?;
+        Ok((
+            Some(file_name.to_string_lossy().into_owned()),
+            Some(sha256_hex(&bytes)),
+            Some(bytes.len() as u64),
+        ))
+    }
+
+    pub async fn remove_event(&self, case_id: u64, log_id: u64, actor: &CustodyActor) -> Result<()> {
+        self.get_open_case(case_id).await?;
+        let result = sqlx::query("DELETE FROM incident_case_events WHERE case_id = ? AND log_id = ?")
+            .bind(case_id)
+            .bind(log_id)
+            .execute(&self.pool)
+            .await?;
+        if result.rows_affected() == 0 {
+            return Err(Error::NotFound(format!("Event {} is not in case {}", log_id, case_id)));
+        }
+        let dir = self.event_dir(case_id, log_id);
+        if let Err(e) = fs::remove_dir_all(&dir).await {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                tracing::warn!(path = %dir.display(), error = %e, "Failed to remove preserved event files");
+            }
+        }
+        self.append_custody(case_id, CustodyAction::EventRemoved, actor, Some(json!({ "log_id": log_id })))
+            .await?;
+        Ok(())
+    }
+
+    // ========================================
+    // Chain of custody
+    // ========================================
+
+    fn row_to_custody(row: &MySqlRow) -> Result<CustodyEntry> {
+        let details: Option<String> = row.try_get("details")?;
+        Ok(CustodyEntry {
+            custody_id: row.try_get("custody_id")?,
+            case_id: row.try_get("case_id")?,
+            occurred_at: row.try_get("occurred_at")?,
+            action: row.try_get("action")?,
+            actor: row.try_get("actor")?,
+            source_ip: row.try_get("source_ip")?,
+            details: details.map(|d| serde_json::from_str(&d)).transpose()?,
+            prev_hash: row.try_get("prev_hash")?,
+            entry_hash: row.try_get("entry_hash")?,
+        })
+    }
+
+    pub async fn custody(&self, case_id: u64) -> Result<Vec<CustodyEntry>> {
+        let rows = sqlx::query(
+            "SELECT custody_id, case_id, occurred_at, action, actor, source_ip, details, prev_hash, entry_hash \
+             FROM incident_custody_log WHERE case_id = ? ORDER BY custody_id",
+        )
+        .bind(case_id)
+        .fetch_all(&self.pool)
+        .await?;
+        rows.iter().map(Self::row_to_custody).collect()
+    }
+
+    async fn append_custody(
+        &self,
+        case_id: u64,
+        action: CustodyAction,
+        actor: &CustodyActor,
+        details: Option<Value>,
+    ) -> Result<CustodyEntry> {
+        let _guard = self.custody_lock.lock().await;
+        let prev_hash: Option<String> = sqlx::query_scalar(
+            "SELECT entry_hash FROM incident_custody_log WHERE case_id = ? ORDER BY custody_id DESC LIMIT 1",
+        )
+        .bind(case_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        let prev_hash = prev_hash.unwrap_or_else(|| GENESIS_HASH.to_string());
+
+        let mut entry = CustodyEntry {
+            custody_id: 0,
+            case_id,
+            occurred_at: Utc::now().trunc_subsecs(3),
+            action: action.as_str().to_string(),
+            actor: actor.name.clone(),
+            source_ip: actor.source_ip.clone(),
+            details,
+            prev_hash: prev_hash.clone(),
+            entry_hash: String::new(),
+        };
+        entry.entry_hash = entry.compute_hash(&prev_hash);
+        let result = sqlx::query(
+            "INSERT INTO incident_custody_log \
+             (case_id, occurred_at, action, actor, source_ip, details, prev_hash, entry_hash) \
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(case_id)
+        .bind(entry.occurred_at)
+        .bind(&entry.action)
+        .bind(&entry.actor)
+        .bind(&entry.source_ip)
+        .bind(entry.details.as_ref().map(|d| d.to_string()))
+        .bind(&entry.prev_hash)
+        .bind(&entry.entry_hash)
+        .execute(&self.pool)
+        .await?;
+        entry.custody_id = result.last_insert_id();
+        Ok(entry)
+    }
+
+    // ========================================
+    // Device key / export / verification
+    // ========================================
+
+    /// LacisID に紐づく署名鍵（初回は生成）
+    pub async fn device_key(&self) -> Result<Arc<DeviceKey>> {
+        let lacis_id = self
+            .config_store
+            .service()
+            .get_setting(config_keys::LACIS_ID)
+            .await?
+            .and_then(|v| v.as_str().map(String::from))
+            .filter(|id| validate_lacis_id(id))
+            .ok_or_else(|| {
+                Error::Validation(
+                    "is22 is not registered (LacisID is required to sign evidence packages)".to_string(),
+                )
+            })?;
+
+        let mut cached = self.device_key.lock().await;
+        if let Some(key) = cached.as_ref().filter(|k| k.lacis_id() == lacis_id) {
+            return Ok(key.clone());
+        }
+        let key_dir = self.evidence_dir.join("keys");
+        let key = tokio::task::spawn_blocking(move || DeviceKey::load_or_create(&key_dir, &lacis_id))
+            .await
+            .map_err(|e| Error::Internal(format!("device key task failed: {}", e)))??;
+        let key = Arc::new(key);
+        *cached = Some(key.clone());
+        Ok(key)
+    }
+
+    const EXPORT_COLUMNS: &'static str = "export_id, case_id, package_id, package_path, manifest_sha256, \
+        lacis_id, key_fingerprint, file_count, exported_by, exported_at";
+
+    fn row_to_export(row: &MySqlRow) -> Result<ExportRecord> {
+        Ok(ExportRecord {
+            export_id: row.try_get("export_id")?,
+            case_id: row.try_get("case_id")?,
+            package_id: row.try_get("package_id")?,
+            package_path: row.try_get("package_path")?,
+            manifest_sha256: row.try_get("manifest_sha256")?,
+            lacis_id: row.try_get("lacis_id")?,
+            key_fingerprint: row.try_get("key_fingerprint")?,
+            file_count: row.try_get("file_count")?,
+            exported_by: row.try_get("exported_by")?,
+            exported_at: row.try_get("exported_at")?,
+        })
+    }
+
+    pub async fn list_exports(&self, case_id: u64) -> Result<Vec<ExportRecord>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM incident_exports WHERE case_id = ? ORDER BY export_id DESC",
+            Self::EXPORT_COLUMNS
+        ))
+        .bind(case_id)
+        .fetch_all(&self.pool)
+        .await?;
+        rows.iter().map(Self::row_to_export).collect()
+    }
+
+    pub async fn get_export(&self, case_id: u64, export_id: u64) -> Result<ExportRecord> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM incident_exports WHERE case_id = ? AND export_id = ?",
+            Self::EXPORT_COLUMNS
+        ))
+        .bind(case_id)
+        .bind(export_id)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| Error::NotFound(format!("Export {} not found in case {}", export_id, case_id)))?;
+        Self::row_to_export(&row)
+    }
+
+    /// 証拠パッケージを生成（evidence_dir/exports/<package_id>/）
+    pub async fn export(&self, case_id: u64, actor: &CustodyActor) -> Result<ExportRecord> {
+        let case = self.get_case(case_id).await?;
+        let events = self.preserved_events(case_id).await?;
+        if events.is_empty() {
+            return Err(Error::Validation(format!("Case {} has no events to export", case_id)));
+        }
+        let key = self.device_key().await?;
+
+        let now = Utc::now();
+        let package_id = format!("case{}-{}", case_id, now.format("%Y%m%dT%H%M%S%3fZ"));
+        let package_dir = self.exports_dir().join(&package_id);
+        fs::create_dir_all(&package_dir).await?;
+
+        match self
+            .write_package(&case, &events, &package_dir, &package_id, actor, key)
+            .await
+        {
+            Ok((manifest, manifest_sha256)) => {
+                let result = sqlx::query(
+                    "INSERT INTO incident_exports \
+                     (case_id, package_id, package_path, manifest_sha256, lacis_id, key_fingerprint, \
+                      file_count, exported_by, exported_at) \
+                     VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                )
+                .bind(case_id)
+                .bind(&package_id)
+                .bind(package_dir.to_string_lossy().as_ref())
+                .bind(&manifest_sha256)
+                .bind(&manifest.lacis_id)
+                .bind(&manifest.key_fingerprint)
+                .bind(manifest.files.len() as u32)
+                .bind(&actor.name)
+                .bind(now)
+                .execute(&self.pool)
+                .await?;
+                self.get_export(case_id, result.last_insert_id()).await
+            }
+            Err(e) => {
+                let _ = fs::remove_dir_all(&package_dir).await;
+                Err(e)
+            }
+        }
+    }
+
+    async fn write_package(
+        &self,
+        case: &IncidentCase,
+        events: &[PreservedEvent],
+        package_dir: &Path,
+        package_id: &str,
+        actor: &CustodyActor,
+        key: Arc<DeviceKey>,
+    ) -> Result<(Manifest, String)> {
+        fs::write(package_dir.join(package::CASE_FILE), serde_json::to_vec_pretty(case)?).await?;
+
+        for preserved in events {
+            let event = &preserved.event;
+            let dir = package_dir.join("events").join(event.log_id.to_string());
+            fs::create_dir_all(&dir).await?;
+
+            let mut event_json: Value = serde_json::from_str(&preserved.event_json)?;
+            let is21_log = event_json
+                .as_object_mut()
+                .and_then(|o| o.remove("is21_log"))
+                .unwrap_or(Value::Null);
+            event_json["evidence"] = json!({
+                "image_file": event.image_file,
+                "image_sha256": event.image_sha256,
+                "image_size": event.image_size,
+                "added_by": event.added_by,
+                "added_at": event.added_at,
+            });
+            fs::write(dir.join("event.json"), serde_json::to_vec_pretty(&event_json)?).await?;
+            fs::write(dir.join("is21_log.json"), serde_json::to_vec_pretty(&is21_log)?).await?;
+            if let Some(camera_json) = &preserved.camera_json {
+                let camera: Value = serde_json::from_str(camera_json)?;
+                fs::write(dir.join("camera.json"), serde_json::to_vec_pretty(&camera)?).await?;
+            }
+
+            // 保全時のハッシュと一致しない画像はエクスポートしない
+            if let (Some(file), Some(expected)) = (&event.image_file, &event.image_sha256) {
+                let bytes = fs::read(self.event_dir(case.case_id, event.log_id).join(file)).await?;
+                if &sha256_hex(&bytes) != expected {
+                    return Err(Error::Conflict(format!(
+                        "preserved image of event {} no longer matches the SHA-256 recorded when it was added",
+                        event.log_id
+                    )));
+                }
+                fs::write(dir.join(file), &bytes).await?;
+            }
+        }
+
+        self.append_custody(
+            case.case_id,
+            CustodyAction::Exported,
+            actor,
+            Some(json!({
+                "package_id": package_id,
+                "event_count": events.len(),
+                "lacis_id": key.lacis_id(),
+                "key_fingerprint": key.fingerprint(),
+            })),
+        )
+        .await?;
+        let custody = self.custody(case.case_id).await?;
+        fs::write(package_dir.join(package::CUSTODY_FILE), serde_json::to_vec_pretty(&custody)?).await?;
+
+        let manifest = Manifest {
+            format_version: package::FORMAT_VERSION,
+            package_id: package_id.to_string(),
+            case_id: case.case_id,
+            case_title: case.title.clone(),
+            lacis_id: String::new(),
+            key_fingerprint: String::new(),
+            signature_algorithm: String::new(),
+            generated_at: Utc::now(),
+            generated_by: actor.name.clone(),
+            event_count: events.len(),
+            files: Vec::new(),
+        };
+        let dir = package_dir.to_path_buf();
+        tokio::task::spawn_blocking(move || package::seal_package(&dir, manifest, &key))
+            .await
+            .map_err(|e| Error::Internal(format!("package sealing task failed: {}", e)))?
+    }
+
+    /// エクスポート済みパッケージを再検証し、結果をカストディに記録
+    pub async fn verify_export(
+        &self,
+        case_id: u64,
+        export_id: u64,
+        actor: &CustodyActor,
+    ) -> Result<ExportVerification> {
+        let export = self.get_export(case_id, export_id).await?;
+        let dir = PathBuf::from(&export.package_path);
+        // 信頼起点はエクスポート時に DB へ記録したフィンガープリント（パッケージ同梱の鍵は信用しない）
+        let trust = TrustAnchor::Fingerprint(export.key_fingerprint.clone());
+        let package = tokio::task::spawn_blocking(move || verify_package(&dir, Some(&trust)))
+            .await
+            .map_err(|e| Error::Internal(format!("package verification task failed: {}", e)))?;
+
+        let manifest_matches_record = package.manifest_sha256.as_deref() == Some(export.manifest_sha256.as_str())
+            && package.key_fingerprint.as_deref() == Some(export.key_fingerprint.as_str());
+        let signed_by_this_device = match self.device_key().await {
+            Ok(key) => package.key_fingerprint.as_deref() == Some(key.fingerprint()),
+            Err(_) => false,
+        };
+        let valid = package.valid && manifest_matches_record;
+
+        self.append_custody(
+            case_id,
+            CustodyAction::Verified,
+            actor,
+            Some(json!({
+                "export_id": export_id,
+                "package_id": export.package_id,
+                "valid": valid,
+                "modified": package.modified,
+                "missing": package.missing,
+                "unexpected": package.unexpected,
+            })),
+        )
+        .await?;
+
+        Ok(ExportVerification {
+            export,
+            package,
+            manifest_matches_record,
+            signed_by_this_device,
+            valid,
+        })
+    }
+}
@@ -0,0 +1,514 @@
+//! Evidence package - SHA-256 manifest, device-key signature and verification
+//!
+//! ## レイアウト
+//! ```text
+//! <package_id>/
+//!   manifest.json                 全ファイルの SHA-256・サイズ + ケース・LacisID・鍵フィンガープリント
+//!   manifest.sig                  manifest.json の RSASSA-PKCS1-v1_5 (SHA-256) 署名（バイナリ）
+//!   device_key.pub.pem            署名鍵の公開鍵（SPKI PEM）
+//!   case.json                     ケース情報
+//!   custody.json                  管理履歴（ハッシュチェーン、このエクスポートを含む）
+//!   events/<log_id>/event.json    保全時点の detection_logs 行（is21_log を除く）
+//!   events/<log_id>/is21_log.json IS21 の生レスポンス
+//!   events/<log_id>/camera.json   保全時点のカメラメタデータ（秘匿情報マスク済み）
+//!   events/<log_id>/<image>       保全した検出画像
+//! ```
+//!
+//! 検証は DB なしでファイルのみで行える（`camserver evidence verify <dir> --fingerprint <sha256>`）。
+//! 同梱の公開鍵は誰でも差し替えられるため、署名鍵の正当性はパッケージ外で入手した
+//! 信頼起点（`GET /api/incidents/device-key` の公開鍵・フィンガープリント）と照合する。
+//! 署名は `openssl dgst -sha256 -verify <受領済み公開鍵> -signature manifest.sig manifest.json`
+//! でも確認できる。
+
+use super::types::{sha256_hex, verify_custody, CustodyEntry, DeviceKeyInfo};
+use crate::error::{Error, Result};
+use chrono::{DateTime, Utc};
+use rsa::pkcs1v15::{Signature, SigningKey, VerifyingKey};
+use rsa::pkcs8::{DecodePrivateKey, DecodePublicKey, EncodePrivateKey, EncodePublicKey, LineEnding};
+use rsa::signature::{SignatureEncoding, Signer, Verifier};
+use rsa::{RsaPrivateKey, RsaPublicKey};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const FORMAT_VERSION: u32 = 1;
+pub const MANIFEST_FILE: &str = "manifest.json";
+pub const SIGNATURE_FILE: &str = "manifest.sig";
+pub const PUBLIC_KEY_FILE: &str = "device_key.pub.pem";
+pub const CASE_FILE: &str = "case.json";
+pub const CUSTODY_FILE: &str = "custody.json";
+pub const SIGNATURE_ALGORITHM: &str = "RSASSA-PKCS1-v1_5-SHA256";
+/// 署名鍵の鍵長
+pub const DEVICE_KEY_BITS: usize = 2048;
+
+/// manifest.json のファイル項目
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManifestFile {
+    /// パッケージ内の相対パス（'/' 区切り）
+    pub path: String,
+    pub sha256: String,
+    pub size: u64,
+}
+
+/// manifest.json
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Manifest {
+    pub format_version: u32,
+    pub package_id: String,
+    pub case_id: u64,
+    pub case_title: String,
+    /// 署名した is22 の LacisID
+    pub lacis_id: String,
+    /// 署名鍵（公開鍵 DER）の SHA-256
+    pub key_fingerprint: String,
+    pub signature_algorithm: String,
+    pub generated_at: DateTime<Utc>,
+    pub generated_by: String,
+    pub event_count: usize,
+    pub files: Vec<ManifestFile>,
+}
+
+/// is22 の署名鍵（LacisID ごとに evidence_dir/keys/ に PKCS#8 PEM で保存）
+pub struct DeviceKey {
+    lacis_id: String,
+    signing_key: SigningKey<Sha256>,
+    public_key_pem: String,
+    fingerprint: String,
+}
+
+impl DeviceKey {
+    pub fn key_path(key_dir: &Path, lacis_id: &str) -> PathBuf {
+        key_dir.join(format!("device_{}.pem", lacis_id))
+    }
+
+    /// 既存の鍵を読み込み、なければ生成して保存（鍵生成は重いので spawn_blocking から呼ぶ）
+    pub fn load_or_create(key_dir: &Path, lacis_id: &str) -> Result<Self> {
+        let path = Self::key_path(key_dir, lacis_id);
+        if path.exists() {
+            let pem = fs::read_to_string(&path)?;
+            let private_key = RsaPrivateKey::from_pkcs8_pem(&pem)
+                .map_err(|e| Error::Internal(format!("invalid device key {}: {}", path.display(), e)))?;
+            return Self::from_private_key(lacis_id, private_key);
+        }
+
+        let key = Self::generate(lacis_id, DEVICE_KEY_BITS)?;
+        fs::create_dir_all(key_dir)?;
+        let pem = key
+            .signing_key
+            .as_ref()
+            .to_pkcs8_pem(LineEnding::LF)
+            .map_err(|e| Error::Internal(format!("failed to encode device key: {}", e)))?;
+        write_private_key(&path, pem.as_bytes())?;
+        tracing::info!(lacis_id = %lacis_id, fingerprint = %key.fingerprint, "Evidence signing key generated");
+        Ok(key)
+    }
+
+    pub fn generate(lacis_id: &str, bits: usize) -> Result<Self> {
+        let private_key = RsaPrivateKey::new(&mut rand::rngs::OsRng, bits)
+            .map_err(|e| Error::Internal(format!("failed to generate device key: {}", e)))?;
+        Self::from_private_key(lacis_id, private_key)
+    }
+
+    fn from_private_key(lacis_id: &str, private_key: RsaPrivateKey) -> Result<Self> {
+        let public_key = private_key.to_public_key();
+        let public_key_pem = public_key
+            .to_public_key_pem(LineEnding::LF)
+            .map_err(|e| Error::Internal(format!("failed to encode public key: {}", e)))?;
+        Ok(Self {
+            lacis_id: lacis_id.to_string(),
+            fingerprint: public_key_fingerprint(&public_key)?,
+            signing_key: SigningKey::<Sha256>::new(private_key),
+            public_key_pem,
+        })
+    }
+
+    pub fn lacis_id(&self) -> &str {
+        &self.lacis_id
+    }
+
+    pub fn fingerprint(&self) -> &str {
+        &self.fingerprint
+    }
+
+    pub fn sign(&self, data: &[u8]) -> Vec<u8> {
+        self.signing_key.sign(data).to_vec()
+    }
+
+    pub fn info(&self) -> DeviceKeyInfo {
+        DeviceKeyInfo {
+            lacis_id: self.lacis_id.clone(),
+            key_fingerprint: self.fingerprint.clone(),
+            public_key_pem: self.public_key_pem.clone(),
+            signature_algorithm: SIGNATURE_ALGORITHM,
+        }
+    }
+}
+
+/// 秘密鍵を所有者のみ読み書き可能なファイルとして新規作成（作成時点から 0600）
+fn write_private_key(path: &Path, pem: &[u8]) -> Result<()> {
+    use std::io::Write;
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    file.write_all(pem)?;
+    file.sync_all()?;
+    Ok(())
+}
+
+/// 公開鍵（SPKI DER）の SHA-256
+pub fn public_key_fingerprint(public_key: &RsaPublicKey) -> Result<String> {
+    let der = public_key
+        .to_public_key_der()
+        .map_err(|e| Error::Internal(format!("failed to encode public key: {}", e)))?;
+    Ok(sha256_hex(der.as_bytes()))
+}
+
+/// 公開鍵 PEM で署名を検証
+pub fn verify_signature(public_key_pem: &str, data: &[u8], signature: &[u8]) -> Result<bool> {
+    let public_key = RsaPublicKey::from_public_key_pem(public_key_pem)
+        .map_err(|e| Error::Validation(format!("invalid public key: {}", e)))?;
+    let Ok(signature) = Signature::try_from(signature) else {
+        return Ok(false);
+    };
+    Ok(VerifyingKey::<Sha256>::new(public_key)
+        .verify(data, &signature)
+        .is_ok())
+}
+
+/// 検証の信頼起点（受領者がパッケージ外で入手した is22 の署名鍵）
+#[derive(Debug, Clone)]
+pub enum TrustAnchor {
+    /// 公開鍵（SPKI DER）の SHA-256（device-key API の key_fingerprint）
+    Fingerprint(String),
+    /// 公開鍵 PEM（device-key API の public_key_pem）
+    PublicKeyPem(String),
+}
+
+impl TrustAnchor {
+    pub fn fingerprint(&self) -> Result<String> {
+        match self {
+            Self::Fingerprint(fingerprint) => {
+                let fingerprint = fingerprint.trim().replace(':', "").to_ascii_lowercase();
+                if fingerprint.len() != 64 || !fingerprint.chars().all(|c| c.is_ascii_hexdigit()) {
+                    return Err(Error::Validation(
+                        "fingerprint must be a SHA-256 hex string".to_string(),
+                    ));
+                }
+                Ok(fingerprint)
+            }
+            Self::PublicKeyPem(pem) => {
+                let public_key = RsaPublicKey::from_public_key_pem(pem)
+                    .map_err(|e| Error::Validation(format!("invalid trusted public key: {}", e)))?;
+                public_key_fingerprint(&public_key)
+            }
+        }
+    }
+}
+
+/// パッケージ内の全ファイル（manifest.json / manifest.sig を除く）をパス順に列挙・ハッシュ
+pub fn collect_files(package_dir: &Path) -> Result<Vec<ManifestFile>> {
+    let mut files = Vec::new();
+    let mut pending = vec![package_dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                pending.push(path);
+                continue;
+            }
+            let relative = path
+                .strip_prefix(package_dir)
+                .map_err(|e| Error::Internal(e.to_string()))?
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                .collect::<Vec<_>>()
+                .join("/");
+            if relative == MANIFEST_FILE || relative == SIGNATURE_FILE {
+                continue;
+            }
+            let bytes = fs::read(&path)?;
+            files.push(ManifestFile {
+                path: relative,
+                sha256: sha256_hex(&bytes),
+                size: bytes.len() as u64,
+            });
+        }
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+/// 公開鍵を配置し、manifest.json を生成して署名する
+///
+/// `manifest.files` は上書きされる。戻り値は (manifest, manifest.json の SHA-256)
+pub fn seal_package(package_dir: &Path, mut manifest: Manifest, key: &DeviceKey) -> Result<(Manifest, String)> {
+    fs::write(package_dir.join(PUBLIC_KEY_FILE), key.public_key_pem.as_bytes())?;
+    manifest.lacis_id = key.lacis_id.clone();
+    manifest.key_fingerprint = key.fingerprint.clone();
+    manifest.signature_algorithm = SIGNATURE_ALGORITHM.to_string();
+    manifest.files = collect_files(package_dir)?;
+
+    let bytes = serde_json::to_vec_pretty(&manifest)?;
+    fs::write(package_dir.join(MANIFEST_FILE), &bytes)?;
+    fs::write(package_dir.join(SIGNATURE_FILE), key.sign(&bytes))?;
+    Ok((manifest, sha256_hex(&bytes)))
+}
+
+/// パッケージ検証結果
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PackageVerification {
+    pub package: String,
+    pub package_id: Option<String>,
+    pub case_id: Option<u64>,
+    pub lacis_id: Option<String>,
+    pub key_fingerprint: Option<String>,
+    pub manifest_sha256: Option<String>,
+    /// manifest.sig が同梱公開鍵で検証できた
+    pub signature_valid: bool,
+    /// 同梱公開鍵のフィンガープリントが manifest の記載と一致
+    pub key_fingerprint_matches: bool,
+    /// 信頼起点として与えられたフィンガープリント
+    pub trusted_fingerprint: Option<String>,
+    /// 同梱公開鍵が信頼起点と一致（信頼起点なしは常に false）
+    pub key_trusted: bool,
+    pub files_checked: usize,
+    /// 内容が manifest と異なる
+    pub modified: Vec<String>,
+    /// manifest にあるが存在しない
+    pub missing: Vec<String>,
+    /// manifest にないファイル
+    pub unexpected: Vec<String>,
+    pub custody_chain_valid: bool,
+    pub errors: Vec<String>,
+    pub valid: bool,
+}
+
+/// パッケージを再検証（DB 不要）
+///
+/// 同梱の公開鍵だけでは出所を証明できないため、`trust` がない場合は valid = false
+pub fn verify_package(package_dir: &Path, trust: Option<&TrustAnchor>) -> PackageVerification {
+    let mut report = PackageVerification {
+        package: package_dir.display().to_string(),
+        ..Default::default()
+    };
+    match trust.map(TrustAnchor::fingerprint).transpose() {
+        Ok(Some(fingerprint)) => report.trusted_fingerprint = Some(fingerprint),
+        Ok(None) => report.errors.push(
+            "no trust anchor given (trusted public key or fingerprint is required)".to_string(),
+        ),
+        Err(e) => report.errors.push(e.to_string()),
+    }
+    if let Err(e) = verify_into(package_dir, &mut report) {
+        report.errors.push(e.to_string());
+    }
+    report.valid = report.errors.is_empty()
+        && report.signature_valid
+        && report.key_fingerprint_matches
+        && report.key_trusted
+        && report.custody_chain_valid
+        && report.modified.is_empty()
+        && report.missing.is_empty()
+        && report.unexpected.is_empty();
+    report
+}
+
+fn verify_into(package_dir: &Path, report: &mut PackageVerification) -> Result<()> {
+    let manifest_bytes = fs::read(package_dir.join(MANIFEST_FILE))?;
+    report.manifest_sha256 = Some(sha256_hex(&manifest_bytes));
+    let manifest: Manifest = serde_json::from_slice(&manifest_bytes)?;
+    report.package_id = Some(manifest.package_id.clone());
+    report.case_id = Some(manifest.case_id);
+    report.lacis_id = Some(manifest.lacis_id.clone());
+    report.key_fingerprint = Some(manifest.key_fingerprint.clone());
+
+    let public_key_pem = fs::read_to_string(package_dir.join(PUBLIC_KEY_FILE))?;
+    let public_key = RsaPublicKey::from_public_key_pem(&public_key_pem)
+        .map_err(|e| Error::Validation(format!("invalid public key: {}", e)))?;
+    let bundled_fingerprint = public_key_fingerprint(&public_key)?;
+    report.key_fingerprint_matches = bundled_fingerprint == manifest.key_fingerprint;
+    report.key_trusted = report.trusted_fingerprint.as_deref() == Some(bundled_fingerprint.as_str());
+    let signature = fs::read(package_dir.join(SIGNATURE_FILE))?;
+    report.signature_valid = verify_signature(&public_key_pem, &manifest_bytes, &signature)?;
+
+    let actual = collect_files(package_dir)?;
+    for expected in &manifest.files {
+        match actual.iter().find(|f| f.path == expected.path) {
+            Some(file) if file.sha256 == expected.sha256 && file.size == expected.size => {}
+            Some(_) => report.modified.push(expected.path.clone()),
+            None => report.missing.push(expected.path.clone()),
+        }
+    }
+    report.unexpected = actual
+        .iter()
+        .filter(|f| !manifest.files.iter().any(|m| m.path == f.path))
+        .map(|f| f.path.clone())
+        .collect();
+    report.files_checked = manifest.files.len();
+
+    let custody: Vec<CustodyEntry> = serde_json::from_slice(&fs::read(package_dir.join(CUSTODY_FILE))?)?;
+    report.custody_chain_valid = match verify_custody(&custody) {
+        Ok(()) => true,
+        Err(custody_id) => {
+            report
+                .errors
+                .push(format!("custody chain broken at custody_id {}", custody_id));
+            false
+        }
+    };
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::incident_case::types::GENESIS_HASH;
+
+    fn write_package(dir: &Path, key: &DeviceKey) -> Manifest {
+        fs::create_dir_all(dir.join("events/42")).unwrap();
+        fs::write(dir.join(CASE_FILE), br#"{"case_id":1,"title":"Break-in"}"#).unwrap();
+        fs::write(dir.join("events/42/event.json"), br#"{"log_id":42}"#).unwrap();
+        fs::write(dir.join("events/42/20261019_cam.jpg"), [0xFF, 0xD8, 0xFF, 0xD9]).unwrap();
+        let mut custody = CustodyEntry {
+            custody_id: 1,
+            case_id: 1,
+            occurred_at: Utc::now(),
+            action: "exported".to_string(),
+            actor: "investigator".to_string(),
+            source_ip: None,
+            details: None,
+            prev_hash: GENESIS_HASH.to_string(),
+            entry_hash: String::new(),
+        };
+        custody.entry_hash = custody.compute_hash(GENESIS_HASH);
+        fs::write(dir.join(CUSTODY_FILE), serde_json::to_vec(&vec![custody]).unwrap()).unwrap();
+
+        let manifest = Manifest {
+            format_version: FORMAT_VERSION,
+            package_id: "case1-test".to_string(),
+            case_id: 1,
+            case_title: "Break-in".to_string(),
+            lacis_id: String::new(),
+            key_fingerprint: String::new(),
+            signature_algorithm: String::new(),
+            generated_at: Utc::now(),
+            generated_by: "investigator".to_string(),
+            event_count: 1,
+            files: Vec::new(),
+        };
+        seal_package(dir, manifest, key).unwrap().0
+    }
+
+    #[test]
+    fn test_seal_and_verify_package() {
+        let dir = std::env::temp_dir().join(format!("is22-evidence-{}", uuid::Uuid::new_v4()));
+        let key = DeviceKey::generate("3022AABBCCDDEEFF0000", 1024).unwrap();
+        let manifest = write_package(&dir, &key);
+        assert_eq!(manifest.lacis_id, "3022AABBCCDDEEFF0000");
+        assert_eq!(manifest.files.len(), 5);
+        assert!(manifest.files.iter().any(|f| f.path == PUBLIC_KEY_FILE));
+
+        let trust = TrustAnchor::Fingerprint(key.fingerprint().to_string());
+        let report = verify_package(&dir, Some(&trust));
+        assert!(report.valid, "{:?}", report);
+        assert!(report.key_trusted);
+        assert_eq!(report.files_checked, 5);
+
+        // 公開鍵 PEM でも同じ結果、信頼起点なしは無効
+        let pem = TrustAnchor::PublicKeyPem(key.info().public_key_pem);
+        assert!(verify_package(&dir, Some(&pem)).valid);
+        let report = verify_package(&dir, None);
+        assert!(report.signature_valid);
+        assert!(!report.key_trusted);
+        assert!(!report.valid);
+
+        // 画像の改ざん・ファイル追加
+        fs::write(dir.join("events/42/20261019_cam.jpg"), [0xFF, 0xD8, 0x00, 0xFF, 0xD9]).unwrap();
+        fs::write(dir.join("events/42/extra.txt"), b"x").unwrap();
+        let report = verify_package(&dir, Some(&trust));
+        assert!(!report.valid);
+        assert!(report.signature_valid);
+        assert_eq!(report.modified, vec!["events/42/20261019_cam.jpg"]);
+        assert_eq!(report.unexpected, vec!["events/42/extra.txt"]);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_manifest_signature_and_key_swap() {
+        let dir = std::env::temp_dir().join(format!("is22-evidence-{}", uuid::Uuid::new_v4()));
+        let key = DeviceKey::generate("3022AABBCCDDEEFF0000", 1024).unwrap();
+        let trust = TrustAnchor::Fingerprint(key.fingerprint().to_string());
+        write_package(&dir, &key);
+
+        // manifest の書き換え（ハッシュを合わせても署名で検出）
+        let path = dir.join(MANIFEST_FILE);
+        let text = fs::read_to_string(&path).unwrap().replace("Break-in", "Nothing");
+        fs::write(&path, text).unwrap();
+        let report = verify_package(&dir, Some(&trust));
+        assert!(!report.signature_valid);
+        assert!(!report.valid);
+
+        // 別の鍵で署名し直し公開鍵も差し替え → フィンガープリント不一致
+        let other = DeviceKey::generate("3022AABBCCDDEEFF0000", 1024).unwrap();
+        let bytes = fs::read(&path).unwrap();
+        fs::write(dir.join(SIGNATURE_FILE), other.sign(&bytes)).unwrap();
+        fs::write(dir.join(PUBLIC_KEY_FILE), other.info().public_key_pem).unwrap();
+        let report = verify_package(&dir, Some(&trust));
+        assert!(report.signature_valid);
+        assert!(!report.key_fingerprint_matches);
+        assert!(!report.valid);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_resealed_package_fails_against_trust_anchor() {
+        let dir = std::env::temp_dir().join(format!("is22-evidence-{}", uuid::Uuid::new_v4()));
+        let key = DeviceKey::generate("3022AABBCCDDEEFF0000", 1024).unwrap();
+        let manifest = write_package(&dir, &key);
+
+        // 証拠を改ざんし、別の鍵で manifest（フィンガープリント含む）を作り直して署名・公開鍵も差し替え
+        fs::write(dir.join("events/42/20261019_cam.jpg"), [0xFF, 0xD8, 0x00, 0xFF, 0xD9]).unwrap();
+        let forger = DeviceKey::generate("3022AABBCCDDEEFF0000", 1024).unwrap();
+        let resealed = seal_package(&dir, manifest, &forger).unwrap().0;
+        assert_eq!(resealed.key_fingerprint, forger.fingerprint());
+
+        // パッケージ単体では整合している
+        let report = verify_package(&dir, Some(&TrustAnchor::Fingerprint(forger.fingerprint().to_string())));
+        assert!(report.valid, "{:?}", report);
+
+        // 本来の is22 の鍵を信頼起点にすると無効
+        let report = verify_package(&dir, Some(&TrustAnchor::Fingerprint(key.fingerprint().to_string())));
+        assert!(report.signature_valid);
+        assert!(report.key_fingerprint_matches);
+        assert!(report.modified.is_empty());
+        assert!(!report.key_trusted);
+        assert!(!report.valid);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_private_key_created_owner_only() {
+        let dir = std::env::temp_dir().join(format!("is22-keys-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("device.pem");
+        write_private_key(&path, b"pem").unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+        }
+        // 既存の鍵は上書きしない
+        assert!(write_private_key(&path, b"other").is_err());
+        assert_eq!(fs::read(&path).unwrap(), b"pem");
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
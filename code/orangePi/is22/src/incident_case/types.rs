@@ -0,0 +1,276 @@
+//! Incident case types
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// カストディチェーン先頭の prev_hash
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// ケース状態
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CaseStatus {
+    #[default]
+    Open,
+    /// イベントの追加・削除不可（エクスポート・検証は可）
+    Closed,
+}
+
+impl CaseStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Open => "open",
+            Self::Closed => "closed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "open" => Some(Self::Open),
+            "closed" => Some(Self::Closed),
+            _ => None,
+        }
+    }
+}
+
+/// インシデントケース
+#[derive(Debug, Clone, Serialize)]
+pub struct IncidentCase {
+    pub case_id: u64,
+    pub title: String,
+    pub description: Option<String>,
+    pub status: CaseStatus,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub closed_at: Option<DateTime<Utc>>,
+    pub event_count: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateCaseRequest {
+    pub title: String,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// 省略したフィールドは変更しない
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct UpdateCaseRequest {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub status: Option<CaseStatus>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AddEventsRequest {
+    pub log_ids: Vec<u64>,
+}
+
+/// 1ケースに保全できるイベント数
+pub const MAX_CASE_EVENTS: usize = 1000;
+
+pub fn validate_title(title: &str) -> crate::Result<()> {
+    let title = title.trim();
+    if title.is_empty() || title.chars().count() > 255 {
+        return Err(crate::Error::Validation(
+            "title must be 1-255 characters".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// ケースに保全したイベント
+#[derive(Debug, Clone, Serialize)]
+pub struct CaseEvent {
+    pub case_id: u64,
+    pub log_id: u64,
+    pub camera_id: String,
+    pub captured_at: DateTime<Utc>,
+    pub primary_event: String,
+    pub severity: i32,
+    /// 保全した画像（None = 保全時に元画像がなかった）
+    pub image_file: Option<String>,
+    pub image_sha256: Option<String>,
+    pub image_size: Option<u64>,
+    pub added_by: String,
+    pub added_at: DateTime<Utc>,
+}
+
+/// 操作者（HTTP ヘッダ / 接続元から取得）
+#[derive(Debug, Clone, Default)]
+pub struct CustodyActor {
+    pub name: String,
+    pub source_ip: Option<String>,
+}
+
+/// カストディ操作種別
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CustodyAction {
+    Created,
+    Updated,
+    EventAdded,
+    EventRemoved,
+    Exported,
+    Verified,
+    Closed,
+    Reopened,
+}
+
+impl CustodyAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Created => "created",
+            Self::Updated => "updated",
+            Self::EventAdded => "event_added",
+            Self::EventRemoved => "event_removed",
+            Self::Exported => "exported",
+            Self::Verified => "verified",
+            Self::Closed => "closed",
+            Self::Reopened => "reopened",
+        }
+    }
+}
+
+/// 証拠の管理履歴（ケースごとのハッシュチェーン）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustodyEntry {
+    pub custody_id: u64,
+    pub case_id: u64,
+    pub occurred_at: DateTime<Utc>,
+    pub action: String,
+    pub actor: String,
+    pub source_ip: Option<String>,
+    pub details: Option<serde_json::Value>,
+    pub prev_hash: String,
+    pub entry_hash: String,
+}
+
+#[derive(Serialize)]
+struct CustodyHashInput<'a> {
+    case_id: u64,
+    occurred_at: String,
+    action: &'a str,
+    actor: &'a str,
+    source_ip: Option<&'a str>,
+    details: Option<String>,
+}
+
+impl CustodyEntry {
+    /// SHA-256(prev_hash + "\n" + 正規化した記録内容)（occurred_at はミリ秒精度）
+    pub fn compute_hash(&self, prev_hash: &str) -> String {
+        let input = CustodyHashInput {
+            case_id: self.case_id,
+            occurred_at: self.occurred_at.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string(),
+            action: &self.action,
+            actor: &self.actor,
+            source_ip: self.source_ip.as_deref(),
+            details: self.details.as_ref().map(|d| d.to_string()),
+        };
+        let canonical = serde_json::to_string(&input).unwrap_or_default();
+        sha256_hex(format!("{}\n{}", prev_hash, canonical).as_bytes())
+    }
+}
+
+/// ケースのカストディチェーンを検証（Err は最初に不整合のあった custody_id）
+pub fn verify_custody(entries: &[CustodyEntry]) -> std::result::Result<(), u64> {
+    let mut prev = GENESIS_HASH.to_string();
+    for entry in entries {
+        if entry.prev_hash != prev || entry.compute_hash(&prev) != entry.entry_hash {
+            return Err(entry.custody_id);
+        }
+        prev = entry.entry_hash.clone();
+    }
+    Ok(())
+}
+
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+/// エクスポート履歴
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportRecord {
+    pub export_id: u64,
+    pub case_id: u64,
+    pub package_id: String,
+    pub package_path: String,
+    pub manifest_sha256: String,
+    pub lacis_id: String,
+    pub key_fingerprint: String,
+    pub file_count: u32,
+    pub exported_by: String,
+    pub exported_at: DateTime<Utc>,
+}
+
+/// 署名鍵の公開情報（受領者への事前共有用）
+#[derive(Debug, Clone, Serialize)]
+pub struct DeviceKeyInfo {
+    pub lacis_id: String,
+    pub key_fingerprint: String,
+    pub public_key_pem: String,
+    pub signature_algorithm: &'static str,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn chain() -> Vec<CustodyEntry> {
+        let mut prev = GENESIS_HASH.to_string();
+        let actions = [
+            ("created", json!({"title": "Break-in"})),
+            ("event_added", json!({"log_id": 42, "image_sha256": "ab"})),
+            ("exported", json!({"package_id": "case1-20261019T000000000Z"})),
+        ];
+        actions
+            .into_iter()
+            .enumerate()
+            .map(|(i, (action, details))| {
+                let mut entry = CustodyEntry {
+                    custody_id: i as u64 + 1,
+                    case_id: 1,
+                    occurred_at: "2026-10-19T09:00:00.123Z".parse().unwrap(),
+                    action: action.to_string(),
+                    actor: "investigator".to_string(),
+                    source_ip: Some("192.168.1.10".to_string()),
+                    details: Some(details),
+                    prev_hash: prev.clone(),
+                    entry_hash: String::new(),
+                };
+                entry.entry_hash = entry.compute_hash(&prev);
+                prev = entry.entry_hash.clone();
+                entry
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_custody_chain_roundtrip_and_tamper() {
+        let entries = chain();
+        assert_eq!(verify_custody(&entries), Ok(()));
+
+        // custody.json 経由の往復でもハッシュが変わらない
+        let text = serde_json::to_string_pretty(&entries).unwrap();
+        let parsed: Vec<CustodyEntry> = serde_json::from_str(&text).unwrap();
+        assert_eq!(verify_custody(&parsed), Ok(()));
+
+        let mut tampered = entries.clone();
+        tampered[1].details = Some(json!({"log_id": 43, "image_sha256": "ab"}));
+        assert_eq!(verify_custody(&tampered), Err(2));
+
+        let removed = vec![entries[0].clone(), entries[2].clone()];
+        assert_eq!(verify_custody(&removed), Err(3));
+    }
+
+    #[test]
+    fn test_case_status_and_title() {
+        assert_eq!(CaseStatus::parse("closed"), Some(CaseStatus::Closed));
+        assert_eq!(CaseStatus::parse(CaseStatus::Open.as_str()), Some(CaseStatus::Open));
+        assert!(CaseStatus::parse("archived").is_none());
+        assert!(validate_title("  ").is_err());
+        assert!(validate_title("Parking lot break-in 2026-10-18").is_ok());
+    }
+}
@@ -109,6 +109,8 @@ pub struct PresetStats {
     /// 誤検知率 (フィードバックベース)
     pub false_positive_rate: f64,
     pub avg_confidence: f64,
+    /// スケジュールによるこのプリセットへの切替回数
+    pub switch_in_count: i64,
 }
 
 /// プリセット効果レスポンス
@@ -443,6 +445,27 @@ impl InferenceStatsService {
             feedback_map.insert(preset_id, count);
         }
 
+        // スケジュール切替回数を取得 (preset_switch_logs)
+        let switch_rows = sqlx::query(
+            r#"SELECT
+                to_preset_id as preset_id,
+                COUNT(*) as switch_count
+            FROM preset_switch_logs
+            WHERE switched_at >= ?
+            GROUP BY to_preset_id"#
+        )
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await
+        .unwrap_or_default();
+
+        let mut switch_map: HashMap<String, i64> = HashMap::new();
+        for row in switch_rows {
+            let preset_id: String = row.get("preset_id");
+            let count: i64 = row.get("switch_count");
+            switch_map.insert(preset_id, count);
+        }
+
         let presets: Vec<PresetStats> = rows
             .into_iter()
             .map(|row| {
@@ -450,6 +473,7 @@ impl InferenceStatsService {
                 let total_inferences: i64 = row.get("total_inferences");
                 let detection_count: i64 = row.get("detection_count");
                 let feedback_count = feedback_map.get(&preset_id).copied().unwrap_or(0);
+                let switch_in_count = switch_map.get(&preset_id).copied().unwrap_or(0);
 
                 let detection_rate = if total_inferences > 0 {
                     detection_count as f64 / total_inferences as f64
@@ -470,6 +494,7 @@ impl InferenceStatsService {
                     detection_rate: (detection_rate * 1000.0).round() / 1000.0, // 小数点3桁
                     false_positive_rate: (false_positive_rate * 1000.0).round() / 1000.0,
                     avg_confidence: row.get("avg_confidence"),
+                    switch_in_count,
                 }
             })
             .collect();
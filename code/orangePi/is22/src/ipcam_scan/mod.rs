@@ -16,6 +16,8 @@ mod utils;
 
 pub use job::*;
 pub use types::*;
+/// ONVIF SOAP helpers (shared with camera_maintenance)
+pub use scanner::{extract_xml_value, generate_ws_security_header, xml_escape};
 
 use crate::access_absorber::AccessFamily;
 use crate::config_store::ConfigStore;
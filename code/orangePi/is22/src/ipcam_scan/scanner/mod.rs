@@ -10,6 +10,8 @@ mod network;
 pub use port_weights::PORT_WEIGHTS;
 pub use oui_data::{lookup_oui, extract_oui_prefix, is_locally_administered, OuiMap};
 pub use probes::{
+    extract_xml_value,
+    generate_ws_security_header,
     probe_onvif,
     probe_onvif_capabilities,
     probe_onvif_detailed,
@@ -26,6 +28,7 @@ pub use probes::{
     OnvifNetworkInterface,
     OnvifScopes,
     ProbeResult as ProbesProbeResult,  // Re-export for type conversion
+    xml_escape,
 };
 pub use network::{
     arp_scan_subnet,
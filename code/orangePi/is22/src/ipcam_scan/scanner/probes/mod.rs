@@ -14,6 +14,8 @@ pub enum ProbeResult {
 
 pub use rtsp::{probe_rtsp, probe_rtsp_detailed, verify_rtsp};
 pub use onvif::{
+    extract_xml_value,
+    generate_ws_security_header,
     probe_onvif,
     probe_onvif_capabilities,
     probe_onvif_detailed,
@@ -26,4 +28,5 @@ pub use onvif::{
     OnvifExtendedInfo,
     OnvifNetworkInterface,
     OnvifScopes,
+    xml_escape,
 };
@@ -1,14 +1,18 @@
 use std::net::IpAddr;
 use std::time::Duration;
 
+use chrono::{DateTime, Utc};
 use rand::Rng;
 use sha1::{Digest, Sha1};
 
 use super::types::OnvifDeviceInfo;
-use super::xml::extract_xml_value;
+use super::xml::{extract_xml_value, xml_escape};
 
 /// Generate WS-Security UsernameToken Digest header for ONVIF authentication
-pub fn generate_ws_security_header(username: &str, password: &str) -> String {
+///
+/// `created_at` is the Created timestamp. Cameras validate it against their own clock,
+/// so callers that know the camera's clock skew pass `Utc::now() + skew`.
+pub fn generate_ws_security_header(username: &str, password: &str, created_at: DateTime<Utc>) -> String {
     // 1. Generate nonce (16 random bytes)
     let mut rng = rand::thread_rng();
     let nonce_bytes: [u8; 16] = rng.gen();
@@ -18,7 +22,7 @@ pub fn generate_ws_security_header(username: &str, password: &str) -> String {
     );
 
     // 2. Generate created timestamp (ISO8601)
-    let created = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+    let created = created_at.format("%Y-%m-%dT%H:%M:%SZ").to_string();
 
     // 3. Calculate PasswordDigest = Base64(SHA1(nonce + created + password))
     let mut hasher = Sha1::new();
@@ -33,15 +37,15 @@ pub fn generate_ws_security_header(username: &str, password: &str) -> String {
 
     // 4. Build SOAP header
     format!(
-        r#"<wsse:Security xmlns:wsse=\"http://docs.oasis-open.org/wss/2004/01/oasis-200401-wss-wssecurity-secext-1.0.xsd\" xmlns:wsu=\"http://docs.oasis-open.org/wss/2004/01/oasis-200401-wss-wssecurity-utility-1.0.xsd\">
+        r#"<wsse:Security xmlns:wsse="http://docs.oasis-open.org/wss/2004/01/oasis-200401-wss-wssecurity-secext-1.0.xsd" xmlns:wsu="http://docs.oasis-open.org/wss/2004/01/oasis-200401-wss-wssecurity-utility-1.0.xsd">
       <wsse:UsernameToken>
         <wsse:Username>{}</wsse:Username>
-        <wsse:Password Type=\"http://docs.oasis-open.org/wss/2004/01/oasis-200401-wss-username-token-profile-1.0#PasswordDigest\">{}</wsse:Password>
-        <wsse:Nonce EncodingType=\"http://docs.oasis-open.org/wss/2004/01/oasis-200401-wss-soap-message-security-1.0#Base64Binary\">{}</wsse:Nonce>
+        <wsse:Password Type="http://docs.oasis-open.org/wss/2004/01/oasis-200401-wss-username-token-profile-1.0#PasswordDigest">{}</wsse:Password>
+        <wsse:Nonce EncodingType="http://docs.oasis-open.org/wss/2004/01/oasis-200401-wss-soap-message-security-1.0#Base64Binary">{}</wsse:Nonce>
         <wsu:Created>{}</wsu:Created>
       </wsse:UsernameToken>
     </wsse:Security>"#,
-        username, digest_b64, nonce_b64, created
+        xml_escape(username), digest_b64, nonce_b64, created
     )
 }
 
@@ -55,7 +59,7 @@ pub async fn probe_onvif_with_auth(
     let ports = [80, 2020, 8080];
     let timeout_dur = Duration::from_millis(timeout_ms as u64);
 
-    let security_header = generate_ws_security_header(username, password);
+    let security_header = generate_ws_security_header(username, password, Utc::now());
 
     // ONVIF GetDeviceInformation SOAP request
     let soap_body = format!(
@@ -141,7 +145,7 @@ pub async fn probe_onvif_mac(
     let ports = [80, 2020, 8080];
     let timeout_dur = Duration::from_millis(timeout_ms as u64);
 
-    let security_header = generate_ws_security_header(username, password);
+    let security_header = generate_ws_security_header(username, password, Utc::now());
 
     // ONVIF GetNetworkInterfaces SOAP request
     let soap_body = format!(
@@ -24,7 +24,7 @@ pub async fn probe_onvif_scopes(
     let ports = [80, 2020, 8080];
     let timeout_dur = Duration::from_millis(timeout_ms as u64);
 
-    let security_header = generate_ws_security_header(username, password);
+    let security_header = generate_ws_security_header(username, password, chrono::Utc::now());
 
     let soap_body = format!(
         r#"<?xml version=\"1.0\" encoding=\"UTF-8\"?>
@@ -133,7 +133,7 @@ pub async fn probe_onvif_network_interfaces_full(
     let ports = [80, 2020, 8080];
     let timeout_dur = Duration::from_millis(timeout_ms as u64);
 
-    let security_header = generate_ws_security_header(username, password);
+    let security_header = generate_ws_security_header(username, password, chrono::Utc::now());
 
     let soap_body = format!(
         r#"<?xml version=\"1.0\" encoding=\"UTF-8\"?>
@@ -220,7 +220,7 @@ pub async fn probe_onvif_capabilities(
     let ports = [80, 2020, 8080];
     let timeout_dur = Duration::from_millis(timeout_ms as u64);
 
-    let security_header = generate_ws_security_header(username, password);
+    let security_header = generate_ws_security_header(username, password, chrono::Utc::now());
 
     let soap_body = format!(
         r#"<?xml version=\"1.0\" encoding=\"UTF-8\"?>
@@ -12,7 +12,9 @@ pub use auth::{
     probe_onvif_scopes,
     probe_onvif_with_auth,
 };
+pub use auth_base::generate_ws_security_header;
 pub use basic::{probe_onvif, probe_onvif_detailed};
+pub use xml::{extract_xml_value, xml_escape};
 pub use types::{
     OnvifCapabilities,
     OnvifDeviceInfo,
@@ -70,50 +70,79 @@ pub fn extract_xml_attribute(xml: &str, tag: &str, attr: &str) -> Option<String>
 }
 
 /// Extract XML value with namespace-agnostic matching
+///
+/// Returns the first non-empty `<tag>` / `<prefix:tag ...>` text content (entities unescaped).
 pub fn extract_xml_value(xml: &str, tag: &str) -> Option<String> {
-    // Try with common ONVIF namespace prefix first
-    let prefixed_patterns = [
-        format!("<tds:{}>", tag),
-        format!("<tt:{}>", tag),
-        format!("<:{}>", tag),
-    ];
+    let mut search = 0;
+    while let Some(pos) = xml[search..].find(tag) {
+        let start = search + pos;
+        let end = start + tag.len();
+        search = end;
 
-    for pattern in &prefixed_patterns {
-        if let Some(start) = xml.find(pattern.as_str()) {
-            let content_start = start + pattern.len();
-            if let Some(end) = xml[content_start..].find("</") {
-                let value = xml[content_start..content_start + end].trim().to_string();
-                if !value.is_empty() {
-                    return Some(value);
-                }
-            }
+        // Opening tag: preceded by '<' or '<prefix:', followed by '>' or attributes
+        let before = &xml[..start];
+        let opening = before.ends_with('<')
+            || before
+                .rfind('<')
+                .map(|lt| {
+                    let prefix = &before[lt + 1..];
+                    prefix.ends_with(':')
+                        && !prefix.starts_with('/')
+                        && prefix[..prefix.len() - 1]
+                            .chars()
+                            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+                })
+                .unwrap_or(false);
+        if !opening {
+            continue;
         }
-    }
-
-    // Try without namespace prefix
-    let pattern = format!(":{}>", tag);
-    if let Some(start) = xml.find(pattern.as_str()) {
-        let content_start = start + pattern.len();
-        if let Some(end) = xml[content_start..].find("</") {
-            let value = xml[content_start..content_start + end].trim().to_string();
-            if !value.is_empty() {
-                return Some(value);
-            }
+        let rest = &xml[end..];
+        let Some(gt) = rest.find('>') else { break };
+        if !(rest.starts_with('>') || rest.starts_with(char::is_whitespace)) || rest[..gt].ends_with('/') {
+            continue;
         }
-    }
-
-    // Final fallback: any tag ending with the name
-    let simple_pattern = format!("<{}>", tag);
-    if let Some(start) = xml.find(simple_pattern.as_str()) {
-        let content_start = start + simple_pattern.len();
-        let close_pattern = format!("</{}>", tag);
-        if let Some(end) = xml[content_start..].find(close_pattern.as_str()) {
-            let value = xml[content_start..content_start + end].trim().to_string();
-            if !value.is_empty() {
-                return Some(value);
-            }
+        let content = &rest[gt + 1..];
+        let value = content[..content.find('<').unwrap_or(content.len())].trim();
+        if !value.is_empty() {
+            return Some(xml_unescape(value));
         }
     }
-
     None
 }
+
+/// Escape text for element content / attribute values
+pub fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+pub fn xml_unescape(value: &str) -> String {
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_xml_value_any_prefix() {
+        let xml = r#"<tds:GetDeviceInformationResponse><tds:Manufacturer>TP-Link</tds:Manufacturer><tds:SerialNumber></tds:SerialNumber><tds:FirmwareVersion>1.3 &amp; Rel</tds:FirmwareVersion></tds:GetDeviceInformationResponse>"#;
+        assert_eq!(extract_xml_value(xml, "Manufacturer").as_deref(), Some("TP-Link"));
+        assert_eq!(extract_xml_value(xml, "FirmwareVersion").as_deref(), Some("1.3 & Rel"));
+        assert_eq!(extract_xml_value(xml, "SerialNumber"), None);
+        // 前方一致する別タグ・属性付き・接頭辞なし
+        assert_eq!(extract_xml_value(xml, "Firmware"), None);
+        let fault = r#"<env:Reason><env:Text xml:lang="en">Sender not Authorized</env:Text></env:Reason>"#;
+        assert_eq!(extract_xml_value(fault, "Text").as_deref(), Some("Sender not Authorized"));
+        assert_eq!(extract_xml_value("<Model>C200</Model>", "Model").as_deref(), Some("C200"));
+        assert_eq!(extract_xml_value("<tt:Model/>", "Model"), None);
+    }
+}
@@ -22,18 +22,31 @@
 //! - SOLID: Single responsibility per module
 //! - MECE: Mutually exclusive, collectively exhaustive
 
+pub mod admin_cli;
+pub mod app_config;
 pub mod aranea_register;
+pub mod audit_log;
 pub mod camera_registry;
 pub mod camera_brand;
 pub mod camera_sync;
 pub mod summary_service;
 pub mod paraclate_client;
+pub mod email_notifier;
 pub mod config_store;
+pub mod db;
 pub mod sdm_integration;
 pub mod admission_controller;
 pub mod ai_client;
 pub mod camera_status_tracker;
+pub mod camera_availability;
 pub mod camera_malfunction_reporter;
+pub mod camera_diagnostics;
+pub mod camera_maintenance;
+pub mod live_layout;
+pub mod incident_case;
+pub mod health;
+pub mod metrics;
+pub mod migration;
 pub mod detection_log_service;
 pub mod event_log_service;
 pub mod suggest_engine;
@@ -43,9 +56,13 @@ pub mod web_api;
 pub mod ipcam_scan;
 pub mod lost_cam_tracker;
 pub mod snapshot_service;
+pub mod storage_manager;
 pub mod prev_frame_cache;
+pub mod motion_prefilter;
 pub mod preset_loader;
+pub mod preset_schedule;
 pub mod polling_orchestrator;
+pub mod task_supervisor;
 pub mod rtsp_manager;
 pub mod models;
 pub mod inference_stats_service;
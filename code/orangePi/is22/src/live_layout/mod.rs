@@ -0,0 +1,381 @@
+//! LiveLayout - マルチカメラ ライブウォールのレイアウト保存
+//!
+//! ## 概要
+//! - ユーザーごとの名前付きグリッド（cols × rows）を live_layouts に保存
+//! - 複数ページ + tour_interval_sec でツアー（ページ自動切替）
+//! - ページ表示計画（plan）は AdmissionController の残り予算内でライブタイルを割り当て、
+//!   main が収まらなければ sub、sub も収まらないセルはスナップショット表示にする
+//!
+//! 計画は表示時点の予算に基づく助言であり、予算を確保（リース発行）はしない。
+
+pub mod types;
+
+pub use types::*;
+
+use crate::admission_controller::{AdmissionController, StreamQuality};
+use crate::config_store::ConfigStore;
+use crate::error::{Error, Result};
+use crate::stream_gateway::reconciler::{desired_streams, stream_name};
+use crate::stream_gateway::{StreamGateway, StreamReconciler, StreamVariant};
+use chrono::Utc;
+use sqlx::{MySqlPool, Row};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// ライブウォールレイアウトサービス
+pub struct LiveLayoutService {
+    pool: MySqlPool,
+    config_store: Arc<ConfigStore>,
+    stream: Arc<StreamGateway>,
+    reconciler: Arc<StreamReconciler>,
+    admission: Arc<AdmissionController>,
+}
+
+impl LiveLayoutService {
+    pub fn new(
+        pool: MySqlPool,
+        config_store: Arc<ConfigStore>,
+        stream: Arc<StreamGateway>,
+        reconciler: Arc<StreamReconciler>,
+        admission: Arc<AdmissionController>,
+    ) -> Self {
+        Self {
+            pool,
+            config_store,
+            stream,
+            reconciler,
+            admission,
+        }
+    }
+
+    const COLUMNS: &'static str = "layout_id, user_id, name, grid_cols, grid_rows, quality, \
+        tour_interval_sec, pages, created_at, updated_at";
+
+    fn row_to_layout(row: &sqlx::mysql::MySqlRow) -> Result<LiveLayout> {
+        let quality: String = row.try_get("quality")?;
+        let pages: serde_json::Value = row.try_get("pages")?;
+        Ok(LiveLayout {
+            layout_id: row.try_get("layout_id")?,
+            user_id: row.try_get("user_id")?,
+            name: row.try_get("name")?,
+            grid_cols: row.try_get("grid_cols")?,
+            grid_rows: row.try_get("grid_rows")?,
+            quality: StreamQuality::parse(&quality).unwrap_or_default(),
+            tour_interval_sec: row.try_get("tour_interval_sec")?,
+            pages: serde_json::from_value(pages)?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+        })
+    }
+
+    /// レイアウト一覧（user_id 指定でそのユーザーのみ）
+    pub async fn list(&self, user_id: Option<&str>) -> Result<Vec<LiveLayout>> {
+        let rows = match user_id {
+            Some(user_id) => {
+                sqlx::query(&format!(
+                    "SELECT {} FROM live_layouts WHERE user_id = ? ORDER BY name",
+                    Self::COLUMNS
+                ))
+                .bind(user_id)
+                .fetch_all(&self.pool)
+                .await?
+            }
+            None => {
+                sqlx::query(&format!(
+                    "SELECT {} FROM live_layouts ORDER BY user_id, name",
+                    Self::COLUMNS
+                ))
+                .fetch_all(&self.pool)
+                .await?
+            }
+        };
+        rows.iter().map(Self::row_to_layout).collect()
+    }
+
+    pub async fn get(&self, layout_id: u64) -> Result<LiveLayout> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM live_layouts WHERE layout_id = ?",
+            Self::COLUMNS
+        ))
+        .bind(layout_id)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| Error::NotFound(format!("Layout {} not found", layout_id)))?;
+        Self::row_to_layout(&row)
+    }
+
+    async fn validate(&self, request: &LayoutRequest) -> Result<()> {
+        let known: Vec<String> = self
+            .config_store
+            .get_cached_cameras()
+            .await
+            .into_iter()
+            .map(|c| c.camera_id)
+            .collect();
+        request.validate(&known)
+    }
+
+    pub async fn create(&self, request: LayoutRequest) -> Result<LiveLayout> {
+        self.validate(&request).await?;
+        let now = Utc::now();
+        let result = sqlx::query(
+            "INSERT INTO live_layouts \
+             (user_id, name, grid_cols, grid_rows, quality, tour_interval_sec, pages, created_at, updated_at) \
+             VALUES (?, ?, ?, ?, ?, ?, CAST(? AS JSON), ?, ?)",
+        )
+        .bind(request.user_id.trim())
+        .bind(request.name.trim())
+        .bind(request.grid_cols)
+        .bind(request.grid_rows)
+        .bind(request.quality.as_str())
+        .bind(request.tour_interval_sec)
+        .bind(serde_json::to_string(&request.pages)?)
+        .bind(now)
+        .bind(now)
+        .execute(&self.pool)
+        .await
+        .map_err(duplicate_name)?;
+        self.get(result.last_insert_id()).await
+    }
+
+    pub async fn update(&self, layout_id: u64, request: LayoutRequest) -> Result<LiveLayout> {
+        self.validate(&request).await?;
+        let result = sqlx::query(
+            "UPDATE live_layouts SET user_id = ?, name = ?, grid_cols = ?, grid_rows = ?, quality = ?, \
+             tour_interval_sec = ?, pages = CAST(? AS JSON), updated_at = ? WHERE layout_id = ?",
+        )
+        .bind(request.user_id.trim())
+        .bind(request.name.trim())
+        .bind(request.grid_cols)
+        .bind(request.grid_rows)
+        .bind(request.quality.as_str())
+        .bind(request.tour_interval_sec)
+        .bind(serde_json::to_string(&request.pages)?)
+        .bind(Utc::now())
+        .bind(layout_id)
+        .execute(&self.pool)
+        .await
+        .map_err(duplicate_name)?;
+        if result.rows_affected() == 0 {
+            // 内容が同一の場合も 0 になるため存在確認する
+            self.get(layout_id).await?;
+        }
+        self.get(layout_id).await
+    }
+
+    pub async fn delete(&self, layout_id: u64) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM live_layouts WHERE layout_id = ?")
+            .bind(layout_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// ページの表示計画（page はページ数で剰余を取る）
+    pub async fn plan(&self, layout_id: u64, page: usize) -> Result<LayoutPagePlan> {
+        let layout = self.get(layout_id).await?;
+        let cameras = self.config_store.get_cached_cameras().await;
+        let streams: HashSet<String> = desired_streams(&cameras, &self.reconciler.config().await)
+            .into_iter()
+            .map(|s| s.name)
+            .collect();
+        let policy = self.admission.policy().await;
+        let budget_remaining = self.admission.get_status().await.modal_budget_remaining;
+
+        let mut plan = plan_page(
+            &layout,
+            page,
+            &streams,
+            budget_remaining,
+            policy.main_stream_cost,
+            policy.sub_stream_cost,
+        );
+        for tile in plan.tiles.iter_mut().filter(|t| t.mode == TileMode::Live) {
+            tile.stream_url = Some(self.stream.get_stream_urls(&tile.stream_name).webrtc_url);
+        }
+        Ok(plan)
+    }
+}
+
+fn duplicate_name(e: sqlx::Error) -> Error {
+    match &e {
+        sqlx::Error::Database(db) if db.message().contains("Duplicate entry") => {
+            Error::Conflict("Layout with the same name already exists for this user".to_string())
+        }
+        _ => e.into(),
+    }
+}
+
+/// 予算内でタイルを割り当てる
+///
+/// - quality=main かつ全セル main が予算に収まる場合のみ main
+/// - それ以外は sub で、収まらないセル（後方）はスナップショット
+pub fn plan_page(
+    layout: &LiveLayout,
+    page: usize,
+    streams: &HashSet<String>,
+    budget_remaining: i32,
+    main_cost: i32,
+    sub_cost: i32,
+) -> LayoutPagePlan {
+    let page_count = layout.pages.len().max(1);
+    let page = page % page_count;
+    let current = layout.pages.get(page).cloned().unwrap_or_default();
+
+    let occupied: Vec<(usize, &String)> = current
+        .cells
+        .iter()
+        .enumerate()
+        .filter_map(|(cell, id)| id.as_ref().map(|id| (cell, id)))
+        .collect();
+    let budget = budget_remaining.max(0);
+
+    let all_main = layout.quality == StreamQuality::Main
+        && budget >= main_cost * occupied.len() as i32;
+    let live_limit = if all_main {
+        occupied.len()
+    } else if sub_cost > 0 {
+        (budget / sub_cost) as usize
+    } else {
+        occupied.len()
+    };
+
+    let tiles: Vec<TilePlan> = occupied
+        .iter()
+        .enumerate()
+        .map(|(index, (cell, camera_id))| {
+            let sub_name = stream_name(camera_id, StreamVariant::Sub);
+            let (quality, stream_name) = if !all_main && streams.contains(&sub_name) {
+                (StreamQuality::Sub, sub_name)
+            } else if all_main {
+                (StreamQuality::Main, camera_id.to_string())
+            } else {
+                // sub ストリームがないカメラは main を sub 扱いで表示
+                (StreamQuality::Sub, camera_id.to_string())
+            };
+            TilePlan {
+                cell: *cell,
+                camera_id: camera_id.to_string(),
+                mode: if index < live_limit { TileMode::Live } else { TileMode::Snapshot },
+                quality,
+                stream_name,
+                stream_url: None,
+                snapshot_url: format!("/api/snapshots/{}/latest.jpg", camera_id),
+            }
+        })
+        .collect();
+
+    let live_tiles = tiles.iter().filter(|t| t.mode == TileMode::Live).count();
+    let touring = page_count > 1 && layout.tour_interval_sec > 0;
+    let next_page = touring.then_some((page + 1) % page_count);
+
+    LayoutPagePlan {
+        layout_id: layout.layout_id,
+        page,
+        page_count,
+        title: current.title,
+        grid_cols: layout.grid_cols,
+        grid_rows: layout.grid_rows,
+        snapshot_tiles: tiles.len() - live_tiles,
+        live_tiles,
+        tiles,
+        budget_remaining,
+        next_page,
+        rotate_after_sec: touring.then_some(layout.tour_interval_sec),
+        next_page_cameras: next_page
+            .and_then(|p| layout.pages.get(p))
+            .map(|p| p.cells.iter().flatten().cloned().collect())
+            .unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout(quality: StreamQuality, pages: Vec<Vec<Option<&str>>>) -> LiveLayout {
+        LiveLayout {
+            layout_id: 1,
+            user_id: "u1".to_string(),
+            name: "wall".to_string(),
+            grid_cols: 2,
+            grid_rows: 2,
+            quality,
+            tour_interval_sec: 15,
+            pages: pages
+                .into_iter()
+                .map(|cells| LayoutPage {
+                    title: None,
+                    cells: cells.into_iter().map(|c| c.map(str::to_string)).collect(),
+                })
+                .collect(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_plan_page_budget_fallback() {
+        let layout = layout(
+            StreamQuality::Main,
+            vec![
+                vec![Some("cam-a"), None, Some("cam-b"), Some("cam-c")],
+                vec![Some("cam-d")],
+            ],
+        );
+        let streams: HashSet<String> = ["cam-a", "cam-a_sub", "cam-b", "cam-c"]
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+
+        // 十分な予算: 全セル main
+        let plan = plan_page(&layout, 0, &streams, 10, 2, 1);
+        assert!(plan.tiles.iter().all(|t| t.quality == StreamQuality::Main && t.mode == TileMode::Live));
+        assert_eq!(plan.next_page, Some(1));
+        assert_eq!(plan.next_page_cameras, vec!["cam-d"]);
+
+        // main 3セル分(6)は収まらない → sub、2セルのみライブ
+        let plan = plan_page(&layout, 0, &streams, 2, 2, 1);
+        let summary: Vec<_> = plan
+            .tiles
+            .iter()
+            .map(|t| (t.cell, t.stream_name.as_str(), t.mode))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (0, "cam-a_sub", TileMode::Live),
+                (2, "cam-b", TileMode::Live),
+                (3, "cam-c", TileMode::Snapshot),
+            ]
+        );
+        assert_eq!((plan.live_tiles, plan.snapshot_tiles), (2, 1));
+
+        // ページ番号はページ数で循環
+        assert_eq!(plan_page(&layout, 3, &streams, 10, 2, 1).page, 1);
+    }
+
+    #[test]
+    fn test_layout_request_validation() {
+        let known = vec!["cam-a".to_string()];
+        let mut request = LayoutRequest {
+            user_id: "u1".to_string(),
+            name: "wall".to_string(),
+            grid_cols: 2,
+            grid_rows: 1,
+            quality: StreamQuality::Sub,
+            tour_interval_sec: 0,
+            pages: vec![LayoutPage {
+                title: None,
+                cells: vec![Some("cam-a".to_string()), None],
+            }],
+        };
+        assert!(request.validate(&known).is_ok());
+
+        request.pages[0].cells.push(None);
+        assert!(request.validate(&known).is_err());
+
+        request.pages[0].cells = vec![Some("cam-x".to_string())];
+        assert!(request.validate(&known).is_err());
+    }
+}
@@ -0,0 +1,139 @@
+//! Live layout types
+
+use crate::admission_controller::StreamQuality;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// グリッドの最大行・列数
+pub const MAX_GRID_DIMENSION: u8 = 6;
+/// 1レイアウトの最大ページ数
+pub const MAX_PAGES: usize = 20;
+
+/// One page of a layout
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LayoutPage {
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Row-major cells (None = empty cell)
+    pub cells: Vec<Option<String>>,
+}
+
+/// Saved layout
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LiveLayout {
+    pub layout_id: u64,
+    pub user_id: String,
+    pub name: String,
+    pub grid_cols: u8,
+    pub grid_rows: u8,
+    pub quality: StreamQuality,
+    /// 0 = no tour
+    pub tour_interval_sec: u32,
+    pub pages: Vec<LayoutPage>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Create / update request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LayoutRequest {
+    pub user_id: String,
+    pub name: String,
+    pub grid_cols: u8,
+    pub grid_rows: u8,
+    #[serde(default)]
+    pub quality: StreamQuality,
+    #[serde(default)]
+    pub tour_interval_sec: u32,
+    pub pages: Vec<LayoutPage>,
+}
+
+impl LayoutRequest {
+    /// Validate against registered camera IDs
+    pub fn validate(&self, known_cameras: &[String]) -> crate::Result<()> {
+        let invalid = |msg: String| Err(crate::Error::Validation(msg));
+
+        if self.user_id.trim().is_empty() || self.name.trim().is_empty() {
+            return invalid("user_id and name are required".to_string());
+        }
+        if !(1..=MAX_GRID_DIMENSION).contains(&self.grid_cols)
+            || !(1..=MAX_GRID_DIMENSION).contains(&self.grid_rows)
+        {
+            return invalid(format!(
+                "grid_cols/grid_rows must be between 1 and {}",
+                MAX_GRID_DIMENSION
+            ));
+        }
+        if self.pages.is_empty() || self.pages.len() > MAX_PAGES {
+            return invalid(format!("pages must contain 1 to {} pages", MAX_PAGES));
+        }
+        if self.pages.len() > 1 && self.tour_interval_sec > 0 && self.tour_interval_sec < 5 {
+            return invalid("tour_interval_sec must be 0 or >= 5".to_string());
+        }
+
+        let capacity = self.grid_cols as usize * self.grid_rows as usize;
+        for (index, page) in self.pages.iter().enumerate() {
+            if page.cells.len() > capacity {
+                return invalid(format!(
+                    "page {} has {} cells but the grid holds {}",
+                    index + 1,
+                    page.cells.len(),
+                    capacity
+                ));
+            }
+            if let Some(unknown) = page
+                .cells
+                .iter()
+                .flatten()
+                .find(|id| !known_cameras.contains(id))
+            {
+                return invalid(format!("Unknown camera in page {}: {}", index + 1, unknown));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// How a tile should be rendered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TileMode {
+    /// WebRTC live stream
+    Live,
+    /// Periodic cached snapshot (budget exhausted)
+    Snapshot,
+}
+
+/// Rendering plan of one cell
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TilePlan {
+    pub cell: usize,
+    pub camera_id: String,
+    pub mode: TileMode,
+    pub quality: StreamQuality,
+    /// go2rtc stream name (sub / rotated variant when available)
+    pub stream_name: String,
+    pub stream_url: Option<String>,
+    pub snapshot_url: String,
+}
+
+/// Rendering plan of a page
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LayoutPagePlan {
+    pub layout_id: u64,
+    pub page: usize,
+    pub page_count: usize,
+    pub title: Option<String>,
+    pub grid_cols: u8,
+    pub grid_rows: u8,
+    pub tiles: Vec<TilePlan>,
+    pub live_tiles: usize,
+    pub snapshot_tiles: usize,
+    /// AdmissionController の残り予算（計画時点）
+    pub budget_remaining: i32,
+    /// Tour: next page and seconds until switching (None = no tour)
+    pub next_page: Option<usize>,
+    pub rotate_after_sec: Option<u32>,
+    /// Cameras on the next page (client may prewarm)
+    pub next_page_cameras: Vec<String>,
+}
@@ -22,6 +22,7 @@ mod types;
 pub use arp_scanner::{ArpScanner, ArpEntry};
 pub use types::*;
 
+use crate::camera_availability::{CameraAvailabilityService, OutageCause};
 use crate::config_store::ConfigStore;
 use crate::event_log_service::{DetectionEvent, EventLogService};
 use crate::Error;
@@ -83,6 +84,8 @@ pub struct LostCamTrackerService {
     pool: Pool<MySql>,
     config_store: Arc<ConfigStore>,
     event_log_service: Arc<EventLogService>,
+    /// 稼働履歴（IP追随をオフライン原因として記録）
+    availability: Arc<CameraAvailabilityService>,
     arp_scanner: ArpScanner,
     config: RwLock<LostCamTrackerConfig>,
     /// is22のローカルサブネット（ARPスキャン可能）
@@ -95,12 +98,14 @@ impl LostCamTrackerService {
         pool: Pool<MySql>,
         config_store: Arc<ConfigStore>,
         event_log_service: Arc<EventLogService>,
+        availability: Arc<CameraAvailabilityService>,
         local_subnets: Vec<String>,
     ) -> Self {
         Self {
             pool,
             config_store,
             event_log_service,
+            availability,
             arp_scanner: ArpScanner::new(),
             config: RwLock::new(LostCamTrackerConfig::default()),
             local_subnets,
@@ -521,6 +526,20 @@ impl LostCamTrackerService {
         };
         self.event_log_service.add_event(event).await;
 
+        // 継続中のオフライン区間の原因をIP追随に更新（復旧は次回ポーリングで記録）
+        let detail = format!("{} -> {}", old_ip, new_ip);
+        if let Err(e) = self
+            .availability
+            .annotate_open_outage(&camera.camera_id, OutageCause::IpRelocated, Some(&detail))
+            .await
+        {
+            tracing::warn!(
+                camera_id = %camera.camera_id,
+                error = %e,
+                "Failed to annotate availability outage with IP relocation"
+            );
+        }
+
         Ok(())
     }
 
@@ -4,48 +4,69 @@
 
 use is22_camserver::{
     access_absorber::AccessAbsorberService,
+    admin_cli::{self, AdminContext, Command},
     admission_controller::AdmissionController,
+    app_config,
     ai_client::AIClient,
     aranea_register::AraneaRegisterService,
     auto_attunement::AutoAttunementService,
     camera_brand::CameraBrandService,
     camera_registry::CameraContextService,
+    camera_availability::CameraAvailabilityService,
+    camera_diagnostics::CameraDiagnosticsService,
+    camera_maintenance::CameraMaintenanceService,
+    camera_malfunction_reporter::CameraMalfunctionReporter,
     camera_status_tracker::CameraStatusTracker,
     config_store::ConfigStore,
     detection_log_service::DetectionLogService,
     event_log_service::EventLogService,
+    health::{stall_threshold, HealthRegistry},
     inference_stats_service::InferenceStatsService,
     ipcam_scan::IpcamScan,
+    live_layout::LiveLayoutService,
+    metrics::Metrics,
+    migration::{self, Migrator, StartupOptions},
     lost_cam_tracker::LostCamTrackerService,
     overdetection_analyzer::OverdetectionAnalyzer,
     camera_sync::{CameraSyncRepository, CameraSyncService},
     paraclate_client::{ConfigSyncService, FidValidator, ParaclateClient, PubSubSubscriber},
-    polling_orchestrator::PollingOrchestrator,
+    polling_orchestrator::{PollingDeps, PollingOrchestrator},
     prev_frame_cache::PrevFrameCache,
-    preset_loader::PresetLoader,
+    preset_loader::{CustomPresetRepository, PresetLoader},
     ptz_controller::PtzService,
     realtime_hub::RealtimeHub,
     rtsp_manager::RtspManager,
     snapshot_service::SnapshotService,
-    stream_gateway::StreamGateway,
+    storage_manager::{StorageManager, StorageStore},
+    audit_log::AuditLogService,
+    incident_case::{self, IncidentCaseService},
+    stream_gateway::{StreamGateway, StreamReconciler},
     suggest_engine::SuggestEngine,
+    email_notifier::EmailNotifier,
+    task_supervisor::{graceful_shutdown, shutdown_signal, TaskSupervisor},
     summary_service::{
-        GrandSummaryGenerator, ScheduleRepository, SummaryGenerator, SummaryRepository,
-        SummaryScheduler,
+        GrandSummaryGenerator, ReportConfig, ReportService, RollupGenerator, ScheduleRepository,
+        SummaryGenerator, SummaryRepository, SummaryScheduler, SummaryTextBuilder,
+        TemplateRepository,
     },
     state::{AppConfig, AppState, SystemHealth},
     web_api,
 };
 use sqlx::mysql::MySqlPoolOptions;
 use sqlx::Row;
+use std::future::IntoFuture;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::RwLock;
 use tower_http::cors::{Any, CorsLayer};
 use tower_http::services::{ServeDir, ServeFile};
 use tower_http::trace::TraceLayer;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
+/// Grace period for open HTTP connections after the shutdown sequence
+const HTTP_DRAIN_TIMEOUT: Duration = Duration::from_secs(5);
+
 /// Load global timeout settings from settings.polling
 async fn load_global_timeout_settings(pool: &sqlx::MySqlPool) -> (u64, u64) {
     let result = sqlx::query("SELECT setting_json FROM settings WHERE setting_key = 'polling'")
@@ -85,21 +106,58 @@ async fn main() -> anyhow::Result<()> {
     // Load .env if present
     dotenvy::dotenv().ok();
 
-    // Initialize tracing
+    // Subcommand (`camserver help` で一覧)
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let command = match Command::parse(&args) {
+        Ok(Command::Help) => {
+            println!("{}", admin_cli::USAGE);
+            return Ok(());
+        }
+        // 受領者の環境でも検証できるよう設定・DB なしで実行
+        Ok(Command::EvidenceVerify {
+            package,
+            fingerprint,
+            pubkey,
+        }) => {
+            let trust = match (fingerprint, pubkey) {
+                (Some(fingerprint), _) => Some(incident_case::TrustAnchor::Fingerprint(fingerprint)),
+                (None, Some(path)) => Some(incident_case::TrustAnchor::PublicKeyPem(
+                    std::fs::read_to_string(&path)?,
+                )),
+                (None, None) => None,
+            };
+            let report = incident_case::verify_package(&package, trust.as_ref());
+            println!("{}", serde_json::to_string_pretty(&report)?);
+            std::process::exit(if report.valid { 0 } else { 1 });
+        }
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    };
+
+    // Initialize tracing (サブコマンドは警告以上のみ stderr へ)
+    let (default_filter, writer) = if matches!(command, Command::Serve) {
+        ("is22_camserver=debug,tower_http=debug", BoxMakeWriter::new(std::io::stdout))
+    } else {
+        ("warn", BoxMakeWriter::new(std::io::stderr))
+    };
     tracing_subscriber::registry()
         .with(
             tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "is22_camserver=debug,tower_http=debug".into()),
+                .unwrap_or_else(|_| default_filter.into()),
         )
-        .with(tracing_subscriber::fmt::layer())
+        .with(tracing_subscriber::fmt::layer().with_writer(writer))
         .init();
 
     tracing::info!("Starting IS22 Camserver v{}", env!("CARGO_PKG_VERSION"));
 
-    // Load configuration
-    let config = AppConfig::default();
+    // Load configuration (defaults → TOML file → env, validated)
+    let config = AppConfig::load()?;
     tracing::info!(
-        database_url = %config.database_url,
+        sources = ?config.sources,
+        database_url = %app_config::redact_url(&config.database_url),
         is21_url = %config.is21_url,
         go2rtc_url = %config.go2rtc_url,
         snapshot_dir = %config.snapshot_dir.display(),
@@ -107,15 +165,42 @@ async fn main() -> anyhow::Result<()> {
         "Configuration loaded"
     );
 
+    // doctor は DB 接続失敗も結果として報告する
+    if matches!(command, Command::Doctor) {
+        let healthy = admin_cli::doctor::run(&config).await;
+        std::process::exit(if healthy { 0 } else { 1 });
+    }
+
     // Create database pool
     let pool = MySqlPoolOptions::new()
-        .max_connections(10)
-        .acquire_timeout(Duration::from_secs(10))
+        .max_connections(config.database.max_connections)
+        .acquire_timeout(Duration::from_secs(config.database.acquire_timeout_secs))
         .connect(&config.database_url)
         .await?;
 
     tracing::info!("Database connected");
 
+    // Schema migrations / admin subcommands run and exit
+    let migrator = Migrator::new(pool.clone());
+    match command {
+        Command::Serve => {}
+        Command::Migrate(args) => {
+            migration::cli::run(&migrator, &args).await?;
+            return Ok(());
+        }
+        command => {
+            let ctx = AdminContext::new(pool, config).await?;
+            admin_cli::run(&ctx, command).await?;
+            return Ok(());
+        }
+    }
+    migrator
+        .run_startup(StartupOptions {
+            auto_apply: config.auto_migrate,
+            baseline: config.migration_baseline,
+        })
+        .await?;
+
     // Initialize system health
     let system_health = Arc::new(RwLock::new(SystemHealth::default()));
 
@@ -123,24 +208,41 @@ async fn main() -> anyhow::Result<()> {
     let config_store = Arc::new(ConfigStore::new(pool.clone()).await?);
     tracing::info!("ConfigStore initialized");
 
+    let realtime = Arc::new(RealtimeHub::new());
+
     let admission_policy = config_store.service().get_admission_policy().await?;
     let admission = Arc::new(AdmissionController::new(
         admission_policy,
         system_health.clone(),
+        pool.clone(),
+        realtime.clone(),
     ));
-    tracing::info!("AdmissionController initialized");
+    match admission.restore_leases().await {
+        Ok(count) => tracing::info!(restored_leases = count, "AdmissionController initialized"),
+        Err(e) => tracing::warn!(error = %e, "AdmissionController initialized (lease restore failed)"),
+    }
 
     let ai_client = Arc::new(AIClient::new(config.is21_url.clone()));
     let stream = Arc::new(StreamGateway::new(config.go2rtc_url.clone()));
-    let event_log = Arc::new(EventLogService::new(2000));
-    let realtime = Arc::new(RealtimeHub::new());
+    let event_log = Arc::new(EventLogService::new(config.event_log.capacity));
 
     // AI Event Log components
     let detection_log = Arc::new(DetectionLogService::with_pool(pool.clone()));
     let prev_frame_cache = Arc::new(PrevFrameCache::with_defaults());
     let preset_loader = Arc::new(PresetLoader::new());
+    match preset_loader
+        .load_custom(&CustomPresetRepository::new(pool.clone()))
+        .await
+    {
+        Ok(count) => tracing::info!(count = count, "Custom presets loaded"),
+        Err(e) => tracing::warn!(error = %e, "Failed to load custom presets (built-in presets only)"),
+    }
     let inference_stats = Arc::new(InferenceStatsService::new(pool.clone()));
-    let auto_attunement = Arc::new(AutoAttunementService::new(pool.clone(), inference_stats.clone()));
+    let auto_attunement = Arc::new(AutoAttunementService::new(
+        pool.clone(),
+        inference_stats.clone(),
+        preset_loader.clone(),
+    ));
     let overdetection_analyzer = Arc::new(OverdetectionAnalyzer::new(pool.clone()));
     tracing::info!("AI Event Log components initialized (DetectionLogService, PrevFrameCache, PresetLoader, InferenceStatsService, AutoAttunementService, OverdetectionAnalyzer)");
 
@@ -188,18 +290,34 @@ async fn main() -> anyhow::Result<()> {
 
     // Camera status tracker for lost/recovered events
     let camera_status_tracker = Arc::new(CameraStatusTracker::new());
-    tracing::info!("CameraStatusTracker initialized");
+    // Persisted availability timeline; rebuild tracker state from open intervals
+    let camera_availability = Arc::new(CameraAvailabilityService::new(pool.clone()));
+    match camera_availability.restore_tracker(&camera_status_tracker).await {
+        Ok(restored) => tracing::info!(
+            restored = restored,
+            "CameraStatusTracker initialized (state restored from availability timeline)"
+        ),
+        Err(e) => tracing::warn!(
+            error = %e,
+            "CameraStatusTracker initialized without restored state"
+        ),
+    }
+
+    // Prometheus metrics (recorded by the polling pipeline, ParaclateClient and
+    // AccessAbsorber, exported at GET /metrics)
+    let metrics = Arc::new(Metrics::new());
 
     // Initialize ParaclateClient BEFORE PollingOrchestrator (needed for event sending)
     let paraclate_client = Arc::new(ParaclateClient::new(
         pool.clone(),
         config_store.clone(),
+        metrics.clone(),
     ));
     tracing::info!("ParaclateClient initialized (Phase 4)");
 
     // Initialize AccessAbsorberService BEFORE PollingOrchestrator (for camera brand connection limits)
     let access_absorber = {
-        let service = AccessAbsorberService::new(pool.clone());
+        let service = AccessAbsorberService::new(pool.clone(), metrics.clone());
         match service.init().await {
             Ok(_) => {
                 tracing::info!("AccessAbsorberService initialized with connection limits cache");
@@ -212,25 +330,133 @@ async fn main() -> anyhow::Result<()> {
         }
     };
 
-    // Create polling orchestrator with AI Event Log pipeline + go2rtc integration + Paraclate + AccessAbsorber
-    let polling = Arc::new(PollingOrchestrator::new(
+    // Initialize Summary Service components BEFORE PollingOrchestrator (Phase 3: Issue #116)
+    let summary_repository = SummaryRepository::new(pool.clone());
+    let schedule_repository = ScheduleRepository::new(pool.clone());
+    let camera_context_service = CameraContextService::new(pool.clone());
+    let summary_text_builder = Arc::new(SummaryTextBuilder::new(
+        detection_log.clone(),
+        camera_context_service.clone(),
+        TemplateRepository::new(pool.clone()),
+        summary_repository.clone(),
+        config_store.clone(),
+    ));
+    let report_service = Arc::new(ReportService::new(
+        detection_log.clone(),
+        camera_context_service.clone(),
+        camera_availability.clone(),
+        summary_repository.clone(),
+        summary_text_builder.clone(),
+        ReportConfig::from(&config.report),
+    ));
+    let summary_generator = Arc::new(SummaryGenerator::new(
+        detection_log.clone(),
+        camera_context_service.clone(),
+        summary_repository.clone(),
+        config_store.clone(),
+        summary_text_builder.clone(),
+    ));
+    let grand_summary_generator = Arc::new(GrandSummaryGenerator::new(
+        summary_repository.clone(),
+        summary_text_builder.clone(),
+    ));
+    let rollup_generator = Arc::new(RollupGenerator::new(
+        pool.clone(),
+        summary_repository.clone(),
+        camera_availability.clone(),
+        camera_context_service,
+        overdetection_analyzer.clone(),
+        summary_text_builder.clone(),
+    ));
+    tracing::info!("Summary Service initialized (SummaryGenerator, GrandSummaryGenerator, RollupGenerator, Repositories)");
+
+    // Email notifier (SMTP) BEFORE PollingOrchestrator (detection alerts)
+    let email_notifier = Arc::new(EmailNotifier::new(
         pool.clone(),
         config_store.clone(),
-        snapshot_service.clone(),
-        ai_client.clone(),
-        event_log.clone(),
         detection_log.clone(),
-        prev_frame_cache.clone(),
-        preset_loader.clone(),
-        suggest.clone(),
+        report_service.clone(),
+        summary_repository.clone(),
+    ));
+    tracing::info!("EmailNotifier initialized");
+
+    // Camera health diagnostics (ffprobe stream checks + snapshot tamper detection)
+    let camera_diagnostics = Arc::new(CameraDiagnosticsService::new(
+        config_store.clone(),
+        rtsp_manager.clone(),
+        snapshot_service.clone(),
+        CameraMalfunctionReporter::new(paraclate_client.clone()),
         realtime.clone(),
-        camera_status_tracker,
-        stream.clone(), // go2rtc StreamGateway for cycle-based registration
-        paraclate_client.clone(), // For sending detection events with snapshots
-        access_absorber.clone(), // For camera brand-specific connection limits
-        default_tid,
-        default_fid,
+        default_tid.clone(),
+        default_fid.clone(),
+    ));
+    tracing::info!("CameraDiagnosticsService initialized (periodic stream/image diagnostics)");
+
+    // Camera maintenance (ONVIF firmware history + clock drift check)
+    let camera_maintenance = Arc::new(CameraMaintenanceService::new(
+        pool.clone(),
+        config_store.clone(),
+    ));
+    tracing::info!("CameraMaintenanceService initialized (ONVIF firmware/clock checks)");
+
+    // go2rtc reconciler (all camera families, sub/rotated variants) + live wall layouts
+    let stream_reconciler = Arc::new(StreamReconciler::new(stream.clone(), config_store.clone()));
+    let live_layouts = Arc::new(LiveLayoutService::new(
+        pool.clone(),
+        config_store.clone(),
+        stream.clone(),
+        stream_reconciler.clone(),
+        admission.clone(),
+    ));
+    tracing::info!("StreamReconciler / LiveLayoutService initialized");
+
+    let audit_log = Arc::new(AuditLogService::new(pool.clone()));
+
+    // Incident evidence (preserved copies / export packages / signing key)
+    if let Err(e) = tokio::fs::create_dir_all(&config.evidence_dir).await {
+        tracing::warn!(path = %config.evidence_dir.display(), error = %e, "Failed to create evidence_dir");
+    }
+    let incident_cases = Arc::new(IncidentCaseService::new(
+        pool.clone(),
+        config_store.clone(),
+        detection_log.clone(),
+        config.evidence_dir.clone(),
     ));
+
+
+    // Background loop heartbeats + uptime (GET /livez, /readyz)
+    let health = Arc::new(HealthRegistry::new());
+    // Supervised background tasks (restart on panic, graceful shutdown)
+    let tasks = config.tasks;
+    let supervisor = Arc::new(TaskSupervisor::new(Duration::from_secs(
+        tasks.restart_backoff_max_secs,
+    )));
+
+    // Create polling orchestrator with AI Event Log pipeline + go2rtc integration + Paraclate + AccessAbsorber
+    let polling = Arc::new(PollingOrchestrator::new(PollingDeps {
+        pool: pool.clone(),
+        config_store: config_store.clone(),
+        snapshot_service: snapshot_service.clone(),
+        ai_client: ai_client.clone(),
+        event_log: event_log.clone(),
+        detection_log: detection_log.clone(),
+        prev_frame_cache: prev_frame_cache.clone(),
+        preset_loader: preset_loader.clone(),
+        suggest_engine: suggest.clone(),
+        realtime_hub: realtime.clone(),
+        camera_status_tracker,
+        availability: camera_availability.clone(), // Availability timeline (SLA metrics)
+        stream_gateway: stream.clone(), // go2rtc StreamGateway for cycle-based registration
+        paraclate_client: paraclate_client.clone(), // For sending detection events with snapshots
+        email_notifier: email_notifier.clone(), // For email detection alerts
+        access_absorber: access_absorber.clone(), // For camera brand-specific connection limits
+        metrics: metrics.clone(), // Prometheus snapshot / IS21 / cycle metrics
+        snapshot_timeout_ms: config.polling.snapshot_timeout_ms,
+        health: health.clone(), // Subnet loop heartbeats (/livez)
+        supervisor: supervisor.clone(), // Subnet loops restart on panic
+        default_tid: default_tid.clone(),
+        default_fid: default_fid.clone(),
+    }));
     tracing::info!("PollingOrchestrator initialized with AI Event Log pipeline + Paraclate event sending + AccessAbsorber");
 
     // Initialize AraneaRegisterService (Phase 1: Issue #114)
@@ -246,19 +472,6 @@ async fn main() -> anyhow::Result<()> {
         None
     };
 
-    // Initialize Summary Service components (Phase 3: Issue #116)
-    let summary_repository = SummaryRepository::new(pool.clone());
-    let schedule_repository = ScheduleRepository::new(pool.clone());
-    let camera_context_service = CameraContextService::new(pool.clone());
-    let summary_generator = Arc::new(SummaryGenerator::new(
-        detection_log.clone(),
-        camera_context_service,
-        summary_repository.clone(),
-        config_store.clone(),
-    ));
-    let grand_summary_generator = Arc::new(GrandSummaryGenerator::new(summary_repository.clone()));
-    tracing::info!("Summary Service initialized (SummaryGenerator, GrandSummaryGenerator, Repositories)");
-
     // Initialize ConfigSyncService and PubSubSubscriber (Phase 4 T4-7: Issue #117)
     let config_sync_service = Arc::new(ConfigSyncService::new(
         pool.clone(),
@@ -290,6 +503,29 @@ async fn main() -> anyhow::Result<()> {
     let ptz_service = Arc::new(PtzService::new(config_store.clone()));
     tracing::info!("PtzService initialized");
 
+    // Initialize StorageManager (disk watermarks across snapshot / prev / frame cache / event images)
+    let mut cache_stores = vec![
+        (StorageStore::Snapshots, snapshot_service.snapshot_dir().to_path_buf()),
+        (StorageStore::PrevImages, snapshot_service.temp_dir().to_path_buf()),
+    ];
+    if let Some(dir) = prev_frame_cache.persist_dir() {
+        cache_stores.push((StorageStore::FrameCache, dir.to_path_buf()));
+    }
+    let storage_manager = Arc::new(StorageManager::new(
+        config_store.clone(),
+        detection_log.clone(),
+        CameraMalfunctionReporter::new(paraclate_client.clone()),
+        realtime.clone(),
+        aranea_register.clone(),
+        cache_stores,
+        default_tid.clone(),
+        default_fid.clone(),
+    ));
+    if let Err(e) = storage_manager.load_config().await {
+        tracing::warn!(error = %e, "StorageManager: failed to load config, using defaults");
+    }
+    tracing::info!("StorageManager initialized");
+
     // Initialize LostCamTracker (DHCP追随によるカメラ自動復旧)
     // ローカルサブネット: is22が直接ARPスキャン可能なサブネット
     let local_subnets = std::env::var("LOCAL_SUBNETS")
@@ -301,6 +537,7 @@ async fn main() -> anyhow::Result<()> {
         pool.clone(),
         config_store.clone(),
         event_log.clone(),
+        camera_availability.clone(),
         local_subnets.clone(),
     ));
     lost_cam_tracker.load_config().await.ok();
@@ -326,15 +563,30 @@ async fn main() -> anyhow::Result<()> {
         ipcam_scan,
         camera_brand,
         snapshot_service,
+        health,
+        supervisor: supervisor.clone(),
         system_health,
         polling: polling.clone(),
         inference_stats,
         auto_attunement,
         overdetection_analyzer,
+        camera_availability,
+        camera_diagnostics,
+        camera_maintenance,
+        storage_manager,
+        stream_reconciler,
+        live_layouts,
+        audit_log,
+        incident_cases,
+        metrics,
         aranea_register,
         summary_generator,
         grand_summary_generator,
+        rollup_generator,
         summary_repository,
+        summary_text_builder,
+        report_service,
+        email_notifier,
         schedule_repository,
         paraclate_client,
         pubsub_subscriber,
@@ -353,12 +605,18 @@ async fn main() -> anyhow::Result<()> {
         state.schedule_repository.clone(),
         state.summary_generator.clone(),
         state.grand_summary_generator.clone(),
+        state.rollup_generator.clone(),
         state.paraclate_client.clone(),
         state.realtime.clone(),
-    ));
-    // Clone scheduler for later use before start() takes ownership
+        state.report_service.clone(),
+        state.email_notifier.clone(),
+    )
+    .with_health(state.health.clone()));
+    // Clone scheduler for later use before run() takes ownership
     let scheduler_for_init = summary_scheduler.clone();
-    summary_scheduler.start().await;
+    supervisor.spawn("summary_scheduler", move |shutdown| {
+        summary_scheduler.clone().run(shutdown)
+    });
     tracing::info!("SummaryScheduler started (60-second tick interval)");
 
     // Auto-initialize schedules for all registered subnets (scan_subnets)
@@ -410,46 +668,65 @@ async fn main() -> anyhow::Result<()> {
     }
 
     // Start Paraclate queue processor background task (Phase 5: Issue #118)
-    // Processes pending queue items and sends to mobes2.0 every 30 seconds
+    // Processes pending queue items and sends to mobes2.0 (tasks.paraclate_queue_secs)
     let queue_paraclate_client = state.paraclate_client.clone();
-    tokio::spawn(async move {
-        let mut interval = tokio::time::interval(Duration::from_secs(30));
-        loop {
-            interval.tick().await;
-
-            // Get all connected configs
-            match queue_paraclate_client.config_repo().get_all_connected().await {
-                Ok(configs) => {
-                    for config in configs {
-                        match queue_paraclate_client.process_queue(&config.tid, &config.fid).await {
-                            Ok(sent) => {
-                                if sent > 0 {
-                                    tracing::info!(
+    let queue_health = state.health.clone();
+    queue_health.register(
+        "paraclate_queue",
+        stall_threshold(Duration::from_secs(tasks.paraclate_queue_secs)),
+    );
+    supervisor.spawn_periodic(
+        "paraclate_queue",
+        Duration::from_secs(tasks.paraclate_queue_secs),
+        Duration::ZERO,
+        move || {
+            let queue_paraclate_client = queue_paraclate_client.clone();
+            let queue_health = queue_health.clone();
+            async move {
+                // Get all connected configs
+                match queue_paraclate_client.config_repo().get_all_connected().await {
+                    Ok(configs) => {
+                        let mut last_error = None;
+                        for config in configs {
+                            match queue_paraclate_client.process_queue(&config.tid, &config.fid).await {
+                                Ok(sent) => {
+                                    if sent > 0 {
+                                        tracing::info!(
+                                            tid = %config.tid,
+                                            fid = %config.fid,
+                                            sent_count = sent,
+                                            "Paraclate queue processed"
+                                        );
+                                    }
+                                }
+                                Err(e) => {
+                                    tracing::warn!(
                                         tid = %config.tid,
                                         fid = %config.fid,
-                                        sent_count = sent,
-                                        "Paraclate queue processed"
+                                        error = %e,
+                                        "Paraclate queue processing failed"
                                     );
+                                    last_error = Some(format!("{}: {}", config.fid, e));
                                 }
                             }
-                            Err(e) => {
-                                tracing::warn!(
-                                    tid = %config.tid,
-                                    fid = %config.fid,
-                                    error = %e,
-                                    "Paraclate queue processing failed"
-                                );
-                            }
+                        }
+                        match last_error {
+                            Some(e) => queue_health.tick_failed("paraclate_queue", e),
+                            None => queue_health.tick_ok("paraclate_queue"),
                         }
                     }
-                }
-                Err(e) => {
-                    tracing::error!(error = %e, "Failed to get connected configs for queue processing");
+                    Err(e) => {
+                        tracing::error!(error = %e, "Failed to get connected configs for queue processing");
+                        queue_health.tick_failed("paraclate_queue", e);
+                    }
                 }
             }
-        }
-    });
-    tracing::info!("Paraclate queue processor started (30-second interval)");
+        },
+    );
+    tracing::info!(
+        interval_secs = tasks.paraclate_queue_secs,
+        "Paraclate queue processor started"
+    );
 
     // Create router with static file serving
     let static_dir = std::env::var("STATIC_DIR").unwrap_or_else(|_| "/opt/is22/frontend/dist".to_string());
@@ -465,87 +742,253 @@ async fn main() -> anyhow::Result<()> {
 
     // Start cleanup task
     let admission_cleanup = state.admission.clone();
-    tokio::spawn(async move {
-        let mut interval = tokio::time::interval(Duration::from_secs(10));
-        loop {
-            interval.tick().await;
-            admission_cleanup.cleanup().await;
-        }
-    });
+    supervisor.spawn_periodic(
+        "admission_cleanup",
+        Duration::from_secs(tasks.admission_cleanup_secs),
+        Duration::ZERO,
+        move || {
+            let admission_cleanup = admission_cleanup.clone();
+            async move { admission_cleanup.cleanup().await }
+        },
+    );
+
+    // Start RealtimeHub fid index refresh task (WebSocket fid subscriptions)
+    let realtime_index = state.realtime.clone();
+    let realtime_index_config = state.config_store.clone();
+    supervisor.spawn_periodic(
+        "realtime_fid_refresh",
+        Duration::from_secs(tasks.realtime_fid_refresh_secs),
+        Duration::ZERO,
+        move || {
+            let realtime_index = realtime_index.clone();
+            let realtime_index_config = realtime_index_config.clone();
+            async move {
+                let camera_fids = realtime_index_config
+                    .get_cached_cameras()
+                    .await
+                    .into_iter()
+                    .filter_map(|c| c.fid.map(|fid| (c.camera_id, fid)))
+                    .collect();
+                realtime_index.set_camera_fids(camera_fids).await;
+            }
+        },
+    );
 
     // Start suggest expiration task
     let suggest_cleanup = state.suggest.clone();
-    tokio::spawn(async move {
-        let mut interval = tokio::time::interval(Duration::from_secs(5));
-        loop {
-            interval.tick().await;
-            suggest_cleanup.check_expiration().await;
-        }
-    });
+    supervisor.spawn_periodic(
+        "suggest_expiration",
+        Duration::from_secs(tasks.suggest_expiration_secs),
+        Duration::ZERO,
+        move || {
+            let suggest_cleanup = suggest_cleanup.clone();
+            async move {
+                suggest_cleanup.check_expiration().await;
+            }
+        },
+    );
 
     // Start AccessAbsorber session cleanup task (expired/stale sessions)
     if let Some(access_absorber_cleanup) = state.access_absorber.clone() {
-        tokio::spawn(async move {
-            let mut interval = tokio::time::interval(Duration::from_secs(60));
-            loop {
-                interval.tick().await;
-                match access_absorber_cleanup.cleanup_expired().await {
-                    Ok(count) => {
-                        if count > 0 {
-                            tracing::info!(cleaned = count, "AccessAbsorber session cleanup completed");
+        let absorber_health = state.health.clone();
+        absorber_health.register(
+            "absorber_cleanup",
+            stall_threshold(Duration::from_secs(tasks.absorber_cleanup_secs)),
+        );
+        supervisor.spawn_periodic(
+            "absorber_cleanup",
+            Duration::from_secs(tasks.absorber_cleanup_secs),
+            Duration::ZERO,
+            move || {
+                let access_absorber_cleanup = access_absorber_cleanup.clone();
+                let absorber_health = absorber_health.clone();
+                async move {
+                    match access_absorber_cleanup.cleanup_expired().await {
+                        Ok(count) => {
+                            if count > 0 {
+                                tracing::info!(cleaned = count, "AccessAbsorber session cleanup completed");
+                            }
+                            absorber_health.tick_ok("absorber_cleanup");
+                        }
+                        Err(e) => {
+                            tracing::warn!(error = %e, "AccessAbsorber session cleanup failed");
+                            absorber_health.tick_failed("absorber_cleanup", e);
                         }
-                    }
-                    Err(e) => {
-                        tracing::warn!(error = %e, "AccessAbsorber session cleanup failed");
                     }
                 }
-            }
-        });
-        tracing::info!("AccessAbsorber session cleanup task started (60-second interval)");
+            },
+        );
+        tracing::info!(
+            interval_secs = tasks.absorber_cleanup_secs,
+            "AccessAbsorber session cleanup task started"
+        );
     }
 
     // Start credential cleanup task (#83 T2-11)
-    // Clears tried_credentials older than 24 hours (runs every hour)
+    // Clears tried_credentials older than 24 hours (tasks.credential_cleanup_secs)
     let ipcam_scan_cleanup = state.ipcam_scan.clone();
-    tokio::spawn(async move {
-        let mut interval = tokio::time::interval(Duration::from_secs(3600)); // Every hour
-        loop {
-            interval.tick().await;
-            if let Err(e) = ipcam_scan_cleanup.cleanup_tried_credentials().await {
-                tracing::error!(error = %e, "Failed to cleanup expired credentials");
+    supervisor.spawn_periodic(
+        "credential_cleanup",
+        Duration::from_secs(tasks.credential_cleanup_secs),
+        Duration::ZERO,
+        move || {
+            let ipcam_scan_cleanup = ipcam_scan_cleanup.clone();
+            async move {
+                if let Err(e) = ipcam_scan_cleanup.cleanup_tried_credentials().await {
+                    tracing::error!(error = %e, "Failed to cleanup expired credentials");
+                }
             }
-        }
-    });
+        },
+    );
 
     // Start LostCamTracker background task (DHCP追随)
     // ARPスキャンのみ使用（カメラ負荷ゼロ）
-    // 10分間隔で実行（閾値30分、リトライ間隔60分）
+    // tasks.lost_cam_tracker_secs 間隔で実行（既定10分、閾値30分、リトライ間隔60分）
+    // 起動直後は少し待機（他サービスの初期化完了を待つ）
     let lost_cam_tracker_task = lost_cam_tracker.clone();
-    tokio::spawn(async move {
-        // 起動直後は少し待機（他サービスの初期化完了を待つ）
-        tokio::time::sleep(Duration::from_secs(60)).await;
-
-        let mut interval = tokio::time::interval(Duration::from_secs(600)); // 10分間隔
-        loop {
-            interval.tick().await;
-
-            match lost_cam_tracker_task.run_tracking_cycle().await {
-                Ok(results) => {
-                    if !results.is_empty() {
-                        tracing::info!(
-                            relocated_count = results.len(),
-                            cameras = ?results.iter().map(|r| format!("{}: {} -> {}", r.camera_name, r.old_ip, r.new_ip)).collect::<Vec<_>>(),
-                            "LostCamTracker: Camera IPs relocated via ARP"
-                        );
+    let tracker_health = state.health.clone();
+    // 初回待機分も停止判定の猶予に含める
+    tracker_health.register(
+        "lost_cam_tracker",
+        stall_threshold(Duration::from_secs(tasks.lost_cam_tracker_secs))
+            + Duration::from_secs(tasks.lost_cam_tracker_initial_delay_secs),
+    );
+    supervisor.spawn_periodic(
+        "lost_cam_tracker",
+        Duration::from_secs(tasks.lost_cam_tracker_secs),
+        Duration::from_secs(tasks.lost_cam_tracker_initial_delay_secs),
+        move || {
+            let lost_cam_tracker_task = lost_cam_tracker_task.clone();
+            let tracker_health = tracker_health.clone();
+            async move {
+                match lost_cam_tracker_task.run_tracking_cycle().await {
+                    Ok(results) => {
+                        if !results.is_empty() {
+                            tracing::info!(
+                                relocated_count = results.len(),
+                                cameras = ?results.iter().map(|r| format!("{}: {} -> {}", r.camera_name, r.old_ip, r.new_ip)).collect::<Vec<_>>(),
+                                "LostCamTracker: Camera IPs relocated via ARP"
+                            );
+                        }
+                        tracker_health.tick_ok("lost_cam_tracker");
+                    }
+                    Err(e) => {
+                        tracing::warn!(error = %e, "LostCamTracker tracking cycle failed");
+                        tracker_health.tick_failed("lost_cam_tracker", e);
                     }
                 }
-                Err(e) => {
-                    tracing::warn!(error = %e, "LostCamTracker tracking cycle failed");
+            }
+        },
+    );
+    tracing::info!(
+        interval_secs = tasks.lost_cam_tracker_secs,
+        "LostCamTracker background task started (ARP-only)"
+    );
+
+    // Start StorageManager task (disk pressure warnings + watermark eviction)
+    let storage_task = state.storage_manager.clone();
+    let storage_health = state.health.clone();
+    storage_health.register(
+        "storage_manager",
+        stall_threshold(Duration::from_secs(tasks.storage_manager_secs)),
+    );
+    supervisor.spawn_periodic(
+        "storage_manager",
+        Duration::from_secs(tasks.storage_manager_secs),
+        Duration::ZERO,
+        move || {
+            let storage_task = storage_task.clone();
+            let storage_health = storage_health.clone();
+            async move {
+                match storage_task.run_cycle().await {
+                    Ok(report) => {
+                        tracing::debug!(level = report.level.as_str(), "Storage check completed");
+                        storage_health.tick_ok("storage_manager");
+                    }
+                    Err(e) => {
+                        tracing::warn!(error = %e, "Storage check failed");
+                        storage_health.tick_failed("storage_manager", e);
+                    }
+                }
+            }
+        },
+    );
+    tracing::info!(
+        interval_secs = tasks.storage_manager_secs,
+        "StorageManager task started"
+    );
+
+    // Start closed-loop auto attunement task
+    // ポリシーを毎サイクル読み直す（enabled=false の間は何もしない）
+    let attunement_task = state.auto_attunement.clone();
+    let attunement_config = state.config_store.clone();
+    supervisor.spawn("auto_attunement", move |shutdown| {
+        let attunement_task = attunement_task.clone();
+        let attunement_config = attunement_config.clone();
+        async move {
+            tokio::select! {
+                _ = shutdown.cancelled() => return,
+                _ = tokio::time::sleep(Duration::from_secs(120)) => {}
+            }
+            loop {
+                let policy = attunement_config
+                    .service()
+                    .get_attunement_policy()
+                    .await
+                    .unwrap_or_default();
+
+                if policy.enabled {
+                    match attunement_task
+                        .run_auto_adjustment(&policy)
+                        .await
+                    {
+                        Ok(events) => {
+                            if !events.is_empty() {
+                                if let Err(e) = attunement_config.refresh_cache().await {
+                                    tracing::warn!(error = %e, "Failed to refresh config cache after attunement");
+                                }
+                            }
+                        }
+                        Err(e) => {
+                            tracing::warn!(error = %e, "Auto attunement cycle failed");
+                        }
+                    }
+                }
+
+                let interval_min = policy.evaluation_interval_min.max(1) as u64;
+                tokio::select! {
+                    _ = shutdown.cancelled() => break,
+                    _ = tokio::time::sleep(Duration::from_secs(interval_min * 60)) => {}
                 }
             }
         }
     });
-    tracing::info!("LostCamTracker background task started (10-minute interval, ARP-only)");
+    tracing::info!("Auto attunement task started");
+
+    // Email queue worker (detection alerts / reports / daily summary)
+    let email_worker = state.email_notifier.clone();
+    supervisor.spawn("email_notifier", move |shutdown| {
+        email_worker
+            .clone()
+            .run(default_tid.clone(), default_fid.clone(), shutdown)
+    });
+    tracing::info!("Email queue worker started");
+
+    // Periodic camera diagnostics / ONVIF maintenance / go2rtc reconcile
+    // 間隔は各サービスの設定から毎サイクル読み直す
+    let diagnostics = state.camera_diagnostics.clone();
+    supervisor.spawn("camera_diagnostics", move |shutdown| {
+        diagnostics.clone().run(shutdown)
+    });
+    let maintenance = state.camera_maintenance.clone();
+    supervisor.spawn("camera_maintenance", move |shutdown| {
+        maintenance.clone().run(shutdown)
+    });
+    let reconciler = state.stream_reconciler.clone();
+    supervisor.spawn("stream_reconciler", move |shutdown| {
+        reconciler.clone().run(shutdown)
+    });
+    tracing::info!("CameraDiagnostics / CameraMaintenance / StreamReconciler tasks started");
 
     // Start polling orchestrator (is21 AI integration)
     polling.start().await;
@@ -553,32 +996,38 @@ async fn main() -> anyhow::Result<()> {
 
     // Start system health monitoring
     let health_monitor = state.system_health.clone();
-    tokio::spawn(async move {
-        use sysinfo::System;
-        let mut sys = System::new_all();
-        let mut interval = tokio::time::interval(Duration::from_secs(30));
-
-        loop {
-            interval.tick().await;
-            sys.refresh_all();
-
-            // Calculate average CPU usage across all cores
-            let cpu = {
-                let cpus = sys.cpus();
-                if cpus.is_empty() {
-                    0.0
-                } else {
-                    cpus.iter().map(|c| c.cpu_usage()).sum::<f32>() / cpus.len() as f32
+    supervisor.spawn("system_health_monitor", move |shutdown| {
+        let health_monitor = health_monitor.clone();
+        async move {
+            use sysinfo::System;
+            let mut sys = System::new_all();
+            let mut interval = tokio::time::interval(Duration::from_secs(tasks.health_monitor_secs));
+
+            loop {
+                tokio::select! {
+                    _ = shutdown.cancelled() => break,
+                    _ = interval.tick() => {}
                 }
-            };
-            let memory = if sys.total_memory() > 0 {
-                (sys.used_memory() as f32 / sys.total_memory() as f32) * 100.0
-            } else {
-                0.0
-            };
+                sys.refresh_all();
 
-            let mut health = health_monitor.write().await;
-            health.update(cpu, memory);
+                // Calculate average CPU usage across all cores
+                let cpu = {
+                    let cpus = sys.cpus();
+                    if cpus.is_empty() {
+                        0.0
+                    } else {
+                        cpus.iter().map(|c| c.cpu_usage()).sum::<f32>() / cpus.len() as f32
+                    }
+                };
+                let memory = if sys.total_memory() > 0 {
+                    (sys.used_memory() as f32 / sys.total_memory() as f32) * 100.0
+                } else {
+                    0.0
+                };
+
+                let mut health = health_monitor.write().await;
+                health.update(cpu, memory);
+            }
         }
     });
 
@@ -587,7 +1036,36 @@ async fn main() -> anyhow::Result<()> {
     let listener = tokio::net::TcpListener::bind(&addr).await?;
     tracing::info!("Listening on {}", addr);
 
-    axum::serve(listener, app).await?;
+    // Graceful shutdown: SIGTERM / Ctrl-C → 停止シーケンス完了後に HTTP を閉じる
+    let shutdown_state = state.clone();
+    let shutdown_supervisor = supervisor.clone();
+    let shutdown_timeout = Duration::from_secs(tasks.shutdown_timeout_secs);
+    let (drained_tx, drained_rx) = tokio::sync::oneshot::channel::<()>();
+    // ConnectInfo: 監査ログの接続元IP
+    let server = axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+    )
+    .with_graceful_shutdown(async move {
+        shutdown_signal().await;
+        graceful_shutdown(&shutdown_state, &shutdown_supervisor, shutdown_timeout).await;
+        let _ = drained_tx.send(());
+    });
+
+    // 長寿命の HTTP 接続（SSE など）が残っていても一定時間で終了する
+    tokio::select! {
+        result = server.into_future() => result?,
+        _ = async {
+            if drained_rx.await.is_ok() {
+                tokio::time::sleep(HTTP_DRAIN_TIMEOUT).await;
+            } else {
+                std::future::pending::<()>().await;
+            }
+        } => {
+            tracing::warn!("HTTP connections did not drain in time; exiting");
+        }
+    }
+    tracing::info!("Server stopped");
 
     Ok(())
 }
@@ -0,0 +1,259 @@
+//! Metric families and Prometheus text encoder
+//!
+//! 外部クレートに依存しない最小実装。ラベル値の組ごとに系列を保持し、
+//! スクレイプ時に text exposition format 0.0.4 で出力する。
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+
+/// Content-Type of the text exposition format
+pub const TEXT_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Metric type written in `# TYPE`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricType {
+    Counter,
+    Gauge,
+    Histogram,
+}
+
+impl MetricType {
+    fn as_str(&self) -> &'static str {
+        match self {
+            MetricType::Counter => "counter",
+            MetricType::Gauge => "gauge",
+            MetricType::Histogram => "histogram",
+        }
+    }
+}
+
+/// Prometheus text format writer
+#[derive(Debug, Default)]
+pub struct TextEncoder {
+    out: String,
+}
+
+impl TextEncoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Write `# HELP` / `# TYPE` of a family
+    pub fn family(&mut self, name: &str, help: &str, metric_type: MetricType) {
+        let _ = writeln!(self.out, "# HELP {} {}", name, help.replace('\n', " "));
+        let _ = writeln!(self.out, "# TYPE {} {}", name, metric_type.as_str());
+    }
+
+    /// Write one sample line
+    pub fn sample(&mut self, name: &str, labels: &[(&str, &str)], value: f64) {
+        self.out.push_str(name);
+        if !labels.is_empty() {
+            self.out.push('{');
+            for (i, (key, val)) in labels.iter().enumerate() {
+                if i > 0 {
+                    self.out.push(',');
+                }
+                let _ = write!(self.out, "{}=\"{}\"", key, escape_label(val));
+            }
+            self.out.push('}');
+        }
+        let _ = writeln!(self.out, " {}", format_value(value));
+    }
+
+    /// Single unlabelled gauge
+    pub fn gauge(&mut self, name: &str, help: &str, value: f64) {
+        self.family(name, help, MetricType::Gauge);
+        self.sample(name, &[], value);
+    }
+
+    pub fn finish(self) -> String {
+        self.out
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn format_value(value: f64) -> String {
+    if value.is_infinite() {
+        if value > 0.0 { "+Inf" } else { "-Inf" }.to_string()
+    } else if value.is_nan() {
+        "NaN".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+fn label_pairs<'a>(names: &'a [&'static str], values: &'a [String]) -> Vec<(&'a str, &'a str)> {
+    names
+        .iter()
+        .zip(values.iter())
+        .map(|(n, v)| (*n, v.as_str()))
+        .collect()
+}
+
+/// Labelled monotonic counter
+pub struct CounterVec {
+    name: &'static str,
+    help: &'static str,
+    label_names: &'static [&'static str],
+    series: Mutex<BTreeMap<Vec<String>, u64>>,
+}
+
+impl CounterVec {
+    pub fn new(
+        name: &'static str,
+        help: &'static str,
+        label_names: &'static [&'static str],
+    ) -> Self {
+        Self {
+            name,
+            help,
+            label_names,
+            series: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Increment by one (`labels` in label_names order)
+    pub fn inc(&self, labels: &[&str]) {
+        debug_assert_eq!(labels.len(), self.label_names.len());
+        let key = labels.iter().map(|s| s.to_string()).collect();
+        let mut series = self.series.lock().unwrap_or_else(|e| e.into_inner());
+        *series.entry(key).or_insert(0) += 1;
+    }
+
+    pub fn encode(&self, enc: &mut TextEncoder) {
+        enc.family(self.name, self.help, MetricType::Counter);
+        let series = self.series.lock().unwrap_or_else(|e| e.into_inner());
+        for (values, count) in series.iter() {
+            enc.sample(
+                self.name,
+                &label_pairs(self.label_names, values),
+                *count as f64,
+            );
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct HistogramSeries {
+    /// Non-cumulative count per bucket (last = +Inf)
+    counts: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+/// Labelled histogram with fixed buckets
+pub struct HistogramVec {
+    name: &'static str,
+    help: &'static str,
+    label_names: &'static [&'static str],
+    buckets: &'static [f64],
+    series: Mutex<BTreeMap<Vec<String>, HistogramSeries>>,
+}
+
+impl HistogramVec {
+    /// `buckets` must be sorted ascending (+Inf is implicit)
+    pub fn new(
+        name: &'static str,
+        help: &'static str,
+        label_names: &'static [&'static str],
+        buckets: &'static [f64],
+    ) -> Self {
+        Self {
+            name,
+            help,
+            label_names,
+            buckets,
+            series: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    pub fn observe(&self, labels: &[&str], value: f64) {
+        debug_assert_eq!(labels.len(), self.label_names.len());
+        let key = labels.iter().map(|s| s.to_string()).collect();
+        let index = self
+            .buckets
+            .iter()
+            .position(|b| value <= *b)
+            .unwrap_or(self.buckets.len());
+
+        let mut series = self.series.lock().unwrap_or_else(|e| e.into_inner());
+        let entry = series.entry(key).or_insert_with(|| HistogramSeries {
+            counts: vec![0; self.buckets.len() + 1],
+            sum: 0.0,
+            count: 0,
+        });
+        entry.counts[index] += 1;
+        entry.sum += value;
+        entry.count += 1;
+    }
+
+    pub fn encode(&self, enc: &mut TextEncoder) {
+        enc.family(self.name, self.help, MetricType::Histogram);
+        let bucket_name = format!("{}_bucket", self.name);
+        let sum_name = format!("{}_sum", self.name);
+        let count_name = format!("{}_count", self.name);
+
+        let series = self.series.lock().unwrap_or_else(|e| e.into_inner());
+        for (values, data) in series.iter() {
+            let labels = label_pairs(self.label_names, values);
+            let mut cumulative = 0u64;
+            for (i, count) in data.counts.iter().enumerate() {
+                cumulative += count;
+                let le = self
+                    .buckets
+                    .get(i)
+                    .map(|b| format_value(*b))
+                    .unwrap_or_else(|| "+Inf".to_string());
+                let mut bucket_labels = labels.clone();
+                bucket_labels.push(("le", le.as_str()));
+                enc.sample(&bucket_name, &bucket_labels, cumulative as f64);
+            }
+            enc.sample(&sum_name, &labels, data.sum);
+            enc.sample(&count_name, &labels, data.count as f64);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_histogram_encoding_is_cumulative() {
+        let histogram = HistogramVec::new("test_seconds", "Test", &["camera_id"], &[0.5, 1.0]);
+        histogram.observe(&["cam-1"], 0.25);
+        histogram.observe(&["cam-1"], 0.75);
+        histogram.observe(&["cam-1"], 3.0);
+
+        let mut enc = TextEncoder::new();
+        histogram.encode(&mut enc);
+        let text = enc.finish();
+
+        assert!(text.contains("# TYPE test_seconds histogram"));
+        assert!(text.contains("test_seconds_bucket{camera_id=\"cam-1\",le=\"0.5\"} 1\n"));
+        assert!(text.contains("test_seconds_bucket{camera_id=\"cam-1\",le=\"1\"} 2\n"));
+        assert!(text.contains("test_seconds_bucket{camera_id=\"cam-1\",le=\"+Inf\"} 3\n"));
+        assert!(text.contains("test_seconds_sum{camera_id=\"cam-1\"} 4\n"));
+        assert!(text.contains("test_seconds_count{camera_id=\"cam-1\"} 3\n"));
+    }
+
+    #[test]
+    fn test_counter_label_escaping() {
+        let counter = CounterVec::new("test_total", "Test", &["reason"]);
+        counter.inc(&["say \"hi\"\n"]);
+        counter.inc(&["say \"hi\"\n"]);
+
+        let mut enc = TextEncoder::new();
+        counter.encode(&mut enc);
+        assert!(enc
+            .finish()
+            .contains("test_total{reason=\"say \\\"hi\\\"\\n\"} 2\n"));
+    }
+}
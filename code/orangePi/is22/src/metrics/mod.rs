@@ -0,0 +1,293 @@
+//! Metrics - Prometheus/OpenMetrics exporter
+//!
+//! ## Responsibilities
+//!
+//! - ポーリング経路で計測する counter / histogram の保持（スナップショット・IS21推論・巡回周期）
+//! - Paraclate 送信失敗・AccessAbsorber 拒否/プリエンプションの counter（各サービスが記録）
+//! - スクレイプ時に DB / OS から取得するゲージ（Paraclate送信キュー、ディスク）
+//!
+//! GET /metrics で text exposition format を返す。カメラ単位は camera_id、
+//! 巡回単位は subnet でラベル付けする。
+
+mod family;
+
+pub use family::{CounterVec, HistogramVec, MetricType, TextEncoder, TEXT_CONTENT_TYPE};
+
+use crate::snapshot_service::SnapshotSource;
+use sqlx::MySqlPool;
+use std::time::{Duration, Instant};
+
+const SNAPSHOT_BUCKETS: &[f64] = &[0.1, 0.25, 0.5, 1.0, 2.0, 5.0, 10.0, 20.0];
+const INFERENCE_BUCKETS: &[f64] = &[0.05, 0.1, 0.25, 0.5, 1.0, 2.0, 5.0, 10.0];
+const CYCLE_BUCKETS: &[f64] = &[5.0, 10.0, 30.0, 60.0, 120.0, 300.0, 600.0, 1200.0];
+
+/// Result of one camera poll (label of is22_polling_camera_polls_total)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PollResult {
+    Success,
+    NoChange,
+    Timeout,
+    Failed,
+}
+
+impl PollResult {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PollResult::Success => "success",
+            PollResult::NoChange => "no_change",
+            PollResult::Timeout => "timeout",
+            PollResult::Failed => "failed",
+        }
+    }
+}
+
+/// In-process metrics recorded by the polling pipeline
+pub struct Metrics {
+    started_at: Instant,
+    snapshot_duration: HistogramVec,
+    snapshot_failures: CounterVec,
+    inference_duration: HistogramVec,
+    inference_errors: CounterVec,
+    inference_skipped: CounterVec,
+    cycle_duration: HistogramVec,
+    camera_polls: CounterVec,
+    paraclate_send_failures: CounterVec,
+    absorber_events: CounterVec,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Self {
+            started_at: Instant::now(),
+            snapshot_duration: HistogramVec::new(
+                "is22_snapshot_duration_seconds",
+                "Snapshot capture latency by source",
+                &["camera_id", "source"],
+                SNAPSHOT_BUCKETS,
+            ),
+            snapshot_failures: CounterVec::new(
+                "is22_snapshot_failures_total",
+                "Snapshot capture failures",
+                &["camera_id", "reason"],
+            ),
+            inference_duration: HistogramVec::new(
+                "is22_is21_inference_duration_seconds",
+                "IS21 inference round-trip latency",
+                &["camera_id"],
+                INFERENCE_BUCKETS,
+            ),
+            inference_errors: CounterVec::new(
+                "is22_is21_inference_errors_total",
+                "IS21 inference request failures",
+                &["camera_id"],
+            ),
+            inference_skipped: CounterVec::new(
+                "is22_is21_inference_skipped_total",
+                "IS21 inferences skipped by the motion pre-filter",
+                &["camera_id"],
+            ),
+            cycle_duration: HistogramVec::new(
+                "is22_polling_cycle_duration_seconds",
+                "Polling cycle duration per subnet",
+                &["subnet"],
+                CYCLE_BUCKETS,
+            ),
+            camera_polls: CounterVec::new(
+                "is22_polling_camera_polls_total",
+                "Camera polls by result",
+                &["subnet", "result"],
+            ),
+            paraclate_send_failures: CounterVec::new(
+                "is22_paraclate_send_failures_total",
+                "Failed Paraclate send attempts",
+                &["fid", "payload_type", "reason"],
+            ),
+            absorber_events: CounterVec::new(
+                "is22_absorber_events_total",
+                "AccessAbsorber rejections and preemptions",
+                &["camera_id", "event"],
+            ),
+        }
+    }
+
+    pub fn observe_snapshot(&self, camera_id: &str, source: SnapshotSource, elapsed: Duration) {
+        self.snapshot_duration
+            .observe(&[camera_id, source.as_str()], elapsed.as_secs_f64());
+    }
+
+    /// `reason`: "error" | "timeout"
+    pub fn snapshot_failed(&self, camera_id: &str, reason: &str) {
+        self.snapshot_failures.inc(&[camera_id, reason]);
+    }
+
+    pub fn observe_inference(&self, camera_id: &str, elapsed: Duration) {
+        self.inference_duration
+            .observe(&[camera_id], elapsed.as_secs_f64());
+    }
+
+    pub fn inference_failed(&self, camera_id: &str) {
+        self.inference_errors.inc(&[camera_id]);
+    }
+
+    pub fn inference_skipped(&self, camera_id: &str) {
+        self.inference_skipped.inc(&[camera_id]);
+    }
+
+    pub fn observe_cycle(&self, subnet: &str, elapsed: Duration) {
+        self.cycle_duration
+            .observe(&[subnet], elapsed.as_secs_f64());
+    }
+
+    pub fn record_poll(&self, subnet: &str, result: PollResult) {
+        self.camera_polls.inc(&[subnet, result.as_str()]);
+    }
+
+    /// `reason`: "http"（非2xx） | "network"（送信エラー） | "rejected"（2xxだが ok=false）
+    pub fn paraclate_send_failed(&self, fid: &str, payload_type: &str, reason: &str) {
+        self.paraclate_send_failures.inc(&[fid, payload_type, reason]);
+    }
+
+    /// `event`: camera_connection_events.event_type（connect_blocked_* / *_preempted）
+    pub fn absorber_event(&self, camera_id: &str, event: &str) {
+        self.absorber_events.inc(&[camera_id, event]);
+    }
+
+    /// Encode in-process metrics
+    pub fn encode(&self, enc: &mut TextEncoder) {
+        enc.gauge(
+            "is22_uptime_seconds",
+            "Seconds since the camserver process started",
+            self.started_at.elapsed().as_secs_f64(),
+        );
+        self.snapshot_duration.encode(enc);
+        self.snapshot_failures.encode(enc);
+        self.inference_duration.encode(enc);
+        self.inference_errors.encode(enc);
+        self.inference_skipped.encode(enc);
+        self.cycle_duration.encode(enc);
+        self.camera_polls.encode(enc);
+        self.paraclate_send_failures.encode(enc);
+        self.absorber_events.encode(enc);
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Encode gauges derived from database tables
+///
+/// 取得失敗は警告のみ（スクレイプ全体は失敗させない）
+pub async fn encode_database_metrics(pool: &MySqlPool, enc: &mut TextEncoder) {
+    let queue: Result<Vec<(String, String, i64, i64)>, sqlx::Error> = sqlx::query_as(
+        "SELECT fid, CAST(status AS CHAR) AS status, COUNT(*) AS items, \
+         CAST(COALESCE(SUM(retry_count), 0) AS SIGNED) AS retries \
+         FROM paraclate_send_queue WHERE status IN ('pending', 'sending', 'failed') \
+         GROUP BY fid, status",
+    )
+    .fetch_all(pool)
+    .await;
+    match queue {
+        Ok(rows) => {
+            enc.family(
+                "is22_paraclate_queue_items",
+                "Paraclate send queue depth by status",
+                MetricType::Gauge,
+            );
+            for (fid, status, items, _) in &rows {
+                enc.sample(
+                    "is22_paraclate_queue_items",
+                    &[("fid", fid), ("status", status)],
+                    *items as f64,
+                );
+            }
+            enc.family(
+                "is22_paraclate_queue_retries",
+                "Failed send attempts of queued Paraclate payloads",
+                MetricType::Gauge,
+            );
+            for (fid, status, _, retries) in &rows {
+                enc.sample(
+                    "is22_paraclate_queue_retries",
+                    &[("fid", fid), ("status", status)],
+                    *retries as f64,
+                );
+            }
+        }
+        Err(e) => tracing::warn!(error = %e, "Metrics: failed to read paraclate_send_queue"),
+    }
+}
+
+/// Encode filesystem usage of mounted disks
+pub fn encode_disk_metrics(enc: &mut TextEncoder) {
+    let disks = sysinfo::Disks::new_with_refreshed_list();
+    let samples: Vec<(String, u64, u64)> = disks
+        .iter()
+        .filter(|d| d.total_space() > 0)
+        .map(|d| {
+            (
+                d.mount_point().to_string_lossy().to_string(),
+                d.total_space(),
+                d.available_space(),
+            )
+        })
+        .collect();
+
+    enc.family(
+        "is22_disk_total_bytes",
+        "Filesystem size",
+        MetricType::Gauge,
+    );
+    for (mount, total, _) in &samples {
+        enc.sample("is22_disk_total_bytes", &[("mount", mount)], *total as f64);
+    }
+    enc.family(
+        "is22_disk_available_bytes",
+        "Filesystem free space available",
+        MetricType::Gauge,
+    );
+    for (mount, _, available) in &samples {
+        enc.sample(
+            "is22_disk_available_bytes",
+            &[("mount", mount)],
+            *available as f64,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pipeline_metrics_encode() {
+        let metrics = Metrics::new();
+        metrics.observe_snapshot("cam-1", SnapshotSource::Go2rtc, Duration::from_millis(300));
+        metrics.snapshot_failed("cam-2", "timeout");
+        metrics.record_poll("192.168.125", PollResult::NoChange);
+        metrics.paraclate_send_failed("fid-1", "event", "network");
+        metrics.paraclate_send_failed("fid-1", "event", "network");
+        metrics.absorber_event("cam-1", "connect_blocked_concurrent");
+
+        let mut enc = TextEncoder::new();
+        metrics.encode(&mut enc);
+        let text = enc.finish();
+
+        assert!(text.contains(
+            "is22_snapshot_duration_seconds_bucket{camera_id=\"cam-1\",source=\"go2rtc\",le=\"0.5\"} 1\n"
+        ));
+        assert!(text
+            .contains("is22_snapshot_failures_total{camera_id=\"cam-2\",reason=\"timeout\"} 1\n"));
+        assert!(text.contains(
+            "is22_polling_camera_polls_total{subnet=\"192.168.125\",result=\"no_change\"} 1\n"
+        ));
+        assert!(text.contains(
+            "is22_paraclate_send_failures_total{fid=\"fid-1\",payload_type=\"event\",reason=\"network\"} 2\n"
+        ));
+        assert!(text.contains(
+            "is22_absorber_events_total{camera_id=\"cam-1\",event=\"connect_blocked_concurrent\"} 1\n"
+        ));
+    }
+}
@@ -0,0 +1,65 @@
+//! `camserver migrate` subcommand
+//!
+//! ```text
+//! camserver migrate [status]          適用状況の一覧
+//! camserver migrate up                未適用分を適用
+//! camserver migrate down <version>    version より新しいものを down で巻き戻す
+//! camserver migrate baseline <version> 既存DBを version まで適用済みとして記録
+//! ```
+
+use super::{MigrationState, Migrator};
+
+const USAGE: &str = "usage: camserver migrate [status | up | down <version> | baseline <version>]";
+
+/// Run a migrate subcommand (`args` excludes "migrate")
+pub async fn run(migrator: &Migrator, args: &[String]) -> crate::Result<()> {
+    let command = args.first().map(String::as_str).unwrap_or("status");
+    let version = || -> crate::Result<u32> {
+        args.get(1)
+            .and_then(|v| v.parse().ok())
+            .ok_or_else(|| crate::Error::Validation(USAGE.to_string()))
+    };
+
+    match command {
+        "status" => {
+            for status in migrator.status().await? {
+                let state = match status.state {
+                    MigrationState::Applied => "applied",
+                    MigrationState::Pending => "pending",
+                    MigrationState::ChecksumMismatch => "CHECKSUM MISMATCH",
+                    MigrationState::Unknown => "UNKNOWN (newer binary?)",
+                };
+                println!(
+                    "{:<40} {:<24} {}{}",
+                    status.name,
+                    state,
+                    status
+                        .applied_at
+                        .map(|t| t.to_rfc3339())
+                        .unwrap_or_default(),
+                    if status.has_down { "  [down]" } else { "" }
+                );
+            }
+        }
+        "up" => {
+            let applied = migrator.up().await?;
+            println!("applied {} migration(s)", applied.len());
+            for name in applied {
+                println!("  + {}", name);
+            }
+        }
+        "down" => {
+            let rolled_back = migrator.down(version()?).await?;
+            println!("rolled back {} migration(s)", rolled_back.len());
+            for name in rolled_back {
+                println!("  - {}", name);
+            }
+        }
+        "baseline" => {
+            let recorded = migrator.baseline(version()?).await?;
+            println!("recorded {} migration(s) as applied", recorded);
+        }
+        _ => return Err(crate::Error::Validation(USAGE.to_string())),
+    }
+    Ok(())
+}
@@ -0,0 +1,128 @@
+//! Embedded migration scripts
+//!
+//! migrations/ の SQL をバイナリに埋め込む。新しいマイグレーションを追加したら
+//! ここにも登録する（登録漏れはテストで検出）。
+//! `NNN_name.down.sql` があるものは `down` を付ける。
+
+use super::Migration;
+
+macro_rules! migration {
+    ($version:expr, $name:literal) => {
+        Migration {
+            version: $version,
+            name: $name,
+            up: include_str!(concat!("../../migrations/", $name, ".sql")),
+            down: None,
+        }
+    };
+    ($version:expr, $name:literal, down) => {
+        Migration {
+            version: $version,
+            name: $name,
+            up: include_str!(concat!("../../migrations/", $name, ".sql")),
+            down: Some(include_str!(concat!(
+                "../../migrations/",
+                $name,
+                ".down.sql"
+            ))),
+        }
+    };
+}
+
+/// All migrations in apply order (version, then name)
+pub static MIGRATIONS: &[Migration] = &[
+    migration!(1, "001_initial_schema"),
+    migration!(2, "002_camera_registration_flow"),
+    migration!(3, "003_detection_reason"),
+    migration!(4, "004_subnet_credentials"),
+    migration!(5, "005_camera_credentials"),
+    migration!(6, "006_subnet_tid"),
+    migration!(7, "007_camera_extended_fields"),
+    migration!(8, "008_detection_logs"),
+    migration!(9, "009_camera_preset_columns"),
+    migration!(10, "010_camera_fit_mode"),
+    migration!(11, "011_onvif_extended_data"),
+    migration!(12, "012_polling_cycles"),
+    migration!(13, "013_fix_suspicious_score_type"),
+    migration!(14, "014_camera_threshold_overrides"),
+    migration!(15, "015_oui_rtsp_ssot", down),
+    migration!(16, "016_oui_rtsp_backfill"),
+    migration!(17, "017_oui_expansion"),
+    migration!(18, "018_sdm_integration"),
+    migration!(19, "019_phase4_feedback_threshold"),
+    migration!(20, "020_aranea_registration"),
+    migration!(21, "021_camera_registry"),
+    migration!(22, "022_summary_service"),
+    migration!(23, "023_paraclate_client"),
+    migration!(25, "025_aranea_registration_fid"),
+    migration!(25, "025_chat_messages"),
+    migration!(26, "026_camera_sync_extension"),
+    migration!(27, "027_vehicle_details"),
+    migration!(28, "028_inference_config"),
+    migration!(29, "029_lost_cam_tracker"),
+    migration!(31, "031_access_absorber"),
+    migration!(32, "032_ptz_disabled"),
+    migration!(33, "033_motion_prefilter", down),
+    migration!(34, "034_preset_schedules", down),
+    migration!(35, "035_custom_presets", down),
+    migration!(36, "036_attunement_trials", down),
+    migration!(37, "037_summary_templates", down),
+    migration!(38, "038_report_attachments", down),
+    migration!(39, "039_email_notifications", down),
+    migration!(40, "040_summary_rollups", down),
+    migration!(41, "041_camera_availability", down),
+    migration!(42, "042_camera_maintenance", down),
+    migration!(43, "043_modal_leases", down),
+    migration!(44, "044_live_layouts", down),
+    migration!(45, "045_audit_log", down),
+    migration!(46, "046_incident_cases", down),
+    migration!(47, "047_unify_conf_threshold", down),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_embedded_list_matches_directory() {
+        let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("migrations");
+        let mut ups = BTreeSet::new();
+        let mut downs = BTreeSet::new();
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let file = entry.unwrap().file_name().to_string_lossy().to_string();
+            if let Some(stem) = file.strip_suffix(".down.sql") {
+                downs.insert(stem.to_string());
+            } else if let Some(stem) = file.strip_suffix(".sql") {
+                ups.insert(stem.to_string());
+            }
+        }
+
+        let embedded: BTreeSet<String> = MIGRATIONS.iter().map(|m| m.name.to_string()).collect();
+        assert_eq!(embedded, ups, "migrations/ and MIGRATIONS are out of sync");
+
+        let embedded_downs: BTreeSet<String> = MIGRATIONS
+            .iter()
+            .filter(|m| m.down.is_some())
+            .map(|m| m.name.to_string())
+            .collect();
+        assert_eq!(embedded_downs, downs);
+
+        for (i, m) in MIGRATIONS.iter().enumerate() {
+            assert_eq!(
+                m.name.split('_').next().and_then(|v| v.parse::<u32>().ok()),
+                Some(m.version),
+                "{} has a mismatched version",
+                m.name
+            );
+            if i > 0 {
+                let prev = &MIGRATIONS[i - 1];
+                assert!(
+                    (prev.version, prev.name) < (m.version, m.name),
+                    "{} is out of order",
+                    m.name
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,550 @@
+//! Migration - Embedded, versioned schema migrations
+//!
+//! ## Responsibilities
+//!
+//! - migrations/*.sql をバイナリに埋め込み、適用済みバージョンを schema_migrations に記録
+//! - チェックサムで適用後のスクリプト改変を検出
+//! - 起動時の自動適用 / `camserver migrate` サブコマンド
+//! - 明示的な down マイグレーション（`NNN_name.down.sql`）
+//!
+//! 番号の重複（025_*）や欠番があるため、識別子は (version, name) の組とする。
+//! DB にバイナリの知らないマイグレーションが適用済みの場合（DB が新しい）は起動を拒否する。
+//!
+//! SQLite バックエンドは別系列（migrations/sqlite/*.sql）を [`SqliteMigrator`] で適用する。
+
+pub mod cli;
+mod embedded;
+mod splitter;
+mod sqlite;
+
+pub use embedded::MIGRATIONS;
+pub use splitter::split_statements;
+pub use sqlite::{SqliteMigrator, SQLITE_MIGRATIONS};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sha1::{Digest, Sha1};
+use sqlx::pool::PoolConnection;
+use sqlx::{Executor, MySql, MySqlPool, Row};
+use std::time::Instant;
+
+/// Advisory lock name (prevents concurrent runners)
+const LOCK_NAME: &str = "is22_schema_migrations";
+const LOCK_TIMEOUT_SECS: i32 = 60;
+
+/// Embedded migration script
+#[derive(Debug)]
+pub struct Migration {
+    pub version: u32,
+    /// File stem (e.g. "025_chat_messages")
+    pub name: &'static str,
+    pub up: &'static str,
+    pub down: Option<&'static str>,
+}
+
+impl Migration {
+    pub fn checksum(&self) -> String {
+        checksum(self.up)
+    }
+}
+
+/// SHA-1 hex of a script (line endings normalized)
+pub fn checksum(sql: &str) -> String {
+    let digest = Sha1::digest(sql.replace("\r\n", "\n").as_bytes());
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Row of schema_migrations
+#[derive(Debug, Clone, Serialize)]
+pub struct AppliedMigration {
+    pub version: u32,
+    pub name: String,
+    pub checksum: String,
+    pub applied_at: DateTime<Utc>,
+    pub execution_ms: u32,
+}
+
+/// Per-migration state for `migrate status`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrationState {
+    Applied,
+    Pending,
+    /// Applied script differs from the embedded one
+    ChecksumMismatch,
+    /// Applied in the DB but unknown to this binary
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationStatus {
+    pub version: u32,
+    pub name: String,
+    pub state: MigrationState,
+    pub has_down: bool,
+    pub applied_at: Option<DateTime<Utc>>,
+}
+
+/// Comparison of embedded and applied migrations
+#[derive(Debug)]
+pub struct MigrationPlan<'a> {
+    pub pending: Vec<&'a Migration>,
+    pub mismatched: Vec<&'a Migration>,
+    pub unknown: Vec<AppliedMigration>,
+    /// Newest embedded version
+    pub latest: u32,
+}
+
+impl MigrationPlan<'_> {
+    /// Refuse to run when the DB does not match this binary
+    pub fn check_compatible(&self) -> crate::Result<()> {
+        let latest = self.latest;
+        if let Some(newest) = self.unknown.iter().max_by_key(|m| m.version) {
+            let reason = if newest.version > latest {
+                format!(
+                    "database schema is ahead of this binary (applied {} but the binary knows up to {:03})",
+                    newest.name, latest
+                )
+            } else {
+                format!(
+                    "database has migrations unknown to this binary: {}",
+                    self.unknown
+                        .iter()
+                        .map(|m| m.name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            };
+            return Err(crate::Error::Migration(reason));
+        }
+        if !self.mismatched.is_empty() {
+            return Err(crate::Error::Migration(format!(
+                "applied migrations were modified after being applied: {}",
+                self.mismatched
+                    .iter()
+                    .map(|m| m.name)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Compare embedded migrations with applied rows
+pub fn plan<'a>(embedded: &'a [Migration], applied: &[AppliedMigration]) -> MigrationPlan<'a> {
+    let mut pending = Vec::new();
+    let mut mismatched = Vec::new();
+    for migration in embedded {
+        match applied
+            .iter()
+            .find(|a| a.version == migration.version && a.name == migration.name)
+        {
+            None => pending.push(migration),
+            Some(a) if a.checksum != migration.checksum() => mismatched.push(migration),
+            Some(_) => {}
+        }
+    }
+    let unknown = applied
+        .iter()
+        .filter(|a| {
+            !embedded
+                .iter()
+                .any(|m| m.version == a.version && m.name == a.name)
+        })
+        .cloned()
+        .collect();
+
+    MigrationPlan {
+        pending,
+        mismatched,
+        unknown,
+        latest: embedded.last().map(|m| m.version).unwrap_or(0),
+    }
+}
+
+/// Migrations to roll back to reach `target` (newest first)
+pub fn rollback_plan<'a>(
+    embedded: &'a [Migration],
+    applied: &[AppliedMigration],
+    target: u32,
+) -> crate::Result<Vec<&'a Migration>> {
+    let mut steps = Vec::new();
+    for migration in embedded.iter().rev().filter(|m| m.version > target) {
+        if !applied
+            .iter()
+            .any(|a| a.version == migration.version && a.name == migration.name)
+        {
+            continue;
+        }
+        if migration.down.is_none() {
+            return Err(crate::Error::Migration(format!(
+                "{} has no down migration ({}.down.sql)",
+                migration.name, migration.name
+            )));
+        }
+        steps.push(migration);
+    }
+    Ok(steps)
+}
+
+/// Startup behaviour
+#[derive(Debug, Clone, Copy)]
+pub struct StartupOptions {
+    /// Apply pending migrations automatically
+    pub auto_apply: bool,
+    /// Mark migrations up to this version as applied on a legacy DB
+    pub baseline: Option<u32>,
+}
+
+/// Schema migration runner
+pub struct Migrator {
+    pool: MySqlPool,
+}
+
+impl Migrator {
+    pub fn new(pool: MySqlPool) -> Self {
+        Self { pool }
+    }
+
+    async fn ensure_table(&self) -> crate::Result<()> {
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS schema_migrations (\
+                version INT UNSIGNED NOT NULL, \
+                name VARCHAR(128) NOT NULL, \
+                checksum CHAR(40) NOT NULL, \
+                applied_at DATETIME(3) NOT NULL, \
+                execution_ms INT UNSIGNED NOT NULL DEFAULT 0, \
+                PRIMARY KEY (version, name)\
+             ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4",
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn applied(&self) -> crate::Result<Vec<AppliedMigration>> {
+        self.ensure_table().await?;
+        let rows = sqlx::query(
+            "SELECT version, name, checksum, applied_at, execution_ms \
+             FROM schema_migrations ORDER BY version, name",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows
+            .iter()
+            .map(|row| AppliedMigration {
+                version: row.get("version"),
+                name: row.get("name"),
+                checksum: row.get("checksum"),
+                applied_at: row.get("applied_at"),
+                execution_ms: row.get("execution_ms"),
+            })
+            .collect())
+    }
+
+    pub async fn status(&self) -> crate::Result<Vec<MigrationStatus>> {
+        let applied = self.applied().await?;
+        let migration_plan = plan(MIGRATIONS, &applied);
+
+        let mut statuses: Vec<MigrationStatus> = MIGRATIONS
+            .iter()
+            .map(|m| {
+                let row = applied
+                    .iter()
+                    .find(|a| a.version == m.version && a.name == m.name);
+                let state = if row.is_none() {
+                    MigrationState::Pending
+                } else if migration_plan.mismatched.iter().any(|x| x.name == m.name) {
+                    MigrationState::ChecksumMismatch
+                } else {
+                    MigrationState::Applied
+                };
+                MigrationStatus {
+                    version: m.version,
+                    name: m.name.to_string(),
+                    state,
+                    has_down: m.down.is_some(),
+                    applied_at: row.map(|a| a.applied_at),
+                }
+            })
+            .collect();
+        statuses.extend(migration_plan.unknown.into_iter().map(|a| MigrationStatus {
+            version: a.version,
+            name: a.name,
+            state: MigrationState::Unknown,
+            has_down: false,
+            applied_at: Some(a.applied_at),
+        }));
+        statuses.sort_by(|a, b| (a.version, &a.name).cmp(&(b.version, &b.name)));
+        Ok(statuses)
+    }
+
+    /// Apply all pending migrations; returns applied names
+    pub async fn up(&self) -> crate::Result<Vec<String>> {
+        let mut conn = self.lock().await?;
+        let result = self.up_locked(&mut conn).await;
+        Self::unlock(&mut conn).await;
+        result
+    }
+
+    async fn up_locked(&self, conn: &mut PoolConnection<MySql>) -> crate::Result<Vec<String>> {
+        let applied = self.applied().await?;
+        let migration_plan = plan(MIGRATIONS, &applied);
+        migration_plan.check_compatible()?;
+
+        let mut done = Vec::new();
+        for migration in migration_plan.pending {
+            let started = Instant::now();
+            tracing::info!(migration = migration.name, "Applying migration");
+            Self::execute_script(conn, migration.name, migration.up).await?;
+            let execution_ms = started.elapsed().as_millis() as u32;
+
+            sqlx::query(
+                "INSERT INTO schema_migrations (version, name, checksum, applied_at, execution_ms) \
+                 VALUES (?, ?, ?, ?, ?)",
+            )
+            .bind(migration.version)
+            .bind(migration.name)
+            .bind(migration.checksum())
+            .bind(Utc::now())
+            .bind(execution_ms)
+            .execute(&mut **conn)
+            .await?;
+
+            tracing::info!(
+                migration = migration.name,
+                execution_ms,
+                "Migration applied"
+            );
+            done.push(migration.name.to_string());
+        }
+        Ok(done)
+    }
+
+    /// Roll back applied migrations newer than `target`; returns rolled back names
+    pub async fn down(&self, target: u32) -> crate::Result<Vec<String>> {
+        let mut conn = self.lock().await?;
+        let result = self.down_locked(&mut conn, target).await;
+        Self::unlock(&mut conn).await;
+        result
+    }
+
+    async fn down_locked(
+        &self,
+        conn: &mut PoolConnection<MySql>,
+        target: u32,
+    ) -> crate::Result<Vec<String>> {
+        let applied = self.applied().await?;
+        let steps = rollback_plan(MIGRATIONS, &applied, target)?;
+
+        let mut done = Vec::new();
+        for migration in steps {
+            tracing::warn!(migration = migration.name, "Rolling back migration");
+            if let Some(down) = migration.down {
+                Self::execute_script(conn, migration.name, down).await?;
+            }
+            sqlx::query("DELETE FROM schema_migrations WHERE version = ? AND name = ?")
+                .bind(migration.version)
+                .bind(migration.name)
+                .execute(&mut **conn)
+                .await?;
+            done.push(migration.name.to_string());
+        }
+        Ok(done)
+    }
+
+    /// Record migrations up to `version` as applied without running them
+    ///
+    /// 既存環境（手動で SQL を流してきた DB）を履歴管理に載せるために使う
+    pub async fn baseline(&self, version: u32) -> crate::Result<usize> {
+        let applied = self.applied().await?;
+        let mut count = 0;
+        for migration in MIGRATIONS.iter().filter(|m| m.version <= version) {
+            if applied
+                .iter()
+                .any(|a| a.version == migration.version && a.name == migration.name)
+            {
+                continue;
+            }
+            sqlx::query(
+                "INSERT INTO schema_migrations (version, name, checksum, applied_at, execution_ms) \
+                 VALUES (?, ?, ?, ?, 0)",
+            )
+            .bind(migration.version)
+            .bind(migration.name)
+            .bind(migration.checksum())
+            .bind(Utc::now())
+            .execute(&self.pool)
+            .await?;
+            count += 1;
+        }
+        tracing::info!(
+            baseline = version,
+            recorded = count,
+            "Migration baseline recorded"
+        );
+        Ok(count)
+    }
+
+    /// Verify (and optionally apply) migrations before the server starts
+    pub async fn run_startup(&self, options: StartupOptions) -> crate::Result<()> {
+        let applied = self.applied().await?;
+        if applied.is_empty() && self.is_legacy_database().await? {
+            match options.baseline {
+                Some(version) => {
+                    self.baseline(version).await?;
+                }
+                None => {
+                    return Err(crate::Error::Migration(
+                        "database has tables but no migration history; set MIGRATION_BASELINE=<version> \
+                         or run `camserver migrate baseline <version>` with the last migration applied manually"
+                            .to_string(),
+                    ))
+                }
+            }
+        }
+
+        let applied = self.applied().await?;
+        let migration_plan = plan(MIGRATIONS, &applied);
+        migration_plan.check_compatible()?;
+
+        if migration_plan.pending.is_empty() {
+            tracing::info!(applied = applied.len(), "Database schema is up to date");
+            return Ok(());
+        }
+        if !options.auto_apply {
+            return Err(crate::Error::Migration(format!(
+                "{} pending migrations ({}); run `camserver migrate up` or enable AUTO_MIGRATE",
+                migration_plan.pending.len(),
+                migration_plan
+                    .pending
+                    .iter()
+                    .map(|m| m.name)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )));
+        }
+
+        let done = self.up().await?;
+        tracing::info!(
+            applied = done.len(),
+            "Pending migrations applied at startup"
+        );
+        Ok(())
+    }
+
+    /// Pre-existing schema without schema_migrations rows
+    async fn is_legacy_database(&self) -> crate::Result<bool> {
+        let row = sqlx::query(
+            "SELECT COUNT(*) AS cnt FROM information_schema.TABLES \
+             WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = 'cameras'",
+        )
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(row.get::<i64, _>("cnt") > 0)
+    }
+
+    async fn execute_script(
+        conn: &mut PoolConnection<MySql>,
+        name: &str,
+        script: &str,
+    ) -> crate::Result<()> {
+        for (index, statement) in split_statements(script).iter().enumerate() {
+            // DDL / CREATE PROCEDURE は prepared statement 非対応のためテキストプロトコルで実行
+            conn.execute(statement.as_str()).await.map_err(|e| {
+                crate::Error::Migration(format!(
+                    "{} failed at statement {}: {}",
+                    name,
+                    index + 1,
+                    e
+                ))
+            })?;
+        }
+        Ok(())
+    }
+
+    async fn lock(&self) -> crate::Result<PoolConnection<MySql>> {
+        let mut conn = self.pool.acquire().await?;
+        let locked: Option<i64> = sqlx::query_scalar("SELECT GET_LOCK(?, ?)")
+            .bind(LOCK_NAME)
+            .bind(LOCK_TIMEOUT_SECS)
+            .fetch_one(&mut *conn)
+            .await?;
+        if locked != Some(1) {
+            return Err(crate::Error::Migration(
+                "another migration runner holds the schema lock".to_string(),
+            ));
+        }
+        Ok(conn)
+    }
+
+    async fn unlock(conn: &mut PoolConnection<MySql>) {
+        if let Err(e) = sqlx::query("SELECT RELEASE_LOCK(?)")
+            .bind(LOCK_NAME)
+            .execute(&mut **conn)
+            .await
+        {
+            tracing::warn!(error = %e, "Failed to release migration lock");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn applied(m: &Migration, checksum: Option<&str>) -> AppliedMigration {
+        AppliedMigration {
+            version: m.version,
+            name: m.name.to_string(),
+            checksum: checksum.map(String::from).unwrap_or_else(|| m.checksum()),
+            applied_at: Utc::now(),
+            execution_ms: 0,
+        }
+    }
+
+    #[test]
+    fn test_plan_pending_and_mismatch() {
+        let rows = vec![
+            applied(&MIGRATIONS[0], None),
+            applied(&MIGRATIONS[1], Some("edited")),
+        ];
+        let result = plan(MIGRATIONS, &rows);
+
+        assert_eq!(result.pending.len(), MIGRATIONS.len() - 2);
+        assert_eq!(result.mismatched.len(), 1);
+        assert!(result.check_compatible().is_err());
+    }
+
+    #[test]
+    fn test_database_ahead_is_refused() {
+        let mut rows: Vec<AppliedMigration> = MIGRATIONS.iter().map(|m| applied(m, None)).collect();
+        assert!(plan(MIGRATIONS, &rows).check_compatible().is_ok());
+
+        rows.push(AppliedMigration {
+            version: 999,
+            name: "999_future".to_string(),
+            checksum: String::new(),
+            applied_at: Utc::now(),
+            execution_ms: 0,
+        });
+        let err = plan(MIGRATIONS, &rows).check_compatible().unwrap_err();
+        assert!(err.to_string().contains("ahead of this binary"));
+    }
+
+    #[test]
+    fn test_rollback_requires_down_scripts() {
+        let rows: Vec<AppliedMigration> = MIGRATIONS.iter().map(|m| applied(m, None)).collect();
+        let latest = MIGRATIONS.last().unwrap();
+        let steps = rollback_plan(MIGRATIONS, &rows, latest.version - 1).unwrap();
+        assert_eq!(steps.len(), 1);
+        assert_eq!(steps[0].name, latest.name);
+
+        // down は 015 と 033 以降にある: 032 までは巻き戻せるが、それより前は 032 で拒否される
+        let steps = rollback_plan(MIGRATIONS, &rows, 32).unwrap();
+        assert_eq!(steps.last().unwrap().version, 33);
+        let err = rollback_plan(MIGRATIONS, &rows, 31).unwrap_err();
+        assert!(err.to_string().contains("032_"), "{}", err);
+    }
+}
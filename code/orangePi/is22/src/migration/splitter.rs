@@ -0,0 +1,150 @@
+//! SQL script splitter
+//!
+//! mysql クライアント同様に `DELIMITER` 指令を解釈し、スクリプトを文単位に分割する。
+//! 文字列・識別子クォート・コメント内の区切り文字は無視する。
+
+/// Split a migration script into executable statements
+pub fn split_statements(sql: &str) -> Vec<String> {
+    let chars: Vec<char> = sql.chars().collect();
+    let mut statements = Vec::new();
+    let mut current = String::new();
+    let mut has_content = false;
+    let mut delimiter: Vec<char> = vec![';'];
+    let mut at_line_start = true;
+    let mut i = 0;
+
+    while i < chars.len() {
+        if at_line_start {
+            let line_end = chars[i..]
+                .iter()
+                .position(|&c| c == '\n')
+                .map(|p| i + p)
+                .unwrap_or(chars.len());
+            let line: String = chars[i..line_end].iter().collect();
+            let trimmed = line.trim_start();
+            let is_directive = trimmed
+                .get(..10)
+                .is_some_and(|p| p.eq_ignore_ascii_case("DELIMITER "));
+            if is_directive {
+                let token = trimmed[10..].trim();
+                if !token.is_empty() {
+                    delimiter = token.chars().collect();
+                }
+                i = line_end + 1;
+                continue;
+            }
+            at_line_start = false;
+        }
+
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+
+        // Line comments: "-- " / "#"
+        let dash_comment =
+            c == '-' && next == Some('-') && chars.get(i + 2).map_or(true, |c| c.is_whitespace());
+        if dash_comment || c == '#' {
+            while i < chars.len() && chars[i] != '\n' {
+                current.push(chars[i]);
+                i += 1;
+            }
+            continue;
+        }
+
+        // Block comment
+        if c == '/' && next == Some('*') {
+            current.push_str("/*");
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                current.push(chars[i]);
+                i += 1;
+            }
+            if i < chars.len() {
+                current.push_str("*/");
+                i += 2;
+            }
+            continue;
+        }
+
+        // Quoted string / identifier
+        if c == '\'' || c == '"' || c == '`' {
+            has_content = true;
+            current.push(c);
+            i += 1;
+            while i < chars.len() {
+                let q = chars[i];
+                current.push(q);
+                i += 1;
+                if q == '\\' && c != '`' {
+                    if let Some(&escaped) = chars.get(i) {
+                        current.push(escaped);
+                        i += 1;
+                    }
+                } else if q == c {
+                    // Doubled quote is an escaped quote
+                    if chars.get(i) == Some(&c) {
+                        current.push(c);
+                        i += 1;
+                    } else {
+                        break;
+                    }
+                }
+            }
+            continue;
+        }
+
+        if chars[i..].starts_with(&delimiter) {
+            if has_content {
+                statements.push(current.trim().to_string());
+            }
+            current.clear();
+            has_content = false;
+            i += delimiter.len();
+            continue;
+        }
+
+        if !c.is_whitespace() {
+            has_content = true;
+        }
+        if c == '\n' {
+            at_line_start = true;
+        }
+        current.push(c);
+        i += 1;
+    }
+
+    if has_content {
+        statements.push(current.trim().to_string());
+    }
+    statements
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_ignores_delimiters_in_strings_and_comments() {
+        let sql = "-- header; comment\n\
+                   CREATE TABLE t (c VARCHAR(8) COMMENT 'a;b', d INT COMMENT 'it''s');\n\
+                   /* block; */ INSERT INTO t VALUES ('x\\';', 1);\n\
+                   # trailing;\n";
+        let statements = split_statements(sql);
+        assert_eq!(statements.len(), 2);
+        assert!(statements[0].ends_with("COMMENT 'it''s')"));
+        assert!(statements[1].contains("VALUES ('x\\';', 1)"));
+    }
+
+    #[test]
+    fn test_split_honours_delimiter_directive() {
+        let sql = "DELIMITER //\n\
+                   CREATE PROCEDURE p()\nBEGIN\n  SELECT 1;\n  SELECT 2;\nEND//\n\
+                   DELIMITER ;\n\
+                   CALL p();\n\
+                   DROP PROCEDURE IF EXISTS p;\n";
+        let statements = split_statements(sql);
+        assert_eq!(statements.len(), 3);
+        assert!(statements[0].starts_with("CREATE PROCEDURE p()"));
+        assert!(statements[0].ends_with("END"));
+        assert_eq!(statements[1], "CALL p()");
+    }
+}
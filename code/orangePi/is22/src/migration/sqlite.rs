@@ -0,0 +1,140 @@
+//! SQLite schema migrations
+//!
+//! migrations/sqlite/ の SQL を埋め込み、MySQL と同じ (version, name) + チェックサムで管理する。
+//! SQLite は単一プロセス前提のため advisory lock は使わず、各マイグレーションを
+//! トランザクション内で適用する（SQLite の DDL はトランザクション対応）。
+
+use super::{plan, AppliedMigration, Migration};
+use chrono::Utc;
+use sqlx::{Executor, Row, SqlitePool};
+use std::time::Instant;
+
+macro_rules! sqlite_migration {
+    ($version:expr, $name:literal) => {
+        Migration {
+            version: $version,
+            name: $name,
+            up: include_str!(concat!("../../migrations/sqlite/", $name, ".sql")),
+            down: None,
+        }
+    };
+}
+
+/// SQLite migrations in apply order
+pub static SQLITE_MIGRATIONS: &[Migration] = &[sqlite_migration!(1, "001_repositories")];
+
+/// Schema migration runner for the SQLite backend
+pub struct SqliteMigrator {
+    pool: SqlitePool,
+}
+
+impl SqliteMigrator {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    async fn ensure_table(&self) -> crate::Result<()> {
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS schema_migrations (\
+                version INTEGER NOT NULL, \
+                name TEXT NOT NULL, \
+                checksum TEXT NOT NULL, \
+                applied_at TEXT NOT NULL, \
+                execution_ms INTEGER NOT NULL DEFAULT 0, \
+                PRIMARY KEY (version, name)\
+             )",
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn applied(&self) -> crate::Result<Vec<AppliedMigration>> {
+        self.ensure_table().await?;
+        let rows = sqlx::query(
+            "SELECT version, name, checksum, applied_at, execution_ms \
+             FROM schema_migrations ORDER BY version, name",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows
+            .iter()
+            .map(|row| AppliedMigration {
+                version: row.get("version"),
+                name: row.get("name"),
+                checksum: row.get("checksum"),
+                applied_at: row.get("applied_at"),
+                execution_ms: row.get("execution_ms"),
+            })
+            .collect())
+    }
+
+    /// Apply all pending migrations; returns applied names
+    pub async fn up(&self) -> crate::Result<Vec<String>> {
+        let applied = self.applied().await?;
+        let migration_plan = plan(SQLITE_MIGRATIONS, &applied);
+        migration_plan.check_compatible()?;
+
+        let mut done = Vec::new();
+        for migration in migration_plan.pending {
+            let started = Instant::now();
+            tracing::info!(migration = migration.name, "Applying SQLite migration");
+            let mut tx = self.pool.begin().await?;
+            tx.execute(migration.up).await.map_err(|e| {
+                crate::Error::Migration(format!("{} failed: {}", migration.name, e))
+            })?;
+            let execution_ms = started.elapsed().as_millis() as u32;
+            sqlx::query(
+                "INSERT INTO schema_migrations (version, name, checksum, applied_at, execution_ms) \
+                 VALUES (?, ?, ?, ?, ?)",
+            )
+            .bind(migration.version)
+            .bind(migration.name)
+            .bind(migration.checksum())
+            .bind(Utc::now())
+            .bind(execution_ms)
+            .execute(&mut *tx)
+            .await?;
+            tx.commit().await?;
+            done.push(migration.name.to_string());
+        }
+        Ok(done)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_embedded_list_matches_directory() {
+        let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("migrations/sqlite");
+        let files: BTreeSet<String> = std::fs::read_dir(dir)
+            .unwrap()
+            .filter_map(|e| {
+                let file = e.unwrap().file_name().to_string_lossy().to_string();
+                file.strip_suffix(".sql").map(String::from)
+            })
+            .collect();
+        let embedded: BTreeSet<String> = SQLITE_MIGRATIONS
+            .iter()
+            .map(|m| m.name.to_string())
+            .collect();
+        assert_eq!(embedded, files);
+    }
+
+    #[tokio::test]
+    async fn test_up_is_idempotent() {
+        // インメモリ DB は接続ごとに別物のため 1 本に固定
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        let migrator = SqliteMigrator::new(pool);
+        assert_eq!(migrator.up().await.unwrap().len(), SQLITE_MIGRATIONS.len());
+        assert!(migrator.up().await.unwrap().is_empty());
+        assert_eq!(migrator.applied().await.unwrap().len(), SQLITE_MIGRATIONS.len());
+    }
+}
@@ -0,0 +1,410 @@
+//! MotionPrefilter - Local Change Detection before IS21 Inference
+//!
+//! ## Responsibilities
+//!
+//! - Compare the new snapshot with the PrevFrameCache frame on-device (CPU only)
+//! - Decide whether IS21 inference can be skipped for an unchanged scene
+//! - Force a full analysis every N consecutive skips
+//!
+//! ## Algorithm
+//!
+//! 1. JPEGをDCTスケーリングで縮小デコード（jpeg-decoder, GPU不要）
+//! 2. グレースケール化し固定グリッド（64x48）へボックス平均で縮小
+//! 3. 各フレームの平均輝度を差し引いてから（露出変動の補正）セル差分を算出
+//! 4. ノイズフロアを超えたセルの割合が感度から求めた閾値以上なら「変化あり」
+//!
+//! PrevFrameCacheはIS21解析後にのみ更新されるため、比較対象は常に
+//! 「最後に解析したフレーム」となり、緩やかな変化も累積して検出される（defer）。
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Grid width after downscaling
+const GRID_WIDTH: usize = 64;
+/// Grid height after downscaling
+const GRID_HEIGHT: usize = 48;
+/// Requested decode size (DCT scaling picks the nearest size >= this)
+const DECODE_WIDTH: u16 = 160;
+const DECODE_HEIGHT: u16 = 120;
+/// Changed-cell ratio threshold at sensitivity 1.0
+const MIN_CHANGE_RATIO: f32 = 0.005;
+/// Changed-cell ratio threshold at sensitivity 0.0
+const MAX_CHANGE_RATIO: f32 = 0.10;
+
+/// Downscaled grayscale frame
+#[derive(Debug, Clone)]
+pub struct LumaGrid {
+    cells: Vec<u8>,
+}
+
+impl LumaGrid {
+    /// Decode a JPEG into a downscaled luma grid
+    pub fn from_jpeg(data: &[u8]) -> Result<Self, String> {
+        let (luma, width, height) = decode_luma(data, DECODE_WIDTH, DECODE_HEIGHT)?;
+        Self::from_luma(&luma, width, height)
+    }
+
+    /// Box-average a luma plane into the fixed grid
+    pub fn from_luma(luma: &[u8], width: usize, height: usize) -> Result<Self, String> {
+        if width == 0 || height == 0 || luma.len() < width * height {
+            return Err(format!("Invalid luma plane {}x{}", width, height));
+        }
+
+        let mut cells = Vec::with_capacity(GRID_WIDTH * GRID_HEIGHT);
+        for gy in 0..GRID_HEIGHT {
+            let y0 = gy * height / GRID_HEIGHT;
+            let y1 = ((gy + 1) * height / GRID_HEIGHT).max(y0 + 1).min(height);
+            for gx in 0..GRID_WIDTH {
+                let x0 = gx * width / GRID_WIDTH;
+                let x1 = ((gx + 1) * width / GRID_WIDTH).max(x0 + 1).min(width);
+                let mut sum: u32 = 0;
+                let mut count: u32 = 0;
+                for y in y0..y1 {
+                    let row = &luma[y * width..y * width + width];
+                    for &v in &row[x0..x1] {
+                        sum += v as u32;
+                        count += 1;
+                    }
+                }
+                cells.push((sum / count.max(1)) as u8);
+            }
+        }
+
+        Ok(Self { cells })
+    }
+
+    fn mean(&self) -> f32 {
+        self.cells.iter().map(|&v| v as f32).sum::<f32>() / self.cells.len() as f32
+    }
+}
+
+/// Decode a JPEG into a grayscale plane via DCT scaling
+///
+/// The decoder picks the nearest scale >= (width, height).
+/// Returns (luma, width, height).
+pub fn decode_luma(data: &[u8], width: u16, height: u16) -> Result<(Vec<u8>, usize, usize), String> {
+    let mut decoder = jpeg_decoder::Decoder::new(data);
+    decoder.read_info().map_err(|e| e.to_string())?;
+    decoder.scale(width, height).map_err(|e| e.to_string())?;
+    let pixels = decoder.decode().map_err(|e| e.to_string())?;
+    let info = decoder
+        .info()
+        .ok_or_else(|| "JPEG info unavailable".to_string())?;
+
+    let luma: Vec<u8> = match info.pixel_format {
+        jpeg_decoder::PixelFormat::L8 => pixels,
+        jpeg_decoder::PixelFormat::L16 => pixels.chunks_exact(2).map(|p| p[0]).collect(),
+        jpeg_decoder::PixelFormat::RGB24 => pixels
+            .chunks_exact(3)
+            .map(|p| ((p[0] as u32 * 299 + p[1] as u32 * 587 + p[2] as u32 * 114) / 1000) as u8)
+            .collect(),
+        jpeg_decoder::PixelFormat::CMYK32 => pixels
+            .chunks_exact(4)
+            .map(|p| 255 - p[3])
+            .collect(),
+    };
+
+    Ok((luma, info.width as usize, info.height as usize))
+}
+
+/// Change detection parameters for one camera
+#[derive(Debug, Clone, Copy)]
+pub struct ChangeParams {
+    /// 0.0 = only large changes, 1.0 = tiny changes
+    pub sensitivity: f32,
+    /// Per-cell luma diff ignored as noise (0-255)
+    pub noise_floor: i32,
+}
+
+impl ChangeParams {
+    /// Changed-cell ratio at which the scene counts as changed
+    pub fn threshold_ratio(&self) -> f32 {
+        let s = self.sensitivity.clamp(0.0, 1.0);
+        MIN_CHANGE_RATIO + (1.0 - s) * (MAX_CHANGE_RATIO - MIN_CHANGE_RATIO)
+    }
+}
+
+/// Result of comparing two frames
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct ChangeScore {
+    /// Ratio of grid cells above the noise floor (0.0-1.0)
+    pub changed_ratio: f32,
+    /// Mean absolute luma diff after exposure compensation
+    pub mean_abs_diff: f32,
+    /// Threshold used for the decision
+    pub threshold_ratio: f32,
+    /// Whether the scene changed
+    pub changed: bool,
+}
+
+/// Compare two grids
+pub fn compare(prev: &LumaGrid, current: &LumaGrid, params: ChangeParams) -> ChangeScore {
+    let prev_mean = prev.mean();
+    let current_mean = current.mean();
+    let noise_floor = params.noise_floor.clamp(0, 255) as f32;
+
+    let mut changed_cells = 0usize;
+    let mut diff_sum = 0f32;
+    for (&a, &b) in prev.cells.iter().zip(current.cells.iter()) {
+        let diff = ((a as f32 - prev_mean) - (b as f32 - current_mean)).abs();
+        diff_sum += diff;
+        if diff > noise_floor {
+            changed_cells += 1;
+        }
+    }
+
+    let total = prev.cells.len().min(current.cells.len()).max(1) as f32;
+    let changed_ratio = changed_cells as f32 / total;
+    let threshold_ratio = params.threshold_ratio();
+
+    ChangeScore {
+        changed_ratio,
+        mean_abs_diff: diff_sum / total,
+        threshold_ratio,
+        changed: changed_ratio >= threshold_ratio,
+    }
+}
+
+/// Pre-filter decision for one poll
+#[derive(Debug, Clone, Copy)]
+pub enum PrefilterDecision {
+    /// Send to IS21 (scene changed, no reference frame, or decode failed)
+    Analyze { score: Option<ChangeScore> },
+    /// Forced analysis after N consecutive skips
+    ForcedAnalyze { score: ChangeScore },
+    /// Scene unchanged - skip IS21 inference
+    Skip { score: ChangeScore, consecutive_skips: u32 },
+}
+
+impl PrefilterDecision {
+    pub fn should_analyze(&self) -> bool {
+        !matches!(self, Self::Skip { .. })
+    }
+}
+
+/// Cached reference grid (keyed by the prev frame's captured_at)
+struct ReferenceGrid {
+    captured_at: DateTime<Utc>,
+    grid: LumaGrid,
+}
+
+/// Per-camera pre-filter counters
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PrefilterCameraStats {
+    pub consecutive_skips: u32,
+    pub total_skipped: u64,
+    pub total_analyzed: u64,
+    pub total_forced: u64,
+    pub last_score: Option<ChangeScore>,
+}
+
+/// MotionPrefilter service
+pub struct MotionPrefilter {
+    references: Arc<RwLock<HashMap<String, ReferenceGrid>>>,
+    stats: Arc<RwLock<HashMap<String, PrefilterCameraStats>>>,
+}
+
+impl MotionPrefilter {
+    /// Create new MotionPrefilter
+    pub fn new() -> Self {
+        Self {
+            references: Arc::new(RwLock::new(HashMap::new())),
+            stats: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Evaluate a new frame against the previous analyzed frame
+    ///
+    /// `prev` is the PrevFrameCache entry (image, captured_at).
+    pub async fn evaluate(
+        &self,
+        camera_id: &str,
+        current: &[u8],
+        prev: Option<(&[u8], DateTime<Utc>)>,
+        params: ChangeParams,
+        force_analysis_every: u32,
+    ) -> PrefilterDecision {
+        let Some((prev_data, prev_captured_at)) = prev else {
+            return self.finish(camera_id, PrefilterDecision::Analyze { score: None }).await;
+        };
+
+        let cached = {
+            let references = self.references.read().await;
+            references
+                .get(camera_id)
+                .filter(|r| r.captured_at == prev_captured_at)
+                .map(|r| r.grid.clone())
+        };
+
+        let current_owned = current.to_vec();
+        let prev_owned = if cached.is_none() { Some(prev_data.to_vec()) } else { None };
+
+        // JPEG decode is CPU-bound; keep it off the async workers
+        let decoded = tokio::task::spawn_blocking(move || {
+            let prev_grid = match cached {
+                Some(grid) => grid,
+                None => LumaGrid::from_jpeg(prev_owned.as_deref().unwrap_or_default())?,
+            };
+            let current_grid = LumaGrid::from_jpeg(&current_owned)?;
+            Ok::<_, String>((prev_grid, current_grid))
+        })
+        .await;
+
+        let (prev_grid, current_grid) = match decoded {
+            Ok(Ok(grids)) => grids,
+            Ok(Err(e)) => {
+                tracing::debug!(camera_id = %camera_id, error = %e, "Motion pre-filter decode failed, analyzing");
+                return self.finish(camera_id, PrefilterDecision::Analyze { score: None }).await;
+            }
+            Err(e) => {
+                tracing::warn!(camera_id = %camera_id, error = %e, "Motion pre-filter task failed, analyzing");
+                return self.finish(camera_id, PrefilterDecision::Analyze { score: None }).await;
+            }
+        };
+
+        self.references.write().await.insert(
+            camera_id.to_string(),
+            ReferenceGrid {
+                captured_at: prev_captured_at,
+                grid: prev_grid.clone(),
+            },
+        );
+
+        let score = compare(&prev_grid, &current_grid, params);
+        let decision = if score.changed {
+            PrefilterDecision::Analyze { score: Some(score) }
+        } else {
+            let consecutive_skips = self
+                .stats
+                .read()
+                .await
+                .get(camera_id)
+                .map(|s| s.consecutive_skips)
+                .unwrap_or(0);
+            if force_analysis_every > 0 && consecutive_skips + 1 > force_analysis_every {
+                PrefilterDecision::ForcedAnalyze { score }
+            } else {
+                PrefilterDecision::Skip {
+                    score,
+                    consecutive_skips: consecutive_skips + 1,
+                }
+            }
+        };
+
+        self.finish(camera_id, decision).await
+    }
+
+    /// Update counters for a decision
+    async fn finish(&self, camera_id: &str, decision: PrefilterDecision) -> PrefilterDecision {
+        let mut stats = self.stats.write().await;
+        let entry = stats.entry(camera_id.to_string()).or_default();
+        match decision {
+            PrefilterDecision::Analyze { score } => {
+                entry.consecutive_skips = 0;
+                entry.total_analyzed += 1;
+                entry.last_score = score;
+            }
+            PrefilterDecision::ForcedAnalyze { score } => {
+                entry.consecutive_skips = 0;
+                entry.total_analyzed += 1;
+                entry.total_forced += 1;
+                entry.last_score = Some(score);
+            }
+            PrefilterDecision::Skip { score, consecutive_skips } => {
+                entry.consecutive_skips = consecutive_skips;
+                entry.total_skipped += 1;
+                entry.last_score = Some(score);
+            }
+        }
+        decision
+    }
+
+    /// Get per-camera counters
+    pub async fn stats(&self) -> HashMap<String, PrefilterCameraStats> {
+        self.stats.read().await.clone()
+    }
+
+    /// Forget a camera (e.g., deleted)
+    pub async fn remove(&self, camera_id: &str) {
+        self.references.write().await.remove(camera_id);
+        self.stats.write().await.remove(camera_id);
+    }
+}
+
+impl Default for MotionPrefilter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plane(width: usize, height: usize, f: impl Fn(usize, usize) -> u8) -> Vec<u8> {
+        (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| f(x, y))
+            .collect()
+    }
+
+    const PARAMS: ChangeParams = ChangeParams {
+        sensitivity: 0.5,
+        noise_floor: 12,
+    };
+
+    #[test]
+    fn test_identical_frames_unchanged() {
+        let luma = plane(320, 240, |x, y| ((x + y) % 256) as u8);
+        let a = LumaGrid::from_luma(&luma, 320, 240).unwrap();
+        let b = LumaGrid::from_luma(&luma, 320, 240).unwrap();
+        let score = compare(&a, &b, PARAMS);
+        assert!(!score.changed);
+        assert_eq!(score.changed_ratio, 0.0);
+    }
+
+    #[test]
+    fn test_global_brightness_shift_is_compensated() {
+        let luma = plane(320, 240, |x, _| (x % 200) as u8);
+        let brighter: Vec<u8> = luma.iter().map(|v| v + 40).collect();
+        let a = LumaGrid::from_luma(&luma, 320, 240).unwrap();
+        let b = LumaGrid::from_luma(&brighter, 320, 240).unwrap();
+        assert!(!compare(&a, &b, PARAMS).changed);
+    }
+
+    #[test]
+    fn test_local_object_detected() {
+        let background = plane(320, 240, |_, _| 100);
+        // 80x60 bright block = 6.25% of the frame
+        let with_object = plane(320, 240, |x, y| {
+            if (100..180).contains(&x) && (80..140).contains(&y) { 230 } else { 100 }
+        });
+        let a = LumaGrid::from_luma(&background, 320, 240).unwrap();
+        let b = LumaGrid::from_luma(&with_object, 320, 240).unwrap();
+        let score = compare(&a, &b, PARAMS);
+        assert!(score.changed, "ratio={}", score.changed_ratio);
+
+        // Low sensitivity ignores the same change
+        let insensitive = ChangeParams { sensitivity: 0.0, noise_floor: 12 };
+        assert!(!compare(&a, &b, insensitive).changed);
+    }
+
+    #[test]
+    fn test_threshold_ratio_range() {
+        let high = ChangeParams { sensitivity: 1.0, noise_floor: 0 };
+        let low = ChangeParams { sensitivity: 0.0, noise_floor: 0 };
+        assert!((high.threshold_ratio() - MIN_CHANGE_RATIO).abs() < f32::EPSILON);
+        assert!((low.threshold_ratio() - MAX_CHANGE_RATIO).abs() < f32::EPSILON);
+    }
+
+    #[tokio::test]
+    async fn test_no_reference_frame_analyzes() {
+        let prefilter = MotionPrefilter::new();
+        let decision = prefilter.evaluate("cam1", &[0xFF, 0xD8], None, PARAMS, 10).await;
+        assert!(decision.should_analyze());
+        assert_eq!(prefilter.stats().await["cam1"].total_analyzed, 1);
+    }
+}
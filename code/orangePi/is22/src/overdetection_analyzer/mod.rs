@@ -119,8 +119,11 @@ impl OverdetectionAnalyzer {
         }
     }
 
-    /// 全プリセットのバランス値を計算 (v2.0)
-    pub fn get_all_preset_balances(&self) -> Vec<PresetBalanceInfo> {
+    /// 全プリセットのバランス値を計算 (v2.0 + DBカスタムプリセット)
+    pub fn get_all_preset_balances(
+        &self,
+        preset_loader: &crate::preset_loader::PresetLoader,
+    ) -> Vec<PresetBalanceInfo> {
         use crate::preset_loader::{Preset, preset_ids};
 
         // v2.0: 13プリセット（廃止3種除外、新規4種追加）
@@ -141,13 +144,24 @@ impl OverdetectionAnalyzer {
         ];
         // v2.0 廃止: night_vision, retail, warehouse
 
-        presets.into_iter().map(|(id, name, preset)| {
+        let mut balances: Vec<PresetBalanceInfo> = presets.into_iter().map(|(id, name, preset)| {
             PresetBalanceInfo {
                 preset_id: id.to_string(),
                 name: name.to_string(),
                 balance: Self::calculate_balance(&preset),
             }
-        }).collect()
+        }).collect();
+
+        // カスタムプリセット（custom_presets）
+        balances.extend(preset_loader.list_custom().into_iter().map(|preset| {
+            PresetBalanceInfo {
+                preset_id: preset.id.clone(),
+                name: preset.name.clone(),
+                balance: Self::calculate_balance(&preset),
+            }
+        }));
+
+        balances
     }
 
     /// プリセットからバランス値を計算
@@ -13,6 +13,7 @@
 //! E2Eテスト結果に基づき実際のエンドポイントを使用
 
 use crate::config_store::ConfigStore;
+use crate::metrics::Metrics;
 use crate::paraclate_client::{
     repository::{ConfigRepository, ConnectionLogRepository, SendQueueRepository},
     types::{
@@ -54,6 +55,8 @@ pub struct ParaclateClient {
     queue_repo: SendQueueRepository,
     log_repo: ConnectionLogRepository,
     config_store: Arc<ConfigStore>,
+    /// 送信失敗カウンタ（is22_paraclate_send_failures_total）
+    metrics: Arc<Metrics>,
 }
 
 impl ParaclateClient {
@@ -61,6 +64,7 @@ impl ParaclateClient {
     pub fn new(
         pool: sqlx::MySqlPool,
         config_store: Arc<ConfigStore>,
+        metrics: Arc<Metrics>,
     ) -> Self {
         let http = Client::builder()
             .timeout(Duration::from_secs(30))
@@ -76,6 +80,7 @@ impl ParaclateClient {
             queue_repo: SendQueueRepository::new(pool.clone()),
             log_repo: ConnectionLogRepository::new(pool),
             config_store,
+            metrics,
         }
     }
 
@@ -469,6 +474,33 @@ impl ParaclateClient {
         Ok(queue_id)
     }
 
+    /// 週次・月次ロールアップ送信（キューに追加）
+    pub async fn send_rollup(
+        &self,
+        tid: &str,
+        fid: &str,
+        payload_type: PayloadType,
+        payload: serde_json::Value,
+        summary_id: u64,
+    ) -> Result<u64, ParaclateError> {
+        let queue_id = self
+            .queue_repo
+            .insert(SendQueueInsert {
+                tid: tid.to_string(),
+                fid: fid.to_string(),
+                payload_type,
+                payload,
+                reference_id: Some(summary_id),
+                max_retries: None,
+            })
+            .await
+            .map_err(|e| ParaclateError::Queue(format!("Failed to enqueue: {}", e)))?;
+
+        debug!(tid = %tid, fid = %fid, queue_id = queue_id, summary_id = summary_id, payload_type = %payload_type, "Rollup enqueued");
+
+        Ok(queue_id)
+    }
+
     /// Event送信（snapshot付き）
     ///
     /// T5-2: LacisFiles連携（mobes2.0回答に基づく実装）
@@ -590,6 +622,7 @@ impl ParaclateClient {
                         })
                     } else {
                         warn!(tid = %tid, log_id = log_id, error = ?event_response.error, "Event send failed");
+                        self.metrics.paraclate_send_failed(fid, "event", "rejected");
 
                         Ok(EventSendResult {
                             success: false,
@@ -602,6 +635,7 @@ impl ParaclateClient {
                 } else {
                     let error_body = response.text().await.unwrap_or_default();
                     let error_msg = format!("HTTP {}: {}", status.as_u16(), error_body);
+                    self.metrics.paraclate_send_failed(fid, "event", "http");
 
                     // 失敗時はキューに追加してリトライ
                     let payload = serde_json::to_value(&event_payload)
@@ -639,6 +673,7 @@ impl ParaclateClient {
             }
             Err(e) => {
                 let error_msg = e.to_string();
+                self.metrics.paraclate_send_failed(fid, "event", "network");
 
                 // エラー時はキューに追加
                 let payload = serde_json::to_value(&event_payload)
@@ -767,6 +802,7 @@ impl ParaclateClient {
                         error = %error_body,
                         "Camera status change send failed"
                     );
+                    self.metrics.paraclate_send_failed(fid, "camera_status", "http");
 
                     // 失敗時はキューに追加してリトライ
                     // mobes形式でラップ: { cameraStatusChange: {...} }
@@ -796,6 +832,7 @@ impl ParaclateClient {
                     error = %e,
                     "Camera status change send error"
                 );
+                self.metrics.paraclate_send_failed(fid, "camera_status", "network");
 
                 // エラー時はキューに追加
                 // mobes形式でラップ: { cameraStatusChange: {...} }
@@ -890,7 +927,10 @@ impl ParaclateClient {
 
             // mobes2.0 Cloud Run エンドポイントを選択
             let url = match item.payload_type {
-                PayloadType::Summary | PayloadType::GrandSummary => endpoints::INGEST_SUMMARY,
+                PayloadType::Summary
+                | PayloadType::GrandSummary
+                | PayloadType::WeeklyRollup
+                | PayloadType::MonthlyRollup => endpoints::INGEST_SUMMARY,
                 PayloadType::Event | PayloadType::Emergency => endpoints::INGEST_EVENT,
             };
 
@@ -976,6 +1016,32 @@ impl ParaclateClient {
                         }
                     })
                 }
+                PayloadType::WeeklyRollup | PayloadType::MonthlyRollup => {
+                    // RollupPayload: periodStart/periodEnd はペイロード直下
+                    let summary_id = item.reference_id.unwrap_or(0).to_string();
+                    let period_start = item.payload
+                        .get("periodStart")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("")
+                        .to_string();
+                    let period_end = item.payload
+                        .get("periodEnd")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("")
+                        .to_string();
+
+                    let mut summary = item.payload.clone();
+                    summary["summaryId"] = serde_json::Value::String(summary_id);
+
+                    serde_json::json!({
+                        "fid": fid,
+                        "payload": {
+                            "summary": summary,
+                            "periodStart": period_start,
+                            "periodEnd": period_end
+                        }
+                    })
+                }
                 PayloadType::Event | PayloadType::Emergency => {
                     // mobes2.0 Ingest API形式: { fid, payload: { event: {...} } }
                     // Note: 既存キューアイテムは logId で保存されている可能性があるため
@@ -1023,6 +1089,8 @@ impl ParaclateClient {
                     } else {
                         let error_body = response.text().await.unwrap_or_default();
                         let error_msg = format!("HTTP {}: {}", status.as_u16(), error_body);
+                        self.metrics
+                            .paraclate_send_failed(fid, &item.payload_type.to_string(), "http");
                         if let Err(e) = self.queue_repo
                             .mark_failed(
                                 item.queue_id,
@@ -1044,6 +1112,8 @@ impl ParaclateClient {
                 }
                 Err(e) => {
                     let error_msg = e.to_string();
+                    self.metrics
+                        .paraclate_send_failed(fid, &item.payload_type.to_string(), "network");
                     self.queue_repo
                         .mark_failed(item.queue_id, &error_msg, None, item.retry_count + 1)
                         .await
@@ -1179,6 +1249,7 @@ impl ParaclateClient {
                         error = %error_msg,
                         "Camera metadata send failed"
                     );
+                    self.metrics.paraclate_send_failed(fid, "camera_metadata", "http");
 
                     Ok(CameraMetadataResponse {
                         success: false,
@@ -1190,6 +1261,7 @@ impl ParaclateClient {
             Err(e) => {
                 let error_msg = e.to_string();
                 error!(tid = %tid, fid = %fid, error = %error_msg, "Camera metadata send error");
+                self.metrics.paraclate_send_failed(fid, "camera_metadata", "network");
 
                 Err(ParaclateError::Http(error_msg))
             }
@@ -1256,6 +1328,7 @@ impl ParaclateClient {
                         status = %status,
                         "Camera deletion notification failed"
                     );
+                    self.metrics.paraclate_send_failed(fid, "camera_deleted", "http");
 
                     Ok(CameraMetadataResponse {
                         success: false,
@@ -1267,6 +1340,7 @@ impl ParaclateClient {
             Err(e) => {
                 let error_msg = e.to_string();
                 error!(tid = %tid, fid = %fid, error = %error_msg, "Camera deletion notification error");
+                self.metrics.paraclate_send_failed(fid, "camera_deleted", "network");
 
                 Err(ParaclateError::Http(error_msg))
             }
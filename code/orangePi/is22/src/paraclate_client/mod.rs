@@ -25,7 +25,7 @@
 //! use is22::paraclate_client::ParaclateClient;
 //!
 //! // クライアント作成
-//! let client = ParaclateClient::new(pool, config_store);
+//! let client = ParaclateClient::new(pool, config_store, metrics);
 //!
 //! // 接続
 //! let result = client.connect(tid, fid, endpoint).await?;
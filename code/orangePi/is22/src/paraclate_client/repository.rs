@@ -6,13 +6,14 @@
 //! ## 概要
 //! Paraclate設定・送信キュー・接続ログのDB操作
 
+use crate::db::{Db, DbRow, InsertId, IntervalUnit};
 use crate::paraclate_client::types::{
     calculate_retry_delay, defaults, ConnectionEventType, ConnectionLog, ConnectionLogInsert,
     ConnectionStatus, ParaclateConfig, ParaclateConfigInsert, ParaclateConfigUpdate, PayloadType,
     QueueStats, QueueStatus, SendQueueInsert, SendQueueItem, SendQueueUpdate,
 };
+use crate::with_db;
 use chrono::{DateTime, Duration, Utc};
-use sqlx::{MySqlPool, Row};
 
 // ============================================================
 // Config Repository
@@ -20,61 +21,65 @@ use sqlx::{MySqlPool, Row};
 
 /// Paraclate設定リポジトリ
 pub struct ConfigRepository {
-    pool: MySqlPool,
+    db: Db,
 }
 
 impl Clone for ConfigRepository {
     fn clone(&self) -> Self {
         Self {
-            pool: self.pool.clone(),
+            db: self.db.clone(),
         }
     }
 }
 
 impl ConfigRepository {
-    pub fn new(pool: MySqlPool) -> Self {
-        Self { pool }
+    pub fn new(db: impl Into<Db>) -> Self {
+        Self { db: db.into() }
     }
 
     /// 設定を取得（tid/fid指定）
     pub async fn get(&self, tid: &str, fid: &str) -> Result<Option<ParaclateConfig>, sqlx::Error> {
-        let row = sqlx::query(
-            r#"
-            SELECT
-                config_id, tid, fid, endpoint,
-                report_interval_minutes, grand_summary_times, retention_days, attunement,
-                sync_source_timestamp, last_sync_at, connection_status, last_error,
-                created_at, updated_at
-            FROM paraclate_config
-            WHERE tid = ? AND fid = ?
-            "#,
-        )
-        .bind(tid)
-        .bind(fid)
-        .fetch_optional(&self.pool)
-        .await?;
+        with_db!(&self.db, pool => {
+            let row = sqlx::query(
+                r#"
+                SELECT
+                    config_id, tid, fid, endpoint,
+                    report_interval_minutes, grand_summary_times, retention_days, attunement,
+                    sync_source_timestamp, last_sync_at, connection_status, last_error,
+                    created_at, updated_at
+                FROM paraclate_config
+                WHERE tid = ? AND fid = ?
+                "#,
+            )
+            .bind(tid)
+            .bind(fid)
+            .fetch_optional(pool)
+            .await?;
 
-        Ok(row.map(|r| self.row_to_config(&r)))
+            Ok(row.map(|r| self.row_to_config(&r)))
+        })
     }
 
     /// 設定を取得（config_id指定）
     pub async fn get_by_id(&self, config_id: u32) -> Result<Option<ParaclateConfig>, sqlx::Error> {
-        let row = sqlx::query(
-            r#"
-            SELECT
-                config_id, tid, fid, endpoint,
-                report_interval_minutes, grand_summary_times, retention_days, attunement,
-                sync_source_timestamp, last_sync_at, connection_status, last_error,
-                created_at, updated_at
-            FROM paraclate_config
-            WHERE config_id = ?
-            "#,
-        )
-        .bind(config_id)
-        .fetch_optional(&self.pool)
-        .await?;
+        with_db!(&self.db, pool => {
+            let row = sqlx::query(
+                r#"
+                SELECT
+                    config_id, tid, fid, endpoint,
+                    report_interval_minutes, grand_summary_times, retention_days, attunement,
+                    sync_source_timestamp, last_sync_at, connection_status, last_error,
+                    created_at, updated_at
+                FROM paraclate_config
+                WHERE config_id = ?
+                "#,
+            )
+            .bind(config_id)
+            .fetch_optional(pool)
+            .await?;
 
-        Ok(row.map(|r| self.row_to_config(&r)))
+            Ok(row.map(|r| self.row_to_config(&r)))
+        })
     }
 
     /// 設定を挿入
@@ -84,24 +89,27 @@ impl ConfigRepository {
             .unwrap_or_else(defaults::grand_summary_times);
         let attunement = insert.attunement.unwrap_or(serde_json::json!({}));
 
-        let result = sqlx::query(
-            r#"
-            INSERT INTO paraclate_config
-                (tid, fid, endpoint, report_interval_minutes, grand_summary_times, retention_days, attunement)
-            VALUES (?, ?, ?, ?, ?, ?, ?)
-            "#,
-        )
-        .bind(&insert.tid)
-        .bind(&insert.fid)
-        .bind(&insert.endpoint)
-        .bind(insert.report_interval_minutes.unwrap_or(defaults::REPORT_INTERVAL_MINUTES))
-        .bind(serde_json::to_string(&grand_summary_times).unwrap())
-        .bind(insert.retention_days.unwrap_or(defaults::RETENTION_DAYS))
-        .bind(attunement.to_string())
-        .execute(&self.pool)
-        .await?;
-
-        Ok(result.last_insert_id() as u32)
+        let config_id = with_db!(&self.db, pool => {
+            sqlx::query(
+                r#"
+                INSERT INTO paraclate_config
+                    (tid, fid, endpoint, report_interval_minutes, grand_summary_times, retention_days, attunement)
+                VALUES (?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(&insert.tid)
+            .bind(&insert.fid)
+            .bind(&insert.endpoint)
+            .bind(insert.report_interval_minutes.unwrap_or(defaults::REPORT_INTERVAL_MINUTES))
+            .bind(serde_json::to_string(&grand_summary_times).unwrap())
+            .bind(insert.retention_days.unwrap_or(defaults::RETENTION_DAYS))
+            .bind(attunement.to_string())
+            .execute(pool)
+            .await
+            .map(|r| r.insert_id())
+        })?;
+
+        Ok(config_id as u32)
     }
 
     /// 設定を更新
@@ -111,7 +119,7 @@ impl ConfigRepository {
         fid: &str,
         update: ParaclateConfigUpdate,
     ) -> Result<bool, sqlx::Error> {
-        let mut query = String::from("UPDATE paraclate_config SET updated_at = NOW(3)");
+        let mut query = format!("UPDATE paraclate_config SET updated_at = {}", self.db.now());
         let mut binds: Vec<String> = vec![];
 
         if let Some(endpoint) = &update.endpoint {
@@ -141,14 +149,16 @@ impl ConfigRepository {
 
         query.push_str(" WHERE tid = ? AND fid = ?");
 
-        let mut q = sqlx::query(&query);
-        for bind in &binds {
-            q = q.bind(bind);
-        }
-        q = q.bind(tid).bind(fid);
+        let affected = with_db!(&self.db, pool => {
+            let mut q = sqlx::query(&query);
+            for bind in &binds {
+                q = q.bind(bind);
+            }
+            q = q.bind(tid).bind(fid);
 
-        let result = q.execute(&self.pool).await?;
-        Ok(result.rows_affected() > 0)
+            q.execute(pool).await.map(|r| r.rows_affected())
+        })?;
+        Ok(affected > 0)
     }
 
     /// 接続状態を更新
@@ -159,52 +169,84 @@ impl ConfigRepository {
         status: ConnectionStatus,
         error: Option<&str>,
     ) -> Result<bool, sqlx::Error> {
-        let result = sqlx::query(
+        let sql = format!(
             r#"
             UPDATE paraclate_config
-            SET connection_status = ?, last_error = ?, last_sync_at = IF(? = 'connected', NOW(3), last_sync_at)
+            SET connection_status = ?, last_error = ?,
+                last_sync_at = CASE WHEN ? = 'connected' THEN {} ELSE last_sync_at END
             WHERE tid = ? AND fid = ?
             "#,
-        )
-        .bind(status.to_string())
-        .bind(error)
-        .bind(status.to_string())
-        .bind(tid)
-        .bind(fid)
-        .execute(&self.pool)
-        .await?;
+            self.db.now()
+        );
+        let affected = with_db!(&self.db, pool => {
+            sqlx::query(&sql)
+                .bind(status.to_string())
+                .bind(error)
+                .bind(status.to_string())
+                .bind(tid)
+                .bind(fid)
+                .execute(pool)
+                .await
+                .map(|r| r.rows_affected())
+        })?;
+
+        Ok(affected > 0)
+    }
 
-        Ok(result.rows_affected() > 0)
+    /// 全設定を取得（接続状態を問わず）
+    pub async fn get_all(&self) -> Result<Vec<ParaclateConfig>, sqlx::Error> {
+        with_db!(&self.db, pool => {
+            let rows = sqlx::query(
+                r#"
+                SELECT
+                    config_id, tid, fid, endpoint,
+                    report_interval_minutes, grand_summary_times, retention_days, attunement,
+                    sync_source_timestamp, last_sync_at, connection_status, last_error,
+                    created_at, updated_at
+                FROM paraclate_config
+                ORDER BY tid, fid
+                "#,
+            )
+            .fetch_all(pool)
+            .await?;
+
+            Ok(rows.iter().map(|r| self.row_to_config(r)).collect())
+        })
     }
 
     /// 全設定を取得（有効なもののみ）
     pub async fn get_all_connected(&self) -> Result<Vec<ParaclateConfig>, sqlx::Error> {
-        let rows = sqlx::query(
-            r#"
-            SELECT
-                config_id, tid, fid, endpoint,
-                report_interval_minutes, grand_summary_times, retention_days, attunement,
-                sync_source_timestamp, last_sync_at, connection_status, last_error,
-                created_at, updated_at
-            FROM paraclate_config
-            WHERE connection_status = 'connected'
-            "#,
-        )
-        .fetch_all(&self.pool)
-        .await?;
+        with_db!(&self.db, pool => {
+            let rows = sqlx::query(
+                r#"
+                SELECT
+                    config_id, tid, fid, endpoint,
+                    report_interval_minutes, grand_summary_times, retention_days, attunement,
+                    sync_source_timestamp, last_sync_at, connection_status, last_error,
+                    created_at, updated_at
+                FROM paraclate_config
+                WHERE connection_status = 'connected'
+                "#,
+            )
+            .fetch_all(pool)
+            .await?;
 
-        Ok(rows.iter().map(|r| self.row_to_config(r)).collect())
+            Ok(rows.iter().map(|r| self.row_to_config(r)).collect())
+        })
     }
 
     /// 設定を削除
     pub async fn delete(&self, tid: &str, fid: &str) -> Result<bool, sqlx::Error> {
-        let result = sqlx::query("DELETE FROM paraclate_config WHERE tid = ? AND fid = ?")
-            .bind(tid)
-            .bind(fid)
-            .execute(&self.pool)
-            .await?;
-
-        Ok(result.rows_affected() > 0)
+        let affected = with_db!(&self.db, pool => {
+            sqlx::query("DELETE FROM paraclate_config WHERE tid = ? AND fid = ?")
+                .bind(tid)
+                .bind(fid)
+                .execute(pool)
+                .await
+                .map(|r| r.rows_affected())
+        })?;
+
+        Ok(affected > 0)
     }
 
     /// 設定が存在しない場合、scan_subnetsから自動作成
@@ -220,18 +262,20 @@ impl ConfigRepository {
         }
 
         // scan_subnetsでtid/fid検証
-        let subnet_valid: Option<(String,)> = sqlx::query_as(
-            r#"
-            SELECT fid
-            FROM scan_subnets
-            WHERE tid = ? AND fid = ?
-            LIMIT 1
-            "#,
-        )
-        .bind(tid)
-        .bind(fid)
-        .fetch_optional(&self.pool)
-        .await?;
+        let subnet_valid: Option<(String,)> = with_db!(&self.db, pool => {
+            sqlx::query_as(
+                r#"
+                SELECT fid
+                FROM scan_subnets
+                WHERE tid = ? AND fid = ?
+                LIMIT 1
+                "#,
+            )
+            .bind(tid)
+            .bind(fid)
+            .fetch_optional(pool)
+            .await
+        })?;
 
         if subnet_valid.is_none() {
             // 無効なtid/fid組み合わせ（scan_subnetsに存在しない）
@@ -240,17 +284,19 @@ impl ConfigRepository {
         }
 
         // 同じtidの既存configからendpointを取得
-        let existing_endpoint: Option<(String,)> = sqlx::query_as(
-            r#"
-            SELECT endpoint
-            FROM paraclate_config
-            WHERE tid = ? AND connection_status = 'connected'
-            LIMIT 1
-            "#,
-        )
-        .bind(tid)
-        .fetch_optional(&self.pool)
-        .await?;
+        let existing_endpoint: Option<(String,)> = with_db!(&self.db, pool => {
+            sqlx::query_as(
+                r#"
+                SELECT endpoint
+                FROM paraclate_config
+                WHERE tid = ? AND connection_status = 'connected'
+                LIMIT 1
+                "#,
+            )
+            .bind(tid)
+            .fetch_optional(pool)
+            .await
+        })?;
 
         // デフォルトエンドポイント（mobes2.0 Cloud Functions）
         let endpoint = existing_endpoint
@@ -274,32 +320,32 @@ impl ConfigRepository {
         self.get_by_id(config_id).await
     }
 
-    fn row_to_config(&self, row: &sqlx::mysql::MySqlRow) -> ParaclateConfig {
-        let grand_summary_json: String = row.get("grand_summary_times");
+    fn row_to_config<R: DbRow>(&self, row: &R) -> ParaclateConfig {
+        let grand_summary_json: String = row.col("grand_summary_times");
         let grand_summary_times: Vec<String> =
             serde_json::from_str(&grand_summary_json).unwrap_or_else(|_| defaults::grand_summary_times());
 
-        let attunement_json: String = row.get("attunement");
+        let attunement_json: String = row.col("attunement");
         let attunement: serde_json::Value =
             serde_json::from_str(&attunement_json).unwrap_or(serde_json::json!({}));
 
-        let status_str: String = row.get("connection_status");
+        let status_str: String = row.col("connection_status");
 
         ParaclateConfig {
-            config_id: row.get("config_id"),
-            tid: row.get("tid"),
-            fid: row.get("fid"),
-            endpoint: row.get("endpoint"),
-            report_interval_minutes: row.get("report_interval_minutes"),
+            config_id: row.col_u32("config_id").expect("config_id"),
+            tid: row.col("tid"),
+            fid: row.col("fid"),
+            endpoint: row.col("endpoint"),
+            report_interval_minutes: row.col("report_interval_minutes"),
             grand_summary_times,
-            retention_days: row.get("retention_days"),
+            retention_days: row.col("retention_days"),
             attunement,
-            sync_source_timestamp: row.get("sync_source_timestamp"),
-            last_sync_at: row.get("last_sync_at"),
+            sync_source_timestamp: row.col("sync_source_timestamp"),
+            last_sync_at: row.col("last_sync_at"),
             connection_status: ConnectionStatus::from(status_str.as_str()),
-            last_error: row.get("last_error"),
-            created_at: row.get("created_at"),
-            updated_at: row.get("updated_at"),
+            last_error: row.col("last_error"),
+            created_at: row.col("created_at"),
+            updated_at: row.col("updated_at"),
         }
     }
 }
@@ -310,65 +356,70 @@ impl ConfigRepository {
 
 /// 送信キューリポジトリ
 pub struct SendQueueRepository {
-    pool: MySqlPool,
+    db: Db,
 }
 
 impl Clone for SendQueueRepository {
     fn clone(&self) -> Self {
         Self {
-            pool: self.pool.clone(),
+            db: self.db.clone(),
         }
     }
 }
 
 impl SendQueueRepository {
-    pub fn new(pool: MySqlPool) -> Self {
-        Self { pool }
+    pub fn new(db: impl Into<Db>) -> Self {
+        Self { db: db.into() }
     }
 
     /// キュー項目を挿入
     pub async fn insert(&self, insert: SendQueueInsert) -> Result<u64, sqlx::Error> {
-        let result = sqlx::query(
-            r#"
-            INSERT INTO paraclate_send_queue
-                (tid, fid, payload_type, payload, reference_id, max_retries)
-            VALUES (?, ?, ?, ?, ?, ?)
-            "#,
-        )
-        .bind(&insert.tid)
-        .bind(&insert.fid)
-        .bind(insert.payload_type.to_string())
-        .bind(insert.payload.to_string())
-        .bind(insert.reference_id)
-        .bind(insert.max_retries.unwrap_or(defaults::MAX_RETRIES))
-        .execute(&self.pool)
-        .await?;
-
-        Ok(result.last_insert_id())
+        let queue_id = with_db!(&self.db, pool => {
+            sqlx::query(
+                r#"
+                INSERT INTO paraclate_send_queue
+                    (tid, fid, payload_type, payload, reference_id, max_retries)
+                VALUES (?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(&insert.tid)
+            .bind(&insert.fid)
+            .bind(insert.payload_type.to_string())
+            .bind(insert.payload.to_string())
+            .bind(insert.reference_id.map(|id| id as i64))
+            .bind(insert.max_retries.unwrap_or(defaults::MAX_RETRIES))
+            .execute(pool)
+            .await
+            .map(|r| r.insert_id())
+        })?;
+
+        Ok(queue_id)
     }
 
     /// キュー項目を取得
     pub async fn get(&self, queue_id: u64) -> Result<Option<SendQueueItem>, sqlx::Error> {
-        let row = sqlx::query(
-            r#"
-            SELECT
-                queue_id, tid, fid, payload_type, payload, reference_id,
-                status, retry_count, max_retries, next_retry_at,
-                last_error, http_status_code, created_at, sent_at
-            FROM paraclate_send_queue
-            WHERE queue_id = ?
-            "#,
-        )
-        .bind(queue_id)
-        .fetch_optional(&self.pool)
-        .await?;
+        with_db!(&self.db, pool => {
+            let row = sqlx::query(
+                r#"
+                SELECT
+                    queue_id, tid, fid, payload_type, payload, reference_id,
+                    status, retry_count, max_retries, next_retry_at,
+                    last_error, http_status_code, created_at, sent_at
+                FROM paraclate_send_queue
+                WHERE queue_id = ?
+                "#,
+            )
+            .bind(queue_id as i64)
+            .fetch_optional(pool)
+            .await?;
 
-        Ok(row.map(|r| self.row_to_item(&r)))
+            Ok(row.map(|r| self.row_to_item(&r)))
+        })
     }
 
     /// 送信待ちキューを取得（リトライ対象含む）
     pub async fn get_pending(&self, tid: &str, fid: &str, limit: i32) -> Result<Vec<SendQueueItem>, sqlx::Error> {
-        let rows = sqlx::query(
+        let sql = format!(
             r#"
             SELECT
                 queue_id, tid, fid, payload_type, payload, reference_id,
@@ -376,18 +427,22 @@ impl SendQueueRepository {
                 last_error, http_status_code, created_at, sent_at
             FROM paraclate_send_queue
             WHERE tid = ? AND fid = ?
-              AND (status = 'pending' OR (status = 'failed' AND retry_count < max_retries AND (next_retry_at IS NULL OR next_retry_at <= NOW(3))))
+              AND (status = 'pending' OR (status = 'failed' AND retry_count < max_retries AND (next_retry_at IS NULL OR next_retry_at <= {})))
             ORDER BY created_at ASC
             LIMIT ?
             "#,
-        )
-        .bind(tid)
-        .bind(fid)
-        .bind(limit)
-        .fetch_all(&self.pool)
-        .await?;
-
-        Ok(rows.iter().map(|r| self.row_to_item(r)).collect())
+            self.db.now()
+        );
+        with_db!(&self.db, pool => {
+            let rows = sqlx::query(&sql)
+                .bind(tid)
+                .bind(fid)
+                .bind(limit)
+                .fetch_all(pool)
+                .await?;
+
+            Ok(rows.iter().map(|r| self.row_to_item(r)).collect())
+        })
     }
 
     /// キュー項目のステータスを更新
@@ -401,8 +456,8 @@ impl SendQueueRepository {
         }
         if let Some(next) = update.next_retry_at {
             query.push_str(", next_retry_at = ?");
-            // MySQL datetime(3) format: "YYYY-MM-DD HH:MM:SS.mmm"
-            params.push(Some(next.format("%Y-%m-%d %H:%M:%S%.3f").to_string()));
+            // MySQL datetime(3) / SQLite RFC3339 (Db::datetime_param)
+            params.push(Some(self.db.datetime_param(next)));
         }
         if let Some(error) = &update.last_error {
             query.push_str(", last_error = ?");
@@ -414,20 +469,22 @@ impl SendQueueRepository {
         }
         if let Some(sent) = update.sent_at {
             query.push_str(", sent_at = ?");
-            // MySQL datetime(3) format: "YYYY-MM-DD HH:MM:SS.mmm"
-            params.push(Some(sent.format("%Y-%m-%d %H:%M:%S%.3f").to_string()));
+            // MySQL datetime(3) / SQLite RFC3339 (Db::datetime_param)
+            params.push(Some(self.db.datetime_param(sent)));
         }
 
         query.push_str(" WHERE queue_id = ?");
 
-        let mut q = sqlx::query(&query);
-        for param in &params {
-            q = q.bind(param.as_deref());
-        }
-        q = q.bind(queue_id);
+        let affected = with_db!(&self.db, pool => {
+            let mut q = sqlx::query(&query);
+            for param in &params {
+                q = q.bind(param.as_deref());
+            }
+            q = q.bind(queue_id as i64);
 
-        let result = q.execute(&self.pool).await?;
-        Ok(result.rows_affected() > 0)
+            q.execute(pool).await.map(|r| r.rows_affected())
+        })?;
+        Ok(affected > 0)
     }
 
     /// 送信済みに更新
@@ -472,44 +529,51 @@ impl SendQueueRepository {
 
     /// キュー統計を取得
     pub async fn get_stats(&self, tid: &str, fid: &str) -> Result<QueueStats, sqlx::Error> {
-        let row = sqlx::query(
+        let sql = format!(
             r#"
             SELECT
                 CAST(COALESCE(SUM(CASE WHEN status = 'pending' THEN 1 ELSE 0 END), 0) AS SIGNED) as pending,
                 CAST(COALESCE(SUM(CASE WHEN status = 'sending' THEN 1 ELSE 0 END), 0) AS SIGNED) as sending,
                 CAST(COALESCE(SUM(CASE WHEN status = 'failed' THEN 1 ELSE 0 END), 0) AS SIGNED) as failed,
-                CAST(COALESCE(SUM(CASE WHEN status = 'sent' AND sent_at >= DATE(NOW()) THEN 1 ELSE 0 END), 0) AS SIGNED) as sent_today
+                CAST(COALESCE(SUM(CASE WHEN status = 'sent' AND sent_at >= {} THEN 1 ELSE 0 END), 0) AS SIGNED) as sent_today
             FROM paraclate_send_queue
             WHERE tid = ? AND fid = ?
             "#,
-        )
-        .bind(tid)
-        .bind(fid)
-        .fetch_one(&self.pool)
-        .await?;
-
-        Ok(QueueStats {
-            pending: row.get::<i64, _>("pending") as u64,
-            sending: row.get::<i64, _>("sending") as u64,
-            failed: row.get::<i64, _>("failed") as u64,
-            sent_today: row.get::<i64, _>("sent_today") as u64,
+            self.db.today()
+        );
+        with_db!(&self.db, pool => {
+            let row = sqlx::query(&sql)
+                .bind(tid)
+                .bind(fid)
+                .fetch_one(pool)
+                .await?;
+
+            Ok(QueueStats {
+                pending: row.col::<i64>("pending") as u64,
+                sending: row.col::<i64>("sending") as u64,
+                failed: row.col::<i64>("failed") as u64,
+                sent_today: row.col::<i64>("sent_today") as u64,
+            })
         })
     }
 
     /// 古いキュー項目を削除
     pub async fn cleanup_old(&self, days: i32) -> Result<u64, sqlx::Error> {
-        let result = sqlx::query(
+        let sql = format!(
             r#"
             DELETE FROM paraclate_send_queue
             WHERE status IN ('sent', 'skipped')
-              AND created_at < DATE_SUB(NOW(), INTERVAL ? DAY)
+              AND created_at < {}
             "#,
-        )
-        .bind(days)
-        .execute(&self.pool)
-        .await?;
-
-        Ok(result.rows_affected())
+            self.db.ago("?", IntervalUnit::Day)
+        );
+        with_db!(&self.db, pool => {
+            sqlx::query(&sql)
+                .bind(days)
+                .execute(pool)
+                .await
+                .map(|r| r.rows_affected())
+        })
     }
 
     /// 一覧取得
@@ -521,70 +585,148 @@ impl SendQueueRepository {
         limit: i32,
         offset: i32,
     ) -> Result<Vec<SendQueueItem>, sqlx::Error> {
-        let query = if let Some(s) = status {
-            sqlx::query(
-                r#"
-                SELECT
-                    queue_id, tid, fid, payload_type, payload, reference_id,
-                    status, retry_count, max_retries, next_retry_at,
-                    last_error, http_status_code, created_at, sent_at
-                FROM paraclate_send_queue
-                WHERE tid = ? AND fid = ? AND status = ?
-                ORDER BY created_at DESC
-                LIMIT ? OFFSET ?
-                "#,
-            )
-            .bind(tid)
-            .bind(fid)
-            .bind(s.to_string())
-            .bind(limit)
-            .bind(offset)
-        } else {
-            sqlx::query(
-                r#"
-                SELECT
-                    queue_id, tid, fid, payload_type, payload, reference_id,
-                    status, retry_count, max_retries, next_retry_at,
-                    last_error, http_status_code, created_at, sent_at
-                FROM paraclate_send_queue
-                WHERE tid = ? AND fid = ?
-                ORDER BY created_at DESC
-                LIMIT ? OFFSET ?
-                "#,
-            )
-            .bind(tid)
-            .bind(fid)
-            .bind(limit)
-            .bind(offset)
-        };
+        with_db!(&self.db, pool => {
+            let query = if let Some(s) = status {
+                sqlx::query(
+                    r#"
+                    SELECT
+                        queue_id, tid, fid, payload_type, payload, reference_id,
+                        status, retry_count, max_retries, next_retry_at,
+                        last_error, http_status_code, created_at, sent_at
+                    FROM paraclate_send_queue
+                    WHERE tid = ? AND fid = ? AND status = ?
+                    ORDER BY created_at DESC
+                    LIMIT ? OFFSET ?
+                    "#,
+                )
+                .bind(tid)
+                .bind(fid)
+                .bind(s.to_string())
+                .bind(limit)
+                .bind(offset)
+            } else {
+                sqlx::query(
+                    r#"
+                    SELECT
+                        queue_id, tid, fid, payload_type, payload, reference_id,
+                        status, retry_count, max_retries, next_retry_at,
+                        last_error, http_status_code, created_at, sent_at
+                    FROM paraclate_send_queue
+                    WHERE tid = ? AND fid = ?
+                    ORDER BY created_at DESC
+                    LIMIT ? OFFSET ?
+                    "#,
+                )
+                .bind(tid)
+                .bind(fid)
+                .bind(limit)
+                .bind(offset)
+            };
+
+            let rows = query.fetch_all(pool).await?;
+            Ok(rows.iter().map(|r| self.row_to_item(r)).collect())
+        })
+    }
+
+    /// 一覧取得（全tid、fid/ステータス任意）- 管理CLI用
+    pub async fn list_recent(
+        &self,
+        fid: Option<&str>,
+        status: Option<QueueStatus>,
+        limit: i32,
+    ) -> Result<Vec<SendQueueItem>, sqlx::Error> {
+        let sql = format!(
+            r#"
+            SELECT
+                queue_id, tid, fid, payload_type, payload, reference_id,
+                status, retry_count, max_retries, next_retry_at,
+                last_error, http_status_code, created_at, sent_at
+            FROM paraclate_send_queue
+            WHERE 1 = 1{}{}
+            ORDER BY created_at DESC
+            LIMIT ?
+            "#,
+            if fid.is_some() { " AND fid = ?" } else { "" },
+            if status.is_some() { " AND status = ?" } else { "" },
+        );
+        with_db!(&self.db, pool => {
+            let mut query = sqlx::query(&sql);
+            if let Some(fid) = fid {
+                query = query.bind(fid);
+            }
+            if let Some(status) = status {
+                query = query.bind(status.to_string());
+            }
+            let rows = query.bind(limit).fetch_all(pool).await?;
+            Ok(rows.iter().map(|r| self.row_to_item(r)).collect())
+        })
+    }
 
-        let rows = query.fetch_all(&self.pool).await?;
-        Ok(rows.iter().map(|r| self.row_to_item(r)).collect())
+    /// failed 項目を pending に戻す（リトライ回数リセット）
+    pub async fn requeue_failed(
+        &self,
+        fid: Option<&str>,
+        queue_id: Option<u64>,
+    ) -> Result<u64, sqlx::Error> {
+        let sql = format!(
+            r#"
+            UPDATE paraclate_send_queue
+            SET status = 'pending', retry_count = 0, next_retry_at = NULL, last_error = NULL
+            WHERE status = 'failed'{}{}
+            "#,
+            if fid.is_some() { " AND fid = ?" } else { "" },
+            if queue_id.is_some() { " AND queue_id = ?" } else { "" },
+        );
+        with_db!(&self.db, pool => {
+            let mut query = sqlx::query(&sql);
+            if let Some(fid) = fid {
+                query = query.bind(fid);
+            }
+            if let Some(queue_id) = queue_id {
+                query = query.bind(queue_id as i64);
+            }
+            Ok(query.execute(pool).await?.rows_affected())
+        })
+    }
+
+    /// 指定ステータスの項目を削除
+    pub async fn purge(&self, fid: Option<&str>, status: QueueStatus) -> Result<u64, sqlx::Error> {
+        let sql = format!(
+            "DELETE FROM paraclate_send_queue WHERE status = ?{}",
+            if fid.is_some() { " AND fid = ?" } else { "" },
+        );
+        with_db!(&self.db, pool => {
+            let mut query = sqlx::query(&sql).bind(status.to_string());
+            if let Some(fid) = fid {
+                query = query.bind(fid);
+            }
+            Ok(query.execute(pool).await?.rows_affected())
+        })
     }
 
-    fn row_to_item(&self, row: &sqlx::mysql::MySqlRow) -> SendQueueItem {
-        let payload_str: String = row.get("payload");
+    fn row_to_item<R: DbRow>(&self, row: &R) -> SendQueueItem {
+        let payload_str: String = row.col("payload");
         let payload: serde_json::Value =
             serde_json::from_str(&payload_str).unwrap_or(serde_json::json!({}));
 
-        let type_str: String = row.get("payload_type");
-        let status_str: String = row.get("status");
+        let type_str: String = row.col("payload_type");
+        let status_str: String = row.col("status");
 
         SendQueueItem {
-            queue_id: row.get("queue_id"),
-            tid: row.get("tid"),
-            fid: row.get("fid"),
+            queue_id: row.col_u64("queue_id").expect("queue_id"),
+            tid: row.col("tid"),
+            fid: row.col("fid"),
             payload_type: PayloadType::from(type_str.as_str()),
             payload,
-            reference_id: row.get("reference_id"),
+            reference_id: row.col_opt_u64("reference_id").expect("reference_id"),
             status: QueueStatus::from(status_str.as_str()),
-            retry_count: row.get("retry_count"),
-            max_retries: row.get("max_retries"),
-            next_retry_at: row.get("next_retry_at"),
-            last_error: row.get("last_error"),
-            http_status_code: row.get("http_status_code"),
-            created_at: row.get("created_at"),
-            sent_at: row.get("sent_at"),
+            retry_count: row.col("retry_count"),
+            max_retries: row.col("max_retries"),
+            next_retry_at: row.col("next_retry_at"),
+            last_error: row.col("last_error"),
+            http_status_code: row.col("http_status_code"),
+            created_at: row.col("created_at"),
+            sent_at: row.col("sent_at"),
         }
     }
 }
@@ -595,41 +737,44 @@ impl SendQueueRepository {
 
 /// 接続ログリポジトリ
 pub struct ConnectionLogRepository {
-    pool: MySqlPool,
+    db: Db,
 }
 
 impl Clone for ConnectionLogRepository {
     fn clone(&self) -> Self {
         Self {
-            pool: self.pool.clone(),
+            db: self.db.clone(),
         }
     }
 }
 
 impl ConnectionLogRepository {
-    pub fn new(pool: MySqlPool) -> Self {
-        Self { pool }
+    pub fn new(db: impl Into<Db>) -> Self {
+        Self { db: db.into() }
     }
 
     /// ログを挿入
     pub async fn insert(&self, insert: ConnectionLogInsert) -> Result<u64, sqlx::Error> {
-        let result = sqlx::query(
-            r#"
-            INSERT INTO paraclate_connection_log
-                (tid, fid, event_type, event_detail, error_code, http_status_code)
-            VALUES (?, ?, ?, ?, ?, ?)
-            "#,
-        )
-        .bind(&insert.tid)
-        .bind(&insert.fid)
-        .bind(insert.event_type.to_string())
-        .bind(&insert.event_detail)
-        .bind(&insert.error_code)
-        .bind(insert.http_status_code)
-        .execute(&self.pool)
-        .await?;
-
-        Ok(result.last_insert_id())
+        let log_id = with_db!(&self.db, pool => {
+            sqlx::query(
+                r#"
+                INSERT INTO paraclate_connection_log
+                    (tid, fid, event_type, event_detail, error_code, http_status_code)
+                VALUES (?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(&insert.tid)
+            .bind(&insert.fid)
+            .bind(insert.event_type.to_string())
+            .bind(&insert.event_detail)
+            .bind(&insert.error_code)
+            .bind(insert.http_status_code)
+            .execute(pool)
+            .await
+            .map(|r| r.insert_id())
+        })?;
+
+        Ok(log_id)
     }
 
     /// 最近のログを取得
@@ -639,43 +784,45 @@ impl ConnectionLogRepository {
         fid: &str,
         limit: i32,
     ) -> Result<Vec<ConnectionLog>, sqlx::Error> {
-        let rows = sqlx::query(
-            r#"
-            SELECT
-                log_id, tid, fid, event_type, event_detail,
-                error_code, http_status_code, created_at
-            FROM paraclate_connection_log
-            WHERE tid = ? AND fid = ?
-            ORDER BY created_at DESC
-            LIMIT ?
-            "#,
-        )
-        .bind(tid)
-        .bind(fid)
-        .bind(limit)
-        .fetch_all(&self.pool)
-        .await?;
+        with_db!(&self.db, pool => {
+            let rows = sqlx::query(
+                r#"
+                SELECT
+                    log_id, tid, fid, event_type, event_detail,
+                    error_code, http_status_code, created_at
+                FROM paraclate_connection_log
+                WHERE tid = ? AND fid = ?
+                ORDER BY created_at DESC
+                LIMIT ?
+                "#,
+            )
+            .bind(tid)
+            .bind(fid)
+            .bind(limit)
+            .fetch_all(pool)
+            .await?;
 
-        Ok(rows.iter().map(|r| self.row_to_log(r)).collect())
+            Ok(rows.iter().map(|r| self.row_to_log(r)).collect())
+        })
     }
 
     /// 古いログを削除
     pub async fn cleanup_old(&self, days: i32) -> Result<u64, sqlx::Error> {
-        let result = sqlx::query(
-            r#"
-            DELETE FROM paraclate_connection_log
-            WHERE created_at < DATE_SUB(NOW(), INTERVAL ? DAY)
-            "#,
-        )
-        .bind(days)
-        .execute(&self.pool)
-        .await?;
-
-        Ok(result.rows_affected())
+        let sql = format!(
+            "DELETE FROM paraclate_connection_log WHERE created_at < {}",
+            self.db.ago("?", IntervalUnit::Day)
+        );
+        with_db!(&self.db, pool => {
+            sqlx::query(&sql)
+                .bind(days)
+                .execute(pool)
+                .await
+                .map(|r| r.rows_affected())
+        })
     }
 
-    fn row_to_log(&self, row: &sqlx::mysql::MySqlRow) -> ConnectionLog {
-        let type_str: String = row.get("event_type");
+    fn row_to_log<R: DbRow>(&self, row: &R) -> ConnectionLog {
+        let type_str: String = row.col("event_type");
         let event_type = match type_str.as_str() {
             "connect" => ConnectionEventType::Connect,
             "disconnect" => ConnectionEventType::Disconnect,
@@ -685,14 +832,86 @@ impl ConnectionLogRepository {
         };
 
         ConnectionLog {
-            log_id: row.get("log_id"),
-            tid: row.get("tid"),
-            fid: row.get("fid"),
+            log_id: row.col_u64("log_id").expect("log_id"),
+            tid: row.col("tid"),
+            fid: row.col("fid"),
             event_type,
-            event_detail: row.get("event_detail"),
-            error_code: row.get("error_code"),
-            http_status_code: row.get("http_status_code"),
-            created_at: row.get("created_at"),
+            event_detail: row.col("event_detail"),
+            error_code: row.col("error_code"),
+            http_status_code: row.col("http_status_code"),
+            created_at: row.col("created_at"),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn queue_insert(payload_type: PayloadType) -> SendQueueInsert {
+        SendQueueInsert {
+            tid: "T1".to_string(),
+            fid: "0150".to_string(),
+            payload_type,
+            payload: serde_json::json!({"summary": "ok"}),
+            reference_id: Some(42),
+            max_retries: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_send_queue_lifecycle() {
+        let repo = SendQueueRepository::new(Db::memory().await.unwrap());
+
+        let first = repo.insert(queue_insert(PayloadType::Summary)).await.unwrap();
+        let second = repo.insert(queue_insert(PayloadType::Event)).await.unwrap();
+
+        let pending = repo.get_pending("T1", "0150", 10).await.unwrap();
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending[0].queue_id, first);
+        assert_eq!(pending[0].reference_id, Some(42));
+
+        assert!(repo.mark_sent(first).await.unwrap());
+        // 失敗直後は next_retry_at が未来のため送信待ちに含まれない
+        assert!(repo.mark_failed(second, "timeout", Some(504), 1).await.unwrap());
+        assert!(repo.get_pending("T1", "0150", 10).await.unwrap().is_empty());
+
+        let stats = repo.get_stats("T1", "0150").await.unwrap();
+        assert_eq!(stats.pending, 0);
+        assert_eq!(stats.failed, 1);
+        assert_eq!(stats.sent_today, 1);
+
+        let failed = repo.get(second).await.unwrap().unwrap();
+        assert_eq!(failed.retry_count, 1);
+        assert_eq!(failed.http_status_code, Some(504));
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_config_connection_status() {
+        let repo = ConfigRepository::new(Db::memory().await.unwrap());
+
+        let config_id = repo
+            .insert(ParaclateConfigInsert {
+                tid: "T1".to_string(),
+                fid: "0150".to_string(),
+                endpoint: "https://paraclate.example/api".to_string(),
+                report_interval_minutes: None,
+                grand_summary_times: None,
+                retention_days: None,
+                attunement: None,
+            })
+            .await
+            .unwrap();
+        assert!(config_id > 0);
+
+        assert!(repo
+            .update_connection_status("T1", "0150", ConnectionStatus::Connected, None)
+            .await
+            .unwrap());
+        let config = repo.get("T1", "0150").await.unwrap().unwrap();
+        assert_eq!(config.config_id, config_id);
+        assert_eq!(config.connection_status, ConnectionStatus::Connected);
+        assert!(config.last_sync_at.is_some());
+        assert_eq!(repo.get_all_connected().await.unwrap().len(), 1);
+    }
+}
@@ -63,6 +63,10 @@ pub enum PayloadType {
     GrandSummary,
     Event,
     Emergency,
+    /// 週次ロールアップ（RollupPayload）
+    WeeklyRollup,
+    /// 月次ロールアップ（RollupPayload）
+    MonthlyRollup,
 }
 
 impl std::fmt::Display for PayloadType {
@@ -72,6 +76,8 @@ impl std::fmt::Display for PayloadType {
             Self::GrandSummary => write!(f, "grand_summary"),
             Self::Event => write!(f, "event"),
             Self::Emergency => write!(f, "emergency"),
+            Self::WeeklyRollup => write!(f, "weekly_rollup"),
+            Self::MonthlyRollup => write!(f, "monthly_rollup"),
         }
     }
 }
@@ -82,6 +88,8 @@ impl From<&str> for PayloadType {
             "grand_summary" => Self::GrandSummary,
             "event" => Self::Event,
             "emergency" => Self::Emergency,
+            "weekly_rollup" => Self::WeeklyRollup,
+            "monthly_rollup" => Self::MonthlyRollup,
             _ => Self::Summary,
         }
     }
@@ -510,6 +518,20 @@ pub enum CameraMalfunctionType {
     LowFps,
     /// フレーム取得不可
     NoFrames,
+    /// 実ストリームがカメラ能力情報と不一致（コーデック・解像度）
+    StreamMismatch,
+    /// 低ビットレート
+    LowBitrate,
+    /// 黒画面
+    BlackFrame,
+    /// フレーム静止（映像更新停止）
+    FrozenFrame,
+    /// ピンぼけ・レンズ汚れ
+    Blurred,
+    /// 画角の急変（カメラ移動・遮蔽）
+    SceneShift,
+    /// IS22 本体のストレージ逼迫（camera_id には IS22 の LacisID を使う）
+    StoragePressure,
 }
 
 impl std::fmt::Display for CameraMalfunctionType {
@@ -520,6 +542,13 @@ impl std::fmt::Display for CameraMalfunctionType {
             Self::HighLatency => write!(f, "high_latency"),
             Self::LowFps => write!(f, "low_fps"),
             Self::NoFrames => write!(f, "no_frames"),
+            Self::StreamMismatch => write!(f, "stream_mismatch"),
+            Self::LowBitrate => write!(f, "low_bitrate"),
+            Self::BlackFrame => write!(f, "black_frame"),
+            Self::FrozenFrame => write!(f, "frozen_frame"),
+            Self::Blurred => write!(f, "blurred"),
+            Self::SceneShift => write!(f, "scene_shift"),
+            Self::StoragePressure => write!(f, "storage_pressure"),
         }
     }
 }
@@ -1018,6 +1047,8 @@ mod tests {
         assert_eq!(PayloadType::GrandSummary.to_string(), "grand_summary");
         assert_eq!(PayloadType::Event.to_string(), "event");
         assert_eq!(PayloadType::Emergency.to_string(), "emergency");
+        assert_eq!(PayloadType::WeeklyRollup.to_string(), "weekly_rollup");
+        assert_eq!(PayloadType::from("monthly_rollup"), PayloadType::MonthlyRollup);
     }
 
     #[test]
@@ -0,0 +1,462 @@
+//! AdaptiveScheduler - Activity-driven per-camera polling intervals
+//!
+//! ## Design Intent
+//!
+//! サブネットループは従来カメラを固定順で毎サイクル巡回していた。
+//! AdaptiveSchedulerはカメラごとに次回巡回時刻を管理し、サイクル開始時に
+//! 「期限到来したカメラ」を優先度順に選出する。
+//!
+//! - 活動あり（検出・SuggestEngineの対象）: priority interval（`ai_interval_sec`）
+//! - `suggest_policy_weight` が高いほど基本間隔を短縮
+//! - フレーム差分で「変化なし」が連続した静的シーン: 指数バックオフ（上限あり）
+//! - サブネット帯域予算: 推定スナップショットサイズの合計が予算内に収まるよう選出
+//!
+//! `PollingPolicy.adaptive.enabled = false` の場合は従来動作（全カメラ固定順）。
+
+use crate::config_store::{Camera, PollingPolicy};
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+
+/// Fallback snapshot size estimate before the first successful poll (bytes)
+const DEFAULT_IMAGE_BYTES_ESTIMATE: usize = 200 * 1024;
+/// Smoothing factor for the image size moving average
+const IMAGE_BYTES_EMA_ALPHA: f64 = 0.3;
+
+/// Why a camera got its current interval
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IntervalReason {
+    /// Weighted base interval
+    Base,
+    /// Recent detection within activity window
+    Activity,
+    /// Camera is the current SuggestEngine target
+    SuggestActive,
+    /// Static scene backoff
+    Backoff,
+    /// Last poll failed (kept at base interval for lost detection)
+    Failure,
+}
+
+/// Observation from a single successful poll
+#[derive(Debug, Clone, Copy)]
+pub struct CameraPollOutcome {
+    /// Total processing time (ms)
+    pub total_ms: i32,
+    /// Captured snapshot size
+    pub image_bytes: usize,
+    /// IS21 reported a detection
+    pub activity: bool,
+    /// Scene did not change since the previous frame
+    pub no_change: bool,
+    /// IS21 inference was skipped by the motion pre-filter
+    pub inference_skipped: bool,
+}
+
+/// Camera fields relevant to interval calculation
+#[derive(Debug, Clone, Copy)]
+pub struct CameraScheduleParams {
+    pub polling_interval_sec: i32,
+    pub ai_interval_sec: i32,
+    pub suggest_policy_weight: i32,
+}
+
+impl From<&Camera> for CameraScheduleParams {
+    fn from(camera: &Camera) -> Self {
+        Self {
+            polling_interval_sec: camera.polling_interval_sec,
+            ai_interval_sec: camera.ai_interval_sec,
+            suggest_policy_weight: camera.suggest_policy_weight,
+        }
+    }
+}
+
+/// Per-camera scheduling state
+#[derive(Debug, Clone, Serialize)]
+pub struct CameraScheduleState {
+    pub camera_id: String,
+    pub subnet: String,
+    pub interval_sec: i64,
+    pub reason: IntervalReason,
+    pub next_due_at: Option<DateTime<Utc>>,
+    pub last_polled_at: Option<DateTime<Utc>>,
+    pub last_activity_at: Option<DateTime<Utc>>,
+    pub no_change_streak: u32,
+    pub failure_streak: u32,
+    pub avg_image_bytes: Option<usize>,
+}
+
+impl CameraScheduleState {
+    fn new(camera_id: &str, subnet: &str) -> Self {
+        Self {
+            camera_id: camera_id.to_string(),
+            subnet: subnet.to_string(),
+            interval_sec: 0,
+            reason: IntervalReason::Base,
+            next_due_at: None,
+            last_polled_at: None,
+            last_activity_at: None,
+            no_change_streak: 0,
+            failure_streak: 0,
+            avg_image_bytes: None,
+        }
+    }
+
+    fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.next_due_at.map(|due| due <= now).unwrap_or(true)
+    }
+
+    fn estimated_bytes(&self) -> usize {
+        self.avg_image_bytes.unwrap_or(DEFAULT_IMAGE_BYTES_ESTIMATE)
+    }
+}
+
+/// Calculate the next interval for a camera
+///
+/// Priority: SuggestActive > Activity > Backoff > Base
+pub fn compute_interval(
+    policy: &PollingPolicy,
+    params: CameraScheduleParams,
+    state: &CameraScheduleState,
+    suggest_active: bool,
+    now: DateTime<Utc>,
+) -> (i64, IntervalReason) {
+    let adaptive = &policy.adaptive;
+
+    let base = if params.polling_interval_sec > 0 {
+        params.polling_interval_sec
+    } else {
+        policy.base_interval_sec
+    }
+    .max(1) as f64;
+    let priority = if params.ai_interval_sec > 0 {
+        params.ai_interval_sec
+    } else {
+        policy.priority_interval_sec
+    }
+    .max(1) as i64;
+    let ceiling = (adaptive.max_interval_sec as i64).max(base as i64);
+
+    // weight 5 (default) = 1.0x, weight 10 = 0.5x, weight 1 = 2.0x
+    let weight_factor = (5.0 / params.suggest_policy_weight.max(1) as f64).clamp(0.5, 2.0);
+    let weighted = ((base * weight_factor).round() as i64).clamp(priority, ceiling);
+
+    if suggest_active {
+        return (priority, IntervalReason::SuggestActive);
+    }
+
+    if let Some(last_activity) = state.last_activity_at {
+        if now - last_activity <= Duration::seconds(adaptive.activity_window_sec as i64) {
+            return (priority, IntervalReason::Activity);
+        }
+    }
+
+    if state.failure_streak > 0 {
+        return (weighted, IntervalReason::Failure);
+    }
+
+    let backoff_after = adaptive.no_change_backoff_after.max(1) as u32;
+    if state.no_change_streak >= backoff_after {
+        let exponent = (state.no_change_streak - backoff_after + 1).min(16);
+        let backed_off = weighted.saturating_mul(1i64 << exponent).min(ceiling);
+        return (backed_off, IntervalReason::Backoff);
+    }
+
+    (weighted, IntervalReason::Base)
+}
+
+/// Select due cameras ordered by urgency, within the subnet budget
+///
+/// At least one camera is always selected when any is due so that a small
+/// budget cannot starve the subnet.
+fn select_due(
+    candidates: &[(usize, &CameraScheduleState, i32)],
+    budget_bytes: Option<usize>,
+    now: DateTime<Utc>,
+) -> Vec<usize> {
+    let mut scored: Vec<(usize, f64, usize)> = candidates
+        .iter()
+        .filter(|(_, state, _)| state.is_due(now))
+        .map(|(index, state, weight)| {
+            let overdue_sec = state
+                .next_due_at
+                .map(|due| (now - due).num_seconds().max(0) as f64)
+                .unwrap_or(f64::MAX / 4.0);
+            let interval = state.interval_sec.max(1) as f64;
+            let mut score = overdue_sec / interval + (*weight as f64) / 10.0;
+            if matches!(
+                state.reason,
+                IntervalReason::Activity | IntervalReason::SuggestActive
+            ) {
+                score += 1.0;
+            }
+            (*index, score, state.estimated_bytes())
+        })
+        .collect();
+
+    scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+    let mut selected = Vec::with_capacity(scored.len());
+    let mut used_bytes: usize = 0;
+    for (index, _, bytes) in scored {
+        if let Some(budget) = budget_bytes {
+            if !selected.is_empty() && used_bytes + bytes > budget {
+                continue;
+            }
+        }
+        used_bytes += bytes;
+        selected.push(index);
+    }
+    selected
+}
+
+/// AdaptiveScheduler instance (shared by all subnet loops)
+pub struct AdaptiveScheduler {
+    states: RwLock<HashMap<String, CameraScheduleState>>,
+}
+
+impl AdaptiveScheduler {
+    /// Create new scheduler
+    pub fn new() -> Self {
+        Self {
+            states: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Plan which cameras to poll in this subnet cycle
+    ///
+    /// Returns cameras in polling order. When adaptive scheduling is disabled
+    /// the input order is returned unchanged.
+    pub async fn plan_cycle(
+        &self,
+        policy: &PollingPolicy,
+        subnet: &str,
+        cameras: Vec<Camera>,
+        now: DateTime<Utc>,
+    ) -> Vec<Camera> {
+        if !policy.adaptive.enabled {
+            return cameras;
+        }
+
+        let mut states = self.states.write().await;
+
+        // Drop state of cameras that left this subnet
+        states.retain(|camera_id, state| {
+            state.subnet != subnet || cameras.iter().any(|c| &c.camera_id == camera_id)
+        });
+
+        for camera in &cameras {
+            states
+                .entry(camera.camera_id.clone())
+                .and_modify(|s| s.subnet = subnet.to_string())
+                .or_insert_with(|| CameraScheduleState::new(&camera.camera_id, subnet));
+        }
+
+        let candidates: Vec<(usize, &CameraScheduleState, i32)> = cameras
+            .iter()
+            .enumerate()
+            .filter_map(|(index, camera)| {
+                states
+                    .get(&camera.camera_id)
+                    .map(|state| (index, state, camera.suggest_policy_weight))
+            })
+            .collect();
+
+        let budget_bytes = match policy.adaptive.subnet_budget_kb_per_cycle {
+            kb if kb > 0 => Some(kb as usize * 1024),
+            _ => None,
+        };
+
+        let order = select_due(&candidates, budget_bytes, now);
+        let deferred = candidates.len() - order.len();
+        drop(candidates);
+        drop(states);
+
+        if deferred > 0 {
+            tracing::debug!(
+                subnet = %subnet,
+                selected = order.len(),
+                deferred = deferred,
+                "Adaptive scheduler deferred cameras (not due or over budget)"
+            );
+        }
+
+        let mut slots: Vec<Option<Camera>> = cameras.into_iter().map(Some).collect();
+        order
+            .into_iter()
+            .filter_map(|index| slots[index].take())
+            .collect()
+    }
+
+    /// Record a poll result and schedule the camera's next poll
+    ///
+    /// `outcome` is None when the poll failed.
+    pub async fn record(
+        &self,
+        policy: &PollingPolicy,
+        camera: &Camera,
+        subnet: &str,
+        outcome: Option<&CameraPollOutcome>,
+        suggest_active: bool,
+        now: DateTime<Utc>,
+    ) {
+        let mut states = self.states.write().await;
+        let state = states
+            .entry(camera.camera_id.clone())
+            .or_insert_with(|| CameraScheduleState::new(&camera.camera_id, subnet));
+
+        state.last_polled_at = Some(now);
+        match outcome {
+            Some(outcome) => {
+                state.failure_streak = 0;
+                if outcome.activity {
+                    state.last_activity_at = Some(now);
+                    state.no_change_streak = 0;
+                } else if outcome.no_change {
+                    state.no_change_streak = state.no_change_streak.saturating_add(1);
+                } else {
+                    state.no_change_streak = 0;
+                }
+                state.avg_image_bytes = Some(match state.avg_image_bytes {
+                    Some(avg) => (avg as f64 * (1.0 - IMAGE_BYTES_EMA_ALPHA)
+                        + outcome.image_bytes as f64 * IMAGE_BYTES_EMA_ALPHA)
+                        as usize,
+                    None => outcome.image_bytes,
+                });
+            }
+            None => {
+                state.failure_streak = state.failure_streak.saturating_add(1);
+                state.no_change_streak = 0;
+            }
+        }
+
+        let (interval_sec, reason) =
+            compute_interval(policy, CameraScheduleParams::from(camera), state, suggest_active, now);
+        state.interval_sec = interval_sec;
+        state.reason = reason;
+        state.next_due_at = Some(now + Duration::seconds(interval_sec));
+
+        tracing::trace!(
+            camera_id = %camera.camera_id,
+            interval_sec = interval_sec,
+            reason = ?reason,
+            no_change_streak = state.no_change_streak,
+            "Adaptive schedule updated"
+        );
+    }
+
+    /// Get scheduling state of all tracked cameras
+    pub async fn snapshot(&self) -> Vec<CameraScheduleState> {
+        let mut states: Vec<_> = self.states.read().await.values().cloned().collect();
+        states.sort_by(|a, b| a.subnet.cmp(&b.subnet).then(a.camera_id.cmp(&b.camera_id)));
+        states
+    }
+
+    /// Forget a camera (e.g., deleted)
+    pub async fn remove(&self, camera_id: &str) {
+        self.states.write().await.remove(camera_id);
+    }
+}
+
+impl Default for AdaptiveScheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(weight: i32) -> CameraScheduleParams {
+        CameraScheduleParams {
+            polling_interval_sec: 60,
+            ai_interval_sec: 15,
+            suggest_policy_weight: weight,
+        }
+    }
+
+    fn enabled_policy() -> PollingPolicy {
+        let mut policy = PollingPolicy::default();
+        policy.adaptive.enabled = true;
+        policy
+    }
+
+    #[test]
+    fn test_interval_base_and_weight() {
+        let policy = enabled_policy();
+        let now = Utc::now();
+        let state = CameraScheduleState::new("cam1", "192.168.125");
+
+        assert_eq!(compute_interval(&policy, params(5), &state, false, now), (60, IntervalReason::Base));
+        assert_eq!(compute_interval(&policy, params(10), &state, false, now).0, 30);
+        assert_eq!(compute_interval(&policy, params(1), &state, false, now).0, 120);
+    }
+
+    #[test]
+    fn test_interval_activity_and_suggest() {
+        let policy = enabled_policy();
+        let now = Utc::now();
+        let mut state = CameraScheduleState::new("cam1", "192.168.125");
+
+        assert_eq!(
+            compute_interval(&policy, params(5), &state, true, now),
+            (15, IntervalReason::SuggestActive)
+        );
+
+        state.last_activity_at = Some(now - Duration::seconds(60));
+        assert_eq!(
+            compute_interval(&policy, params(5), &state, false, now),
+            (15, IntervalReason::Activity)
+        );
+
+        state.last_activity_at = Some(now - Duration::seconds(3600));
+        assert_eq!(compute_interval(&policy, params(5), &state, false, now).1, IntervalReason::Base);
+    }
+
+    #[test]
+    fn test_interval_backoff_ceiling() {
+        let policy = enabled_policy();
+        let now = Utc::now();
+        let mut state = CameraScheduleState::new("cam1", "192.168.125");
+
+        state.no_change_streak = 3;
+        assert_eq!(
+            compute_interval(&policy, params(5), &state, false, now),
+            (120, IntervalReason::Backoff)
+        );
+
+        state.no_change_streak = 30;
+        assert_eq!(
+            compute_interval(&policy, params(5), &state, false, now),
+            (600, IntervalReason::Backoff)
+        );
+    }
+
+    #[test]
+    fn test_select_due_respects_budget_and_order() {
+        let now = Utc::now();
+        let mut overdue = CameraScheduleState::new("cam1", "s");
+        overdue.interval_sec = 60;
+        overdue.next_due_at = Some(now - Duration::seconds(120));
+        overdue.avg_image_bytes = Some(300 * 1024);
+
+        let mut fresh = CameraScheduleState::new("cam2", "s");
+        fresh.interval_sec = 60;
+        fresh.next_due_at = Some(now);
+        fresh.avg_image_bytes = Some(300 * 1024);
+
+        let mut not_due = CameraScheduleState::new("cam3", "s");
+        not_due.next_due_at = Some(now + Duration::seconds(30));
+
+        let candidates = vec![(0, &fresh, 5), (1, &overdue, 5), (2, &not_due, 5)];
+
+        assert_eq!(select_due(&candidates, None, now), vec![1, 0]);
+        // Budget fits only one camera, the most overdue one wins
+        assert_eq!(select_due(&candidates, Some(400 * 1024), now), vec![1]);
+        // Budget smaller than a single camera still polls one
+        assert_eq!(select_due(&candidates, Some(1), now), vec![1]);
+    }
+}
@@ -17,19 +17,44 @@
 //! サブネット 192.168.126.x: (並列実行)
 //!   [Camera1取得(3s timeout)] → [is21 POST] → [Camera2取得] → ...
 //! ```
+//!
+//! ## Adaptive Scheduling
+//!
+//! `settings.polling.adaptive.enabled` が有効な場合、各サイクルで巡回するカメラは
+//! `AdaptiveScheduler` が選出する（活動中は短間隔、静的シーンはバックオフ、
+//! サブネット帯域予算内）。無効時は全カメラを固定順で巡回する。
+//!
+//! ## Supervision
+//!
+//! 各サブネットループは TaskSupervisor 配下（タスク名 `polling:<subnet>`）で動き、
+//! パニック時はバックオフ後に再起動される。`active_subnets` の登録はループが
+//! 正常終了（`stop()`）したときのみ外し、再起動待ちの間に同じサブネットが二重起動されないようにする。
+
+mod adaptive_scheduler;
+
+pub use adaptive_scheduler::{
+    AdaptiveScheduler, CameraPollOutcome, CameraScheduleState, IntervalReason,
+};
 
 use crate::access_absorber::{AccessAbsorberService, StreamPurpose};
 use crate::ai_client::{AIClient, AnalyzeRequest, AnalyzeResponse, CameraContext};
-use crate::camera_status_tracker::{CameraStatusEvent, CameraStatusTracker};
-use crate::config_store::{Camera, ConfigStore};
+use crate::camera_availability::{AvailabilityState, CameraAvailabilityService, OutageCause};
+use crate::camera_status_tracker::{CameraConnectionStatus, CameraStatusEvent, CameraStatusTracker};
+use crate::config_store::{Camera, ConfigStore, PollingPolicy};
+use crate::metrics::{Metrics, PollResult};
 use crate::models::ProcessingTimings;
+use crate::motion_prefilter::{ChangeParams, MotionPrefilter, PrefilterDecision};
+use crate::preset_schedule::PresetScheduleService;
+use crate::email_notifier::{DetectionAlert, EmailNotifier};
 use crate::detection_log_service::{DetectionLogService, should_save_image};
 use crate::event_log_service::{DetectionEvent, EventLogService};
+use crate::health::HealthRegistry;
 use crate::prev_frame_cache::{FrameMeta, PrevFrameCache};
 use crate::preset_loader::PresetLoader;
 use crate::snapshot_service::{CaptureResult, SnapshotService, SnapshotSource};
 use crate::stream_gateway::StreamGateway;
 use crate::suggest_engine::SuggestEngine;
+use crate::task_supervisor::TaskSupervisor;
 use crate::realtime_hub::{
     CooldownTickMessage, CycleStatsMessage, EventLogMessage, HubMessage, RealtimeHub,
     SnapshotUpdatedMessage,
@@ -38,16 +63,18 @@ use chrono::{DateTime, Utc};
 use rand::Rng;
 use sqlx::MySqlPool;
 use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 
 /// Cooldown duration between polling cycles (seconds)
 const CYCLE_COOLDOWN_SEC: u32 = 15;
-/// Snapshot capture timeout in milliseconds (30 seconds for very slow cameras)
-const SNAPSHOT_TIMEOUT_MS: u64 = 30000;
 /// Threshold for "slow camera" warning (10 seconds)
 const SLOW_CAMERA_THRESHOLD_MS: u64 = 10000;
+/// Subnet loop without a completed cycle for this long is stalled (/livez)
+/// 1サイクルはカメラ台数 × スナップショットタイムアウトまで伸びうるため長めに取る
+const SUBNET_STALL_AFTER: Duration = Duration::from_secs(900);
 
 /// Filter excluded objects from IS21 response
 /// Issue #104: unknown乱発防止 - IS22側でexcluded_objectsをポストフィルタリング
@@ -95,71 +122,89 @@ fn filter_excluded_objects(response: &mut AnalyzeResponse, excluded_objects: &[S
     }
 }
 
+/// Services and settings shared by every subnet polling loop
+#[derive(Clone)]
+pub struct PollingDeps {
+    pub pool: MySqlPool,
+    pub config_store: Arc<ConfigStore>,
+    pub snapshot_service: Arc<SnapshotService>,
+    pub ai_client: Arc<AIClient>,
+    pub event_log: Arc<EventLogService>,
+    pub detection_log: Arc<DetectionLogService>,
+    pub prev_frame_cache: Arc<PrevFrameCache>,
+    pub preset_loader: Arc<PresetLoader>,
+    pub suggest_engine: Arc<SuggestEngine>,
+    pub realtime_hub: Arc<RealtimeHub>,
+    pub camera_status_tracker: Arc<CameraStatusTracker>,
+    /// Persisted availability timeline (lost/recovered intervals with cause)
+    pub availability: Arc<CameraAvailabilityService>,
+    /// go2rtc registration at cycle start
+    pub stream_gateway: Arc<StreamGateway>,
+    /// Detection events with snapshots
+    pub paraclate_client: Arc<crate::paraclate_client::ParaclateClient>,
+    /// Email notifier (high-severity detection alerts)
+    pub email_notifier: Arc<EmailNotifier>,
+    /// AccessAbsorberService for camera brand-specific connection limits (None = bypass)
+    pub access_absorber: Option<Arc<AccessAbsorberService>>,
+    /// Prometheus metrics (snapshot / IS21 latency, cycle duration)
+    pub metrics: Arc<Metrics>,
+    /// Snapshot capture timeout per camera (ms, AppConfig.polling)
+    pub snapshot_timeout_ms: u64,
+    /// Heartbeats of subnet polling loops
+    pub health: Arc<HealthRegistry>,
+    /// Runs the subnet loops (restart on panic)
+    pub supervisor: Arc<TaskSupervisor>,
+    /// Default TID (tenant ID) for logging
+    pub default_tid: String,
+    /// Default FID (facility ID) for logging
+    pub default_fid: String,
+}
+
 /// PollingOrchestrator instance
+#[derive(Clone)]
 pub struct PollingOrchestrator {
-    pool: MySqlPool,
-    config_store: Arc<ConfigStore>,
-    snapshot_service: Arc<SnapshotService>,
-    ai_client: Arc<AIClient>,
-    event_log: Arc<EventLogService>,
-    detection_log: Arc<DetectionLogService>,
-    prev_frame_cache: Arc<PrevFrameCache>,
-    preset_loader: Arc<PresetLoader>,
-    suggest_engine: Arc<SuggestEngine>,
-    realtime_hub: Arc<RealtimeHub>,
-    camera_status_tracker: Arc<CameraStatusTracker>,
-    stream_gateway: Arc<StreamGateway>,
-    paraclate_client: Arc<crate::paraclate_client::ParaclateClient>,
-    /// AccessAbsorberService for camera brand-specific connection limits
-    access_absorber: Option<Arc<AccessAbsorberService>>,
+    deps: PollingDeps,
     running: Arc<RwLock<bool>>,
     /// Active subnet polling loops (to prevent duplicate spawns)
     active_subnets: Arc<RwLock<HashSet<String>>>,
-    /// Default TID (tenant ID) for logging
-    default_tid: String,
-    /// Default FID (facility ID) for logging
-    default_fid: String,
+    /// Subnet loops currently executing (excludes loops waiting for a restart)
+    running_loops: Arc<AtomicUsize>,
+    /// Per-camera adaptive interval scheduler (shared by all subnet loops)
+    adaptive_scheduler: Arc<AdaptiveScheduler>,
+    /// Local change detector (skips IS21 for unchanged scenes)
+    motion_prefilter: Arc<MotionPrefilter>,
+    /// Time-based preset resolution (JST weekly schedules)
+    preset_schedule: Arc<PresetScheduleService>,
+}
+
+/// Counts an executing subnet loop; released on return or panic
+struct RunningLoopGuard(Arc<AtomicUsize>);
+
+impl RunningLoopGuard {
+    fn enter(counter: &Arc<AtomicUsize>) -> Self {
+        counter.fetch_add(1, Ordering::SeqCst);
+        Self(counter.clone())
+    }
+}
+
+impl Drop for RunningLoopGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
 }
 
 impl PollingOrchestrator {
     /// Create new PollingOrchestrator
-    pub fn new(
-        pool: MySqlPool,
-        config_store: Arc<ConfigStore>,
-        snapshot_service: Arc<SnapshotService>,
-        ai_client: Arc<AIClient>,
-        event_log: Arc<EventLogService>,
-        detection_log: Arc<DetectionLogService>,
-        prev_frame_cache: Arc<PrevFrameCache>,
-        preset_loader: Arc<PresetLoader>,
-        suggest_engine: Arc<SuggestEngine>,
-        realtime_hub: Arc<RealtimeHub>,
-        camera_status_tracker: Arc<CameraStatusTracker>,
-        stream_gateway: Arc<StreamGateway>,
-        paraclate_client: Arc<crate::paraclate_client::ParaclateClient>,
-        access_absorber: Option<Arc<AccessAbsorberService>>,
-        default_tid: String,
-        default_fid: String,
-    ) -> Self {
+    pub fn new(deps: PollingDeps) -> Self {
+        let preset_schedule = Arc::new(PresetScheduleService::new(deps.pool.clone()));
         Self {
-            pool,
-            config_store,
-            snapshot_service,
-            ai_client,
-            event_log,
-            detection_log,
-            prev_frame_cache,
-            preset_loader,
-            suggest_engine,
-            realtime_hub,
-            camera_status_tracker,
-            stream_gateway,
-            paraclate_client,
-            access_absorber,
+            deps,
             running: Arc::new(RwLock::new(false)),
             active_subnets: Arc::new(RwLock::new(HashSet::new())),
-            default_tid,
-            default_fid,
+            running_loops: Arc::new(AtomicUsize::new(0)),
+            adaptive_scheduler: Arc::new(AdaptiveScheduler::new()),
+            motion_prefilter: Arc::new(MotionPrefilter::new()),
+            preset_schedule,
         }
     }
 
@@ -226,12 +271,14 @@ impl PollingOrchestrator {
     }
 
     /// Update polling cycle record (at cycle completion)
+    #[allow(clippy::too_many_arguments)]
     async fn complete_polling_cycle(
         pool: &MySqlPool,
         polling_id: &str,
         success_count: u32,
         failed_count: u32,
         timeout_count: u32,
+        no_change_count: u32,
         duration_ms: i32,
         avg_processing_ms: Option<i32>,
     ) -> crate::error::Result<()> {
@@ -242,6 +289,7 @@ impl PollingOrchestrator {
                 success_count = ?,
                 failed_count = ?,
                 timeout_count = ?,
+                no_change_count = ?,
                 duration_ms = ?,
                 avg_processing_ms = ?,
                 status = 'completed'
@@ -251,6 +299,7 @@ impl PollingOrchestrator {
         .bind(success_count)
         .bind(failed_count)
         .bind(timeout_count)
+        .bind(no_change_count)
         .bind(duration_ms)
         .bind(avg_processing_ms)
         .bind(polling_id)
@@ -279,7 +328,7 @@ impl PollingOrchestrator {
         }
 
         // Get enabled cameras and group by subnet
-        let cameras = self.config_store.get_cached_cameras().await;
+        let cameras = self.deps.config_store.get_cached_cameras().await;
         let enabled: Vec<_> = cameras
             .into_iter()
             .filter(|c| c.enabled && c.polling_enabled)
@@ -289,7 +338,7 @@ impl PollingOrchestrator {
 
         tracing::info!(
             cooldown_sec = CYCLE_COOLDOWN_SEC,
-            snapshot_timeout_ms = SNAPSHOT_TIMEOUT_MS,
+            snapshot_timeout_ms = self.deps.snapshot_timeout_ms,
             subnet_count = subnets.len(),
             total_cameras = enabled.len(),
             "Starting subnet-parallel polling orchestrator"
@@ -303,62 +352,12 @@ impl PollingOrchestrator {
                 active.insert(subnet.clone());
             }
 
-            let pool = self.pool.clone();
-            let config_store = self.config_store.clone();
-            let snapshot_service = self.snapshot_service.clone();
-            let ai_client = self.ai_client.clone();
-            let event_log = self.event_log.clone();
-            let detection_log = self.detection_log.clone();
-            let prev_frame_cache = self.prev_frame_cache.clone();
-            let preset_loader = self.preset_loader.clone();
-            let suggest_engine = self.suggest_engine.clone();
-            let realtime_hub = self.realtime_hub.clone();
-            let camera_status_tracker = self.camera_status_tracker.clone();
-            let stream_gateway = self.stream_gateway.clone();
-            let paraclate_client = self.paraclate_client.clone();
-            let access_absorber = self.access_absorber.clone();
-            let running = self.running.clone();
-            let active_subnets = self.active_subnets.clone();
-            let default_tid = self.default_tid.clone();
-            let default_fid = self.default_fid.clone();
-
-            let initial_camera_count = cameras.len();
-            let subnet_clone = subnet.clone();
-
             tracing::info!(
                 subnet = %subnet,
-                initial_cameras = initial_camera_count,
+                initial_cameras = cameras.len(),
                 "Spawning subnet polling loop (dynamic camera detection enabled)"
             );
-
-            tokio::spawn(async move {
-                Self::run_subnet_loop(
-                    pool,
-                    subnet_clone.clone(),
-                    config_store,
-                    snapshot_service,
-                    ai_client,
-                    event_log,
-                    detection_log,
-                    prev_frame_cache,
-                    preset_loader,
-                    suggest_engine,
-                    realtime_hub,
-                    camera_status_tracker,
-                    stream_gateway,
-                    paraclate_client,
-                    access_absorber,
-                    running,
-                    default_tid,
-                    default_fid,
-                )
-                .await;
-
-                // Remove from active subnets when loop exits
-                let mut active = active_subnets.write().await;
-                active.remove(&subnet_clone);
-                tracing::info!(subnet = %subnet_clone, "Subnet polling loop removed from active set");
-            });
+            self.spawn_subnet_loop(subnet);
         }
     }
 
@@ -395,59 +394,30 @@ impl PollingOrchestrator {
             active.insert(subnet.clone());
         }
 
-        let pool = self.pool.clone();
-        let config_store = self.config_store.clone();
-        let snapshot_service = self.snapshot_service.clone();
-        let ai_client = self.ai_client.clone();
-        let event_log = self.event_log.clone();
-        let detection_log = self.detection_log.clone();
-        let prev_frame_cache = self.prev_frame_cache.clone();
-        let preset_loader = self.preset_loader.clone();
-        let suggest_engine = self.suggest_engine.clone();
-        let realtime_hub = self.realtime_hub.clone();
-        let camera_status_tracker = self.camera_status_tracker.clone();
-        let stream_gateway = self.stream_gateway.clone();
-        let paraclate_client = self.paraclate_client.clone();
-        let access_absorber = self.access_absorber.clone();
-        let running = self.running.clone();
-        let active_subnets = self.active_subnets.clone();
-        let default_tid = self.default_tid.clone();
-        let default_fid = self.default_fid.clone();
-
-        let subnet_clone = subnet.clone();
-
         tracing::info!(
             subnet = %subnet,
             "Spawning NEW subnet polling loop for dynamically added camera"
         );
+        self.spawn_subnet_loop(subnet);
+    }
 
-        tokio::spawn(async move {
-            Self::run_subnet_loop(
-                pool,
-                subnet_clone.clone(),
-                config_store,
-                snapshot_service,
-                ai_client,
-                event_log,
-                detection_log,
-                prev_frame_cache,
-                preset_loader,
-                suggest_engine,
-                realtime_hub,
-                camera_status_tracker,
-                stream_gateway,
-                paraclate_client,
-                access_absorber,
-                running,
-                default_tid,
-                default_fid,
-            )
-            .await;
-
-            // Remove from active subnets when loop exits
-            let mut active = active_subnets.write().await;
-            active.remove(&subnet_clone);
-            tracing::info!(subnet = %subnet_clone, "Subnet polling loop removed from active set");
+    /// Spawn the polling loop of a subnet already registered in `active_subnets`
+    fn spawn_subnet_loop(&self, subnet: String) {
+        let this = self.clone();
+        let name = format!("polling:{}", subnet);
+        self.deps.supervisor.spawn(&name, move |_shutdown| {
+            // 停止は running フラグで行う（stop() → wait_stopped() が supervisor 停止より先）
+            let this = this.clone();
+            let subnet = subnet.clone();
+            async move {
+                let _running = RunningLoopGuard::enter(&this.running_loops);
+                this.run_subnet_loop(subnet.clone()).await;
+
+                // Remove from active subnets when loop exits (a panic keeps it for the restart)
+                let mut active = this.active_subnets.write().await;
+                active.remove(&subnet);
+                tracing::info!(subnet = %subnet, "Subnet polling loop removed from active set");
+            }
         });
     }
 
@@ -457,6 +427,30 @@ impl PollingOrchestrator {
         active.iter().cloned().collect()
     }
 
+    /// Get adaptive scheduler (per-camera interval state)
+    pub fn adaptive_scheduler(&self) -> &Arc<AdaptiveScheduler> {
+        &self.adaptive_scheduler
+    }
+
+    /// Get motion pre-filter (per-camera skip counters)
+    pub fn motion_prefilter(&self) -> &Arc<MotionPrefilter> {
+        &self.motion_prefilter
+    }
+
+    /// Get preset schedule service (scheduled preset switching)
+    pub fn preset_schedule(&self) -> &Arc<PresetScheduleService> {
+        &self.preset_schedule
+    }
+
+    /// Load polling policy from ConfigStore cache (fallback to defaults)
+    async fn load_polling_policy(config_store: &ConfigStore) -> PollingPolicy {
+        config_store
+            .get_cached_setting("polling")
+            .await
+            .and_then(|json| serde_json::from_value(json).ok())
+            .unwrap_or_default()
+    }
+
     /// Extract subnet from IP address (first 3 octets)
     pub fn extract_subnet(ip: &str) -> String {
         let parts: Vec<&str> = ip.split('.').collect();
@@ -497,28 +491,39 @@ impl PollingOrchestrator {
     }
 
     /// Run polling loop for a specific subnet
-    #[allow(clippy::too_many_arguments)]
-    async fn run_subnet_loop(
-        pool: MySqlPool,
-        subnet: String,
-        config_store: Arc<ConfigStore>,
-        snapshot_service: Arc<SnapshotService>,
-        ai_client: Arc<AIClient>,
-        event_log: Arc<EventLogService>,
-        detection_log: Arc<DetectionLogService>,
-        prev_frame_cache: Arc<PrevFrameCache>,
-        preset_loader: Arc<PresetLoader>,
-        suggest_engine: Arc<SuggestEngine>,
-        realtime_hub: Arc<RealtimeHub>,
-        camera_status_tracker: Arc<CameraStatusTracker>,
-        stream_gateway: Arc<StreamGateway>,
-        paraclate_client: Arc<crate::paraclate_client::ParaclateClient>,
-        access_absorber: Option<Arc<AccessAbsorberService>>,
-        running: Arc<RwLock<bool>>,
-        default_tid: String,
-        default_fid: String,
-    ) {
+    async fn run_subnet_loop(&self, subnet: String) {
+        let PollingDeps {
+            pool,
+            config_store,
+            snapshot_service,
+            ai_client,
+            event_log,
+            detection_log,
+            prev_frame_cache,
+            preset_loader,
+            suggest_engine,
+            realtime_hub,
+            camera_status_tracker,
+            availability,
+            stream_gateway,
+            paraclate_client,
+            email_notifier,
+            access_absorber,
+            metrics,
+            snapshot_timeout_ms,
+            health,
+            default_tid,
+            default_fid,
+            ..
+        } = self.deps.clone();
+        let running = self.running.clone();
+        let adaptive_scheduler = self.adaptive_scheduler.clone();
+        let motion_prefilter = self.motion_prefilter.clone();
+        let preset_schedule = self.preset_schedule.clone();
+
         let mut cycle_number: u64 = 0;
+        let health_name = format!("polling:{}", subnet);
+        health.register(&health_name, SUBNET_STALL_AFTER);
 
         loop {
             // Check if still running
@@ -529,17 +534,6 @@ impl PollingOrchestrator {
                 }
             }
 
-            cycle_number += 1;
-            let cycle_start = Instant::now();
-            let cycle_started_at = Utc::now();
-            let mut successful: u32 = 0;
-            let mut failed: u32 = 0;
-            let mut timeout_count: u32 = 0;
-            let mut processing_times: Vec<i32> = Vec::new();
-
-            // Generate polling ID for this cycle
-            let polling_id = Self::generate_polling_id(&subnet, cycle_started_at);
-
             // Refresh camera list for this subnet (dynamically - new cameras included)
             let all_cameras = config_store.get_cached_cameras().await;
             let enabled: Vec<_> = all_cameras
@@ -552,7 +546,32 @@ impl PollingOrchestrator {
                 })
                 .collect();
 
-            let camera_count = enabled.len() as u32;
+            // Select cameras due in this cycle (all cameras when adaptive is disabled)
+            let polling_policy = Self::load_polling_policy(&config_store).await;
+            let due_cameras = adaptive_scheduler
+                .plan_cycle(&polling_policy, &subnet, enabled.clone(), Utc::now())
+                .await;
+
+            if polling_policy.adaptive.enabled && due_cameras.is_empty() && !enabled.is_empty() {
+                // Nothing due yet - wait for the next camera to come due
+                health.tick_ok(&health_name);
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                continue;
+            }
+
+            cycle_number += 1;
+            let cycle_start = Instant::now();
+            let cycle_started_at = Utc::now();
+            let mut successful: u32 = 0;
+            let mut failed: u32 = 0;
+            let mut timeout_count: u32 = 0;
+            let mut no_change_count: u32 = 0;
+            let mut processing_times: Vec<i32> = Vec::new();
+
+            // Generate polling ID for this cycle
+            let polling_id = Self::generate_polling_id(&subnet, cycle_started_at);
+
+            let camera_count = due_cameras.len() as u32;
 
             // === go2rtc Stream Registration at Cycle Start ===
             // Register all cameras with RTSP URLs to go2rtc at the beginning of each cycle.
@@ -661,7 +680,7 @@ impl PollingOrchestrator {
             );
 
             // Poll each camera sequentially within this subnet
-            for (index, camera) in enabled.iter().enumerate() {
+            for (index, camera) in due_cameras.iter().enumerate() {
                 // Check if still running before each camera
                 {
                     let is_running = running.read().await;
@@ -693,19 +712,67 @@ impl PollingOrchestrator {
                     &realtime_hub,
                     &config_store,
                     &paraclate_client,
+                    &email_notifier,
                     access_absorber.as_deref(),
+                    &metrics,
+                    snapshot_timeout_ms,
+                    &motion_prefilter,
+                    &preset_schedule,
+                    &polling_policy,
                     &default_tid,
                     &default_fid,
                     Some(&polling_id),
                 )
                 .await;
 
+                // Schedule this camera's next poll
+                let suggest_active = {
+                    let suggest_state = suggest_engine.get_state().await;
+                    suggest_state.active
+                        && suggest_state.camera_id.as_deref() == Some(camera.camera_id.as_str())
+                };
+                adaptive_scheduler
+                    .record(
+                        &polling_policy,
+                        camera,
+                        &subnet,
+                        poll_result.as_ref().ok(),
+                        suggest_active,
+                        Utc::now(),
+                    )
+                    .await;
+
                 // Track camera connection status and generate events
                 let is_online = poll_result.is_ok();
-                if let Some(status_event) = camera_status_tracker
+                let first_seen = camera_status_tracker.get_status(&camera.camera_id).await
+                    == CameraConnectionStatus::Unknown;
+                let status_event = camera_status_tracker
                     .update_status(&camera.camera_id, is_online)
-                    .await
-                {
+                    .await;
+
+                // Persist availability interval on transitions (and first observation)
+                if status_event.is_some() || first_seen {
+                    let cause = poll_result.as_ref().err().map(OutageCause::from_error);
+                    let detail = poll_result.as_ref().err().map(|e| e.to_string());
+                    if let Err(e) = availability
+                        .record_transition(
+                            &camera.camera_id,
+                            AvailabilityState::from_online(is_online),
+                            cause,
+                            detail.as_deref(),
+                            Utc::now(),
+                        )
+                        .await
+                    {
+                        tracing::warn!(
+                            camera_id = %camera.camera_id,
+                            error = %e,
+                            "Failed to record availability transition"
+                        );
+                    }
+                }
+
+                if let Some(status_event) = status_event {
                     // Get TID/FID for camera event logging (use defaults)
                     let tid = &default_tid;
                     let fid = &default_fid;
@@ -810,16 +877,21 @@ impl PollingOrchestrator {
 
                 // Handle poll result
                 match poll_result {
-                    Ok(processing_ms) => {
+                    Ok(outcome) => {
                         successful += 1;
-                        if let Some(ms) = processing_ms {
-                            processing_times.push(ms);
+                        if outcome.inference_skipped {
+                            no_change_count += 1;
+                            metrics.record_poll(&subnet, PollResult::NoChange);
+                        } else {
+                            metrics.record_poll(&subnet, PollResult::Success);
+                            processing_times.push(outcome.total_ms);
                         }
                     }
                     Err(e) => {
                         let error_str = format!("{}", e);
                         if error_str.contains("timeout") || error_str.contains("Timeout") {
                             timeout_count += 1;
+                            metrics.record_poll(&subnet, PollResult::Timeout);
                             tracing::warn!(
                                 subnet = %subnet,
                                 cycle = cycle_number,
@@ -834,13 +906,14 @@ impl PollingOrchestrator {
                                     timestamp: Utc::now().to_rfc3339(),
                                     primary_event: None,
                                     severity: None,
-                                    processing_ms: Some(SNAPSHOT_TIMEOUT_MS),
+                                    processing_ms: Some(snapshot_timeout_ms),
                                     error: Some("timeout".to_string()),
                                     snapshot_source: None,
                                 }))
                                 .await;
                         } else {
                             failed += 1;
+                            metrics.record_poll(&subnet, PollResult::Failed);
                             tracing::error!(
                                 subnet = %subnet,
                                 cycle = cycle_number,
@@ -868,6 +941,8 @@ impl PollingOrchestrator {
 
             // Cycle completed - calculate stats
             let cycle_duration = cycle_start.elapsed();
+            metrics.observe_cycle(&subnet, cycle_duration);
+            health.tick_ok(&health_name);
             let cycle_duration_sec = cycle_duration.as_secs();
             let cycle_duration_ms = cycle_duration.as_millis() as i32;
             let minutes = cycle_duration_sec / 60;
@@ -888,6 +963,7 @@ impl PollingOrchestrator {
                 successful,
                 failed,
                 timeout_count,
+                no_change_count,
                 cycle_duration_ms,
                 avg_processing_ms,
             )
@@ -911,6 +987,7 @@ impl PollingOrchestrator {
                 successful = successful,
                 failed = failed,
                 timeout = timeout_count,
+                no_change = no_change_count,
                 avg_processing_ms = ?avg_processing_ms,
                 "Subnet cycle completed"
             );
@@ -949,6 +1026,7 @@ impl PollingOrchestrator {
             }
         }
 
+        health.unregister(&health_name);
         tracing::info!(subnet = %subnet, "Subnet polling loop stopped");
     }
 
@@ -959,6 +1037,21 @@ impl PollingOrchestrator {
         tracing::info!("Stopping polling orchestrator");
     }
 
+    /// Wait until every subnet loop has exited after `stop()` (false = deadline passed)
+    ///
+    /// 各ループは次のカメラに進む前に停止するため、処理中カメラの検出ログ保存は完了する
+    pub async fn wait_stopped(&self, deadline: Instant) -> bool {
+        loop {
+            if self.running_loops.load(Ordering::SeqCst) == 0 {
+                return true;
+            }
+            if Instant::now() >= deadline {
+                return false;
+            }
+            tokio::time::sleep(Duration::from_millis(200)).await;
+        }
+    }
+
     /// Poll a single camera with AI Event Log pipeline
     ///
     /// Flow (AI Event Log v1.7 + Access Absorber):
@@ -972,7 +1065,7 @@ impl PollingOrchestrator {
     /// 8. Legacy: update in-memory EventLogService
     /// 9. Broadcast updates via RealtimeHub
     ///
-    /// Returns: Ok(Some(processing_ms)) on success, or error
+    /// Returns: Ok(CameraPollOutcome) on success (timing + activity for AdaptiveScheduler), or error
     #[allow(clippy::too_many_arguments)]
     async fn poll_camera_with_ai_log(
         camera: &Camera,
@@ -986,23 +1079,31 @@ impl PollingOrchestrator {
         realtime_hub: &RealtimeHub,
         config_store: &ConfigStore,
         paraclate_client: &crate::paraclate_client::ParaclateClient,
+        email_notifier: &EmailNotifier,
         access_absorber: Option<&AccessAbsorberService>,
+        metrics: &Metrics,
+        snapshot_timeout_ms: u64,
+        motion_prefilter: &MotionPrefilter,
+        preset_schedule: &PresetScheduleService,
+        polling_policy: &PollingPolicy,
         default_tid: &str,
         default_fid: &str,
         polling_cycle_id: Option<&str>,
-    ) -> crate::error::Result<Option<i32>> {
+    ) -> crate::error::Result<CameraPollOutcome> {
         let start_time = Instant::now();
         let captured_at = Utc::now();
         let captured_at_str = captured_at.to_rfc3339();
 
         // Get camera IP for logging
         let camera_ip = camera.ip_address.as_deref().unwrap_or("unknown");
-        let preset_id = camera.preset_id.as_deref().unwrap_or("balanced");
+        // Scheduled preset for captured_at (falls back to cameras.preset_id)
+        let resolved_preset = preset_schedule.resolve(camera, captured_at).await;
+        let preset_id = resolved_preset.preset_id.as_str();
 
         // === Phase 1: Snapshot capture (with AccessAbsorber rate limiting) ===
         let snapshot_start = Instant::now();
         let capture_result = match tokio::time::timeout(
-            Duration::from_millis(SNAPSHOT_TIMEOUT_MS),
+            Duration::from_millis(snapshot_timeout_ms),
             snapshot_service.capture_with_absorber(
                 camera,
                 access_absorber,
@@ -1014,6 +1115,7 @@ impl PollingOrchestrator {
         {
             Ok(Ok(result)) => result,
             Ok(Err(e)) => {
+                metrics.snapshot_failed(&camera.camera_id, "error");
                 tracing::warn!(
                     camera_id = %camera.camera_id,
                     camera_ip = %camera_ip,
@@ -1024,22 +1126,24 @@ impl PollingOrchestrator {
                 return Err(e);
             }
             Err(_) => {
+                metrics.snapshot_failed(&camera.camera_id, "timeout");
                 tracing::warn!(
                     camera_id = %camera.camera_id,
                     camera_ip = %camera_ip,
                     preset_id = %preset_id,
-                    timeout_ms = SNAPSHOT_TIMEOUT_MS,
+                    timeout_ms = snapshot_timeout_ms,
                     "Snapshot capture timeout"
                 );
                 return Err(crate::error::Error::Internal(format!(
                     "Snapshot capture timeout ({}ms) for camera {}",
-                    SNAPSHOT_TIMEOUT_MS, camera.camera_id
+                    snapshot_timeout_ms, camera.camera_id
                 )));
             }
         };
         let snapshot_ms = snapshot_start.elapsed().as_millis() as i32;
         let image_data = capture_result.data;
         let snapshot_source = capture_result.source;
+        metrics.observe_snapshot(&camera.camera_id, snapshot_source, snapshot_start.elapsed());
         let image_size = image_data.len();
 
         // Generate image filename for logging
@@ -1055,6 +1159,84 @@ impl PollingOrchestrator {
         let prev_frame = prev_frame_cache.get(&camera.camera_id).await?;
         let prev_image_data = prev_frame.as_ref().map(|(data, _)| data.clone());
 
+        // 3.5 Local motion pre-filter: skip IS21 when nothing changed since the last analyzed frame
+        // PrevFrameCacheは解析時のみ更新されるため、スキップ中も差分は累積する
+        if polling_policy.prefilter.enabled {
+            let params = ChangeParams {
+                sensitivity: camera
+                    .motion_sensitivity
+                    .unwrap_or(polling_policy.prefilter.default_sensitivity),
+                noise_floor: camera
+                    .motion_noise_floor
+                    .unwrap_or(polling_policy.prefilter.default_noise_floor),
+            };
+            let decision = motion_prefilter
+                .evaluate(
+                    &camera.camera_id,
+                    &image_data,
+                    prev_frame
+                        .as_ref()
+                        .map(|(data, meta)| (data.as_slice(), meta.captured_at)),
+                    params,
+                    polling_policy.prefilter.force_analysis_every,
+                )
+                .await;
+
+            match decision {
+                PrefilterDecision::Skip { score, consecutive_skips } => {
+                    metrics.inference_skipped(&camera.camera_id);
+                    let total_ms = start_time.elapsed().as_millis() as i32;
+                    tracing::info!(
+                        camera_id = %camera.camera_id,
+                        camera_ip = %camera_ip,
+                        preset_id = %preset_id,
+                        image_filename = %image_filename,
+                        changed_ratio = score.changed_ratio,
+                        threshold_ratio = score.threshold_ratio,
+                        consecutive_skips = consecutive_skips,
+                        total_ms = total_ms,
+                        snapshot_ms = snapshot_ms,
+                        "NO_CHANGE: IS21 inference skipped by motion pre-filter"
+                    );
+
+                    // CameraGrid still refreshes the cached snapshot
+                    realtime_hub
+                        .broadcast(HubMessage::SnapshotUpdated(SnapshotUpdatedMessage {
+                            camera_id: camera.camera_id.clone(),
+                            timestamp: captured_at_str.clone(),
+                            primary_event: None,
+                            severity: None,
+                            processing_ms: Some(total_ms as u64),
+                            error: None,
+                            snapshot_source: Some(snapshot_source.as_str().to_string()),
+                        }))
+                        .await;
+
+                    return Ok(CameraPollOutcome {
+                        total_ms,
+                        image_bytes: image_size,
+                        activity: false,
+                        no_change: true,
+                        inference_skipped: true,
+                    });
+                }
+                PrefilterDecision::ForcedAnalyze { score } => {
+                    tracing::debug!(
+                        camera_id = %camera.camera_id,
+                        changed_ratio = score.changed_ratio,
+                        force_analysis_every = polling_policy.prefilter.force_analysis_every,
+                        "Motion pre-filter: forced full analysis"
+                    );
+                }
+                PrefilterDecision::Analyze { .. } => {}
+            }
+        }
+
+        // Record preset switches only when the preset is actually used for inference
+        preset_schedule
+            .record_applied(camera, &resolved_preset, captured_at)
+            .await;
+
         // 4. Build AnalyzeRequest with preset configuration
         let mut request = preset_loader.create_request(
             preset_id,
@@ -1097,6 +1279,23 @@ impl PollingOrchestrator {
             }
         }
 
+        // Apply per-camera threshold columns (UI / auto attunement) - highest priority
+        if camera.conf_override.is_some()
+            || camera.nms_threshold.is_some()
+            || camera.par_threshold.is_some()
+        {
+            let ctx = request.camera_context.get_or_insert_with(CameraContext::default);
+            if camera.conf_override.is_some() {
+                ctx.conf_override = camera.conf_override;
+            }
+            if camera.nms_threshold.is_some() {
+                ctx.nms_threshold = camera.nms_threshold;
+            }
+            if camera.par_threshold.is_some() {
+                ctx.par_threshold = camera.par_threshold;
+            }
+        }
+
         // Debug: log final conf_override after merge
         if let Some(ref ctx) = request.camera_context {
             tracing::debug!(
@@ -1117,10 +1316,23 @@ impl PollingOrchestrator {
 
         // === Phase 2: IS21 inference ===
         let is21_start = Instant::now();
-        let mut result = ai_client
+        let mut result = match ai_client
             .analyze(image_data.clone(), prev_image_data, request)
-            .await?;
+            .await
+        {
+            Ok(result) => result,
+            Err(e) => {
+                metrics.inference_failed(&camera.camera_id);
+                return Err(e);
+            }
+        };
         let is21_roundtrip_ms = is21_start.elapsed().as_millis() as i32;
+        metrics.observe_inference(&camera.camera_id, is21_start.elapsed());
+
+        // detection_logs.preset_id はスケジュール解決後のプリセットで記録する
+        if result.preset_id.is_none() {
+            result.preset_id = Some(preset_id.to_string());
+        }
 
         // Issue #104: Filter excluded objects from IS21 response
         // プリセットのexcluded_objectsに基づいてbboxesをフィルタリング
@@ -1336,6 +1548,21 @@ impl PollingOrchestrator {
                         );
                     }
                 }
+
+                // Email alert (subscribers with alert_min_severity <= severity)
+                let alert = DetectionAlert {
+                    tid: tid.to_string(),
+                    fid: fid.to_string(),
+                    camera_id: camera.camera_id.clone(),
+                    camera_name: camera.name.clone(),
+                    log_id,
+                    primary_event: result.primary_event.clone(),
+                    severity: result.severity,
+                    captured_at,
+                };
+                if let Err(e) = email_notifier.notify_detection(&alert).await {
+                    tracing::warn!(log_id = log_id, error = %e, "Failed to enqueue email alert");
+                }
             }
 
             // Detailed logging with timing breakdown
@@ -1400,7 +1627,22 @@ impl PollingOrchestrator {
             );
         }
 
-        Ok(Some(total_ms))
+        // Static scene: no detection and IS21 frame diff reports no significant change
+        let no_change = !result.detected
+            && result
+                .frame_diff
+                .as_ref()
+                .and_then(|fd| fd.scene_change.as_ref())
+                .map(|sc| !sc.significant)
+                .unwrap_or(false);
+
+        Ok(CameraPollOutcome {
+            total_ms,
+            image_bytes: image_size,
+            activity: result.detected,
+            no_change,
+            inference_skipped: false,
+        })
     }
 
     /// Build event attributes JSON from AnalyzeResponse
@@ -0,0 +1,391 @@
+//! Custom Presets - User-defined presets persisted in the database
+//!
+//! ## Design
+//!
+//! - 組み込みプリセット（base_preset_id）を継承し、対象/除外オブジェクト・閾値・
+//!   コンテキストを上書きする
+//! - 更新ごとに revision を加算し、`preset_version` を `{base_version}+c{revision}` とする
+//!   （detection_logs.preset_version から当時の定義を custom_preset_versions で辿れる）
+//!
+//! ## テーブル
+//! - custom_presets: 現行定義
+//! - custom_preset_versions: revision 履歴（構築済みPreset JSON）
+
+use super::Preset;
+use crate::error::{Error, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{MySqlPool, Row};
+
+/// Overrides applied on top of the base preset (None = inherit)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CustomPresetOverrides {
+    #[serde(default)]
+    pub location_type: Option<String>,
+    #[serde(default)]
+    pub distance: Option<String>,
+    #[serde(default)]
+    pub expected_objects: Option<Vec<String>>,
+    #[serde(default)]
+    pub excluded_objects: Option<Vec<String>>,
+    #[serde(default)]
+    pub enable_frame_diff: Option<bool>,
+    #[serde(default)]
+    pub return_bboxes: Option<bool>,
+    #[serde(default)]
+    pub conf_override: Option<f32>,
+    #[serde(default)]
+    pub nms_threshold: Option<f32>,
+    #[serde(default)]
+    pub par_threshold: Option<f32>,
+    #[serde(default)]
+    pub suggested_interval_sec: Option<u32>,
+}
+
+/// Custom preset definition (custom_presets row)
+#[derive(Debug, Clone, Serialize)]
+pub struct CustomPresetDefinition {
+    pub preset_id: String,
+    pub base_preset_id: String,
+    pub name: String,
+    pub description: String,
+    pub overrides: CustomPresetOverrides,
+    pub revision: u32,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl CustomPresetDefinition {
+    /// Build the effective preset from its base
+    pub fn build(&self, base: &Preset) -> Preset {
+        let o = &self.overrides;
+        Preset {
+            id: self.preset_id.clone(),
+            name: self.name.clone(),
+            description: self.description.clone(),
+            version: format!("{}+c{}", base.version, self.revision),
+            location_type: o.location_type.clone().or_else(|| base.location_type.clone()),
+            distance: o.distance.clone().or_else(|| base.distance.clone()),
+            expected_objects: o
+                .expected_objects
+                .clone()
+                .unwrap_or_else(|| base.expected_objects.clone()),
+            excluded_objects: o
+                .excluded_objects
+                .clone()
+                .unwrap_or_else(|| base.excluded_objects.clone()),
+            enable_frame_diff: o.enable_frame_diff.unwrap_or(base.enable_frame_diff),
+            return_bboxes: o.return_bboxes.unwrap_or(base.return_bboxes),
+            output_schema: base.output_schema.clone(),
+            conf_override: o.conf_override.or(base.conf_override),
+            nms_threshold: o.nms_threshold.or(base.nms_threshold),
+            par_threshold: o.par_threshold.or(base.par_threshold),
+            suggested_interval_sec: o
+                .suggested_interval_sec
+                .unwrap_or(base.suggested_interval_sec),
+        }
+    }
+}
+
+/// Create/update request
+#[derive(Debug, Clone, Deserialize)]
+pub struct UpsertCustomPresetRequest {
+    /// Required on create, ignored on update
+    pub preset_id: Option<String>,
+    pub base_preset_id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub overrides: CustomPresetOverrides,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Stored revision (custom_preset_versions row)
+#[derive(Debug, Clone, Serialize)]
+pub struct CustomPresetVersion {
+    pub preset_id: String,
+    pub revision: u32,
+    pub preset_version: String,
+    pub preset: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Validate custom preset ID format (a-z, 0-9, '_' and '-')
+pub fn validate_preset_id(preset_id: &str) -> Result<()> {
+    let valid = !preset_id.is_empty()
+        && preset_id.len() <= 64
+        && preset_id
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-');
+    if valid {
+        Ok(())
+    } else {
+        Err(Error::Validation(
+            "preset_id must be 1-64 characters of a-z, 0-9, '_' or '-'".to_string(),
+        ))
+    }
+}
+
+/// Validate overrides (ranges follow the built-in preset documentation)
+pub fn validate_overrides(o: &CustomPresetOverrides) -> Result<()> {
+    fn in_range(name: &str, v: Option<f32>, min: f32, max: f32) -> Result<()> {
+        match v {
+            Some(v) if !(min..=max).contains(&v) => Err(Error::Validation(format!(
+                "{} must be between {} and {}",
+                name, min, max
+            ))),
+            _ => Ok(()),
+        }
+    }
+    in_range("conf_override", o.conf_override, 0.2, 0.8)?;
+    in_range("nms_threshold", o.nms_threshold, 0.3, 0.6)?;
+    in_range("par_threshold", o.par_threshold, 0.3, 0.8)?;
+
+    if o.suggested_interval_sec == Some(0) {
+        return Err(Error::Validation(
+            "suggested_interval_sec must be >= 1".to_string(),
+        ));
+    }
+    for (name, list) in [
+        ("expected_objects", &o.expected_objects),
+        ("excluded_objects", &o.excluded_objects),
+    ] {
+        if list
+            .as_ref()
+            .is_some_and(|l| l.iter().any(|s| s.trim().is_empty()))
+        {
+            return Err(Error::Validation(format!("{} must not contain empty labels", name)));
+        }
+    }
+    Ok(())
+}
+
+/// Custom preset repository
+pub struct CustomPresetRepository {
+    pool: MySqlPool,
+}
+
+impl CustomPresetRepository {
+    pub fn new(pool: MySqlPool) -> Self {
+        Self { pool }
+    }
+
+    fn row_to_definition(row: &sqlx::mysql::MySqlRow) -> Result<CustomPresetDefinition> {
+        let overrides: serde_json::Value = row.try_get("overrides")?;
+        Ok(CustomPresetDefinition {
+            preset_id: row.try_get("preset_id")?,
+            base_preset_id: row.try_get("base_preset_id")?,
+            name: row.try_get("name")?,
+            description: row.try_get("description")?,
+            overrides: serde_json::from_value(overrides)?,
+            revision: row.try_get("revision")?,
+            enabled: row.try_get("enabled")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+        })
+    }
+
+    /// List all custom presets
+    pub async fn list(&self) -> Result<Vec<CustomPresetDefinition>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT preset_id, base_preset_id, name, description, overrides,
+                   revision, enabled, created_at, updated_at
+            FROM custom_presets
+            ORDER BY preset_id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(Self::row_to_definition).collect()
+    }
+
+    /// Get custom preset by ID
+    pub async fn get(&self, preset_id: &str) -> Result<Option<CustomPresetDefinition>> {
+        let row = sqlx::query(
+            r#"
+            SELECT preset_id, base_preset_id, name, description, overrides,
+                   revision, enabled, created_at, updated_at
+            FROM custom_presets
+            WHERE preset_id = ?
+            "#,
+        )
+        .bind(preset_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.as_ref().map(Self::row_to_definition).transpose()
+    }
+
+    /// Insert (revision 1) or update (revision + 1) and record the built preset
+    ///
+    /// `build` receives the saved definition and returns the effective preset for history.
+    pub async fn save(
+        &self,
+        preset_id: &str,
+        req: &UpsertCustomPresetRequest,
+        build: impl Fn(&CustomPresetDefinition) -> Preset,
+    ) -> Result<CustomPresetDefinition> {
+        let overrides_json = serde_json::to_string(&req.overrides)?;
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO custom_presets
+                (preset_id, base_preset_id, name, description, overrides, revision, enabled)
+            VALUES (?, ?, ?, ?, CAST(? AS JSON), 1, ?)
+            ON DUPLICATE KEY UPDATE
+                base_preset_id = VALUES(base_preset_id),
+                name = VALUES(name),
+                description = VALUES(description),
+                overrides = VALUES(overrides),
+                enabled = VALUES(enabled),
+                revision = revision + 1
+            "#,
+        )
+        .bind(preset_id)
+        .bind(&req.base_preset_id)
+        .bind(&req.name)
+        .bind(&req.description)
+        .bind(overrides_json)
+        .bind(req.enabled)
+        .execute(&mut *tx)
+        .await?;
+
+        let row = sqlx::query(
+            r#"
+            SELECT preset_id, base_preset_id, name, description, overrides,
+                   revision, enabled, created_at, updated_at
+            FROM custom_presets
+            WHERE preset_id = ?
+            "#,
+        )
+        .bind(preset_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        let definition = Self::row_to_definition(&row)?;
+
+        let preset = build(&definition);
+        sqlx::query(
+            r#"
+            INSERT INTO custom_preset_versions (preset_id, revision, preset_version, preset_json)
+            VALUES (?, ?, ?, CAST(? AS JSON))
+            "#,
+        )
+        .bind(preset_id)
+        .bind(definition.revision)
+        .bind(&preset.version)
+        .bind(serde_json::to_string(&preset)?)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(definition)
+    }
+
+    /// Delete custom preset (revision history is kept for attribution)
+    pub async fn delete(&self, preset_id: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM custom_presets WHERE preset_id = ?")
+            .bind(preset_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Revision history (newest first)
+    pub async fn versions(&self, preset_id: &str) -> Result<Vec<CustomPresetVersion>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT preset_id, revision, preset_version, preset_json, created_at
+            FROM custom_preset_versions
+            WHERE preset_id = ?
+            ORDER BY revision DESC
+            "#,
+        )
+        .bind(preset_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(CustomPresetVersion {
+                    preset_id: row.try_get("preset_id")?,
+                    revision: row.try_get("revision")?,
+                    preset_version: row.try_get("preset_version")?,
+                    preset: row.try_get("preset_json")?,
+                    created_at: row.try_get("created_at")?,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn definition(overrides: CustomPresetOverrides, revision: u32) -> CustomPresetDefinition {
+        CustomPresetDefinition {
+            preset_id: "lobby_night".to_string(),
+            base_preset_id: "entrance".to_string(),
+            name: "ロビー夜間".to_string(),
+            description: String::new(),
+            overrides,
+            revision,
+            enabled: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_build_inherits_and_overrides() {
+        let base = Preset::entrance();
+        let def = definition(
+            CustomPresetOverrides {
+                excluded_objects: Some(vec!["cat".to_string()]),
+                conf_override: Some(0.3),
+                ..Default::default()
+            },
+            3,
+        );
+        let preset = def.build(&base);
+
+        assert_eq!(preset.id, "lobby_night");
+        assert_eq!(preset.version, format!("{}+c3", base.version));
+        assert_eq!(preset.excluded_objects, vec!["cat".to_string()]);
+        assert_eq!(preset.conf_override, Some(0.3));
+        // Inherited
+        assert_eq!(preset.expected_objects, base.expected_objects);
+        assert_eq!(preset.location_type, base.location_type);
+        assert_eq!(preset.enable_frame_diff, base.enable_frame_diff);
+    }
+
+    #[test]
+    fn test_validation() {
+        assert!(validate_preset_id("lobby_night-2").is_ok());
+        assert!(validate_preset_id("").is_err());
+        assert!(validate_preset_id("Lobby").is_err());
+        assert!(validate_preset_id("a b").is_err());
+
+        assert!(validate_overrides(&CustomPresetOverrides::default()).is_ok());
+        assert!(validate_overrides(&CustomPresetOverrides {
+            conf_override: Some(0.9),
+            ..Default::default()
+        })
+        .is_err());
+        assert!(validate_overrides(&CustomPresetOverrides {
+            excluded_objects: Some(vec![" ".to_string()]),
+            ..Default::default()
+        })
+        .is_err());
+    }
+}
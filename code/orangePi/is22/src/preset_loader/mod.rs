@@ -3,6 +3,7 @@
 //! ## Responsibilities
 //!
 //! - Define 13 standard presets for IS21 analysis (v2.0)
+//! - Hold user-defined custom presets loaded from DB (custom_presets)
 //! - Provide preset lookup by ID
 //! - Apply preset configurations to analyze requests
 //!
@@ -11,9 +12,17 @@
 //! - §2.3 PresetLoader (is22_AI_EVENT_LOG_DESIGN.md)
 //! - PresetRedesign_v2.md (Issue #107)
 
+mod custom;
+
+pub use custom::{
+    validate_overrides, validate_preset_id, CustomPresetDefinition, CustomPresetOverrides,
+    CustomPresetRepository, CustomPresetVersion, UpsertCustomPresetRequest,
+};
+
 use crate::ai_client::{AnalyzeRequest, CameraContext};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::RwLock;
 
 /// YOLO COCO Labels - 正確なラベル名でIS21と連携
 /// Issue #107: "vehicle"/"animal"カテゴリ名ではなくYOLOラベルを使用
@@ -554,8 +563,10 @@ impl Preset {
 
 /// PresetLoader service
 pub struct PresetLoader {
-    /// Preset registry
+    /// Built-in preset registry
     presets: HashMap<String, Preset>,
+    /// Custom presets (DB-backed, replaced at runtime via API)
+    custom: RwLock<HashMap<String, Preset>>,
 }
 
 impl PresetLoader {
@@ -587,39 +598,121 @@ impl PresetLoader {
             presets.insert(preset.id.clone(), preset);
         }
 
-        Self { presets }
+        Self {
+            presets,
+            custom: RwLock::new(HashMap::new()),
+        }
     }
 
-    /// Get preset by ID
-    pub fn get(&self, preset_id: &str) -> Option<&Preset> {
-        self.presets.get(preset_id)
+    /// Get preset by ID (built-in first, then custom)
+    pub fn get(&self, preset_id: &str) -> Option<Preset> {
+        if let Some(preset) = self.presets.get(preset_id) {
+            return Some(preset.clone());
+        }
+        self.custom.read().ok()?.get(preset_id).cloned()
     }
 
     /// Get preset by ID or return balanced as default
-    pub fn get_or_default(&self, preset_id: &str) -> &Preset {
-        self.presets
-            .get(preset_id)
-            .unwrap_or_else(|| self.presets.get(preset_ids::BALANCED).unwrap())
+    pub fn get_or_default(&self, preset_id: &str) -> Preset {
+        self.get(preset_id)
+            .unwrap_or_else(|| self.presets[preset_ids::BALANCED].clone())
+    }
+
+    /// Get built-in preset by ID
+    pub fn get_builtin(&self, preset_id: &str) -> Option<&Preset> {
+        self.presets.get(preset_id)
+    }
+
+    /// Check if preset ID is a built-in preset
+    pub fn is_builtin(&self, preset_id: &str) -> bool {
+        self.presets.contains_key(preset_id)
     }
 
     /// List all preset IDs
-    pub fn list_ids(&self) -> Vec<&str> {
-        self.presets.keys().map(|s| s.as_str()).collect()
+    pub fn list_ids(&self) -> Vec<String> {
+        let mut ids: Vec<String> = self.presets.keys().cloned().collect();
+        ids.extend(self.list_custom().into_iter().map(|p| p.id));
+        ids
+    }
+
+    /// List all presets (built-in + custom)
+    pub fn list_all(&self) -> Vec<Preset> {
+        let mut all: Vec<Preset> = self.presets.values().cloned().collect();
+        all.extend(self.list_custom());
+        all
     }
 
-    /// List all presets
-    pub fn list_all(&self) -> Vec<&Preset> {
-        self.presets.values().collect()
+    /// List custom presets (sorted by ID)
+    pub fn list_custom(&self) -> Vec<Preset> {
+        let mut custom: Vec<Preset> = self
+            .custom
+            .read()
+            .map(|c| c.values().cloned().collect())
+            .unwrap_or_default();
+        custom.sort_by(|a, b| a.id.cmp(&b.id));
+        custom
     }
 
     /// Check if preset exists
     pub fn exists(&self, preset_id: &str) -> bool {
         self.presets.contains_key(preset_id)
+            || self
+                .custom
+                .read()
+                .map(|c| c.contains_key(preset_id))
+                .unwrap_or(false)
+    }
+
+    /// Register custom preset (built-in IDs cannot be shadowed)
+    pub fn register(&self, preset: Preset) {
+        if self.presets.contains_key(&preset.id) {
+            tracing::warn!(preset_id = %preset.id, "Refusing to shadow built-in preset");
+            return;
+        }
+        if let Ok(mut custom) = self.custom.write() {
+            custom.insert(preset.id.clone(), preset);
+        }
+    }
+
+    /// Remove custom preset
+    pub fn unregister(&self, preset_id: &str) -> bool {
+        self.custom
+            .write()
+            .map(|mut c| c.remove(preset_id).is_some())
+            .unwrap_or(false)
+    }
+
+    /// Apply a custom preset definition (register if enabled, otherwise remove)
+    pub fn apply_definition(&self, definition: &CustomPresetDefinition) -> Option<Preset> {
+        let Some(base) = self.presets.get(&definition.base_preset_id) else {
+            tracing::warn!(
+                preset_id = %definition.preset_id,
+                base_preset_id = %definition.base_preset_id,
+                "Custom preset base not found, skipped"
+            );
+            self.unregister(&definition.preset_id);
+            return None;
+        };
+        let preset = definition.build(base);
+        if definition.enabled {
+            self.register(preset.clone());
+        } else {
+            self.unregister(&definition.preset_id);
+        }
+        Some(preset)
     }
 
-    /// Register custom preset
-    pub fn register(&mut self, preset: Preset) {
-        self.presets.insert(preset.id.clone(), preset);
+    /// Load all custom presets from DB (replaces the in-memory set)
+    pub async fn load_custom(&self, repo: &CustomPresetRepository) -> crate::error::Result<usize> {
+        let definitions = repo.list().await?;
+        if let Ok(mut custom) = self.custom.write() {
+            custom.clear();
+        }
+        let loaded = definitions
+            .iter()
+            .filter(|d| self.apply_definition(d).is_some() && d.enabled)
+            .count();
+        Ok(loaded)
     }
 
     /// Create AnalyzeRequest with preset applied
@@ -693,6 +786,28 @@ mod tests {
         assert_eq!(preset.id, preset_ids::BALANCED);
     }
 
+    #[test]
+    fn test_custom_preset_registration() {
+        let loader = PresetLoader::new();
+        let mut preset = Preset::entrance();
+        preset.id = "lobby_night".to_string();
+        loader.register(preset);
+
+        assert!(loader.exists("lobby_night"));
+        assert!(!loader.is_builtin("lobby_night"));
+        assert_eq!(loader.get_or_default("lobby_night").id, "lobby_night");
+        assert_eq!(loader.list_all().len(), 14);
+
+        // Built-in presets cannot be shadowed
+        let mut shadow = Preset::security_zone();
+        shadow.id = preset_ids::BALANCED.to_string();
+        loader.register(shadow);
+        assert_eq!(loader.get_or_default(preset_ids::BALANCED).name, "バランス");
+
+        assert!(loader.unregister("lobby_night"));
+        assert!(!loader.exists("lobby_night"));
+    }
+
     #[test]
     fn test_preset_to_camera_context() {
         let parking = Preset::parking();
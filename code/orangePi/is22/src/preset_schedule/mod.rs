@@ -0,0 +1,19 @@
+//! Preset Schedule - Time-based preset switching per camera
+//!
+//! ## Purpose
+//!
+//! `cameras.preset_id` は単一値だが、night_vision / office / retail 等は
+//! 時間帯によってのみ意味を持つ。カメラ・施設(fid)・全体単位で
+//! JST週間スケジュール＋祝日オーバーライドを定義し、
+//! PollingOrchestrator が `captured_at` 時点の有効プリセットを解決する。
+//!
+//! 切替は `preset_switch_logs` に記録され、
+//! `InferenceStatsService::get_preset_effectiveness` の集計に使われる。
+
+mod repository;
+mod service;
+mod types;
+
+pub use repository::PresetScheduleRepository;
+pub use service::{resolve_schedule, validate_rules, PresetScheduleService};
+pub use types::*;
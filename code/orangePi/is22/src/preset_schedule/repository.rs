@@ -0,0 +1,240 @@
+//! Preset Schedule Repository - Database operations
+//!
+//! ## テーブル
+//! - preset_schedules: 週間スケジュール
+//! - preset_holidays: 祝日オーバーライド
+//! - preset_switch_logs: プリセット切替履歴
+
+use super::types::*;
+use crate::error::{Error, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::{MySqlPool, Row};
+
+const SCHEDULE_COLUMNS: &str =
+    "schedule_id, name, scope, scope_id, rules, priority, enabled, created_at, updated_at";
+
+pub struct PresetScheduleRepository {
+    pool: MySqlPool,
+}
+
+impl PresetScheduleRepository {
+    pub fn new(pool: MySqlPool) -> Self {
+        Self { pool }
+    }
+
+    fn row_to_schedule(row: &sqlx::mysql::MySqlRow) -> Result<PresetSchedule> {
+        let scope: String = row.try_get("scope")?;
+        let rules: serde_json::Value = row.try_get("rules")?;
+        Ok(PresetSchedule {
+            schedule_id: row.try_get("schedule_id")?,
+            name: row.try_get("name")?,
+            scope: ScheduleScope::parse(&scope)
+                .ok_or_else(|| Error::Internal(format!("Unknown schedule scope: {}", scope)))?,
+            scope_id: row.try_get("scope_id")?,
+            rules: serde_json::from_value(rules)?,
+            priority: row.try_get("priority")?,
+            enabled: row.try_get("enabled")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+        })
+    }
+
+    // ========================================================================
+    // Schedules
+    // ========================================================================
+
+    /// List all schedules
+    pub async fn list_schedules(&self) -> Result<Vec<PresetSchedule>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM preset_schedules ORDER BY scope, priority DESC, schedule_id",
+            SCHEDULE_COLUMNS
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(Self::row_to_schedule).collect()
+    }
+
+    /// Get schedule by ID
+    pub async fn get_schedule(&self, schedule_id: &str) -> Result<Option<PresetSchedule>> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM preset_schedules WHERE schedule_id = ?",
+            SCHEDULE_COLUMNS
+        ))
+        .bind(schedule_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.as_ref().map(Self::row_to_schedule).transpose()
+    }
+
+    /// Insert or replace schedule
+    pub async fn upsert_schedule(
+        &self,
+        schedule_id: &str,
+        req: &UpsertPresetScheduleRequest,
+    ) -> Result<()> {
+        let rules_json = serde_json::to_string(&req.rules)?;
+        sqlx::query(
+            r#"
+            INSERT INTO preset_schedules
+                (schedule_id, name, scope, scope_id, rules, priority, enabled)
+            VALUES (?, ?, ?, ?, CAST(? AS JSON), ?, ?)
+            ON DUPLICATE KEY UPDATE
+                name = VALUES(name),
+                scope = VALUES(scope),
+                scope_id = VALUES(scope_id),
+                rules = VALUES(rules),
+                priority = VALUES(priority),
+                enabled = VALUES(enabled)
+            "#,
+        )
+        .bind(schedule_id)
+        .bind(&req.name)
+        .bind(req.scope.as_str())
+        .bind(&req.scope_id)
+        .bind(rules_json)
+        .bind(req.priority)
+        .bind(req.enabled)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Delete schedule
+    pub async fn delete_schedule(&self, schedule_id: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM preset_schedules WHERE schedule_id = ?")
+            .bind(schedule_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    // ========================================================================
+    // Holidays
+    // ========================================================================
+
+    /// List holidays
+    pub async fn list_holidays(&self) -> Result<Vec<PresetHoliday>> {
+        let rows = sqlx::query("SELECT holiday_date, name FROM preset_holidays ORDER BY holiday_date")
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(PresetHoliday {
+                    holiday_date: row.try_get("holiday_date")?,
+                    name: row.try_get("name")?,
+                })
+            })
+            .collect()
+    }
+
+    /// Add or rename holiday
+    pub async fn upsert_holiday(&self, holiday: &PresetHoliday) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO preset_holidays (holiday_date, name) VALUES (?, ?)
+             ON DUPLICATE KEY UPDATE name = VALUES(name)",
+        )
+        .bind(holiday.holiday_date)
+        .bind(&holiday.name)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Delete holiday
+    pub async fn delete_holiday(&self, date: NaiveDate) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM preset_holidays WHERE holiday_date = ?")
+            .bind(date)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    // ========================================================================
+    // Switch Logs
+    // ========================================================================
+
+    /// Record a preset switch
+    pub async fn insert_switch_log(
+        &self,
+        camera_id: &str,
+        from_preset_id: &str,
+        to_preset_id: &str,
+        schedule_id: Option<&str>,
+        switched_at: DateTime<Utc>,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO preset_switch_logs
+                (camera_id, from_preset_id, to_preset_id, schedule_id, switched_at)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(camera_id)
+        .bind(from_preset_id)
+        .bind(to_preset_id)
+        .bind(schedule_id)
+        .bind(switched_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// List switch logs (newest first)
+    pub async fn list_switch_logs(
+        &self,
+        camera_id: Option<&str>,
+        since: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<PresetSwitchLog>> {
+        let rows = match camera_id {
+            Some(camera_id) => {
+                sqlx::query(
+                    r#"
+                    SELECT id, camera_id, from_preset_id, to_preset_id, schedule_id, switched_at
+                    FROM preset_switch_logs
+                    WHERE camera_id = ? AND switched_at >= ?
+                    ORDER BY switched_at DESC
+                    LIMIT ?
+                    "#,
+                )
+                .bind(camera_id)
+                .bind(since)
+                .bind(limit)
+                .fetch_all(&self.pool)
+                .await?
+            }
+            None => {
+                sqlx::query(
+                    r#"
+                    SELECT id, camera_id, from_preset_id, to_preset_id, schedule_id, switched_at
+                    FROM preset_switch_logs
+                    WHERE switched_at >= ?
+                    ORDER BY switched_at DESC
+                    LIMIT ?
+                    "#,
+                )
+                .bind(since)
+                .bind(limit)
+                .fetch_all(&self.pool)
+                .await?
+            }
+        };
+
+        rows.iter()
+            .map(|row| {
+                Ok(PresetSwitchLog {
+                    id: row.try_get("id")?,
+                    camera_id: row.try_get("camera_id")?,
+                    from_preset_id: row.try_get("from_preset_id")?,
+                    to_preset_id: row.try_get("to_preset_id")?,
+                    schedule_id: row.try_get("schedule_id")?,
+                    switched_at: row.try_get("switched_at")?,
+                })
+            })
+            .collect()
+    }
+}
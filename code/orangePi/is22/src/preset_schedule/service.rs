@@ -0,0 +1,350 @@
+//! Preset Schedule Service - Resolve active preset per camera
+//!
+//! ## 解決順序
+//!
+//! 1. scope=camera（camera_id一致）
+//! 2. scope=fid（camera.fid一致）
+//! 3. scope=global
+//!
+//! 同一scope内は priority 降順。いずれのルールにも一致しない場合は
+//! `cameras.preset_id`（未設定なら "balanced"）を使用する。
+//!
+//! 祝日（preset_holidays）には day=7 のルールのみを評価する。
+//! day=7 のルールを持たないスケジュールは祝日も通常の曜日として扱う。
+
+use super::repository::PresetScheduleRepository;
+use super::types::*;
+use crate::config_store::Camera;
+use crate::error::{Error, Result};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, Utc};
+use chrono_tz::Asia::Tokyo;
+use sqlx::MySqlPool;
+use std::collections::{HashMap, HashSet};
+use tokio::sync::RwLock;
+
+/// Schedule cache lifetime (DB changes made outside the API are picked up after this)
+const CACHE_TTL_SEC: i64 = 60;
+
+/// Default preset when camera has none
+const DEFAULT_PRESET_ID: &str = "balanced";
+
+/// Does `rule` cover the JST local time `(date, time)`?
+///
+/// `day_key` returns the day index (0-7) used for a date.
+fn rule_matches(
+    rule: &ScheduleRule,
+    date: NaiveDate,
+    time: NaiveTime,
+    day_key: &impl Fn(NaiveDate) -> u8,
+) -> bool {
+    let (Some(start), Some(end)) = (rule.start_time(), rule.end_time()) else {
+        return false;
+    };
+    let applies_on = |d: NaiveDate| rule.days.is_empty() || rule.days.contains(&day_key(d));
+
+    if start == end {
+        // 終日
+        applies_on(date)
+    } else if start < end {
+        applies_on(date) && time >= start && time < end
+    } else {
+        // 日跨ぎ: 当日の start 以降、または前日開始分の end 未満
+        (applies_on(date) && time >= start)
+            || (time < end && applies_on(date - Duration::days(1)))
+    }
+}
+
+/// Find the first matching rule of a schedule at a JST local time
+pub fn resolve_schedule<'a>(
+    schedule: &'a PresetSchedule,
+    date: NaiveDate,
+    time: NaiveTime,
+    holidays: &HashSet<NaiveDate>,
+) -> Option<&'a ScheduleRule> {
+    let use_holiday_rules = schedule.has_holiday_rules();
+    let day_key = |d: NaiveDate| {
+        if use_holiday_rules && holidays.contains(&d) {
+            HOLIDAY_DAY
+        } else {
+            d.weekday().num_days_from_monday() as u8
+        }
+    };
+
+    schedule
+        .rules
+        .iter()
+        .find(|rule| rule_matches(rule, date, time, &day_key))
+}
+
+/// Validate rules before saving
+pub fn validate_rules(rules: &[ScheduleRule], known_preset: impl Fn(&str) -> bool) -> Result<()> {
+    if rules.is_empty() {
+        return Err(Error::Validation("rules must not be empty".to_string()));
+    }
+    for (i, rule) in rules.iter().enumerate() {
+        if rule.start_time().is_none() || rule.end_time().is_none() {
+            return Err(Error::Validation(format!(
+                "rules[{}]: start/end must be HH:MM",
+                i
+            )));
+        }
+        if let Some(day) = rule.days.iter().find(|d| **d > HOLIDAY_DAY) {
+            return Err(Error::Validation(format!(
+                "rules[{}]: invalid day {} (0=Mon ... 6=Sun, 7=Holiday)",
+                i, day
+            )));
+        }
+        if !known_preset(&rule.preset_id) {
+            return Err(Error::Validation(format!(
+                "rules[{}]: unknown preset_id '{}'",
+                i, rule.preset_id
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[derive(Default)]
+struct ScheduleCache {
+    schedules: Vec<PresetSchedule>,
+    holidays: HashSet<NaiveDate>,
+    loaded_at: Option<DateTime<Utc>>,
+}
+
+/// PresetScheduleService
+pub struct PresetScheduleService {
+    repo: PresetScheduleRepository,
+    cache: RwLock<ScheduleCache>,
+    /// Last applied preset per camera (switch detection)
+    applied: RwLock<HashMap<String, ResolvedPreset>>,
+}
+
+impl PresetScheduleService {
+    pub fn new(pool: MySqlPool) -> Self {
+        Self {
+            repo: PresetScheduleRepository::new(pool),
+            cache: RwLock::new(ScheduleCache::default()),
+            applied: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn repository(&self) -> &PresetScheduleRepository {
+        &self.repo
+    }
+
+    /// Reload schedules and holidays from DB
+    pub async fn refresh(&self) -> Result<()> {
+        let schedules = self.repo.list_schedules().await?;
+        let holidays = self
+            .repo
+            .list_holidays()
+            .await?
+            .into_iter()
+            .map(|h| h.holiday_date)
+            .collect();
+
+        let mut cache = self.cache.write().await;
+        cache.schedules = schedules;
+        cache.holidays = holidays;
+        cache.loaded_at = Some(Utc::now());
+        Ok(())
+    }
+
+    async fn ensure_fresh(&self) {
+        let stale = {
+            let cache = self.cache.read().await;
+            cache
+                .loaded_at
+                .map(|t| Utc::now() - t > Duration::seconds(CACHE_TTL_SEC))
+                .unwrap_or(true)
+        };
+        if stale {
+            if let Err(e) = self.refresh().await {
+                // 取得失敗時は既存キャッシュで継続（TTL経過後に再試行）
+                tracing::warn!(error = %e, "Failed to load preset schedules");
+                self.cache.write().await.loaded_at = Some(Utc::now());
+            }
+        }
+    }
+
+    /// Resolve the active preset for a camera at `at`
+    pub async fn resolve(&self, camera: &Camera, at: DateTime<Utc>) -> ResolvedPreset {
+        self.ensure_fresh().await;
+
+        let local = at.with_timezone(&Tokyo).naive_local();
+        let (date, time) = (local.date(), local.time());
+
+        let cache = self.cache.read().await;
+        let mut candidates: Vec<&PresetSchedule> = cache
+            .schedules
+            .iter()
+            .filter(|s| s.enabled)
+            .filter(|s| match s.scope {
+                ScheduleScope::Camera => s.scope_id.as_deref() == Some(camera.camera_id.as_str()),
+                ScheduleScope::Fid => s.scope_id.is_some() && s.scope_id == camera.fid,
+                ScheduleScope::Global => true,
+            })
+            .collect();
+        candidates.sort_by(|a, b| a.scope.cmp(&b.scope).then(b.priority.cmp(&a.priority)));
+
+        for schedule in candidates {
+            if let Some(rule) = resolve_schedule(schedule, date, time, &cache.holidays) {
+                return ResolvedPreset {
+                    preset_id: rule.preset_id.clone(),
+                    schedule_id: Some(schedule.schedule_id.clone()),
+                };
+            }
+        }
+
+        ResolvedPreset {
+            preset_id: camera
+                .preset_id
+                .clone()
+                .unwrap_or_else(|| DEFAULT_PRESET_ID.to_string()),
+            schedule_id: None,
+        }
+    }
+
+    /// Record the preset used for a poll; logs a switch when it changed
+    ///
+    /// 初回は cameras.preset_id からの切替として扱う（再起動直後もスケジュール適用を記録）
+    pub async fn record_applied(
+        &self,
+        camera: &Camera,
+        resolved: &ResolvedPreset,
+        at: DateTime<Utc>,
+    ) {
+        let previous = {
+            let mut applied = self.applied.write().await;
+            applied.insert(camera.camera_id.clone(), resolved.clone())
+        };
+        let from_preset_id = match previous {
+            Some(prev) => prev.preset_id,
+            None => camera
+                .preset_id
+                .clone()
+                .unwrap_or_else(|| DEFAULT_PRESET_ID.to_string()),
+        };
+        if from_preset_id == resolved.preset_id {
+            return;
+        }
+
+        tracing::info!(
+            camera_id = %camera.camera_id,
+            from_preset = %from_preset_id,
+            to_preset = %resolved.preset_id,
+            schedule_id = ?resolved.schedule_id,
+            "Scheduled preset switch"
+        );
+        if let Err(e) = self
+            .repo
+            .insert_switch_log(
+                &camera.camera_id,
+                &from_preset_id,
+                &resolved.preset_id,
+                resolved.schedule_id.as_deref(),
+                at,
+            )
+            .await
+        {
+            tracing::warn!(camera_id = %camera.camera_id, error = %e, "Failed to save preset switch log");
+        }
+    }
+
+    /// Currently applied presets (camera_id → preset)
+    pub async fn applied(&self) -> HashMap<String, ResolvedPreset> {
+        self.applied.read().await.clone()
+    }
+
+    /// Forget a camera (e.g., deleted)
+    pub async fn remove(&self, camera_id: &str) {
+        self.applied.write().await.remove(camera_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(days: &[u8], start: &str, end: &str, preset: &str) -> ScheduleRule {
+        ScheduleRule {
+            days: days.to_vec(),
+            start: start.to_string(),
+            end: end.to_string(),
+            preset_id: preset.to_string(),
+        }
+    }
+
+    fn schedule(rules: Vec<ScheduleRule>) -> PresetSchedule {
+        PresetSchedule {
+            schedule_id: "s1".to_string(),
+            name: "test".to_string(),
+            scope: ScheduleScope::Camera,
+            scope_id: Some("cam-1".to_string()),
+            rules,
+            priority: 0,
+            enabled: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn at(date: &str, time: &str) -> (NaiveDate, NaiveTime) {
+        (
+            NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap(),
+            NaiveTime::parse_from_str(time, "%H:%M").unwrap(),
+        )
+    }
+
+    fn preset_at<'a>(s: &'a PresetSchedule, date: &str, time: &str, holidays: &HashSet<NaiveDate>) -> Option<&'a str> {
+        let (d, t) = at(date, time);
+        resolve_schedule(s, d, t, holidays).map(|r| r.preset_id.as_str())
+    }
+
+    #[test]
+    fn test_business_hours_weekdays() {
+        let s = schedule(vec![rule(&[0, 1, 2, 3, 4], "09:00", "18:00", "office")]);
+        let none = HashSet::new();
+        // 2026-10-19 is Monday
+        assert_eq!(preset_at(&s, "2026-10-19", "09:00", &none), Some("office"));
+        assert_eq!(preset_at(&s, "2026-10-19", "17:59", &none), Some("office"));
+        assert_eq!(preset_at(&s, "2026-10-19", "18:00", &none), None);
+        // Sunday
+        assert_eq!(preset_at(&s, "2026-10-25", "10:00", &none), None);
+    }
+
+    #[test]
+    fn test_overnight_window() {
+        let s = schedule(vec![rule(&[4], "18:00", "06:00", "night_vision")]);
+        let none = HashSet::new();
+        // Friday evening and the following Saturday morning
+        assert_eq!(preset_at(&s, "2026-10-23", "23:00", &none), Some("night_vision"));
+        assert_eq!(preset_at(&s, "2026-10-24", "05:59", &none), Some("night_vision"));
+        assert_eq!(preset_at(&s, "2026-10-24", "06:00", &none), None);
+        // Friday early morning belongs to Thursday's window (not scheduled)
+        assert_eq!(preset_at(&s, "2026-10-23", "03:00", &none), None);
+    }
+
+    #[test]
+    fn test_holiday_override() {
+        let s = schedule(vec![
+            rule(&[HOLIDAY_DAY], "00:00", "00:00", "security_zone"),
+            rule(&[0, 1, 2, 3, 4], "09:00", "18:00", "office"),
+        ]);
+        let mut holidays = HashSet::new();
+        holidays.insert(NaiveDate::from_ymd_opt(2026, 11, 3).unwrap());
+        // 2026-11-03 (Tue) is a holiday
+        assert_eq!(preset_at(&s, "2026-11-03", "10:00", &holidays), Some("security_zone"));
+        assert_eq!(preset_at(&s, "2026-11-04", "10:00", &holidays), Some("office"));
+    }
+
+    #[test]
+    fn test_validate_rules() {
+        let known = |p: &str| p == "office";
+        assert!(validate_rules(&[rule(&[0], "09:00", "18:00", "office")], known).is_ok());
+        assert!(validate_rules(&[], known).is_err());
+        assert!(validate_rules(&[rule(&[8], "09:00", "18:00", "office")], known).is_err());
+        assert!(validate_rules(&[rule(&[0], "9am", "18:00", "office")], known).is_err());
+        assert!(validate_rules(&[rule(&[0], "09:00", "18:00", "unknown")], known).is_err());
+    }
+}
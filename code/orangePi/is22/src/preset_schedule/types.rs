@@ -0,0 +1,126 @@
+//! Preset Schedule Types
+
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Day index used for holiday rules (0=Mon ... 6=Sun)
+pub const HOLIDAY_DAY: u8 = 7;
+
+/// Schedule scope (precedence: Camera > Fid > Global)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScheduleScope {
+    Camera,
+    Fid,
+    Global,
+}
+
+impl ScheduleScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Camera => "camera",
+            Self::Fid => "fid",
+            Self::Global => "global",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "camera" => Some(Self::Camera),
+            "fid" => Some(Self::Fid),
+            "global" => Some(Self::Global),
+            _ => None,
+        }
+    }
+}
+
+/// Weekly time window → preset
+///
+/// `start > end` は日跨ぎ（例: 18:00-06:00）、`start == end` は終日
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScheduleRule {
+    /// 0=Mon ... 6=Sun, 7=Holiday (empty = every weekday)
+    #[serde(default)]
+    pub days: Vec<u8>,
+    /// JST "HH:MM"
+    pub start: String,
+    /// JST "HH:MM"
+    pub end: String,
+    pub preset_id: String,
+}
+
+impl ScheduleRule {
+    pub fn start_time(&self) -> Option<NaiveTime> {
+        NaiveTime::parse_from_str(&self.start, "%H:%M").ok()
+    }
+
+    pub fn end_time(&self) -> Option<NaiveTime> {
+        NaiveTime::parse_from_str(&self.end, "%H:%M").ok()
+    }
+}
+
+/// Preset schedule (preset_schedules row)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PresetSchedule {
+    pub schedule_id: String,
+    pub name: String,
+    pub scope: ScheduleScope,
+    pub scope_id: Option<String>,
+    pub rules: Vec<ScheduleRule>,
+    pub priority: i32,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl PresetSchedule {
+    /// Holiday rules exist (day 7) → holidays use only those rules
+    pub fn has_holiday_rules(&self) -> bool {
+        self.rules.iter().any(|r| r.days.contains(&HOLIDAY_DAY))
+    }
+}
+
+/// Create/replace schedule request
+#[derive(Debug, Clone, Deserialize)]
+pub struct UpsertPresetScheduleRequest {
+    pub schedule_id: Option<String>,
+    pub name: String,
+    pub scope: ScheduleScope,
+    pub scope_id: Option<String>,
+    pub rules: Vec<ScheduleRule>,
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Holiday override date
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PresetHoliday {
+    pub holiday_date: NaiveDate,
+    #[serde(default)]
+    pub name: String,
+}
+
+/// Resolved preset for a camera at a point in time
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ResolvedPreset {
+    pub preset_id: String,
+    /// None = cameras.preset_id (no active schedule)
+    pub schedule_id: Option<String>,
+}
+
+/// Preset switch log entry (preset_switch_logs row)
+#[derive(Debug, Clone, Serialize)]
+pub struct PresetSwitchLog {
+    pub id: u64,
+    pub camera_id: String,
+    pub from_preset_id: String,
+    pub to_preset_id: String,
+    pub schedule_id: Option<String>,
+    pub switched_at: DateTime<Utc>,
+}
@@ -128,6 +128,13 @@ impl PrevFrameCache {
         Self::new(PrevFrameCacheConfig::default())
     }
 
+    /// Persistence directory (None when persistence is disabled)
+    pub fn persist_dir(&self) -> Option<&std::path::Path> {
+        self.config
+            .enable_persistence
+            .then_some(self.config.persist_dir.as_path())
+    }
+
     /// Store frame as previous for a camera
     ///
     /// This should be called after IS21 analysis completes
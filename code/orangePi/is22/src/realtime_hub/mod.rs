@@ -7,6 +7,8 @@
 //! - Snapshot update notifications (triggers CameraGrid to fetch new image)
 //! - Suggest state distribution
 //!
+//! - Topic subscriptions, bounded per-connection outboxes and resumable sequence numbers
+//!
 //! Note: Only snapshot update NOTIFICATIONS are sent via WebSocket (camera_id + timestamp).
 //! Actual image data is fetched via HTTP GET /api/snapshots/{camera_id}/latest.jpg
 
@@ -14,12 +16,19 @@ use crate::suggest_engine::GlobalSuggestState;
 use futures::stream::SplitSink;
 use futures::SinkExt;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde_json::json;
+use std::collections::{HashMap, VecDeque};
 use std::sync::atomic::{AtomicU64, Ordering};
-use tokio::sync::mpsc;
-use tokio::sync::RwLock;
+use std::sync::Arc;
+use tokio::sync::{Mutex, RwLock};
 use uuid::Uuid;
 
+pub mod outbox;
+pub mod subscription;
+
+pub use outbox::{Outbox, OutboxItem, OutboxStats, DEFAULT_OUTBOX_CAPACITY};
+pub use subscription::{ClientCommand, MessageMeta, SubscriptionFilter, SubscriptionRequest};
+
 /// Hub message types
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", content = "data")]
@@ -43,6 +52,12 @@ pub enum HubMessage {
     /// AccessAbsorber stream preemption notification
     /// Notifies clients that their stream was preempted by higher priority request
     StreamPreempted(StreamPreemptedMessage),
+    /// Camera health diagnostics (stream quality / image tamper issues raised or cleared)
+    CameraHealth(CameraHealthMessage),
+    /// Modal lease waiting queue (a queued ticket was admitted)
+    LeaseQueue(LeaseQueueMessage),
+    /// Disk pressure level of image storage changed (StorageManager)
+    StoragePressure(StoragePressureMessage),
 }
 
 /// Event log message
@@ -154,6 +169,9 @@ pub struct SummaryReportMessage {
     pub summary_text: String,
     /// Timestamp of report generation
     pub created_at: String,
+    /// Printable report download URL (scheduled report attachment)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub report_url: Option<String>,
 }
 
 /// Chat message sync for cross-device real-time updates
@@ -206,6 +224,51 @@ pub struct StreamPreemptedMessage {
     pub exit_delay_sec: u32,
 }
 
+/// Camera health diagnostics notification
+/// Sent by CameraDiagnosticsService when the set of active issues changes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CameraHealthMessage {
+    pub camera_id: String,
+    pub lacis_id: Option<String>,
+    /// Newly raised issue kinds (e.g., "low_fps", "scene_shift")
+    pub raised: Vec<String>,
+    /// Issue kinds that are no longer detected
+    pub cleared: Vec<String>,
+    /// All currently active issue kinds
+    pub active: Vec<String>,
+    pub checked_at: String,
+}
+
+/// Storage pressure notification
+/// Sent by StorageManager when the disk pressure level changes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoragePressureMessage {
+    /// "normal" / "warning" / "critical"
+    pub level: String,
+    pub previous_level: String,
+    pub mount_point: String,
+    pub used_percent: f64,
+    pub available_bytes: u64,
+    /// Bytes freed by eviction in the same cycle
+    pub bytes_freed: u64,
+    pub checked_at: String,
+}
+
+/// Modal lease queue notification
+/// Sent by AdmissionController when a slot opens for a waiting ticket.
+/// Clients match ticket_id/user_id and fetch the lease via GET /api/modal/queue/:ticket_id
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeaseQueueMessage {
+    pub ticket_id: String,
+    pub user_id: String,
+    pub camera_id: String,
+    /// "admitted"
+    pub status: String,
+    pub lease_id: Option<String>,
+    /// "main" | "sub"
+    pub allowed_quality: Option<String>,
+}
+
 /// User message for preemption feedback
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PreemptionUserMessage {
@@ -214,17 +277,99 @@ pub struct PreemptionUserMessage {
     pub severity: String,
 }
 
+impl HubMessage {
+    /// Type tag used on the wire (`type` field)
+    pub fn kind(&self) -> &'static str {
+        match self {
+            HubMessage::SuggestUpdate(_) => "suggest_update",
+            HubMessage::EventLog(_) => "event_log",
+            HubMessage::SystemStatus(_) => "system_status",
+            HubMessage::CameraStatus(_) => "camera_status",
+            HubMessage::SnapshotUpdated(_) => "snapshot_updated",
+            HubMessage::CycleStats(_) => "cycle_stats",
+            HubMessage::CooldownTick(_) => "cooldown_tick",
+            HubMessage::SummaryReport(_) => "summary_report",
+            HubMessage::ChatSync(_) => "chat_sync",
+            HubMessage::StreamPreempted(_) => "stream_preempted",
+            HubMessage::CameraHealth(_) => "camera_health",
+            HubMessage::LeaseQueue(_) => "lease_queue",
+            HubMessage::StoragePressure(_) => "storage_pressure",
+        }
+    }
+
+    /// Routing attributes for subscription filtering and outbox coalescing
+    ///
+    /// 状態通知系（スナップショット更新・クールダウン等）は最新のみ意味を持つため coalesce 対象
+    pub fn meta(&self) -> MessageMeta {
+        let kind = self.kind();
+        let (camera_id, severity, coalesce_key) = match self {
+            HubMessage::EventLog(m) => (Some(m.camera_id.clone()), Some(m.severity), None),
+            HubMessage::SnapshotUpdated(m) => (
+                Some(m.camera_id.clone()),
+                Some(m.severity.unwrap_or(0)),
+                Some(format!("{}:{}", kind, m.camera_id)),
+            ),
+            HubMessage::CameraStatus(m) => (
+                Some(m.camera_id.clone()),
+                None,
+                Some(format!("{}:{}", kind, m.camera_id)),
+            ),
+            HubMessage::CameraHealth(m) => (Some(m.camera_id.clone()), None, None),
+            HubMessage::StreamPreempted(m) => (Some(m.camera_id.clone()), None, None),
+            HubMessage::LeaseQueue(m) => (Some(m.camera_id.clone()), None, None),
+            HubMessage::SummaryReport(m) => (None, Some(m.severity_max), None),
+            HubMessage::CycleStats(m) => (None, None, Some(format!("{}:{}", kind, m.subnet))),
+            HubMessage::CooldownTick(m) => (None, None, Some(format!("{}:{}", kind, m.subnet))),
+            HubMessage::SystemStatus(_)
+            | HubMessage::SuggestUpdate(_)
+            | HubMessage::StoragePressure(_) => {
+                (None, None, Some(kind.to_string()))
+            }
+            HubMessage::ChatSync(_) => (None, None, None),
+        };
+        MessageMeta {
+            kind,
+            camera_id,
+            severity,
+            coalesce_key,
+        }
+    }
+}
+
+/// Number of recent messages kept for resume
+pub const REPLAY_BUFFER_SIZE: usize = 1000;
+
+/// Buffered message for resume
+struct ReplayEntry {
+    seq: u64,
+    meta: MessageMeta,
+    /// Some = send_to_user の宛先
+    target_user: Option<String>,
+    json: String,
+}
+
+/// Sequence numbering and replay buffer
+///
+/// 採番から全接続への投入までこのロックを保持し、各接続で seq が単調増加することを保証する
+#[derive(Default)]
+struct Sequencer {
+    last_seq: u64,
+    replay: VecDeque<ReplayEntry>,
+}
+
 /// Client connection
 struct ClientConnection {
-    id: Uuid,
     user_id: String,
-    tx: mpsc::UnboundedSender<String>,
+    outbox: Arc<Outbox>,
 }
 
 /// RealtimeHub instance
 pub struct RealtimeHub {
     connections: RwLock<HashMap<Uuid, ClientConnection>>,
     connection_count: AtomicU64,
+    sequencer: Mutex<Sequencer>,
+    /// camera_id → fid (fid 購読の解決用)
+    camera_fids: RwLock<HashMap<String, String>>,
 }
 
 impl RealtimeHub {
@@ -233,15 +378,32 @@ impl RealtimeHub {
         Self {
             connections: RwLock::new(HashMap::new()),
             connection_count: AtomicU64::new(0),
+            sequencer: Mutex::new(Sequencer::default()),
+            camera_fids: RwLock::new(HashMap::new()),
         }
     }
 
     /// Register a new client
-    pub async fn register(&self, user_id: String) -> (Uuid, mpsc::UnboundedReceiver<String>) {
+    ///
+    /// 最初のフレームは welcome（connection_id と現在の seq）。再接続時はその後 resume を送る
+    pub async fn register(&self, user_id: String) -> (Uuid, Arc<Outbox>) {
         let id = Uuid::new_v4();
-        let (tx, rx) = mpsc::unbounded_channel();
+        let outbox = Arc::new(Outbox::new(DEFAULT_OUTBOX_CAPACITY));
+
+        let last_seq = self.sequencer.lock().await.last_seq;
+        outbox.push(control_frame(
+            "welcome",
+            json!({
+                "connection_id": id,
+                "seq": last_seq,
+                "outbox_capacity": DEFAULT_OUTBOX_CAPACITY,
+            }),
+        ));
 
-        let conn = ClientConnection { id, user_id, tx };
+        let conn = ClientConnection {
+            user_id,
+            outbox: outbox.clone(),
+        };
 
         {
             let mut connections = self.connections.write().await;
@@ -252,71 +414,198 @@ impl RealtimeHub {
 
         tracing::info!(connection_id = %id, "Client connected");
 
-        (id, rx)
+        (id, outbox)
     }
 
     /// Unregister a client
     pub async fn unregister(&self, id: &Uuid) {
         let mut connections = self.connections.write().await;
-        if connections.remove(id).is_some() {
+        if let Some(conn) = connections.remove(id) {
+            conn.outbox.close();
             self.connection_count.fetch_sub(1, Ordering::Relaxed);
-            tracing::info!(connection_id = %id, "Client disconnected");
+            let stats = conn.outbox.stats();
+            tracing::info!(
+                connection_id = %id,
+                dropped = stats.dropped,
+                coalesced = stats.coalesced,
+                "Client disconnected"
+            );
+        }
+    }
+
+    /// Close every connection (graceful shutdown)
+    ///
+    /// shutdown フレームを送ってから送信側を終了させる。戻り値は対象接続数
+    pub async fn close_all(&self) -> usize {
+        let connections = self.connections.read().await;
+        for conn in connections.values() {
+            conn.outbox.push(control_frame(
+                "shutdown",
+                json!({ "reason": "server shutting down" }),
+            ));
+            conn.outbox.finish();
         }
+        tracing::info!(connections = connections.len(), "Closing all realtime connections");
+        connections.len()
+    }
+
+    /// Replace the camera_id → fid index used for fid subscriptions
+    pub async fn set_camera_fids(&self, camera_fids: HashMap<String, String>) {
+        *self.camera_fids.write().await = camera_fids;
     }
 
     /// Broadcast message to all clients
     pub async fn broadcast(&self, message: HubMessage) {
-        let msg_type = match &message {
-            HubMessage::SuggestUpdate(_) => "suggest_update",
-            HubMessage::EventLog(_) => "event_log",
-            HubMessage::SystemStatus(_) => "system_status",
-            HubMessage::CameraStatus(_) => "camera_status",
-            HubMessage::SnapshotUpdated(_) => "snapshot_updated",
-            HubMessage::CycleStats(_) => "cycle_stats",
-            HubMessage::CooldownTick(_) => "cooldown_tick",
-            HubMessage::SummaryReport(_) => "summary_report",
-            HubMessage::ChatSync(_) => "chat_sync",
-            HubMessage::StreamPreempted(_) => "stream_preempted",
-        };
-        tracing::info!(message_type = %msg_type, "Broadcasting message to clients");
+        tracing::debug!(message_type = %message.kind(), "Broadcasting message to clients");
+        self.dispatch(message, None).await;
+    }
+
+    /// Send message to specific user
+    pub async fn send_to_user(&self, user_id: &str, message: HubMessage) {
+        self.dispatch(message, Some(user_id)).await;
+    }
 
-        let json = match serde_json::to_string(&message) {
-            Ok(j) => j,
+    /// Number the message, keep it for resume and enqueue it to matching connections
+    async fn dispatch(&self, message: HubMessage, target_user: Option<&str>) {
+        let meta = message.meta();
+        let mut value = match serde_json::to_value(&message) {
+            Ok(v) => v,
             Err(e) => {
                 tracing::error!(error = %e, "Failed to serialize message");
                 return;
             }
         };
 
-        let connections = self.connections.read().await;
-        let client_count = connections.len();
-        tracing::debug!(client_count = %client_count, "Sending to connected clients");
+        let mut sequencer = self.sequencer.lock().await;
+        let seq = sequencer.last_seq + 1;
+        value["seq"] = json!(seq);
+        let json = value.to_string();
+        sequencer.last_seq = seq;
+
+        let fid = match &meta.camera_id {
+            Some(camera_id) => self.camera_fids.read().await.get(camera_id).cloned(),
+            None => None,
+        };
 
+        let connections = self.connections.read().await;
+        tracing::debug!(client_count = %connections.len(), seq, "Sending to connected clients");
         for conn in connections.values() {
-            if let Err(e) = conn.tx.send(json.clone()) {
-                tracing::warn!(connection_id = %conn.id, error = %e, "Failed to send message");
+            if target_user.is_some_and(|user| conn.user_id != user) {
+                continue;
             }
+            conn.outbox.with_state(|state| {
+                if state.filter.matches(&meta, fid.as_deref()) {
+                    state.push(OutboxItem {
+                        seq: Some(seq),
+                        coalesce_key: meta.coalesce_key.clone(),
+                        json: json.clone(),
+                    });
+                }
+            });
+        }
+        drop(connections);
+
+        if sequencer.replay.len() >= REPLAY_BUFFER_SIZE {
+            sequencer.replay.pop_front();
         }
+        sequencer.replay.push_back(ReplayEntry {
+            seq,
+            meta,
+            target_user: target_user.map(String::from),
+            json,
+        });
     }
 
-    /// Send message to specific user
-    pub async fn send_to_user(&self, user_id: &str, message: HubMessage) {
-        let json = match serde_json::to_string(&message) {
-            Ok(j) => j,
-            Err(e) => {
-                tracing::error!(error = %e, "Failed to serialize message");
-                return;
-            }
+    /// Apply a subscription command sent by a client
+    pub async fn handle_command(&self, id: &Uuid, command: ClientCommand) {
+        if let ClientCommand::Resume { last_seq } = command {
+            self.resume(id, last_seq).await;
+            return;
+        }
+
+        let connections = self.connections.read().await;
+        let Some(conn) = connections.get(id) else {
+            return;
         };
+        let filter = conn.outbox.with_state(|state| {
+            match &command {
+                ClientCommand::Subscribe(request) => state.filter.subscribe(request),
+                ClientCommand::Unsubscribe(request) => state.filter.unsubscribe(request),
+                ClientCommand::Reset => state.filter = SubscriptionFilter::default(),
+                ClientCommand::Resume { .. } => {}
+            }
+            state.filter.clone()
+        });
+        conn.outbox
+            .push(control_frame("subscription", json!(filter)));
+    }
+
+    /// Send an error frame to one connection (e.g. unparsable command)
+    pub async fn send_error(&self, id: &Uuid, message: &str) {
+        if let Some(conn) = self.connections.read().await.get(id) {
+            conn.outbox
+                .push(control_frame("error", json!({ "message": message })));
+        }
+    }
 
+    /// Replay buffered messages with seq > last_seq
+    ///
+    /// キュー内の未送信分は seq 順に再構築する。送信済みフレームが再送される場合があるため
+    /// クライアントは seq で重複排除する。complete=false はバッファから溢れた（またはサーバー
+    /// 再起動で seq が巻き戻った）ことを示し、クライアントは全状態を HTTP で再取得する。
+    async fn resume(&self, id: &Uuid, last_seq: u64) {
+        let sequencer = self.sequencer.lock().await;
+        let camera_fids = self.camera_fids.read().await;
         let connections = self.connections.read().await;
-        for conn in connections.values() {
-            if conn.user_id == user_id {
-                if let Err(e) = conn.tx.send(json.clone()) {
-                    tracing::warn!(connection_id = %conn.id, error = %e, "Failed to send message");
+        let Some(conn) = connections.get(id) else {
+            return;
+        };
+
+        let oldest = sequencer.replay.front().map(|e| e.seq);
+        let complete =
+            last_seq <= sequencer.last_seq && oldest.map(|o| last_seq + 1 >= o).unwrap_or(true);
+
+        let replayed = conn.outbox.with_state(|state| {
+            state.rewind(last_seq);
+
+            let mut replayed = 0usize;
+            for entry in sequencer.replay.iter().filter(|e| e.seq > last_seq) {
+                if entry
+                    .target_user
+                    .as_deref()
+                    .is_some_and(|user| conn.user_id != user)
+                {
+                    continue;
+                }
+                let fid = entry
+                    .meta
+                    .camera_id
+                    .as_ref()
+                    .and_then(|c| camera_fids.get(c))
+                    .map(String::as_str);
+                if state.filter.matches(&entry.meta, fid) {
+                    state.push(OutboxItem {
+                        seq: Some(entry.seq),
+                        coalesce_key: entry.meta.coalesce_key.clone(),
+                        json: entry.json.clone(),
+                    });
+                    replayed += 1;
                 }
             }
-        }
+            state.last_seq = state.last_seq.max(sequencer.last_seq);
+            replayed
+        });
+
+        tracing::info!(connection_id = %id, last_seq, replayed, complete, "Client resumed");
+        conn.outbox.push(control_frame(
+            "resumed",
+            json!({
+                "from_seq": last_seq,
+                "seq": sequencer.last_seq,
+                "replayed": replayed,
+                "complete": complete,
+            }),
+        ));
     }
 
     /// Get connection count
@@ -330,3 +619,12 @@ impl Default for RealtimeHub {
         Self::new()
     }
 }
+
+/// Hub-originated control frame (not numbered, not replayed)
+fn control_frame(frame_type: &str, data: serde_json::Value) -> OutboxItem {
+    OutboxItem {
+        seq: None,
+        coalesce_key: None,
+        json: json!({ "type": frame_type, "data": data }).to_string(),
+    }
+}
@@ -0,0 +1,226 @@
+//! Per-connection bounded outbox
+//!
+//! 低速回線のクライアントでメモリが際限なく増えないよう、接続ごとの送信キューを有界にする。
+//! - coalesce_key を持つメッセージ（カメラ毎の snapshot_updated 等）は最新のみ保持
+//! - 満杯時は coalesce 可能な最古のメッセージ、なければ最古のメッセージを破棄
+//!
+//! 破棄は seq の欠番としてクライアントから観測でき、resume で再取得できる。
+
+use super::subscription::SubscriptionFilter;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use tokio::sync::Notify;
+
+/// Default outbox capacity per connection
+pub const DEFAULT_OUTBOX_CAPACITY: usize = 256;
+
+/// Queued outgoing frame
+#[derive(Debug, Clone)]
+pub struct OutboxItem {
+    /// None for control frames (acks, errors)
+    pub seq: Option<u64>,
+    pub coalesce_key: Option<String>,
+    pub json: String,
+}
+
+/// Outbox counters
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OutboxStats {
+    pub queued: usize,
+    pub dropped: u64,
+    pub coalesced: u64,
+}
+
+/// Queue state guarded by the outbox mutex
+#[derive(Debug)]
+pub(crate) struct OutboxState {
+    items: VecDeque<OutboxItem>,
+    capacity: usize,
+    dropped: u64,
+    coalesced: u64,
+    closed: bool,
+    pub(crate) filter: SubscriptionFilter,
+    /// Highest seq enqueued (resume と live 配信の重複防止)
+    pub(crate) last_seq: u64,
+}
+
+impl OutboxState {
+    fn new(capacity: usize) -> Self {
+        Self {
+            items: VecDeque::new(),
+            capacity: capacity.max(1),
+            dropped: 0,
+            coalesced: 0,
+            closed: false,
+            filter: SubscriptionFilter::default(),
+            last_seq: 0,
+        }
+    }
+
+    pub(crate) fn push(&mut self, item: OutboxItem) {
+        if let Some(seq) = item.seq {
+            if seq <= self.last_seq {
+                return;
+            }
+            self.last_seq = seq;
+        }
+
+        if let Some(key) = &item.coalesce_key {
+            if let Some(pos) = self
+                .items
+                .iter()
+                .position(|i| i.coalesce_key.as_ref() == Some(key))
+            {
+                self.items.remove(pos);
+                self.coalesced += 1;
+            }
+        }
+
+        if self.items.len() >= self.capacity {
+            let victim = self
+                .items
+                .iter()
+                .position(|i| i.coalesce_key.is_some())
+                .unwrap_or(0);
+            self.items.remove(victim);
+            self.dropped += 1;
+        }
+
+        self.items.push_back(item);
+    }
+
+    /// Discard numbered frames and restart numbering after `last_seq` (resume)
+    ///
+    /// seq > last_seq の未送信分はリプレイバッファから再投入される
+    pub(crate) fn rewind(&mut self, last_seq: u64) {
+        self.items.retain(|i| i.seq.is_none());
+        self.last_seq = last_seq;
+    }
+
+    pub(crate) fn stats(&self) -> OutboxStats {
+        OutboxStats {
+            queued: self.items.len(),
+            dropped: self.dropped,
+            coalesced: self.coalesced,
+        }
+    }
+}
+
+/// Bounded outbox shared between the hub and the connection's send task
+pub struct Outbox {
+    state: Mutex<OutboxState>,
+    notify: Notify,
+}
+
+impl Outbox {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            state: Mutex::new(OutboxState::new(capacity)),
+            notify: Notify::new(),
+        }
+    }
+
+    /// Run `f` with the locked state and wake the receiver
+    pub(crate) fn with_state<R>(&self, f: impl FnOnce(&mut OutboxState) -> R) -> R {
+        let result = {
+            let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            f(&mut state)
+        };
+        self.notify.notify_one();
+        result
+    }
+
+    pub fn push(&self, item: OutboxItem) {
+        self.with_state(|state| state.push(item));
+    }
+
+    pub fn stats(&self) -> OutboxStats {
+        self.state.lock().unwrap_or_else(|e| e.into_inner()).stats()
+    }
+
+    /// Stop the receiver (pending frames are discarded)
+    pub fn close(&self) {
+        self.with_state(|state| {
+            state.closed = true;
+            state.items.clear();
+        });
+    }
+
+    /// Stop the receiver after the queued frames have been delivered
+    pub fn finish(&self) {
+        self.with_state(|state| state.closed = true);
+    }
+
+    /// Wait for the next frame (None = closed)
+    pub async fn recv(&self) -> Option<String> {
+        loop {
+            {
+                let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+                if let Some(item) = state.items.pop_front() {
+                    return Some(item.json);
+                }
+                if state.closed {
+                    return None;
+                }
+            }
+            self.notify.notified().await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(seq: u64, key: Option<&str>) -> OutboxItem {
+        OutboxItem {
+            seq: Some(seq),
+            coalesce_key: key.map(String::from),
+            json: seq.to_string(),
+        }
+    }
+
+    fn seqs(state: &OutboxState) -> Vec<u64> {
+        state.items.iter().filter_map(|i| i.seq).collect()
+    }
+
+    #[test]
+    fn test_latest_snapshot_per_camera_wins() {
+        let mut state = OutboxState::new(8);
+        state.push(item(1, Some("snapshot_updated:cam-1")));
+        state.push(item(2, Some("snapshot_updated:cam-2")));
+        state.push(item(3, None));
+        state.push(item(4, Some("snapshot_updated:cam-1")));
+        // 既に送信済み扱いの seq は無視
+        state.push(item(4, None));
+
+        assert_eq!(seqs(&state), vec![2, 3, 4]);
+        assert_eq!(state.stats().coalesced, 1);
+    }
+
+    #[test]
+    fn test_overflow_drops_coalescable_first() {
+        let mut state = OutboxState::new(3);
+        state.push(item(1, None));
+        state.push(item(2, Some("cooldown_tick:a")));
+        state.push(item(3, None));
+        state.push(item(4, None));
+        assert_eq!(seqs(&state), vec![1, 3, 4]);
+
+        state.push(item(5, None));
+        assert_eq!(seqs(&state), vec![3, 4, 5]);
+        assert_eq!(state.stats().dropped, 2);
+    }
+
+    #[tokio::test]
+    async fn test_finish_delivers_queued_frames() {
+        let outbox = Outbox::new(4);
+        outbox.push(item(1, None));
+        outbox.push(item(2, None));
+        outbox.finish();
+
+        assert_eq!(outbox.recv().await.as_deref(), Some("1"));
+        assert_eq!(outbox.recv().await.as_deref(), Some("2"));
+        assert_eq!(outbox.recv().await, None);
+    }
+}
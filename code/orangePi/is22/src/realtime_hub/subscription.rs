@@ -0,0 +1,175 @@
+//! WebSocket subscription protocol
+//!
+//! クライアントは /api/ws 上で JSON テキストを送信して購読条件を変更する。
+//! 条件未指定（空集合）は「すべて受信」を意味し、従来クライアントと互換。
+//!
+//! ```json
+//! {"action":"subscribe","types":["event_log"],"camera_ids":["cam-1"],"fids":["0150"],"min_severity":2}
+//! {"action":"unsubscribe","types":["cooldown_tick"]}
+//! {"action":"reset"}
+//! {"action":"resume","last_seq":1234}
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// Client → server command
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum ClientCommand {
+    /// Add types / cameras / fids to the subscription (min_severity replaces)
+    Subscribe(SubscriptionRequest),
+    /// Remove types / cameras / fids from the subscription
+    Unsubscribe(SubscriptionRequest),
+    /// Back to "receive everything"
+    Reset,
+    /// Replay buffered messages with seq > last_seq
+    Resume { last_seq: u64 },
+}
+
+/// Subscribe / unsubscribe payload
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct SubscriptionRequest {
+    pub types: Vec<String>,
+    pub camera_ids: Vec<String>,
+    pub fids: Vec<String>,
+    pub min_severity: Option<i32>,
+}
+
+/// Routing attributes of a hub message
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MessageMeta {
+    /// HubMessage type tag (e.g., "snapshot_updated")
+    pub kind: &'static str,
+    pub camera_id: Option<String>,
+    pub severity: Option<i32>,
+    /// Queued messages with the same key are coalesced (latest wins)
+    pub coalesce_key: Option<String>,
+}
+
+/// Per-connection subscription filter
+///
+/// 空集合はワイルドカード。カメラ条件は camera_ids と fids の和集合で判定し、
+/// camera_id を持たないシステム系メッセージはカメラ条件の対象外とする。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SubscriptionFilter {
+    pub types: BTreeSet<String>,
+    /// Types removed while `types` is a wildcard
+    pub excluded_types: BTreeSet<String>,
+    pub camera_ids: BTreeSet<String>,
+    pub fids: BTreeSet<String>,
+    /// Applies only to messages carrying a severity
+    pub min_severity: Option<i32>,
+}
+
+impl SubscriptionFilter {
+    pub fn subscribe(&mut self, request: &SubscriptionRequest) {
+        for t in &request.types {
+            self.excluded_types.remove(t);
+            self.types.insert(t.clone());
+        }
+        self.camera_ids.extend(request.camera_ids.iter().cloned());
+        self.fids.extend(request.fids.iter().cloned());
+        if request.min_severity.is_some() {
+            self.min_severity = request.min_severity;
+        }
+    }
+
+    pub fn unsubscribe(&mut self, request: &SubscriptionRequest) {
+        for t in &request.types {
+            if self.types.is_empty() {
+                self.excluded_types.insert(t.clone());
+            } else {
+                self.types.remove(t);
+            }
+        }
+        for id in &request.camera_ids {
+            self.camera_ids.remove(id);
+        }
+        for fid in &request.fids {
+            self.fids.remove(fid);
+        }
+        if request.min_severity.is_some() {
+            self.min_severity = None;
+        }
+    }
+
+    /// Whether a message passes the filter (`fid` = facility of meta.camera_id)
+    pub fn matches(&self, meta: &MessageMeta, fid: Option<&str>) -> bool {
+        if self.excluded_types.contains(meta.kind)
+            || (!self.types.is_empty() && !self.types.contains(meta.kind))
+        {
+            return false;
+        }
+
+        if let Some(camera_id) = &meta.camera_id {
+            let filtered = !self.camera_ids.is_empty() || !self.fids.is_empty();
+            let camera_match = self.camera_ids.contains(camera_id)
+                || fid.map(|f| self.fids.contains(f)).unwrap_or(false);
+            if filtered && !camera_match {
+                return false;
+            }
+        }
+
+        match (self.min_severity, meta.severity) {
+            (Some(min), Some(severity)) => severity >= min,
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meta(kind: &'static str, camera_id: Option<&str>, severity: Option<i32>) -> MessageMeta {
+        MessageMeta {
+            kind,
+            camera_id: camera_id.map(String::from),
+            severity,
+            coalesce_key: None,
+        }
+    }
+
+    #[test]
+    fn test_default_filter_passes_everything() {
+        let filter = SubscriptionFilter::default();
+        assert!(filter.matches(&meta("cooldown_tick", None, None), None));
+        assert!(filter.matches(&meta("event_log", Some("cam-1"), Some(0)), Some("0150")));
+    }
+
+    #[test]
+    fn test_camera_and_fid_union() {
+        let mut filter = SubscriptionFilter::default();
+        filter.subscribe(&SubscriptionRequest {
+            camera_ids: vec!["cam-1".to_string()],
+            fids: vec!["0150".to_string()],
+            min_severity: Some(2),
+            ..Default::default()
+        });
+
+        assert!(filter.matches(&meta("event_log", Some("cam-1"), Some(2)), Some("0999")));
+        assert!(filter.matches(&meta("event_log", Some("cam-2"), Some(3)), Some("0150")));
+        assert!(!filter.matches(&meta("event_log", Some("cam-3"), Some(3)), Some("0999")));
+        assert!(!filter.matches(&meta("event_log", Some("cam-1"), Some(1)), None));
+        // システム系メッセージはカメラ条件の対象外
+        assert!(filter.matches(&meta("system_status", None, None), None));
+    }
+
+    #[test]
+    fn test_unsubscribe_type_from_wildcard() {
+        let mut filter = SubscriptionFilter::default();
+        let request = SubscriptionRequest {
+            types: vec!["cooldown_tick".to_string()],
+            ..Default::default()
+        };
+        filter.unsubscribe(&request);
+        assert!(!filter.matches(&meta("cooldown_tick", None, None), None));
+        assert!(filter.matches(&meta("cycle_stats", None, None), None));
+
+        filter.subscribe(&request);
+        assert!(filter.matches(&meta("cooldown_tick", None, None), None));
+        assert!(!filter.matches(&meta("cycle_stats", None, None), None));
+    }
+}
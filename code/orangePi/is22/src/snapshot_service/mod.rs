@@ -606,6 +606,16 @@ impl SnapshotService {
         Ok(path)
     }
 
+    /// Directory of latest.jpg caches
+    pub fn snapshot_dir(&self) -> &std::path::Path {
+        &self.snapshot_dir
+    }
+
+    /// Directory of prev.jpg files
+    pub fn temp_dir(&self) -> &std::path::Path {
+        &self.temp_dir
+    }
+
     /// Get path to cached snapshot
     pub fn get_cache_path(&self, camera_id: &str) -> PathBuf {
         self.snapshot_dir.join(camera_id).join("latest.jpg")
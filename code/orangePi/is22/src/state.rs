@@ -12,73 +12,38 @@ use crate::camera_sync::CameraSyncService;
 use crate::config_store::ConfigStore;
 use crate::detection_log_service::DetectionLogService;
 use crate::event_log_service::EventLogService;
+use crate::health::HealthRegistry;
 use crate::inference_stats_service::InferenceStatsService;
 use crate::ipcam_scan::IpcamScan;
 use crate::overdetection_analyzer::OverdetectionAnalyzer;
+use crate::camera_availability::CameraAvailabilityService;
+use crate::camera_diagnostics::CameraDiagnosticsService;
+use crate::camera_maintenance::CameraMaintenanceService;
 use crate::polling_orchestrator::PollingOrchestrator;
 use crate::prev_frame_cache::PrevFrameCache;
 use crate::preset_loader::PresetLoader;
 use crate::ptz_controller::PtzService;
 use crate::realtime_hub::RealtimeHub;
 use crate::snapshot_service::SnapshotService;
-use crate::stream_gateway::StreamGateway;
+use crate::storage_manager::StorageManager;
+use crate::live_layout::LiveLayoutService;
+use crate::audit_log::AuditLogService;
+use crate::incident_case::IncidentCaseService;
+use crate::metrics::Metrics;
+use crate::stream_gateway::{StreamGateway, StreamReconciler};
 use crate::suggest_engine::SuggestEngine;
+use crate::task_supervisor::TaskSupervisor;
 use crate::summary_service::{
-    GrandSummaryGenerator, ScheduleRepository, SummaryGenerator, SummaryRepository,
+    GrandSummaryGenerator, ReportService, RollupGenerator, ScheduleRepository, SummaryGenerator,
+    SummaryRepository, SummaryTextBuilder,
 };
+use crate::email_notifier::EmailNotifier;
 use crate::paraclate_client::{FidValidator, ParaclateClient, PubSubSubscriber};
+pub use crate::app_config::AppConfig;
 use sqlx::MySqlPool;
-use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
-/// Application configuration
-#[derive(Debug, Clone)]
-pub struct AppConfig {
-    /// Database URL
-    pub database_url: String,
-    /// IS21 AI server URL
-    pub is21_url: String,
-    /// go2rtc URL
-    pub go2rtc_url: String,
-    /// Server port
-    pub port: u16,
-    /// Server host
-    pub host: String,
-    /// Snapshot cache directory (for CameraGrid display)
-    pub snapshot_dir: PathBuf,
-    /// Temporary directory (for prev images for IS21 diff detection)
-    pub temp_dir: PathBuf,
-    /// araneaDeviceGate URL (Phase 1: AraneaRegister)
-    pub aranea_gate_url: Option<String>,
-}
-
-impl Default for AppConfig {
-    fn default() -> Self {
-        Self {
-            database_url: std::env::var("DATABASE_URL")
-                .unwrap_or_else(|_| "mysql://root:mijeos12345@@localhost/camserver".to_string()),
-            is21_url: std::env::var("IS21_URL")
-                .unwrap_or_else(|_| "http://192.168.3.240:9000".to_string()),
-            go2rtc_url: std::env::var("GO2RTC_URL")
-                .unwrap_or_else(|_| "http://localhost:1984".to_string()),
-            port: std::env::var("PORT")
-                .ok()
-                .and_then(|p| p.parse().ok())
-                .unwrap_or(8080),
-            host: std::env::var("HOST")
-                .unwrap_or_else(|_| "0.0.0.0".to_string()),
-            snapshot_dir: std::env::var("SNAPSHOT_DIR")
-                .map(PathBuf::from)
-                .unwrap_or_else(|_| PathBuf::from("/var/lib/is22/snapshots")),
-            temp_dir: std::env::var("TEMP_DIR")
-                .map(PathBuf::from)
-                .unwrap_or_else(|_| PathBuf::from("/var/lib/is22/temp")),
-            aranea_gate_url: std::env::var("ARANEA_GATE_URL").ok(),
-        }
-    }
-}
-
 /// Application state shared across handlers
 #[derive(Clone)]
 pub struct AppState {
@@ -112,6 +77,10 @@ pub struct AppState {
     pub camera_brand: Arc<CameraBrandService>,
     /// SnapshotService (RTSP -> ffmpeg -> cache)
     pub snapshot_service: Arc<SnapshotService>,
+    /// HealthRegistry (稼働時間・バックグラウンドループのハートビート)
+    pub health: Arc<HealthRegistry>,
+    /// TaskSupervisor (監視下バックグラウンドタスク・グレースフルシャットダウン)
+    pub supervisor: Arc<TaskSupervisor>,
     /// System health status
     pub system_health: Arc<RwLock<SystemHealth>>,
     /// PollingOrchestrator (camera polling)
@@ -122,14 +91,40 @@ pub struct AppState {
     pub auto_attunement: Arc<AutoAttunementService>,
     /// OverdetectionAnalyzer (Issue #107: 過剰検出分析)
     pub overdetection_analyzer: Arc<OverdetectionAnalyzer>,
+    /// CameraAvailabilityService (稼働履歴・SLA指標)
+    pub camera_availability: Arc<CameraAvailabilityService>,
+    /// CameraDiagnosticsService (ストリーム品質・画像改ざん診断)
+    pub camera_diagnostics: Arc<CameraDiagnosticsService>,
+    /// CameraMaintenanceService (ONVIF ファームウェア・時刻メンテナンス)
+    pub camera_maintenance: Arc<CameraMaintenanceService>,
+    /// StorageManager (画像ストア横断のディスク逼迫管理)
+    pub storage_manager: Arc<StorageManager>,
+    /// StreamReconciler (go2rtc ストリームと ConfigStore の同期)
+    pub stream_reconciler: Arc<StreamReconciler>,
+    /// LiveLayoutService (ライブウォールのレイアウト・ツアー)
+    pub live_layouts: Arc<LiveLayoutService>,
+    /// AuditLogService (変更系 API の監査証跡・ハッシュチェーン)
+    pub audit_log: Arc<AuditLogService>,
+    /// IncidentCaseService (証拠保全・署名付きエクスポート・管理履歴)
+    pub incident_cases: Arc<IncidentCaseService>,
+    /// Metrics (Prometheus /metrics エクスポート)
+    pub metrics: Arc<Metrics>,
     /// AraneaRegisterService (Phase 1: Issue #114)
     pub aranea_register: Option<Arc<AraneaRegisterService>>,
     /// SummaryGenerator (Phase 3: Issue #116)
     pub summary_generator: Arc<SummaryGenerator>,
     /// GrandSummaryGenerator (Phase 3: Issue #116)
     pub grand_summary_generator: Arc<GrandSummaryGenerator>,
+    /// RollupGenerator (週次・月次ロールアップ)
+    pub rollup_generator: Arc<RollupGenerator>,
     /// SummaryRepository (Phase 3: Issue #116)
     pub summary_repository: SummaryRepository,
+    /// SummaryTextBuilder (summary_textテンプレート・プレビュー)
+    pub summary_text_builder: Arc<SummaryTextBuilder>,
+    /// ReportService (印刷用HTML/PDFレポート)
+    pub report_service: Arc<ReportService>,
+    /// EmailNotifier (SMTP配信: レポート・検出アラート)
+    pub email_notifier: Arc<EmailNotifier>,
     /// ScheduleRepository (Phase 3: Issue #116)
     pub schedule_repository: ScheduleRepository,
     /// ParaclateClient (Phase 4: Issue #117)
@@ -0,0 +1,569 @@
+//! StorageManager - 画像ストア横断のディスク逼迫管理
+//!
+//! ## 概要
+//! 画像は以下のストアに分散している（いずれも `<dir>/<camera_id>/<file>`）。
+//! - snapshot_dir: SnapshotService の latest.jpg
+//! - temp_dir: SnapshotService の prev.jpg
+//! - PrevFrameCache の永続化ディレクトリ
+//! - 検出画像: DetectionLogService（detection_logs.image_path_local）
+//!
+//! DetectionLogService のクォータ（枚数・容量）とは別に、ファイルシステムの使用率で判定する。
+//!
+//! 1. 集計: ストア別・カメラ別の使用量と、ストアを含むファイルシステムの使用率
+//! 2. 警告: warn_percent 以上になったら RealtimeHub（storage_pressure）と
+//!    Paraclate 不調報告（storage_pressure）で通知（レベル上昇時のみ）
+//! 3. 退避: high_watermark_percent 以上で low_watermark_percent を下回るまで削除
+//!    - 未登録カメラのキャッシュ → 孤立ファイル → severity の低い順 → 古い順
+//!    - 高 severity の検出画像ほど長く残る
+//!    - 削除した検出画像の行は残し、image_path_local を空にする
+//! 4. 整合性: 検出画像ファイルと detection_logs 行の照合（欠損・孤立・0 バイト）
+
+pub mod types;
+
+pub use types::*;
+
+use crate::aranea_register::AraneaRegisterService;
+use crate::camera_malfunction_reporter::CameraMalfunctionReporter;
+use crate::config_store::ConfigStore;
+use crate::detection_log_service::DetectionLogService;
+use crate::health;
+use crate::paraclate_client::types::CameraMalfunctionType;
+use crate::realtime_hub::{HubMessage, RealtimeHub, StoragePressureMessage};
+use chrono::Utc;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::fs;
+use tokio::sync::RwLock;
+use tracing::{debug, info, warn};
+
+/// 1 ファイル（カメラディレクトリ直下）
+struct StoredFile {
+    camera_id: String,
+    path: PathBuf,
+    size: u64,
+    modified: std::time::SystemTime,
+}
+
+#[derive(Default)]
+struct ManagerState {
+    last_report: Option<StorageReport>,
+    level: PressureLevel,
+    last_eviction: Option<EvictionResult>,
+}
+
+/// ストレージ管理サービス
+pub struct StorageManager {
+    config_store: Arc<ConfigStore>,
+    detection_log: Arc<DetectionLogService>,
+    reporter: CameraMalfunctionReporter,
+    realtime_hub: Arc<RealtimeHub>,
+    aranea_register: Option<Arc<AraneaRegisterService>>,
+    /// 検出画像以外のストア（検出画像は DetectionLogService の設定から都度取得）
+    cache_stores: Vec<(StorageStore, PathBuf)>,
+    /// IS22 未登録時の報告先
+    default_tid: String,
+    default_fid: String,
+    config: RwLock<StorageManagerConfig>,
+    state: RwLock<ManagerState>,
+}
+
+impl StorageManager {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        config_store: Arc<ConfigStore>,
+        detection_log: Arc<DetectionLogService>,
+        reporter: CameraMalfunctionReporter,
+        realtime_hub: Arc<RealtimeHub>,
+        aranea_register: Option<Arc<AraneaRegisterService>>,
+        cache_stores: Vec<(StorageStore, PathBuf)>,
+        default_tid: String,
+        default_fid: String,
+    ) -> Self {
+        // temp_dir と PrevFrameCache は同じディレクトリを使うことがある（二重集計しない）
+        let mut seen = HashSet::new();
+        let cache_stores = cache_stores
+            .into_iter()
+            .filter(|(_, path)| seen.insert(path.clone()))
+            .collect();
+        Self {
+            config_store,
+            detection_log,
+            reporter,
+            realtime_hub,
+            aranea_register,
+            cache_stores,
+            default_tid,
+            default_fid,
+            config: RwLock::new(StorageManagerConfig::default()),
+            state: RwLock::new(ManagerState::default()),
+        }
+    }
+
+    /// 設定をDBから読み込み（未設定ならデフォルト）
+    pub async fn load_config(&self) -> crate::Result<()> {
+        let stored = self
+            .config_store
+            .service()
+            .get_setting(STORAGE_MANAGER_CONFIG_KEY)
+            .await?
+            .and_then(|v| serde_json::from_value(v).ok());
+        if let Some(config) = stored {
+            *self.config.write().await = config;
+        }
+        Ok(())
+    }
+
+    pub async fn config(&self) -> StorageManagerConfig {
+        self.config.read().await.clone()
+    }
+
+    /// 設定を保存
+    pub async fn set_config(&self, config: StorageManagerConfig) -> crate::Result<StorageManagerConfig> {
+        config.validate()?;
+        self.config_store
+            .service()
+            .set_setting(STORAGE_MANAGER_CONFIG_KEY, serde_json::to_value(&config)?)
+            .await?;
+        *self.config.write().await = config.clone();
+        Ok(config)
+    }
+
+    /// 直近の集計結果
+    pub async fn last_report(&self) -> Option<StorageReport> {
+        self.state.read().await.last_report.clone()
+    }
+
+    /// 全ストア（検出画像を含む）
+    async fn stores(&self) -> Vec<(StorageStore, PathBuf)> {
+        let event_dir = self.detection_log.config().await.image_base_path;
+        let mut stores = self.cache_stores.clone();
+        if !stores.iter().any(|(_, path)| *path == event_dir) {
+            stores.push((StorageStore::EventImages, event_dir));
+        }
+        stores
+    }
+
+    /// 定期実行: 集計 → 逼迫度の変化を通知 → 必要なら退避
+    pub async fn run_cycle(&self) -> crate::Result<StorageReport> {
+        let config = self.config().await;
+        let mut report = self.scan(&config).await?;
+        if !config.enabled {
+            return Ok(report);
+        }
+
+        let mut eviction = None;
+        if report.level == PressureLevel::Critical && config.auto_evict {
+            let result = self.evict(&config, &report).await?;
+            if result.bytes_freed > 0 {
+                report = self.scan(&config).await?;
+            }
+            eviction = Some(result);
+        }
+
+        let previous = {
+            let mut state = self.state.write().await;
+            if let Some(result) = &eviction {
+                state.last_eviction = Some(result.clone());
+            }
+            report.last_eviction = state.last_eviction.clone();
+            state.last_report = Some(report.clone());
+            std::mem::replace(&mut state.level, report.level)
+        };
+        if previous != report.level {
+            self.notify(&report, previous, eviction.as_ref()).await;
+        }
+        Ok(report)
+    }
+
+    /// 使用量を集計（削除は行わない）
+    pub async fn scan(&self, config: &StorageManagerConfig) -> crate::Result<StorageReport> {
+        let mut stores = Vec::new();
+        let mut disks: BTreeMap<PathBuf, DiskPressure> = BTreeMap::new();
+
+        for (store, path) in self.stores().await {
+            let files = list_store_files(&path).await?;
+            let mut per_camera: HashMap<String, CameraUsage> = HashMap::new();
+            for file in &files {
+                let usage = per_camera
+                    .entry(file.camera_id.clone())
+                    .or_insert_with(|| CameraUsage {
+                        camera_id: file.camera_id.clone(),
+                        bytes: 0,
+                        files: 0,
+                    });
+                usage.bytes += file.size;
+                usage.files += 1;
+            }
+            let mut cameras: Vec<CameraUsage> = per_camera.into_values().collect();
+            cameras.sort_by(|a, b| b.bytes.cmp(&a.bytes).then(a.camera_id.cmp(&b.camera_id)));
+
+            if let Some(disk) = health::disk_usage(&path) {
+                let used_percent = used_percent(disk.total, disk.available);
+                disks
+                    .entry(disk.mount_point.clone())
+                    .or_insert_with(|| DiskPressure {
+                        mount_point: disk.mount_point.clone(),
+                        total_bytes: disk.total,
+                        available_bytes: disk.available,
+                        used_percent,
+                        level: config.pressure(used_percent),
+                        stores: Vec::new(),
+                    })
+                    .stores
+                    .push(store);
+            }
+
+            stores.push(StoreUsage {
+                store,
+                bytes: cameras.iter().map(|c| c.bytes).sum(),
+                files: files.len() as u64,
+                cameras,
+                path,
+            });
+        }
+
+        let disks: Vec<DiskPressure> = disks.into_values().collect();
+        Ok(StorageReport {
+            checked_at: Utc::now(),
+            level: disks.iter().map(|d| d.level).max().unwrap_or_default(),
+            disks,
+            stores,
+            last_eviction: self.state.read().await.last_eviction.clone(),
+        })
+    }
+
+    /// high watermark を超えたファイルシステムで low watermark まで削除
+    pub async fn evict(
+        &self,
+        config: &StorageManagerConfig,
+        report: &StorageReport,
+    ) -> crate::Result<EvictionResult> {
+        let registered: HashSet<String> = self
+            .config_store
+            .get_cached_cameras()
+            .await
+            .into_iter()
+            .map(|c| c.camera_id)
+            .collect();
+        let refs: HashMap<PathBuf, ImageRef> = self
+            .detection_log
+            .list_image_refs(None)
+            .await?
+            .into_iter()
+            .map(|r| (PathBuf::from(&r.path), r))
+            .collect();
+
+        let mut result = EvictionResult::default();
+        for disk in report.disks.iter().filter(|d| d.level == PressureLevel::Critical) {
+            let target = config.bytes_to_free(disk.total_bytes, disk.available_bytes);
+            result.target_bytes += target;
+            let mut remaining = target;
+
+            // 1. 未登録カメラのキャッシュ（再生成可能）
+            if config.remove_stale_caches {
+                for store in report
+                    .stores
+                    .iter()
+                    .filter(|s| s.store.is_cache() && disk.stores.contains(&s.store))
+                {
+                    for file in list_store_files(&store.path).await? {
+                        if remaining == 0 {
+                            break;
+                        }
+                        if registered.contains(&file.camera_id) {
+                            continue;
+                        }
+                        if remove(&file.path).await {
+                            result.cache_files_deleted += 1;
+                            result.bytes_freed += file.size;
+                            remaining = remaining.saturating_sub(file.size);
+                        }
+                    }
+                }
+            }
+
+            // 2. 検出画像（孤立 → severity 昇順 → 古い順）
+            let mut candidates = Vec::new();
+            for store in report
+                .stores
+                .iter()
+                .filter(|s| !s.store.is_cache() && disk.stores.contains(&s.store))
+            {
+                for file in list_store_files(&store.path).await? {
+                    let image_ref = refs.get(&file.path);
+                    candidates.push(EvictionCandidate {
+                        log_id: image_ref.map(|r| r.log_id),
+                        severity: image_ref.map(|r| r.severity),
+                        path: file.path,
+                        size: file.size,
+                        modified: file.modified,
+                    });
+                }
+            }
+
+            let mut evicted_ids = Vec::new();
+            for candidate in plan_eviction(candidates, remaining) {
+                if !remove(&candidate.path).await {
+                    continue;
+                }
+                result.event_images_deleted += 1;
+                result.bytes_freed += candidate.size;
+                if let Some(severity) = candidate.severity {
+                    result.max_severity_evicted =
+                        Some(result.max_severity_evicted.map_or(severity, |m| m.max(severity)));
+                }
+                evicted_ids.extend(candidate.log_id);
+            }
+            if !evicted_ids.is_empty() {
+                self.detection_log.clear_image_paths(&evicted_ids).await?;
+            }
+
+            warn!(
+                mount_point = %disk.mount_point.display(),
+                used_percent = format!("{:.1}", disk.used_percent),
+                target_bytes = target,
+                bytes_freed = result.bytes_freed,
+                event_images = result.event_images_deleted,
+                cache_files = result.cache_files_deleted,
+                max_severity = ?result.max_severity_evicted,
+                "Storage high watermark exceeded, evicted images"
+            );
+        }
+        Ok(result)
+    }
+
+    /// 検出画像ファイルと detection_logs 行の照合
+    ///
+    /// `camera_id` 指定時はそのカメラのディレクトリと行のみ対象
+    pub async fn check_integrity(&self, camera_id: Option<&str>) -> crate::Result<IntegrityReport> {
+        let refs = self.detection_log.list_image_refs(camera_id).await?;
+        let base = self.detection_log.config().await.image_base_path;
+        let files: Vec<StoredFile> = list_store_files(&base)
+            .await?
+            .into_iter()
+            .filter(|f| camera_id.map_or(true, |id| f.camera_id == id))
+            .collect();
+
+        let referenced: HashSet<PathBuf> = refs.iter().map(|r| PathBuf::from(&r.path)).collect();
+        let on_disk: HashMap<&Path, u64> = files.iter().map(|f| (f.path.as_path(), f.size)).collect();
+
+        let mut missing_files = Vec::new();
+        for image_ref in &refs {
+            let path = Path::new(&image_ref.path);
+            // 画像ディレクトリ外（設定変更前の行など）は直接確認
+            let exists = on_disk.contains_key(path) || fs::metadata(path).await.is_ok();
+            if !exists {
+                missing_files.push(MissingImage {
+                    log_id: image_ref.log_id,
+                    camera_id: image_ref.camera_id.clone(),
+                    path: image_ref.path.clone(),
+                });
+            }
+        }
+
+        let mut orphan_files = Vec::new();
+        let mut empty_files = Vec::new();
+        for file in &files {
+            if !referenced.contains(&file.path) {
+                orphan_files.push(file.path.to_string_lossy().to_string());
+            }
+            if file.size == 0 {
+                empty_files.push(file.path.to_string_lossy().to_string());
+            }
+        }
+        orphan_files.sort();
+        empty_files.sort();
+
+        let report = IntegrityReport {
+            checked_at: Utc::now(),
+            camera_id: camera_id.map(str::to_string),
+            rows_checked: refs.len(),
+            files_checked: files.len(),
+            missing_files,
+            orphan_files,
+            empty_files,
+        };
+        debug!(
+            camera_id = ?camera_id,
+            rows = report.rows_checked,
+            files = report.files_checked,
+            missing = report.missing_files.len(),
+            orphans = report.orphan_files.len(),
+            empty = report.empty_files.len(),
+            "Storage integrity check completed"
+        );
+        Ok(report)
+    }
+
+    /// 逼迫度の変化を通知（上昇時のみ Paraclate へ不調報告）
+    async fn notify(
+        &self,
+        report: &StorageReport,
+        previous: PressureLevel,
+        eviction: Option<&EvictionResult>,
+    ) {
+        let Some(disk) = report
+            .disks
+            .iter()
+            .max_by(|a, b| a.level.cmp(&b.level).then(a.used_percent.total_cmp(&b.used_percent)))
+        else {
+            return;
+        };
+        let bytes_freed = eviction.map_or(0, |e| e.bytes_freed);
+
+        if report.level > previous {
+            warn!(
+                level = report.level.as_str(),
+                previous = previous.as_str(),
+                mount_point = %disk.mount_point.display(),
+                used_percent = format!("{:.1}", disk.used_percent),
+                available_bytes = disk.available_bytes,
+                "Storage pressure raised"
+            );
+            self.report_malfunction(report, disk, bytes_freed).await;
+        } else {
+            info!(
+                level = report.level.as_str(),
+                previous = previous.as_str(),
+                used_percent = format!("{:.1}", disk.used_percent),
+                "Storage pressure eased"
+            );
+        }
+
+        self.realtime_hub
+            .broadcast(HubMessage::StoragePressure(StoragePressureMessage {
+                level: report.level.as_str().to_string(),
+                previous_level: previous.as_str().to_string(),
+                mount_point: disk.mount_point.to_string_lossy().to_string(),
+                used_percent: disk.used_percent,
+                available_bytes: disk.available_bytes,
+                bytes_freed,
+                checked_at: report.checked_at.to_rfc3339(),
+            }))
+            .await;
+    }
+
+    async fn report_malfunction(&self, report: &StorageReport, disk: &DiskPressure, bytes_freed: u64) {
+        let Some(register) = &self.aranea_register else {
+            debug!("Storage pressure not reported to Paraclate (AraneaRegister disabled)");
+            return;
+        };
+        let Some(lacis_id) = register.get_lacis_id().await else {
+            debug!("Storage pressure not reported to Paraclate (IS22 not registered)");
+            return;
+        };
+        let tid = register.get_tid().await.unwrap_or_else(|| self.default_tid.clone());
+        let fid = register.get_fid().await.unwrap_or_else(|| self.default_fid.clone());
+
+        let details = serde_json::json!({
+            "level": report.level.as_str(),
+            "mountPoint": disk.mount_point.to_string_lossy(),
+            "usedPercent": disk.used_percent,
+            "availableBytes": disk.available_bytes,
+            "totalBytes": disk.total_bytes,
+            "bytesFreed": bytes_freed,
+            "stores": report
+                .stores
+                .iter()
+                .map(|s| serde_json::json!({ "store": s.store.as_str(), "bytes": s.bytes, "files": s.files }))
+                .collect::<Vec<_>>(),
+        });
+        if let Err(e) = self
+            .reporter
+            .report_malfunction(
+                &tid,
+                &fid,
+                &lacis_id,
+                CameraMalfunctionType::StoragePressure,
+                Some(details),
+            )
+            .await
+        {
+            warn!(error = %e, "Failed to report storage pressure");
+        }
+    }
+}
+
+fn used_percent(total: u64, available: u64) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    total.saturating_sub(available) as f64 / total as f64 * 100.0
+}
+
+/// `<dir>/<camera_id>/<file>` のファイル一覧（ディレクトリがなければ空）
+async fn list_store_files(dir: &Path) -> crate::Result<Vec<StoredFile>> {
+    let mut files = Vec::new();
+    let mut cameras = match fs::read_dir(dir).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(files),
+        Err(e) => return Err(e.into()),
+    };
+    while let Some(camera_entry) = cameras.next_entry().await? {
+        if !camera_entry.file_type().await?.is_dir() {
+            continue;
+        }
+        let camera_id = camera_entry.file_name().to_string_lossy().to_string();
+        let mut entries = fs::read_dir(camera_entry.path()).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let Ok(meta) = entry.metadata().await else {
+                continue;
+            };
+            if !meta.is_file() {
+                continue;
+            }
+            files.push(StoredFile {
+                camera_id: camera_id.clone(),
+                path: entry.path(),
+                size: meta.len(),
+                modified: meta.modified().unwrap_or(std::time::SystemTime::UNIX_EPOCH),
+            });
+        }
+    }
+    Ok(files)
+}
+
+async fn remove(path: &Path) -> bool {
+    match fs::remove_file(path).await {
+        Ok(()) => true,
+        Err(e) => {
+            warn!(path = %path.display(), error = %e, "Failed to delete file during eviction");
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_list_store_files_groups_by_camera() {
+        let dir = std::env::temp_dir().join(format!("is22-storage-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(dir.join("cam-1")).await.unwrap();
+        fs::create_dir_all(dir.join("cam-2")).await.unwrap();
+        fs::write(dir.join("cam-1/a.jpg"), b"abcd").await.unwrap();
+        fs::write(dir.join("cam-1/b.jpg"), b"").await.unwrap();
+        fs::write(dir.join("cam-2/latest.jpg"), b"xy").await.unwrap();
+        // ストア直下のファイルはカメラに属さないため対象外
+        fs::write(dir.join("stray.jpg"), b"z").await.unwrap();
+
+        let mut files = list_store_files(&dir).await.unwrap();
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        let summary: Vec<_> = files
+            .iter()
+            .map(|f| (f.camera_id.as_str(), f.size))
+            .collect();
+        assert_eq!(summary, vec![("cam-1", 4), ("cam-1", 0), ("cam-2", 2)]);
+
+        assert!(list_store_files(&dir.join("missing")).await.unwrap().is_empty());
+        fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[test]
+    fn test_used_percent() {
+        assert_eq!(used_percent(0, 0), 0.0);
+        assert_eq!(used_percent(200, 50), 75.0);
+    }
+}
@@ -0,0 +1,323 @@
+//! Storage manager types
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// settings テーブルのキー
+pub const STORAGE_MANAGER_CONFIG_KEY: &str = "storage_manager";
+
+/// 画像ストア種別
+///
+/// いずれも `<dir>/<camera_id>/<file>` のレイアウト
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageStore {
+    /// SnapshotService の latest.jpg キャッシュ（snapshot_dir）
+    Snapshots,
+    /// SnapshotService の prev.jpg（temp_dir）
+    PrevImages,
+    /// PrevFrameCache の永続化ファイル
+    FrameCache,
+    /// DetectionLogService の検出画像（detection_logs.image_path_local）
+    EventImages,
+}
+
+impl StorageStore {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Snapshots => "snapshots",
+            Self::PrevImages => "prev_images",
+            Self::FrameCache => "frame_cache",
+            Self::EventImages => "event_images",
+        }
+    }
+
+    /// カメラごとの作業ファイル（登録外カメラの分は再生成可能なキャッシュ）
+    pub fn is_cache(&self) -> bool {
+        !matches!(self, Self::EventImages)
+    }
+}
+
+impl std::fmt::Display for StorageStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// ディスク逼迫度
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PressureLevel {
+    #[default]
+    Normal,
+    /// warn_percent 以上: 警告のみ
+    Warning,
+    /// high_watermark_percent 以上: 退避（削除）対象
+    Critical,
+}
+
+impl PressureLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Normal => "normal",
+            Self::Warning => "warning",
+            Self::Critical => "critical",
+        }
+    }
+}
+
+/// ストレージ管理設定（settings.storage_manager）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StorageManagerConfig {
+    pub enabled: bool,
+    /// 使用率がこれ以上で警告（RealtimeHub / Paraclate 不調報告）
+    pub warn_percent: f64,
+    /// 使用率がこれ以上で退避を開始
+    pub high_watermark_percent: f64,
+    /// 退避はこの使用率を下回るまで行う
+    pub low_watermark_percent: f64,
+    /// false の場合は警告のみ（削除しない）
+    pub auto_evict: bool,
+    /// 未登録カメラのキャッシュ（latest.jpg / prev.jpg）を先に削除する
+    pub remove_stale_caches: bool,
+}
+
+impl Default for StorageManagerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            warn_percent: 80.0,
+            high_watermark_percent: 90.0,
+            low_watermark_percent: 75.0,
+            auto_evict: true,
+            remove_stale_caches: true,
+        }
+    }
+}
+
+impl StorageManagerConfig {
+    pub fn validate(&self) -> crate::Result<()> {
+        if !(self.low_watermark_percent > 0.0
+            && self.low_watermark_percent < self.high_watermark_percent
+            && self.high_watermark_percent < 100.0)
+        {
+            return Err(crate::Error::Validation(
+                "watermarks must satisfy 0 < low_watermark_percent < high_watermark_percent < 100"
+                    .to_string(),
+            ));
+        }
+        if !(self.warn_percent > 0.0 && self.warn_percent <= self.high_watermark_percent) {
+            return Err(crate::Error::Validation(
+                "warn_percent must be within (0, high_watermark_percent]".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// 使用率から逼迫度を判定
+    pub fn pressure(&self, used_percent: f64) -> PressureLevel {
+        if used_percent >= self.high_watermark_percent {
+            PressureLevel::Critical
+        } else if used_percent >= self.warn_percent {
+            PressureLevel::Warning
+        } else {
+            PressureLevel::Normal
+        }
+    }
+
+    /// low watermark まで下げるのに必要な削除量（バイト）
+    pub fn bytes_to_free(&self, total: u64, available: u64) -> u64 {
+        let used = total.saturating_sub(available);
+        let target = (total as f64 * self.low_watermark_percent / 100.0) as u64;
+        used.saturating_sub(target)
+    }
+}
+
+/// カメラ別使用量
+#[derive(Debug, Clone, Serialize)]
+pub struct CameraUsage {
+    pub camera_id: String,
+    pub bytes: u64,
+    pub files: u64,
+}
+
+/// ストア別使用量
+#[derive(Debug, Clone, Serialize)]
+pub struct StoreUsage {
+    pub store: StorageStore,
+    pub path: PathBuf,
+    pub bytes: u64,
+    pub files: u64,
+    /// 使用量の大きい順
+    pub cameras: Vec<CameraUsage>,
+}
+
+/// ファイルシステム単位の使用率
+#[derive(Debug, Clone, Serialize)]
+pub struct DiskPressure {
+    pub mount_point: PathBuf,
+    pub total_bytes: u64,
+    pub available_bytes: u64,
+    pub used_percent: f64,
+    pub level: PressureLevel,
+    /// このファイルシステム上のストア
+    pub stores: Vec<StorageStore>,
+}
+
+/// 退避結果
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EvictionResult {
+    pub target_bytes: u64,
+    pub bytes_freed: u64,
+    /// 削除した検出画像（detection_logs.image_path_local は空にする）
+    pub event_images_deleted: u64,
+    /// 削除した未登録カメラのキャッシュファイル
+    pub cache_files_deleted: u64,
+    /// 削除した検出画像の最大 severity（高いほど保護が破られている）
+    pub max_severity_evicted: Option<i32>,
+}
+
+/// 使用量レポート（GET /api/storage/usage）
+#[derive(Debug, Clone, Serialize)]
+pub struct StorageReport {
+    pub checked_at: DateTime<Utc>,
+    pub level: PressureLevel,
+    pub disks: Vec<DiskPressure>,
+    pub stores: Vec<StoreUsage>,
+    pub last_eviction: Option<EvictionResult>,
+}
+
+/// DB 行と紐付いた検出画像
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageRef {
+    pub log_id: u64,
+    pub camera_id: String,
+    pub path: String,
+    pub severity: i32,
+}
+
+/// 退避候補のファイル
+#[derive(Debug, Clone)]
+pub struct EvictionCandidate {
+    pub path: PathBuf,
+    pub size: u64,
+    pub modified: std::time::SystemTime,
+    /// None = DB 行のない孤立ファイル
+    pub log_id: Option<u64>,
+    pub severity: Option<i32>,
+}
+
+impl EvictionCandidate {
+    /// 小さいほど先に削除（孤立ファイル → severity 昇順 → 古い順）
+    fn sort_key(&self) -> (i32, std::time::SystemTime) {
+        (self.severity.map(|s| s + 1).unwrap_or(0), self.modified)
+    }
+}
+
+/// `bytes_to_free` に達するまでの削除対象を優先度順に選ぶ
+pub fn plan_eviction(mut candidates: Vec<EvictionCandidate>, bytes_to_free: u64) -> Vec<EvictionCandidate> {
+    candidates.sort_by_key(|c| c.sort_key());
+    let mut planned = 0u64;
+    candidates
+        .into_iter()
+        .take_while(|c| {
+            let take = planned < bytes_to_free;
+            planned += c.size;
+            take
+        })
+        .collect()
+}
+
+/// DB 行のファイル欠損
+#[derive(Debug, Clone, Serialize)]
+pub struct MissingImage {
+    pub log_id: u64,
+    pub camera_id: String,
+    pub path: String,
+}
+
+/// 検出画像の整合性チェック結果
+#[derive(Debug, Clone, Serialize)]
+pub struct IntegrityReport {
+    pub checked_at: DateTime<Utc>,
+    pub camera_id: Option<String>,
+    pub rows_checked: usize,
+    pub files_checked: usize,
+    /// DB 行はあるがファイルがない
+    pub missing_files: Vec<MissingImage>,
+    /// ファイルはあるが DB 行がない
+    pub orphan_files: Vec<String>,
+    /// 0 バイトのファイル（書き込み途中でディスクが埋まった等）
+    pub empty_files: Vec<String>,
+}
+
+impl IntegrityReport {
+    pub fn is_clean(&self) -> bool {
+        self.missing_files.is_empty() && self.orphan_files.is_empty() && self.empty_files.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, SystemTime};
+
+    fn candidate(name: &str, size: u64, age_secs: u64, severity: Option<i32>) -> EvictionCandidate {
+        EvictionCandidate {
+            path: PathBuf::from(name),
+            size,
+            modified: SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000 - age_secs),
+            log_id: severity.map(|_| 1),
+            severity,
+        }
+    }
+
+    #[test]
+    fn test_pressure_levels_and_target() {
+        let config = StorageManagerConfig::default();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.pressure(50.0), PressureLevel::Normal);
+        assert_eq!(config.pressure(80.0), PressureLevel::Warning);
+        assert_eq!(config.pressure(95.0), PressureLevel::Critical);
+
+        // 100GB 中 95GB 使用 → 75GB まで下げる
+        let gb = 1024 * 1024 * 1024;
+        assert_eq!(config.bytes_to_free(100 * gb, 5 * gb), 20 * gb);
+        assert_eq!(config.bytes_to_free(100 * gb, 50 * gb), 0);
+    }
+
+    #[test]
+    fn test_config_validation() {
+        let inverted = StorageManagerConfig {
+            low_watermark_percent: 95.0,
+            ..Default::default()
+        };
+        assert!(inverted.validate().is_err());
+        let warn_above_high = StorageManagerConfig {
+            warn_percent: 92.0,
+            ..Default::default()
+        };
+        assert!(warn_above_high.validate().is_err());
+    }
+
+    #[test]
+    fn test_plan_eviction_keeps_high_severity_longest() {
+        let candidates = vec![
+            candidate("sev3_old.jpg", 10, 900, Some(3)),
+            candidate("sev0_new.jpg", 10, 10, Some(0)),
+            candidate("orphan.jpg", 10, 5, None),
+            candidate("sev0_old.jpg", 10, 500, Some(0)),
+            candidate("sev2.jpg", 10, 800, Some(2)),
+        ];
+        let planned: Vec<_> = plan_eviction(candidates.clone(), 25)
+            .into_iter()
+            .map(|c| c.path.to_string_lossy().to_string())
+            .collect();
+        assert_eq!(planned, vec!["orphan.jpg", "sev0_old.jpg", "sev0_new.jpg"]);
+
+        assert!(plan_eviction(candidates.clone(), 0).is_empty());
+        assert_eq!(plan_eviction(candidates, 1000).len(), 5);
+    }
+}
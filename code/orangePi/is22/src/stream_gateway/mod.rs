@@ -5,6 +5,11 @@
 //! - go2rtc API adapter
 //! - Stream URL management
 //! - Prewarm functionality
+//! - Reconciling go2rtc sources with ConfigStore cameras (reconciler)
+
+pub mod reconciler;
+
+pub use reconciler::{StreamReconciler, StreamVariant};
 
 use crate::error::{Error, Result};
 use serde::{Deserialize, Serialize};
@@ -0,0 +1,408 @@
+//! go2rtc stream reconciler
+//!
+//! ConfigStore のカメラを正として go2rtc のストリーム一覧を同期する（全ファミリー共通）。
+//!
+//! ## 管理するストリーム
+//! - `{camera_id}`: main（なければ sub）。get_stream_urls / ポーリング登録と同名
+//! - `{camera_id}_sub`: sub（main/sub 両方ある場合のみ。ライブウォール用）
+//! - `{camera_id}_rotated`: rotation が 90/180/270 のカメラ。ffmpeg で回転（sub 優先で負荷軽減）
+//!
+//! 無効・削除済みカメラのストリームは managed_prefix に一致するものだけ削除する
+//! （go2rtc.yaml に手動定義されたストリームは触らない）。
+
+use super::StreamGateway;
+use crate::config_store::{Camera, ConfigStore};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tokio_util::sync::CancellationToken;
+
+/// settings テーブルのキー
+pub const RECONCILER_CONFIG_KEY: &str = "go2rtc_reconciler";
+
+/// Reconciler settings (settings.go2rtc_reconciler)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReconcilerConfig {
+    pub enabled: bool,
+    pub interval_secs: u64,
+    pub register_sub_streams: bool,
+    pub register_rotated_streams: bool,
+    pub remove_orphans: bool,
+    /// 削除対象とみなすストリーム名の接頭辞
+    pub managed_prefix: String,
+}
+
+impl Default for ReconcilerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_secs: 300,
+            register_sub_streams: true,
+            register_rotated_streams: true,
+            remove_orphans: true,
+            managed_prefix: "cam-".to_string(),
+        }
+    }
+}
+
+impl ReconcilerConfig {
+    pub fn validate(&self) -> crate::Result<()> {
+        if self.interval_secs < 30 {
+            return Err(crate::Error::Validation(
+                "interval_secs must be >= 30".to_string(),
+            ));
+        }
+        // 空の接頭辞だと go2rtc 上の全ストリームが削除対象になる
+        if self.remove_orphans && self.managed_prefix.trim().is_empty() {
+            return Err(crate::Error::Validation(
+                "managed_prefix is required when remove_orphans is enabled".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Stream variant
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StreamVariant {
+    Main,
+    Sub,
+    Rotated,
+}
+
+/// go2rtc stream name for a camera variant
+pub fn stream_name(camera_id: &str, variant: StreamVariant) -> String {
+    match variant {
+        StreamVariant::Main => camera_id.to_string(),
+        StreamVariant::Sub => format!("{}_sub", camera_id),
+        StreamVariant::Rotated => format!("{}_rotated", camera_id),
+    }
+}
+
+/// Stream that should exist in go2rtc
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DesiredStream {
+    pub name: String,
+    pub camera_id: String,
+    pub variant: StreamVariant,
+    pub source: String,
+}
+
+/// Build the desired stream list from cameras
+pub fn desired_streams(cameras: &[Camera], config: &ReconcilerConfig) -> Vec<DesiredStream> {
+    let mut desired = Vec::new();
+    for camera in cameras.iter().filter(|c| c.enabled) {
+        let main = camera.rtsp_main.as_deref().filter(|u| !u.is_empty());
+        let sub = camera.rtsp_sub.as_deref().filter(|u| !u.is_empty());
+        let Some(primary) = main.or(sub) else {
+            continue;
+        };
+        let stream = |variant: StreamVariant, source: String| DesiredStream {
+            name: stream_name(&camera.camera_id, variant),
+            camera_id: camera.camera_id.clone(),
+            variant,
+            source,
+        };
+
+        desired.push(stream(StreamVariant::Main, primary.to_string()));
+
+        let has_sub_stream = config.register_sub_streams && main.is_some() && sub.is_some();
+        if let (true, Some(sub)) = (has_sub_stream, sub) {
+            desired.push(stream(StreamVariant::Sub, sub.to_string()));
+        }
+
+        let rotation = camera.rotation.rem_euclid(360);
+        if config.register_rotated_streams && matches!(rotation, 90 | 180 | 270) {
+            // 既存ストリームを入力にして go2rtc 内で再利用する
+            let input = if has_sub_stream {
+                stream_name(&camera.camera_id, StreamVariant::Sub)
+            } else {
+                camera.camera_id.clone()
+            };
+            desired.push(stream(
+                StreamVariant::Rotated,
+                format!("ffmpeg:{}#video=h264#rotate={}", input, rotation),
+            ));
+        }
+    }
+    desired
+}
+
+/// Parse GET /api/streams into name → first producer URL
+pub fn parse_go2rtc_streams(json: &serde_json::Value) -> BTreeMap<String, Option<String>> {
+    json.as_object()
+        .map(|streams| {
+            streams
+                .iter()
+                .map(|(name, stream)| {
+                    let url = stream
+                        .get("producers")
+                        .and_then(|p| p.as_array())
+                        .and_then(|p| p.first())
+                        .and_then(|p| p.get("url"))
+                        .and_then(|u| u.as_str())
+                        .map(str::to_string);
+                    (name.clone(), url)
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Changes required to match go2rtc to the desired state
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReconcilePlan {
+    pub add: Vec<DesiredStream>,
+    /// Source URL differs
+    pub update: Vec<DesiredStream>,
+    pub remove: Vec<String>,
+    pub unchanged: usize,
+}
+
+pub fn plan_reconcile(
+    desired: &[DesiredStream],
+    actual: &BTreeMap<String, Option<String>>,
+    config: &ReconcilerConfig,
+) -> ReconcilePlan {
+    let mut plan = ReconcilePlan::default();
+    for stream in desired {
+        match actual.get(&stream.name) {
+            None => plan.add.push(stream.clone()),
+            // producer URL が取れない場合は判断できないため触らない
+            Some(Some(url)) if url != &stream.source => plan.update.push(stream.clone()),
+            Some(_) => plan.unchanged += 1,
+        }
+    }
+
+    if config.remove_orphans {
+        let desired_names: HashSet<&str> = desired.iter().map(|s| s.name.as_str()).collect();
+        plan.remove = actual
+            .keys()
+            .filter(|name| name.starts_with(&config.managed_prefix) && !desired_names.contains(name.as_str()))
+            .cloned()
+            .collect();
+    }
+    plan
+}
+
+/// Result of a reconcile run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconcileReport {
+    pub ran_at: DateTime<Utc>,
+    pub desired: usize,
+    pub added: Vec<String>,
+    pub updated: Vec<String>,
+    pub removed: Vec<String>,
+    pub unchanged: usize,
+    pub errors: Vec<String>,
+}
+
+/// Periodic go2rtc reconciler
+pub struct StreamReconciler {
+    stream: Arc<StreamGateway>,
+    config_store: Arc<ConfigStore>,
+    config: RwLock<ReconcilerConfig>,
+    last_report: RwLock<Option<ReconcileReport>>,
+    /// 同時実行防止（定期実行と API 実行）
+    running: tokio::sync::Mutex<()>,
+}
+
+impl StreamReconciler {
+    pub fn new(stream: Arc<StreamGateway>, config_store: Arc<ConfigStore>) -> Self {
+        Self {
+            stream,
+            config_store,
+            config: RwLock::new(ReconcilerConfig::default()),
+            last_report: RwLock::new(None),
+            running: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// 設定をDBから読み込み（未設定ならデフォルト）
+    pub async fn load_config(&self) -> crate::Result<()> {
+        let stored = self
+            .config_store
+            .service()
+            .get_setting(RECONCILER_CONFIG_KEY)
+            .await?
+            .and_then(|v| serde_json::from_value(v).ok());
+        if let Some(config) = stored {
+            *self.config.write().await = config;
+        }
+        Ok(())
+    }
+
+    pub async fn config(&self) -> ReconcilerConfig {
+        self.config.read().await.clone()
+    }
+
+    /// 設定を保存
+    pub async fn set_config(&self, config: ReconcilerConfig) -> crate::Result<ReconcilerConfig> {
+        config.validate()?;
+        self.config_store
+            .service()
+            .set_setting(RECONCILER_CONFIG_KEY, serde_json::to_value(&config)?)
+            .await?;
+        *self.config.write().await = config.clone();
+        Ok(config)
+    }
+
+    pub async fn last_report(&self) -> Option<ReconcileReport> {
+        self.last_report.read().await.clone()
+    }
+
+    /// 定期同期ループ（`shutdown` がキャンセルされるまで。TaskSupervisor から起動）
+    pub async fn run(self: Arc<Self>, shutdown: CancellationToken) {
+        if let Err(e) = self.load_config().await {
+            tracing::warn!(error = %e, "go2rtc reconciler: failed to load config, using defaults");
+        }
+
+        loop {
+            let config = self.config().await;
+            if config.enabled {
+                match self.reconcile().await {
+                    Ok(report) if !report.added.is_empty()
+                        || !report.updated.is_empty()
+                        || !report.removed.is_empty()
+                        || !report.errors.is_empty() =>
+                    {
+                        tracing::info!(
+                            added = report.added.len(),
+                            updated = report.updated.len(),
+                            removed = report.removed.len(),
+                            errors = report.errors.len(),
+                            "go2rtc streams reconciled"
+                        );
+                    }
+                    Ok(_) => {}
+                    Err(e) => tracing::warn!(error = %e, "go2rtc reconcile failed"),
+                }
+            }
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = tokio::time::sleep(std::time::Duration::from_secs(config.interval_secs.max(30))) => {}
+            }
+        }
+    }
+
+    /// Desired state vs go2rtc の差分を計算（変更しない）
+    pub async fn plan(&self) -> crate::Result<ReconcilePlan> {
+        let config = self.config().await;
+        let cameras = self.config_store.get_cached_cameras().await;
+        let actual = parse_go2rtc_streams(&self.stream.list_streams().await?);
+        Ok(plan_reconcile(&desired_streams(&cameras, &config), &actual, &config))
+    }
+
+    /// go2rtc を ConfigStore に合わせる
+    pub async fn reconcile(&self) -> crate::Result<ReconcileReport> {
+        let _guard = self.running.lock().await;
+        let config = self.config().await;
+        let cameras = self.config_store.get_cached_cameras().await;
+        let desired = desired_streams(&cameras, &config);
+        let actual = parse_go2rtc_streams(&self.stream.list_streams().await?);
+        let plan = plan_reconcile(&desired, &actual, &config);
+
+        let mut report = ReconcileReport {
+            ran_at: Utc::now(),
+            desired: desired.len(),
+            added: Vec::new(),
+            updated: Vec::new(),
+            removed: Vec::new(),
+            unchanged: plan.unchanged,
+            errors: Vec::new(),
+        };
+
+        // 回転ストリームは入力ストリームに依存するため main/sub を先に登録する
+        let mut add = plan.add;
+        add.sort_by_key(|s| s.variant == StreamVariant::Rotated);
+        for stream in add {
+            match self.stream.add_source_with_retry(&stream.name, &stream.source, 1).await {
+                Ok(()) => report.added.push(stream.name),
+                Err(e) => report.errors.push(format!("{}: add failed: {}", stream.name, e)),
+            }
+        }
+
+        for stream in plan.update {
+            let result = match self.stream.remove_source(&stream.name).await {
+                Ok(()) => self.stream.add_source_with_retry(&stream.name, &stream.source, 1).await,
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(()) => report.updated.push(stream.name),
+                Err(e) => report.errors.push(format!("{}: update failed: {}", stream.name, e)),
+            }
+        }
+
+        for name in plan.remove {
+            match self.stream.remove_source(&name).await {
+                Ok(()) => report.removed.push(name),
+                Err(e) => report.errors.push(format!("{}: remove failed: {}", name, e)),
+            }
+        }
+
+        *self.last_report.write().await = Some(report.clone());
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn desired(name: &str, source: &str) -> DesiredStream {
+        DesiredStream {
+            name: name.to_string(),
+            camera_id: name.trim_end_matches("_sub").to_string(),
+            variant: if name.ends_with("_sub") { StreamVariant::Sub } else { StreamVariant::Main },
+            source: source.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_go2rtc_streams() {
+        let streams = parse_go2rtc_streams(&json!({
+            "cam-a": { "producers": [{ "url": "rtsp://a/1" }], "consumers": null },
+            "cam-b": { "producers": null }
+        }));
+        assert_eq!(streams.get("cam-a"), Some(&Some("rtsp://a/1".to_string())));
+        assert_eq!(streams.get("cam-b"), Some(&None));
+    }
+
+    #[test]
+    fn test_plan_reconcile() {
+        let desired = vec![
+            desired("cam-a", "rtsp://a/1"),
+            desired("cam-a_sub", "rtsp://a/2"),
+            desired("cam-b", "rtsp://b/1"),
+            desired("cam-c", "rtsp://c/1"),
+        ];
+        let actual: BTreeMap<String, Option<String>> = [
+            ("cam-a", Some("rtsp://a/1")),
+            ("cam-b", Some("rtsp://b/old")),
+            ("cam-c", None),
+            ("cam-deleted", Some("rtsp://x/1")),
+            ("manual_stream", Some("rtsp://m/1")),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.map(str::to_string)))
+        .collect();
+
+        let plan = plan_reconcile(&desired, &actual, &ReconcilerConfig::default());
+        let names = |s: &[DesiredStream]| s.iter().map(|d| d.name.clone()).collect::<Vec<_>>();
+        assert_eq!(names(&plan.add), vec!["cam-a_sub"]);
+        assert_eq!(names(&plan.update), vec!["cam-b"]);
+        assert_eq!(plan.remove, vec!["cam-deleted"]);
+        assert_eq!(plan.unchanged, 2);
+
+        let keep = ReconcilerConfig {
+            remove_orphans: false,
+            ..Default::default()
+        };
+        assert!(plan_reconcile(&desired, &actual, &keep).remove.is_empty());
+    }
+}
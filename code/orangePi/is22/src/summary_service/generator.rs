@@ -6,13 +6,15 @@
 //! ## 処理フロー
 //! 1. 期間内の検出ログを取得
 //! 2. カメラごとに集計
-//! 3. summary_text生成（テンプレートベース）
+//! 3. summary_text生成（fid・locale別テンプレート、SummaryTextBuilder）
 //! 4. summary_json構築（Paraclate送信用）
 //! 5. DB保存
 //! 6. summaryID更新（DB正本値）
 
 use super::payload_builder::{calculate_detect_times, PayloadBuilder};
 use super::repository::SummaryRepository;
+use super::template::{SummaryFacts, TemplateKind};
+use super::text_builder::SummaryTextBuilder;
 use super::types::{CameraStats, SummaryInsert, SummaryResult, SummaryType};
 use crate::camera_registry::CameraContextService;
 use crate::config_store::ConfigStore;
@@ -32,6 +34,8 @@ pub struct SummaryGenerator {
     repository: SummaryRepository,
     /// 設定ストア
     config_store: Arc<ConfigStore>,
+    /// summary_textテンプレート展開
+    text_builder: Arc<SummaryTextBuilder>,
 }
 
 impl SummaryGenerator {
@@ -41,12 +45,14 @@ impl SummaryGenerator {
         camera_context_service: CameraContextService,
         repository: SummaryRepository,
         config_store: Arc<ConfigStore>,
+        text_builder: Arc<SummaryTextBuilder>,
     ) -> Self {
         Self {
             detection_log_service,
             camera_context_service,
             repository,
             config_store,
+            text_builder,
         }
     }
 
@@ -105,13 +111,9 @@ impl SummaryGenerator {
         // 4. 最初/最後の検出時刻を算出
         let (first_detect, last_detect) = calculate_detect_times(&logs);
 
-        // 5. summary_text生成
-        let summary_text = self.generate_summary_text(
-            &logs,
-            &camera_stats,
-            period_start,
-            period_end,
-        );
+        // 5. summary_text生成（テンプレート）
+        let facts = SummaryFacts::from_logs(fid, period_start, period_end, &logs, &context_map);
+        let summary_text = self.text_builder.render(TemplateKind::Summary, &facts).await;
 
         // 6. PayloadBuilder取得とsummary_json構築
         let payload_builder = self.get_payload_builder().await?;
@@ -169,11 +171,8 @@ impl SummaryGenerator {
         period_start: DateTime<Utc>,
         period_end: DateTime<Utc>,
     ) -> crate::Result<SummaryResult> {
-        let summary_text = format!(
-            "{}から{}の期間: 検出イベントなし",
-            period_start.format("%Y-%m-%d %H:%M"),
-            period_end.format("%Y-%m-%d %H:%M")
-        );
+        let facts = SummaryFacts::empty(fid, period_start, period_end);
+        let summary_text = self.text_builder.render(TemplateKind::Summary, &facts).await;
 
         let result = self
             .repository
@@ -200,58 +199,6 @@ impl SummaryGenerator {
         Ok(result)
     }
 
-    /// テンプレートベースのサマリーテキスト生成
-    fn generate_summary_text(
-        &self,
-        logs: &[crate::detection_log_service::DetectionLog],
-        camera_stats: &HashMap<String, CameraStats>,
-        period_start: DateTime<Utc>,
-        period_end: DateTime<Utc>,
-    ) -> String {
-        let duration_hours = (period_end - period_start).num_hours();
-        let duration_str = if duration_hours >= 1 {
-            format!("{}時間", duration_hours)
-        } else {
-            let minutes = (period_end - period_start).num_minutes();
-            format!("{}分", minutes)
-        };
-
-        let total_count = logs.len();
-        let camera_count = camera_stats.len();
-
-        let camera_breakdown = self.format_camera_breakdown(camera_stats);
-
-        format!(
-            "{}の検出サマリー: 合計{}件の検出（{}台のカメラ）\n{}",
-            duration_str,
-            total_count,
-            camera_count,
-            camera_breakdown
-        )
-    }
-
-    /// カメラ別の検出内訳をフォーマット
-    fn format_camera_breakdown(&self, stats: &HashMap<String, CameraStats>) -> String {
-        let mut lines: Vec<String> = stats
-            .iter()
-            .map(|(id, s)| {
-                // LacisIDの場合は短縮表示
-                let display_id = if id.len() > 10 {
-                    format!("{}...", &id[..10])
-                } else {
-                    id.clone()
-                };
-                format!(
-                    "- {}: {}件 (最大severity: {})",
-                    display_id, s.detection_count, s.severity_max
-                )
-            })
-            .collect();
-
-        lines.sort();
-        lines.join("\n")
-    }
-
     /// PayloadBuilderを取得（設定値からis22の登録情報を取得）
     async fn get_payload_builder(&self) -> crate::Result<PayloadBuilder> {
         let is22_lacis_id = self
@@ -334,20 +281,3 @@ impl SummaryGenerator {
         Ok(result)
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_format_camera_breakdown_empty() {
-        let generator = SummaryGenerator {
-            detection_log_service: todo!(),
-            camera_context_service: todo!(),
-            repository: todo!(),
-            config_store: todo!(),
-        };
-
-        // このテストはモック化が必要なため、実際のテストは統合テストで行う
-    }
-}
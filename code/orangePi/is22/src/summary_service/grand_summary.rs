@@ -17,21 +17,49 @@
 //! - scheduled_times（09:00等）はAsia/Tokyo（JST）基準
 
 use super::repository::SummaryRepository;
+use super::template::{SummaryFacts, TemplateKind};
+use super::text_builder::SummaryTextBuilder;
 use super::types::{SummaryInsert, SummaryResult, SummaryType};
 use chrono::{DateTime, Duration, NaiveDate, NaiveTime, TimeZone, Utc};
 use chrono_tz::Asia::Tokyo;
 use std::collections::HashSet;
+use std::sync::Arc;
 use tracing::{debug, info, warn};
 
 /// GrandSummary生成サービス
 pub struct GrandSummaryGenerator {
     repository: SummaryRepository,
+    text_builder: Arc<SummaryTextBuilder>,
 }
 
 impl GrandSummaryGenerator {
     /// 新しいGrandSummaryGeneratorを作成
-    pub fn new(repository: SummaryRepository) -> Self {
-        Self { repository }
+    pub fn new(repository: SummaryRepository, text_builder: Arc<SummaryTextBuilder>) -> Self {
+        Self {
+            repository,
+            text_builder,
+        }
+    }
+
+    /// シフト期間の集計（取得失敗時は検出なしとして扱う）
+    async fn collect_facts(
+        &self,
+        tid: &str,
+        fid: &str,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+    ) -> SummaryFacts {
+        match self
+            .text_builder
+            .collect_facts(tid, fid, period_start, period_end)
+            .await
+        {
+            Ok(facts) => facts,
+            Err(e) => {
+                warn!(error = %e, "Failed to collect GrandSummary facts");
+                SummaryFacts::empty(fid, period_start, period_end)
+            }
+        }
     }
 
     /// GrandSummary生成（シフト単位でSummaryを統合）
@@ -93,15 +121,16 @@ impl GrandSummaryGenerator {
             included_summary_ids.push(summary.summary_id);
         }
 
-        // 3. 統合summary_text生成（シフト単位）
-        let summary_text = format!(
-            "シフトサマリー（{} 〜 {}）: 合計{}件の検出（{}台のカメラ）\n{}個のhourlyサマリーを統合",
-            start_jst.format("%m/%d %H:%M"),
-            end_jst.format("%H:%M"),
-            total_detection_count,
-            all_camera_ids.len(),
-            hourly_summaries.len()
-        );
+        // 3. 統合summary_text生成（シフト単位・テンプレート）
+        // 件数・severityはhourly Summaryの合計値に揃える
+        let mut facts = self.collect_facts(tid, fid, period_start, period_end).await;
+        facts.summary_count = hourly_summaries.len();
+        facts.total_detections = total_detection_count as i64;
+        facts.severity_max = severity_max;
+        let summary_text = self
+            .text_builder
+            .render(TemplateKind::GrandSummary, &facts)
+            .await;
 
         // 4. summary_json生成
         let summary_json = serde_json::json!({
@@ -157,11 +186,13 @@ impl GrandSummaryGenerator {
         let start_jst = period_start.with_timezone(&Tokyo);
         let end_jst = period_end.with_timezone(&Tokyo);
 
-        let summary_text = format!(
-            "シフトサマリー（{} 〜 {}）: 検出イベントなし",
-            start_jst.format("%m/%d %H:%M"),
-            end_jst.format("%H:%M")
-        );
+        // hourly Summaryなし = 検出0件として扱う（オフライン情報のみ反映）
+        let mut facts = self.collect_facts(tid, fid, period_start, period_end).await;
+        facts.total_detections = 0;
+        let summary_text = self
+            .text_builder
+            .render(TemplateKind::GrandSummary, &facts)
+            .await;
 
         let result = self
             .repository
@@ -285,6 +316,7 @@ pub fn calculate_next_grand_summary_time(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::{Datelike, Timelike};
 
     #[test]
     fn test_calculate_next_time_today() {
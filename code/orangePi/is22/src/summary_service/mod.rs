@@ -13,14 +13,19 @@
 //! | Summary | 時間間隔経過 | 60分 | 期間内の検出イベント要約 |
 //! | GrandSummary | 指定時刻到達 | 09:00, 17:00, 21:00 | 複数Summaryの統合・1日の総括 |
 //! | Emergency | 異常検出時 | severity >= 閾値 | 即時報告 |
+//! | Weekly / Monthly | 月曜 / 1日 06:00 | 直前に完了した週・月 | ISMSレビュー用ロールアップ |
 //!
 //! ## モジュール構成
 //! - `types`: 型定義・定数
 //! - `repository`: DB永続化
 //! - `generator`: Summary生成ロジック
 //! - `grand_summary`: GrandSummary生成ロジック
+//! - `rollup`: 週次・月次ロールアップ（前期間比・稼働率・過剰検出・attunement）
 //! - `scheduler`: 定時実行スケジューラ
 //! - `payload_builder`: Paraclate送信用ペイロード構築
+//! - `template`: summary_textテンプレート（構文・ローカライズ・組み込みデフォルト）
+//! - `text_builder`: テンプレート選択・展開・プレビュー
+//! - `report`: 印刷用HTML/PDFレポート（SVGチャート・サムネイル・稼働率）
 //!
 //! ## 使用例
 //! ```rust,ignore
@@ -29,21 +34,32 @@
 //! // Summary手動生成
 //! let result = generator.generate(tid, fid, period_start, period_end).await?;
 //!
-//! // スケジューラ起動
-//! scheduler.start().await;
+//! // スケジューラ起動（TaskSupervisor 管理下）
+//! supervisor.spawn("summary_scheduler", move |shutdown| scheduler.clone().run(shutdown));
 //! ```
 
 pub mod generator;
 pub mod grand_summary;
 pub mod payload_builder;
+pub mod report;
 pub mod repository;
+pub mod rollup;
 pub mod scheduler;
+pub mod template;
+pub mod text_builder;
 pub mod types;
 
 // Re-exports
 pub use generator::SummaryGenerator;
 pub use grand_summary::GrandSummaryGenerator;
 pub use payload_builder::PayloadBuilder;
-pub use repository::{ScheduleRepository, SummaryRepository};
+pub use report::{ReportConfig, ReportFormat, ReportService};
+pub use repository::{ScheduleRepository, SummaryRepository, TemplateRepository};
+pub use rollup::RollupGenerator;
 pub use scheduler::SummaryScheduler;
+pub use template::{
+    SummaryFacts, SummaryLocale, SummaryLocaleSettings, SummaryTemplate, TemplateKind,
+    UpsertSummaryTemplateRequest,
+};
+pub use text_builder::{SummaryPreview, SummaryTextBuilder};
 pub use types::*;
@@ -0,0 +1,150 @@
+//! SVG charts for reports
+//!
+//! 外部ライブラリなしで埋め込みSVGを生成する（印刷・PDF変換で崩れない固定サイズ）
+
+use super::html::escape;
+
+/// Vertical bar chart (e.g. detections per hour)
+pub fn bar_chart(values: &[(String, i64)], width: u32, height: u32, color: &str) -> String {
+    let margin_left = 36.0;
+    let margin_bottom = 22.0;
+    let margin_top = 8.0;
+    let plot_w = width as f64 - margin_left - 8.0;
+    let plot_h = height as f64 - margin_bottom - margin_top;
+    let max = values.iter().map(|(_, v)| *v).max().unwrap_or(0).max(1) as f64;
+
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="sans-serif" font-size="10">"#,
+        w = width,
+        h = height
+    );
+    // 軸
+    svg.push_str(&format!(
+        r##"<line x1="{x}" y1="{t}" x2="{x}" y2="{b}" stroke="#999"/><line x1="{x}" y1="{b}" x2="{r}" y2="{b}" stroke="#999"/>"##,
+        x = margin_left,
+        t = margin_top,
+        b = margin_top + plot_h,
+        r = margin_left + plot_w
+    ));
+    svg.push_str(&format!(
+        r##"<text x="{}" y="{}" text-anchor="end" fill="#666">{}</text>"##,
+        margin_left - 4.0,
+        margin_top + 8.0,
+        max as i64
+    ));
+
+    if !values.is_empty() {
+        let slot = plot_w / values.len() as f64;
+        let bar_w = (slot * 0.7).max(1.0);
+        // ラベルが重ならないよう間引く
+        let label_every = ((values.len() as f64 * 28.0) / plot_w).ceil().max(1.0) as usize;
+
+        for (i, (label, value)) in values.iter().enumerate() {
+            let bar_h = plot_h * (*value as f64) / max;
+            let x = margin_left + slot * i as f64 + (slot - bar_w) / 2.0;
+            let y = margin_top + plot_h - bar_h;
+            svg.push_str(&format!(
+                r#"<rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" fill="{}"><title>{}: {}</title></rect>"#,
+                x,
+                y,
+                bar_w,
+                bar_h,
+                escape(color),
+                escape(label),
+                value
+            ));
+            if i % label_every == 0 {
+                svg.push_str(&format!(
+                    r##"<text x="{:.1}" y="{:.1}" text-anchor="middle" fill="#333">{}</text>"##,
+                    x + bar_w / 2.0,
+                    margin_top + plot_h + 14.0,
+                    escape(label)
+                ));
+            }
+        }
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+/// Horizontal bar chart (e.g. detections per camera)
+pub fn horizontal_bar_chart(values: &[(String, i64)], width: u32, color: &str) -> String {
+    let row_h = 18.0;
+    let label_w = 160.0;
+    let height = (values.len().max(1) as f64 * row_h + 8.0) as u32;
+    let plot_w = width as f64 - label_w - 48.0;
+    let max = values.iter().map(|(_, v)| *v).max().unwrap_or(0).max(1) as f64;
+
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="sans-serif" font-size="10">"#,
+        w = width,
+        h = height
+    );
+    for (i, (label, value)) in values.iter().enumerate() {
+        let y = 4.0 + row_h * i as f64;
+        let bar_w = plot_w * (*value as f64) / max;
+        // 長い名前は末尾を省略（全文はtitleに保持）
+        let short: String = if label.chars().count() > 24 {
+            format!("{}…", label.chars().take(23).collect::<String>())
+        } else {
+            label.clone()
+        };
+        svg.push_str(&format!(
+            r##"<text x="{:.1}" y="{:.1}" text-anchor="end" fill="#333"><title>{}</title>{}</text>"##,
+            label_w - 6.0,
+            y + 12.0,
+            escape(label),
+            escape(&short)
+        ));
+        svg.push_str(&format!(
+            r#"<rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" fill="{}"/>"#,
+            label_w,
+            y + 2.0,
+            bar_w,
+            row_h - 6.0,
+            escape(color)
+        ));
+        svg.push_str(&format!(
+            r##"<text x="{:.1}" y="{:.1}" fill="#333">{}</text>"##,
+            label_w + bar_w + 4.0,
+            y + 12.0,
+            value
+        ));
+    }
+    svg.push_str("</svg>");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bar_chart_scales_and_escapes() {
+        let values = vec![
+            ("09".to_string(), 10),
+            ("10".to_string(), 5),
+            ("<b>".to_string(), 0),
+        ];
+        let svg = bar_chart(&values, 300, 120, "#3b82f6");
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>"));
+        assert_eq!(svg.matches("<rect").count(), 3);
+        assert!(svg.contains("&lt;b&gt;"));
+        assert!(!svg.contains("<b>"));
+
+        // Empty data still renders valid axes
+        let empty = bar_chart(&[], 300, 120, "#000");
+        assert!(empty.contains("<line"));
+        assert!(!empty.contains("<rect"));
+    }
+
+    #[test]
+    fn test_horizontal_bar_chart_truncates_labels() {
+        let long = "エントランス北側駐車場ゲート前カメラ（屋外・夜間赤外線）".to_string();
+        let svg = horizontal_bar_chart(&[(long.clone(), 3)], 400, "#10b981");
+        assert!(svg.contains(&format!("<title>{}</title>", long)));
+        assert!(svg.contains('…'));
+    }
+}
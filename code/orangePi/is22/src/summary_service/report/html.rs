@@ -0,0 +1,295 @@
+//! HTML report renderer
+//!
+//! 印刷向けの単一HTMLを生成する（CSS・SVG・画像はすべて埋め込み、外部参照なし）
+
+use super::chart::{bar_chart, horizontal_bar_chart};
+use super::ReportData;
+use crate::summary_service::template::{event_label, SummaryLocale, TemplateKind};
+use crate::summary_service::types::SummaryType;
+use chrono::{DateTime, Utc};
+use chrono_tz::Asia::Tokyo;
+
+/// HTML特殊文字のエスケープ
+pub fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// 表示ラベル（locale別）
+struct Labels {
+    summary_title: &'static str,
+    grand_title: &'static str,
+    weekly_title: &'static str,
+    monthly_title: &'static str,
+    facility: &'static str,
+    period: &'static str,
+    generated: &'static str,
+    detections: &'static str,
+    cameras: &'static str,
+    severity_max: &'static str,
+    offline: &'static str,
+    hourly_summaries: &'static str,
+    overview: &'static str,
+    trend: &'static str,
+    per_camera: &'static str,
+    availability: &'static str,
+    camera: &'static str,
+    location: &'static str,
+    lost_events: &'static str,
+    downtime: &'static str,
+    uptime: &'static str,
+    status: &'static str,
+    online: &'static str,
+    offline_now: &'static str,
+    notable: &'static str,
+    no_image: &'static str,
+    none: &'static str,
+    minutes: &'static str,
+}
+
+impl Labels {
+    fn for_locale(locale: SummaryLocale) -> Self {
+        match locale {
+            SummaryLocale::Ja => Self {
+                summary_title: "検出サマリーレポート",
+                grand_title: "シフトレポート",
+                weekly_title: "週次レポート",
+                monthly_title: "月次レポート",
+                facility: "施設",
+                period: "対象期間",
+                generated: "作成日時",
+                detections: "検出数",
+                cameras: "検出カメラ数",
+                severity_max: "最大severity",
+                offline: "オフライン",
+                hourly_summaries: "統合サマリー数",
+                overview: "概要",
+                trend: "時間帯別の検出数",
+                per_camera: "カメラ別の検出数",
+                availability: "カメラ稼働状況",
+                camera: "カメラ",
+                location: "設置場所",
+                lost_events: "切断回数",
+                downtime: "停止時間",
+                uptime: "稼働率",
+                status: "終了時点",
+                online: "オンライン",
+                offline_now: "オフライン",
+                notable: "注目すべき検出",
+                no_image: "画像なし",
+                none: "該当なし",
+                minutes: "分",
+            },
+            SummaryLocale::En => Self {
+                summary_title: "Detection Summary Report",
+                grand_title: "Shift Report",
+                weekly_title: "Weekly Report",
+                monthly_title: "Monthly Report",
+                facility: "Facility",
+                period: "Period",
+                generated: "Generated",
+                detections: "Detections",
+                cameras: "Active cameras",
+                severity_max: "Max severity",
+                offline: "Offline",
+                hourly_summaries: "Hourly summaries",
+                overview: "Overview",
+                trend: "Detections by hour",
+                per_camera: "Detections by camera",
+                availability: "Camera availability",
+                camera: "Camera",
+                location: "Location",
+                lost_events: "Disconnects",
+                downtime: "Downtime",
+                uptime: "Availability",
+                status: "At period end",
+                online: "Online",
+                offline_now: "Offline",
+                notable: "Notable detections",
+                no_image: "No image",
+                none: "None",
+                minutes: "min",
+            },
+        }
+    }
+}
+
+const STYLE: &str = r#"
+@page { size: A4; margin: 12mm; }
+body { font-family: "Noto Sans CJK JP", "Hiragino Sans", sans-serif; font-size: 11px; color: #222; margin: 0; }
+h1 { font-size: 20px; margin: 0 0 4px; }
+h2 { font-size: 14px; border-bottom: 2px solid #3b82f6; padding-bottom: 2px; margin: 18px 0 8px; }
+.meta { color: #555; margin-bottom: 12px; }
+.stats { display: flex; gap: 8px; }
+.stat { flex: 1; border: 1px solid #ddd; border-radius: 4px; padding: 6px 8px; }
+.stat .value { font-size: 20px; font-weight: bold; }
+.stat .label { color: #666; }
+.text { white-space: pre-wrap; background: #f8fafc; border: 1px solid #e2e8f0; padding: 8px; }
+table { border-collapse: collapse; width: 100%; }
+th, td { border: 1px solid #ddd; padding: 3px 6px; text-align: left; }
+th { background: #f1f5f9; }
+td.num { text-align: right; }
+tr.warn td { background: #fef2f2; }
+.grid { display: flex; flex-wrap: wrap; gap: 8px; }
+.thumb { width: 31%; border: 1px solid #ddd; page-break-inside: avoid; }
+.thumb img { width: 100%; display: block; }
+.thumb .noimg { height: 90px; background: #eee; color: #888; text-align: center; line-height: 90px; }
+.thumb .caption { padding: 3px 5px; }
+.sev { font-weight: bold; color: #b91c1c; }
+"#;
+
+fn format_jst(at: DateTime<Utc>) -> String {
+    at.with_timezone(&Tokyo).format("%Y-%m-%d %H:%M").to_string()
+}
+
+/// ReportDataからHTML文書を生成
+pub fn render_html(data: &ReportData) -> String {
+    let l = Labels::for_locale(data.locale);
+    let title = match (data.summary.summary_type, data.kind) {
+        (SummaryType::Weekly, _) => l.weekly_title,
+        (SummaryType::Monthly, _) => l.monthly_title,
+        (_, TemplateKind::Summary) => l.summary_title,
+        (_, TemplateKind::GrandSummary) => l.grand_title,
+    };
+    let lang = data.locale.as_str();
+
+    let mut html = String::with_capacity(64 * 1024);
+    html.push_str(&format!(
+        r#"<!DOCTYPE html><html lang="{}"><head><meta charset="utf-8"><title>{} - {}</title><style>{}</style></head><body>"#,
+        lang,
+        escape(title),
+        escape(&data.summary.fid),
+        STYLE
+    ));
+
+    // Header
+    html.push_str(&format!(
+        r#"<h1>{}</h1><div class="meta">{}: {} / {}: {} 〜 {} (JST) / {}: {}</div>"#,
+        escape(title),
+        l.facility,
+        escape(&data.summary.fid),
+        l.period,
+        format_jst(data.summary.period_start),
+        format_jst(data.summary.period_end),
+        l.generated,
+        format_jst(data.generated_at)
+    ));
+
+    // Key stats
+    let facts = &data.facts;
+    html.push_str(r#"<div class="stats">"#);
+    let mut stats = vec![
+        (l.detections, facts.total_detections.to_string()),
+        (l.cameras, facts.cameras.len().to_string()),
+        (l.severity_max, facts.severity_max.to_string()),
+        (l.offline, facts.offline_cameras.len().to_string()),
+    ];
+    if data.kind == TemplateKind::GrandSummary {
+        stats.push((l.hourly_summaries, facts.summary_count.to_string()));
+    }
+    for (label, value) in stats {
+        html.push_str(&format!(
+            r#"<div class="stat"><div class="value">{}</div><div class="label">{}</div></div>"#,
+            escape(&value),
+            label
+        ));
+    }
+    html.push_str("</div>");
+
+    // Summary text
+    html.push_str(&format!(
+        r#"<h2>{}</h2><div class="text">{}</div>"#,
+        l.overview,
+        escape(&data.summary.summary_text)
+    ));
+
+    // Charts
+    html.push_str(&format!("<h2>{}</h2>", l.trend));
+    html.push_str(&bar_chart(&data.hourly_trend, 680, 160, "#3b82f6"));
+
+    if !facts.cameras.is_empty() {
+        let per_camera: Vec<(String, i64)> = facts
+            .cameras
+            .iter()
+            .take(15)
+            .map(|c| (c.name.clone(), c.detection_count))
+            .collect();
+        html.push_str(&format!("<h2>{}</h2>", l.per_camera));
+        html.push_str(&horizontal_bar_chart(&per_camera, 680, "#10b981"));
+    }
+
+    // Availability table
+    html.push_str(&format!("<h2>{}</h2>", l.availability));
+    if data.availability.is_empty() {
+        html.push_str(&format!("<p>{}</p>", l.none));
+    } else {
+        html.push_str(&format!(
+            "<table><thead><tr><th>{}</th><th>{}</th><th>{}</th><th>{}</th><th>{}</th><th>{}</th></tr></thead><tbody>",
+            l.camera, l.location, l.lost_events, l.downtime, l.uptime, l.status
+        ));
+        for row in &data.availability {
+            html.push_str(&format!(
+                r#"<tr{}><td>{}</td><td>{}</td><td class="num">{}</td><td class="num">{} {}</td><td class="num">{:.1}%</td><td>{}</td></tr>"#,
+                if row.online_at_end { "" } else { r#" class="warn""# },
+                escape(&row.name),
+                escape(row.location.as_deref().unwrap_or("-")),
+                row.lost_events,
+                row.downtime_minutes,
+                l.minutes,
+                row.availability * 100.0,
+                if row.online_at_end { l.online } else { l.offline_now }
+            ));
+        }
+        html.push_str("</tbody></table>");
+    }
+
+    // Thumbnail grid
+    html.push_str(&format!("<h2>{}</h2>", l.notable));
+    if data.thumbnails.is_empty() {
+        html.push_str(&format!("<p>{}</p>", l.none));
+    } else {
+        html.push_str(r#"<div class="grid">"#);
+        for thumb in &data.thumbnails {
+            let image = match &thumb.data_uri {
+                Some(uri) => format!(r#"<img src="{}" alt="">"#, escape(uri)),
+                None => format!(r#"<div class="noimg">{}</div>"#, l.no_image),
+            };
+            html.push_str(&format!(
+                r#"<div class="thumb">{}<div class="caption">{}<br>{} / {} <span class="sev">sev {}</span></div></div>"#,
+                image,
+                format_jst(thumb.captured_at),
+                escape(&thumb.camera_name),
+                escape(&event_label(&thumb.event, data.locale)),
+                thumb.severity
+            ));
+        }
+        html.push_str("</div>");
+    }
+
+    html.push_str("</body></html>");
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(
+            escape(r#"<a href="x">'&'</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;&#39;&amp;&#39;&lt;/a&gt;"
+        );
+        assert_eq!(escape("カメラ1"), "カメラ1");
+    }
+}
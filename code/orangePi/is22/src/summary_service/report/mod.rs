@@ -0,0 +1,648 @@
+//! Summary Report Rendering
+//!
+//! 施設管理者向けの印刷可能なSummary/GrandSummaryレポート（HTML/PDF）
+//!
+//! ## 構成
+//! - 主要統計（検出数・カメラ数・最大severity・オフライン数）
+//! - summary_text（テンプレート展開済み）
+//! - 時間帯別・カメラ別の検出推移（埋め込みSVG）
+//! - カメラ稼働率テーブル（CameraAvailabilityService の稼働履歴から算出）
+//! - 高severity検出のサムネイルグリッド（画像はdata URIで埋め込み）
+//!
+//! ## PDF
+//! HTMLを外部コマンド（既定: `wkhtmltopdf`、AppConfig の `report.pdf_command` で変更可）で変換する。
+//!
+//! ## 保存
+//! スケジュール実行時に `report_format` が設定されていれば
+//! `report.report_dir/{fid}/report_{fid}_{summary_id}.{ext}` に保存し、
+//! ダウンロード時は保存済みファイルを優先する（検出ログの保持期間切れ対策）。
+
+pub mod chart;
+pub mod html;
+pub mod pdf;
+
+use super::repository::SummaryRepository;
+use super::template::{SummaryFacts, SummaryLocale, TemplateKind, NOTABLE_SEVERITY};
+use super::text_builder::SummaryTextBuilder;
+use super::types::{SummaryResult, SummaryType};
+use crate::camera_availability::{build_report, AvailabilityInterval, AvailabilityState, CameraAvailabilityService};
+use crate::camera_registry::{CameraContextService, ContextMapEntry};
+use crate::detection_log_service::{DetectionLog, DetectionLogService};
+use base64::Engine;
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use chrono_tz::Asia::Tokyo;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+
+/// サムネイル1枚あたりの最大サイズ（超過分は埋め込まない）
+const MAX_THUMBNAIL_BYTES: u64 = 2 * 1024 * 1024;
+
+/// Report output format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    Html,
+    Pdf,
+}
+
+impl ReportFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Html => "html",
+            Self::Pdf => "pdf",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "html" => Some(Self::Html),
+            "pdf" => Some(Self::Pdf),
+            _ => None,
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Html => "text/html; charset=utf-8",
+            Self::Pdf => "application/pdf",
+        }
+    }
+}
+
+/// Report configuration (AppConfig `[report]` から構築)
+#[derive(Debug, Clone)]
+pub struct ReportConfig {
+    /// 保存先ディレクトリ（report.report_dir）
+    pub report_dir: PathBuf,
+    /// HTML→PDF変換コマンド（report.pdf_command）
+    pub pdf_command: String,
+    /// PDF変換タイムアウト
+    pub pdf_timeout: std::time::Duration,
+    /// サムネイル最大枚数
+    pub max_thumbnails: usize,
+}
+
+impl From<&crate::app_config::ReportSettings> for ReportConfig {
+    fn from(settings: &crate::app_config::ReportSettings) -> Self {
+        Self {
+            report_dir: settings.report_dir.clone(),
+            pdf_command: settings.pdf_command.clone(),
+            pdf_timeout: std::time::Duration::from_secs(settings.pdf_timeout_secs),
+            max_thumbnails: 12,
+        }
+    }
+}
+
+impl Default for ReportConfig {
+    fn default() -> Self {
+        Self::from(&crate::app_config::ReportSettings::default())
+    }
+}
+
+/// カメラ稼働率（レポート期間内）
+#[derive(Debug, Clone, Serialize)]
+pub struct CameraAvailability {
+    pub camera_id: String,
+    pub name: String,
+    pub location: Option<String>,
+    pub lost_events: i64,
+    pub downtime_minutes: i64,
+    /// 0.0-1.0
+    pub availability: f64,
+    /// 期間終了時点でオンラインか
+    pub online_at_end: bool,
+}
+
+/// 高severity検出のサムネイル
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportThumbnail {
+    pub captured_at: DateTime<Utc>,
+    pub camera_name: String,
+    pub event: String,
+    pub severity: i32,
+    /// data URI（画像が読めない場合はNone）
+    pub data_uri: Option<String>,
+}
+
+/// レポート描画用データ
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportData {
+    pub locale: SummaryLocale,
+    pub kind: TemplateKind,
+    pub summary: SummaryResult,
+    pub facts: SummaryFacts,
+    /// (JSTラベル, 検出数)
+    pub hourly_trend: Vec<(String, i64)>,
+    pub availability: Vec<CameraAvailability>,
+    pub thumbnails: Vec<ReportThumbnail>,
+    pub generated_at: DateTime<Utc>,
+}
+
+/// 描画済みレポート
+#[derive(Debug, Clone)]
+pub struct RenderedReport {
+    pub format: ReportFormat,
+    pub filename: String,
+    pub bytes: Vec<u8>,
+}
+
+/// 稼働履歴（camera_availability_intervals）から期間内のカメラ稼働率を算出
+///
+/// 指標は CameraAvailabilityService の SLA 集計（/api/stats/availability）と同じ。
+/// context_map のカメラのみ対象で、期間内に記録のないカメラは除外する。
+pub fn compute_availability(
+    intervals: &[AvailabilityInterval],
+    context_map: &HashMap<String, ContextMapEntry>,
+    period_start: DateTime<Utc>,
+    period_end: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Vec<CameraAvailability> {
+    let cameras: HashMap<&str, &ContextMapEntry> = context_map
+        .values()
+        .map(|e| (e.camera_id.as_str(), e))
+        .collect();
+    let report = build_report(intervals, period_start, period_end, now);
+
+    let mut rows: Vec<CameraAvailability> = report
+        .cameras
+        .into_iter()
+        .filter_map(|metrics| {
+            let entry = cameras.get(metrics.camera_id.as_str())?;
+            let uptime_pct = metrics.uptime_pct?;
+            // 期間終了時点の状態（終了時点を含む区間、なければ最後の区間）
+            let state_at_end = intervals
+                .iter()
+                .filter(|i| i.camera_id == metrics.camera_id && i.started_at < period_end)
+                .max_by_key(|i| i.started_at)
+                .map(|i| i.state);
+            Some(CameraAvailability {
+                name: entry.name.clone(),
+                location: entry.location.clone(),
+                lost_events: metrics.failures as i64,
+                downtime_minutes: metrics.offline_secs / 60,
+                availability: (uptime_pct / 100.0).clamp(0.0, 1.0),
+                online_at_end: state_at_end != Some(AvailabilityState::Offline),
+                camera_id: metrics.camera_id,
+            })
+        })
+        .collect();
+
+    rows.sort_by(|a, b| {
+        a.availability
+            .partial_cmp(&b.availability)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(a.name.cmp(&b.name))
+    });
+    rows
+}
+
+/// 時間帯別の検出数（JST 1時間単位、接続イベントは除外）
+pub fn hourly_trend(
+    logs: &[DetectionLog],
+    period_start: DateTime<Utc>,
+    period_end: DateTime<Utc>,
+) -> Vec<(String, i64)> {
+    let Ok(first) = period_start.duration_trunc(Duration::hours(1)) else {
+        return Vec::new();
+    };
+    let buckets = ((period_end - first).num_minutes() as f64 / 60.0).ceil().max(1.0) as usize;
+    // 長期間でも表示可能な本数に抑える
+    let buckets = buckets.min(24 * 7);
+    let multi_day = period_end - period_start > Duration::hours(24);
+
+    let mut counts = vec![0i64; buckets];
+    for log in logs {
+        if matches!(
+            log.primary_event.as_str(),
+            "camera_lost" | "connection_lost" | "camera_recovered"
+        ) {
+            continue;
+        }
+        let idx = (log.captured_at - first).num_hours();
+        if idx >= 0 && (idx as usize) < buckets {
+            counts[idx as usize] += 1;
+        }
+    }
+
+    counts
+        .into_iter()
+        .enumerate()
+        .map(|(i, count)| {
+            let at = (first + Duration::hours(i as i64)).with_timezone(&Tokyo);
+            let label = if multi_day {
+                at.format("%m/%d %H").to_string()
+            } else {
+                at.format("%H").to_string()
+            };
+            (label, count)
+        })
+        .collect()
+}
+
+/// Summaryレポート生成サービス
+pub struct ReportService {
+    detection_log_service: Arc<DetectionLogService>,
+    camera_context_service: CameraContextService,
+    camera_availability: Arc<CameraAvailabilityService>,
+    repository: SummaryRepository,
+    text_builder: Arc<SummaryTextBuilder>,
+    config: ReportConfig,
+}
+
+impl ReportService {
+    pub fn new(
+        detection_log_service: Arc<DetectionLogService>,
+        camera_context_service: CameraContextService,
+        camera_availability: Arc<CameraAvailabilityService>,
+        repository: SummaryRepository,
+        text_builder: Arc<SummaryTextBuilder>,
+        config: ReportConfig,
+    ) -> Self {
+        Self {
+            detection_log_service,
+            camera_context_service,
+            camera_availability,
+            repository,
+            text_builder,
+            config,
+        }
+    }
+
+    /// レポート描画用データを構築
+    pub async fn build(&self, summary_id: u64) -> crate::Result<ReportData> {
+        let summary = self
+            .repository
+            .get_by_id(summary_id)
+            .await?
+            .ok_or_else(|| crate::Error::NotFound(format!("Summary {} not found", summary_id)))?;
+
+        let logs = self
+            .detection_log_service
+            .get_by_tid_fid(&summary.tid, Some(&summary.fid), 10000)
+            .await?
+            .into_iter()
+            .filter(|log| {
+                log.captured_at >= summary.period_start && log.captured_at <= summary.period_end
+            })
+            .collect::<Vec<_>>();
+        let context_map = self
+            .camera_context_service
+            .build_context_map_by_fid(&summary.tid, &summary.fid)
+            .await?;
+
+        // ロールアップも複数Summaryの統合としてGrandSummary形式で描画
+        let kind = match summary.summary_type {
+            SummaryType::Daily | SummaryType::Weekly | SummaryType::Monthly => {
+                TemplateKind::GrandSummary
+            }
+            _ => TemplateKind::Summary,
+        };
+        let mut facts = SummaryFacts::from_logs(
+            &summary.fid,
+            summary.period_start,
+            summary.period_end,
+            &logs,
+            &context_map,
+        );
+        if kind == TemplateKind::GrandSummary {
+            facts.summary_count = summary
+                .summary_json
+                .as_ref()
+                .and_then(|j| j.get("includedSummaryIds"))
+                .and_then(|ids| ids.as_array())
+                .map(|ids| ids.len())
+                .unwrap_or(0);
+            facts.total_detections = summary.detection_count as i64;
+        }
+
+        let locale = self.text_builder.locale_settings().await.locale_for(&summary.fid);
+        let thumbnails = self.collect_thumbnails(&logs, &context_map).await;
+        let availability = match self
+            .camera_availability
+            .intervals(None, summary.period_start, summary.period_end)
+            .await
+        {
+            Ok(intervals) => compute_availability(
+                &intervals,
+                &context_map,
+                summary.period_start,
+                summary.period_end,
+                Utc::now(),
+            ),
+            Err(e) => {
+                warn!(summary_id = summary.summary_id, error = %e, "Failed to load availability timeline for report");
+                Vec::new()
+            }
+        };
+
+        Ok(ReportData {
+            locale,
+            kind,
+            hourly_trend: hourly_trend(&logs, summary.period_start, summary.period_end),
+            availability,
+            thumbnails,
+            facts,
+            summary,
+            generated_at: Utc::now(),
+        })
+    }
+
+    /// 高severity検出の画像を埋め込み用に読み込む
+    async fn collect_thumbnails(
+        &self,
+        logs: &[DetectionLog],
+        context_map: &HashMap<String, ContextMapEntry>,
+    ) -> Vec<ReportThumbnail> {
+        let mut notable: Vec<&DetectionLog> =
+            logs.iter().filter(|l| l.severity >= NOTABLE_SEVERITY).collect();
+        notable.sort_by(|a, b| b.severity.cmp(&a.severity).then(a.captured_at.cmp(&b.captured_at)));
+        notable.truncate(self.config.max_thumbnails);
+
+        let mut thumbnails = Vec::with_capacity(notable.len());
+        for log in notable {
+            let camera_name = context_map
+                .values()
+                .find(|e| e.camera_id == log.camera_id)
+                .map(|e| e.name.clone())
+                .unwrap_or_else(|| log.camera_id.clone());
+            thumbnails.push(ReportThumbnail {
+                captured_at: log.captured_at,
+                camera_name,
+                event: log.primary_event.clone(),
+                severity: log.severity,
+                data_uri: load_data_uri(&log.image_path_local).await,
+            });
+        }
+        thumbnails
+    }
+
+    /// fidのlocale（summary_locale設定）
+    pub async fn locale_for(&self, fid: &str) -> SummaryLocale {
+        self.text_builder.locale_settings().await.locale_for(fid)
+    }
+
+    /// レポートを描画
+    pub async fn render(&self, summary_id: u64, format: ReportFormat) -> crate::Result<RenderedReport> {
+        let data = self.build(summary_id).await?;
+        let html = html::render_html(&data);
+        let filename = report_filename(&data.summary, format);
+
+        let bytes = match format {
+            ReportFormat::Html => html.into_bytes(),
+            ReportFormat::Pdf => {
+                pdf::html_to_pdf(&html, &self.config.pdf_command, self.config.pdf_timeout).await?
+            }
+        };
+
+        debug!(summary_id = summary_id, format = format.as_str(), size = bytes.len(), "Report rendered");
+        Ok(RenderedReport {
+            format,
+            filename,
+            bytes,
+        })
+    }
+
+    /// 保存済みレポートがあれば返し、なければ描画する
+    pub async fn get_or_render(&self, summary_id: u64, format: ReportFormat) -> crate::Result<RenderedReport> {
+        if let Some(summary) = self.repository.get_by_id(summary_id).await? {
+            let filename = report_filename(&summary, format);
+            let path = self.config.report_dir.join(&summary.fid).join(&filename);
+            if let Ok(bytes) = tokio::fs::read(&path).await {
+                return Ok(RenderedReport {
+                    format,
+                    filename,
+                    bytes,
+                });
+            }
+        }
+        self.render(summary_id, format).await
+    }
+
+    /// 描画して report_dir に保存（スケジュール実行時の添付用）
+    pub async fn render_and_store(&self, summary_id: u64, format: ReportFormat) -> crate::Result<PathBuf> {
+        let report = self.render(summary_id, format).await?;
+        let summary = self
+            .repository
+            .get_by_id(summary_id)
+            .await?
+            .ok_or_else(|| crate::Error::NotFound(format!("Summary {} not found", summary_id)))?;
+
+        let dir = self.config.report_dir.join(&summary.fid);
+        tokio::fs::create_dir_all(&dir).await?;
+        let path = dir.join(&report.filename);
+        tokio::fs::write(&path, &report.bytes).await?;
+
+        info!(
+            summary_id = summary_id,
+            path = %path.display(),
+            size = report.bytes.len(),
+            "Report stored"
+        );
+        Ok(path)
+    }
+}
+
+/// report_{fid}_{summary_id}.{ext}
+pub fn report_filename(summary: &SummaryResult, format: ReportFormat) -> String {
+    let fid: String = summary
+        .fid
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+        .collect();
+    format!("report_{}_{}.{}", fid, summary.summary_id, format.as_str())
+}
+
+/// 画像ファイルをdata URIとして読み込む
+async fn load_data_uri(path: &str) -> Option<String> {
+    let (mime, bytes) = read_image(path).await?;
+    Some(format!(
+        "data:{};base64,{}",
+        mime,
+        base64::engine::general_purpose::STANDARD.encode(bytes)
+    ))
+}
+
+/// 検出画像を読み込む（MIMEタイプ, バイト列）
+///
+/// 未対応の拡張子・サイズ超過・読み込み失敗はNone
+pub async fn read_image(path: &str) -> Option<(&'static str, Vec<u8>)> {
+    if path.is_empty() {
+        return None;
+    }
+    let mime = match std::path::Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase())
+        .as_deref()
+    {
+        Some("jpg") | Some("jpeg") => "image/jpeg",
+        Some("png") => "image/png",
+        Some("webp") => "image/webp",
+        _ => return None,
+    };
+    match tokio::fs::metadata(path).await {
+        Ok(meta) if meta.len() <= MAX_THUMBNAIL_BYTES => {}
+        Ok(_) => return None,
+        Err(e) => {
+            debug!(path = %path, error = %e, "Report thumbnail not available");
+            return None;
+        }
+    }
+    match tokio::fs::read(path).await {
+        Ok(bytes) => Some((mime, bytes)),
+        Err(e) => {
+            warn!(path = %path, error = %e, "Failed to read report thumbnail");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn event(camera_id: &str, event: &str, minute: i64) -> DetectionLog {
+        let at = Utc.with_ymd_and_hms(2026, 1, 10, 0, 0, 0).unwrap() + Duration::minutes(minute);
+        DetectionLog {
+            log_id: None,
+            tid: "T1".to_string(),
+            fid: "0150".to_string(),
+            camera_id: camera_id.to_string(),
+            lacis_id: None,
+            camera_lacis_id: None,
+            captured_at: at,
+            analyzed_at: at,
+            primary_event: event.to_string(),
+            severity: 0,
+            confidence: 0.0,
+            count_hint: 0,
+            unknown_flag: false,
+            tags: vec![],
+            person_details: None,
+            vehicle_details: None,
+            bboxes: None,
+            suspicious: None,
+            frame_diff: None,
+            loitering_detected: false,
+            preset_id: "balanced".to_string(),
+            preset_version: None,
+            output_schema: None,
+            context_applied: false,
+            camera_context: None,
+            is21_log: serde_json::Value::Null,
+            image_path_local: String::new(),
+            image_path_cloud: None,
+            processing_ms: None,
+            polling_cycle_id: None,
+            schema_version: String::new(),
+            timings: None,
+            created_at: at,
+            synced_to_bq: false,
+            synced_at: None,
+        }
+    }
+
+    fn interval(camera_id: &str, state: AvailabilityState, from: i64, to: Option<i64>) -> AvailabilityInterval {
+        let base = Utc.with_ymd_and_hms(2026, 1, 10, 0, 0, 0).unwrap();
+        AvailabilityInterval {
+            interval_id: 0,
+            camera_id: camera_id.to_string(),
+            state,
+            cause: None,
+            detail: None,
+            started_at: base + Duration::minutes(from),
+            ended_at: to.map(|m| base + Duration::minutes(m)),
+        }
+    }
+
+    fn context(camera_ids: &[&str]) -> HashMap<String, ContextMapEntry> {
+        camera_ids
+            .iter()
+            .map(|id| {
+                (
+                    format!("lacis-{}", id),
+                    ContextMapEntry {
+                        camera_id: id.to_string(),
+                        lacis_id: format!("lacis-{}", id),
+                        name: id.to_uppercase(),
+                        location: None,
+                        ip_address: None,
+                        context_summary: String::new(),
+                    },
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_compute_availability() {
+        use AvailabilityState::{Offline, Online};
+        let start = Utc.with_ymd_and_hms(2026, 1, 10, 0, 0, 0).unwrap();
+        let end = start + Duration::hours(1);
+        let intervals = vec![
+            // cam-a: 10分ダウン後に復旧
+            interval("cam-a", Online, -60, Some(10)),
+            interval("cam-a", Offline, 10, Some(20)),
+            interval("cam-a", Online, 20, None),
+            // cam-b: 期間開始時点でダウン、30分に復旧
+            interval("cam-b", Offline, -30, Some(30)),
+            interval("cam-b", Online, 30, None),
+            // cam-c: 45分にダウンしたまま
+            interval("cam-c", Online, -10, Some(45)),
+            interval("cam-c", Offline, 45, None),
+            // 他施設のカメラ
+            interval("cam-x", Offline, 0, None),
+        ];
+        let now = end + Duration::hours(2);
+        let rows = compute_availability(&intervals, &context(&["cam-a", "cam-b", "cam-c", "cam-d"]), start, end, now);
+        let get = |id: &str| rows.iter().find(|r| r.camera_id == id).unwrap();
+
+        assert_eq!(get("cam-a").downtime_minutes, 10);
+        assert_eq!(get("cam-a").lost_events, 1);
+        assert_eq!(get("cam-a").name, "CAM-A");
+        assert!(get("cam-a").online_at_end);
+        assert_eq!(get("cam-b").downtime_minutes, 30);
+        // 期間前に開始したオフラインは件数に含めない（SLA 集計と同じ）
+        assert_eq!(get("cam-b").lost_events, 0);
+        assert_eq!(get("cam-c").downtime_minutes, 15);
+        assert!(!get("cam-c").online_at_end);
+        // 記録のないカメラ・他施設のカメラは対象外
+        assert!(rows.iter().all(|r| r.camera_id != "cam-d" && r.camera_id != "cam-x"));
+        // 稼働率の低い順
+        assert_eq!(rows[0].camera_id, "cam-b");
+        assert!((rows[0].availability - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_hourly_trend() {
+        let start = Utc.with_ymd_and_hms(2026, 1, 10, 0, 30, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2026, 1, 10, 3, 0, 0).unwrap();
+        let logs = vec![
+            event("cam-a", "human", 40),
+            event("cam-a", "human", 70),
+            event("cam-a", "camera_lost", 75),
+            event("cam-a", "vehicle", 150),
+        ];
+        let trend = hourly_trend(&logs, start, end);
+        // UTC 00:00-03:00 = JST 09-11時
+        assert_eq!(
+            trend,
+            vec![
+                ("09".to_string(), 1),
+                ("10".to_string(), 1),
+                ("11".to_string(), 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_report_format_and_filename() {
+        assert_eq!(ReportFormat::parse("PDF"), Some(ReportFormat::Pdf));
+        assert_eq!(ReportFormat::parse("docx"), None);
+        assert_eq!(ReportFormat::Html.content_type(), "text/html; charset=utf-8");
+    }
+}
@@ -0,0 +1,72 @@
+//! HTML → PDF conversion
+//!
+//! 外部コマンド（既定: wkhtmltopdf）にstdinでHTMLを渡し、stdoutからPDFを受け取る。
+//! タイムアウト時は kill_on_drop によりプロセスを確実に終了させる。
+
+use crate::{Error, Result};
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+/// HTMLをPDFに変換
+pub async fn html_to_pdf(html: &str, command: &str, timeout: Duration) -> Result<Vec<u8>> {
+    let mut child = Command::new(command)
+        .args([
+            "--quiet",
+            "--encoding", "utf-8",
+            "--print-media-type",
+            "-",
+            "-",
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => Error::Config(format!(
+                "PDF renderer '{}' not found (install it or set report.pdf_command)",
+                command
+            )),
+            _ => Error::Internal(format!("{} spawn failed: {}", command, e)),
+        })?;
+
+    // stdoutが詰まらないよう書き込みは別タスクで行う
+    let mut stdin = child
+        .stdin
+        .take()
+        .ok_or_else(|| Error::Internal("PDF renderer stdin unavailable".to_string()))?;
+    let input = html.as_bytes().to_vec();
+    let writer = tokio::spawn(async move {
+        let result = stdin.write_all(&input).await;
+        drop(stdin);
+        result
+    });
+
+    let output = match tokio::time::timeout(timeout, child.wait_with_output()).await {
+        Ok(Ok(output)) => output,
+        Ok(Err(e)) => return Err(Error::Internal(format!("{} execution failed: {}", command, e))),
+        Err(_) => {
+            return Err(Error::Internal(format!(
+                "{} timed out after {}s",
+                command,
+                timeout.as_secs()
+            )))
+        }
+    };
+
+    if let Ok(Err(e)) = writer.await {
+        return Err(Error::Internal(format!("Failed to write HTML to {}: {}", command, e)));
+    }
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(Error::Internal(format!("{} failed: {}", command, stderr.trim())));
+    }
+    if !output.stdout.starts_with(b"%PDF") {
+        return Err(Error::Internal(format!("{} returned invalid PDF output", command)));
+    }
+
+    Ok(output.stdout)
+}
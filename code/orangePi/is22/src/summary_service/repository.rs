@@ -6,12 +6,17 @@
 //! ## テーブル
 //! - ai_summary_cache: Summary保存
 //! - scheduled_reports: スケジュール設定
+//! - summary_templates: summary_textテンプレート
 
+use super::template::{
+    SummaryLocale, SummaryTemplate, TemplateKind, UpsertSummaryTemplateRequest,
+};
 use super::types::{
     ReportSchedule, ReportType, SummaryInsert, SummaryResult, SummaryType,
 };
+use crate::db::{Db, InsertId};
+use crate::with_db;
 use chrono::{DateTime, Utc};
-use sqlx::MySqlPool;
 use tracing::{debug, info};
 
 // ============================================================
@@ -20,56 +25,52 @@ use tracing::{debug, info};
 
 /// Summary保存・取得リポジトリ
 pub struct SummaryRepository {
-    pool: MySqlPool,
+    db: Db,
 }
 
 impl Clone for SummaryRepository {
     fn clone(&self) -> Self {
         Self {
-            pool: self.pool.clone(),
+            db: self.db.clone(),
         }
     }
 }
 
 impl SummaryRepository {
-    pub fn new(pool: MySqlPool) -> Self {
-        Self { pool }
-    }
-
-    /// プールへの参照を取得
-    pub fn pool(&self) -> &MySqlPool {
-        &self.pool
+    pub fn new(db: impl Into<Db>) -> Self {
+        Self { db: db.into() }
     }
 
     /// Summaryを保存
     pub async fn insert(&self, data: SummaryInsert) -> crate::Result<SummaryResult> {
-        let result = sqlx::query(
-            r#"
-            INSERT INTO ai_summary_cache
-                (tid, fid, summary_type, period_start, period_end,
-                 summary_text, summary_json, detection_count, severity_max,
-                 camera_ids, expires_at)
-            VALUES
-                (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
-            "#,
-        )
-        .bind(&data.tid)
-        .bind(&data.fid)
-        .bind(data.summary_type.to_string())
-        .bind(data.period_start)
-        .bind(data.period_end)
-        .bind(&data.summary_text)
-        .bind(&data.summary_json)
-        .bind(data.detection_count)
-        .bind(data.severity_max)
-        .bind(&data.camera_ids)
-        .bind(data.expires_at)
-        .execute(&self.pool)
-        .await
+        let summary_id = with_db!(&self.db, pool => {
+            sqlx::query(
+                r#"
+                INSERT INTO ai_summary_cache
+                    (tid, fid, summary_type, period_start, period_end,
+                     summary_text, summary_json, detection_count, severity_max,
+                     camera_ids, expires_at)
+                VALUES
+                    (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(&data.tid)
+            .bind(&data.fid)
+            .bind(data.summary_type.to_string())
+            .bind(data.period_start)
+            .bind(data.period_end)
+            .bind(&data.summary_text)
+            .bind(&data.summary_json)
+            .bind(data.detection_count)
+            .bind(data.severity_max)
+            .bind(&data.camera_ids)
+            .bind(data.expires_at)
+            .execute(pool)
+            .await
+            .map(|r| r.insert_id())
+        })
         .map_err(|e| crate::Error::Database(e.to_string()))?;
 
-        let summary_id = result.last_insert_id();
-
         info!(
             summary_id = summary_id,
             tid = %data.tid,
@@ -106,17 +107,20 @@ impl SummaryRepository {
         summary_id: u64,
         summary_json: &serde_json::Value,
     ) -> crate::Result<()> {
-        sqlx::query(
-            r#"
-            UPDATE ai_summary_cache
-            SET summary_json = ?
-            WHERE summary_id = ?
-            "#,
-        )
-        .bind(summary_json)
-        .bind(summary_id)
-        .execute(&self.pool)
-        .await
+        with_db!(&self.db, pool => {
+            sqlx::query(
+                r#"
+                UPDATE ai_summary_cache
+                SET summary_json = ?
+                WHERE summary_id = ?
+                "#,
+            )
+            .bind(summary_json)
+            .bind(summary_id as i64)
+            .execute(pool)
+            .await
+            .map(|r| r.rows_affected())
+        })
         .map_err(|e| crate::Error::Database(e.to_string()))?;
 
         debug!(summary_id = summary_id, "Summary JSON updated");
@@ -125,19 +129,21 @@ impl SummaryRepository {
 
     /// IDでSummaryを取得
     pub async fn get_by_id(&self, summary_id: u64) -> crate::Result<Option<SummaryResult>> {
-        let row = sqlx::query_as::<_, SummaryRow>(
-            r#"
-            SELECT
-                summary_id, tid, fid, summary_type, period_start, period_end,
-                summary_text, summary_json, detection_count, severity_max,
-                camera_ids, created_at, expires_at
-            FROM ai_summary_cache
-            WHERE summary_id = ?
-            "#,
-        )
-        .bind(summary_id)
-        .fetch_optional(&self.pool)
-        .await
+        let row = with_db!(&self.db, pool => {
+            sqlx::query_as::<_, SummaryRow>(
+                r#"
+                SELECT
+                    summary_id, tid, fid, summary_type, period_start, period_end,
+                    summary_text, summary_json, detection_count, severity_max,
+                    camera_ids, created_at, expires_at
+                FROM ai_summary_cache
+                WHERE summary_id = ?
+                "#,
+            )
+            .bind(summary_id as i64)
+            .fetch_optional(pool)
+            .await
+        })
         .map_err(|e| crate::Error::Database(e.to_string()))?;
 
         Ok(row.map(|r| r.into()))
@@ -151,40 +157,44 @@ impl SummaryRepository {
         summary_type: SummaryType,
     ) -> crate::Result<Option<SummaryResult>> {
         let query = if let Some(f) = fid {
-            sqlx::query_as::<_, SummaryRow>(
-                r#"
-                SELECT
-                    summary_id, tid, fid, summary_type, period_start, period_end,
-                    summary_text, summary_json, detection_count, severity_max,
-                    camera_ids, created_at, expires_at
-                FROM ai_summary_cache
-                WHERE tid = ? AND fid = ? AND summary_type = ?
-                ORDER BY created_at DESC
-                LIMIT 1
-                "#,
-            )
-            .bind(tid)
-            .bind(f)
-            .bind(summary_type.to_string())
-            .fetch_optional(&self.pool)
-            .await
+            with_db!(&self.db, pool => {
+                sqlx::query_as::<_, SummaryRow>(
+                    r#"
+                    SELECT
+                        summary_id, tid, fid, summary_type, period_start, period_end,
+                        summary_text, summary_json, detection_count, severity_max,
+                        camera_ids, created_at, expires_at
+                    FROM ai_summary_cache
+                    WHERE tid = ? AND fid = ? AND summary_type = ?
+                    ORDER BY created_at DESC
+                    LIMIT 1
+                    "#,
+                )
+                .bind(tid)
+                .bind(f)
+                .bind(summary_type.to_string())
+                .fetch_optional(pool)
+                .await
+            })
         } else {
-            sqlx::query_as::<_, SummaryRow>(
-                r#"
-                SELECT
-                    summary_id, tid, fid, summary_type, period_start, period_end,
-                    summary_text, summary_json, detection_count, severity_max,
-                    camera_ids, created_at, expires_at
-                FROM ai_summary_cache
-                WHERE tid = ? AND summary_type = ?
-                ORDER BY created_at DESC
-                LIMIT 1
-                "#,
-            )
-            .bind(tid)
-            .bind(summary_type.to_string())
-            .fetch_optional(&self.pool)
-            .await
+            with_db!(&self.db, pool => {
+                sqlx::query_as::<_, SummaryRow>(
+                    r#"
+                    SELECT
+                        summary_id, tid, fid, summary_type, period_start, period_end,
+                        summary_text, summary_json, detection_count, severity_max,
+                        camera_ids, created_at, expires_at
+                    FROM ai_summary_cache
+                    WHERE tid = ? AND summary_type = ?
+                    ORDER BY created_at DESC
+                    LIMIT 1
+                    "#,
+                )
+                .bind(tid)
+                .bind(summary_type.to_string())
+                .fetch_optional(pool)
+                .await
+            })
         };
 
         let row = query.map_err(|e| crate::Error::Database(e.to_string()))?;
@@ -200,25 +210,27 @@ impl SummaryRepository {
         period_start: DateTime<Utc>,
         period_end: DateTime<Utc>,
     ) -> crate::Result<Vec<SummaryResult>> {
-        let rows = sqlx::query_as::<_, SummaryRow>(
-            r#"
-            SELECT
-                summary_id, tid, fid, summary_type, period_start, period_end,
-                summary_text, summary_json, detection_count, severity_max,
-                camera_ids, created_at, expires_at
-            FROM ai_summary_cache
-            WHERE tid = ? AND fid = ? AND summary_type = ?
-            AND period_start >= ? AND period_end <= ?
-            ORDER BY period_start ASC
-            "#,
-        )
-        .bind(tid)
-        .bind(fid)
-        .bind(summary_type.to_string())
-        .bind(period_start)
-        .bind(period_end)
-        .fetch_all(&self.pool)
-        .await
+        let rows = with_db!(&self.db, pool => {
+            sqlx::query_as::<_, SummaryRow>(
+                r#"
+                SELECT
+                    summary_id, tid, fid, summary_type, period_start, period_end,
+                    summary_text, summary_json, detection_count, severity_max,
+                    camera_ids, created_at, expires_at
+                FROM ai_summary_cache
+                WHERE tid = ? AND fid = ? AND summary_type = ?
+                AND period_start >= ? AND period_end <= ?
+                ORDER BY period_start ASC
+                "#,
+            )
+            .bind(tid)
+            .bind(fid)
+            .bind(summary_type.to_string())
+            .bind(period_start)
+            .bind(period_end)
+            .fetch_all(pool)
+            .await
+        })
         .map_err(|e| crate::Error::Database(e.to_string()))?;
 
         Ok(rows.into_iter().map(|r| r.into()).collect())
@@ -297,21 +309,21 @@ impl SummaryRepository {
             ),
         };
 
-        let mut query = sqlx::query_as::<_, SummaryRow>(query_str).bind(tid);
+        let rows = with_db!(&self.db, pool => {
+            let mut query = sqlx::query_as::<_, SummaryRow>(query_str).bind(tid);
 
-        if has_fid {
-            query = query.bind(fid.unwrap());
-        }
-        if has_type {
-            query = query.bind(summary_type.unwrap().to_string());
-        }
+            if has_fid {
+                query = query.bind(fid.unwrap());
+            }
+            if has_type {
+                query = query.bind(summary_type.unwrap().to_string());
+            }
 
-        query = query.bind(from).bind(to).bind(limit);
+            query = query.bind(from).bind(to).bind(limit);
 
-        let rows = query
-            .fetch_all(&self.pool)
-            .await
-            .map_err(|e| crate::Error::Database(e.to_string()))?;
+            query.fetch_all(pool).await
+        })
+        .map_err(|e| crate::Error::Database(e.to_string()))?;
 
         Ok(rows.into_iter().map(|r| r.into()).collect())
     }
@@ -319,7 +331,7 @@ impl SummaryRepository {
     /// Summaryを保存または更新（UPSERT）
     /// 同一期間の既存エントリがある場合は更新する
     pub async fn upsert(&self, data: SummaryInsert) -> crate::Result<SummaryResult> {
-        let result = sqlx::query(
+        let sql = format!(
             r#"
             INSERT INTO ai_summary_cache
                 (tid, fid, summary_type, period_start, period_end,
@@ -327,33 +339,40 @@ impl SummaryRepository {
                  camera_ids, expires_at)
             VALUES
                 (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
-            ON DUPLICATE KEY UPDATE
-                summary_text = VALUES(summary_text),
-                summary_json = VALUES(summary_json),
-                detection_count = VALUES(detection_count),
-                severity_max = VALUES(severity_max),
-                camera_ids = VALUES(camera_ids),
-                expires_at = VALUES(expires_at),
-                created_at = CURRENT_TIMESTAMP(3)
+            {}
             "#,
-        )
-        .bind(&data.tid)
-        .bind(&data.fid)
-        .bind(data.summary_type.to_string())
-        .bind(data.period_start)
-        .bind(data.period_end)
-        .bind(&data.summary_text)
-        .bind(&data.summary_json)
-        .bind(data.detection_count)
-        .bind(data.severity_max)
-        .bind(&data.camera_ids)
-        .bind(data.expires_at)
-        .execute(&self.pool)
-        .await
+            self.db.upsert(
+                "tid, fid, summary_type, period_start",
+                &[
+                    "summary_text",
+                    "summary_json",
+                    "detection_count",
+                    "severity_max",
+                    "camera_ids",
+                    "expires_at",
+                ],
+                &[&format!("created_at = {}", self.db.now())],
+            )
+        );
+        let summary_id = with_db!(&self.db, pool => {
+            sqlx::query(&sql)
+                .bind(&data.tid)
+                .bind(&data.fid)
+                .bind(data.summary_type.to_string())
+                .bind(data.period_start)
+                .bind(data.period_end)
+                .bind(&data.summary_text)
+                .bind(&data.summary_json)
+                .bind(data.detection_count)
+                .bind(data.severity_max)
+                .bind(&data.camera_ids)
+                .bind(data.expires_at)
+                .execute(pool)
+                .await
+                .map(|r| r.insert_id())
+        })
         .map_err(|e| crate::Error::Database(e.to_string()))?;
 
-        let summary_id = result.last_insert_id();
-
         info!(
             summary_id = summary_id,
             tid = %data.tid,
@@ -386,17 +405,18 @@ impl SummaryRepository {
 
     /// 期限切れSummaryを削除
     pub async fn delete_expired(&self) -> crate::Result<u64> {
-        let result = sqlx::query(
-            r#"
-            DELETE FROM ai_summary_cache
-            WHERE expires_at < NOW()
-            "#,
-        )
-        .execute(&self.pool)
-        .await
+        let sql = format!(
+            "DELETE FROM ai_summary_cache WHERE expires_at < {}",
+            self.db.now()
+        );
+        let deleted = with_db!(&self.db, pool => {
+            sqlx::query(&sql)
+                .execute(pool)
+                .await
+                .map(|r| r.rows_affected())
+        })
         .map_err(|e| crate::Error::Database(e.to_string()))?;
 
-        let deleted = result.rows_affected();
         if deleted > 0 {
             info!(count = deleted, "Expired summaries deleted");
         }
@@ -410,20 +430,20 @@ impl SummaryRepository {
 
 /// スケジュール設定リポジトリ
 pub struct ScheduleRepository {
-    pool: MySqlPool,
+    db: Db,
 }
 
 impl Clone for ScheduleRepository {
     fn clone(&self) -> Self {
         Self {
-            pool: self.pool.clone(),
+            db: self.db.clone(),
         }
     }
 }
 
 impl ScheduleRepository {
-    pub fn new(pool: MySqlPool) -> Self {
-        Self { pool }
+    pub fn new(db: impl Into<Db>) -> Self {
+        Self { db: db.into() }
     }
 
     /// スケジュールを保存または更新（UPSERT）
@@ -434,29 +454,35 @@ impl ScheduleRepository {
             .map(|t| serde_json::to_value(t).ok())
             .flatten();
 
-        sqlx::query(
+        // next_run_at は既存値を保持（上書きしない）
+        let sql = format!(
             r#"
             INSERT INTO scheduled_reports
-                (tid, fid, report_type, interval_minutes, scheduled_times, enabled, next_run_at)
+                (tid, fid, report_type, interval_minutes, scheduled_times, report_format, enabled, next_run_at)
             VALUES
-                (?, ?, ?, ?, ?, ?, ?)
-            ON DUPLICATE KEY UPDATE
-                interval_minutes = VALUES(interval_minutes),
-                scheduled_times = VALUES(scheduled_times),
-                enabled = VALUES(enabled),
-                -- next_run_at は既存値を保持（上書きしない）
-                updated_at = CURRENT_TIMESTAMP(3)
+                (?, ?, ?, ?, ?, ?, ?, ?)
+            {}
             "#,
-        )
-        .bind(&schedule.tid)
-        .bind(&schedule.fid)
-        .bind(schedule.report_type.to_string())
-        .bind(schedule.interval_minutes)
-        .bind(&scheduled_times)
-        .bind(schedule.enabled)
-        .bind(schedule.next_run_at)
-        .execute(&self.pool)
-        .await
+            self.db.upsert(
+                "tid, fid, report_type",
+                &["interval_minutes", "scheduled_times", "report_format", "enabled"],
+                &[&format!("updated_at = {}", self.db.now())],
+            )
+        );
+        with_db!(&self.db, pool => {
+            sqlx::query(&sql)
+                .bind(&schedule.tid)
+                .bind(&schedule.fid)
+                .bind(schedule.report_type.to_string())
+                .bind(schedule.interval_minutes)
+                .bind(&scheduled_times)
+                .bind(&schedule.report_format)
+                .bind(schedule.enabled)
+                .bind(schedule.next_run_at)
+                .execute(pool)
+                .await
+                .map(|r| r.rows_affected())
+        })
         .map_err(|e| crate::Error::Database(e.to_string()))?;
 
         info!(
@@ -470,20 +496,22 @@ impl ScheduleRepository {
 
     /// スケジュール一覧を取得
     pub async fn get_schedules(&self, tid: &str, fid: &str) -> crate::Result<Vec<ReportSchedule>> {
-        let rows = sqlx::query_as::<_, ScheduleRow>(
-            r#"
-            SELECT
-                schedule_id, tid, fid, report_type, interval_minutes,
-                scheduled_times, last_run_at, next_run_at, enabled
-            FROM scheduled_reports
-            WHERE tid = ? AND fid = ?
-            ORDER BY report_type
-            "#,
-        )
-        .bind(tid)
-        .bind(fid)
-        .fetch_all(&self.pool)
-        .await
+        let rows = with_db!(&self.db, pool => {
+            sqlx::query_as::<_, ScheduleRow>(
+                r#"
+                SELECT
+                    schedule_id, tid, fid, report_type, interval_minutes,
+                    scheduled_times, report_format, last_run_at, next_run_at, enabled
+                FROM scheduled_reports
+                WHERE tid = ? AND fid = ?
+                ORDER BY report_type
+                "#,
+            )
+            .bind(tid)
+            .bind(fid)
+            .fetch_all(pool)
+            .await
+        })
         .map_err(|e| crate::Error::Database(e.to_string()))?;
 
         Ok(rows.into_iter().map(|r| r.into()).collect())
@@ -491,20 +519,22 @@ impl ScheduleRepository {
 
     /// 実行待ちスケジュールを取得
     pub async fn get_due_schedules(&self, now: DateTime<Utc>) -> crate::Result<Vec<ReportSchedule>> {
-        let rows = sqlx::query_as::<_, ScheduleRow>(
-            r#"
-            SELECT
-                schedule_id, tid, fid, report_type, interval_minutes,
-                scheduled_times, last_run_at, next_run_at, enabled
-            FROM scheduled_reports
-            WHERE enabled = TRUE
-            AND (next_run_at IS NULL OR next_run_at <= ?)
-            ORDER BY next_run_at ASC
-            "#,
-        )
-        .bind(now)
-        .fetch_all(&self.pool)
-        .await
+        let rows = with_db!(&self.db, pool => {
+            sqlx::query_as::<_, ScheduleRow>(
+                r#"
+                SELECT
+                    schedule_id, tid, fid, report_type, interval_minutes,
+                    scheduled_times, report_format, last_run_at, next_run_at, enabled
+                FROM scheduled_reports
+                WHERE enabled = TRUE
+                AND (next_run_at IS NULL OR next_run_at <= ?)
+                ORDER BY next_run_at ASC
+                "#,
+            )
+            .bind(now)
+            .fetch_all(pool)
+            .await
+        })
         .map_err(|e| crate::Error::Database(e.to_string()))?;
 
         Ok(rows.into_iter().map(|r| r.into()).collect())
@@ -517,18 +547,19 @@ impl ScheduleRepository {
         last_run: DateTime<Utc>,
         next_run: DateTime<Utc>,
     ) -> crate::Result<()> {
-        sqlx::query(
-            r#"
-            UPDATE scheduled_reports
-            SET last_run_at = ?, next_run_at = ?, updated_at = CURRENT_TIMESTAMP(3)
-            WHERE schedule_id = ?
-            "#,
-        )
-        .bind(last_run)
-        .bind(next_run)
-        .bind(schedule_id)
-        .execute(&self.pool)
-        .await
+        let sql = format!(
+            "UPDATE scheduled_reports SET last_run_at = ?, next_run_at = ?, updated_at = {} WHERE schedule_id = ?",
+            self.db.now()
+        );
+        with_db!(&self.db, pool => {
+            sqlx::query(&sql)
+                .bind(last_run)
+                .bind(next_run)
+                .bind(schedule_id)
+                .execute(pool)
+                .await
+                .map(|r| r.rows_affected())
+        })
         .map_err(|e| crate::Error::Database(e.to_string()))?;
 
         debug!(
@@ -541,17 +572,18 @@ impl ScheduleRepository {
 
     /// スケジュールを有効/無効切り替え
     pub async fn set_enabled(&self, schedule_id: u32, enabled: bool) -> crate::Result<()> {
-        sqlx::query(
-            r#"
-            UPDATE scheduled_reports
-            SET enabled = ?, updated_at = CURRENT_TIMESTAMP(3)
-            WHERE schedule_id = ?
-            "#,
-        )
-        .bind(enabled)
-        .bind(schedule_id)
-        .execute(&self.pool)
-        .await
+        let sql = format!(
+            "UPDATE scheduled_reports SET enabled = ?, updated_at = {} WHERE schedule_id = ?",
+            self.db.now()
+        );
+        with_db!(&self.db, pool => {
+            sqlx::query(&sql)
+                .bind(enabled)
+                .bind(schedule_id)
+                .execute(pool)
+                .await
+                .map(|r| r.rows_affected())
+        })
         .map_err(|e| crate::Error::Database(e.to_string()))?;
 
         info!(schedule_id = schedule_id, enabled = enabled, "Schedule enabled state changed");
@@ -559,12 +591,194 @@ impl ScheduleRepository {
     }
 }
 
+// ============================================================
+// Template Repository
+// ============================================================
+
+/// summary_textテンプレートリポジトリ
+#[derive(Clone)]
+pub struct TemplateRepository {
+    db: Db,
+}
+
+const TEMPLATE_COLUMNS: &str =
+    "template_id, fid, locale, kind, name, body, enabled, created_at, updated_at";
+
+impl TemplateRepository {
+    pub fn new(db: impl Into<Db>) -> Self {
+        Self { db: db.into() }
+    }
+
+    /// テンプレート一覧（fid指定時は当該fid＋共通テンプレート）
+    pub async fn list(&self, fid: Option<&str>) -> crate::Result<Vec<SummaryTemplate>> {
+        let rows = match fid {
+            Some(fid) => {
+                with_db!(&self.db, pool => {
+                    sqlx::query_as::<_, TemplateRow>(&format!(
+                        "SELECT {} FROM summary_templates WHERE fid = ? OR fid IS NULL \
+                         ORDER BY fid IS NULL, locale, kind, template_id",
+                        TEMPLATE_COLUMNS
+                    ))
+                    .bind(fid)
+                    .fetch_all(pool)
+                    .await
+                })?
+            }
+            None => {
+                with_db!(&self.db, pool => {
+                    sqlx::query_as::<_, TemplateRow>(&format!(
+                        "SELECT {} FROM summary_templates ORDER BY fid IS NULL, fid, locale, kind, template_id",
+                        TEMPLATE_COLUMNS
+                    ))
+                    .fetch_all(pool)
+                    .await
+                })?
+            }
+        };
+
+        rows.into_iter().map(SummaryTemplate::try_from).collect()
+    }
+
+    /// テンプレート取得
+    pub async fn get(&self, template_id: u64) -> crate::Result<Option<SummaryTemplate>> {
+        let row = with_db!(&self.db, pool => {
+            sqlx::query_as::<_, TemplateRow>(&format!(
+                "SELECT {} FROM summary_templates WHERE template_id = ?",
+                TEMPLATE_COLUMNS
+            ))
+            .bind(template_id as i64)
+            .fetch_optional(pool)
+            .await
+        })?;
+
+        row.map(SummaryTemplate::try_from).transpose()
+    }
+
+    /// 有効なテンプレートを選択（fid指定 → 共通の順）
+    pub async fn find_active(
+        &self,
+        fid: &str,
+        locale: SummaryLocale,
+        kind: TemplateKind,
+    ) -> crate::Result<Option<SummaryTemplate>> {
+        let row = with_db!(&self.db, pool => {
+            sqlx::query_as::<_, TemplateRow>(&format!(
+                "SELECT {} FROM summary_templates \
+                 WHERE (fid = ? OR fid IS NULL) AND locale = ? AND kind = ? AND enabled = TRUE \
+                 ORDER BY fid IS NULL, updated_at DESC LIMIT 1",
+                TEMPLATE_COLUMNS
+            ))
+            .bind(fid)
+            .bind(locale.as_str())
+            .bind(kind.as_str())
+            .fetch_optional(pool)
+            .await
+        })?;
+
+        row.map(SummaryTemplate::try_from).transpose()
+    }
+
+    /// テンプレートを保存（template_id=None で新規作成）
+    ///
+    /// enabled=true の場合、同一 (fid, locale, kind) の他テンプレートは無効化する
+    pub async fn save(
+        &self,
+        template_id: Option<u64>,
+        req: &UpsertSummaryTemplateRequest,
+    ) -> crate::Result<SummaryTemplate> {
+        let disable_others = format!(
+            "UPDATE summary_templates SET enabled = FALSE \
+             WHERE template_id != ? AND fid {} ? AND locale = ? AND kind = ? AND enabled = TRUE",
+            self.db.null_safe_eq()
+        );
+        let template_id = with_db!(&self.db, pool => {
+            let mut tx = pool.begin().await?;
+
+            let template_id = match template_id {
+                Some(id) => {
+                    sqlx::query(
+                        r#"
+                        UPDATE summary_templates
+                        SET fid = ?, locale = ?, kind = ?, name = ?, body = ?, enabled = ?
+                        WHERE template_id = ?
+                        "#,
+                    )
+                    .bind(&req.fid)
+                    .bind(req.locale.as_str())
+                    .bind(req.kind.as_str())
+                    .bind(&req.name)
+                    .bind(&req.body)
+                    .bind(req.enabled)
+                    .bind(id as i64)
+                    .execute(&mut *tx)
+                    .await?;
+                    id
+                }
+                None => sqlx::query(
+                    r#"
+                    INSERT INTO summary_templates (fid, locale, kind, name, body, enabled)
+                    VALUES (?, ?, ?, ?, ?, ?)
+                    "#,
+                )
+                .bind(&req.fid)
+                .bind(req.locale.as_str())
+                .bind(req.kind.as_str())
+                .bind(&req.name)
+                .bind(&req.body)
+                .bind(req.enabled)
+                .execute(&mut *tx)
+                .await?
+                .insert_id(),
+            };
+
+            if req.enabled {
+                sqlx::query(&disable_others)
+                    .bind(template_id as i64)
+                    .bind(&req.fid)
+                    .bind(req.locale.as_str())
+                    .bind(req.kind.as_str())
+                    .execute(&mut *tx)
+                    .await?;
+            }
+
+            tx.commit().await?;
+            template_id
+        });
+
+        info!(
+            template_id = template_id,
+            fid = ?req.fid,
+            locale = req.locale.as_str(),
+            kind = req.kind.as_str(),
+            enabled = req.enabled,
+            "Summary template saved"
+        );
+
+        self.get(template_id)
+            .await?
+            .ok_or_else(|| crate::Error::NotFound(format!("Template {} not found", template_id)))
+    }
+
+    /// テンプレート削除
+    pub async fn delete(&self, template_id: u64) -> crate::Result<bool> {
+        let result = with_db!(&self.db, pool => {
+            sqlx::query("DELETE FROM summary_templates WHERE template_id = ?")
+                .bind(template_id as i64)
+                .execute(pool)
+                .await
+                .map(|r| r.rows_affected())
+        })?;
+        Ok(result > 0)
+    }
+}
+
 // ============================================================
 // Row Structures
 // ============================================================
 
 #[derive(sqlx::FromRow)]
 struct SummaryRow {
+    #[sqlx(try_from = "crate::db::DbId")]
     summary_id: u64,
     tid: String,
     fid: String,
@@ -602,6 +816,42 @@ impl From<SummaryRow> for SummaryResult {
     }
 }
 
+#[derive(sqlx::FromRow)]
+struct TemplateRow {
+    #[sqlx(try_from = "crate::db::DbId")]
+    template_id: u64,
+    fid: Option<String>,
+    locale: String,
+    kind: String,
+    name: String,
+    body: String,
+    enabled: bool,
+    created_at: chrono::NaiveDateTime,
+    updated_at: chrono::NaiveDateTime,
+}
+
+impl TryFrom<TemplateRow> for SummaryTemplate {
+    type Error = crate::Error;
+
+    fn try_from(row: TemplateRow) -> crate::Result<Self> {
+        Ok(Self {
+            template_id: row.template_id,
+            fid: row.fid,
+            locale: SummaryLocale::parse(&row.locale).ok_or_else(|| {
+                crate::Error::Internal(format!("Unknown summary locale: {}", row.locale))
+            })?,
+            kind: TemplateKind::parse(&row.kind).ok_or_else(|| {
+                crate::Error::Internal(format!("Unknown template kind: {}", row.kind))
+            })?,
+            name: row.name,
+            body: row.body,
+            enabled: row.enabled,
+            created_at: DateTime::<Utc>::from_naive_utc_and_offset(row.created_at, Utc),
+            updated_at: DateTime::<Utc>::from_naive_utc_and_offset(row.updated_at, Utc),
+        })
+    }
+}
+
 #[derive(sqlx::FromRow)]
 struct ScheduleRow {
     schedule_id: u32,
@@ -610,6 +860,7 @@ struct ScheduleRow {
     report_type: String,
     interval_minutes: Option<i32>,
     scheduled_times: Option<serde_json::Value>,
+    report_format: Option<String>,
     last_run_at: Option<chrono::NaiveDateTime>,
     next_run_at: Option<chrono::NaiveDateTime>,
     enabled: bool,
@@ -628,6 +879,7 @@ impl From<ScheduleRow> for ReportSchedule {
             report_type: ReportType::from(row.report_type.as_str()),
             interval_minutes: row.interval_minutes,
             scheduled_times,
+            report_format: row.report_format,
             last_run_at: row
                 .last_run_at
                 .map(|dt| DateTime::<Utc>::from_naive_utc_and_offset(dt, Utc)),
@@ -665,4 +917,47 @@ mod tests {
         assert_eq!(result.summary_type, SummaryType::Hourly);
         assert_eq!(result.camera_ids, vec!["cam1".to_string()]);
     }
+
+    fn summary_insert(text: &str, expires_at: DateTime<Utc>) -> SummaryInsert {
+        let period_start = chrono::TimeZone::with_ymd_and_hms(&Utc, 2026, 1, 5, 9, 0, 0).unwrap();
+        SummaryInsert {
+            tid: "T1".to_string(),
+            fid: "0150".to_string(),
+            summary_type: SummaryType::Hourly,
+            period_start,
+            period_end: period_start + chrono::Duration::hours(1),
+            summary_text: text.to_string(),
+            summary_json: None,
+            detection_count: 3,
+            severity_max: 2,
+            camera_ids: serde_json::json!(["cam1"]),
+            expires_at,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_summary_upsert_and_expiry() {
+        let repo = SummaryRepository::new(Db::memory().await.unwrap());
+        let future = Utc::now() + chrono::Duration::days(1);
+
+        let first = repo.upsert(summary_insert("first", future)).await.unwrap();
+        let second = repo.upsert(summary_insert("second", future)).await.unwrap();
+        assert_eq!(first.summary_id, second.summary_id);
+        assert_eq!(second.summary_text, "second");
+
+        let latest = repo
+            .get_latest("T1", Some("0150"), SummaryType::Hourly)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(latest.camera_ids, vec!["cam1".to_string()]);
+        assert_eq!(latest.period_start, first.period_start);
+
+        let past = Utc::now() - chrono::Duration::days(1);
+        let mut expired = summary_insert("expired", past);
+        expired.summary_type = SummaryType::Daily;
+        repo.insert(expired).await.unwrap();
+        assert_eq!(repo.delete_expired().await.unwrap(), 1);
+        assert!(repo.get_by_id(first.summary_id).await.unwrap().is_some());
+    }
 }
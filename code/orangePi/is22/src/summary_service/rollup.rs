@@ -0,0 +1,795 @@
+//! Weekly / Monthly Rollup Generator
+//!
+//! ISMSレビュー用の週次・月次ロールアップ
+//!
+//! ## 集計内容
+//! - 検出数・最大severity（hourly Summaryの合計）と前期間比
+//! - JST日付別の検出数
+//! - カメラ稼働率（CameraAvailabilityService の稼働履歴。SLA 集計と同じ指標）
+//! - 繰り返し発生している過剰検出（OverdetectionAnalyzer）
+//! - 誤検知報告（misdetection_feedbacks）
+//! - 閾値変更・attunement trialの結果
+//!
+//! ## 期間（Asia/Tokyo基準）
+//! - Weekly: 月曜00:00〜翌月曜00:00
+//! - Monthly: 1日00:00〜翌月1日00:00
+//!
+//! スケジュール実行時は「直前に完了した期間」を集計する。
+
+use super::report::compute_availability;
+use super::repository::SummaryRepository;
+use super::template::SummaryLocale;
+use super::text_builder::SummaryTextBuilder;
+use super::types::{
+    RollupAttunement, RollupCameraUptime, RollupDailyCount, RollupFeedback,
+    RollupOverdetectionIssue, RollupPayload, RollupTotals, RollupTrend, RollupTrialDecision,
+    SummaryInsert, SummaryResult, SummaryType,
+};
+use crate::camera_availability::CameraAvailabilityService;
+use crate::camera_registry::CameraContextService;
+use crate::overdetection_analyzer::OverdetectionAnalyzer;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, TimeZone, Utc};
+use chrono_tz::Asia::Tokyo;
+use sqlx::{MySqlPool, Row};
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+/// ロールアップの実行時刻（JST）デフォルト
+pub const DEFAULT_ROLLUP_TIME: &str = "06:00";
+
+/// 前期間比で「横ばい」とみなす変化率（%）
+const FLAT_THRESHOLD_PCT: f64 = 5.0;
+
+/// ペイロードに含める過剰検出Issueの上限
+const MAX_OVERDETECTION_ISSUES: usize = 5;
+
+/// ロールアップ保存期間（年次レビューで前年同期と比較するため）
+const ROLLUP_RETENTION_DAYS: i64 = 400;
+
+/// 週次・月次ロールアップ生成サービス
+pub struct RollupGenerator {
+    /// 誤検知報告・閾値調整の集計用（MySQL 専用テーブル）
+    pool: MySqlPool,
+    repository: SummaryRepository,
+    camera_availability: Arc<CameraAvailabilityService>,
+    camera_context_service: CameraContextService,
+    overdetection_analyzer: Arc<OverdetectionAnalyzer>,
+    text_builder: Arc<SummaryTextBuilder>,
+}
+
+impl RollupGenerator {
+    pub fn new(
+        pool: MySqlPool,
+        repository: SummaryRepository,
+        camera_availability: Arc<CameraAvailabilityService>,
+        camera_context_service: CameraContextService,
+        overdetection_analyzer: Arc<OverdetectionAnalyzer>,
+        text_builder: Arc<SummaryTextBuilder>,
+    ) -> Self {
+        Self {
+            pool,
+            repository,
+            camera_availability,
+            camera_context_service,
+            overdetection_analyzer,
+            text_builder,
+        }
+    }
+
+    /// 直前に完了した期間のロールアップを生成
+    pub async fn generate_latest(
+        &self,
+        tid: &str,
+        fid: &str,
+        summary_type: SummaryType,
+        now: DateTime<Utc>,
+    ) -> crate::Result<SummaryResult> {
+        let (period_start, period_end) = rollup_period(summary_type, now).ok_or_else(|| {
+            crate::Error::Validation(format!("{} is not a rollup type", summary_type))
+        })?;
+        self.generate(tid, fid, summary_type, period_start, period_end).await
+    }
+
+    /// ロールアップ生成（同一期間の既存ロールアップは更新）
+    pub async fn generate(
+        &self,
+        tid: &str,
+        fid: &str,
+        summary_type: SummaryType,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+    ) -> crate::Result<SummaryResult> {
+        if !summary_type.is_rollup() {
+            return Err(crate::Error::Validation(format!(
+                "{} is not a rollup type",
+                summary_type
+            )));
+        }
+        if period_end <= period_start {
+            return Err(crate::Error::Validation(
+                "periodEnd must be after periodStart".to_string(),
+            ));
+        }
+
+        info!(
+            tid = %tid,
+            fid = %fid,
+            summary_type = %summary_type,
+            period_start = %period_start.with_timezone(&Tokyo).format("%Y-%m-%d"),
+            period_end = %period_end.with_timezone(&Tokyo).format("%Y-%m-%d"),
+            "Generating rollup"
+        );
+
+        // 1. 今期・前期の集計（hourly / GrandSummaryから）
+        let (totals, daily_detections, camera_ids) =
+            self.collect_totals(tid, fid, period_start, period_end).await?;
+        let (prev_start, prev_end) = previous_period(summary_type, period_start);
+        let (previous_totals, _, _) = self.collect_totals(tid, fid, prev_start, prev_end).await?;
+        let trend = compute_trend(totals.detection_count, previous_totals.detection_count);
+
+        // 2. カメラ稼働率
+        let camera_uptime = self
+            .collect_uptime(tid, fid, period_start, period_end)
+            .await
+            .unwrap_or_else(|e| {
+                warn!(error = %e, "Failed to collect camera uptime for rollup");
+                Vec::new()
+            });
+        let average_uptime_pct = average_uptime(&camera_uptime);
+
+        // 3. 過剰検出・誤検知報告・attunement
+        let overdetection_issues = self
+            .collect_overdetection(tid, fid, summary_type)
+            .await
+            .unwrap_or_else(|e| {
+                warn!(error = %e, "Failed to collect overdetection issues for rollup");
+                Vec::new()
+            });
+        let feedback = self
+            .collect_feedback(fid, period_start, period_end, prev_start)
+            .await
+            .unwrap_or_else(|e| {
+                warn!(error = %e, "Failed to collect feedback stats for rollup");
+                RollupFeedback::default()
+            });
+        let attunement = self
+            .collect_attunement(fid, period_start, period_end)
+            .await
+            .unwrap_or_else(|e| {
+                warn!(error = %e, "Failed to collect attunement changes for rollup");
+                RollupAttunement::default()
+            });
+
+        let payload = RollupPayload {
+            rollup_type: rollup_payload_type(summary_type).to_string(),
+            period_start: period_start.to_rfc3339(),
+            period_end: period_end.to_rfc3339(),
+            totals: totals.clone(),
+            previous_totals,
+            trend,
+            daily_detections,
+            camera_uptime,
+            average_uptime_pct,
+            overdetection_issues,
+            feedback,
+            attunement,
+        };
+
+        // 4. summary_text（locale別）
+        let locale = self.text_builder.locale_settings().await.locale_for(fid);
+        let summary_text = render_rollup_text(locale, summary_type, &payload);
+
+        let result = self
+            .repository
+            .upsert(SummaryInsert {
+                tid: tid.to_string(),
+                fid: fid.to_string(),
+                summary_type,
+                period_start,
+                period_end,
+                summary_text,
+                summary_json: Some(serde_json::to_value(&payload)?),
+                detection_count: totals.detection_count.min(i32::MAX as i64) as i32,
+                severity_max: totals.severity_max,
+                camera_ids: serde_json::to_value(&camera_ids)?,
+                expires_at: Utc::now() + Duration::days(ROLLUP_RETENTION_DAYS),
+            })
+            .await?;
+
+        info!(
+            summary_id = result.summary_id,
+            summary_type = %summary_type,
+            detection_count = totals.detection_count,
+            direction = %payload.trend.direction,
+            "Rollup generated successfully"
+        );
+
+        Ok(result)
+    }
+
+    /// hourly Summary / GrandSummaryから期間の集計値を算出
+    async fn collect_totals(
+        &self,
+        tid: &str,
+        fid: &str,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+    ) -> crate::Result<(RollupTotals, Vec<RollupDailyCount>, Vec<String>)> {
+        let hourly = self
+            .repository
+            .get_by_period(tid, fid, SummaryType::Hourly, period_start, period_end)
+            .await?;
+        let grand = self
+            .repository
+            .get_by_period(tid, fid, SummaryType::Daily, period_start, period_end)
+            .await?;
+
+        let mut camera_ids: HashSet<String> = HashSet::new();
+        let mut totals = RollupTotals {
+            summary_count: hourly.len(),
+            grand_summary_count: grand.len(),
+            ..Default::default()
+        };
+        for summary in &hourly {
+            totals.detection_count += summary.detection_count as i64;
+            totals.severity_max = totals.severity_max.max(summary.severity_max);
+            camera_ids.extend(summary.camera_ids.iter().cloned());
+        }
+        totals.camera_count = camera_ids.len();
+
+        let daily = daily_counts(&hourly, period_start, period_end);
+        let mut camera_ids: Vec<String> = camera_ids.into_iter().collect();
+        camera_ids.sort();
+        Ok((totals, daily, camera_ids))
+    }
+
+    /// 稼働履歴から施設内カメラの稼働率を算出
+    async fn collect_uptime(
+        &self,
+        tid: &str,
+        fid: &str,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+    ) -> crate::Result<Vec<RollupCameraUptime>> {
+        let intervals = self
+            .camera_availability
+            .intervals(None, period_start, period_end)
+            .await?;
+        let context_map = self
+            .camera_context_service
+            .build_context_map_by_fid(tid, fid)
+            .await?;
+
+        Ok(compute_availability(&intervals, &context_map, period_start, period_end, Utc::now())
+            .into_iter()
+            .map(|a| RollupCameraUptime {
+                camera_id: a.camera_id,
+                camera_name: a.name,
+                uptime_pct: round1(a.availability * 100.0),
+                lost_events: a.lost_events,
+                downtime_minutes: a.downtime_minutes,
+            })
+            .collect())
+    }
+
+    /// 施設内カメラの過剰検出Issue（件数の多い順）
+    async fn collect_overdetection(
+        &self,
+        tid: &str,
+        fid: &str,
+        summary_type: SummaryType,
+    ) -> crate::Result<Vec<RollupOverdetectionIssue>> {
+        let period = if summary_type == SummaryType::Monthly { "30d" } else { "7d" };
+        let result = self.overdetection_analyzer.analyze(period).await?;
+        let facility_cameras: HashSet<String> = self
+            .camera_context_service
+            .build_context_map_by_fid(tid, fid)
+            .await?
+            .into_values()
+            .map(|e| e.camera_id)
+            .collect();
+
+        let mut issues: Vec<RollupOverdetectionIssue> = result
+            .cameras
+            .into_iter()
+            .filter(|c| facility_cameras.contains(&c.camera_id))
+            .flat_map(|camera| {
+                let camera_id = camera.camera_id;
+                let camera_name = camera.camera_name;
+                camera.issues.into_iter().map(move |issue| RollupOverdetectionIssue {
+                    camera_id: camera_id.clone(),
+                    camera_name: camera_name.clone(),
+                    issue_type: enum_str(&issue.issue_type),
+                    severity: enum_str(&issue.severity),
+                    rate: issue.rate,
+                    count: issue.count,
+                    suggestion: issue.suggestion,
+                })
+            })
+            .collect();
+        issues.sort_by_key(|issue| std::cmp::Reverse(issue.count));
+        issues.truncate(MAX_OVERDETECTION_ISSUES);
+        Ok(issues)
+    }
+
+    /// 誤検知報告の集計（今期・前期）
+    async fn collect_feedback(
+        &self,
+        fid: &str,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+        prev_start: DateTime<Utc>,
+    ) -> crate::Result<RollupFeedback> {
+        let row = sqlx::query(
+            r#"
+            SELECT
+                CAST(COALESCE(SUM(f.created_at >= ?), 0) AS SIGNED) AS total,
+                CAST(COALESCE(SUM(f.created_at >= ? AND f.correct_label = 'none' AND f.reported_label != 'none'), 0) AS SIGNED) AS false_positive,
+                CAST(COALESCE(SUM(f.created_at >= ? AND f.correct_label != 'none' AND f.reported_label = 'none'), 0) AS SIGNED) AS false_negative,
+                CAST(COALESCE(SUM(f.created_at < ?), 0) AS SIGNED) AS previous_total
+            FROM misdetection_feedbacks f
+            INNER JOIN cameras c ON c.camera_id = f.camera_id
+            WHERE c.fid = ? AND f.created_at >= ? AND f.created_at < ?
+            "#,
+        )
+        .bind(period_start)
+        .bind(period_start)
+        .bind(period_start)
+        .bind(period_start)
+        .bind(fid)
+        .bind(prev_start)
+        .bind(period_end)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(RollupFeedback {
+            total: row.try_get("total")?,
+            false_positive: row.try_get("false_positive")?,
+            false_negative: row.try_get("false_negative")?,
+            previous_total: row.try_get("previous_total")?,
+        })
+    }
+
+    /// 閾値変更履歴・attunement trialの集計
+    async fn collect_attunement(
+        &self,
+        fid: &str,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+    ) -> crate::Result<RollupAttunement> {
+        let threshold_changes: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*)
+            FROM threshold_change_history h
+            INNER JOIN cameras c ON c.camera_id = h.camera_id
+            WHERE c.fid = ? AND h.created_at >= ? AND h.created_at < ?
+            "#,
+        )
+        .bind(fid)
+        .bind(period_start)
+        .bind(period_end)
+        .fetch_one(&self.pool)
+        .await?;
+
+        let trials_started: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*)
+            FROM attunement_trials t
+            INNER JOIN cameras c ON c.camera_id = t.camera_id
+            WHERE c.fid = ? AND t.started_at >= ? AND t.started_at < ?
+            "#,
+        )
+        .bind(fid)
+        .bind(period_start)
+        .bind(period_end)
+        .fetch_one(&self.pool)
+        .await?;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT t.trial_id, t.camera_id, t.parameter, t.status,
+                   t.effective_baseline, t.candidate_value, t.finished_at
+            FROM attunement_trials t
+            INNER JOIN cameras c ON c.camera_id = t.camera_id
+            WHERE c.fid = ? AND t.finished_at >= ? AND t.finished_at < ?
+            ORDER BY t.finished_at ASC
+            "#,
+        )
+        .bind(fid)
+        .bind(period_start)
+        .bind(period_end)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut attunement = RollupAttunement {
+            threshold_changes,
+            trials_started,
+            ..Default::default()
+        };
+        for row in rows {
+            let status: String = row.try_get("status")?;
+            match status.as_str() {
+                "promoted" => attunement.trials_promoted += 1,
+                "rolled_back" => attunement.trials_rolled_back += 1,
+                "aborted" => attunement.trials_aborted += 1,
+                _ => {}
+            }
+            let finished_at: DateTime<Utc> = row.try_get("finished_at")?;
+            attunement.decisions.push(RollupTrialDecision {
+                trial_id: row.try_get("trial_id")?,
+                camera_id: row.try_get("camera_id")?,
+                parameter: row.try_get("parameter")?,
+                status,
+                baseline_value: row.try_get("effective_baseline")?,
+                candidate_value: row.try_get("candidate_value")?,
+                finished_at: finished_at.to_rfc3339(),
+            });
+        }
+        Ok(attunement)
+    }
+}
+
+/// Paraclateペイロード種別名（summary_json.type）
+pub fn rollup_payload_type(summary_type: SummaryType) -> &'static str {
+    match summary_type {
+        SummaryType::Monthly => "monthly_rollup",
+        _ => "weekly_rollup",
+    }
+}
+
+fn jst_midnight(date: NaiveDate) -> DateTime<Utc> {
+    Tokyo
+        .from_local_datetime(&date.and_time(NaiveTime::MIN))
+        .single()
+        .expect("Asia/Tokyo has no DST")
+        .with_timezone(&Utc)
+}
+
+/// 期間の開始日（JST）: Weekly = 当週月曜 / Monthly = 当月1日
+fn period_start_date(summary_type: SummaryType, date: NaiveDate) -> Option<NaiveDate> {
+    match summary_type {
+        SummaryType::Weekly => {
+            Some(date - Duration::days(date.weekday().num_days_from_monday() as i64))
+        }
+        SummaryType::Monthly => date.with_day(1),
+        _ => None,
+    }
+}
+
+/// 次の期間の開始日（JST）
+fn next_period_date(summary_type: SummaryType, start: NaiveDate) -> NaiveDate {
+    match summary_type {
+        SummaryType::Monthly => start
+            .checked_add_months(chrono::Months::new(1))
+            .unwrap_or(start + Duration::days(31)),
+        _ => start + Duration::days(7),
+    }
+}
+
+/// `now` の直前に完了したロールアップ期間（UTC）
+pub fn rollup_period(
+    summary_type: SummaryType,
+    now: DateTime<Utc>,
+) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    let current_start = period_start_date(summary_type, now.with_timezone(&Tokyo).date_naive())?;
+    let (start, end) = previous_period(summary_type, jst_midnight(current_start));
+    Some((start, end))
+}
+
+/// 比較対象の前期間（`period_start` の直前の同種期間）
+pub fn previous_period(
+    summary_type: SummaryType,
+    period_start: DateTime<Utc>,
+) -> (DateTime<Utc>, DateTime<Utc>) {
+    let start_date = period_start.with_timezone(&Tokyo).date_naive();
+    let prev = match summary_type {
+        SummaryType::Monthly => start_date
+            .checked_sub_months(chrono::Months::new(1))
+            .unwrap_or(start_date - Duration::days(30)),
+        _ => start_date - Duration::days(7),
+    };
+    (jst_midnight(prev), period_start)
+}
+
+/// 次回ロールアップ実行時刻（JST: 週次=月曜 / 月次=1日 の `run_time`）
+pub fn calculate_next_rollup_time(
+    summary_type: SummaryType,
+    run_time: &str,
+    now: DateTime<Utc>,
+) -> crate::Result<DateTime<Utc>> {
+    let time = NaiveTime::parse_from_str(run_time, "%H:%M")
+        .map_err(|e| crate::Error::Parse(format!("Invalid time format: {}", e)))?;
+    let start = period_start_date(summary_type, now.with_timezone(&Tokyo).date_naive())
+        .ok_or_else(|| crate::Error::Validation(format!("{} is not a rollup type", summary_type)))?;
+
+    let at = |date: NaiveDate| -> crate::Result<DateTime<Utc>> {
+        Tokyo
+            .from_local_datetime(&date.and_time(time))
+            .single()
+            .map(|t| t.with_timezone(&Utc))
+            .ok_or_else(|| crate::Error::Parse("Invalid timezone conversion".into()))
+    };
+
+    let candidate = at(start)?;
+    if candidate > now {
+        Ok(candidate)
+    } else {
+        at(next_period_date(summary_type, start))
+    }
+}
+
+/// 前期間比
+pub fn compute_trend(current: i64, previous: i64) -> RollupTrend {
+    let detection_delta = current - previous;
+    let detection_change_pct =
+        (previous > 0).then(|| round1(detection_delta as f64 * 100.0 / previous as f64));
+    let direction = match detection_change_pct {
+        Some(pct) if pct.abs() < FLAT_THRESHOLD_PCT => "flat",
+        _ if detection_delta > 0 => "up",
+        _ if detection_delta < 0 => "down",
+        _ => "flat",
+    };
+    RollupTrend {
+        detection_delta,
+        detection_change_pct,
+        direction: direction.to_string(),
+    }
+}
+
+/// hourly SummaryをJST日付別に集計（検出0件の日も含める）
+pub fn daily_counts(
+    hourly: &[SummaryResult],
+    period_start: DateTime<Utc>,
+    period_end: DateTime<Utc>,
+) -> Vec<RollupDailyCount> {
+    let mut counts: BTreeMap<NaiveDate, i64> = BTreeMap::new();
+    let mut date = period_start.with_timezone(&Tokyo).date_naive();
+    while jst_midnight(date) < period_end {
+        counts.insert(date, 0);
+        date += Duration::days(1);
+    }
+    for summary in hourly {
+        let date = summary.period_start.with_timezone(&Tokyo).date_naive();
+        if let Some(count) = counts.get_mut(&date) {
+            *count += summary.detection_count as i64;
+        }
+    }
+    counts
+        .into_iter()
+        .map(|(date, detection_count)| RollupDailyCount {
+            date: date.format("%Y-%m-%d").to_string(),
+            detection_count,
+        })
+        .collect()
+}
+
+fn average_uptime(rows: &[RollupCameraUptime]) -> f64 {
+    if rows.is_empty() {
+        return 100.0;
+    }
+    round1(rows.iter().map(|r| r.uptime_pct).sum::<f64>() / rows.len() as f64)
+}
+
+fn round1(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+/// serde(rename_all = "snake_case") の列挙値を文字列化
+fn enum_str<T: serde::Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|v| v.as_str().map(String::from))
+        .unwrap_or_default()
+}
+
+/// ロールアップのsummary_text
+pub fn render_rollup_text(
+    locale: SummaryLocale,
+    summary_type: SummaryType,
+    payload: &RollupPayload,
+) -> String {
+    let period = |s: &str| {
+        DateTime::parse_from_rfc3339(s)
+            .map(|t| t.with_timezone(&Tokyo).format("%Y-%m-%d").to_string())
+            .unwrap_or_default()
+    };
+    let start = period(&payload.period_start);
+    // 期間終了は排他的境界なので表示は前日まで
+    let end = DateTime::parse_from_rfc3339(&payload.period_end)
+        .map(|t| (t.with_timezone(&Tokyo) - Duration::days(1)).format("%Y-%m-%d").to_string())
+        .unwrap_or_default();
+    let totals = &payload.totals;
+    let trend = &payload.trend;
+    let pct = trend
+        .detection_change_pct
+        .map(|p| format!("{:+.1}%", p))
+        .unwrap_or_else(|| "-".to_string());
+    let low_uptime: Vec<String> = payload
+        .camera_uptime
+        .iter()
+        .filter(|c| c.uptime_pct < 99.0)
+        .take(3)
+        .map(|c| format!("{} {:.1}%", c.camera_name, c.uptime_pct))
+        .collect();
+    let att = &payload.attunement;
+
+    let mut lines = Vec::new();
+    match locale {
+        SummaryLocale::Ja => {
+            let title = if summary_type == SummaryType::Monthly { "月次" } else { "週次" };
+            let direction = match trend.direction.as_str() {
+                "up" => "増加",
+                "down" => "減少",
+                _ => "横ばい",
+            };
+            lines.push(format!("【{}レポート】{} 〜 {}", title, start, end));
+            lines.push(format!(
+                "検出 {}件（前期間 {}件, {} {}）/ 最大severity {} / 稼働カメラ {}台",
+                totals.detection_count,
+                payload.previous_totals.detection_count,
+                pct,
+                direction,
+                totals.severity_max,
+                totals.camera_count
+            ));
+            lines.push(format!("平均稼働率 {:.1}%", payload.average_uptime_pct));
+            if !low_uptime.is_empty() {
+                lines.push(format!("稼働率低下: {}", low_uptime.join(", ")));
+            }
+            if !payload.overdetection_issues.is_empty() {
+                let issues: Vec<String> = payload
+                    .overdetection_issues
+                    .iter()
+                    .map(|i| {
+                        format!("{} {}({}件)", i.camera_name.as_deref().unwrap_or(&i.camera_id), i.issue_type, i.count)
+                    })
+                    .collect();
+                lines.push(format!("過剰検出: {}", issues.join(", ")));
+            }
+            lines.push(format!(
+                "誤検知報告 {}件（前期間 {}件）/ 閾値変更 {}件 / trial 採用{}・ロールバック{}",
+                payload.feedback.total,
+                payload.feedback.previous_total,
+                att.threshold_changes,
+                att.trials_promoted,
+                att.trials_rolled_back
+            ));
+        }
+        SummaryLocale::En => {
+            let title = if summary_type == SummaryType::Monthly { "Monthly" } else { "Weekly" };
+            lines.push(format!("[{} report] {} - {}", title, start, end));
+            lines.push(format!(
+                "{} detections (previous {}, {} {}) / max severity {} / {} active cameras",
+                totals.detection_count,
+                payload.previous_totals.detection_count,
+                pct,
+                trend.direction,
+                totals.severity_max,
+                totals.camera_count
+            ));
+            lines.push(format!("Average uptime {:.1}%", payload.average_uptime_pct));
+            if !low_uptime.is_empty() {
+                lines.push(format!("Low uptime: {}", low_uptime.join(", ")));
+            }
+            if !payload.overdetection_issues.is_empty() {
+                let issues: Vec<String> = payload
+                    .overdetection_issues
+                    .iter()
+                    .map(|i| {
+                        format!("{} {} ({})", i.camera_name.as_deref().unwrap_or(&i.camera_id), i.issue_type, i.count)
+                    })
+                    .collect();
+                lines.push(format!("Overdetection: {}", issues.join(", ")));
+            }
+            lines.push(format!(
+                "{} misdetection reports (previous {}) / {} threshold changes / trials promoted {}, rolled back {}",
+                payload.feedback.total,
+                payload.feedback.previous_total,
+                att.threshold_changes,
+                att.trials_promoted,
+                att.trials_rolled_back
+            ));
+        }
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Timelike;
+
+    #[test]
+    fn test_rollup_period_weekly() {
+        // 2026-01-14 (Wed) 10:00 JST
+        let now = Utc.with_ymd_and_hms(2026, 1, 14, 1, 0, 0).unwrap();
+        let (start, end) = rollup_period(SummaryType::Weekly, now).unwrap();
+        // 2026-01-05 (Mon) 00:00 JST 〜 2026-01-12 (Mon) 00:00 JST
+        assert_eq!(start, Utc.with_ymd_and_hms(2026, 1, 4, 15, 0, 0).unwrap());
+        assert_eq!(end, Utc.with_ymd_and_hms(2026, 1, 11, 15, 0, 0).unwrap());
+        assert!(rollup_period(SummaryType::Daily, now).is_none());
+    }
+
+    #[test]
+    fn test_rollup_period_monthly() {
+        // 2026-03-01 06:00 JST（JSTでは3月、UTCでは2月）
+        let now = Utc.with_ymd_and_hms(2026, 2, 28, 21, 0, 0).unwrap();
+        let (start, end) = rollup_period(SummaryType::Monthly, now).unwrap();
+        assert_eq!(start, Utc.with_ymd_and_hms(2026, 1, 31, 15, 0, 0).unwrap());
+        assert_eq!(end, Utc.with_ymd_and_hms(2026, 2, 28, 15, 0, 0).unwrap());
+
+        let (prev_start, prev_end) = previous_period(SummaryType::Monthly, start);
+        assert_eq!(prev_start, Utc.with_ymd_and_hms(2025, 12, 31, 15, 0, 0).unwrap());
+        assert_eq!(prev_end, start);
+    }
+
+    #[test]
+    fn test_calculate_next_rollup_time() {
+        // 2026-01-12 (Mon) 05:00 JST → 同日 06:00 JST
+        let now = Utc.with_ymd_and_hms(2026, 1, 11, 20, 0, 0).unwrap();
+        let next = calculate_next_rollup_time(SummaryType::Weekly, "06:00", now).unwrap();
+        assert_eq!(next, Utc.with_ymd_and_hms(2026, 1, 11, 21, 0, 0).unwrap());
+
+        // 2026-01-12 (Mon) 07:00 JST → 翌週月曜 06:00 JST
+        let now = Utc.with_ymd_and_hms(2026, 1, 11, 22, 0, 0).unwrap();
+        let next = calculate_next_rollup_time(SummaryType::Weekly, "06:00", now).unwrap();
+        assert_eq!(next, Utc.with_ymd_and_hms(2026, 1, 18, 21, 0, 0).unwrap());
+
+        // 2026-01-20 JST → 2026-02-01 06:00 JST
+        let now = Utc.with_ymd_and_hms(2026, 1, 20, 0, 0, 0).unwrap();
+        let next = calculate_next_rollup_time(SummaryType::Monthly, "06:00", now).unwrap();
+        assert_eq!(next, Utc.with_ymd_and_hms(2026, 1, 31, 21, 0, 0).unwrap());
+        assert_eq!(next.with_timezone(&Tokyo).hour(), 6);
+
+        assert!(calculate_next_rollup_time(SummaryType::Weekly, "25:00", now).is_err());
+    }
+
+    #[test]
+    fn test_compute_trend() {
+        let up = compute_trend(120, 100);
+        assert_eq!(up.direction, "up");
+        assert_eq!(up.detection_change_pct, Some(20.0));
+
+        assert_eq!(compute_trend(80, 100).direction, "down");
+        assert_eq!(compute_trend(102, 100).direction, "flat");
+
+        let from_zero = compute_trend(5, 0);
+        assert_eq!(from_zero.direction, "up");
+        assert_eq!(from_zero.detection_change_pct, None);
+        assert_eq!(compute_trend(0, 0).direction, "flat");
+    }
+
+    #[test]
+    fn test_daily_counts() {
+        let start = jst_midnight(NaiveDate::from_ymd_opt(2026, 1, 5).unwrap());
+        let end = start + Duration::days(7);
+        let hourly = |at: DateTime<Utc>, count: i32| SummaryResult {
+            summary_id: 1,
+            tid: "T".to_string(),
+            fid: "0150".to_string(),
+            summary_type: SummaryType::Hourly,
+            period_start: at,
+            period_end: at + Duration::hours(1),
+            summary_text: String::new(),
+            summary_json: None,
+            detection_count: count,
+            severity_max: 0,
+            camera_ids: vec![],
+            created_at: at,
+            expires_at: at,
+        };
+        let summaries = vec![
+            hourly(start, 3),
+            hourly(start + Duration::hours(23), 2),
+            hourly(start + Duration::days(6), 4),
+        ];
+
+        let daily = daily_counts(&summaries, start, end);
+        assert_eq!(daily.len(), 7);
+        assert_eq!(daily[0].date, "2026-01-05");
+        assert_eq!(daily[0].detection_count, 5);
+        assert_eq!(daily[1].detection_count, 0);
+        assert_eq!(daily[6].detection_count, 4);
+    }
+}
@@ -13,13 +13,19 @@
 
 use super::generator::SummaryGenerator;
 use super::grand_summary::{calculate_next_grand_summary_time, GrandSummaryGenerator};
+use super::report::{ReportFormat, ReportService};
 use super::repository::ScheduleRepository;
-use super::types::{ReportSchedule, ReportType};
+use super::rollup::{calculate_next_rollup_time, RollupGenerator, DEFAULT_ROLLUP_TIME};
+use super::types::{ReportSchedule, ReportType, SummaryType};
+use crate::email_notifier::EmailNotifier;
+use crate::health::{stall_threshold, HealthRegistry};
+use crate::paraclate_client::types::PayloadType;
 use crate::paraclate_client::ParaclateClient;
 use crate::realtime_hub::{HubMessage, RealtimeHub, SummaryReportMessage};
 use chrono::{DateTime, Duration, Utc};
 use std::sync::Arc;
 use tokio::time::Duration as TokioDuration;
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, warn};
 
 /// Summary/GrandSummaryスケジューラ
@@ -30,30 +36,51 @@ pub struct SummaryScheduler {
     summary_generator: Arc<SummaryGenerator>,
     /// GrandSummary生成サービス
     grand_summary_generator: Arc<GrandSummaryGenerator>,
+    /// 週次・月次ロールアップ生成サービス
+    rollup_generator: Arc<RollupGenerator>,
     /// Paraclate APPクライアント（Ingest API送信用）
     paraclate_client: Arc<ParaclateClient>,
     /// RealtimeHub（チャットへの報告用）
     realtime: Arc<RealtimeHub>,
+    /// 印刷用レポート生成
+    report_service: Arc<ReportService>,
+    /// メール配信（購読者へのレポート送信）
+    email_notifier: Arc<EmailNotifier>,
     /// チェック間隔（秒）
     tick_interval_secs: u64,
+    /// ハートビート記録先（/livez の停止検知）
+    health: Option<Arc<HealthRegistry>>,
 }
 
+/// Heartbeat name of the scheduler loop
+const HEALTH_LOOP_NAME: &str = "summary_scheduler";
+/// Summary生成・メール送信でティックが長引くため停止判定は最低10分
+const MIN_STALL_AFTER: TokioDuration = TokioDuration::from_secs(600);
+
 impl SummaryScheduler {
     /// 新しいSummarySchedulerを作成
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         schedule_repository: ScheduleRepository,
         summary_generator: Arc<SummaryGenerator>,
         grand_summary_generator: Arc<GrandSummaryGenerator>,
+        rollup_generator: Arc<RollupGenerator>,
         paraclate_client: Arc<ParaclateClient>,
         realtime: Arc<RealtimeHub>,
+        report_service: Arc<ReportService>,
+        email_notifier: Arc<EmailNotifier>,
     ) -> Self {
         Self {
             schedule_repository,
             summary_generator,
             grand_summary_generator,
+            rollup_generator,
             paraclate_client,
             realtime,
+            report_service,
+            email_notifier,
             tick_interval_secs: 60, // 1分間隔
+            health: None,
         }
     }
 
@@ -63,19 +90,44 @@ impl SummaryScheduler {
         self
     }
 
-    /// スケジューラ起動（バックグラウンドタスク）
-    pub async fn start(self: Arc<Self>) {
+    /// ティック毎のハートビートを記録
+    pub fn with_health(mut self, health: Arc<HealthRegistry>) -> Self {
+        self.health = Some(health);
+        self
+    }
+
+    /// スケジューラループ本体（`shutdown` がキャンセルされるまで）
+    ///
+    /// TaskSupervisor から起動する
+    pub async fn run(self: Arc<Self>, shutdown: CancellationToken) {
         info!("Summary scheduler started");
 
-        tokio::spawn(async move {
-            loop {
-                if let Err(e) = self.tick().await {
-                    error!(error = %e, "Scheduler tick error");
+        if let Some(health) = &self.health {
+            health.register(
+                HEALTH_LOOP_NAME,
+                stall_threshold(TokioDuration::from_secs(self.tick_interval_secs))
+                    .max(MIN_STALL_AFTER),
+            );
+        }
+
+        loop {
+            let result = self.tick().await;
+            if let Err(e) = &result {
+                error!(error = %e, "Scheduler tick error");
+            }
+            if let Some(health) = &self.health {
+                match &result {
+                    Ok(()) => health.tick_ok(HEALTH_LOOP_NAME),
+                    Err(e) => health.tick_failed(HEALTH_LOOP_NAME, e),
                 }
+            }
 
-                tokio::time::sleep(TokioDuration::from_secs(self.tick_interval_secs)).await;
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = tokio::time::sleep(TokioDuration::from_secs(self.tick_interval_secs)) => {}
             }
-        });
+        }
+        info!("Summary scheduler stopped");
     }
 
     /// 1回のスケジューラティック
@@ -112,12 +164,64 @@ impl SummaryScheduler {
                         );
                     }
                 }
+                ReportType::WeeklyRollup | ReportType::MonthlyRollup => {
+                    if let Err(e) = self.execute_rollup(&schedule, now).await {
+                        error!(
+                            schedule_id = schedule.schedule_id,
+                            report_type = %schedule.report_type,
+                            error = %e,
+                            "Failed to execute rollup"
+                        );
+                    }
+                }
             }
         }
 
         Ok(())
     }
 
+    /// スケジュールにreport_formatがあればレポートを生成・保存し、ダウンロードURLを返す
+    ///
+    /// 生成失敗はSummary実行自体を失敗させない
+    async fn attach_report(&self, schedule: &ReportSchedule, summary_id: u64) -> Option<String> {
+        let format = ReportFormat::parse(schedule.report_format.as_deref()?)?;
+        match self.report_service.render_and_store(summary_id, format).await {
+            Ok(_) => Some(format!(
+                "/api/paraclate/summary/{}/report?format={}",
+                summary_id,
+                format.as_str()
+            )),
+            Err(e) => {
+                warn!(
+                    error = %e,
+                    summary_id = summary_id,
+                    format = format.as_str(),
+                    "Failed to generate scheduled report"
+                );
+                None
+            }
+        }
+    }
+
+    /// 購読者へのレポートメールをキュー登録
+    ///
+    /// キュー登録失敗はSummary実行自体を失敗させない
+    async fn enqueue_report_email(&self, schedule: &ReportSchedule, summary_id: u64, report_type: &str) {
+        let attach_pdf = schedule.report_format.as_deref() == Some(ReportFormat::Pdf.as_str());
+        if let Err(e) = self
+            .email_notifier
+            .enqueue_report(&schedule.tid, &schedule.fid, summary_id, report_type, attach_pdf)
+            .await
+        {
+            warn!(
+                error = %e,
+                summary_id = summary_id,
+                report_type = report_type,
+                "Failed to enqueue report email"
+            );
+        }
+    }
+
     /// Summary実行
     async fn execute_summary(
         &self,
@@ -205,6 +309,10 @@ impl SummaryScheduler {
             }
         };
 
+        // 印刷用レポート添付（report_format設定時）
+        let report_url = self.attach_report(schedule, result.summary_id).await;
+        self.enqueue_report_email(schedule, result.summary_id, "summary").await;
+
         // チャットへ報告（RealtimeHub経由）
         // LLMサマリーがある場合のみ送信（IS22ローカル生成サマリーは使用しない）
         if let Some(llm_text) = llm_summary_text {
@@ -218,6 +326,7 @@ impl SummaryScheduler {
                 camera_count: result.camera_ids.len(),
                 summary_text: llm_text,
                 created_at: Utc::now().to_rfc3339(),
+                report_url,
             })).await;
         } else {
             warn!(
@@ -336,6 +445,10 @@ impl SummaryScheduler {
             }
         };
 
+        // 印刷用レポート添付（report_format設定時）
+        let report_url = self.attach_report(schedule, result.summary_id).await;
+        self.enqueue_report_email(schedule, result.summary_id, "grand_summary").await;
+
         // チャットへ報告（RealtimeHub経由）
         // LLMサマリーがある場合のみ送信（IS22ローカル生成サマリーは使用しない）
         if let Some(llm_text) = llm_summary_text {
@@ -349,6 +462,7 @@ impl SummaryScheduler {
                 camera_count: result.camera_ids.len(),
                 summary_text: llm_text,
                 created_at: Utc::now().to_rfc3339(),
+                report_url,
             })).await;
         } else {
             warn!(
@@ -382,6 +496,76 @@ impl SummaryScheduler {
         Ok(())
     }
 
+    /// 週次・月次ロールアップ実行（直前に完了した期間を集計）
+    async fn execute_rollup(&self, schedule: &ReportSchedule, now: DateTime<Utc>) -> crate::Result<()> {
+        let (summary_type, payload_type) = match schedule.report_type {
+            ReportType::MonthlyRollup => (SummaryType::Monthly, PayloadType::MonthlyRollup),
+            _ => (SummaryType::Weekly, PayloadType::WeeklyRollup),
+        };
+
+        info!(
+            tid = %schedule.tid,
+            fid = %schedule.fid,
+            summary_type = %summary_type,
+            "Executing scheduled rollup"
+        );
+
+        let result = self
+            .rollup_generator
+            .generate_latest(&schedule.tid, &schedule.fid, summary_type, now)
+            .await?;
+
+        // Paraclate APPへ送信（Ingest API）
+        if let Some(json) = &result.summary_json {
+            match self
+                .paraclate_client
+                .send_rollup(&result.tid, &result.fid, payload_type, json.clone(), result.summary_id)
+                .await
+            {
+                Ok(queue_id) => {
+                    info!(
+                        queue_id = queue_id,
+                        summary_id = result.summary_id,
+                        "Rollup queued for Paraclate APP"
+                    );
+                }
+                Err(e) => {
+                    // 送信失敗してもロールアップ生成自体は成功扱い
+                    warn!(
+                        error = %e,
+                        summary_id = result.summary_id,
+                        "Failed to queue rollup for Paraclate APP"
+                    );
+                }
+            }
+        }
+
+        self.attach_report(schedule, result.summary_id).await;
+        self.enqueue_report_email(schedule, result.summary_id, &schedule.report_type.to_string())
+            .await;
+
+        // 次回実行時刻（scheduled_times[0] = JST実行時刻）
+        let run_time = schedule
+            .scheduled_times
+            .as_ref()
+            .and_then(|t| t.first().cloned())
+            .unwrap_or_else(|| DEFAULT_ROLLUP_TIME.to_string());
+        let next_run = calculate_next_rollup_time(summary_type, &run_time, now)?;
+
+        self.schedule_repository
+            .update_last_run(schedule.schedule_id, now, next_run)
+            .await?;
+
+        info!(
+            summary_id = result.summary_id,
+            detection_count = result.detection_count,
+            next_run = %next_run,
+            "Rollup executed successfully"
+        );
+
+        Ok(())
+    }
+
     /// デフォルトスケジュールを初期化
     pub async fn init_default_schedules(&self, tid: &str, fid: &str) -> crate::Result<()> {
         let now = Utc::now();
@@ -394,6 +578,7 @@ impl SummaryScheduler {
             report_type: ReportType::Summary,
             interval_minutes: Some(60),
             scheduled_times: None,
+            report_format: None,
             last_run_at: None,
             next_run_at: Some(now + Duration::hours(1)),
             enabled: true,
@@ -426,12 +611,37 @@ impl SummaryScheduler {
             report_type: ReportType::GrandSummary,
             interval_minutes: None,
             scheduled_times: Some(scheduled_times),
+            report_format: None,
             last_run_at: None,
             next_run_at: Some(next_grand),
             enabled: true,
         };
         self.schedule_repository.upsert(&grand_summary_schedule).await?;
 
+        // 週次・月次ロールアップ（既存設定は保持）
+        let existing = self.schedule_repository.get_schedules(tid, fid).await?;
+        for (report_type, summary_type) in [
+            (ReportType::WeeklyRollup, SummaryType::Weekly),
+            (ReportType::MonthlyRollup, SummaryType::Monthly),
+        ] {
+            if existing.iter().any(|s| s.report_type == report_type) {
+                continue;
+            }
+            let rollup_schedule = ReportSchedule {
+                schedule_id: 0,
+                tid: tid.to_string(),
+                fid: fid.to_string(),
+                report_type,
+                interval_minutes: None,
+                scheduled_times: Some(vec![DEFAULT_ROLLUP_TIME.to_string()]),
+                report_format: None,
+                last_run_at: None,
+                next_run_at: Some(calculate_next_rollup_time(summary_type, DEFAULT_ROLLUP_TIME, now)?),
+                enabled: true,
+            };
+            self.schedule_repository.upsert(&rollup_schedule).await?;
+        }
+
         info!(
             tid = %tid,
             fid = %fid,
@@ -0,0 +1,752 @@
+//! Summary Templates
+//!
+//! summary_text / GrandSummary テキストのテンプレート化とローカライズ
+//!
+//! ## テンプレート構文
+//! - `{{variable}}` - 変数展開（`TEMPLATE_VARIABLES` 参照）
+//! - `{{#if variable}}...{{else}}...{{/if}}` - 変数が空/"0"でなければ前半を出力（ネスト可）
+//!
+//! ## 選択順序
+//! 1. fid指定・locale一致・有効なテンプレート
+//! 2. 全施設共通（fid = NULL）・locale一致・有効なテンプレート
+//! 3. 組み込みデフォルト（`default_template`）
+
+use crate::camera_registry::ContextMapEntry;
+use crate::detection_log_service::DetectionLog;
+use chrono::{DateTime, Timelike, Utc};
+use chrono_tz::Asia::Tokyo;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// 注目インシデントとして扱う最小severity
+pub const NOTABLE_SEVERITY: i32 = 3;
+
+/// 各リストの最大件数
+const TOP_EVENTS_LIMIT: usize = 5;
+const PEAK_HOURS_LIMIT: usize = 3;
+const INCIDENTS_LIMIT: usize = 5;
+
+/// 接続状態イベント（検出イベントの集計から除外）
+const CONNECTION_EVENTS: [&str; 3] = ["camera_lost", "connection_lost", "camera_recovered"];
+
+/// テンプレートで使用可能な変数
+pub const TEMPLATE_VARIABLES: [&str; 12] = [
+    "fid",
+    "period",
+    "duration",
+    "total",
+    "camera_count",
+    "severity_max",
+    "summary_count",
+    "camera_breakdown",
+    "top_events",
+    "peak_hours",
+    "offline_cameras",
+    "incidents",
+];
+
+/// Summary locale
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SummaryLocale {
+    #[default]
+    Ja,
+    En,
+}
+
+impl SummaryLocale {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Ja => "ja",
+            Self::En => "en",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "ja" | "ja-jp" => Some(Self::Ja),
+            "en" | "en-us" | "en-gb" => Some(Self::En),
+            _ => None,
+        }
+    }
+}
+
+/// Template kind
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TemplateKind {
+    Summary,
+    GrandSummary,
+}
+
+impl TemplateKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Summary => "summary",
+            Self::GrandSummary => "grand_summary",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "summary" => Some(Self::Summary),
+            "grand_summary" => Some(Self::GrandSummary),
+            _ => None,
+        }
+    }
+}
+
+/// fid別のlocale設定（settings.summary_locale）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SummaryLocaleSettings {
+    pub default_locale: SummaryLocale,
+    pub fid_locales: HashMap<String, SummaryLocale>,
+}
+
+impl SummaryLocaleSettings {
+    pub fn locale_for(&self, fid: &str) -> SummaryLocale {
+        self.fid_locales.get(fid).copied().unwrap_or(self.default_locale)
+    }
+}
+
+/// 保存済みテンプレート（summary_templates）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SummaryTemplate {
+    pub template_id: u64,
+    /// None = 全施設共通
+    pub fid: Option<String>,
+    pub locale: SummaryLocale,
+    pub kind: TemplateKind,
+    pub name: String,
+    pub body: String,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// テンプレート作成/更新リクエスト
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpsertSummaryTemplateRequest {
+    pub fid: Option<String>,
+    pub locale: SummaryLocale,
+    pub kind: TemplateKind,
+    pub name: String,
+    pub body: String,
+    /// 既定false（プレビュー確認後に有効化）
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+// ============================================================
+// Facts (locale-independent)
+// ============================================================
+
+/// カメラ別集計
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CameraFact {
+    pub camera_id: String,
+    pub name: String,
+    pub location: Option<String>,
+    pub detection_count: i64,
+    pub severity_max: i32,
+}
+
+/// 期間終了時点でオフラインのカメラ
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OfflineFact {
+    pub name: String,
+    pub reason: String,
+    pub since: DateTime<Utc>,
+}
+
+/// 注目すべき高severity検出
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IncidentFact {
+    pub captured_at: DateTime<Utc>,
+    pub camera_name: String,
+    pub event: String,
+    pub severity: i32,
+}
+
+/// テンプレート展開の元になる集計結果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SummaryFacts {
+    pub fid: String,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    pub total_detections: i64,
+    pub severity_max: i32,
+    /// 統合したhourly Summary数（GrandSummaryのみ）
+    pub summary_count: usize,
+    /// 検出数の多い順
+    pub cameras: Vec<CameraFact>,
+    pub top_events: Vec<(String, i64)>,
+    /// (JST hour, count) 検出数の多い順
+    pub peak_hours: Vec<(u32, i64)>,
+    pub offline_cameras: Vec<OfflineFact>,
+    pub incidents: Vec<IncidentFact>,
+}
+
+impl SummaryFacts {
+    /// 検出ログから集計（context_map は lacisID → エントリ）
+    pub fn from_logs(
+        fid: &str,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+        logs: &[DetectionLog],
+        context_map: &HashMap<String, ContextMapEntry>,
+    ) -> Self {
+        let by_camera_id: HashMap<&str, &ContextMapEntry> = context_map
+            .values()
+            .map(|e| (e.camera_id.as_str(), e))
+            .collect();
+        let resolve = |log: &DetectionLog| -> (String, Option<&ContextMapEntry>) {
+            let entry = log
+                .camera_lacis_id
+                .as_ref()
+                .and_then(|id| context_map.get(id))
+                .or_else(|| by_camera_id.get(log.camera_id.as_str()).copied());
+            (log.camera_id.clone(), entry)
+        };
+
+        let mut cameras: HashMap<String, CameraFact> = HashMap::new();
+        let mut events: HashMap<String, i64> = HashMap::new();
+        let mut hours: HashMap<u32, i64> = HashMap::new();
+        let mut connection: HashMap<String, &DetectionLog> = HashMap::new();
+        let mut incidents = Vec::new();
+        let mut total = 0i64;
+        let mut severity_max = 0i32;
+
+        for log in logs {
+            let (camera_id, entry) = resolve(log);
+            let name = entry.map(|e| e.name.clone()).unwrap_or_else(|| camera_id.clone());
+
+            if CONNECTION_EVENTS.contains(&log.primary_event.as_str()) {
+                let latest = connection.entry(camera_id).or_insert(log);
+                if log.captured_at >= latest.captured_at {
+                    *latest = log;
+                }
+                continue;
+            }
+
+            total += 1;
+            severity_max = severity_max.max(log.severity);
+
+            let fact = cameras.entry(camera_id.clone()).or_insert_with(|| CameraFact {
+                camera_id,
+                name: name.clone(),
+                location: entry.and_then(|e| e.location.clone()),
+                detection_count: 0,
+                severity_max: 0,
+            });
+            fact.detection_count += 1;
+            fact.severity_max = fact.severity_max.max(log.severity);
+
+            if log.primary_event != "none" {
+                *events.entry(log.primary_event.clone()).or_default() += 1;
+            }
+            *hours.entry(log.captured_at.with_timezone(&Tokyo).hour()).or_default() += 1;
+
+            if log.severity >= NOTABLE_SEVERITY {
+                incidents.push(IncidentFact {
+                    captured_at: log.captured_at,
+                    camera_name: name,
+                    event: log.primary_event.clone(),
+                    severity: log.severity,
+                });
+            }
+        }
+
+        let mut cameras: Vec<CameraFact> = cameras.into_values().collect();
+        cameras.sort_by(|a, b| b.detection_count.cmp(&a.detection_count).then(a.name.cmp(&b.name)));
+
+        let mut top_events: Vec<(String, i64)> = events.into_iter().collect();
+        top_events.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        top_events.truncate(TOP_EVENTS_LIMIT);
+
+        let mut peak_hours: Vec<(u32, i64)> = hours.into_iter().collect();
+        peak_hours.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        peak_hours.truncate(PEAK_HOURS_LIMIT);
+
+        // severity降順 → 時刻順
+        incidents.sort_by(|a, b| b.severity.cmp(&a.severity).then(a.captured_at.cmp(&b.captured_at)));
+        incidents.truncate(INCIDENTS_LIMIT);
+
+        // 最後の接続イベントが復旧以外のカメラのみオフライン扱い
+        let mut offline_cameras: Vec<OfflineFact> = connection
+            .into_iter()
+            .filter(|(_, log)| log.primary_event != "camera_recovered")
+            .map(|(camera_id, log)| {
+                let (_, entry) = resolve(log);
+                OfflineFact {
+                    name: entry.map(|e| e.name.clone()).unwrap_or(camera_id),
+                    reason: log.primary_event.clone(),
+                    since: log.captured_at,
+                }
+            })
+            .collect();
+        offline_cameras.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Self {
+            fid: fid.to_string(),
+            period_start,
+            period_end,
+            total_detections: total,
+            severity_max,
+            summary_count: 0,
+            cameras,
+            top_events,
+            peak_hours,
+            offline_cameras,
+            incidents,
+        }
+    }
+
+    /// 検出なしの集計
+    pub fn empty(fid: &str, period_start: DateTime<Utc>, period_end: DateTime<Utc>) -> Self {
+        Self::from_logs(fid, period_start, period_end, &[], &HashMap::new())
+    }
+
+    /// ロケールに応じてテンプレート変数を構築
+    pub fn variables(&self, locale: SummaryLocale) -> HashMap<&'static str, String> {
+        let start = self.period_start.with_timezone(&Tokyo);
+        let end = self.period_end.with_timezone(&Tokyo);
+        let same_day = start.date_naive() == end.date_naive();
+        let period = if same_day {
+            format!("{} 〜 {}", start.format("%m/%d %H:%M"), end.format("%H:%M"))
+        } else {
+            format!("{} 〜 {}", start.format("%m/%d %H:%M"), end.format("%m/%d %H:%M"))
+        };
+
+        let minutes = (self.period_end - self.period_start).num_minutes();
+        let duration = match (locale, minutes >= 60) {
+            (SummaryLocale::Ja, true) => format!("{}時間", minutes / 60),
+            (SummaryLocale::Ja, false) => format!("{}分", minutes),
+            (SummaryLocale::En, true) if minutes / 60 == 1 => "1 hour".to_string(),
+            (SummaryLocale::En, true) => format!("{} hours", minutes / 60),
+            (SummaryLocale::En, false) => format!("{} minutes", minutes),
+        };
+
+        let camera_breakdown = self
+            .cameras
+            .iter()
+            .map(|c| {
+                let label = match &c.location {
+                    Some(loc) if !loc.is_empty() => format!("{} ({})", c.name, loc),
+                    _ => c.name.clone(),
+                };
+                match locale {
+                    SummaryLocale::Ja => format!(
+                        "- {}: {}件 (最大severity: {})",
+                        label, c.detection_count, c.severity_max
+                    ),
+                    SummaryLocale::En => format!(
+                        "- {}: {} detections (max severity: {})",
+                        label, c.detection_count, c.severity_max
+                    ),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let top_events = self
+            .top_events
+            .iter()
+            .map(|(event, count)| match locale {
+                SummaryLocale::Ja => format!("{} {}件", event_label(event, locale), count),
+                SummaryLocale::En => format!("{} {}", event_label(event, locale), count),
+            })
+            .collect::<Vec<_>>()
+            .join(list_separator(locale));
+
+        let peak_hours = self
+            .peak_hours
+            .iter()
+            .map(|(hour, count)| match locale {
+                SummaryLocale::Ja => format!("{:02}時台({}件)", hour, count),
+                SummaryLocale::En => format!("{:02}:00 ({})", hour, count),
+            })
+            .collect::<Vec<_>>()
+            .join(list_separator(locale));
+
+        let offline_cameras = self
+            .offline_cameras
+            .iter()
+            .map(|o| {
+                let since = o.since.with_timezone(&Tokyo).format("%H:%M");
+                match locale {
+                    SummaryLocale::Ja => format!("{}（{} {}〜）", o.name, event_label(&o.reason, locale), since),
+                    SummaryLocale::En => format!("{} ({} since {})", o.name, event_label(&o.reason, locale), since),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(list_separator(locale));
+
+        let incidents = self
+            .incidents
+            .iter()
+            .map(|i| {
+                format!(
+                    "- {} {}: {} (severity {})",
+                    i.captured_at.with_timezone(&Tokyo).format("%H:%M"),
+                    i.camera_name,
+                    event_label(&i.event, locale),
+                    i.severity
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        HashMap::from([
+            ("fid", self.fid.clone()),
+            ("period", period),
+            ("duration", duration),
+            ("total", self.total_detections.to_string()),
+            ("camera_count", self.cameras.len().to_string()),
+            ("severity_max", self.severity_max.to_string()),
+            ("summary_count", self.summary_count.to_string()),
+            ("camera_breakdown", camera_breakdown),
+            ("top_events", top_events),
+            ("peak_hours", peak_hours),
+            ("offline_cameras", offline_cameras),
+            ("incidents", incidents),
+        ])
+    }
+}
+
+fn list_separator(locale: SummaryLocale) -> &'static str {
+    match locale {
+        SummaryLocale::Ja => "、",
+        SummaryLocale::En => ", ",
+    }
+}
+
+/// primary_event の表示名
+pub fn event_label(event: &str, locale: SummaryLocale) -> String {
+    let label = match (locale, event) {
+        (SummaryLocale::Ja, "human") => "人物",
+        (SummaryLocale::Ja, "vehicle") => "車両",
+        (SummaryLocale::Ja, "animal") => "動物",
+        (SummaryLocale::Ja, "unknown") => "不明",
+        (SummaryLocale::Ja, "camera_lost" | "connection_lost") => "接続断",
+        (SummaryLocale::Ja, "camera_recovered") => "復旧",
+        (SummaryLocale::En, "human") => "person",
+        (SummaryLocale::En, "camera_lost" | "connection_lost") => "offline",
+        (SummaryLocale::En, "camera_recovered") => "recovered",
+        _ => event,
+    };
+    label.to_string()
+}
+
+// ============================================================
+// Template engine
+// ============================================================
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Text(String),
+    Var(String),
+    If {
+        name: String,
+        then: Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+}
+
+/// テンプレートを構文解析（未知の変数・閉じ忘れはエラー）
+fn parse(body: &str) -> Result<Vec<Node>, String> {
+    // (name, then, otherwise, in_else)
+    let mut stack: Vec<(String, Vec<Node>, Vec<Node>, bool)> = Vec::new();
+    let mut root: Vec<Node> = Vec::new();
+    let mut rest = body;
+
+    fn current<'a>(
+        root: &'a mut Vec<Node>,
+        stack: &'a mut [(String, Vec<Node>, Vec<Node>, bool)],
+    ) -> &'a mut Vec<Node> {
+        match stack.last_mut() {
+            Some((_, then, otherwise, in_else)) => {
+                if *in_else {
+                    otherwise
+                } else {
+                    then
+                }
+            }
+            None => root,
+        }
+    }
+
+    let check_var = |name: &str| -> Result<(), String> {
+        if TEMPLATE_VARIABLES.contains(&name) {
+            Ok(())
+        } else {
+            Err(format!("unknown variable '{}'", name))
+        }
+    };
+
+    while let Some(open) = rest.find("{{") {
+        if open > 0 {
+            current(&mut root, &mut stack).push(Node::Text(rest[..open].to_string()));
+        }
+        let after = &rest[open + 2..];
+        let close = after
+            .find("}}")
+            .ok_or_else(|| "unterminated '{{'".to_string())?;
+        let tag = after[..close].trim();
+        rest = &after[close + 2..];
+
+        if let Some(name) = tag.strip_prefix("#if ") {
+            let name = name.trim();
+            check_var(name)?;
+            stack.push((name.to_string(), Vec::new(), Vec::new(), false));
+        } else if tag == "else" {
+            match stack.last_mut() {
+                Some((_, _, _, in_else)) if !*in_else => *in_else = true,
+                _ => return Err("unexpected '{{else}}'".to_string()),
+            }
+        } else if tag == "/if" {
+            let (name, then, otherwise, _) = stack
+                .pop()
+                .ok_or_else(|| "unexpected '{{/if}}'".to_string())?;
+            current(&mut root, &mut stack).push(Node::If { name, then, otherwise });
+        } else {
+            check_var(tag)?;
+            current(&mut root, &mut stack).push(Node::Var(tag.to_string()));
+        }
+    }
+    if !rest.is_empty() {
+        current(&mut root, &mut stack).push(Node::Text(rest.to_string()));
+    }
+    if let Some((name, ..)) = stack.last() {
+        return Err(format!("'{{{{#if {}}}}}' is not closed", name));
+    }
+    Ok(root)
+}
+
+fn render_nodes(nodes: &[Node], vars: &HashMap<&'static str, String>, out: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => out.push_str(text),
+            Node::Var(name) => out.push_str(vars.get(name.as_str()).map(String::as_str).unwrap_or("")),
+            Node::If { name, then, otherwise } => {
+                let truthy = vars
+                    .get(name.as_str())
+                    .map(|v| !v.is_empty() && v != "0")
+                    .unwrap_or(false);
+                render_nodes(if truthy { then } else { otherwise }, vars, out);
+            }
+        }
+    }
+}
+
+/// テンプレートの構文・変数名を検証
+pub fn validate_template(body: &str) -> crate::Result<()> {
+    if body.trim().is_empty() {
+        return Err(crate::Error::Validation("template body must not be empty".to_string()));
+    }
+    if body.len() > 8192 {
+        return Err(crate::Error::Validation("template body must be 8192 bytes or less".to_string()));
+    }
+    parse(body)
+        .map(|_| ())
+        .map_err(|e| crate::Error::Validation(format!("invalid template: {}", e)))
+}
+
+/// テンプレートを展開（末尾の空白は除去）
+pub fn render_template(body: &str, vars: &HashMap<&'static str, String>) -> crate::Result<String> {
+    let nodes = parse(body).map_err(|e| crate::Error::Validation(format!("invalid template: {}", e)))?;
+    let mut out = String::new();
+    render_nodes(&nodes, vars, &mut out);
+    Ok(out.trim_end().to_string())
+}
+
+/// 組み込みデフォルトテンプレート
+pub fn default_template(locale: SummaryLocale, kind: TemplateKind) -> &'static str {
+    match (locale, kind) {
+        (SummaryLocale::Ja, TemplateKind::Summary) => concat!(
+            "{{#if total}}{{duration}}の検出サマリー（{{period}}）: 合計{{total}}件の検出（{{camera_count}}台のカメラ）\n",
+            "{{camera_breakdown}}\n",
+            "{{#if top_events}}主なイベント: {{top_events}}\n{{/if}}",
+            "{{#if peak_hours}}ピーク時間帯: {{peak_hours}}\n{{/if}}",
+            "{{#if incidents}}注目すべき検出:\n{{incidents}}\n{{/if}}",
+            "{{else}}{{period}}の期間: 検出イベントなし\n{{/if}}",
+            "{{#if offline_cameras}}オフライン: {{offline_cameras}}\n{{/if}}",
+        ),
+        (SummaryLocale::En, TemplateKind::Summary) => concat!(
+            "{{#if total}}Detection summary for {{duration}} ({{period}}): {{total}} detections across {{camera_count}} cameras\n",
+            "{{camera_breakdown}}\n",
+            "{{#if top_events}}Top events: {{top_events}}\n{{/if}}",
+            "{{#if peak_hours}}Peak hours: {{peak_hours}}\n{{/if}}",
+            "{{#if incidents}}Notable detections:\n{{incidents}}\n{{/if}}",
+            "{{else}}{{period}}: no detection events\n{{/if}}",
+            "{{#if offline_cameras}}Offline: {{offline_cameras}}\n{{/if}}",
+        ),
+        (SummaryLocale::Ja, TemplateKind::GrandSummary) => concat!(
+            "{{#if total}}シフトサマリー（{{period}}）: 合計{{total}}件の検出（{{camera_count}}台のカメラ）\n",
+            "{{summary_count}}個のhourlyサマリーを統合\n",
+            "{{#if top_events}}主なイベント: {{top_events}}\n{{/if}}",
+            "{{#if peak_hours}}ピーク時間帯: {{peak_hours}}\n{{/if}}",
+            "{{#if incidents}}注目すべき検出:\n{{incidents}}\n{{/if}}",
+            "{{else}}シフトサマリー（{{period}}）: 検出イベントなし\n{{/if}}",
+            "{{#if offline_cameras}}オフライン: {{offline_cameras}}\n{{/if}}",
+        ),
+        (SummaryLocale::En, TemplateKind::GrandSummary) => concat!(
+            "{{#if total}}Shift summary ({{period}}): {{total}} detections across {{camera_count}} cameras\n",
+            "Merged {{summary_count}} hourly summaries\n",
+            "{{#if top_events}}Top events: {{top_events}}\n{{/if}}",
+            "{{#if peak_hours}}Peak hours: {{peak_hours}}\n{{/if}}",
+            "{{#if incidents}}Notable detections:\n{{incidents}}\n{{/if}}",
+            "{{else}}Shift summary ({{period}}): no detection events\n{{/if}}",
+            "{{#if offline_cameras}}Offline: {{offline_cameras}}\n{{/if}}",
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn log(camera_id: &str, event: &str, severity: i32, hour: u32) -> DetectionLog {
+        let at = Utc.with_ymd_and_hms(2026, 1, 10, hour, 0, 0).unwrap();
+        DetectionLog {
+            log_id: None,
+            tid: "T1".to_string(),
+            fid: "0150".to_string(),
+            camera_id: camera_id.to_string(),
+            lacis_id: None,
+            camera_lacis_id: Some(format!("3080{}", camera_id)),
+            captured_at: at,
+            analyzed_at: at,
+            primary_event: event.to_string(),
+            severity,
+            confidence: 0.9,
+            count_hint: 1,
+            unknown_flag: false,
+            tags: vec![],
+            person_details: None,
+            vehicle_details: None,
+            bboxes: None,
+            suspicious: None,
+            frame_diff: None,
+            loitering_detected: false,
+            preset_id: "balanced".to_string(),
+            preset_version: None,
+            output_schema: None,
+            context_applied: false,
+            camera_context: None,
+            is21_log: serde_json::Value::Null,
+            image_path_local: String::new(),
+            image_path_cloud: None,
+            processing_ms: None,
+            polling_cycle_id: None,
+            schema_version: String::new(),
+            timings: None,
+            created_at: at,
+            synced_to_bq: false,
+            synced_at: None,
+        }
+    }
+
+    fn context_map() -> HashMap<String, ContextMapEntry> {
+        HashMap::from([(
+            "3080cam-a".to_string(),
+            ContextMapEntry {
+                camera_id: "cam-a".to_string(),
+                lacis_id: "3080cam-a".to_string(),
+                name: "エントランス北".to_string(),
+                location: Some("1F".to_string()),
+                ip_address: None,
+                context_summary: String::new(),
+            },
+        )])
+    }
+
+    #[test]
+    fn test_parse_and_render() {
+        let vars = HashMap::from([("total", "3".to_string()), ("fid", String::new())]);
+        let out = render_template("{{#if total}}n={{total}}{{else}}none{{/if}}{{#if fid}}!{{/if}}", &vars).unwrap();
+        assert_eq!(out, "n=3");
+
+        let vars = HashMap::from([("total", "0".to_string())]);
+        assert_eq!(render_template("{{#if total}}n{{else}}none{{/if}}", &vars).unwrap(), "none");
+
+        assert!(validate_template("{{unknown_var}}").is_err());
+        assert!(validate_template("{{#if total}}open").is_err());
+        assert!(validate_template("{{/if}}").is_err());
+        assert!(validate_template("{{total").is_err());
+        for locale in [SummaryLocale::Ja, SummaryLocale::En] {
+            for kind in [TemplateKind::Summary, TemplateKind::GrandSummary] {
+                validate_template(default_template(locale, kind)).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn test_facts_from_logs() {
+        let start = Utc.with_ymd_and_hms(2026, 1, 10, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2026, 1, 10, 3, 0, 0).unwrap();
+        let logs = vec![
+            log("cam-a", "human", 1, 0),
+            log("cam-a", "human", 3, 1),
+            log("cam-a", "vehicle", 1, 1),
+            log("cam-b-long-camera-identifier", "human", 1, 2),
+            log("cam-b-long-camera-identifier", "camera_lost", 0, 2),
+            log("cam-a", "camera_lost", 0, 0),
+            log("cam-a", "camera_recovered", 0, 1),
+        ];
+        let facts = SummaryFacts::from_logs("0150", start, end, &logs, &context_map());
+
+        assert_eq!(facts.total_detections, 4);
+        assert_eq!(facts.severity_max, 3);
+        assert_eq!(facts.cameras[0].name, "エントランス北");
+        // 名前が解決できないカメラはIDを省略せず表示
+        assert_eq!(facts.cameras[1].name, "cam-b-long-camera-identifier");
+        assert_eq!(facts.top_events[0], ("human".to_string(), 3));
+        // UTC 01:00 = JST 10時台
+        assert_eq!(facts.peak_hours[0], (10, 2));
+        assert_eq!(facts.incidents.len(), 1);
+        // 復旧済みのcam-aはオフライン扱いしない
+        assert_eq!(facts.offline_cameras.len(), 1);
+        assert_eq!(facts.offline_cameras[0].name, "cam-b-long-camera-identifier");
+
+        let ja = render_template(
+            default_template(SummaryLocale::Ja, TemplateKind::Summary),
+            &facts.variables(SummaryLocale::Ja),
+        )
+        .unwrap();
+        assert!(ja.starts_with("3時間の検出サマリー（01/10 09:00 〜 12:00）: 合計4件"));
+        assert!(ja.contains("- エントランス北 (1F): 3件"));
+        assert!(ja.contains("主なイベント: 人物 3件、車両 1件"));
+
+        let en = render_template(
+            default_template(SummaryLocale::En, TemplateKind::Summary),
+            &facts.variables(SummaryLocale::En),
+        )
+        .unwrap();
+        assert!(en.starts_with("Detection summary for 3 hours"));
+        assert!(en.contains("Top events: person 3, vehicle 1"));
+        assert!(en.contains("Offline: cam-b-long-camera-identifier (offline since 11:00)"));
+    }
+
+    #[test]
+    fn test_format_camera_breakdown_empty() {
+        let start = Utc.with_ymd_and_hms(2026, 1, 10, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2026, 1, 10, 1, 0, 0).unwrap();
+        let facts = SummaryFacts::empty("0150", start, end);
+
+        for locale in [SummaryLocale::Ja, SummaryLocale::En] {
+            let vars = facts.variables(locale);
+            assert_eq!(vars["camera_breakdown"], "");
+            let text = render_template(default_template(locale, TemplateKind::Summary), &vars).unwrap();
+            assert!(!text.contains("- "), "{}", text);
+        }
+    }
+}
@@ -0,0 +1,196 @@
+//! Summary Text Builder
+//!
+//! テンプレート選択（fid・locale）とsummary_textの展開、
+//! 過去データに対するテンプレートプレビューを担当する。
+
+use super::repository::{SummaryRepository, TemplateRepository};
+use super::template::{
+    default_template, render_template, SummaryFacts, SummaryLocale, SummaryLocaleSettings,
+    TemplateKind,
+};
+use super::types::SummaryType;
+use crate::camera_registry::CameraContextService;
+use crate::config_store::ConfigStore;
+use crate::detection_log_service::DetectionLogService;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::sync::Arc;
+use tracing::warn;
+
+/// settingsテーブルのキー
+const LOCALE_SETTINGS_KEY: &str = "summary_locale";
+
+/// プレビュー結果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SummaryPreview {
+    pub locale: SummaryLocale,
+    pub kind: TemplateKind,
+    /// 使用したテンプレート（None = 組み込みデフォルト or リクエスト本文）
+    pub template_id: Option<u64>,
+    pub text: String,
+    pub facts: SummaryFacts,
+}
+
+/// summary_text生成サービス
+pub struct SummaryTextBuilder {
+    detection_log_service: Arc<DetectionLogService>,
+    camera_context_service: CameraContextService,
+    templates: TemplateRepository,
+    summaries: SummaryRepository,
+    config_store: Arc<ConfigStore>,
+}
+
+impl SummaryTextBuilder {
+    pub fn new(
+        detection_log_service: Arc<DetectionLogService>,
+        camera_context_service: CameraContextService,
+        templates: TemplateRepository,
+        summaries: SummaryRepository,
+        config_store: Arc<ConfigStore>,
+    ) -> Self {
+        Self {
+            detection_log_service,
+            camera_context_service,
+            templates,
+            summaries,
+            config_store,
+        }
+    }
+
+    pub fn templates(&self) -> &TemplateRepository {
+        &self.templates
+    }
+
+    /// locale設定を取得（未設定時はja）
+    pub async fn locale_settings(&self) -> SummaryLocaleSettings {
+        self.config_store
+            .service()
+            .get_setting(LOCALE_SETTINGS_KEY)
+            .await
+            .ok()
+            .flatten()
+            .and_then(|v| serde_json::from_value(v).ok())
+            .unwrap_or_default()
+    }
+
+    /// locale設定を保存
+    pub async fn set_locale_settings(&self, settings: &SummaryLocaleSettings) -> crate::Result<()> {
+        self.config_store
+            .service()
+            .set_setting(LOCALE_SETTINGS_KEY, serde_json::to_value(settings)?)
+            .await
+    }
+
+    /// 期間内の検出ログから集計
+    pub async fn collect_facts(
+        &self,
+        tid: &str,
+        fid: &str,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+    ) -> crate::Result<SummaryFacts> {
+        let logs = self
+            .detection_log_service
+            .get_by_tid_fid(tid, Some(fid), 10000)
+            .await?
+            .into_iter()
+            .filter(|log| log.captured_at >= period_start && log.captured_at <= period_end)
+            .collect::<Vec<_>>();
+        let context_map = self.camera_context_service.build_context_map(tid).await?;
+
+        Ok(SummaryFacts::from_logs(fid, period_start, period_end, &logs, &context_map))
+    }
+
+    /// 有効なテンプレートでsummary_textを展開
+    ///
+    /// テンプレート取得・展開に失敗した場合は組み込みデフォルトにフォールバック
+    pub async fn render(&self, kind: TemplateKind, facts: &SummaryFacts) -> String {
+        let locale = self.locale_settings().await.locale_for(&facts.fid);
+        let vars = facts.variables(locale);
+
+        match self.templates.find_active(&facts.fid, locale, kind).await {
+            Ok(Some(template)) => match render_template(&template.body, &vars) {
+                Ok(text) => return text,
+                Err(e) => warn!(
+                    template_id = template.template_id,
+                    error = %e,
+                    "Summary template render failed, using default"
+                ),
+            },
+            Ok(None) => {}
+            Err(e) => warn!(error = %e, "Failed to load summary template, using default"),
+        }
+
+        render_template(default_template(locale, kind), &vars).unwrap_or_default()
+    }
+
+    /// 過去データに対してテンプレートをプレビュー（保存・送信なし）
+    ///
+    /// `template_id` / `body` がどちらも未指定の場合は現在選択されるテンプレートを使用
+    #[allow(clippy::too_many_arguments)]
+    pub async fn preview(
+        &self,
+        tid: &str,
+        fid: &str,
+        kind: TemplateKind,
+        locale: Option<SummaryLocale>,
+        template_id: Option<u64>,
+        body: Option<String>,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+    ) -> crate::Result<SummaryPreview> {
+        if period_end <= period_start {
+            return Err(crate::Error::Validation(
+                "periodEnd must be after periodStart".to_string(),
+            ));
+        }
+
+        let mut facts = self.collect_facts(tid, fid, period_start, period_end).await?;
+        if kind == TemplateKind::GrandSummary {
+            facts.summary_count = self
+                .summaries
+                .get_by_period(tid, fid, SummaryType::Hourly, period_start, period_end)
+                .await?
+                .len();
+        }
+
+        let (template_id, body, locale) = match (template_id, body) {
+            (Some(id), _) => {
+                let template = self
+                    .templates
+                    .get(id)
+                    .await?
+                    .ok_or_else(|| crate::Error::NotFound(format!("Template {} not found", id)))?;
+                (Some(id), template.body, locale.unwrap_or(template.locale))
+            }
+            (None, Some(body)) => {
+                let locale = match locale {
+                    Some(locale) => locale,
+                    None => self.locale_settings().await.locale_for(fid),
+                };
+                (None, body, locale)
+            }
+            (None, None) => {
+                let locale = match locale {
+                    Some(locale) => locale,
+                    None => self.locale_settings().await.locale_for(fid),
+                };
+                match self.templates.find_active(fid, locale, kind).await? {
+                    Some(t) => (Some(t.template_id), t.body, locale),
+                    None => (None, default_template(locale, kind).to_string(), locale),
+                }
+            }
+        };
+
+        let text = render_template(&body, &facts.variables(locale))?;
+
+        Ok(SummaryPreview {
+            locale,
+            kind,
+            template_id,
+            text,
+            facts,
+        })
+    }
+}
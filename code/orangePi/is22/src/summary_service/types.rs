@@ -7,6 +7,7 @@
 //! - Hourly: 時間間隔経過（デフォルト60分）
 //! - Daily: 指定時刻到達（GrandSummary）
 //! - Emergency: 異常検出時（severity >= 閾値）
+//! - Weekly / Monthly: 期間ロールアップ（ISMSレビュー用）
 
 use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
@@ -23,6 +24,17 @@ pub enum SummaryType {
     Hourly,
     Daily,
     Emergency,
+    /// 週次ロールアップ（JST 月曜00:00〜翌月曜00:00）
+    Weekly,
+    /// 月次ロールアップ（JST 1日00:00〜翌月1日00:00）
+    Monthly,
+}
+
+impl SummaryType {
+    /// ロールアップ種別か
+    pub fn is_rollup(&self) -> bool {
+        matches!(self, Self::Weekly | Self::Monthly)
+    }
 }
 
 impl std::fmt::Display for SummaryType {
@@ -31,6 +43,8 @@ impl std::fmt::Display for SummaryType {
             Self::Hourly => write!(f, "hourly"),
             Self::Daily => write!(f, "daily"),
             Self::Emergency => write!(f, "emergency"),
+            Self::Weekly => write!(f, "weekly"),
+            Self::Monthly => write!(f, "monthly"),
         }
     }
 }
@@ -40,6 +54,8 @@ impl From<&str> for SummaryType {
         match s.to_lowercase().as_str() {
             "daily" => Self::Daily,
             "emergency" => Self::Emergency,
+            "weekly" => Self::Weekly,
+            "monthly" => Self::Monthly,
             _ => Self::Hourly,
         }
     }
@@ -51,6 +67,32 @@ impl From<&str> for SummaryType {
 pub enum ReportType {
     Summary,
     GrandSummary,
+    /// 週次ロールアップ（scheduled_times[0] = 月曜の実行時刻）
+    WeeklyRollup,
+    /// 月次ロールアップ（scheduled_times[0] = 1日の実行時刻）
+    MonthlyRollup,
+}
+
+impl ReportType {
+    /// API/DB表記から変換（不明な値はNone）
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "summary" => Some(Self::Summary),
+            "grand_summary" => Some(Self::GrandSummary),
+            "weekly_rollup" => Some(Self::WeeklyRollup),
+            "monthly_rollup" => Some(Self::MonthlyRollup),
+            _ => None,
+        }
+    }
+
+    /// ロールアップ種別の場合、保存するSummaryType
+    pub fn rollup_summary_type(&self) -> Option<SummaryType> {
+        match self {
+            Self::WeeklyRollup => Some(SummaryType::Weekly),
+            Self::MonthlyRollup => Some(SummaryType::Monthly),
+            _ => None,
+        }
+    }
 }
 
 impl std::fmt::Display for ReportType {
@@ -58,16 +100,15 @@ impl std::fmt::Display for ReportType {
         match self {
             Self::Summary => write!(f, "summary"),
             Self::GrandSummary => write!(f, "grand_summary"),
+            Self::WeeklyRollup => write!(f, "weekly_rollup"),
+            Self::MonthlyRollup => write!(f, "monthly_rollup"),
         }
     }
 }
 
 impl From<&str> for ReportType {
     fn from(s: &str) -> Self {
-        match s.to_lowercase().as_str() {
-            "grand_summary" => Self::GrandSummary,
-            _ => Self::Summary,
-        }
+        Self::parse(s).unwrap_or(Self::Summary)
     }
 }
 
@@ -122,6 +163,8 @@ pub struct ReportSchedule {
     pub report_type: ReportType,
     pub interval_minutes: Option<i32>,
     pub scheduled_times: Option<Vec<String>>,
+    /// 実行時に生成するレポート形式（"html" / "pdf"、None = 生成なし）
+    pub report_format: Option<String>,
     pub last_run_at: Option<DateTime<Utc>>,
     pub next_run_at: Option<DateTime<Utc>>,
     pub enabled: bool,
@@ -265,6 +308,124 @@ pub struct CameraDetectionItem {
     pub detection_detail: String,
 }
 
+/// 週次・月次ロールアップペイロード（Paraclate送信用 / summary_json）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RollupPayload {
+    /// "weekly_rollup" | "monthly_rollup"
+    #[serde(rename = "type")]
+    pub rollup_type: String,
+    pub period_start: String,
+    pub period_end: String,
+    pub totals: RollupTotals,
+    pub previous_totals: RollupTotals,
+    pub trend: RollupTrend,
+    /// JST日付別の検出数
+    pub daily_detections: Vec<RollupDailyCount>,
+    pub camera_uptime: Vec<RollupCameraUptime>,
+    /// 平均稼働率（%）
+    pub average_uptime_pct: f64,
+    /// 繰り返し発生している過剰検出（件数の多い順）
+    pub overdetection_issues: Vec<RollupOverdetectionIssue>,
+    pub feedback: RollupFeedback,
+    pub attunement: RollupAttunement,
+}
+
+/// ロールアップ期間の集計値
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RollupTotals {
+    pub detection_count: i64,
+    pub severity_max: i32,
+    pub camera_count: usize,
+    /// 集計したhourly Summary数
+    pub summary_count: usize,
+    /// 集計したGrandSummary数
+    pub grand_summary_count: usize,
+}
+
+/// 前期間比
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RollupTrend {
+    pub detection_delta: i64,
+    /// 前期間が0件の場合はNone
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detection_change_pct: Option<f64>,
+    /// "up" | "down" | "flat"
+    pub direction: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RollupDailyCount {
+    /// JST日付（YYYY-MM-DD）
+    pub date: String,
+    pub detection_count: i64,
+}
+
+/// カメラ稼働率（camera_lost / camera_recovered 履歴から算出）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RollupCameraUptime {
+    pub camera_id: String,
+    pub camera_name: String,
+    pub uptime_pct: f64,
+    pub lost_events: i64,
+    pub downtime_minutes: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RollupOverdetectionIssue {
+    pub camera_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub camera_name: Option<String>,
+    /// OverdetectionType (snake_case)
+    pub issue_type: String,
+    /// "info" | "warning" | "critical"
+    pub severity: String,
+    pub rate: f64,
+    pub count: i64,
+    pub suggestion: String,
+}
+
+/// 誤検知報告（misdetection_feedbacks）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RollupFeedback {
+    pub total: i64,
+    pub false_positive: i64,
+    pub false_negative: i64,
+    pub previous_total: i64,
+}
+
+/// 閾値変更・attunement trial
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RollupAttunement {
+    /// threshold_change_history の件数
+    pub threshold_changes: i64,
+    pub trials_started: i64,
+    pub trials_promoted: i64,
+    pub trials_rolled_back: i64,
+    pub trials_aborted: i64,
+    /// 期間内に確定したtrial
+    pub decisions: Vec<RollupTrialDecision>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RollupTrialDecision {
+    pub trial_id: u64,
+    pub camera_id: String,
+    pub parameter: String,
+    pub status: String,
+    pub baseline_value: f32,
+    pub candidate_value: f32,
+    pub finished_at: String,
+}
+
 // ============================================================
 // API Request/Response Structures
 // ============================================================
@@ -424,12 +585,22 @@ mod tests {
         assert_eq!(SummaryType::from("hourly"), SummaryType::Hourly);
         assert_eq!(SummaryType::from("DAILY"), SummaryType::Daily);
         assert_eq!(SummaryType::from("unknown"), SummaryType::Hourly);
+        assert_eq!(SummaryType::from("monthly"), SummaryType::Monthly);
+        assert!(SummaryType::Weekly.is_rollup());
+        assert!(!SummaryType::Daily.is_rollup());
     }
 
     #[test]
     fn test_report_type_display() {
         assert_eq!(ReportType::Summary.to_string(), "summary");
         assert_eq!(ReportType::GrandSummary.to_string(), "grand_summary");
+        assert_eq!(ReportType::WeeklyRollup.to_string(), "weekly_rollup");
+        assert_eq!(ReportType::parse("monthly_rollup"), Some(ReportType::MonthlyRollup));
+        assert_eq!(ReportType::parse("yearly"), None);
+        assert_eq!(
+            ReportType::WeeklyRollup.rollup_summary_type(),
+            Some(SummaryType::Weekly)
+        );
     }
 
     #[test]
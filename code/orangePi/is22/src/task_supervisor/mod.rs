@@ -0,0 +1,336 @@
+//! TaskSupervisor - supervised background tasks
+//!
+//! ## Responsibilities
+//!
+//! - 名前付きバックグラウンドタスクの起動と状態追跡（/readyz で公開）
+//! - パニック時の指数バックオフ再起動（一定時間安定稼働したらバックオフをリセット）
+//! - CancellationToken による協調停止と期限付きの終了待ち
+//!
+//! タスクは `CancellationToken` を受け取り、キャンセルされたら速やかに return すること。
+//! 正常 return したタスクは再起動しない（Finished）。
+
+mod shutdown;
+
+pub use shutdown::{graceful_shutdown, shutdown_signal, ShutdownReport};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::task::{AbortHandle, JoinHandle};
+use tokio_util::sync::CancellationToken;
+
+/// First restart delay after a panic
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+/// A run at least this long resets the backoff
+const STABLE_RUN: Duration = Duration::from_secs(300);
+
+/// Lifecycle state of a supervised task
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskState {
+    Running,
+    /// Panicked, waiting for the backoff before restarting
+    Restarting,
+    /// Returned on its own (not restarted)
+    Finished,
+    /// Stopped by shutdown
+    Stopped,
+}
+
+/// Task status for the health API
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskStatus {
+    pub name: String,
+    pub state: TaskState,
+    pub restarts: u32,
+    pub started_at: DateTime<Utc>,
+    pub last_panic: Option<String>,
+    pub last_panic_at: Option<DateTime<Utc>>,
+}
+
+/// Named task registry with restart-on-panic and cooperative shutdown
+pub struct TaskSupervisor {
+    shutdown: CancellationToken,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    tasks: Mutex<BTreeMap<String, TaskStatus>>,
+    handles: Mutex<Vec<(String, JoinHandle<()>)>>,
+}
+
+impl TaskSupervisor {
+    pub fn new(max_backoff: Duration) -> Self {
+        Self {
+            shutdown: CancellationToken::new(),
+            initial_backoff: INITIAL_BACKOFF,
+            max_backoff: max_backoff.max(INITIAL_BACKOFF),
+            tasks: Mutex::new(BTreeMap::new()),
+            handles: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Token cancelled when shutdown begins
+    pub fn token(&self) -> CancellationToken {
+        self.shutdown.clone()
+    }
+
+    pub fn is_shutting_down(&self) -> bool {
+        self.shutdown.is_cancelled()
+    }
+
+    /// Spawn a supervised task
+    ///
+    /// `factory` は起動・再起動のたびに呼ばれ、新しい future を返す
+    pub fn spawn<F, Fut>(self: &Arc<Self>, name: &str, factory: F)
+    where
+        F: Fn(CancellationToken) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let name = name.to_string();
+        self.update(&name, |status| *status = Self::new_status(&name));
+
+        let supervisor = self.clone();
+        let token = self.shutdown.child_token();
+        let task_name = name.clone();
+        let handle = tokio::spawn(async move {
+            let name = task_name;
+            let mut backoff = supervisor.initial_backoff;
+            loop {
+                let started = Instant::now();
+                let run = tokio::spawn(factory(token.clone()));
+                // 監視タスクが abort されたら実行中のタスクも止める
+                let _guard = AbortOnDrop(run.abort_handle());
+                let result = run.await;
+
+                if token.is_cancelled() {
+                    supervisor.update(&name, |s| s.state = TaskState::Stopped);
+                    break;
+                }
+                let panic = match result {
+                    Ok(()) => {
+                        tracing::warn!(task = %name, "Supervised task returned; not restarting");
+                        supervisor.update(&name, |s| s.state = TaskState::Finished);
+                        break;
+                    }
+                    Err(e) if e.is_panic() => panic_message(e.into_panic()),
+                    Err(_) => {
+                        supervisor.update(&name, |s| s.state = TaskState::Stopped);
+                        break;
+                    }
+                };
+
+                if started.elapsed() >= STABLE_RUN {
+                    backoff = supervisor.initial_backoff;
+                }
+                tracing::error!(
+                    task = %name,
+                    panic = %panic,
+                    restart_in_ms = backoff.as_millis() as u64,
+                    "Supervised task panicked; restarting"
+                );
+                supervisor.update(&name, |s| {
+                    s.state = TaskState::Restarting;
+                    s.last_panic = Some(panic.clone());
+                    s.last_panic_at = Some(Utc::now());
+                });
+
+                tokio::select! {
+                    _ = token.cancelled() => {
+                        supervisor.update(&name, |s| s.state = TaskState::Stopped);
+                        break;
+                    }
+                    _ = tokio::time::sleep(backoff) => {}
+                }
+                backoff = (backoff * 2).min(supervisor.max_backoff);
+                supervisor.update(&name, |s| {
+                    s.state = TaskState::Running;
+                    s.restarts += 1;
+                    s.started_at = Utc::now();
+                });
+            }
+        });
+
+        self.handles
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push((name, handle));
+    }
+
+    /// Spawn a supervised task running `job` every `period` after `initial_delay`
+    pub fn spawn_periodic<F, Fut>(
+        self: &Arc<Self>,
+        name: &str,
+        period: Duration,
+        initial_delay: Duration,
+        job: F,
+    ) where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let job = Arc::new(job);
+        self.spawn(name, move |token| {
+            let job = job.clone();
+            async move {
+                tokio::select! {
+                    _ = token.cancelled() => return,
+                    _ = tokio::time::sleep(initial_delay) => {}
+                }
+                let mut interval = tokio::time::interval(period);
+                interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+                loop {
+                    tokio::select! {
+                        _ = token.cancelled() => break,
+                        _ = interval.tick() => {}
+                    }
+                    // ジョブ実行中のキャンセルは次のティックで反映（途中で中断しない）
+                    job().await;
+                }
+            }
+        });
+    }
+
+    /// Current status of all tasks
+    pub fn tasks(&self) -> Vec<TaskStatus> {
+        self.tasks
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .values()
+            .cloned()
+            .collect()
+    }
+
+    /// Cancel all tasks and wait until `deadline`; returns tasks that had to be aborted
+    pub async fn shutdown(&self, deadline: Instant) -> Vec<String> {
+        self.shutdown.cancel();
+        let handles: Vec<_> =
+            std::mem::take(&mut *self.handles.lock().unwrap_or_else(|e| e.into_inner()));
+
+        let mut aborted = Vec::new();
+        for (name, mut handle) in handles {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if tokio::time::timeout(remaining, &mut handle).await.is_err() {
+                handle.abort();
+                tracing::warn!(task = %name, "Supervised task did not stop before the deadline; aborted");
+                self.update(&name, |s| s.state = TaskState::Stopped);
+                aborted.push(name);
+            }
+        }
+        aborted
+    }
+
+    fn new_status(name: &str) -> TaskStatus {
+        TaskStatus {
+            name: name.to_string(),
+            state: TaskState::Running,
+            restarts: 0,
+            started_at: Utc::now(),
+            last_panic: None,
+            last_panic_at: None,
+        }
+    }
+
+    fn update(&self, name: &str, f: impl FnOnce(&mut TaskStatus)) {
+        let mut tasks = self.tasks.lock().unwrap_or_else(|e| e.into_inner());
+        let status = tasks
+            .entry(name.to_string())
+            .or_insert_with(|| Self::new_status(name));
+        f(status);
+    }
+}
+
+struct AbortOnDrop(AbortHandle);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// Best-effort text of a panic payload
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "non-string panic payload".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn supervisor(max_backoff: Duration) -> Arc<TaskSupervisor> {
+        let mut supervisor = TaskSupervisor::new(max_backoff);
+        supervisor.initial_backoff = Duration::from_millis(10);
+        Arc::new(supervisor)
+    }
+
+    #[tokio::test]
+    async fn test_panicking_task_is_restarted_with_backoff() {
+        let supervisor = supervisor(Duration::from_millis(40));
+        let runs = Arc::new(AtomicU32::new(0));
+        let counter = runs.clone();
+        supervisor.spawn("flaky", move |token| {
+            let counter = counter.clone();
+            async move {
+                if counter.fetch_add(1, Ordering::SeqCst) < 2 {
+                    panic!("boom");
+                }
+                token.cancelled().await;
+            }
+        });
+
+        // 10ms + 20ms のバックオフ後に3回目の起動で安定
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 3);
+        let status = &supervisor.tasks()[0];
+        assert_eq!(status.state, TaskState::Running);
+        assert_eq!(status.restarts, 2);
+        assert_eq!(status.last_panic.as_deref(), Some("boom"));
+
+        let aborted = supervisor
+            .shutdown(Instant::now() + Duration::from_secs(1))
+            .await;
+        assert!(aborted.is_empty());
+        assert_eq!(supervisor.tasks()[0].state, TaskState::Stopped);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_aborts_tasks_ignoring_cancellation() {
+        let supervisor = supervisor(Duration::from_secs(60));
+        supervisor.spawn("stubborn", |_token| async {
+            tokio::time::sleep(Duration::from_secs(3600)).await;
+        });
+        let ticks = Arc::new(AtomicU32::new(0));
+        let counter = ticks.clone();
+        supervisor.spawn_periodic(
+            "periodic",
+            Duration::from_millis(20),
+            Duration::ZERO,
+            move || {
+                let counter = counter.clone();
+                async move {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                }
+            },
+        );
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(ticks.load(Ordering::SeqCst) >= 2);
+
+        let aborted = supervisor
+            .shutdown(Instant::now() + Duration::from_millis(100))
+            .await;
+        assert_eq!(aborted, vec!["stubborn".to_string()]);
+        assert!(supervisor.is_shutting_down());
+        assert!(supervisor
+            .tasks()
+            .iter()
+            .all(|t| t.state == TaskState::Stopped));
+    }
+}
@@ -0,0 +1,142 @@
+//! Graceful shutdown sequence
+//!
+//! SIGTERM / Ctrl-C 受信後、期限内に以下の順で停止する:
+//!
+//! 1. ポーリング停止（処理中カメラの検出ログ保存・Paraclate 送信キュー投入の完了を待つ）
+//! 2. 監視下タスクのキャンセル（キュー処理ループと最終フラッシュの競合を避ける）
+//! 3. Paraclate 送信キューの最終フラッシュ
+//! 4. メール送信キューの最終フラッシュ
+//! 5. WebSocket 接続のクローズ（shutdown フレーム送信後）
+
+use super::TaskSupervisor;
+use crate::state::AppState;
+use serde::Serialize;
+use std::time::{Duration, Instant};
+
+/// Outcome of the shutdown sequence (logged on exit)
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ShutdownReport {
+    pub polling_stopped: bool,
+    pub aborted_tasks: Vec<String>,
+    pub paraclate_sent: u32,
+    pub paraclate_failed: Vec<String>,
+    pub emails_sent: usize,
+    pub email_flush_failed: bool,
+    pub websockets_closed: usize,
+    pub elapsed_ms: u64,
+}
+
+/// Resolve on Ctrl-C or SIGTERM
+pub async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::warn!(error = %e, "Failed to listen for Ctrl-C");
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to listen for SIGTERM");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => tracing::info!("Ctrl-C received, shutting down"),
+        _ = terminate => tracing::info!("SIGTERM received, shutting down"),
+    }
+}
+
+/// Run the shutdown sequence within `timeout`
+pub async fn graceful_shutdown(
+    state: &AppState,
+    supervisor: &TaskSupervisor,
+    timeout: Duration,
+) -> ShutdownReport {
+    let started = Instant::now();
+    let deadline = started + timeout;
+    let mut report = ShutdownReport::default();
+
+    state.polling.stop().await;
+    report.polling_stopped = state.polling.wait_stopped(deadline).await;
+    if !report.polling_stopped {
+        tracing::warn!("Polling loops did not stop before the shutdown deadline");
+    }
+
+    report.aborted_tasks = supervisor.shutdown(deadline).await;
+
+    let remaining = deadline.saturating_duration_since(Instant::now());
+    match tokio::time::timeout(remaining, flush_paraclate_queue(state, &mut report)).await {
+        Ok(()) => {}
+        Err(_) => {
+            tracing::warn!("Paraclate queue flush did not finish before the shutdown deadline")
+        }
+    }
+
+    let remaining = deadline.saturating_duration_since(Instant::now());
+    match tokio::time::timeout(remaining, state.email_notifier.flush()).await {
+        Ok(Ok(stats)) => report.emails_sent = stats.sent,
+        Ok(Err(e)) => {
+            tracing::warn!(error = %e, "Final email queue flush failed");
+            report.email_flush_failed = true;
+        }
+        Err(_) => {
+            tracing::warn!("Email queue flush did not finish before the shutdown deadline");
+            report.email_flush_failed = true;
+        }
+    }
+
+    report.websockets_closed = state.realtime.close_all().await;
+
+    report.elapsed_ms = started.elapsed().as_millis() as u64;
+    tracing::info!(
+        polling_stopped = report.polling_stopped,
+        aborted_tasks = ?report.aborted_tasks,
+        paraclate_sent = report.paraclate_sent,
+        paraclate_failed = ?report.paraclate_failed,
+        emails_sent = report.emails_sent,
+        email_flush_failed = report.email_flush_failed,
+        websockets_closed = report.websockets_closed,
+        elapsed_ms = report.elapsed_ms,
+        "Graceful shutdown sequence completed"
+    );
+    report
+}
+
+/// Send whatever is pending for every connected TID/FID once
+async fn flush_paraclate_queue(state: &AppState, report: &mut ShutdownReport) {
+    let configs = match state
+        .paraclate_client
+        .config_repo()
+        .get_all_connected()
+        .await
+    {
+        Ok(configs) => configs,
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to load Paraclate configs for the final flush");
+            return;
+        }
+    };
+    for config in configs {
+        match state
+            .paraclate_client
+            .process_queue(&config.tid, &config.fid)
+            .await
+        {
+            Ok(sent) => report.paraclate_sent += sent,
+            Err(e) => {
+                tracing::warn!(fid = %config.fid, error = %e, "Final Paraclate queue flush failed");
+                report.paraclate_failed.push(config.fid);
+            }
+        }
+    }
+}
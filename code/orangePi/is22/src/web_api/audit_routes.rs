@@ -0,0 +1,217 @@
+//! Audit Log API Routes
+//!
+//! ## エンドポイント
+//! - GET /api/audit - 監査ログ検索（?actor=&target_type=&target_id=&action=&since=&until=&limit=&offset=）
+//! - GET /api/audit/verify - ハッシュチェーン検証
+//!
+//! `audit_middleware` は create_router で全ルートに適用し、/api 配下の変更系リクエストを記録する。
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::{ConnectInfo, MatchedPath, Query, Request, State},
+    http::{header, HeaderMap, Method},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use serde_json::Value;
+use std::net::SocketAddr;
+
+use crate::audit_log::{classify_target, AuditLogService, AuditQuery, NewAuditEntry};
+use crate::models::ApiResponse;
+use crate::state::AppState;
+
+/// 記録するリクエストボディの上限（超えるものは本文なしで記録）
+const MAX_CAPTURED_BODY: usize = 1024 * 1024;
+
+/// 操作ではなく定期通信のため記録しないルート
+const UNAUDITED_ROUTES: &[&str] = &["/api/modal/lease/:id/heartbeat"];
+
+/// Audit Log API ルーター
+pub fn audit_routes() -> Router<AppState> {
+    Router::new()
+        .route("/", get(query_audit_log))
+        .route("/verify", get(verify_chain))
+}
+
+/// GET /api/audit
+async fn query_audit_log(
+    State(state): State<AppState>,
+    Query(query): Query<AuditQuery>,
+) -> impl IntoResponse {
+    match state.audit_log.query(&query).await {
+        Ok(entries) => Json(ApiResponse::success(entries)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// GET /api/audit/verify
+async fn verify_chain(State(state): State<AppState>) -> impl IntoResponse {
+    match state.audit_log.verify_chain().await {
+        Ok(result) => Json(ApiResponse::success(result)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// 変更系リクエストの監査記録
+pub async fn audit_middleware(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|m| m.as_str().to_string());
+    let mutating = matches!(method, Method::POST | Method::PUT | Method::PATCH | Method::DELETE);
+    let Some(route) = route.filter(|r| {
+        mutating && r.starts_with("/api/") && !UNAUDITED_ROUTES.contains(&r.as_str())
+    }) else {
+        return next.run(request).await;
+    };
+
+    let actor = actor(request.headers());
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| *addr);
+    let source_ip = source_ip(peer, request.headers());
+
+    let (request, body) = capture_json_body(request).await;
+    let (target_type, mut target_id) = classify_target(&route, &path);
+    let audit = &state.audit_log;
+    let snapshot_key = target_type.as_deref().and_then(AuditLogService::snapshot_source);
+
+    // 作成系はパスに ID がないため、ボディのキー列（camera_id 等）から対象を特定する
+    if target_id.is_none() {
+        if let (Some((_, key)), Some(body)) = (snapshot_key, &body) {
+            target_id = body.get(key).and_then(json_scalar);
+        }
+    }
+    let snapshot = |target_id: Option<String>| {
+        let target_type = target_type.clone();
+        async move {
+            match (snapshot_key, target_type, target_id) {
+                (Some(_), Some(t), Some(id)) => audit.snapshot(&t, &id).await.unwrap_or_else(|e| {
+                    tracing::warn!(target_type = %t, target_id = %id, error = %e, "Audit snapshot failed");
+                    None
+                }),
+                _ => None,
+            }
+        }
+    };
+
+    let before = snapshot(target_id.clone()).await;
+    let response = next.run(request).await;
+    let status = response.status();
+    let after = if status.is_success() {
+        snapshot(target_id.clone()).await
+    } else {
+        before.clone()
+    };
+
+    let entry = NewAuditEntry {
+        actor,
+        source_ip,
+        method: method.to_string(),
+        path,
+        action: format!("{} {}", method, route),
+        target_type,
+        target_id,
+        status_code: status.as_u16(),
+        request: body,
+        before,
+        after,
+    };
+    if let Err(e) = audit.record(entry).await {
+        tracing::error!(error = %e, "Failed to record audit log entry");
+    }
+    response
+}
+
+/// 操作者（X-Actor → X-User-Id → anonymous）
+pub(super) fn actor(headers: &HeaderMap) -> String {
+    ["x-actor", "x-user-id"]
+        .iter()
+        .filter_map(|name| headers.get(*name))
+        .filter_map(|v| v.to_str().ok())
+        .map(|v| v.trim())
+        .find(|v| !v.is_empty())
+        .map(|v| v.chars().take(128).collect())
+        .unwrap_or_else(|| "anonymous".to_string())
+}
+
+/// 接続元IP
+///
+/// 同一ホストのリバースプロキシ経由（接続元がループバック）の場合のみ X-Forwarded-For / X-Real-IP を採用する
+pub(super) fn source_ip(peer: Option<SocketAddr>, headers: &HeaderMap) -> Option<String> {
+    let forwarded = || {
+        headers
+            .get("x-forwarded-for")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.split(',').next())
+            .or_else(|| headers.get("x-real-ip").and_then(|v| v.to_str().ok()))
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    };
+    match peer {
+        Some(addr) if addr.ip().is_loopback() => forwarded().or_else(|| Some(addr.ip().to_string())),
+        Some(addr) => Some(addr.ip().to_string()),
+        None => forwarded(),
+    }
+}
+
+fn json_scalar(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) if !s.is_empty() => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// JSON ボディを読み取り、同じ内容でリクエストを組み直す
+async fn capture_json_body(request: Request) -> (Request, Option<Value>) {
+    let headers = request.headers();
+    let is_json = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("application/json"));
+    let length = headers
+        .get(header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<usize>().ok());
+    if !is_json || !length.is_some_and(|len| len <= MAX_CAPTURED_BODY) {
+        return (request, None);
+    }
+
+    let (parts, body) = request.into_parts();
+    match to_bytes(body, MAX_CAPTURED_BODY).await {
+        Ok(bytes) => {
+            let value = serde_json::from_slice(&bytes).ok();
+            (Request::from_parts(parts, Body::from(bytes)), value)
+        }
+        // Content-Length と実際の長さが異なる等。空ボディで渡しハンドラ側でエラーにする
+        Err(_) => (Request::from_parts(parts, Body::empty()), None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_actor_and_source_ip() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(actor(&headers), "anonymous");
+        headers.insert("x-user-id", "user-1".parse().unwrap());
+        assert_eq!(actor(&headers), "user-1");
+        headers.insert("x-actor", "operator".parse().unwrap());
+        assert_eq!(actor(&headers), "operator");
+
+        headers.insert("x-forwarded-for", "203.0.113.7, 10.0.0.1".parse().unwrap());
+        let proxy: SocketAddr = "127.0.0.1:50000".parse().unwrap();
+        let direct: SocketAddr = "192.168.1.20:50000".parse().unwrap();
+        assert_eq!(source_ip(Some(proxy), &headers).as_deref(), Some("203.0.113.7"));
+        // 直接接続の場合はヘッダを信用しない
+        assert_eq!(source_ip(Some(direct), &headers).as_deref(), Some("192.168.1.20"));
+    }
+}
@@ -0,0 +1,213 @@
+//! Custom Preset API Routes
+//!
+//! ## エンドポイント
+//! - GET /api/presets/custom - カスタムプリセット定義一覧
+//! - POST /api/presets/custom - 作成（revision 1）
+//! - GET /api/presets/custom/:id - 定義＋構築済みプリセット
+//! - PUT /api/presets/custom/:id - 更新（revision+1, preset_version更新）
+//! - DELETE /api/presets/custom/:id - 削除（使用中はConflict）
+//! - GET /api/presets/custom/:id/versions - revision履歴
+
+use axum::{
+    extract::{Path, State},
+    response::IntoResponse,
+    routing::get,
+    Json, Router,
+};
+use serde_json::json;
+
+use crate::models::ApiResponse;
+use crate::preset_loader::{
+    validate_overrides, validate_preset_id, CustomPresetRepository, UpsertCustomPresetRequest,
+};
+use crate::state::AppState;
+
+/// Custom Preset API ルーター
+pub fn custom_preset_routes() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_custom_presets).post(create_custom_preset))
+        .route(
+            "/:id",
+            get(get_custom_preset)
+                .put(update_custom_preset)
+                .delete(delete_custom_preset),
+        )
+        .route("/:id/versions", get(get_custom_preset_versions))
+}
+
+fn repository(state: &AppState) -> CustomPresetRepository {
+    CustomPresetRepository::new(state.pool.clone())
+}
+
+/// Validate request and save, then refresh the in-memory PresetLoader
+async fn save_custom_preset(
+    state: &AppState,
+    preset_id: &str,
+    req: UpsertCustomPresetRequest,
+) -> crate::Result<serde_json::Value> {
+    if req.name.is_empty() || req.name.len() > 128 {
+        return Err(crate::Error::Validation("name must be 1-128 characters".to_string()));
+    }
+    // 継承は組み込みプリセットのみ（カスタム同士の連鎖は不可）
+    let Some(base) = state.preset_loader.get_builtin(&req.base_preset_id).cloned() else {
+        return Err(crate::Error::Validation(format!(
+            "base_preset_id '{}' is not a built-in preset",
+            req.base_preset_id
+        )));
+    };
+    validate_overrides(&req.overrides)?;
+
+    let definition = repository(state)
+        .save(preset_id, &req, |d| d.build(&base))
+        .await?;
+    let preset = state.preset_loader.apply_definition(&definition);
+
+    tracing::info!(
+        preset_id = %preset_id,
+        base_preset_id = %definition.base_preset_id,
+        revision = definition.revision,
+        enabled = definition.enabled,
+        "Custom preset saved"
+    );
+
+    Ok(json!({
+        "definition": definition,
+        "preset": preset,
+    }))
+}
+
+/// GET /api/presets/custom
+async fn list_custom_presets(State(state): State<AppState>) -> impl IntoResponse {
+    match repository(&state).list().await {
+        Ok(definitions) => Json(ApiResponse::success(definitions)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// POST /api/presets/custom
+async fn create_custom_preset(
+    State(state): State<AppState>,
+    Json(req): Json<UpsertCustomPresetRequest>,
+) -> impl IntoResponse {
+    let preset_id = req.preset_id.clone().unwrap_or_default();
+    if let Err(e) = validate_preset_id(&preset_id) {
+        return e.into_response();
+    }
+    if state.preset_loader.is_builtin(&preset_id) {
+        return crate::Error::Conflict(format!("'{}' is a built-in preset", preset_id))
+            .into_response();
+    }
+    match repository(&state).get(&preset_id).await {
+        Ok(Some(_)) => {
+            return crate::Error::Conflict(format!("Preset {} already exists", preset_id))
+                .into_response()
+        }
+        Ok(None) => {}
+        Err(e) => return e.into_response(),
+    }
+
+    match save_custom_preset(&state, &preset_id, req).await {
+        Ok(saved) => Json(ApiResponse::success(saved)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// GET /api/presets/custom/:id
+async fn get_custom_preset(State(state): State<AppState>, Path(id): Path<String>) -> impl IntoResponse {
+    match repository(&state).get(&id).await {
+        Ok(Some(definition)) => {
+            let preset = state
+                .preset_loader
+                .get_builtin(&definition.base_preset_id)
+                .map(|base| definition.build(base));
+            Json(ApiResponse::success(json!({
+                "definition": definition,
+                "preset": preset,
+            })))
+            .into_response()
+        }
+        Ok(None) => crate::Error::NotFound(format!("Preset {} not found", id)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// PUT /api/presets/custom/:id
+async fn update_custom_preset(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(req): Json<UpsertCustomPresetRequest>,
+) -> impl IntoResponse {
+    match repository(&state).get(&id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return crate::Error::NotFound(format!("Preset {} not found", id)).into_response(),
+        Err(e) => return e.into_response(),
+    }
+
+    match save_custom_preset(&state, &id, req).await {
+        Ok(saved) => Json(ApiResponse::success(saved)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// DELETE /api/presets/custom/:id
+///
+/// カメラ・プリセットスケジュールから参照中の場合は削除不可
+async fn delete_custom_preset(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    let cameras = state.config_store.get_cached_cameras().await;
+    let in_use: Vec<String> = cameras
+        .iter()
+        .filter(|c| c.deleted_at.is_none() && c.preset_id.as_deref() == Some(id.as_str()))
+        .map(|c| c.camera_id.clone())
+        .collect();
+    if !in_use.is_empty() {
+        return crate::Error::Conflict(format!(
+            "Preset {} is used by cameras: {}",
+            id,
+            in_use.join(", ")
+        ))
+        .into_response();
+    }
+
+    match state.polling.preset_schedule().repository().list_schedules().await {
+        Ok(schedules) => {
+            let referencing: Vec<String> = schedules
+                .iter()
+                .filter(|s| s.rules.iter().any(|r| r.preset_id == id))
+                .map(|s| s.schedule_id.clone())
+                .collect();
+            if !referencing.is_empty() {
+                return crate::Error::Conflict(format!(
+                    "Preset {} is used by schedules: {}",
+                    id,
+                    referencing.join(", ")
+                ))
+                .into_response();
+            }
+        }
+        Err(e) => return e.into_response(),
+    }
+
+    match repository(&state).delete(&id).await {
+        Ok(true) => {
+            state.preset_loader.unregister(&id);
+            tracing::info!(preset_id = %id, "Custom preset deleted");
+            Json(ApiResponse::success(json!({ "deleted": id }))).into_response()
+        }
+        Ok(false) => crate::Error::NotFound(format!("Preset {} not found", id)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// GET /api/presets/custom/:id/versions
+async fn get_custom_preset_versions(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    match repository(&state).versions(&id).await {
+        Ok(versions) => Json(ApiResponse::success(versions)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
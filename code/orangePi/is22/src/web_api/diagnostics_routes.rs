@@ -0,0 +1,97 @@
+//! Camera Diagnostics API Routes
+//!
+//! ## エンドポイント
+//! - GET /api/diagnostics/cameras - 全カメラの最新診断結果
+//! - GET /api/diagnostics/cameras/config - 診断設定
+//! - PUT /api/diagnostics/cameras/config - 診断設定更新
+//! - GET /api/diagnostics/cameras/:camera_id - カメラの最新診断結果
+//! - POST /api/diagnostics/cameras/:camera_id/run - 即時診断
+//! - POST /api/diagnostics/cameras/:camera_id/reset-baseline - 画角急変の基準フレームを破棄
+
+use axum::{
+    extract::{Path, State},
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
+use serde_json::json;
+
+use crate::camera_diagnostics::DiagnosticsConfig;
+use crate::models::ApiResponse;
+use crate::state::AppState;
+
+/// Camera Diagnostics API ルーター
+pub fn diagnostics_routes() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_reports))
+        .route("/config", get(get_config).put(update_config))
+        .route("/:camera_id", get(get_report))
+        .route("/:camera_id/run", post(run_diagnostics))
+        .route("/:camera_id/reset-baseline", post(reset_baseline))
+}
+
+/// GET /api/diagnostics/cameras
+async fn list_reports(State(state): State<AppState>) -> impl IntoResponse {
+    Json(ApiResponse::success(state.camera_diagnostics.reports().await))
+}
+
+/// GET /api/diagnostics/cameras/config
+async fn get_config(State(state): State<AppState>) -> impl IntoResponse {
+    Json(ApiResponse::success(state.camera_diagnostics.config().await))
+}
+
+/// PUT /api/diagnostics/cameras/config
+async fn update_config(
+    State(state): State<AppState>,
+    Json(config): Json<DiagnosticsConfig>,
+) -> impl IntoResponse {
+    match state.camera_diagnostics.set_config(config).await {
+        Ok(saved) => Json(ApiResponse::success(saved)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// GET /api/diagnostics/cameras/:camera_id
+async fn get_report(
+    State(state): State<AppState>,
+    Path(camera_id): Path<String>,
+) -> impl IntoResponse {
+    match state.camera_diagnostics.report(&camera_id).await {
+        Some(report) => Json(ApiResponse::success(report)).into_response(),
+        None => crate::Error::NotFound(format!(
+            "No diagnostics result for camera {}",
+            camera_id
+        ))
+        .into_response(),
+    }
+}
+
+/// POST /api/diagnostics/cameras/:camera_id/run
+///
+/// ffprobe 計測を含むため数十秒かかる場合がある
+async fn run_diagnostics(
+    State(state): State<AppState>,
+    Path(camera_id): Path<String>,
+) -> impl IntoResponse {
+    let camera = state
+        .config_store
+        .get_cached_cameras()
+        .await
+        .into_iter()
+        .find(|c| c.camera_id == camera_id);
+    let Some(camera) = camera else {
+        return crate::Error::NotFound(format!("Camera {} not found", camera_id)).into_response();
+    };
+
+    let report = state.camera_diagnostics.run_camera(&camera).await;
+    Json(ApiResponse::success(report)).into_response()
+}
+
+/// POST /api/diagnostics/cameras/:camera_id/reset-baseline
+async fn reset_baseline(
+    State(state): State<AppState>,
+    Path(camera_id): Path<String>,
+) -> impl IntoResponse {
+    state.camera_diagnostics.reset_baseline(&camera_id).await;
+    Json(ApiResponse::success(json!({ "camera_id": camera_id, "reset": true })))
+}
@@ -0,0 +1,208 @@
+//! Email Notification API Routes
+//!
+//! ## エンドポイント
+//! - GET /api/notifications/email/config - SMTP設定（パスワードはマスク）
+//! - PUT /api/notifications/email/config - SMTP設定更新
+//! - GET /api/notifications/email/subscriptions - 購読一覧
+//! - POST /api/notifications/email/subscriptions - 購読作成
+//! - GET /api/notifications/email/subscriptions/:id - 購読取得
+//! - PUT /api/notifications/email/subscriptions/:id - 購読更新
+//! - DELETE /api/notifications/email/subscriptions/:id - 購読削除
+//! - GET /api/notifications/email/queue - 送信キュー一覧
+//! - POST /api/notifications/email/queue/process - 送信キューを即時処理
+//! - POST /api/notifications/email/test - テストメール送信
+
+use axum::{
+    extract::{Path, Query, State},
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
+use serde::Deserialize;
+use serde_json::json;
+
+use super::summary_routes::{get_config_fid, get_config_tid};
+use crate::email_notifier::{EmailStatus, SmtpConfig, UpsertEmailSubscriptionRequest};
+use crate::models::ApiResponse;
+use crate::state::AppState;
+
+/// Email Notification API ルーター
+pub fn email_routes() -> Router<AppState> {
+    Router::new()
+        .route("/config", get(get_smtp_config).put(update_smtp_config))
+        .route(
+            "/subscriptions",
+            get(list_subscriptions).post(create_subscription),
+        )
+        .route(
+            "/subscriptions/:id",
+            get(get_subscription)
+                .put(update_subscription)
+                .delete(delete_subscription),
+        )
+        .route("/queue", get(list_queue))
+        .route("/queue/process", post(process_queue))
+        .route("/test", post(send_test_email))
+}
+
+/// GET /api/notifications/email/config
+async fn get_smtp_config(State(state): State<AppState>) -> impl IntoResponse {
+    let config = state.email_notifier.smtp_config().await;
+    Json(ApiResponse::success(config.masked()))
+}
+
+/// PUT /api/notifications/email/config
+async fn update_smtp_config(
+    State(state): State<AppState>,
+    Json(config): Json<SmtpConfig>,
+) -> impl IntoResponse {
+    match state.email_notifier.set_smtp_config(config).await {
+        Ok(saved) => {
+            tracing::info!(
+                host = %saved.host,
+                port = saved.port,
+                enabled = saved.enabled,
+                "SMTP config updated"
+            );
+            Json(ApiResponse::success(saved.masked())).into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// GET /api/notifications/email/subscriptions
+async fn list_subscriptions(State(state): State<AppState>) -> impl IntoResponse {
+    match state.email_notifier.subscriptions().list().await {
+        Ok(subscriptions) => Json(ApiResponse::success(subscriptions)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// POST /api/notifications/email/subscriptions
+async fn create_subscription(
+    State(state): State<AppState>,
+    Json(req): Json<UpsertEmailSubscriptionRequest>,
+) -> impl IntoResponse {
+    if let Err(e) = req.validate() {
+        return e.into_response();
+    }
+    match state.email_notifier.subscriptions().save(None, &req).await {
+        Ok(subscription) => Json(ApiResponse::success(subscription)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// GET /api/notifications/email/subscriptions/:id
+async fn get_subscription(State(state): State<AppState>, Path(id): Path<u64>) -> impl IntoResponse {
+    match state.email_notifier.subscriptions().get(id).await {
+        Ok(Some(subscription)) => Json(ApiResponse::success(subscription)).into_response(),
+        Ok(None) => crate::Error::NotFound(format!("Subscription {} not found", id)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// PUT /api/notifications/email/subscriptions/:id
+async fn update_subscription(
+    State(state): State<AppState>,
+    Path(id): Path<u64>,
+    Json(req): Json<UpsertEmailSubscriptionRequest>,
+) -> impl IntoResponse {
+    if let Err(e) = req.validate() {
+        return e.into_response();
+    }
+    let repository = state.email_notifier.subscriptions();
+    match repository.get(id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return crate::Error::NotFound(format!("Subscription {} not found", id)).into_response()
+        }
+        Err(e) => return e.into_response(),
+    }
+    match repository.save(Some(id), &req).await {
+        Ok(subscription) => Json(ApiResponse::success(subscription)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// DELETE /api/notifications/email/subscriptions/:id
+async fn delete_subscription(State(state): State<AppState>, Path(id): Path<u64>) -> impl IntoResponse {
+    match state.email_notifier.subscriptions().delete(id).await {
+        Ok(true) => Json(ApiResponse::success(json!({ "deleted": id }))).into_response(),
+        Ok(false) => crate::Error::NotFound(format!("Subscription {} not found", id)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct QueueQuery {
+    status: Option<String>,
+    limit: Option<i32>,
+    offset: Option<i32>,
+}
+
+/// GET /api/notifications/email/queue
+async fn list_queue(State(state): State<AppState>, Query(query): Query<QueueQuery>) -> impl IntoResponse {
+    let status = match query.status.as_deref() {
+        None | Some("") => None,
+        Some(s) => match EmailStatus::parse(s) {
+            Some(status) => Some(status),
+            None => {
+                return crate::Error::Validation(format!("Invalid status: {}", s)).into_response()
+            }
+        },
+    };
+    let limit = query.limit.unwrap_or(50).clamp(1, 500);
+    let offset = query.offset.unwrap_or(0).max(0);
+
+    match state.email_notifier.queue().list(status, limit, offset).await {
+        Ok(items) => Json(ApiResponse::success(items)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// POST /api/notifications/email/queue/process
+async fn process_queue(State(state): State<AppState>) -> impl IntoResponse {
+    match state.email_notifier.process_queue(50).await {
+        Ok(stats) => Json(ApiResponse::success(stats)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct TestEmailRequest {
+    recipient: String,
+}
+
+/// POST /api/notifications/email/test
+///
+/// キュー登録後すぐに処理し、結果（送信成功/失敗理由）を返す
+async fn send_test_email(
+    State(state): State<AppState>,
+    Json(req): Json<TestEmailRequest>,
+) -> impl IntoResponse {
+    if !state.email_notifier.smtp_config().await.enabled {
+        return crate::Error::Validation("SMTP is not enabled".to_string()).into_response();
+    }
+    let tid = get_config_tid(&state).await.unwrap_or_default();
+    let fid = get_config_fid(&state).await.unwrap_or_default();
+
+    let queue_id = match state.email_notifier.enqueue_test(&tid, &fid, &req.recipient).await {
+        Ok(id) => id,
+        Err(e) => return e.into_response(),
+    };
+    if let Err(e) = state.email_notifier.process_queue(50).await {
+        return e.into_response();
+    }
+
+    let item = state
+        .email_notifier
+        .queue()
+        .list(None, 50, 0)
+        .await
+        .ok()
+        .and_then(|items| items.into_iter().find(|i| i.queue_id == queue_id));
+    match item {
+        Some(item) => Json(ApiResponse::success(item)).into_response(),
+        None => Json(ApiResponse::success(json!({ "queueId": queue_id }))).into_response(),
+    }
+}
@@ -0,0 +1,201 @@
+//! Incident Case API Routes
+//!
+//! ## エンドポイント
+//! - GET /api/incidents?status= - ケース一覧
+//! - POST /api/incidents - ケース作成
+//! - GET /api/incidents/device-key - 署名鍵の公開鍵・フィンガープリント（LacisID）
+//! - GET /api/incidents/:case_id - ケース詳細（保全イベント・エクスポート履歴）
+//! - PUT /api/incidents/:case_id - 件名・概要・状態（open / closed）の更新
+//! - POST /api/incidents/:case_id/events - イベント保全（{"log_ids": [...]}）
+//! - DELETE /api/incidents/:case_id/events/:log_id - 保全イベントの除外
+//! - GET /api/incidents/:case_id/custody - 管理履歴（chain of custody）とチェーン検証結果
+//! - GET /api/incidents/:case_id/exports - エクスポート履歴
+//! - POST /api/incidents/:case_id/exports - 署名付き証拠パッケージ生成
+//! - POST /api/incidents/:case_id/exports/:export_id/verify - パッケージ再検証
+//!
+//! 操作者は監査ログと同じく X-Actor / X-User-Id ヘッダから取得する。
+
+use axum::{
+    extract::{ConnectInfo, Path, Query, State},
+    http::HeaderMap,
+    response::IntoResponse,
+    routing::{delete, get, post},
+    Json, Router,
+};
+use serde::Deserialize;
+use serde_json::json;
+use std::net::SocketAddr;
+
+use super::audit_routes::{actor, source_ip};
+use crate::incident_case::{
+    verify_custody, AddEventsRequest, CaseStatus, CreateCaseRequest, CustodyActor, UpdateCaseRequest,
+};
+use crate::models::ApiResponse;
+use crate::state::AppState;
+
+/// Incident Case API ルーター
+pub fn incident_routes() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_cases).post(create_case))
+        .route("/device-key", get(get_device_key))
+        .route("/:case_id", get(get_case).put(update_case))
+        .route("/:case_id/events", post(add_events))
+        .route("/:case_id/events/:log_id", delete(remove_event))
+        .route("/:case_id/custody", get(get_custody))
+        .route("/:case_id/exports", get(list_exports).post(export_case))
+        .route("/:case_id/exports/:export_id/verify", post(verify_export))
+}
+
+fn custody_actor(headers: &HeaderMap, connect_info: Option<ConnectInfo<SocketAddr>>) -> CustodyActor {
+    CustodyActor {
+        name: actor(headers),
+        source_ip: source_ip(connect_info.map(|ConnectInfo(addr)| addr), headers),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ListQuery {
+    status: Option<CaseStatus>,
+}
+
+/// GET /api/incidents
+async fn list_cases(State(state): State<AppState>, Query(query): Query<ListQuery>) -> impl IntoResponse {
+    match state.incident_cases.list_cases(query.status).await {
+        Ok(cases) => Json(ApiResponse::success(cases)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// POST /api/incidents
+async fn create_case(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    Json(request): Json<CreateCaseRequest>,
+) -> impl IntoResponse {
+    let actor = custody_actor(&headers, connect_info);
+    match state.incident_cases.create_case(request, &actor).await {
+        Ok(case) => Json(ApiResponse::success(case)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// GET /api/incidents/device-key
+async fn get_device_key(State(state): State<AppState>) -> impl IntoResponse {
+    match state.incident_cases.device_key().await {
+        Ok(key) => Json(ApiResponse::success(key.info())).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// GET /api/incidents/:case_id
+async fn get_case(State(state): State<AppState>, Path(case_id): Path<u64>) -> impl IntoResponse {
+    let service = &state.incident_cases;
+    let result = async {
+        let case = service.get_case(case_id).await?;
+        let events = service.list_events(case_id).await?;
+        let exports = service.list_exports(case_id).await?;
+        Ok::<_, crate::Error>(json!({ "case": case, "events": events, "exports": exports }))
+    }
+    .await;
+    match result {
+        Ok(detail) => Json(ApiResponse::success(detail)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// PUT /api/incidents/:case_id
+async fn update_case(
+    State(state): State<AppState>,
+    Path(case_id): Path<u64>,
+    headers: HeaderMap,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    Json(request): Json<UpdateCaseRequest>,
+) -> impl IntoResponse {
+    let actor = custody_actor(&headers, connect_info);
+    match state.incident_cases.update_case(case_id, request, &actor).await {
+        Ok(case) => Json(ApiResponse::success(case)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// POST /api/incidents/:case_id/events
+async fn add_events(
+    State(state): State<AppState>,
+    Path(case_id): Path<u64>,
+    headers: HeaderMap,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    Json(request): Json<AddEventsRequest>,
+) -> impl IntoResponse {
+    let actor = custody_actor(&headers, connect_info);
+    match state.incident_cases.add_events(case_id, &request.log_ids, &actor).await {
+        Ok(events) => Json(ApiResponse::success(events)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// DELETE /api/incidents/:case_id/events/:log_id
+async fn remove_event(
+    State(state): State<AppState>,
+    Path((case_id, log_id)): Path<(u64, u64)>,
+    headers: HeaderMap,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+) -> impl IntoResponse {
+    let actor = custody_actor(&headers, connect_info);
+    match state.incident_cases.remove_event(case_id, log_id, &actor).await {
+        Ok(()) => Json(ApiResponse::success(json!({ "case_id": case_id, "log_id": log_id }))).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// GET /api/incidents/:case_id/custody
+async fn get_custody(State(state): State<AppState>, Path(case_id): Path<u64>) -> impl IntoResponse {
+    match state.incident_cases.custody(case_id).await {
+        Ok(entries) => {
+            let broken_at = verify_custody(&entries).err();
+            Json(ApiResponse::success(json!({
+                "entries": entries,
+                "chain_valid": broken_at.is_none(),
+                "first_broken_id": broken_at,
+            })))
+            .into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// GET /api/incidents/:case_id/exports
+async fn list_exports(State(state): State<AppState>, Path(case_id): Path<u64>) -> impl IntoResponse {
+    match state.incident_cases.list_exports(case_id).await {
+        Ok(exports) => Json(ApiResponse::success(exports)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// POST /api/incidents/:case_id/exports
+async fn export_case(
+    State(state): State<AppState>,
+    Path(case_id): Path<u64>,
+    headers: HeaderMap,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+) -> impl IntoResponse {
+    let actor = custody_actor(&headers, connect_info);
+    match state.incident_cases.export(case_id, &actor).await {
+        Ok(export) => Json(ApiResponse::success(export)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// POST /api/incidents/:case_id/exports/:export_id/verify
+async fn verify_export(
+    State(state): State<AppState>,
+    Path((case_id, export_id)): Path<(u64, u64)>,
+    headers: HeaderMap,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+) -> impl IntoResponse {
+    let actor = custody_actor(&headers, connect_info);
+    match state.incident_cases.verify_export(case_id, export_id, &actor).await {
+        Ok(result) => Json(ApiResponse::success(result)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
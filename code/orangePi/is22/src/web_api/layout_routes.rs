@@ -0,0 +1,106 @@
+//! Live Layout API Routes
+//!
+//! ## エンドポイント
+//! - GET /api/layouts?user_id= - レイアウト一覧
+//! - POST /api/layouts - レイアウト作成
+//! - GET /api/layouts/:id - レイアウト取得
+//! - PUT /api/layouts/:id - レイアウト更新
+//! - DELETE /api/layouts/:id - レイアウト削除
+//! - GET /api/layouts/:id/plan?page= - ページ表示計画（予算内のライブ/スナップショット割当）
+
+use axum::{
+    extract::{Path, Query, State},
+    response::IntoResponse,
+    routing::get,
+    Json, Router,
+};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::live_layout::LayoutRequest;
+use crate::models::ApiResponse;
+use crate::state::AppState;
+
+/// Live Layout API ルーター
+pub fn layout_routes() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_layouts).post(create_layout))
+        .route("/:id", get(get_layout).put(update_layout).delete(delete_layout))
+        .route("/:id/plan", get(get_plan))
+}
+
+#[derive(Debug, Deserialize)]
+struct ListQuery {
+    user_id: Option<String>,
+}
+
+/// GET /api/layouts
+async fn list_layouts(
+    State(state): State<AppState>,
+    Query(query): Query<ListQuery>,
+) -> impl IntoResponse {
+    let user_id = query.user_id.as_deref().filter(|s| !s.is_empty());
+    match state.live_layouts.list(user_id).await {
+        Ok(layouts) => Json(ApiResponse::success(layouts)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// POST /api/layouts
+async fn create_layout(
+    State(state): State<AppState>,
+    Json(request): Json<LayoutRequest>,
+) -> impl IntoResponse {
+    match state.live_layouts.create(request).await {
+        Ok(layout) => Json(ApiResponse::success(layout)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// GET /api/layouts/:id
+async fn get_layout(State(state): State<AppState>, Path(id): Path<u64>) -> impl IntoResponse {
+    match state.live_layouts.get(id).await {
+        Ok(layout) => Json(ApiResponse::success(layout)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// PUT /api/layouts/:id
+async fn update_layout(
+    State(state): State<AppState>,
+    Path(id): Path<u64>,
+    Json(request): Json<LayoutRequest>,
+) -> impl IntoResponse {
+    match state.live_layouts.update(id, request).await {
+        Ok(layout) => Json(ApiResponse::success(layout)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// DELETE /api/layouts/:id
+async fn delete_layout(State(state): State<AppState>, Path(id): Path<u64>) -> impl IntoResponse {
+    match state.live_layouts.delete(id).await {
+        Ok(true) => Json(ApiResponse::success(json!({ "deleted": id }))).into_response(),
+        Ok(false) => crate::Error::NotFound(format!("Layout {} not found", id)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PlanQuery {
+    page: Option<usize>,
+}
+
+/// GET /api/layouts/:id/plan
+///
+/// ツアー表示ではクライアントが rotate_after_sec 後に next_page を要求する
+async fn get_plan(
+    State(state): State<AppState>,
+    Path(id): Path<u64>,
+    Query(query): Query<PlanQuery>,
+) -> impl IntoResponse {
+    match state.live_layouts.plan(id, query.page.unwrap_or(0)).await {
+        Ok(plan) => Json(ApiResponse::success(plan)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
@@ -0,0 +1,107 @@
+//! Camera Maintenance API Routes
+//!
+//! ## エンドポイント
+//! - GET /api/maintenance/cameras/inventory - メーカー/機種別ファームウェアインベントリ
+//! - GET /api/maintenance/cameras/clock - 全カメラの最新時刻チェック結果
+//! - GET /api/maintenance/cameras/firmware-history - ファームウェア変更履歴
+//! - GET /api/maintenance/cameras/config - メンテナンス設定
+//! - PUT /api/maintenance/cameras/config - メンテナンス設定更新
+//! - POST /api/maintenance/cameras/run - 全カメラ即時チェック
+//! - POST /api/maintenance/cameras/:camera_id/run - 1カメラ即時チェック
+
+use axum::{
+    extract::{Path, Query, State},
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
+use serde::Deserialize;
+
+use crate::camera_maintenance::MaintenanceConfig;
+use crate::models::ApiResponse;
+use crate::state::AppState;
+
+/// Camera Maintenance API ルーター
+pub fn maintenance_routes() -> Router<AppState> {
+    Router::new()
+        .route("/inventory", get(get_inventory))
+        .route("/clock", get(get_clock_statuses))
+        .route("/firmware-history", get(get_firmware_history))
+        .route("/config", get(get_config).put(update_config))
+        .route("/run", post(run_all))
+        .route("/:camera_id/run", post(run_camera))
+}
+
+/// GET /api/maintenance/cameras/inventory
+async fn get_inventory(State(state): State<AppState>) -> impl IntoResponse {
+    Json(ApiResponse::success(state.camera_maintenance.firmware_inventory().await))
+}
+
+/// GET /api/maintenance/cameras/clock
+async fn get_clock_statuses(State(state): State<AppState>) -> impl IntoResponse {
+    Json(ApiResponse::success(state.camera_maintenance.clock_statuses().await))
+}
+
+#[derive(Debug, Deserialize)]
+struct HistoryQuery {
+    camera_id: Option<String>,
+    limit: Option<u32>,
+}
+
+/// GET /api/maintenance/cameras/firmware-history
+async fn get_firmware_history(
+    State(state): State<AppState>,
+    Query(query): Query<HistoryQuery>,
+) -> impl IntoResponse {
+    let limit = query.limit.unwrap_or(100).clamp(1, 1000);
+    match state
+        .camera_maintenance
+        .firmware_history(query.camera_id.as_deref().filter(|s| !s.is_empty()), limit)
+        .await
+    {
+        Ok(history) => Json(ApiResponse::success(history)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// GET /api/maintenance/cameras/config
+async fn get_config(State(state): State<AppState>) -> impl IntoResponse {
+    Json(ApiResponse::success(state.camera_maintenance.config().await))
+}
+
+/// PUT /api/maintenance/cameras/config
+async fn update_config(
+    State(state): State<AppState>,
+    Json(config): Json<MaintenanceConfig>,
+) -> impl IntoResponse {
+    match state.camera_maintenance.set_config(config).await {
+        Ok(saved) => Json(ApiResponse::success(saved)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// POST /api/maintenance/cameras/run
+///
+/// カメラ台数 × request_timeout_secs かかる場合がある
+async fn run_all(State(state): State<AppState>) -> impl IntoResponse {
+    Json(ApiResponse::success(state.camera_maintenance.run_all().await))
+}
+
+/// POST /api/maintenance/cameras/:camera_id/run
+async fn run_camera(
+    State(state): State<AppState>,
+    Path(camera_id): Path<String>,
+) -> impl IntoResponse {
+    let camera = state
+        .config_store
+        .get_cached_cameras()
+        .await
+        .into_iter()
+        .find(|c| c.camera_id == camera_id);
+    let Some(camera) = camera else {
+        return crate::Error::NotFound(format!("Camera {} not found", camera_id)).into_response();
+    };
+
+    let result = state.camera_maintenance.run_camera(&camera).await;
+    Json(ApiResponse::success(result)).into_response()
+}
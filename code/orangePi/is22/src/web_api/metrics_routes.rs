@@ -0,0 +1,43 @@
+//! Prometheus Metrics Routes
+//!
+//! ## エンドポイント
+//! - GET /metrics - Prometheus text exposition format
+
+use axum::{extract::State, http::header, response::IntoResponse, routing::get, Router};
+
+use crate::metrics::{self, TextEncoder, TEXT_CONTENT_TYPE};
+use crate::state::AppState;
+
+/// Metrics ルーター
+pub fn metrics_routes() -> Router<AppState> {
+    Router::new().route("/", get(get_metrics))
+}
+
+/// GET /metrics
+async fn get_metrics(State(state): State<AppState>) -> impl IntoResponse {
+    let mut enc = TextEncoder::new();
+    state.metrics.encode(&mut enc);
+
+    enc.gauge(
+        "is22_websocket_clients",
+        "Connected realtime WebSocket clients",
+        state.realtime.connection_count() as f64,
+    );
+
+    let cache = state.prev_frame_cache.stats().await;
+    enc.gauge(
+        "is22_prev_frame_cache_cameras",
+        "Cameras with a cached previous frame",
+        cache.camera_count as f64,
+    );
+    enc.gauge(
+        "is22_prev_frame_cache_bytes",
+        "Bytes held by the previous frame cache",
+        cache.total_bytes as f64,
+    );
+
+    metrics::encode_disk_metrics(&mut enc);
+    metrics::encode_database_metrics(&state.pool, &mut enc).await;
+
+    ([(header::CONTENT_TYPE, TEXT_CONTENT_TYPE)], enc.finish())
+}
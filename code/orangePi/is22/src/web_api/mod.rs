@@ -7,53 +7,117 @@
 //! - Response formatting
 
 mod access_absorber_routes;
+mod audit_routes;
 mod chat_routes;
+mod custom_preset_routes;
+mod diagnostics_routes;
+mod email_routes;
+mod incident_routes;
+mod layout_routes;
+mod maintenance_routes;
+mod metrics_routes;
 mod paraclate_routes;
+mod preset_schedule_routes;
 mod ptz_routes;
 mod register_routes;
 mod routes;
 mod sdm_routes;
+mod storage_routes;
 mod summary_routes;
+mod summary_template_routes;
 
 pub use access_absorber_routes::access_absorber_routes;
+pub use audit_routes::{audit_middleware, audit_routes};
 pub use chat_routes::chat_routes;
+pub use custom_preset_routes::custom_preset_routes;
+pub use diagnostics_routes::diagnostics_routes;
+pub use email_routes::email_routes;
+pub use incident_routes::incident_routes;
+pub use layout_routes::layout_routes;
+pub use maintenance_routes::maintenance_routes;
+pub use metrics_routes::metrics_routes;
 pub use paraclate_routes::paraclate_routes;
+pub use preset_schedule_routes::preset_schedule_routes;
 pub use ptz_routes::{ptz_home, ptz_move, ptz_status, ptz_stop};
 pub use register_routes::register_routes;
 pub use routes::create_router;
 pub use sdm_routes::sdm_routes;
 pub use summary_routes::summary_routes;
+pub use summary_template_routes::summary_template_routes;
 
 use axum::extract::State;
+use axum::http::StatusCode;
 use axum::response::IntoResponse;
 use axum::Json;
 use serde_json::json;
 
+use crate::health::{self, ComponentStatus};
 use crate::state::AppState;
 use crate::models::HealthResponse;
 
 /// Health check endpoint
 pub async fn health_check(State(state): State<AppState>) -> impl IntoResponse {
-    let is21_ok = state.ai_client.health_check().await.unwrap_or(false);
-    let go2rtc_ok = state.stream.health_check().await.unwrap_or(false);
+    let (is21_ok, go2rtc_ok, db) = tokio::join!(
+        state.ai_client.health_check(),
+        state.stream.health_check(),
+        health::check_database(&state),
+    );
+    let is21_ok = is21_ok.unwrap_or(false);
+    let go2rtc_ok = go2rtc_ok.unwrap_or(false);
+    let db_ok = db.status == ComponentStatus::Ok;
 
     let response = HealthResponse {
-        status: "ok".to_string(),
+        status: if db_ok { "ok" } else { "degraded" }.to_string(),
         version: env!("CARGO_PKG_VERSION").to_string(),
-        uptime_sec: 0, // TODO: Track uptime
+        uptime_sec: state.health.uptime().as_secs(),
         is21_connected: is21_ok,
         go2rtc_connected: go2rtc_ok,
-        db_connected: true, // If we got here, DB is connected
+        db_connected: db_ok,
     };
 
     Json(response)
 }
 
+/// Liveness endpoint (watchdog): 503 while any background loop is stalled
+pub async fn liveness(State(state): State<AppState>) -> impl IntoResponse {
+    let stalled = state.health.stalled();
+    let (code, status) = if stalled.is_empty() {
+        (StatusCode::OK, ComponentStatus::Ok)
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, ComponentStatus::Degraded)
+    };
+    (
+        code,
+        Json(json!({
+            "status": status,
+            "uptime_sec": state.health.uptime().as_secs(),
+            "stalled_loops": stalled,
+        })),
+    )
+}
+
+/// Readiness endpoint: per-component report, 503 when the DB is down or shutting down
+pub async fn readiness(State(state): State<AppState>) -> impl IntoResponse {
+    let report = health::readiness(&state).await;
+    let code = if report.status == ComponentStatus::Down {
+        StatusCode::SERVICE_UNAVAILABLE
+    } else {
+        StatusCode::OK
+    };
+    (code, Json(report))
+}
+
 /// Status endpoint (araneaDevices common)
-pub async fn device_status(State(_state): State<AppState>) -> impl IntoResponse {
+pub async fn device_status(State(state): State<AppState>) -> impl IntoResponse {
+    let status = if state.health.stalled().is_empty() {
+        "running"
+    } else {
+        "degraded"
+    };
     Json(json!({
         "device_type": "ar-is22",
         "firmware_version": env!("CARGO_PKG_VERSION"),
-        "status": "running"
+        "status": status,
+        "uptime_sec": state.health.uptime().as_secs()
     }))
 }
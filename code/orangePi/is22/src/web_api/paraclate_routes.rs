@@ -13,13 +13,16 @@
 //! - POST /api/paraclate/queue/process - キュー処理実行
 //! - POST /api/paraclate/pubsub/push - Pub/Sub Push通知受信 (T4-7)
 //! - POST /api/paraclate/notify - 直接通知受信 (T4-7)
+//! - GET /api/paraclate/summary/:id/report - Summary/GrandSummaryの印刷用レポート（HTML/PDF）
 //!
 //! ## セキュリティ (Issue #119)
 //! - 全エンドポイントでFID所属検証を実施
 //! - SKIP_FID_VALIDATION=1 で検証をスキップ可能（開発用）
 
 use axum::{
-    extract::{Query, State},
+    extract::{Path, Query, State},
+    http::header,
+    response::{IntoResponse, Response},
     routing::{get, post, put},
     Json, Router,
 };
@@ -32,6 +35,7 @@ use crate::paraclate_client::{
     UpdateConfigRequest,
 };
 use crate::state::AppState;
+use crate::summary_service::ReportFormat;
 use crate::Result;
 
 /// Paraclate APIルートを作成
@@ -49,6 +53,8 @@ pub fn paraclate_routes() -> Router<AppState> {
         .route("/notify", post(handle_direct_notification))
         // AI Chat (Paraclate_DesignOverview.md準拠)
         .route("/chat", post(handle_ai_chat))
+        // 印刷用レポート
+        .route("/summary/:id/report", get(get_summary_report))
 }
 
 /// TIDクエリパラメータ（将来のマルチテナント対応用）
@@ -86,6 +92,16 @@ fn default_limit() -> i32 {
     50
 }
 
+/// レポート取得クエリパラメータ
+#[derive(Debug, Deserialize)]
+pub struct ReportQuery {
+    /// "html"（既定） / "pdf"
+    pub format: Option<String>,
+    /// true: Content-Disposition: attachment
+    #[serde(default)]
+    pub download: bool,
+}
+
 /// FID所属検証ヘルパー (Issue #119)
 ///
 /// FIDがデバイスの登録TIDに所属しているか検証する。
@@ -556,3 +572,49 @@ async fn build_ai_context(
         facility_context,
     })
 }
+
+/// GET /api/paraclate/summary/:id/report
+///
+/// Summary/GrandSummaryの印刷用レポートを取得（保存済みがあれば優先）
+async fn get_summary_report(
+    State(state): State<AppState>,
+    Path(summary_id): Path<u64>,
+    Query(query): Query<ReportQuery>,
+) -> Result<Response> {
+    let format = match query.format.as_deref() {
+        None => ReportFormat::Html,
+        Some(f) => ReportFormat::parse(f).ok_or_else(|| {
+            crate::Error::Validation("format must be 'html' or 'pdf'".to_string())
+        })?,
+    };
+
+    let summary = state
+        .summary_repository
+        .get_by_id(summary_id)
+        .await?
+        .ok_or_else(|| crate::Error::NotFound(format!("Summary {} not found", summary_id)))?;
+
+    // Issue #119: FID所属検証
+    validate_fid(&state, &summary.fid).await?;
+
+    let report = state.report_service.get_or_render(summary_id, format).await?;
+
+    let disposition = format!(
+        "{}; filename=\"{}\"",
+        if query.download || format == ReportFormat::Pdf {
+            "attachment"
+        } else {
+            "inline"
+        },
+        report.filename
+    );
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        report.bytes,
+    )
+        .into_response())
+}
@@ -0,0 +1,271 @@
+//! Preset Schedule API Routes
+//!
+//! ## エンドポイント
+//! - GET /api/preset-schedules - スケジュール一覧
+//! - POST /api/preset-schedules - スケジュール作成
+//! - GET /api/preset-schedules/:id - スケジュール取得
+//! - PUT /api/preset-schedules/:id - スケジュール更新
+//! - DELETE /api/preset-schedules/:id - スケジュール削除
+//! - GET /api/preset-schedules/holidays - 祝日一覧
+//! - PUT /api/preset-schedules/holidays - 祝日追加/更新
+//! - DELETE /api/preset-schedules/holidays/:date - 祝日削除
+//! - GET /api/preset-schedules/switches - プリセット切替履歴
+//! - GET /api/preset-schedules/active - カメラ別の現在有効プリセット
+
+use axum::{
+    extract::{Path, Query, State},
+    response::IntoResponse,
+    routing::{delete, get},
+    Json, Router,
+};
+use chrono::{Duration, NaiveDate, Utc};
+use serde::Deserialize;
+
+use crate::models::ApiResponse;
+use crate::preset_schedule::{
+    validate_rules, PresetHoliday, ScheduleScope, UpsertPresetScheduleRequest,
+};
+use crate::state::AppState;
+
+/// Preset Schedule API ルーター
+pub fn preset_schedule_routes() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_schedules).post(create_schedule))
+        .route("/holidays", get(list_holidays).put(upsert_holiday))
+        .route("/holidays/:date", delete(delete_holiday))
+        .route("/switches", get(list_switches))
+        .route("/active", get(get_active_presets))
+        .route(
+            "/:id",
+            get(get_schedule).put(update_schedule).delete(delete_schedule),
+        )
+}
+
+/// Validate scope/rules against current cameras and presets
+fn validate_request(state: &AppState, req: &UpsertPresetScheduleRequest) -> crate::Result<()> {
+    if req.name.is_empty() || req.name.len() > 128 {
+        return Err(crate::Error::Validation("name must be 1-128 characters".to_string()));
+    }
+    match (req.scope, req.scope_id.as_deref()) {
+        (ScheduleScope::Global, None) => {}
+        (ScheduleScope::Global, Some(_)) => {
+            return Err(crate::Error::Validation(
+                "scope_id must be empty for global schedules".to_string(),
+            ))
+        }
+        (_, None) | (_, Some("")) => {
+            return Err(crate::Error::Validation(
+                "scope_id is required for camera/fid schedules".to_string(),
+            ))
+        }
+        _ => {}
+    }
+    validate_rules(&req.rules, |id| state.preset_loader.exists(id))
+}
+
+async fn save_schedule(
+    state: &AppState,
+    schedule_id: &str,
+    req: UpsertPresetScheduleRequest,
+) -> crate::Result<crate::preset_schedule::PresetSchedule> {
+    validate_request(state, &req)?;
+    if req.scope == ScheduleScope::Camera {
+        let camera_id = req.scope_id.as_deref().unwrap_or_default();
+        if state.config_store.service().get_camera(camera_id).await?.is_none() {
+            return Err(crate::Error::NotFound(format!("Camera {} not found", camera_id)));
+        }
+    }
+
+    let service = state.polling.preset_schedule();
+    service.repository().upsert_schedule(schedule_id, &req).await?;
+    service.refresh().await?;
+    service
+        .repository()
+        .get_schedule(schedule_id)
+        .await?
+        .ok_or_else(|| crate::Error::Internal(format!("Schedule {} not found after save", schedule_id)))
+}
+
+/// GET /api/preset-schedules
+async fn list_schedules(State(state): State<AppState>) -> impl IntoResponse {
+    match state.polling.preset_schedule().repository().list_schedules().await {
+        Ok(schedules) => Json(ApiResponse::success(schedules)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// POST /api/preset-schedules
+async fn create_schedule(
+    State(state): State<AppState>,
+    Json(req): Json<UpsertPresetScheduleRequest>,
+) -> impl IntoResponse {
+    let schedule_id = req
+        .schedule_id
+        .clone()
+        .filter(|id| !id.is_empty())
+        .unwrap_or_else(|| format!("sch-{}", uuid::Uuid::new_v4()));
+
+    match state
+        .polling
+        .preset_schedule()
+        .repository()
+        .get_schedule(&schedule_id)
+        .await
+    {
+        Ok(Some(_)) => {
+            return crate::Error::Conflict(format!("Schedule {} already exists", schedule_id))
+                .into_response()
+        }
+        Ok(None) => {}
+        Err(e) => return e.into_response(),
+    }
+
+    match save_schedule(&state, &schedule_id, req).await {
+        Ok(schedule) => {
+            tracing::info!(schedule_id = %schedule_id, "Preset schedule created");
+            Json(ApiResponse::success(schedule)).into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// GET /api/preset-schedules/:id
+async fn get_schedule(State(state): State<AppState>, Path(id): Path<String>) -> impl IntoResponse {
+    match state.polling.preset_schedule().repository().get_schedule(&id).await {
+        Ok(Some(schedule)) => Json(ApiResponse::success(schedule)).into_response(),
+        Ok(None) => crate::Error::NotFound(format!("Schedule {} not found", id)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// PUT /api/preset-schedules/:id
+async fn update_schedule(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(req): Json<UpsertPresetScheduleRequest>,
+) -> impl IntoResponse {
+    match state.polling.preset_schedule().repository().get_schedule(&id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return crate::Error::NotFound(format!("Schedule {} not found", id)).into_response(),
+        Err(e) => return e.into_response(),
+    }
+
+    match save_schedule(&state, &id, req).await {
+        Ok(schedule) => {
+            tracing::info!(schedule_id = %id, "Preset schedule updated");
+            Json(ApiResponse::success(schedule)).into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// DELETE /api/preset-schedules/:id
+async fn delete_schedule(State(state): State<AppState>, Path(id): Path<String>) -> impl IntoResponse {
+    let service = state.polling.preset_schedule();
+    match service.repository().delete_schedule(&id).await {
+        Ok(true) => {
+            if let Err(e) = service.refresh().await {
+                tracing::warn!(error = %e, "Failed to refresh preset schedules");
+            }
+            tracing::info!(schedule_id = %id, "Preset schedule deleted");
+            Json(ApiResponse::success(serde_json::json!({ "deleted": id }))).into_response()
+        }
+        Ok(false) => crate::Error::NotFound(format!("Schedule {} not found", id)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// GET /api/preset-schedules/holidays
+async fn list_holidays(State(state): State<AppState>) -> impl IntoResponse {
+    match state.polling.preset_schedule().repository().list_holidays().await {
+        Ok(holidays) => Json(ApiResponse::success(holidays)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// PUT /api/preset-schedules/holidays
+async fn upsert_holiday(
+    State(state): State<AppState>,
+    Json(holiday): Json<PresetHoliday>,
+) -> impl IntoResponse {
+    let service = state.polling.preset_schedule();
+    match service.repository().upsert_holiday(&holiday).await {
+        Ok(()) => {
+            if let Err(e) = service.refresh().await {
+                tracing::warn!(error = %e, "Failed to refresh preset schedules");
+            }
+            Json(ApiResponse::success(holiday)).into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// DELETE /api/preset-schedules/holidays/:date
+async fn delete_holiday(State(state): State<AppState>, Path(date): Path<String>) -> impl IntoResponse {
+    let date = match NaiveDate::parse_from_str(&date, "%Y-%m-%d") {
+        Ok(d) => d,
+        Err(_) => {
+            return crate::Error::Validation("date must be YYYY-MM-DD".to_string()).into_response()
+        }
+    };
+
+    let service = state.polling.preset_schedule();
+    match service.repository().delete_holiday(date).await {
+        Ok(true) => {
+            if let Err(e) = service.refresh().await {
+                tracing::warn!(error = %e, "Failed to refresh preset schedules");
+            }
+            Json(ApiResponse::success(serde_json::json!({ "deleted": date }))).into_response()
+        }
+        Ok(false) => crate::Error::NotFound(format!("Holiday {} not found", date)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SwitchLogQuery {
+    camera_id: Option<String>,
+    #[serde(default = "default_switch_hours")]
+    hours: i64,
+    #[serde(default = "default_switch_limit")]
+    limit: i64,
+}
+
+fn default_switch_hours() -> i64 {
+    24
+}
+
+fn default_switch_limit() -> i64 {
+    200
+}
+
+/// GET /api/preset-schedules/switches?camera_id=&hours=24&limit=200
+async fn list_switches(
+    State(state): State<AppState>,
+    Query(query): Query<SwitchLogQuery>,
+) -> impl IntoResponse {
+    let since = Utc::now() - Duration::hours(query.hours.clamp(1, 24 * 90));
+    match state
+        .polling
+        .preset_schedule()
+        .repository()
+        .list_switch_logs(query.camera_id.as_deref(), since, query.limit.clamp(1, 1000))
+        .await
+    {
+        Ok(logs) => Json(ApiResponse::success(logs)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// GET /api/preset-schedules/active - 現時点の解決結果（全カメラ）
+async fn get_active_presets(State(state): State<AppState>) -> impl IntoResponse {
+    let service = state.polling.preset_schedule();
+    let now = Utc::now();
+    let cameras = state.config_store.get_cached_cameras().await;
+
+    let mut active = std::collections::BTreeMap::new();
+    for camera in cameras.iter().filter(|c| c.deleted_at.is_none()) {
+        active.insert(camera.camera_id.clone(), service.resolve(camera, now).await);
+    }
+    Json(ApiResponse::success(active))
+}
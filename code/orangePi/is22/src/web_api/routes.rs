@@ -16,12 +16,12 @@ use serde_json::json;
 use sqlx::Row;
 
 use crate::access_absorber::AccessFamily;
-use crate::admission_controller::{LeaseRequest, LeaseResponse, StreamQuality};
+use crate::admission_controller::{LeaseGrant, LeaseRequest, LeaseResponse, ModalLease, StreamQuality};
 use crate::camera_brand::{
     AddGenericPathRequest, AddOuiRequest, AddTemplateRequest, CreateBrandRequest,
     UpdateBrandRequest, UpdateGenericPathRequest, UpdateOuiRequest, UpdateTemplateRequest,
 };
-use crate::config_store::{Camera, CreateCameraRequest, UpdateCameraRequest};
+use crate::config_store::{AdaptivePollingPolicy, Camera, MotionPrefilterPolicy, CreateCameraRequest, UpdateCameraRequest};
 use crate::inference_stats_service::StatsPeriod;
 use crate::models::ApiResponse;
 use crate::state::AppState;
@@ -31,6 +31,8 @@ pub fn create_router(state: AppState) -> Router {
     Router::new()
         // Health & Status
         .route("/healthz", get(super::health_check))
+        .route("/livez", get(super::liveness))
+        .route("/readyz", get(super::readiness))
         .route("/api/status", get(super::device_status))
         // Cameras
         .route("/api/cameras", get(list_cameras))
@@ -55,6 +57,8 @@ pub fn create_router(state: AppState) -> Router {
         .route("/api/modal/lease", post(request_lease))
         .route("/api/modal/lease/:id", delete(release_lease))
         .route("/api/modal/lease/:id/heartbeat", post(heartbeat))
+        .route("/api/modal/queue", get(list_queue))
+        .route("/api/modal/queue/:id", get(get_queue_ticket).delete(cancel_queue_ticket))
         // Suggest
         .route("/api/suggest", get(get_suggest))
         .route("/api/suggest/manual", post(set_manual_suggest))
@@ -76,10 +80,19 @@ pub fn create_router(state: AppState) -> Router {
         .route("/api/settings/is21", put(update_is21_config))
         .route("/api/settings/is21/test", post(test_is21_connection))
         .route("/api/settings/system", get(get_system_info))
+        .route("/api/settings/runtime", get(get_runtime_settings))
         .route("/api/settings/performance/logs", get(get_performance_logs))
         .route("/api/settings/polling/logs", get(get_polling_logs))
         .route("/api/settings/timeouts", get(get_global_timeouts))
         .route("/api/settings/timeouts", put(update_global_timeouts))
+        // Adaptive polling (per-camera intervals)
+        .route("/api/settings/polling/adaptive", get(get_adaptive_polling))
+        .route("/api/settings/polling/adaptive", put(update_adaptive_polling))
+        .route("/api/settings/polling/schedule", get(get_polling_schedule))
+        // Motion pre-filter (skip IS21 for unchanged scenes)
+        .route("/api/settings/polling/prefilter", get(get_motion_prefilter))
+        .route("/api/settings/polling/prefilter", put(update_motion_prefilter))
+        .route("/api/settings/polling/prefilter/stats", get(get_motion_prefilter_stats))
         // Storage Management (AIEventlog.md T1-5)
         .route("/api/settings/storage", get(get_storage_settings))
         .route("/api/settings/storage", put(update_storage_settings))
@@ -96,6 +109,8 @@ pub fn create_router(state: AppState) -> Router {
         .route("/api/stats/presets", get(get_preset_effectiveness))
         .route("/api/stats/storage", get(get_storage_stats))
         .route("/api/stats/anomalies", get(get_anomaly_cameras))
+        // Camera availability timeline / SLA (uptime, MTTR, MTBF)
+        .route("/api/stats/availability", get(get_camera_availability))
         // Misdetection Feedback (AIEventlog.md T4-6〜T4-10)
         .route("/api/feedback/misdetection", post(create_misdetection_feedback))
         .route("/api/feedback/misdetection/:camera_id", get(get_misdetection_feedback))
@@ -109,7 +124,10 @@ pub fn create_router(state: AppState) -> Router {
         .route("/api/attunement/calculate/:camera_id", post(calculate_attunement))
         .route("/api/attunement/apply/:camera_id", post(apply_attunement))
         .route("/api/attunement/auto-adjust", post(run_auto_adjustment))
+        .route("/api/attunement/policy", get(get_attunement_policy).put(update_attunement_policy))
+        .route("/api/attunement/trials", get(list_attunement_trials))
         // Preset Graphical UI (Issue #107: T2-1〜T2-4)
+        .route("/api/presets", get(list_presets))
         .route("/api/presets/balance", get(get_preset_balances))
         .route("/api/stats/overdetection", get(get_overdetection))
         .route("/api/stats/tags/:camera_id", get(get_tag_trends))
@@ -179,6 +197,9 @@ pub fn create_router(state: AppState) -> Router {
         .route("/api/streams", get(list_streams))
         .route("/api/streams/:camera_id", get(get_stream_urls))
         .route("/api/streams/:camera_id/snapshot", get(get_snapshot))
+        // go2rtc reconciler
+        .route("/api/go2rtc/reconcile", get(get_reconcile_plan).post(run_reconcile))
+        .route("/api/go2rtc/reconciler/config", get(get_reconciler_config).put(update_reconciler_config))
         // Snapshots (ffmpeg cached images for CameraGrid)
         .route("/api/snapshots/:camera_id/latest.jpg", get(get_cached_snapshot))
         // WebSocket
@@ -195,6 +216,28 @@ pub fn create_router(state: AppState) -> Router {
         .nest("/api", super::chat_routes::chat_routes())
         // Access Absorber (camera brand connection limits)
         .nest("/api/access-absorber", super::access_absorber_routes::access_absorber_routes())
+        // User-defined presets (DB-backed, inherit from built-in)
+        .nest("/api/presets/custom", super::custom_preset_routes::custom_preset_routes())
+        // Scheduled preset switching (JST weekly schedules)
+        .nest("/api/preset-schedules", super::preset_schedule_routes::preset_schedule_routes())
+        // Email notifications (SMTP config, subscriptions, send queue)
+        .nest("/api/notifications/email", super::email_routes::email_routes())
+        // Camera health diagnostics (stream quality, tamper detection)
+        .nest("/api/diagnostics/cameras", super::diagnostics_routes::diagnostics_routes())
+        // Camera maintenance (ONVIF firmware inventory, clock drift)
+        .nest("/api/maintenance/cameras", super::maintenance_routes::maintenance_routes())
+        // Storage manager (disk watermarks, per-store usage, image integrity)
+        .nest("/api/storage", super::storage_routes::storage_routes())
+        // Live wall layouts (named grids / tours)
+        .nest("/api/layouts", super::layout_routes::layout_routes())
+        // Audit trail (query / hash chain verification)
+        .nest("/api/audit", super::audit_routes::audit_routes())
+        // Incident cases (evidence preservation, signed export packages, chain of custody)
+        .nest("/api/incidents", super::incident_routes::incident_routes())
+        // Prometheus metrics (scrape endpoint)
+        .nest("/metrics", super::metrics_routes::metrics_routes())
+        // 変更系 API の監査記録（MatchedPath を使うためルート単位のレイヤーとして適用）
+        .layer(axum::middleware::from_fn_with_state(state.clone(), super::audit_routes::audit_middleware))
         .with_state(state)
 }
 
@@ -315,6 +358,9 @@ async fn delete_camera(
         Ok(_) => {
             // go2rtc cleanup is handled by polling_orchestrator at cycle start
             let _ = state.config_store.refresh_cache().await;
+            state.polling.adaptive_scheduler().remove(&id).await;
+            state.polling.motion_prefilter().remove(&id).await;
+            state.polling.preset_schedule().remove(&id).await;
             Json(serde_json::json!({"ok": true})).into_response()
         }
         Err(e) => e.into_response(),
@@ -981,25 +1027,76 @@ async fn auto_activate_camera(state: &AppState, camera_id: &str) -> crate::Resul
 // Modal Lease Handlers
 // ========================================
 
+fn lease_response(state: &AppState, lease: &ModalLease) -> LeaseResponse {
+    let stream_urls = state.stream.get_stream_urls(&lease.camera_id);
+    LeaseResponse {
+        lease_id: lease.lease_id,
+        allowed_quality: lease.quality,
+        downgraded: lease.quality != lease.requested_quality,
+        expires_at: lease.expires_at,
+        stream_url: stream_urls.webrtc_url,
+    }
+}
+
+/// POST /api/modal/lease
+///
+/// 即時発行なら 200 + LeaseResponse、待機キューに入った場合は 202 + QueueTicket
 async fn request_lease(
     State(state): State<AppState>,
     Json(req): Json<LeaseRequest>,
 ) -> impl IntoResponse {
-    match state.admission.request_lease(req.clone()).await {
-        Ok(lease) => {
-            let stream_urls = state.stream.get_stream_urls(&lease.camera_id);
-            let response = LeaseResponse {
-                lease_id: lease.lease_id,
-                allowed_quality: lease.quality,
-                expires_at: lease.expires_at,
-                stream_url: stream_urls.webrtc_url,
-            };
-            Json(ApiResponse::success(response)).into_response()
+    match state.admission.request_lease(req).await {
+        Ok(LeaseGrant::Granted(lease)) => {
+            Json(ApiResponse::success(lease_response(&state, &lease))).into_response()
+        }
+        Ok(LeaseGrant::Queued(ticket)) => {
+            (StatusCode::ACCEPTED, Json(ApiResponse::success(ticket))).into_response()
         }
         Err(e) => e.into_response(),
     }
 }
 
+/// GET /api/modal/queue - waiting tickets in service order
+async fn list_queue(State(state): State<AppState>) -> impl IntoResponse {
+    Json(ApiResponse::success(state.admission.waiting_tickets().await))
+}
+
+/// GET /api/modal/queue/:id - poll a ticket (keeps it alive)
+async fn get_queue_ticket(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    let ticket_id = match uuid::Uuid::parse_str(&id) {
+        Ok(id) => id,
+        Err(_) => return (StatusCode::BAD_REQUEST, Json(serde_json::json!({"error": "Invalid UUID"}))).into_response(),
+    };
+
+    match state.admission.get_ticket(&ticket_id).await {
+        Some(ticket) => {
+            let lease = ticket.lease.as_ref().map(|l| lease_response(&state, l));
+            Json(ApiResponse::success(json!({ "ticket": ticket, "lease": lease }))).into_response()
+        }
+        None => crate::Error::NotFound(format!("Queue ticket {} not found", ticket_id)).into_response(),
+    }
+}
+
+/// DELETE /api/modal/queue/:id - leave the queue
+async fn cancel_queue_ticket(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    let ticket_id = match uuid::Uuid::parse_str(&id) {
+        Ok(id) => id,
+        Err(_) => return (StatusCode::BAD_REQUEST, Json(serde_json::json!({"error": "Invalid UUID"}))).into_response(),
+    };
+
+    if state.admission.cancel_ticket(&ticket_id).await {
+        Json(serde_json::json!({"ok": true})).into_response()
+    } else {
+        crate::Error::NotFound(format!("Queue ticket {} not found", ticket_id)).into_response()
+    }
+}
+
 async fn release_lease(
     State(state): State<AppState>,
     Path(id): Path<String>,
@@ -1469,6 +1566,11 @@ struct SystemInfoResponse {
 }
 
 /// Get system information
+/// Effective runtime configuration (defaults → TOML file → env, credentials masked)
+async fn get_runtime_settings(State(state): State<AppState>) -> impl IntoResponse {
+    Json(ApiResponse::success(state.config.redacted()))
+}
+
 async fn get_system_info(State(state): State<AppState>) -> impl IntoResponse {
     use sysinfo::{System, Disks};
 
@@ -1790,6 +1892,8 @@ struct PollingLogEntry {
     failed_count: i32,
     /// タイムアウト件数
     timeout_count: i32,
+    /// 変化なし（IS21推論スキップ）件数
+    no_change_count: i32,
     /// 巡回所要時間 (ms)
     duration_ms: Option<i32>,
     /// 平均処理時間 (ms)
@@ -1835,6 +1939,7 @@ async fn get_polling_logs(
                     i32,                                 // success_count
                     i32,                                 // failed_count
                     i32,                                 // timeout_count
+                    i32,                                 // no_change_count
                     Option<i32>,                         // duration_ms
                     Option<i32>,                         // avg_processing_ms
                     String,                              // status
@@ -1850,6 +1955,7 @@ async fn get_polling_logs(
                         success_count,
                         failed_count,
                         timeout_count,
+                        no_change_count,
                         duration_ms,
                         avg_processing_ms,
                         status
@@ -1866,12 +1972,12 @@ async fn get_polling_logs(
                 sqlx::query_as::<_, (
                     String, String, i32,
                     chrono::DateTime<chrono::Utc>, Option<chrono::DateTime<chrono::Utc>>,
-                    i64, i32, i32, i32, i32, Option<i32>, Option<i32>, String,
+                    i64, i32, i32, i32, i32, i32, Option<i32>, Option<i32>, String,
                 )>(
                     r#"SELECT
                         polling_id, subnet, subnet_octet3, started_at, completed_at,
                         cycle_number, camera_count, success_count, failed_count, timeout_count,
-                        duration_ms, avg_processing_ms, status
+                        no_change_count, duration_ms, avg_processing_ms, status
                     FROM polling_cycles
                     WHERE status = ?
                     ORDER BY started_at DESC
@@ -1885,12 +1991,12 @@ async fn get_polling_logs(
                 sqlx::query_as::<_, (
                     String, String, i32,
                     chrono::DateTime<chrono::Utc>, Option<chrono::DateTime<chrono::Utc>>,
-                    i64, i32, i32, i32, i32, Option<i32>, Option<i32>, String,
+                    i64, i32, i32, i32, i32, i32, Option<i32>, Option<i32>, String,
                 )>(
                     r#"SELECT
                         polling_id, subnet, subnet_octet3, started_at, completed_at,
                         cycle_number, camera_count, success_count, failed_count, timeout_count,
-                        duration_ms, avg_processing_ms, status
+                        no_change_count, duration_ms, avg_processing_ms, status
                     FROM polling_cycles
                     ORDER BY started_at DESC
                     LIMIT ?"#
@@ -1908,7 +2014,7 @@ async fn get_polling_logs(
                             polling_id, subnet, subnet_octet3,
                             started_at, completed_at,
                             cycle_number, camera_count, success_count, failed_count, timeout_count,
-                            duration_ms, avg_processing_ms, status
+                            no_change_count, duration_ms, avg_processing_ms, status
                         )| {
                             PollingLogEntry {
                                 polling_id,
@@ -1921,6 +2027,7 @@ async fn get_polling_logs(
                                 success_count,
                                 failed_count,
                                 timeout_count,
+                                no_change_count,
                                 duration_ms,
                                 avg_processing_ms,
                                 status,
@@ -2196,6 +2303,150 @@ async fn update_global_timeouts(
     }
 }
 
+// ============================================================================
+// Adaptive Polling API
+// ============================================================================
+
+/// GET /api/settings/polling/adaptive - Get adaptive polling policy
+async fn get_adaptive_polling(State(state): State<AppState>) -> impl IntoResponse {
+    match state.config_store.service().get_polling_policy().await {
+        Ok(policy) => Json(ApiResponse::success(policy.adaptive)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// PUT /api/settings/polling/adaptive - Update adaptive polling policy
+///
+/// settings.polling の他フィールド（timeout_main_sec等）を保持するため JSON_SET で部分更新
+async fn update_adaptive_polling(
+    State(state): State<AppState>,
+    Json(payload): Json<AdaptivePollingPolicy>,
+) -> impl IntoResponse {
+    if payload.activity_window_sec < 0 {
+        return crate::Error::Validation("activity_window_sec must be >= 0".to_string())
+            .into_response();
+    }
+    if payload.no_change_backoff_after < 1 {
+        return crate::Error::Validation("no_change_backoff_after must be >= 1".to_string())
+            .into_response();
+    }
+    if payload.max_interval_sec < 10 || payload.max_interval_sec > 86400 {
+        return crate::Error::Validation("max_interval_sec must be between 10 and 86400".to_string())
+            .into_response();
+    }
+    if payload.subnet_budget_kb_per_cycle < 0 {
+        return crate::Error::Validation("subnet_budget_kb_per_cycle must be >= 0".to_string())
+            .into_response();
+    }
+
+    let adaptive_json = match serde_json::to_string(&payload) {
+        Ok(json) => json,
+        Err(e) => return crate::Error::from(e).into_response(),
+    };
+
+    let result = sqlx::query(
+        "UPDATE settings
+         SET setting_json = JSON_SET(setting_json, '$.adaptive', CAST(? AS JSON))
+         WHERE setting_key = 'polling'"
+    )
+    .bind(adaptive_json)
+    .execute(&state.pool)
+    .await;
+
+    match result {
+        Ok(_) => {
+            tracing::info!(
+                enabled = payload.enabled,
+                max_interval_sec = payload.max_interval_sec,
+                subnet_budget_kb_per_cycle = payload.subnet_budget_kb_per_cycle,
+                "Adaptive polling policy updated"
+            );
+            // Subnet loops read the policy from ConfigStore cache each cycle
+            if let Err(e) = state.config_store.refresh_cache().await {
+                tracing::warn!(error = %e, "Failed to refresh ConfigStore cache");
+            }
+            Json(ApiResponse::success(payload)).into_response()
+        }
+        Err(e) => crate::Error::from(e).into_response(),
+    }
+}
+
+/// GET /api/settings/polling/schedule - Per-camera adaptive schedule state
+async fn get_polling_schedule(State(state): State<AppState>) -> impl IntoResponse {
+    let schedule = state.polling.adaptive_scheduler().snapshot().await;
+    Json(ApiResponse::success(schedule))
+}
+
+// ============================================================================
+// Motion Pre-filter API
+// ============================================================================
+
+/// GET /api/settings/polling/prefilter - Get motion pre-filter policy
+async fn get_motion_prefilter(State(state): State<AppState>) -> impl IntoResponse {
+    match state.config_store.service().get_polling_policy().await {
+        Ok(policy) => Json(ApiResponse::success(policy.prefilter)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// PUT /api/settings/polling/prefilter - Update motion pre-filter policy
+///
+/// カメラ個別の motion_sensitivity / motion_noise_floor が未設定の場合にこの既定値を使用
+async fn update_motion_prefilter(
+    State(state): State<AppState>,
+    Json(payload): Json<MotionPrefilterPolicy>,
+) -> impl IntoResponse {
+    if !(0.0..=1.0).contains(&payload.default_sensitivity) {
+        return crate::Error::Validation("default_sensitivity must be between 0.0 and 1.0".to_string())
+            .into_response();
+    }
+    if !(0..=255).contains(&payload.default_noise_floor) {
+        return crate::Error::Validation("default_noise_floor must be between 0 and 255".to_string())
+            .into_response();
+    }
+    if payload.force_analysis_every < 1 {
+        return crate::Error::Validation("force_analysis_every must be >= 1".to_string())
+            .into_response();
+    }
+
+    let prefilter_json = match serde_json::to_string(&payload) {
+        Ok(json) => json,
+        Err(e) => return crate::Error::from(e).into_response(),
+    };
+
+    let result = sqlx::query(
+        "UPDATE settings
+         SET setting_json = JSON_SET(setting_json, '$.prefilter', CAST(? AS JSON))
+         WHERE setting_key = 'polling'"
+    )
+    .bind(prefilter_json)
+    .execute(&state.pool)
+    .await;
+
+    match result {
+        Ok(_) => {
+            tracing::info!(
+                enabled = payload.enabled,
+                default_sensitivity = payload.default_sensitivity,
+                default_noise_floor = payload.default_noise_floor,
+                force_analysis_every = payload.force_analysis_every,
+                "Motion pre-filter policy updated"
+            );
+            if let Err(e) = state.config_store.refresh_cache().await {
+                tracing::warn!(error = %e, "Failed to refresh ConfigStore cache");
+            }
+            Json(ApiResponse::success(payload)).into_response()
+        }
+        Err(e) => crate::Error::from(e).into_response(),
+    }
+}
+
+/// GET /api/settings/polling/prefilter/stats - Per-camera skip counters
+async fn get_motion_prefilter_stats(State(state): State<AppState>) -> impl IntoResponse {
+    let stats = state.polling.motion_prefilter().stats().await;
+    Json(ApiResponse::success(stats))
+}
+
 // ============================================================================
 // Storage Management API (AIEventlog.md T1-5)
 // ============================================================================
@@ -2536,6 +2787,7 @@ struct RecoveryDetail {
 ///
 /// DBのunknown画像パスとファイルシステムの存在を照合し、
 /// 欠損ファイルを検出する診断機能。
+/// `integrity` には全検出画像の照合結果（StorageManager: 欠損・孤立・0バイト）を含める。
 async fn diagnose_camera_images(
     State(state): State<AppState>,
     Path(camera_id): Path<String>,
@@ -2553,7 +2805,7 @@ async fn diagnose_camera_images(
         "#
     )
     .bind(&camera_id)
-    .fetch_all(&*state.detection_log.pool())
+    .fetch_all(&state.pool)
     .await {
         Ok(paths) => paths,
         Err(e) => {
@@ -2596,6 +2848,14 @@ async fn diagnose_camera_images(
         "Image diagnostics completed"
     );
 
+    let integrity = match state.storage_manager.check_integrity(Some(&camera_id)).await {
+        Ok(report) => Some(report),
+        Err(e) => {
+            tracing::warn!(camera_id = %camera_id, error = %e, "Storage integrity check failed");
+            None
+        }
+    };
+
     let result = DiagnosticsResult {
         camera_id,
         total_in_db: db_paths.len(),
@@ -2608,7 +2868,8 @@ async fn diagnose_camera_images(
 
     Json(json!({
         "ok": true,
-        "diagnostics": result
+        "diagnostics": result,
+        "integrity": integrity
     })).into_response()
 }
 
@@ -2646,7 +2907,7 @@ async fn recover_camera_images(
             for id in ids {
                 query_builder = query_builder.bind(*id);
             }
-            match query_builder.fetch_all(&*state.detection_log.pool()).await {
+            match query_builder.fetch_all(&state.pool).await {
                 Ok(logs) => logs,
                 Err(e) => {
                     return Json(json!({
@@ -2668,7 +2929,7 @@ async fn recover_camera_images(
                 "#
             )
             .bind(&camera_id)
-            .fetch_all(&*state.detection_log.pool())
+            .fetch_all(&state.pool)
             .await {
                 Ok(logs) => logs,
                 Err(e) => {
@@ -2699,7 +2960,7 @@ async fn recover_camera_images(
                 // 欠損レコードをDB削除
                 match sqlx::query("UPDATE detection_logs SET image_path_local = '' WHERE log_id = ?")
                     .bind(log_id)
-                    .execute(&*state.detection_log.pool())
+                    .execute(&state.pool)
                     .await
                 {
                     Ok(_) => {
@@ -2779,7 +3040,7 @@ struct CameraInferenceStats {
 async fn get_inference_stats(
     State(state): State<AppState>,
 ) -> impl IntoResponse {
-    let pool = state.detection_log.pool();
+    let pool = &state.pool;
 
     // Total inferences
     let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM detection_logs")
@@ -3005,6 +3266,58 @@ async fn get_anomaly_cameras(
     }
 }
 
+/// GET /api/stats/availability クエリ
+#[derive(Debug, serde::Deserialize)]
+struct AvailabilityQuery {
+    /// 範囲開始（RFC3339）。未指定時は to - period
+    from: Option<chrono::DateTime<chrono::Utc>>,
+    /// 範囲終了（RFC3339）。未指定時は現在時刻
+    to: Option<chrono::DateTime<chrono::Utc>>,
+    /// from未指定時の期間: 24h, 7d, 30d (default: 7d)
+    period: Option<String>,
+    camera_id: Option<String>,
+    /// 区間リストを含める
+    #[serde(default)]
+    include_timeline: bool,
+}
+
+/// GET /api/stats/availability - カメラ稼働率・MTTR・MTBF
+async fn get_camera_availability(
+    State(state): State<AppState>,
+    Query(query): Query<AvailabilityQuery>,
+) -> impl IntoResponse {
+    let to = query.to.unwrap_or_else(chrono::Utc::now);
+    let from = query.from.unwrap_or_else(|| {
+        to - match query.period.as_deref() {
+            Some("24h") => chrono::Duration::hours(24),
+            Some("30d") => chrono::Duration::days(30),
+            _ => chrono::Duration::days(7),
+        }
+    });
+
+    match state
+        .camera_availability
+        .report(query.camera_id.as_deref(), from, to, query.include_timeline)
+        .await
+    {
+        Ok(report) => Json(json!({
+            "ok": true,
+            "data": report
+        })).into_response(),
+        Err(crate::Error::Validation(msg)) => (StatusCode::BAD_REQUEST, Json(json!({
+            "ok": false,
+            "error": msg
+        }))).into_response(),
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to get camera availability");
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({
+                "ok": false,
+                "error": e.to_string()
+            }))).into_response()
+        }
+    }
+}
+
 // ============================================================================
 // Misdetection Feedback API (T4-6〜T4-10)
 // ============================================================================
@@ -3036,7 +3349,7 @@ async fn create_misdetection_feedback(
     State(state): State<AppState>,
     Json(req): Json<CreateMisdetectionFeedbackRequest>,
 ) -> impl IntoResponse {
-    let pool = state.detection_log.pool();
+    let pool = &state.pool;
 
     // Get confidence from detection log
     let confidence: Option<f64> = sqlx::query_scalar(
@@ -3094,7 +3407,7 @@ async fn get_misdetection_feedback(
     State(state): State<AppState>,
     Path(camera_id): Path<String>,
 ) -> impl IntoResponse {
-    let pool = state.detection_log.pool();
+    let pool = &state.pool;
 
     let rows = sqlx::query(
         r#"SELECT
@@ -3143,7 +3456,7 @@ async fn get_feedback_stats(
     State(state): State<AppState>,
     Path(camera_id): Path<String>,
 ) -> impl IntoResponse {
-    let pool = state.detection_log.pool();
+    let pool = &state.pool;
 
     // Count by label
     let rows = sqlx::query(
@@ -3202,50 +3515,22 @@ async fn get_feedback_stats(
 // ============================================================================
 
 /// GET /api/cameras/:id/threshold - Get camera threshold settings
+///
+/// conf_threshold は cameras.conf_override（未設定ならプリセット値）
 async fn get_camera_threshold(
     State(state): State<AppState>,
     Path(camera_id): Path<String>,
 ) -> impl IntoResponse {
-    let pool = state.detection_log.pool();
-
-    let row = sqlx::query(
-        r#"SELECT
-            camera_id, conf_threshold, min_threshold, max_threshold,
-            auto_adjust_enabled, created_at, updated_at
-        FROM camera_thresholds
-        WHERE camera_id = ?"#
-    )
-        .bind(&camera_id)
-        .fetch_optional(pool)
-        .await;
-
-    match row {
-        Ok(Some(row)) => {
-            Json(json!({
-                "ok": true,
-                "threshold": {
-                    "camera_id": row.get::<String, _>("camera_id"),
-                    "conf_threshold": row.get::<f64, _>("conf_threshold"),
-                    "min_threshold": row.get::<f64, _>("min_threshold"),
-                    "max_threshold": row.get::<f64, _>("max_threshold"),
-                    "auto_adjust_enabled": row.get::<bool, _>("auto_adjust_enabled"),
-                    "updated_at": row.get::<chrono::NaiveDateTime, _>("updated_at").to_string()
-                }
-            })).into_response()
-        }
-        Ok(None) => {
-            // Return default threshold
-            Json(json!({
-                "ok": true,
-                "threshold": {
-                    "camera_id": camera_id,
-                    "conf_threshold": 0.5,
-                    "min_threshold": 0.2,
-                    "max_threshold": 0.8,
-                    "auto_adjust_enabled": false,
-                    "updated_at": null
-                }
-            })).into_response()
+    match state.auto_attunement.get_threshold_settings(&camera_id).await {
+        Ok(threshold) => Json(json!({
+            "ok": true,
+            "threshold": threshold
+        })).into_response(),
+        Err(crate::Error::NotFound(msg)) => {
+            (StatusCode::NOT_FOUND, Json(json!({
+                "ok": false,
+                "error": msg
+            }))).into_response()
         }
         Err(e) => {
             (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({
@@ -3259,7 +3544,7 @@ async fn get_camera_threshold(
 /// T4-13: PUT /api/cameras/:id/threshold - Update camera threshold
 #[derive(Debug, serde::Deserialize)]
 struct UpdateThresholdRequest {
-    conf_threshold: f64,
+    conf_threshold: f32,
     auto_adjust_enabled: Option<bool>,
 }
 
@@ -3268,70 +3553,34 @@ async fn update_camera_threshold(
     Path(camera_id): Path<String>,
     Json(req): Json<UpdateThresholdRequest>,
 ) -> impl IntoResponse {
-    let pool = state.detection_log.pool();
-
-    // Validate range (0.2 - 0.8)
-    if req.conf_threshold < 0.2 || req.conf_threshold > 0.8 {
-        return (StatusCode::BAD_REQUEST, Json(json!({
-            "ok": false,
-            "error": "conf_threshold must be between 0.2 and 0.8"
-        }))).into_response();
-    }
-
-    // Get current threshold for history
-    let current: Option<f64> = sqlx::query_scalar(
-        "SELECT conf_threshold FROM camera_thresholds WHERE camera_id = ?"
-    )
-        .bind(&camera_id)
-        .fetch_optional(pool)
-        .await
-        .ok()
-        .flatten();
-
-    let auto_adjust = req.auto_adjust_enabled.unwrap_or(false);
-
-    // Upsert threshold
-    let result = sqlx::query(
-        r#"INSERT INTO camera_thresholds (camera_id, conf_threshold, auto_adjust_enabled)
-           VALUES (?, ?, ?)
-           ON DUPLICATE KEY UPDATE
-             conf_threshold = VALUES(conf_threshold),
-             auto_adjust_enabled = VALUES(auto_adjust_enabled),
-             updated_at = NOW()"#
-    )
-        .bind(&camera_id)
-        .bind(req.conf_threshold)
-        .bind(auto_adjust)
-        .execute(pool)
+    let result = state
+        .auto_attunement
+        .update_threshold_settings(&camera_id, req.conf_threshold, req.auto_adjust_enabled)
         .await;
 
     match result {
-        Ok(_) => {
-            // Record history
-            let _ = sqlx::query(
-                r#"INSERT INTO threshold_change_history
-                   (camera_id, old_threshold, new_threshold, change_reason)
-                   VALUES (?, ?, ?, ?)"#
-            )
-                .bind(&camera_id)
-                .bind(current)
-                .bind(req.conf_threshold)
-                .bind("manual")
-                .execute(pool)
-                .await;
-
-            tracing::info!(
-                camera_id = %camera_id,
-                old = ?current,
-                new = req.conf_threshold,
-                "Camera threshold updated"
-            );
-
+        Ok(()) => {
+            // ポーリングが参照する cameras キャッシュを更新
+            if let Err(e) = state.config_store.refresh_cache().await {
+                tracing::warn!(error = %e, "Failed to refresh config cache after threshold update");
+            }
             Json(json!({
                 "ok": true,
                 "message": "Threshold updated successfully"
             })).into_response()
         }
+        Err(crate::Error::Validation(msg)) => {
+            (StatusCode::BAD_REQUEST, Json(json!({
+                "ok": false,
+                "error": msg
+            }))).into_response()
+        }
+        Err(crate::Error::NotFound(msg)) => {
+            (StatusCode::NOT_FOUND, Json(json!({
+                "ok": false,
+                "error": msg
+            }))).into_response()
+        }
         Err(e) => {
             tracing::error!(error = %e, "Failed to update threshold");
             (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({
@@ -4352,6 +4601,40 @@ async fn list_streams(State(state): State<AppState>) -> impl IntoResponse {
     }
 }
 
+/// GET /api/go2rtc/reconcile - dry run (changes that would be applied) + last report
+async fn get_reconcile_plan(State(state): State<AppState>) -> impl IntoResponse {
+    match state.stream_reconciler.plan().await {
+        Ok(plan) => Json(ApiResponse::success(json!({
+            "plan": plan,
+            "last_report": state.stream_reconciler.last_report().await,
+        })))
+        .into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// POST /api/go2rtc/reconcile - sync go2rtc with registered cameras now
+async fn run_reconcile(State(state): State<AppState>) -> impl IntoResponse {
+    match state.stream_reconciler.reconcile().await {
+        Ok(report) => Json(ApiResponse::success(report)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+async fn get_reconciler_config(State(state): State<AppState>) -> impl IntoResponse {
+    Json(ApiResponse::success(state.stream_reconciler.config().await))
+}
+
+async fn update_reconciler_config(
+    State(state): State<AppState>,
+    Json(config): Json<crate::stream_gateway::reconciler::ReconcilerConfig>,
+) -> impl IntoResponse {
+    match state.stream_reconciler.set_config(config).await {
+        Ok(saved) => Json(ApiResponse::success(saved)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
 async fn get_stream_urls(
     State(state): State<AppState>,
     Path(camera_id): Path<String>,
@@ -5109,23 +5392,36 @@ async fn handle_websocket(socket: WebSocket, state: AppState) {
 
     // Register with RealtimeHub
     let user_id = uuid::Uuid::new_v4().to_string();
-    let (conn_id, mut rx) = state.realtime.register(user_id).await;
+    let (conn_id, outbox) = state.realtime.register(user_id).await;
 
     tracing::info!(connection_id = %conn_id, "WebSocket client connected");
 
-    // Spawn task to forward messages from hub to WebSocket
+    // Spawn task to forward messages from the connection outbox to WebSocket
     let send_task = tokio::spawn(async move {
-        while let Some(msg) = rx.recv().await {
+        while let Some(msg) = outbox.recv().await {
             if sender.send(Message::Text(msg)).await.is_err() {
-                break;
+                return;
             }
         }
+        // Outbox closed (unregister / server shutdown)
+        let _ = sender.send(Message::Close(None)).await;
     });
 
-    // Handle incoming messages (for future use - ping/pong, commands, etc.)
+    // Handle incoming messages (subscribe / unsubscribe / reset / resume commands)
+    let realtime = state.realtime.clone();
     let recv_task = tokio::spawn(async move {
         while let Some(result) = receiver.next().await {
             match result {
+                Ok(Message::Text(text)) => {
+                    match serde_json::from_str::<crate::realtime_hub::ClientCommand>(&text) {
+                        Ok(command) => realtime.handle_command(&conn_id, command).await,
+                        Err(e) => {
+                            realtime
+                                .send_error(&conn_id, &format!("Invalid command: {}", e))
+                                .await
+                        }
+                    }
+                }
                 Ok(Message::Ping(data)) => {
                     // Pong is handled automatically by axum
                     tracing::trace!("Received ping: {:?}", data);
@@ -5544,11 +5840,16 @@ async fn apply_attunement(
     };
 
     match state.auto_attunement.apply_threshold(&camera_id, threshold).await {
-        Ok(()) => Json(json!({
-            "ok": true,
-            "message": format!("Threshold updated to {:.3}", threshold),
-            "new_threshold": threshold
-        })).into_response(),
+        Ok(()) => {
+            if let Err(e) = state.config_store.refresh_cache().await {
+                tracing::warn!(error = %e, "Failed to refresh config cache after attunement");
+            }
+            Json(json!({
+                "ok": true,
+                "message": format!("Threshold updated to {:.3}", threshold),
+                "new_threshold": threshold
+            })).into_response()
+        }
         Err(e) => {
             tracing::error!(camera_id = %camera_id, error = %e, "Failed to apply threshold");
             (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({
@@ -5559,17 +5860,35 @@ async fn apply_attunement(
     }
 }
 
-/// POST /api/attunement/auto-adjust - Run auto adjustment for all enabled cameras
+/// POST /api/attunement/auto-adjust - Run one closed-loop attunement cycle now
+///
+/// 試行の開始・評価（採用/ロールバック）を即時実行する（ポリシーの enabled に関係なく実行）
 async fn run_auto_adjustment(
     State(state): State<AppState>,
 ) -> impl IntoResponse {
-    match state.auto_attunement.run_auto_adjustment().await {
-        Ok(results) => {
-            let adjusted_count = results.iter().filter(|r| r.adjustment.abs() > 0.001).count();
+    let policy = match state.config_store.service().get_attunement_policy().await {
+        Ok(policy) => policy,
+        Err(e) => return e.into_response(),
+    };
+
+    match state.auto_attunement.run_auto_adjustment(&policy).await {
+        Ok(events) => {
+            if !events.is_empty() {
+                if let Err(e) = state.config_store.refresh_cache().await {
+                    tracing::warn!(error = %e, "Failed to refresh config cache after attunement");
+                }
+            }
+            use crate::auto_attunement::TrialStatus;
+            let count = |status: TrialStatus| events.iter().filter(|e| e.status == status).count();
             Json(json!({
                 "ok": true,
-                "message": format!("Auto adjustment completed. {} cameras adjusted.", adjusted_count),
-                "results": results
+                "message": format!(
+                    "Auto adjustment completed. {} started, {} promoted, {} rolled back.",
+                    count(TrialStatus::Running),
+                    count(TrialStatus::Promoted),
+                    count(TrialStatus::RolledBack)
+                ),
+                "results": events
             })).into_response()
         }
         Err(e) => {
@@ -5582,6 +5901,76 @@ async fn run_auto_adjustment(
     }
 }
 
+/// GET /api/attunement/policy
+async fn get_attunement_policy(State(state): State<AppState>) -> impl IntoResponse {
+    match state.config_store.service().get_attunement_policy().await {
+        Ok(policy) => Json(ApiResponse::success(policy)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// PUT /api/attunement/policy
+async fn update_attunement_policy(
+    State(state): State<AppState>,
+    Json(policy): Json<crate::config_store::AttunementPolicy>,
+) -> impl IntoResponse {
+    if policy.evaluation_interval_min == 0
+        || policy.baseline_window_hours == 0
+        || policy.trial_window_hours == 0
+    {
+        return crate::Error::Validation(
+            "evaluation_interval_min / baseline_window_hours / trial_window_hours must be > 0".to_string(),
+        )
+        .into_response();
+    }
+    let rates = [
+        policy.target_false_positive_rate,
+        policy.target_unknown_rate,
+        policy.rate_tolerance,
+        policy.max_volume_drop,
+    ];
+    if rates.iter().any(|r| !(0.0..=1.0).contains(r)) {
+        return crate::Error::Validation("rates must be within 0.0-1.0".to_string()).into_response();
+    }
+    let steps = [policy.conf_step, policy.nms_step, policy.par_step];
+    if steps.iter().any(|s| !(0.005..=0.2).contains(s)) {
+        return crate::Error::Validation("steps must be within 0.005-0.2".to_string()).into_response();
+    }
+
+    match state.config_store.service().set_attunement_policy(policy.clone()).await {
+        Ok(()) => {
+            if let Err(e) = state.config_store.refresh_cache().await {
+                tracing::warn!(error = %e, "Failed to refresh config cache");
+            }
+            Json(ApiResponse::success(policy)).into_response()
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Query params for attunement trials
+#[derive(Debug, Deserialize)]
+struct AttunementTrialsQuery {
+    camera_id: Option<String>,
+    limit: Option<i64>,
+}
+
+/// GET /api/attunement/trials - Trial history (newest first)
+async fn list_attunement_trials(
+    State(state): State<AppState>,
+    Query(query): Query<AttunementTrialsQuery>,
+) -> impl IntoResponse {
+    let limit = query.limit.unwrap_or(50).clamp(1, 500);
+    match state
+        .auto_attunement
+        .list_trials(query.camera_id.as_deref(), limit)
+        .await
+    {
+        Ok(trials) => Json(ApiResponse::success(trials)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
 // =============================================================================
 // Preset Graphical UI (Issue #107)
 // =============================================================================
@@ -5590,13 +5979,31 @@ async fn run_auto_adjustment(
 async fn get_preset_balances(
     State(state): State<AppState>,
 ) -> impl IntoResponse {
-    let balances = state.overdetection_analyzer.get_all_preset_balances();
+    let balances = state.overdetection_analyzer.get_all_preset_balances(&state.preset_loader);
     Json(json!({
         "ok": true,
         "presets": balances
     }))
 }
 
+/// GET /api/presets - List built-in and custom presets
+async fn list_presets(State(state): State<AppState>) -> impl IntoResponse {
+    let mut presets = state.preset_loader.list_all();
+    // 組み込み→カスタムの順、各ID昇順
+    presets.sort_by_key(|p| (!state.preset_loader.is_builtin(&p.id), p.id.clone()));
+
+    let presets: Vec<serde_json::Value> = presets
+        .into_iter()
+        .map(|preset| {
+            let builtin = state.preset_loader.is_builtin(&preset.id);
+            let mut value = serde_json::to_value(&preset).unwrap_or_default();
+            value["builtin"] = json!(builtin);
+            value
+        })
+        .collect();
+    Json(ApiResponse::success(presets))
+}
+
 /// Query params for overdetection/tag endpoints
 #[derive(Debug, Deserialize)]
 struct OverdetectionQuery {
@@ -0,0 +1,96 @@
+//! Storage Manager API Routes
+//!
+//! ## エンドポイント
+//! - GET /api/storage/usage - ストア別・カメラ別使用量とディスク逼迫度（?refresh=true で再集計）
+//! - POST /api/storage/enforce - 逼迫判定と退避を即時実行
+//! - GET /api/storage/config - ストレージ管理設定
+//! - PUT /api/storage/config - ストレージ管理設定更新
+//! - GET /api/storage/integrity - 検出画像と detection_logs の照合（?camera_id= で絞り込み）
+
+use axum::{
+    extract::{Query, State},
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
+use serde::Deserialize;
+
+use crate::models::ApiResponse;
+use crate::state::AppState;
+use crate::storage_manager::StorageManagerConfig;
+
+/// Storage Manager API ルーター
+pub fn storage_routes() -> Router<AppState> {
+    Router::new()
+        .route("/usage", get(get_usage))
+        .route("/enforce", post(enforce))
+        .route("/config", get(get_config).put(update_config))
+        .route("/integrity", get(check_integrity))
+}
+
+#[derive(Debug, Deserialize)]
+struct UsageQuery {
+    #[serde(default)]
+    refresh: bool,
+}
+
+/// GET /api/storage/usage
+async fn get_usage(
+    State(state): State<AppState>,
+    Query(query): Query<UsageQuery>,
+) -> impl IntoResponse {
+    let manager = &state.storage_manager;
+    if !query.refresh {
+        if let Some(report) = manager.last_report().await {
+            return Json(ApiResponse::success(report)).into_response();
+        }
+    }
+    match manager.scan(&manager.config().await).await {
+        Ok(report) => Json(ApiResponse::success(report)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// POST /api/storage/enforce
+async fn enforce(State(state): State<AppState>) -> impl IntoResponse {
+    match state.storage_manager.run_cycle().await {
+        Ok(report) => Json(ApiResponse::success(report)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// GET /api/storage/config
+async fn get_config(State(state): State<AppState>) -> impl IntoResponse {
+    Json(ApiResponse::success(state.storage_manager.config().await))
+}
+
+/// PUT /api/storage/config
+async fn update_config(
+    State(state): State<AppState>,
+    Json(config): Json<StorageManagerConfig>,
+) -> impl IntoResponse {
+    match state.storage_manager.set_config(config).await {
+        Ok(saved) => Json(ApiResponse::success(saved)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct IntegrityQuery {
+    camera_id: Option<String>,
+}
+
+/// GET /api/storage/integrity
+async fn check_integrity(
+    State(state): State<AppState>,
+    Query(query): Query<IntegrityQuery>,
+) -> impl IntoResponse {
+    match state
+        .storage_manager
+        .check_integrity(query.camera_id.as_deref().filter(|s| !s.is_empty()))
+        .await
+    {
+        Ok(report) => Json(ApiResponse::success(report)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
@@ -7,6 +7,7 @@
 //! - POST /api/summary/generate - 手動Summary生成
 //! - POST /api/summary/force-hourly - 強制Hourly Summary生成（テスト用）
 //! - POST /api/summary/force-grand - 強制GrandSummary生成（テスト用）
+//! - POST /api/summary/rollup - 週次・月次ロールアップ生成
 //! - GET /api/summary/latest - 最新Summary取得
 //! - GET /api/summary/:id - Summary取得
 //! - GET /api/summary/range - 期間指定Summary一覧
@@ -14,6 +15,7 @@
 //! - GET /api/grand-summary/latest - 最新GrandSummary
 //! - GET /api/reports/schedule - スケジュール設定取得
 //! - PUT /api/reports/schedule - スケジュール設定更新
+//! - /api/summary/templates, /api/summary/locale - summary_template_routes 参照
 
 use axum::{
     extract::{Path, Query, State},
@@ -27,8 +29,9 @@ use serde::{Deserialize, Serialize};
 
 use crate::realtime_hub::{HubMessage, SummaryReportMessage};
 use crate::state::AppState;
+use crate::summary_service::rollup::calculate_next_rollup_time;
 use crate::summary_service::{
-    ReportSchedule, ReportType, SummaryInsert, SummaryResult, SummaryType,
+    ReportFormat, ReportSchedule, ReportType, SummaryInsert, SummaryResult, SummaryType,
 };
 
 /// Summary API ルーター
@@ -38,6 +41,7 @@ pub fn summary_routes() -> Router<AppState> {
         .route("/summary/generate", post(generate_summary))
         .route("/summary/force-hourly", post(force_generate_hourly))
         .route("/summary/force-grand", post(force_generate_grand))
+        .route("/summary/rollup", post(generate_rollup))
         .route("/summary/latest", get(get_latest_summary))
         .route("/summary/:id", get(get_summary_by_id))
         .route("/summary/range", get(get_summaries_range))
@@ -47,6 +51,8 @@ pub fn summary_routes() -> Router<AppState> {
         // Schedule endpoints
         .route("/reports/schedule", get(get_schedules))
         .route("/reports/schedule", put(update_schedule))
+        // Template endpoints
+        .merge(super::summary_template_routes::summary_template_routes())
 }
 
 // ========================================
@@ -110,6 +116,19 @@ impl From<SummaryResult> for SummaryResponse {
     }
 }
 
+/// ロールアップ生成リクエスト
+///
+/// period未指定時は直前に完了した週・月を集計
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GenerateRollupRequest {
+    /// "weekly" | "monthly"
+    pub rollup_type: String,
+    pub fid: Option<String>,
+    pub period_start: Option<DateTime<Utc>>,
+    pub period_end: Option<DateTime<Utc>>,
+}
+
 /// 最新Summary取得クエリ
 #[derive(Debug, Deserialize)]
 pub struct LatestSummaryQuery {
@@ -146,6 +165,7 @@ pub struct ScheduleResponse {
     pub report_type: String,
     pub interval_minutes: Option<i32>,
     pub scheduled_times: Option<Vec<String>>,
+    pub report_format: Option<String>,
     pub enabled: bool,
     pub last_run_at: Option<DateTime<Utc>>,
     pub next_run_at: Option<DateTime<Utc>>,
@@ -155,12 +175,10 @@ impl From<ReportSchedule> for ScheduleResponse {
     fn from(schedule: ReportSchedule) -> Self {
         Self {
             schedule_id: schedule.schedule_id,
-            report_type: match schedule.report_type {
-                ReportType::Summary => "summary".to_string(),
-                ReportType::GrandSummary => "grand_summary".to_string(),
-            },
+            report_type: schedule.report_type.to_string(),
             interval_minutes: schedule.interval_minutes,
             scheduled_times: schedule.scheduled_times,
+            report_format: schedule.report_format,
             enabled: schedule.enabled,
             last_run_at: schedule.last_run_at,
             next_run_at: schedule.next_run_at,
@@ -182,6 +200,8 @@ pub struct UpdateScheduleRequest {
     pub report_type: String,
     pub interval_minutes: Option<i32>,
     pub scheduled_times: Option<Vec<String>>,
+    /// "html" / "pdf"、空文字でレポート生成を無効化
+    pub report_format: Option<String>,
     pub enabled: Option<bool>,
 }
 
@@ -238,6 +258,98 @@ async fn generate_summary(
     }
 }
 
+/// POST /api/summary/rollup - 週次・月次ロールアップ生成
+async fn generate_rollup(
+    State(state): State<AppState>,
+    Json(req): Json<GenerateRollupRequest>,
+) -> impl IntoResponse {
+    let summary_type = match req.rollup_type.as_str() {
+        "weekly" => SummaryType::Weekly,
+        "monthly" => SummaryType::Monthly,
+        _ => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({
+                    "error_code": "VALIDATION_ERROR",
+                    "message": "Invalid rollupType. Use 'weekly' or 'monthly'"
+                })),
+            )
+                .into_response()
+        }
+    };
+
+    let tid = match get_config_tid(&state).await {
+        Some(tid) => tid,
+        None => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({
+                    "error_code": "CONFIG_ERROR",
+                    "message": "TID not configured"
+                })),
+            )
+                .into_response()
+        }
+    };
+    let fid = match req.fid.clone() {
+        Some(fid) => fid,
+        None => match get_config_fid(&state).await {
+            Some(fid) => fid,
+            None => {
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(serde_json::json!({
+                        "error_code": "CONFIG_ERROR",
+                        "message": "FID not configured"
+                    })),
+                )
+                    .into_response()
+            }
+        },
+    };
+
+    let result = match (req.period_start, req.period_end) {
+        (Some(start), Some(end)) => {
+            state
+                .rollup_generator
+                .generate(&tid, &fid, summary_type, start, end)
+                .await
+        }
+        (None, None) => {
+            state
+                .rollup_generator
+                .generate_latest(&tid, &fid, summary_type, Utc::now())
+                .await
+        }
+        _ => Err(crate::Error::Validation(
+            "periodStart and periodEnd must be specified together".to_string(),
+        )),
+    };
+
+    match result {
+        Ok(result) => (StatusCode::CREATED, Json(SummaryResponse::from(result))).into_response(),
+        Err(crate::Error::Validation(message)) => (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({
+                "error_code": "VALIDATION_ERROR",
+                "message": message
+            })),
+        )
+            .into_response(),
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to generate rollup");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({
+                    "error_code": "SUMMARY_ERROR",
+                    "message": e.to_string()
+                })),
+            )
+                .into_response()
+        }
+    }
+}
+
 /// GET /api/summary/latest - 最新Summary取得
 async fn get_latest_summary(
     State(state): State<AppState>,
@@ -260,6 +372,8 @@ async fn get_latest_summary(
     let summary_type = match query.summary_type.as_deref() {
         Some("daily") => SummaryType::Daily,
         Some("emergency") => SummaryType::Emergency,
+        Some("weekly") => SummaryType::Weekly,
+        Some("monthly") => SummaryType::Monthly,
         _ => SummaryType::Hourly,
     };
 
@@ -343,6 +457,8 @@ async fn get_summaries_range(
         Some("daily") => Some(SummaryType::Daily),
         Some("emergency") => Some(SummaryType::Emergency),
         Some("hourly") => Some(SummaryType::Hourly),
+        Some("weekly") => Some(SummaryType::Weekly),
+        Some("monthly") => Some(SummaryType::Monthly),
         _ => None,
     };
 
@@ -577,21 +693,56 @@ async fn update_schedule(
         }
     };
 
-    let report_type = match req.report_type.as_str() {
-        "summary" => ReportType::Summary,
-        "grand_summary" => ReportType::GrandSummary,
-        _ => {
+    let report_type = match ReportType::parse(&req.report_type) {
+        Some(report_type) => report_type,
+        None => {
             return (
                 StatusCode::BAD_REQUEST,
                 Json(serde_json::json!({
                     "error_code": "VALIDATION_ERROR",
-                    "message": "Invalid report_type. Use 'summary' or 'grand_summary'"
+                    "message": "Invalid report_type. Use 'summary', 'grand_summary', 'weekly_rollup' or 'monthly_rollup'"
                 })),
             )
                 .into_response()
         }
     };
 
+    // ロールアップは実行時刻（JST HH:MM）を1件のみ指定
+    if let (Some(summary_type), Some(times)) =
+        (report_type.rollup_summary_type(), req.scheduled_times.as_ref())
+    {
+        let valid = times.len() == 1
+            && calculate_next_rollup_time(summary_type, &times[0], Utc::now()).is_ok();
+        if !valid {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({
+                    "error_code": "VALIDATION_ERROR",
+                    "message": "Rollup schedules take exactly one run time (HH:MM, JST)"
+                })),
+            )
+                .into_response();
+        }
+    }
+
+    let report_format = match req.report_format.as_deref() {
+        None => None,
+        Some("") => Some(None),
+        Some(f) => match ReportFormat::parse(f) {
+            Some(format) => Some(Some(format.as_str().to_string())),
+            None => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(serde_json::json!({
+                        "error_code": "VALIDATION_ERROR",
+                        "message": "Invalid report_format. Use 'html', 'pdf' or ''"
+                    })),
+                )
+                    .into_response()
+            }
+        },
+    };
+
     // 既存スケジュールを取得または新規作成
     let schedules = match state.schedule_repository.get_schedules(&tid, &fid).await {
         Ok(s) => s,
@@ -616,6 +767,8 @@ async fn update_schedule(
         report_type,
         interval_minutes: req.interval_minutes.or(existing.and_then(|s| s.interval_minutes)),
         scheduled_times: req.scheduled_times.or(existing.and_then(|s| s.scheduled_times.clone())),
+        report_format: report_format
+            .unwrap_or_else(|| existing.and_then(|s| s.report_format.clone())),
         last_run_at: existing.and_then(|s| s.last_run_at),
         next_run_at: existing.and_then(|s| s.next_run_at),
         enabled: req.enabled.unwrap_or(existing.map(|s| s.enabled).unwrap_or(true)),
@@ -645,7 +798,7 @@ async fn update_schedule(
 // ========================================
 
 /// ConfigStoreからTIDを取得
-async fn get_config_tid(state: &AppState) -> Option<String> {
+pub(super) async fn get_config_tid(state: &AppState) -> Option<String> {
     state
         .config_store
         .service()
@@ -657,7 +810,7 @@ async fn get_config_tid(state: &AppState) -> Option<String> {
 }
 
 /// ConfigStoreからFIDを取得
-async fn get_config_fid(state: &AppState) -> Option<String> {
+pub(super) async fn get_config_fid(state: &AppState) -> Option<String> {
     state
         .config_store
         .service()
@@ -832,6 +985,7 @@ async fn force_generate_hourly(
                 camera_count: result.camera_ids.len(),
                 summary_text: final_summary_text,
                 created_at: Utc::now().to_rfc3339(),
+                report_url: None,
             })).await;
 
             let response = ForceGenerateResponse {
@@ -1060,6 +1214,7 @@ async fn force_generate_grand(
                 camera_count: all_camera_ids.len(),
                 summary_text: final_summary_text,
                 created_at: Utc::now().to_rfc3339(),
+                report_url: None,
             })).await;
 
             let response = ForceGenerateResponse {
@@ -0,0 +1,215 @@
+//! Summary Template API Routes
+//!
+//! ## エンドポイント
+//! - GET /api/summary/templates - テンプレート一覧（?fid=）
+//! - POST /api/summary/templates - 作成（既定は無効、プレビュー後に有効化）
+//! - GET /api/summary/templates/defaults - 組み込みデフォルトと使用可能な変数
+//! - POST /api/summary/templates/preview - 過去データでプレビュー（保存なし）
+//! - GET /api/summary/templates/:id - 取得
+//! - PUT /api/summary/templates/:id - 更新（enabled=trueで同一スコープの他テンプレートを無効化）
+//! - DELETE /api/summary/templates/:id - 削除
+//! - GET /api/summary/locale - fid別locale設定取得
+//! - PUT /api/summary/locale - fid別locale設定更新
+
+use axum::{
+    extract::{Path, Query, State},
+    response::IntoResponse,
+    routing::get,
+    routing::post,
+    Json, Router,
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::models::ApiResponse;
+use crate::state::AppState;
+use crate::summary_service::template::{default_template, validate_template, TEMPLATE_VARIABLES};
+use crate::summary_service::{
+    SummaryLocale, SummaryLocaleSettings, TemplateKind, UpsertSummaryTemplateRequest,
+};
+
+/// Summary Template API ルーター
+pub fn summary_template_routes() -> Router<AppState> {
+    Router::new()
+        .route("/summary/templates", get(list_templates).post(create_template))
+        .route("/summary/templates/defaults", get(get_default_templates))
+        .route("/summary/templates/preview", post(preview_template))
+        .route(
+            "/summary/templates/:id",
+            get(get_template).put(update_template).delete(delete_template),
+        )
+        .route("/summary/locale", get(get_locale_settings).put(update_locale_settings))
+}
+
+/// テンプレート一覧クエリ
+#[derive(Debug, Deserialize)]
+struct TemplateListQuery {
+    fid: Option<String>,
+}
+
+/// プレビューリクエスト
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PreviewTemplateRequest {
+    fid: String,
+    kind: TemplateKind,
+    locale: Option<SummaryLocale>,
+    /// 保存済みテンプレートをプレビュー
+    template_id: Option<u64>,
+    /// 未保存のテンプレート本文をプレビュー
+    body: Option<String>,
+    period_start: DateTime<Utc>,
+    period_end: DateTime<Utc>,
+}
+
+fn validate_request(req: &UpsertSummaryTemplateRequest) -> crate::Result<()> {
+    if req.name.is_empty() || req.name.len() > 128 {
+        return Err(crate::Error::Validation("name must be 1-128 characters".to_string()));
+    }
+    if req.fid.as_deref().is_some_and(|fid| fid.is_empty() || fid.len() > 32) {
+        return Err(crate::Error::Validation("fid must be 1-32 characters".to_string()));
+    }
+    validate_template(&req.body)
+}
+
+/// GET /api/summary/templates
+async fn list_templates(
+    State(state): State<AppState>,
+    Query(query): Query<TemplateListQuery>,
+) -> impl IntoResponse {
+    match state
+        .summary_text_builder
+        .templates()
+        .list(query.fid.as_deref())
+        .await
+    {
+        Ok(templates) => Json(ApiResponse::success(templates)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// POST /api/summary/templates
+async fn create_template(
+    State(state): State<AppState>,
+    Json(req): Json<UpsertSummaryTemplateRequest>,
+) -> impl IntoResponse {
+    if let Err(e) = validate_request(&req) {
+        return e.into_response();
+    }
+    match state.summary_text_builder.templates().save(None, &req).await {
+        Ok(template) => Json(ApiResponse::success(template)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// GET /api/summary/templates/defaults
+async fn get_default_templates() -> impl IntoResponse {
+    let defaults: Vec<serde_json::Value> = [SummaryLocale::Ja, SummaryLocale::En]
+        .into_iter()
+        .flat_map(|locale| {
+            [TemplateKind::Summary, TemplateKind::GrandSummary]
+                .into_iter()
+                .map(move |kind| {
+                    json!({
+                        "locale": locale,
+                        "kind": kind,
+                        "body": default_template(locale, kind),
+                    })
+                })
+        })
+        .collect();
+
+    Json(ApiResponse::success(json!({
+        "templates": defaults,
+        "variables": TEMPLATE_VARIABLES,
+    })))
+}
+
+/// POST /api/summary/templates/preview
+async fn preview_template(
+    State(state): State<AppState>,
+    Json(req): Json<PreviewTemplateRequest>,
+) -> impl IntoResponse {
+    if let Some(body) = req.body.as_deref() {
+        if let Err(e) = validate_template(body) {
+            return e.into_response();
+        }
+    }
+    let Some(tid) = super::summary_routes::get_config_tid(&state).await else {
+        return crate::Error::Config("TID not configured".to_string()).into_response();
+    };
+
+    match state
+        .summary_text_builder
+        .preview(
+            &tid,
+            &req.fid,
+            req.kind,
+            req.locale,
+            req.template_id,
+            req.body,
+            req.period_start,
+            req.period_end,
+        )
+        .await
+    {
+        Ok(preview) => Json(ApiResponse::success(preview)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// GET /api/summary/templates/:id
+async fn get_template(State(state): State<AppState>, Path(id): Path<u64>) -> impl IntoResponse {
+    match state.summary_text_builder.templates().get(id).await {
+        Ok(Some(template)) => Json(ApiResponse::success(template)).into_response(),
+        Ok(None) => crate::Error::NotFound(format!("Template {} not found", id)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// PUT /api/summary/templates/:id
+async fn update_template(
+    State(state): State<AppState>,
+    Path(id): Path<u64>,
+    Json(req): Json<UpsertSummaryTemplateRequest>,
+) -> impl IntoResponse {
+    if let Err(e) = validate_request(&req) {
+        return e.into_response();
+    }
+    let templates = state.summary_text_builder.templates();
+    match templates.get(id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return crate::Error::NotFound(format!("Template {} not found", id)).into_response(),
+        Err(e) => return e.into_response(),
+    }
+    match templates.save(Some(id), &req).await {
+        Ok(template) => Json(ApiResponse::success(template)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// DELETE /api/summary/templates/:id
+async fn delete_template(State(state): State<AppState>, Path(id): Path<u64>) -> impl IntoResponse {
+    match state.summary_text_builder.templates().delete(id).await {
+        Ok(true) => Json(ApiResponse::success(json!({ "deleted": id }))).into_response(),
+        Ok(false) => crate::Error::NotFound(format!("Template {} not found", id)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+/// GET /api/summary/locale
+async fn get_locale_settings(State(state): State<AppState>) -> impl IntoResponse {
+    Json(ApiResponse::success(state.summary_text_builder.locale_settings().await))
+}
+
+/// PUT /api/summary/locale
+async fn update_locale_settings(
+    State(state): State<AppState>,
+    Json(settings): Json<SummaryLocaleSettings>,
+) -> impl IntoResponse {
+    match state.summary_text_builder.set_locale_settings(&settings).await {
+        Ok(()) => Json(ApiResponse::success(settings)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}